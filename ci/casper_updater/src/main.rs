@@ -232,6 +232,9 @@ fn get_args() -> Args {
 }
 
 fn main() {
+    let types_derive = Package::cargo("types_derive", &*regex_data::types_derive::DEPENDENT_FILES);
+    types_derive.update();
+
     let types = Package::cargo("types", &*regex_data::types::DEPENDENT_FILES);
     types.update();
 
@@ -22,6 +22,34 @@ fn replacement_with_slash(updated_version: &str) -> String {
     format!(r#"$1/{}"#, updated_version)
 }
 
+pub mod types_derive {
+    use super::*;
+
+    pub static DEPENDENT_FILES: Lazy<Vec<DependentFile>> = Lazy::new(|| {
+        vec![
+            DependentFile::new(
+                "types/Cargo.toml",
+                Regex::new(r#"(?m)(^casper-types-derive = \{[^\}]*version = )"(?:[^"]+)"#)
+                    .unwrap(),
+                replacement,
+            ),
+            DependentFile::new(
+                "types_derive/Cargo.toml",
+                MANIFEST_VERSION_REGEX.clone(),
+                replacement,
+            ),
+            DependentFile::new(
+                "types_derive/src/lib.rs",
+                Regex::new(
+                    r#"(?m)(#!\[doc\(html_root_url = "https://docs.rs/casper-types-derive)/(?:[^"]+)"#,
+                )
+                .unwrap(),
+                replacement_with_slash,
+            ),
+        ]
+    });
+}
+
 pub mod types {
     use super::*;
 
@@ -37,6 +65,11 @@ pub mod types {
                 Regex::new(r#"(?m)(^casper-types = \{[^\}]*version = )"(?:[^"]+)"#).unwrap(),
                 replacement,
             ),
+            DependentFile::new(
+                "execution_engine_testing/cargo_casper/Cargo.toml",
+                Regex::new(r#"(?m)(^casper-types = \{[^\}]*version = )"(?:[^"]+)"#).unwrap(),
+                replacement,
+            ),
             DependentFile::new(
                 "execution_engine_testing/cargo_casper/src/common.rs",
                 Regex::new(r#"(?m)("casper-types",\s*)"(?:[^"]+)"#).unwrap(),
@@ -233,6 +266,12 @@ pub mod execution_engine_testing_test_support {
 
     pub static DEPENDENT_FILES: Lazy<Vec<DependentFile>> = Lazy::new(|| {
         vec![
+                DependentFile::new(
+                    "execution_engine_testing/cargo_casper/Cargo.toml",
+                    Regex::new(r#"(?m)(^casper-engine-test-support = \{[^\}]*version = )"(?:[^"]+)"#)
+                        .unwrap(),
+                    replacement,
+                ),
                 DependentFile::new(
                     "execution_engine_testing/cargo_casper/src/tests_package.rs",
                     Regex::new(r#"(?m)("casper-engine-test-support",\s*)"(?:[^"]+)"#).unwrap(),
@@ -8,14 +8,17 @@ use std::{
 
 use casper_execution_engine::core::engine_state::ExecutableDeployItem;
 use casper_node::{
-    rpcs::{account::PutDeploy, chain::GetBlockResult, info::GetDeploy, RpcWithParams},
+    rpcs::{
+        account::PutDeploy, chain::GetBlockResult, info::GetDeploy,
+        speculative_exec::EstimateGasResult, RpcWithParams,
+    },
     types::{Deploy, DeployHash, TimeDiff, Timestamp},
 };
-use casper_types::{ProtocolVersion, RuntimeArgs, SecretKey, URef, U512};
+use casper_types::{ExecutionResult, ProtocolVersion, RuntimeArgs, SecretKey, URef, U512};
 
 use crate::{
     error::{Error, Result},
-    rpc::{RpcClient, TransferTarget},
+    rpc::{RpcCall, RpcClient, TransferTarget},
 };
 
 /// The maximum permissible size in bytes of a Deploy when serialized via `ToBytes`.
@@ -24,6 +27,9 @@ use crate::{
 /// production chainspec.
 const MAX_SERIALIZED_SIZE: u32 = 1_024 * 1_024;
 
+/// The name of the 'amount' arg of the standard-payment system contract.
+const STANDARD_PAYMENT_ARG_AMOUNT: &str = "amount";
+
 /// SendDeploy allows sending a deploy to the node.
 pub(crate) struct SendDeploy;
 
@@ -182,6 +188,18 @@ pub(super) trait DeployExt {
         session: ExecutableDeployItem,
     ) -> Result<Deploy>;
 
+    /// Constructs a `Deploy` using the standard-payment system contract with a payment amount
+    /// estimated by the node.
+    ///
+    /// The session is first sent to the node's "estimate_gas" RPC endpoint via `rpc_call`, and the
+    /// suggested payment amount is then used as the 'amount' arg of the standard payment.
+    fn with_estimated_payment(
+        params: DeployParams,
+        session: ExecutableDeployItem,
+        safety_margin_percent: Option<u64>,
+        rpc_call: RpcCall,
+    ) -> Result<Deploy>;
+
     /// Constructs a transfer `Deploy`.
     fn new_transfer(
         amount: U512,
@@ -238,6 +256,40 @@ impl DeployExt for Deploy {
         Ok(deploy)
     }
 
+    fn with_estimated_payment(
+        params: DeployParams,
+        session: ExecutableDeployItem,
+        safety_margin_percent: Option<u64>,
+        rpc_call: RpcCall,
+    ) -> Result<Deploy> {
+        // The payment amount of the deploy sent for estimation is irrelevant, as the node replaces
+        // it while searching for the lowest sufficient amount.
+        let estimation_deploy = Deploy::new(
+            params.timestamp,
+            params.ttl,
+            params.gas_price,
+            params.dependencies.clone(),
+            params.chain_name.clone(),
+            standard_payment(U512::zero())?,
+            session.clone(),
+            &params.secret_key,
+        );
+        estimation_deploy.is_valid_size(MAX_SERIALIZED_SIZE)?;
+
+        let response = rpc_call.estimate_gas(estimation_deploy, "", safety_margin_percent)?;
+        let value = response
+            .get_result()
+            .ok_or_else(|| Error::InvalidRpcResponse(response.clone()))?;
+        let estimate_gas_result: EstimateGasResult = serde_json::from_value(value.to_owned())?;
+        if let ExecutionResult::Failure { error_message, .. } = estimate_gas_result.execution_result
+        {
+            return Err(Error::FailedToEstimatePayment(error_message));
+        }
+
+        let payment = standard_payment(estimate_gas_result.payment_amount)?;
+        Deploy::with_payment_and_session(params, payment, session)
+    }
+
     fn new_transfer(
         amount: U512,
         source_purse: Option<URef>,
@@ -306,6 +358,16 @@ impl DeployExt for Deploy {
     }
 }
 
+/// Returns the payment code for the standard-payment system contract paying `amount`.
+pub(crate) fn standard_payment(amount: U512) -> Result<ExecutableDeployItem> {
+    let mut payment_args = RuntimeArgs::new();
+    payment_args.insert(STANDARD_PAYMENT_ARG_AMOUNT, amount)?;
+    Ok(ExecutableDeployItem::ModuleBytes {
+        module_bytes: vec![].into(),
+        args: payment_args,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::convert::TryInto;
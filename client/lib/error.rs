@@ -6,7 +6,8 @@ use thiserror::Error;
 
 use casper_node::{crypto::Error as CryptoError, types::ExcessiveSizeDeployError};
 use casper_types::{
-    bytesrepr::Error as ToBytesError, CLValueError, UIntParseError, URefFromStrError,
+    bytesrepr::Error as ToBytesError, CLValueError, ContractSchemaError, UIntParseError,
+    URefFromStrError,
 };
 
 use crate::validation::ValidateResponseError;
@@ -123,6 +124,14 @@ pub enum Error {
     #[error("Invalid response: {0}")]
     InvalidResponse(#[from] ValidateResponseError),
 
+    /// The deploy failed to execute while estimating its payment amount.
+    #[error("Failed to estimate payment, deploy execution failed: {0}")]
+    FailedToEstimatePayment(String),
+
+    /// Failed to encode the args of a contract call using the contract's schema.
+    #[error("Invalid contract call args: {0}")]
+    InvalidContractArgs(ContractSchemaError),
+
     /// Failed to create a DictionaryIdentifier
     #[error("Failed to parse the dictionary identifier")]
     FailedToParseDictionaryIdentifier,
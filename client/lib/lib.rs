@@ -26,10 +26,14 @@ use std::{convert::TryInto, fs, io::Cursor};
 
 use jsonrpc_lite::JsonRpc;
 use serde::Serialize;
+use serde_json::{Map, Value};
 
 use casper_execution_engine::core::engine_state::ExecutableDeployItem;
-use casper_node::{rpcs::state::DictionaryIdentifier, types::Deploy};
-use casper_types::{Key, UIntParseError, U512};
+use casper_node::{
+    rpcs::state::{DictionaryIdentifier, GetContractSchemaResult},
+    types::Deploy,
+};
+use casper_types::{ContractSchema, Key, UIntParseError, U512};
 
 pub use cl_type::help;
 pub use deploy::ListDeploysResult;
@@ -73,6 +77,173 @@ pub fn put_deploy(
     RpcCall::new(maybe_rpc_id, node_address, verbosity_level).put_deploy(deploy)
 }
 
+/// Creates a `Deploy` paying via the standard-payment system contract with an amount estimated by
+/// the node, and sends it to the network for execution.
+///
+/// The payment amount is the one suggested by the node's "estimate_gas" RPC endpoint, so two
+/// JSON-RPC requests are sent to the node.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbosity_level` is `1`, the JSON-RPC requests will be printed to `stdout` with long
+///   string fields (e.g. hex-formatted raw Wasm bytes) shortened to a string indicating the char
+///   count of the field.  When `verbosity_level` is greater than `1`, the requests will be printed
+///   to `stdout` with no abbreviation of long fields.  When `verbosity_level` is `0`, the requests
+///   will not be printed to `stdout`.
+/// * `deploy_params` contains deploy-related options for this `Deploy`. See
+///   [`DeployStrParams`](struct.DeployStrParams.html) for more details.
+/// * `session_params` contains session-related options for this `Deploy`. See
+///   [`SessionStrParams`](struct.SessionStrParams.html) for more details.
+/// * `safety_margin_percent` is the percentage added to the estimated gas when deriving the payment
+///   amount. If empty, the node's default margin is used.
+pub fn put_deploy_with_estimated_payment(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbosity_level: u64,
+    deploy_params: DeployStrParams<'_>,
+    session_params: SessionStrParams<'_>,
+    safety_margin_percent: &str,
+) -> Result<JsonRpc> {
+    let safety_margin_percent = parsing::safety_margin_percent(safety_margin_percent)?;
+    let deploy = Deploy::with_estimated_payment(
+        deploy_params.try_into()?,
+        session_params.try_into()?,
+        safety_margin_percent,
+        RpcCall::new(maybe_rpc_id, node_address, verbosity_level),
+    )?;
+    RpcCall::new(maybe_rpc_id, node_address, verbosity_level).put_deploy(deploy)
+}
+
+/// Estimates the gas used by executing a `Deploy` and suggests a payment amount for the
+/// standard-payment system contract.
+///
+/// The `Deploy` is executed by the node on top of the global state of the given block without
+/// being committed. Its payment is always the standard payment, with the amount chosen by the node.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbosity_level` is `1`, the JSON-RPC request will be printed to `stdout` with long
+///   string fields (e.g. hex-formatted raw Wasm bytes) shortened to a string indicating the char
+///   count of the field.  When `verbosity_level` is greater than `1`, the request will be printed
+///   to `stdout` with no abbreviation of long fields.  When `verbosity_level` is `0`, the request
+///   will not be printed to `stdout`.
+/// * `maybe_block_id` must be a hex-encoded, 32-byte hash digest or a `u64` representing the
+///   `Block` height or empty. If empty, the latest `Block` will be used.
+/// * `deploy_params` contains deploy-related options for this `Deploy`. See
+///   [`DeployStrParams`](struct.DeployStrParams.html) for more details.
+/// * `session_params` contains session-related options for this `Deploy`. See
+///   [`SessionStrParams`](struct.SessionStrParams.html) for more details.
+/// * `safety_margin_percent` is the percentage added to the estimated gas when deriving the
+///   suggested payment amount. If empty, the node's default margin is used.
+pub fn estimate_gas(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbosity_level: u64,
+    maybe_block_id: &str,
+    deploy_params: DeployStrParams<'_>,
+    session_params: SessionStrParams<'_>,
+    safety_margin_percent: &str,
+) -> Result<JsonRpc> {
+    let safety_margin_percent = parsing::safety_margin_percent(safety_margin_percent)?;
+    let deploy = Deploy::with_payment_and_session(
+        deploy_params.try_into()?,
+        deploy::standard_payment(U512::zero())?,
+        session_params.try_into()?,
+    )?;
+    RpcCall::new(maybe_rpc_id, node_address, verbosity_level).estimate_gas(
+        deploy,
+        maybe_block_id,
+        safety_margin_percent,
+    )
+}
+
+/// Creates a `Deploy` calling an entry point of a stored contract, and sends it to the network for
+/// execution.
+///
+/// The args of the call are given as plain JSON and encoded using the types declared by the
+/// contract's schema.  Unless read from a file, the schema is retrieved from the node via its
+/// "state_get_contract_schema" RPC endpoint, so two JSON-RPC requests are sent to the node.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbosity_level` is `1`, the JSON-RPC requests will be printed to `stdout` with long
+///   string fields (e.g. hex-formatted raw Wasm bytes) shortened to a string indicating the char
+///   count of the field.  When `verbosity_level` is greater than `1`, the requests will be printed
+///   to `stdout` with no abbreviation of long fields.  When `verbosity_level` is `0`, the requests
+///   will not be printed to `stdout`.
+/// * `deploy_params` contains deploy-related options for this `Deploy`. See
+///   [`DeployStrParams`](struct.DeployStrParams.html) for more details.
+/// * `payment_params` contains payment-related options for this `Deploy`. See
+///   [`PaymentStrParams`](struct.PaymentStrParams.html) for more details.
+/// * `contract_hash` must be a hex-encoded, 32-byte hash, optionally prefixed with `hash-`.
+/// * `entry_point` is the name of the entry point to call.
+/// * `json_args` is a JSON object mapping the name of each arg to the JSON representation of its
+///   value, e.g. `{"amount": "100", "memo": null}`.  If empty, no args are passed.
+/// * `maybe_schema_path` is the path to a file containing the contract's schema as output by `cargo
+///   casper --schema`.  If empty, the schema is retrieved from the node.
+/// * `maybe_state_root_hash` must be a hex-encoded, 32-byte hash digest identifying the global
+///   state from which the schema is retrieved.  It is ignored if `maybe_schema_path` is provided.
+#[allow(clippy::too_many_arguments)]
+pub fn call_contract(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbosity_level: u64,
+    deploy_params: DeployStrParams<'_>,
+    payment_params: PaymentStrParams<'_>,
+    contract_hash: &str,
+    entry_point: &str,
+    json_args: &str,
+    maybe_schema_path: &str,
+    maybe_state_root_hash: &str,
+) -> Result<JsonRpc> {
+    let contract_hash = parsing::contract_hash(contract_hash)?;
+
+    let schema: ContractSchema = if maybe_schema_path.is_empty() {
+        let response = RpcCall::new(maybe_rpc_id, node_address, verbosity_level)
+            .get_contract_schema(maybe_state_root_hash, contract_hash)?;
+        let value = response
+            .get_result()
+            .ok_or_else(|| Error::InvalidRpcResponse(response.clone()))?;
+        serde_json::from_value::<GetContractSchemaResult>(value.to_owned())?.schema
+    } else {
+        let schema = fs::read_to_string(maybe_schema_path).map_err(|error| Error::IoError {
+            context: format!("unable to read schema file '{}'", maybe_schema_path),
+            error,
+        })?;
+        serde_json::from_str(&schema)?
+    };
+
+    let json_args: Map<String, Value> = if json_args.is_empty() {
+        Map::new()
+    } else {
+        serde_json::from_str(json_args)?
+    };
+    let args = schema
+        .encode_args(entry_point, &json_args)
+        .map_err(Error::InvalidContractArgs)?;
+
+    let session = ExecutableDeployItem::StoredContractByHash {
+        hash: contract_hash,
+        entry_point: entry_point.to_string(),
+        args,
+    };
+    let deploy = Deploy::with_payment_and_session(
+        deploy_params.try_into()?,
+        payment_params.try_into()?,
+        session,
+    )?;
+    RpcCall::new(maybe_rpc_id, node_address, verbosity_level).put_deploy(deploy)
+}
+
 /// Creates a `Deploy` and outputs it to a file or stdout.
 ///
 /// As a file, the `Deploy` can subsequently be signed by other parties using
@@ -500,6 +671,66 @@ pub fn get_account_info(
         .get_account_info(public_key, maybe_block_id)
 }
 
+/// Retrieves the pending unbonding purses of a validator or delegator.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbosity_level` is `1`, the JSON-RPC request will be printed to `stdout` with long
+///   string fields (e.g. hex-formatted raw Wasm bytes) shortened to a string indicating the char
+///   count of the field.  When `verbosity_level` is greater than `1`, the request will be printed
+///   to `stdout` with no abbreviation of long fields.  When `verbosity_level` is `0`, the request
+///   will not be printed to `stdout`.
+/// * `public_key` the public key of the unbonding validator or delegator.
+/// * `maybe_block_id` must be a hex-encoded, 32-byte hash digest or a `u64` representing the
+///   `Block` height or empty. If empty, the latest `Block` will be used.
+pub fn get_unbonding_purses(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbosity_level: u64,
+    public_key: &str,
+    maybe_block_id: &str,
+) -> Result<JsonRpc> {
+    RpcCall::new(maybe_rpc_id, node_address, verbosity_level)
+        .get_unbonding_purses(public_key, maybe_block_id)
+}
+
+/// Retrieves the rewards paid out to a validator or delegator in each of a range of eras.
+///
+/// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
+///   response. If it can be parsed as an `i64` it will be used as a JSON integer. If empty, a
+///   random `i64` will be assigned. Otherwise the provided string will be used verbatim.
+/// * `node_address` is the hostname or IP and port of the node on which the HTTP service is
+///   running, e.g. `"http://127.0.0.1:7777"`.
+/// * When `verbosity_level` is `1`, the JSON-RPC request will be printed to `stdout` with long
+///   string fields (e.g. hex-formatted raw Wasm bytes) shortened to a string indicating the char
+///   count of the field.  When `verbosity_level` is greater than `1`, the request will be printed
+///   to `stdout` with no abbreviation of long fields.  When `verbosity_level` is `0`, the request
+///   will not be printed to `stdout`.
+/// * `public_key` the public key of the validator or delegator.
+/// * `start_era_id` and `end_era_id` must each be a `u64` identifying the first and last era of the
+///   range, inclusive.
+/// * `maybe_block_id` must be a hex-encoded, 32-byte hash digest or a `u64` representing the
+///   `Block` height or empty. If empty, the latest `Block` will be used.
+pub fn get_rewards(
+    maybe_rpc_id: &str,
+    node_address: &str,
+    verbosity_level: u64,
+    public_key: &str,
+    start_era_id: &str,
+    end_era_id: &str,
+    maybe_block_id: &str,
+) -> Result<JsonRpc> {
+    RpcCall::new(maybe_rpc_id, node_address, verbosity_level).get_rewards(
+        public_key,
+        start_era_id,
+        end_era_id,
+        maybe_block_id,
+    )
+}
+
 /// Retrieves information and examples for all currently supported RPCs.
 ///
 /// * `maybe_rpc_id` is the JSON-RPC identifier, applied to the request and returned in the
@@ -4,6 +4,7 @@
 use std::{convert::TryInto, fs, io, path::PathBuf, str::FromStr};
 
 use serde::{self, Deserialize};
+use serde_json::Value;
 
 use casper_execution_engine::core::engine_state::executable_deploy_item::ExecutableDeployItem;
 use casper_node::{
@@ -11,8 +12,8 @@ use casper_node::{
     types::{DeployHash, TimeDiff, Timestamp},
 };
 use casper_types::{
-    bytesrepr, AsymmetricType, CLType, CLValue, HashAddr, Key, NamedArg, PublicKey, RuntimeArgs,
-    SecretKey, UIntParseError, U512,
+    bytesrepr, AsymmetricType, CLType, CLValue, ContractHash, HashAddr, Key, NamedArg, PublicKey,
+    RuntimeArgs, SecretKey, UIntParseError, U512,
 };
 
 use crate::{
@@ -139,6 +140,9 @@ mod args_complex {
         /// Contains `CLValue` serialized into bytes in base16 form.
         #[serde(deserialize_with = "hex::deserialize")]
         RawBytes(Vec<u8>),
+        /// Contains the `CLType` of a `CLValue` and its JSON representation, e.g. an object of
+        /// field names to values for a `CLType::Struct`.
+        Json { cl_type: CLType, value: Value },
     }
 
     #[derive(Debug, Deserialize)]
@@ -154,6 +158,10 @@ mod args_complex {
             match value {
                 DeployArgValue::RawBytes(bytes) => bytesrepr::deserialize(bytes)
                     .unwrap_or_else(|error| panic!("should deserialize deploy arg: {}", error)),
+                DeployArgValue::Json { cl_type, value } => CLValue::from_json(&cl_type, &value)
+                    .unwrap_or_else(|| {
+                        panic!("should convert deploy arg {} to {:?}", value, cl_type)
+                    }),
             }
         }
     }
@@ -573,6 +581,12 @@ fn parse_contract_hash(value: &str) -> Result<Option<HashAddr>> {
     Err(Error::FailedToParseKey)
 }
 
+pub(crate) fn contract_hash(value: &str) -> Result<ContractHash> {
+    parse_contract_hash(value)?
+        .map(ContractHash::new)
+        .ok_or(Error::FailedToParseKey)
+}
+
 fn name(value: &str) -> Option<String> {
     none_if_empty(value).map(str::to_string)
 }
@@ -593,6 +607,16 @@ pub(crate) fn transfer_id(value: &str) -> Result<u64> {
         .map_err(|error| Error::FailedToParseInt("transfer-id", error))
 }
 
+pub(crate) fn safety_margin_percent(value: &str) -> Result<Option<u64>> {
+    none_if_empty(value)
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|error| Error::FailedToParseInt("safety_margin_percent", error))
+        })
+        .transpose()
+}
+
 #[cfg(test)]
 mod tests {
     use std::{convert::TryFrom, result::Result as StdResult};
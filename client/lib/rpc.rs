@@ -14,19 +14,23 @@ use casper_node::{
         account::{PutDeploy, PutDeployParams},
         chain::{
             BlockIdentifier, GetBlock, GetBlockParams, GetBlockTransfers, GetBlockTransfersParams,
-            GetEraInfoBySwitchBlock, GetEraInfoParams, GetStateRootHash, GetStateRootHashParams,
+            GetEraInfoBySwitchBlock, GetEraInfoParams, GetRewards, GetRewardsParams,
+            GetStateRootHash, GetStateRootHashParams,
         },
         docs::ListRpcs,
         info::{GetDeploy, GetDeployParams},
+        speculative_exec::{EstimateGas, EstimateGasParams},
         state::{
             GetAccountInfo, GetAccountInfoParams, GetAuctionInfo, GetAuctionInfoParams, GetBalance,
-            GetBalanceParams, GetDictionaryItem, GetDictionaryItemParams, GetItem, GetItemParams,
+            GetBalanceParams, GetContractSchema, GetContractSchemaParams, GetDictionaryItem,
+            GetDictionaryItemParams, GetItem, GetItemParams, GetUnbondingPurses,
+            GetUnbondingPursesParams,
         },
         RpcWithOptionalParams, RpcWithParams, RpcWithoutParams, RPC_API_PATH,
     },
     types::{BlockHash, Deploy, DeployHash},
 };
-use casper_types::{AsymmetricType, Key, PublicKey, URef, U512};
+use casper_types::{AsymmetricType, ContractHash, EraId, Key, PublicKey, URef, U512};
 
 use crate::{
     deploy::{DeployExt, DeployParams, SendDeploy, Transfer},
@@ -123,6 +127,23 @@ impl RpcCall {
         Ok(response)
     }
 
+    pub(crate) fn get_contract_schema(
+        self,
+        state_root_hash: &str,
+        contract_hash: ContractHash,
+    ) -> Result<JsonRpc> {
+        let state_root_hash =
+            Digest::from_hex(state_root_hash).map_err(|error| Error::CryptoError {
+                context: "state_root_hash",
+                error,
+            })?;
+        let params = GetContractSchemaParams {
+            state_root_hash,
+            contract_hash,
+        };
+        GetContractSchema::request_with_map_params(self, params)
+    }
+
     pub(crate) fn get_dictionary_item(
         self,
         state_root_hash: &str,
@@ -240,6 +261,20 @@ impl RpcCall {
         PutDeploy::request_with_map_params(self, params)
     }
 
+    pub(crate) fn estimate_gas(
+        self,
+        deploy: Deploy,
+        maybe_block_identifier: &str,
+        safety_margin_percent: Option<u64>,
+    ) -> Result<JsonRpc> {
+        let params = EstimateGasParams {
+            block_identifier: Self::block_identifier(maybe_block_identifier)?,
+            deploy,
+            safety_margin_percent,
+        };
+        EstimateGas::request_with_map_params(self, params)
+    }
+
     pub(crate) fn get_block(self, maybe_block_identifier: &str) -> Result<JsonRpc> {
         let maybe_block_identifier = Self::block_identifier(maybe_block_identifier)?;
         let response = match maybe_block_identifier {
@@ -283,6 +318,44 @@ impl RpcCall {
         GetAccountInfo::request_with_map_params(self, params)
     }
 
+    pub(crate) fn get_unbonding_purses(
+        self,
+        public_key: &str,
+        maybe_block_identifier: &str,
+    ) -> Result<JsonRpc> {
+        let public_key = PublicKey::from_hex(public_key).map_err(|_| Error::FailedToParseKey)?;
+        let block_identifier = Self::block_identifier(maybe_block_identifier)?;
+        let params = GetUnbondingPursesParams {
+            public_key,
+            block_identifier,
+        };
+        GetUnbondingPurses::request_with_map_params(self, params)
+    }
+
+    pub(crate) fn get_rewards(
+        self,
+        public_key: &str,
+        start_era_id: &str,
+        end_era_id: &str,
+        maybe_block_identifier: &str,
+    ) -> Result<JsonRpc> {
+        let public_key = PublicKey::from_hex(public_key).map_err(|_| Error::FailedToParseKey)?;
+        let start_era_id = start_era_id
+            .parse::<u64>()
+            .map_err(|error| Error::FailedToParseInt("start_era_id", error))?;
+        let end_era_id = end_era_id
+            .parse::<u64>()
+            .map_err(|error| Error::FailedToParseInt("end_era_id", error))?;
+        let block_identifier = Self::block_identifier(maybe_block_identifier)?;
+        let params = GetRewardsParams {
+            public_key,
+            start_era_id: EraId::from(start_era_id),
+            end_era_id: EraId::from(end_era_id),
+            block_identifier,
+        };
+        GetRewards::request_with_map_params(self, params)
+    }
+
     fn block_identifier(maybe_block_identifier: &str) -> Result<Option<BlockIdentifier>> {
         if maybe_block_identifier.is_empty() {
             return Ok(None);
@@ -415,6 +488,22 @@ impl RpcClient for GetDictionaryItem {
     const RPC_METHOD: &'static str = Self::METHOD;
 }
 
+impl RpcClient for GetContractSchema {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
+impl RpcClient for GetUnbondingPurses {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
+impl RpcClient for GetRewards {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
+impl RpcClient for EstimateGas {
+    const RPC_METHOD: &'static str = Self::METHOD;
+}
+
 pub(crate) trait IntoJsonMap: Serialize {
     fn into_json_map(self) -> Map<String, Value>
     where
@@ -439,3 +528,7 @@ impl IntoJsonMap for ListRpcs {}
 impl IntoJsonMap for GetAuctionInfoParams {}
 impl IntoJsonMap for GetAccountInfoParams {}
 impl IntoJsonMap for GetDictionaryItemParams {}
+impl IntoJsonMap for GetContractSchemaParams {}
+impl IntoJsonMap for GetUnbondingPursesParams {}
+impl IntoJsonMap for GetRewardsParams {}
+impl IntoJsonMap for EstimateGasParams {}
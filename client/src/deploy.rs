@@ -1,4 +1,6 @@
+mod call_contract;
 mod creation_common;
+mod estimate_gas;
 mod get;
 mod list;
 mod make;
@@ -8,6 +10,7 @@ mod send;
 mod sign;
 mod transfer;
 
+pub use call_contract::CallContract;
 pub use list::ListDeploys;
 pub use make::MakeDeploy;
 pub use make_transfer::MakeTransfer;
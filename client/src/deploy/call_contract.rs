@@ -0,0 +1,184 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_client::{DeployStrParams, Error};
+
+use super::creation_common::{self, DisplayOrder};
+use crate::{command::ClientCommand, common, Success};
+
+/// Handles providing the arg for and retrieval of the contract hash.
+mod contract_hash {
+    use super::*;
+
+    const ARG_NAME: &str = "contract-hash";
+    const ARG_VALUE_NAME: &str = common::ARG_HEX_STRING;
+    const ARG_HELP: &str =
+        "Hex-encoded hash of the stored contract to call, optionally prefixed with 'hash-'";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required_unless(creation_common::show_arg_examples::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::ContractHash as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+/// Handles providing the arg for and retrieval of the entry point.
+mod entry_point {
+    use super::*;
+
+    const ARG_NAME: &str = "entry-point";
+    const ARG_VALUE_NAME: &str = "NAME";
+    const ARG_HELP: &str = "Name of the entry point of the contract to call";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required_unless(creation_common::show_arg_examples::ARG_NAME)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::ContractEntryPoint as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+/// Handles providing the arg for and retrieval of the JSON-encoded args of the call.
+mod args {
+    use super::*;
+
+    const ARG_NAME: &str = "args";
+    const ARG_VALUE_NAME: &str = "JSON";
+    const ARG_HELP: &str =
+        "JSON object mapping the name of each arg of the entry point to its value, e.g. \
+        '{\"amount\": \"100\", \"memo\": null}'. Each value is encoded using the type declared by \
+        the contract's schema. Args of an Option type may be omitted. If not given, no args are \
+        passed";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::ContractArgs as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+/// Handles providing the arg for and retrieval of the path to the contract's schema.
+mod schema {
+    use super::*;
+
+    pub(super) const ARG_NAME: &str = "schema";
+    const ARG_VALUE_NAME: &str = common::ARG_PATH;
+    const ARG_HELP: &str =
+        "Path to the contract's schema as output by 'cargo casper --schema'. If not given, the \
+        schema is retrieved from the node using --state-root-hash";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::ContractSchema as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
+pub struct CallContract;
+
+impl<'a, 'b> ClientCommand<'a, 'b> for CallContract {
+    const NAME: &'static str = "call-contract";
+    const ABOUT: &'static str =
+        "Creates a deploy calling an entry point of a stored contract, with args encoded using the \
+        contract's schema, and sends it to the network for execution";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        let subcommand = SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .arg(contract_hash::arg())
+            .arg(entry_point::arg())
+            .arg(args::arg())
+            .arg(schema::arg())
+            .arg(
+                common::state_root_hash::arg(DisplayOrder::StateRootHash as usize)
+                    .required_unless_one(&[
+                        schema::ARG_NAME,
+                        creation_common::show_arg_examples::ARG_NAME,
+                    ]),
+            );
+        let subcommand = creation_common::apply_common_payment_options(subcommand);
+        creation_common::apply_common_creation_options(subcommand, true)
+    }
+
+    fn run(matches: &ArgMatches<'_>) -> Result<Success, Error> {
+        creation_common::show_arg_examples_and_exit_if_required(matches);
+
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbosity_level = common::verbose::get(matches);
+
+        let secret_key = common::secret_key::get(matches);
+        let timestamp = creation_common::timestamp::get(matches);
+        let ttl = creation_common::ttl::get(matches);
+        let gas_price = creation_common::gas_price::get(matches);
+        let dependencies = creation_common::dependencies::get(matches);
+        let chain_name = creation_common::chain_name::get(matches);
+
+        let deploy_str_params = DeployStrParams {
+            secret_key,
+            timestamp,
+            ttl,
+            dependencies,
+            gas_price,
+            chain_name,
+        };
+        let payment_str_params = creation_common::payment_str_params(matches);
+
+        let contract_hash = contract_hash::get(matches);
+        let entry_point = entry_point::get(matches);
+        let args = args::get(matches);
+        let maybe_schema_path = schema::get(matches);
+        let maybe_state_root_hash = if maybe_schema_path.is_empty() {
+            common::state_root_hash::get(matches)
+        } else {
+            ""
+        };
+
+        casper_client::call_contract(
+            maybe_rpc_id,
+            node_address,
+            verbosity_level,
+            deploy_str_params,
+            payment_str_params,
+            contract_hash,
+            entry_point,
+            args,
+            maybe_schema_path,
+            maybe_state_root_hash,
+        )
+        .map(Success::from)
+    }
+}
@@ -15,6 +15,7 @@ pub(super) enum DisplayOrder {
     Verbose,
     NodeAddress,
     RpcId,
+    BlockIdentifier,
     SecretKey,
     Input,
     Output,
@@ -26,6 +27,11 @@ pub(super) enum DisplayOrder {
     GasPrice,
     Dependencies,
     ChainName,
+    ContractHash,
+    ContractEntryPoint,
+    ContractArgs,
+    ContractSchema,
+    StateRootHash,
     SessionCode,
     SessionArgSimple,
     SessionArgsComplex,
@@ -37,6 +43,8 @@ pub(super) enum DisplayOrder {
     SessionVersion,
     SessionTransfer,
     StandardPayment,
+    EstimatedPayment,
+    SafetyMargin,
     PaymentCode,
     PaymentArgSimple,
     PaymentArgsComplex,
@@ -420,8 +428,8 @@ pub(super) mod args_complex {
 
     const ARG_VALUE_NAME: &str = common::ARG_PATH;
     const ARG_HELP: &str =
-        "Path to file containing 'ToBytes'-encoded named and typed args for passing to the Wasm \
-        code";
+        "Path to file containing a JSON list of named args for passing to the Wasm code, each \
+        given either as 'ToBytes'-encoded raw bytes or as a CLType and a JSON value";
 
     pub(in crate::deploy) mod session {
         use super::*;
@@ -512,6 +520,54 @@ pub(super) mod standard_payment_amount {
     }
 }
 
+/// Handles providing the arg for and retrieval of the estimated payment flag.
+pub(super) mod estimated_payment {
+    use super::*;
+
+    pub(in crate::deploy) const ARG_NAME: &str = "estimate-payment";
+    const ARG_HELP: &str =
+        "If passed, uses the standard-payment system contract with an amount estimated by the node \
+        via the estimate_gas RPC rather than custom payment Wasm. This arg is incompatible with all \
+        other --payment-xxx args";
+
+    pub(in crate::deploy) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .help(ARG_HELP)
+            .group("payment")
+            .display_order(DisplayOrder::EstimatedPayment as usize)
+    }
+
+    pub(in crate::deploy) fn get(matches: &ArgMatches) -> bool {
+        matches.is_present(ARG_NAME)
+    }
+}
+
+/// Handles providing the arg for and retrieval of the gas estimation safety margin.
+pub(super) mod safety_margin {
+    use super::*;
+
+    const ARG_NAME: &str = "safety-margin";
+    const ARG_VALUE_NAME: &str = common::ARG_INTEGER;
+    const ARG_HELP: &str =
+        "The percentage added to the estimated gas when deriving the payment amount. If not given, \
+        the node's default of 10 percent is used";
+
+    pub(in crate::deploy) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(false)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::SafetyMargin as usize)
+    }
+
+    pub(in crate::deploy) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches.value_of(ARG_NAME).unwrap_or_default()
+    }
+}
+
 pub(super) fn apply_common_creation_options<'a, 'b>(
     subcommand: App<'a, 'b>,
     include_node_address: bool,
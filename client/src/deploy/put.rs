@@ -17,7 +17,12 @@ impl<'a, 'b> ClientCommand<'a, 'b> for PutDeploy {
             .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
             .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize));
         let subcommand = creation_common::apply_common_session_options(subcommand);
-        let subcommand = creation_common::apply_common_payment_options(subcommand);
+        let subcommand = creation_common::apply_common_payment_options(subcommand)
+            .arg(creation_common::estimated_payment::arg())
+            .arg(
+                creation_common::safety_margin::arg()
+                    .requires(creation_common::estimated_payment::ARG_NAME),
+            );
         creation_common::apply_common_creation_options(subcommand, true)
     }
 
@@ -35,21 +40,35 @@ impl<'a, 'b> ClientCommand<'a, 'b> for PutDeploy {
         let dependencies = creation_common::dependencies::get(matches);
         let chain_name = creation_common::chain_name::get(matches);
 
+        let deploy_str_params = DeployStrParams {
+            secret_key,
+            timestamp,
+            ttl,
+            dependencies,
+            gas_price,
+            chain_name,
+        };
         let session_str_params = creation_common::session_str_params(matches);
-        let payment_str_params = creation_common::payment_str_params(matches);
 
+        if creation_common::estimated_payment::get(matches) {
+            let safety_margin = creation_common::safety_margin::get(matches);
+            return casper_client::put_deploy_with_estimated_payment(
+                maybe_rpc_id,
+                node_address,
+                verbosity_level,
+                deploy_str_params,
+                session_str_params,
+                safety_margin,
+            )
+            .map(Success::from);
+        }
+
+        let payment_str_params = creation_common::payment_str_params(matches);
         casper_client::put_deploy(
             maybe_rpc_id,
             node_address,
             verbosity_level,
-            DeployStrParams {
-                secret_key,
-                timestamp,
-                ttl,
-                dependencies,
-                gas_price,
-                chain_name,
-            },
+            deploy_str_params,
             session_str_params,
             payment_str_params,
         )
@@ -0,0 +1,111 @@
+use std::str;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use casper_client::Error;
+use casper_node::rpcs::chain::GetRewards;
+
+use crate::{command::ClientCommand, common, Success};
+
+/// This struct defines the order in which the args are shown for this subcommand's help message.
+enum DisplayOrder {
+    Verbose,
+    NodeAddress,
+    RpcId,
+    PublicKey,
+    StartEra,
+    EndEra,
+    BlockIdentifier,
+}
+
+/// Handles providing the arg for and retrieval of the first era of the range.
+mod start_era {
+    use super::*;
+
+    const ARG_NAME: &str = "start-era";
+    const ARG_VALUE_NAME: &str = "INTEGER";
+    const ARG_HELP: &str = "The ID of the first era of the range to query, inclusive";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(true)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::StartEra as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+/// Handles providing the arg for and retrieval of the last era of the range.
+mod end_era {
+    use super::*;
+
+    const ARG_NAME: &str = "end-era";
+    const ARG_VALUE_NAME: &str = "INTEGER";
+    const ARG_HELP: &str = "The ID of the last era of the range to query, inclusive";
+
+    pub(super) fn arg() -> Arg<'static, 'static> {
+        Arg::with_name(ARG_NAME)
+            .long(ARG_NAME)
+            .required(true)
+            .value_name(ARG_VALUE_NAME)
+            .help(ARG_HELP)
+            .display_order(DisplayOrder::EndEra as usize)
+    }
+
+    pub(super) fn get<'a>(matches: &'a ArgMatches) -> &'a str {
+        matches
+            .value_of(ARG_NAME)
+            .unwrap_or_else(|| panic!("should have {} arg", ARG_NAME))
+    }
+}
+
+impl<'a, 'b> ClientCommand<'a, 'b> for GetRewards {
+    const NAME: &'static str = "get-rewards";
+    const ABOUT: &'static str =
+        "Retrieves the rewards paid to a validator or delegator over a range of eras";
+
+    fn build(display_order: usize) -> App<'a, 'b> {
+        SubCommand::with_name(Self::NAME)
+            .about(Self::ABOUT)
+            .display_order(display_order)
+            .arg(common::verbose::arg(DisplayOrder::Verbose as usize))
+            .arg(common::node_address::arg(
+                DisplayOrder::NodeAddress as usize,
+            ))
+            .arg(common::rpc_id::arg(DisplayOrder::RpcId as usize))
+            .arg(common::public_key::arg(DisplayOrder::PublicKey as usize))
+            .arg(start_era::arg())
+            .arg(end_era::arg())
+            .arg(common::block_identifier::arg(
+                DisplayOrder::BlockIdentifier as usize,
+            ))
+    }
+
+    fn run(matches: &ArgMatches<'_>) -> Result<Success, Error> {
+        let maybe_rpc_id = common::rpc_id::get(matches);
+        let node_address = common::node_address::get(matches);
+        let verbosity_level = common::verbose::get(matches);
+        let public_key = common::public_key::get(matches)?;
+        let start_era_id = start_era::get(matches);
+        let end_era_id = end_era::get(matches);
+        let block_identifier = common::block_identifier::get(matches);
+
+        casper_client::get_rewards(
+            maybe_rpc_id,
+            node_address,
+            verbosity_level,
+            &public_key,
+            start_era_id,
+            end_era_id,
+            block_identifier,
+        )
+        .map(Success::from)
+    }
+}
@@ -9,7 +9,9 @@ mod get_account_info;
 mod get_auction_info;
 mod get_balance;
 mod get_era_info_by_switch_block;
+mod get_rewards;
 mod get_state_hash;
+mod get_unbonding_purses;
 mod keygen;
 mod query_dictionary;
 mod query_state;
@@ -21,15 +23,21 @@ use clap::{crate_description, crate_version, App};
 use casper_client::Error;
 use casper_node::rpcs::{
     account::PutDeploy,
-    chain::{GetBlock, GetBlockTransfers, GetEraInfoBySwitchBlock, GetStateRootHash},
+    chain::{GetBlock, GetBlockTransfers, GetEraInfoBySwitchBlock, GetRewards, GetStateRootHash},
     docs::ListRpcs,
     info::GetDeploy,
-    state::{GetAccountInfo, GetAuctionInfo, GetBalance, GetDictionaryItem, GetItem as QueryState},
+    speculative_exec::EstimateGas,
+    state::{
+        GetAccountInfo, GetAuctionInfo, GetBalance, GetDictionaryItem, GetItem as QueryState,
+        GetUnbondingPurses,
+    },
 };
 
 use account_address::GenerateAccountHash as AccountAddress;
 use command::{ClientCommand, Success};
-use deploy::{ListDeploys, MakeDeploy, MakeTransfer, SendDeploy, SignDeploy, Transfer};
+use deploy::{
+    CallContract, ListDeploys, MakeDeploy, MakeTransfer, SendDeploy, SignDeploy, Transfer,
+};
 use generate_completion::GenerateCompletion;
 use keygen::Keygen;
 
@@ -38,6 +46,8 @@ const APP_NAME: &str = "Casper client";
 /// This struct defines the order in which the subcommands are shown in the app's help message.
 enum DisplayOrder {
     PutDeploy,
+    EstimateGas,
+    CallContract,
     MakeDeploy,
     SignDeploy,
     SendDeploy,
@@ -53,6 +63,8 @@ enum DisplayOrder {
     GetAccountInfo,
     GetEraInfo,
     GetAuctionInfo,
+    GetUnbondingPurses,
+    GetRewards,
     Keygen,
     GenerateCompletion,
     GetRpcs,
@@ -65,6 +77,8 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
         .version(crate_version!())
         .about(crate_description!())
         .subcommand(PutDeploy::build(DisplayOrder::PutDeploy as usize))
+        .subcommand(EstimateGas::build(DisplayOrder::EstimateGas as usize))
+        .subcommand(CallContract::build(DisplayOrder::CallContract as usize))
         .subcommand(MakeDeploy::build(DisplayOrder::MakeDeploy as usize))
         .subcommand(SignDeploy::build(DisplayOrder::SignDeploy as usize))
         .subcommand(SendDeploy::build(DisplayOrder::SendDeploy as usize))
@@ -86,6 +100,10 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
             DisplayOrder::GetEraInfo as usize,
         ))
         .subcommand(GetAuctionInfo::build(DisplayOrder::GetAuctionInfo as usize))
+        .subcommand(GetUnbondingPurses::build(
+            DisplayOrder::GetUnbondingPurses as usize,
+        ))
+        .subcommand(GetRewards::build(DisplayOrder::GetRewards as usize))
         .subcommand(Keygen::build(DisplayOrder::Keygen as usize))
         .subcommand(GenerateCompletion::build(
             DisplayOrder::GenerateCompletion as usize,
@@ -102,6 +120,8 @@ async fn main() {
     let arg_matches = cli().get_matches();
     let (result, matches) = match arg_matches.subcommand() {
         (PutDeploy::NAME, Some(matches)) => (PutDeploy::run(matches), matches),
+        (EstimateGas::NAME, Some(matches)) => (EstimateGas::run(matches), matches),
+        (CallContract::NAME, Some(matches)) => (CallContract::run(matches), matches),
         (MakeDeploy::NAME, Some(matches)) => (MakeDeploy::run(matches), matches),
         (SignDeploy::NAME, Some(matches)) => (SignDeploy::run(matches), matches),
         (SendDeploy::NAME, Some(matches)) => (SendDeploy::run(matches), matches),
@@ -119,6 +139,8 @@ async fn main() {
             (GetEraInfoBySwitchBlock::run(matches), matches)
         }
         (GetAuctionInfo::NAME, Some(matches)) => (GetAuctionInfo::run(matches), matches),
+        (GetUnbondingPurses::NAME, Some(matches)) => (GetUnbondingPurses::run(matches), matches),
+        (GetRewards::NAME, Some(matches)) => (GetRewards::run(matches), matches),
         (Keygen::NAME, Some(matches)) => (Keygen::run(matches), matches),
         (GenerateCompletion::NAME, Some(matches)) => (GenerateCompletion::run(matches), matches),
         (ListRpcs::NAME, Some(matches)) => (ListRpcs::run(matches), matches),
@@ -17,6 +17,7 @@ use casper_node::rpcs::{
     account::{PutDeploy, PutDeployParams},
     chain::{GetStateRootHash, GetStateRootHashParams},
     info::{GetDeploy, GetDeployParams},
+    speculative_exec::{EstimateGas, EstimateGasParams},
     state::{GetBalance, GetBalanceParams},
     RpcWithOptionalParams, RpcWithParams,
 };
@@ -188,6 +189,25 @@ impl MockServerHandle {
         .map_err(ErrWrapper)
     }
 
+    fn estimate_gas(
+        &self,
+        deploy_params: DeployStrParams,
+        session_params: SessionStrParams,
+        safety_margin_percent: &str,
+    ) -> Result<(), ErrWrapper> {
+        casper_client::estimate_gas(
+            "1",
+            &self.url(),
+            0,
+            "",
+            deploy_params,
+            session_params,
+            safety_margin_percent,
+        )
+        .map(|_| ())
+        .map_err(ErrWrapper)
+    }
+
     fn send_deploy_file(&self, input_path: &str) -> Result<(), ErrWrapper> {
         casper_client::send_deploy_file("1", &self.url(), 0, input_path)
             .map(|_| ())
@@ -1038,6 +1058,50 @@ mod put_deploy {
     }
 }
 
+mod estimate_gas {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_send_estimate_gas() {
+        let server_handle = MockServerHandle::spawn::<EstimateGasParams>(EstimateGas::METHOD);
+        assert_eq!(
+            server_handle.estimate_gas(
+                deploy_params::test_data_valid(),
+                session_params::test_data_with_package_hash(),
+                "20"
+            ),
+            Ok(())
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_send_estimate_gas_with_default_safety_margin() {
+        let server_handle = MockServerHandle::spawn::<EstimateGasParams>(EstimateGas::METHOD);
+        assert_eq!(
+            server_handle.estimate_gas(
+                deploy_params::test_data_valid(),
+                session_params::test_data_with_package_hash(),
+                ""
+            ),
+            Ok(())
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_fail_with_invalid_safety_margin() {
+        let server_handle = MockServerHandle::spawn::<EstimateGasParams>(EstimateGas::METHOD);
+        let parse_error = "-1".parse::<u64>().unwrap_err();
+        assert_eq!(
+            server_handle.estimate_gas(
+                deploy_params::test_data_valid(),
+                session_params::test_data_with_package_hash(),
+                "-1"
+            ),
+            Err(Error::FailedToParseInt("safety_margin_percent", parse_error).into())
+        );
+    }
+}
+
 mod rate_limit {
     use super::*;
     use casper_node::types::Timestamp;
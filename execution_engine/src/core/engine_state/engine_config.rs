@@ -1,22 +1,141 @@
-const DEFAULT_MAX_QUERY_DEPTH: u64 = 5;
+use num_rational::Ratio;
+
+use casper_types::system::{auction::SlashHandling, handle_payment::FeeHandling};
+
+/// The default maximum depth of a global state query.
+pub const DEFAULT_MAX_QUERY_DEPTH: u64 = 5;
+
+/// The default ratio of unspent payment which is refunded to the caller.
+pub const DEFAULT_REFUND_RATIO: Ratio<u64> = Ratio::new_raw(0, 1);
+
+/// The default fraction of a slashed validator's and its delegators' stakes which is slashed.
+pub const DEFAULT_SLASHING_RATE: Ratio<u64> = Ratio::new_raw(1, 1);
+
+/// The default maximum number of deserialized contract Wasm modules held in the Wasm cache.
+pub const DEFAULT_MAX_WASM_CACHE_SIZE: usize = 256;
+
+/// The default maximum number of entries recorded in the execution trace of a deploy.
+pub const DEFAULT_MAX_TRACE_ENTRIES: usize = 100_000;
 
 /// The runtime configuration of the execution engine
 #[derive(Debug, Copy, Clone)]
 pub struct EngineConfig {
     pub(crate) max_query_depth: u64,
+    pub(crate) refund_ratio: Ratio<u64>,
+    pub(crate) fee_handling: FeeHandling,
+    pub(crate) slashing_rate: Ratio<u64>,
+    pub(crate) slash_handling: SlashHandling,
+    pub(crate) trace_execution: bool,
+    pub(crate) max_trace_entries: usize,
+    pub(crate) max_wasm_cache_size: usize,
 }
 
 impl Default for EngineConfig {
     fn default() -> Self {
         EngineConfig {
             max_query_depth: DEFAULT_MAX_QUERY_DEPTH,
+            refund_ratio: DEFAULT_REFUND_RATIO,
+            fee_handling: FeeHandling::default(),
+            slashing_rate: DEFAULT_SLASHING_RATE,
+            slash_handling: SlashHandling::default(),
+            trace_execution: false,
+            max_trace_entries: DEFAULT_MAX_TRACE_ENTRIES,
+            max_wasm_cache_size: DEFAULT_MAX_WASM_CACHE_SIZE,
         }
     }
 }
 
 impl EngineConfig {
     /// Creates a new engine configuration with provided parameters.
-    pub fn new(max_query_depth: u64) -> EngineConfig {
-        EngineConfig { max_query_depth }
+    pub fn new(
+        max_query_depth: u64,
+        refund_ratio: Ratio<u64>,
+        fee_handling: FeeHandling,
+    ) -> EngineConfig {
+        EngineConfig {
+            max_query_depth,
+            refund_ratio,
+            fee_handling,
+            slashing_rate: DEFAULT_SLASHING_RATE,
+            slash_handling: SlashHandling::default(),
+            trace_execution: false,
+            max_trace_entries: DEFAULT_MAX_TRACE_ENTRIES,
+            max_wasm_cache_size: DEFAULT_MAX_WASM_CACHE_SIZE,
+        }
+    }
+
+    /// Returns a copy of this configuration with the given fraction of a slashed validator's and
+    /// its delegators' stakes which is slashed.
+    pub fn with_slashing_rate(mut self, slashing_rate: Ratio<u64>) -> EngineConfig {
+        self.slashing_rate = slashing_rate;
+        self
+    }
+
+    /// Returns a copy of this configuration with the given handling of slashed stake.
+    pub fn with_slash_handling(mut self, slash_handling: SlashHandling) -> EngineConfig {
+        self.slash_handling = slash_handling;
+        self
+    }
+
+    /// Returns a copy of this configuration with the recording of execution traces enabled or
+    /// disabled.
+    pub fn with_trace_execution(mut self, trace_execution: bool) -> EngineConfig {
+        self.trace_execution = trace_execution;
+        self
+    }
+
+    /// Returns a copy of this configuration with the given maximum number of entries recorded in
+    /// the execution trace of a deploy.  Further entries are dropped and the trace is marked as
+    /// truncated.
+    pub fn with_max_trace_entries(mut self, max_trace_entries: usize) -> EngineConfig {
+        self.max_trace_entries = max_trace_entries;
+        self
+    }
+
+    /// Returns a copy of this configuration with the given maximum number of contract Wasm modules
+    /// held in the Wasm cache.  A size of 0 disables the cache.
+    pub fn with_max_wasm_cache_size(mut self, max_wasm_cache_size: usize) -> EngineConfig {
+        self.max_wasm_cache_size = max_wasm_cache_size;
+        self
+    }
+
+    /// Returns the ratio of unspent payment which is refunded to the caller.
+    pub fn refund_ratio(&self) -> Ratio<u64> {
+        self.refund_ratio
+    }
+
+    /// Returns the way in which fees are handled.
+    pub fn fee_handling(&self) -> FeeHandling {
+        self.fee_handling
+    }
+
+    /// Returns `true` if unspent payment is refunded and fees are handled as by default.
+    pub(crate) fn has_default_payment_handling(&self) -> bool {
+        self.refund_ratio == DEFAULT_REFUND_RATIO && self.fee_handling == FeeHandling::default()
+    }
+
+    /// Returns the fraction of a slashed validator's and its delegators' stakes which is slashed.
+    pub fn slashing_rate(&self) -> Ratio<u64> {
+        self.slashing_rate
+    }
+
+    /// Returns the way in which slashed stake is handled.
+    pub fn slash_handling(&self) -> SlashHandling {
+        self.slash_handling
+    }
+
+    /// Returns `true` if an execution trace is recorded for each executed deploy.
+    pub fn trace_execution(&self) -> bool {
+        self.trace_execution
+    }
+
+    /// Returns the maximum number of entries recorded in the execution trace of a deploy.
+    pub fn max_trace_entries(&self) -> usize {
+        self.max_trace_entries
+    }
+
+    /// Returns the maximum number of contract Wasm modules held in the Wasm cache.
+    pub fn max_wasm_cache_size(&self) -> usize {
+        self.max_wasm_cache_size
     }
 }
@@ -30,7 +30,7 @@ use casper_types::{
 use super::error;
 use crate::{
     core::{
-        engine_state::{Error, ExecError, MAX_PAYMENT_AMOUNT},
+        engine_state::{wasm_cache::WasmCache, Error, ExecError, MAX_PAYMENT_AMOUNT},
         execution,
         tracking_copy::{TrackingCopy, TrackingCopyExt},
     },
@@ -127,6 +127,7 @@ impl ExecutableDeployItem {
         account: &Account,
         correlation_id: CorrelationId,
         preprocessor: &Preprocessor,
+        wasm_cache: &WasmCache,
         protocol_version: &ProtocolVersion,
         protocol_data: &ProtocolData,
         phase: Phase,
@@ -339,7 +340,12 @@ impl ExecutableDeployItem {
             .borrow_mut()
             .get_contract_wasm(correlation_id, contract.contract_wasm_hash())?;
 
-        let module = wasm_prep::deserialize(contract_wasm.bytes())?;
+        let module = wasm_cache.get_or_try_insert_with(
+            contract.contract_wasm_hash(),
+            *protocol_version,
+            protocol_data.wasm_config(),
+            || wasm_prep::deserialize(contract_wasm.bytes()),
+        )?;
 
         match entry_point.entry_point_type() {
             EntryPointType::Session => {
@@ -1,6 +1,8 @@
 use std::collections::VecDeque;
 
-use casper_types::{bytesrepr::FromBytes, CLTyped, CLValue, CLValueError, Key, TransferAddr};
+use casper_types::{
+    bytesrepr::FromBytes, CLTyped, CLValue, CLValueError, ContractEvent, Key, TransferAddr,
+};
 
 use super::{error, execution_effect::ExecutionEffect, op::Op};
 use crate::{
@@ -57,6 +59,7 @@ pub enum ExecutionResult {
         effect: ExecutionEffect,
         transfers: Vec<TransferAddr>,
         cost: Gas,
+        events: Vec<ContractEvent>,
     },
 }
 
@@ -66,6 +69,7 @@ impl Default for ExecutionResult {
             effect: ExecutionEffect::default(),
             transfers: Vec::default(),
             cost: Gas::default(),
+            events: Vec::default(),
         }
     }
 }
@@ -139,6 +143,14 @@ impl ExecutionResult {
         }
     }
 
+    /// Returns the events emitted by contracts, which are only retained on success.
+    pub fn events(&self) -> &[ContractEvent] {
+        match self {
+            ExecutionResult::Failure { .. } => &[],
+            ExecutionResult::Success { events, .. } => events,
+        }
+    }
+
     pub fn with_cost(self, cost: Gas) -> Self {
         match self {
             ExecutionResult::Failure {
@@ -153,11 +165,15 @@ impl ExecutionResult {
                 cost,
             },
             ExecutionResult::Success {
-                effect, transfers, ..
+                effect,
+                transfers,
+                events,
+                ..
             } => ExecutionResult::Success {
                 effect,
                 transfers,
                 cost,
+                events,
             },
         }
     }
@@ -176,11 +192,15 @@ impl ExecutionResult {
                 cost,
             },
             ExecutionResult::Success {
-                cost, transfers, ..
+                cost,
+                transfers,
+                events,
+                ..
             } => ExecutionResult::Success {
                 effect,
                 transfers,
                 cost,
+                events,
             },
         }
     }
@@ -198,10 +218,16 @@ impl ExecutionResult {
                 transfers,
                 cost,
             },
-            ExecutionResult::Success { cost, effect, .. } => ExecutionResult::Success {
+            ExecutionResult::Success {
+                cost,
+                effect,
+                events,
+                ..
+            } => ExecutionResult::Success {
                 effect,
                 transfers,
                 cost,
+                events,
             },
         }
     }
@@ -290,10 +316,12 @@ impl From<&ExecutionResult> for casper_types::ExecutionResult {
                 effect,
                 transfers,
                 cost,
+                events,
             } => casper_types::ExecutionResult::Success {
                 effect: effect.into(),
                 transfers: transfers.clone(),
                 cost: cost.value(),
+                events: events.clone(),
             },
             ExecutionResult::Failure {
                 error,
@@ -374,10 +402,22 @@ impl ExecutionResultBuilder {
     }
 
     pub fn transfers(&self) -> Vec<TransferAddr> {
-        self.session_execution_result
+        let mut transfers: Vec<TransferAddr> = self
+            .session_execution_result
             .as_ref()
             .map(ExecutionResult::transfers)
             .cloned()
+            .unwrap_or_default();
+        if let Some(finalize_execution_result) = self.finalize_execution_result.as_ref() {
+            transfers.extend(finalize_execution_result.transfers());
+        }
+        transfers
+    }
+
+    pub fn events(&self) -> Vec<ContractEvent> {
+        self.session_execution_result
+            .as_ref()
+            .map(|result| result.events().to_vec())
             .unwrap_or_default()
     }
 
@@ -387,6 +427,7 @@ impl ExecutionResultBuilder {
         correlation_id: CorrelationId,
     ) -> Result<ExecutionResult, ExecutionResultBuilderError> {
         let transfers = self.transfers();
+        let events = self.events();
         let cost = self.total_cost();
         let mut ops = AdditiveMap::new();
         let mut transforms = AdditiveMap::new();
@@ -395,6 +436,7 @@ impl ExecutionResultBuilder {
             effect: Default::default(),
             transfers,
             cost,
+            events,
         };
 
         match self.payment_execution_result {
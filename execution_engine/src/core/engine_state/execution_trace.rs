@@ -0,0 +1,90 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use casper_types::{system::CallStackElement, ContractHash, Key, U512};
+
+/// A single step recorded while executing a deploy with tracing enabled.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub enum TraceEntry {
+    /// A host function was called by Wasm code.
+    HostFunctionCall {
+        /// The name of the host function.
+        name: String,
+        /// The raw Wasm arguments the host function was called with.
+        args: Vec<i64>,
+    },
+    /// An entry point of a stored contract was called.
+    ContractCall {
+        /// The hash of the called contract.
+        contract_hash: ContractHash,
+        /// The name of the called entry point.
+        entry_point: String,
+        /// The call stack of the caller, as returned by `get_call_stack`.
+        call_stack: Vec<CallStackElement>,
+    },
+    /// Gas was charged.
+    GasCharge {
+        /// The amount of gas charged.
+        amount: U512,
+        /// The total gas used by the current phase after the charge.
+        gas_used: U512,
+    },
+    /// A key was read from the global state.
+    Read {
+        /// The key read.
+        #[schemars(with = "String")]
+        key: Key,
+    },
+    /// A value was written to the global state.
+    Write {
+        /// The key written.
+        #[schemars(with = "String")]
+        key: Key,
+    },
+    /// A value was added to an existing value in the global state.
+    Add {
+        /// The key added to.
+        #[schemars(with = "String")]
+        key: Key,
+    },
+}
+
+/// The steps recorded while executing a deploy with tracing enabled, in order of occurrence.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ExecutionTrace {
+    entries: Vec<TraceEntry>,
+    /// Whether steps were dropped after the maximum number of entries was reached.
+    truncated: bool,
+}
+
+impl ExecutionTrace {
+    /// Constructs a new `ExecutionTrace` from the given steps.
+    pub fn new(entries: Vec<TraceEntry>) -> Self {
+        ExecutionTrace {
+            entries,
+            truncated: false,
+        }
+    }
+
+    /// Returns the recorded steps.
+    pub fn entries(&self) -> &[TraceEntry] {
+        &self.entries
+    }
+
+    /// Returns `true` if steps were dropped after the maximum number of entries was reached.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Records the entry returned by `entry`, unless the trace already holds `max_entries`
+    /// entries, in which case it is marked as truncated instead.
+    pub(crate) fn push<F: FnOnce() -> TraceEntry>(&mut self, entry: F, max_entries: usize) {
+        if self.entries.len() < max_entries {
+            self.entries.push(entry());
+        } else {
+            self.truncated = true;
+        }
+    }
+}
@@ -21,6 +21,7 @@ use casper_types::{
             SeigniorageRecipients, SeigniorageRecipientsSnapshot, AUCTION_DELAY_KEY,
             DELEGATION_RATE_DENOMINATOR, ERA_END_TIMESTAMP_MILLIS_KEY, ERA_ID_KEY,
             INITIAL_ERA_END_TIMESTAMP_MILLIS, INITIAL_ERA_ID, LOCKED_FUNDS_PERIOD_KEY,
+            MAX_DELEGATORS_PER_VALIDATOR_KEY, MINIMUM_DELEGATION_AMOUNT_KEY,
             SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY, UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
         },
         handle_payment::{self},
@@ -37,7 +38,7 @@ use casper_types::{
 
 use crate::{
     core::{
-        engine_state::{execution_effect::ExecutionEffect, EngineConfig},
+        engine_state::{execution_effect::ExecutionEffect, wasm_cache::WasmCache, EngineConfig},
         execution,
         execution::{AddressGenerator, Executor},
         tracking_copy::TrackingCopy,
@@ -535,6 +536,8 @@ pub struct ExecConfig {
     locked_funds_period_millis: u64,
     round_seigniorage_rate: Ratio<u64>,
     unbonding_delay: u64,
+    minimum_delegation_amount: u64,
+    max_delegators_per_validator: u32,
     genesis_timestamp_millis: u64,
 }
 
@@ -549,6 +552,8 @@ impl ExecConfig {
         locked_funds_period_millis: u64,
         round_seigniorage_rate: Ratio<u64>,
         unbonding_delay: u64,
+        minimum_delegation_amount: u64,
+        max_delegators_per_validator: u32,
         genesis_timestamp_millis: u64,
     ) -> ExecConfig {
         ExecConfig {
@@ -560,6 +565,8 @@ impl ExecConfig {
             locked_funds_period_millis,
             round_seigniorage_rate,
             unbonding_delay,
+            minimum_delegation_amount,
+            max_delegators_per_validator,
             genesis_timestamp_millis,
         }
     }
@@ -614,6 +621,14 @@ impl ExecConfig {
         self.unbonding_delay
     }
 
+    pub fn minimum_delegation_amount(&self) -> u64 {
+        self.minimum_delegation_amount
+    }
+
+    pub fn max_delegators_per_validator(&self) -> u32 {
+        self.max_delegators_per_validator
+    }
+
     pub fn genesis_timestamp_millis(&self) -> u64 {
         self.genesis_timestamp_millis
     }
@@ -642,6 +657,10 @@ impl Distribution<ExecConfig> for Standard {
 
         let unbonding_delay = rng.gen();
 
+        let minimum_delegation_amount = rng.gen();
+
+        let max_delegators_per_validator = rng.gen();
+
         let genesis_timestamp_millis = rng.gen();
 
         ExecConfig {
@@ -653,6 +672,8 @@ impl Distribution<ExecConfig> for Standard {
             locked_funds_period_millis,
             round_seigniorage_rate,
             unbonding_delay,
+            minimum_delegation_amount,
+            max_delegators_per_validator,
             genesis_timestamp_millis,
         }
     }
@@ -686,6 +707,12 @@ pub enum GenesisError {
     InvalidDelegatedAmount {
         public_key: PublicKey,
     },
+    DelegatedAmountTooSmall {
+        public_key: PublicKey,
+    },
+    ExceededDelegatorSizeLimit {
+        validator_public_key: PublicKey,
+    },
 }
 
 pub(crate) struct GenesisInstaller<S>
@@ -717,11 +744,12 @@ where
         protocol_version: ProtocolVersion,
         correlation_id: CorrelationId,
         engine_config: EngineConfig,
+        wasm_cache: WasmCache,
         exec_config: ExecConfig,
         tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
         system_module: Module,
     ) -> Self {
-        let executor = Executor::new(engine_config);
+        let executor = Executor::new(engine_config, wasm_cache);
 
         let phase = Phase::System;
         let genesis_config_hash_bytes = genesis_config_hash.as_ref();
@@ -866,6 +894,8 @@ where
         let locked_funds_period_millis = self.exec_config.locked_funds_period_millis();
         let auction_delay: u64 = self.exec_config.auction_delay();
         let genesis_timestamp_millis: u64 = self.exec_config.genesis_timestamp_millis();
+        let minimum_delegation_amount = self.exec_config.minimum_delegation_amount();
+        let max_delegators_per_validator = self.exec_config.max_delegators_per_validator();
 
         let mut named_keys = NamedKeys::new();
 
@@ -883,6 +913,12 @@ where
                 });
             }
 
+            if delegated_amount.value() < U512::from(minimum_delegation_amount) {
+                return Err(GenesisError::DelegatedAmountTooSmall {
+                    public_key: (*delegator_public_key).clone(),
+                });
+            }
+
             let orphan_condition = genesis_validators.iter().find(|genesis_validator| {
                 genesis_validator.public_key() == (*validator_public_key).clone()
             });
@@ -965,6 +1001,14 @@ where
                         }
                     }
 
+                    if max_delegators_per_validator != 0
+                        && bid.delegators().len() > max_delegators_per_validator as usize
+                    {
+                        return Err(GenesisError::ExceededDelegatorSizeLimit {
+                            validator_public_key: public_key,
+                        });
+                    }
+
                     bid
                 };
 
@@ -1085,6 +1129,38 @@ where
         );
         named_keys.insert(UNBONDING_DELAY_KEY.into(), unbonding_delay_uref.into());
 
+        let minimum_delegation_amount_uref = self
+            .uref_address_generator
+            .borrow_mut()
+            .new_uref(AccessRights::READ_ADD_WRITE);
+        self.tracking_copy.borrow_mut().write(
+            minimum_delegation_amount_uref.into(),
+            StoredValue::CLValue(
+                CLValue::from_t(minimum_delegation_amount).map_err(|_| {
+                    GenesisError::CLValue(MINIMUM_DELEGATION_AMOUNT_KEY.to_string())
+                })?,
+            ),
+        );
+        named_keys.insert(
+            MINIMUM_DELEGATION_AMOUNT_KEY.into(),
+            minimum_delegation_amount_uref.into(),
+        );
+
+        let max_delegators_per_validator_uref = self
+            .uref_address_generator
+            .borrow_mut()
+            .new_uref(AccessRights::READ_ADD_WRITE);
+        self.tracking_copy.borrow_mut().write(
+            max_delegators_per_validator_uref.into(),
+            StoredValue::CLValue(CLValue::from_t(max_delegators_per_validator).map_err(|_| {
+                GenesisError::CLValue(MAX_DELEGATORS_PER_VALIDATOR_KEY.to_string())
+            })?),
+        );
+        named_keys.insert(
+            MAX_DELEGATORS_PER_VALIDATOR_KEY.into(),
+            max_delegators_per_validator_uref.into(),
+        );
+
         let entry_points = auction::auction_entry_points();
 
         let access_key = self
@@ -7,6 +7,7 @@ pub mod executable_deploy_item;
 pub mod execute_request;
 pub mod execution_effect;
 pub mod execution_result;
+pub mod execution_trace;
 pub mod genesis;
 pub mod op;
 pub mod query;
@@ -15,6 +16,7 @@ pub mod step;
 pub mod system_contract_cache;
 mod transfer;
 pub mod upgrade;
+pub mod wasm_cache;
 
 use std::{
     cell::RefCell,
@@ -26,7 +28,7 @@ use std::{
 
 use num_rational::Ratio;
 use once_cell::sync::Lazy;
-use tracing::{debug, error};
+use tracing::{debug, error, info};
 
 use casper_types::{
     account::AccountHash,
@@ -36,7 +38,8 @@ use casper_types::{
         auction::{
             EraValidators, ARG_ERA_END_TIMESTAMP_MILLIS, ARG_EVICTED_VALIDATORS,
             ARG_REWARD_FACTORS, ARG_VALIDATOR_PUBLIC_KEYS, AUCTION_DELAY_KEY,
-            LOCKED_FUNDS_PERIOD_KEY, UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
+            LOCKED_FUNDS_PERIOD_KEY, MAX_DELEGATORS_PER_VALIDATOR_KEY,
+            MINIMUM_DELEGATION_AMOUNT_KEY, UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
         },
         handle_payment,
         mint::{self, ROUND_SEIGNIORAGE_RATE_KEY},
@@ -49,27 +52,36 @@ use casper_types::{
 pub use self::{
     balance::{BalanceRequest, BalanceResult},
     deploy_item::DeployItem,
-    engine_config::EngineConfig,
+    engine_config::{
+        EngineConfig, DEFAULT_MAX_QUERY_DEPTH, DEFAULT_MAX_TRACE_ENTRIES,
+        DEFAULT_MAX_WASM_CACHE_SIZE, DEFAULT_REFUND_RATIO, DEFAULT_SLASHING_RATE,
+    },
     era_validators::{GetEraValidatorsError, GetEraValidatorsRequest},
     error::Error,
     executable_deploy_item::ExecutableDeployItem,
     execute_request::ExecuteRequest,
     execution::Error as ExecError,
     execution_result::{ExecutionResult, ExecutionResults, ForcedTransferResult},
+    execution_trace::{ExecutionTrace, TraceEntry},
     genesis::{ExecConfig, GenesisAccount, GenesisResult},
-    query::{GetBidsRequest, GetBidsResult, QueryRequest, QueryResult},
+    query::{
+        GetBidsRequest, GetBidsResult, GetWithdrawsRequest, GetWithdrawsResult, QueryRequest,
+        QueryResult,
+    },
     step::{RewardItem, SlashItem, StepRequest, StepResult},
     system_contract_cache::SystemContractCache,
     transfer::{TransferArgs, TransferRuntimeArgsBuilder, TransferTargetMode},
     upgrade::{UpgradeConfig, UpgradeResult},
+    wasm_cache::{WasmCache, WasmCacheStats},
 };
+pub use crate::core::tracking_copy::ReadSet;
 use crate::{
     core::{
         engine_state::{
             executable_deploy_item::DeployKind, execution_result::ExecutionResultBuilder,
             genesis::GenesisInstaller, upgrade::SystemUpgrader,
         },
-        execution::{self, DirectSystemContractCall, Executor},
+        execution::{self, AddressGenerator, DirectSystemContractCall, Executor},
         tracking_copy::{TrackingCopy, TrackingCopyExt},
     },
     shared::{
@@ -96,10 +108,28 @@ pub static MAX_PAYMENT: Lazy<U512> = Lazy::new(|| U512::from(MAX_PAYMENT_AMOUNT)
 /// pay.
 pub const WASMLESS_TRANSFER_FIXED_GAS_PRICE: u64 = 1;
 
+/// The result of executing a single deploy item, along with what was recorded while executing it.
+struct DeployItemExecution {
+    result: ExecutionResult,
+    read_set: ReadSet,
+    trace: ExecutionTrace,
+}
+
+impl DeployItemExecution {
+    fn precondition_failure(error: Error) -> Self {
+        DeployItemExecution {
+            result: ExecutionResult::precondition_failure(error),
+            read_set: ReadSet::default(),
+            trace: ExecutionTrace::default(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct EngineState<S> {
     config: EngineConfig,
     system_contract_cache: SystemContractCache,
+    wasm_cache: WasmCache,
     state: S,
 }
 
@@ -110,9 +140,11 @@ where
 {
     pub fn new(state: S, config: EngineConfig) -> EngineState<S> {
         let system_contract_cache = Default::default();
+        let wasm_cache = WasmCache::new(config.max_wasm_cache_size());
         EngineState {
             config,
             system_contract_cache,
+            wasm_cache,
             state,
         }
     }
@@ -121,6 +153,16 @@ where
         &self.config
     }
 
+    /// Returns the cache of deserialized contract Wasm modules.
+    pub fn wasm_cache(&self) -> &WasmCache {
+        &self.wasm_cache
+    }
+
+    /// Returns the global state this engine operates on.
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
     pub fn get_protocol_data(
         &self,
         protocol_version: ProtocolVersion,
@@ -163,6 +205,7 @@ where
             protocol_version,
             correlation_id,
             self.config,
+            WasmCache::clone(&self.wasm_cache),
             ee_config.clone(),
             tracking_copy,
             system_module,
@@ -350,6 +393,54 @@ where
             tracking_copy.borrow_mut().write(unbonding_delay_key, value);
         }
 
+        // The delegation limits postdate the genesis of existing networks, so their named keys are
+        // installed in the auction contract if missing.
+        let mut new_delegation_limits = Vec::new();
+        if let Some(new_minimum_delegation_amount) = upgrade_config.new_minimum_delegation_amount()
+        {
+            let value = CLValue::from_t(new_minimum_delegation_amount)
+                .map_err(|_| Error::Bytesrepr("new_minimum_delegation_amount".to_string()))?;
+            new_delegation_limits.push((MINIMUM_DELEGATION_AMOUNT_KEY, value));
+        }
+        if let Some(new_max_delegators_per_validator) =
+            upgrade_config.new_max_delegators_per_validator()
+        {
+            let value = CLValue::from_t(new_max_delegators_per_validator)
+                .map_err(|_| Error::Bytesrepr("new_max_delegators_per_validator".to_string()))?;
+            new_delegation_limits.push((MAX_DELEGATORS_PER_VALIDATOR_KEY, value));
+        }
+
+        if !new_delegation_limits.is_empty() {
+            let mut auction_contract = tracking_copy
+                .borrow_mut()
+                .get_contract(correlation_id, new_protocol_data.auction())?;
+
+            let mut address_generator =
+                AddressGenerator::new(pre_state_hash.as_ref(), Phase::System);
+            let mut new_named_keys = NamedKeys::new();
+            for (name, value) in new_delegation_limits {
+                let key = match auction_contract.named_keys().get(name) {
+                    Some(key) => *key,
+                    None => {
+                        let uref = address_generator.new_uref(AccessRights::READ_ADD_WRITE);
+                        new_named_keys.insert(name.to_string(), uref.into());
+                        uref.into()
+                    }
+                };
+                tracking_copy
+                    .borrow_mut()
+                    .write(key, StoredValue::CLValue(value));
+            }
+
+            if !new_named_keys.is_empty() {
+                auction_contract.named_keys_append(&mut new_named_keys);
+                tracking_copy.borrow_mut().write(
+                    new_protocol_data.auction().into(),
+                    StoredValue::Contract(auction_contract),
+                );
+            }
+        }
+
         if let Some(new_round_seigniorage_rate) = upgrade_config.new_round_seigniorage_rate() {
             let new_round_seigniorage_rate: Ratio<U512> = {
                 let (numer, denom) = new_round_seigniorage_rate.into();
@@ -387,6 +478,9 @@ where
             )
             .map_err(Into::into)?;
 
+        // modules cached under the previous chainspec may no longer be valid
+        self.wasm_cache.clear();
+
         // return result and effects
         Ok(UpgradeResult::from_commit_result(commit_result, effects))
     }
@@ -427,45 +521,142 @@ where
     pub fn run_execute(
         &self,
         correlation_id: CorrelationId,
-        mut exec_request: ExecuteRequest,
+        exec_request: ExecuteRequest,
     ) -> Result<ExecutionResults, Error> {
-        let executor = Executor::new(self.config);
+        let executions = self.execute_deploy_items(correlation_id, exec_request)?;
+        Ok(executions
+            .into_iter()
+            .map(|execution| execution.result)
+            .collect())
+    }
+
+    /// Executes the deploys of `exec_request` in the same way as `run_execute`, additionally
+    /// returning the keys each deploy read from the global state under the parent state hash.
+    pub fn run_execute_recording_reads(
+        &self,
+        correlation_id: CorrelationId,
+        exec_request: ExecuteRequest,
+    ) -> Result<Vec<(ExecutionResult, ReadSet)>, Error> {
+        let executions = self.execute_deploy_items(correlation_id, exec_request)?;
+        Ok(executions
+            .into_iter()
+            .map(|execution| (execution.result, execution.read_set))
+            .collect())
+    }
+
+    /// Executes the deploys of `exec_request` in the same way as `run_execute`, additionally
+    /// returning the execution trace of each deploy.
+    ///
+    /// The traces are empty unless tracing is enabled in the `EngineConfig`.
+    pub fn run_execute_with_traces(
+        &self,
+        correlation_id: CorrelationId,
+        exec_request: ExecuteRequest,
+    ) -> Result<Vec<(ExecutionResult, ExecutionTrace)>, Error> {
+        let executions = self.execute_deploy_items(correlation_id, exec_request)?;
+        Ok(executions
+            .into_iter()
+            .map(|execution| (execution.result, execution.trace))
+            .collect())
+    }
+
+    fn execute_deploy_items(
+        &self,
+        correlation_id: CorrelationId,
+        mut exec_request: ExecuteRequest,
+    ) -> Result<Vec<DeployItemExecution>, Error> {
+        let executor = Executor::new(self.config, WasmCache::clone(&self.wasm_cache));
 
         let deploys = exec_request.take_deploys();
-        let mut results = ExecutionResults::with_capacity(deploys.len());
+        let mut results = Vec::with_capacity(deploys.len());
 
         for deploy_item in deploys {
-            let result = match deploy_item.session {
-                ExecutableDeployItem::Transfer { .. } => self.transfer(
-                    correlation_id,
-                    &executor,
-                    exec_request.protocol_version,
-                    exec_request.parent_state_hash,
-                    BlockTime::new(exec_request.block_time),
-                    deploy_item,
-                    exec_request.proposer.clone(),
-                ),
-                _ => self.deploy(
-                    correlation_id,
-                    &executor,
-                    exec_request.protocol_version,
-                    exec_request.parent_state_hash,
-                    BlockTime::new(exec_request.block_time),
-                    deploy_item,
-                    exec_request.proposer.clone(),
-                ),
-            };
-            match result {
-                Ok(result) => results.push_back(result),
-                Err(error) => {
-                    return Err(error);
-                }
-            };
+            let result = self.execute_deploy_item(
+                correlation_id,
+                &executor,
+                exec_request.protocol_version,
+                exec_request.parent_state_hash,
+                BlockTime::new(exec_request.block_time),
+                deploy_item,
+                exec_request.proposer.clone(),
+            )?;
+            results.push(result);
         }
 
         Ok(results)
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn execute_deploy_item(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        protocol_version: ProtocolVersion,
+        prestate_hash: Blake2bHash,
+        blocktime: BlockTime,
+        deploy_item: DeployItem,
+        proposer: PublicKey,
+    ) -> Result<DeployItemExecution, Error> {
+        // Obtain current protocol data for given version
+        // do this first, as there is no reason to proceed if protocol version is invalid
+        let protocol_data = match self.state.get_protocol_data(protocol_version) {
+            Ok(Some(protocol_data)) => protocol_data,
+            Ok(None) => {
+                let error = Error::InvalidProtocolVersion(protocol_version);
+                return Ok(DeployItemExecution::precondition_failure(error));
+            }
+            Err(error) => {
+                return Ok(DeployItemExecution::precondition_failure(Error::Exec(
+                    error.into(),
+                )));
+            }
+        };
+
+        // Create tracking copy (which functions as a deploy context)
+        // validation_spec_2: prestate_hash check
+        // do this second; as there is no reason to proceed if the prestate hash is invalid
+        let tracking_copy = match self.tracking_copy(prestate_hash) {
+            Err(error) => return Ok(DeployItemExecution::precondition_failure(error)),
+            Ok(None) => return Err(Error::RootNotFound(prestate_hash)),
+            Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
+        };
+        if self.config.trace_execution() {
+            tracking_copy
+                .borrow_mut()
+                .enable_tracing(self.config.max_trace_entries());
+        }
+
+        let result = match deploy_item.session {
+            ExecutableDeployItem::Transfer { .. } => self.transfer(
+                correlation_id,
+                executor,
+                protocol_version,
+                protocol_data,
+                Rc::clone(&tracking_copy),
+                blocktime,
+                deploy_item,
+                proposer,
+            ),
+            _ => self.deploy(
+                correlation_id,
+                executor,
+                protocol_version,
+                protocol_data,
+                Rc::clone(&tracking_copy),
+                blocktime,
+                deploy_item,
+                proposer,
+            ),
+        }?;
+        let tracking_copy = tracking_copy.borrow();
+
+        Ok(DeployItemExecution {
+            result,
+            read_set: tracking_copy.read_set(),
+            trace: tracking_copy.trace().unwrap_or_default(),
+        })
+    }
+
     fn get_authorized_account(
         &self,
         correlation_id: CorrelationId,
@@ -516,35 +707,17 @@ where
     }
 
     #[allow(clippy::too_many_arguments)]
-    pub fn transfer(
+    fn transfer(
         &self,
         correlation_id: CorrelationId,
         executor: &Executor,
         protocol_version: ProtocolVersion,
-        prestate_hash: Blake2bHash,
+        protocol_data: ProtocolData,
+        tracking_copy: Rc<RefCell<TrackingCopy<S::Reader>>>,
         blocktime: BlockTime,
         deploy_item: DeployItem,
         proposer: PublicKey,
     ) -> Result<ExecutionResult, Error> {
-        let protocol_data = match self.state.get_protocol_data(protocol_version) {
-            Ok(Some(protocol_data)) => protocol_data,
-            Ok(None) => {
-                let error = Error::InvalidProtocolVersion(protocol_version);
-                return Ok(ExecutionResult::precondition_failure(error));
-            }
-            Err(error) => {
-                return Ok(ExecutionResult::precondition_failure(Error::Exec(
-                    error.into(),
-                )));
-            }
-        };
-
-        let tracking_copy = match self.tracking_copy(prestate_hash) {
-            Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
-            Ok(None) => return Err(Error::RootNotFound(prestate_hash)),
-            Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
-        };
-
         let preprocessor = {
             let wasm_config = protocol_data.wasm_config();
             Preprocessor::new(*wasm_config)
@@ -1099,47 +1272,24 @@ where
     }
 
     #[allow(clippy::too_many_arguments)]
-    pub fn deploy(
+    fn deploy(
         &self,
         correlation_id: CorrelationId,
         executor: &Executor,
         protocol_version: ProtocolVersion,
-        prestate_hash: Blake2bHash,
+        protocol_data: ProtocolData,
+        tracking_copy: Rc<RefCell<TrackingCopy<S::Reader>>>,
         blocktime: BlockTime,
         deploy_item: DeployItem,
         proposer: PublicKey,
     ) -> Result<ExecutionResult, Error> {
         // spec: https://casperlabs.atlassian.net/wiki/spaces/EN/pages/123404576/Payment+code+execution+specification
 
-        // Obtain current protocol data for given version
-        // do this first, as there is no reason to proceed if protocol version is invalid
-        let protocol_data = match self.state.get_protocol_data(protocol_version) {
-            Ok(Some(protocol_data)) => protocol_data,
-            Ok(None) => {
-                let error = Error::InvalidProtocolVersion(protocol_version);
-                return Ok(ExecutionResult::precondition_failure(error));
-            }
-            Err(error) => {
-                return Ok(ExecutionResult::precondition_failure(Error::Exec(
-                    error.into(),
-                )));
-            }
-        };
-
         let preprocessor = {
             let wasm_config = protocol_data.wasm_config();
             Preprocessor::new(*wasm_config)
         };
 
-        // Create tracking copy (which functions as a deploy context)
-        // validation_spec_2: prestate_hash check
-        // do this second; as there is no reason to proceed if the prestate hash is invalid
-        let tracking_copy = match self.tracking_copy(prestate_hash) {
-            Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
-            Ok(None) => return Err(Error::RootNotFound(prestate_hash)),
-            Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
-        };
-
         let system_module = {
             match tracking_copy.borrow_mut().get_system_module(&preprocessor) {
                 Ok(module) => module,
@@ -1185,6 +1335,7 @@ where
             &account,
             correlation_id,
             &preprocessor,
+            &self.wasm_cache,
             &protocol_version,
             &protocol_data,
             Phase::Session,
@@ -1264,6 +1415,7 @@ where
                 &account,
                 correlation_id,
                 &preprocessor,
+                &self.wasm_cache,
                 &protocol_version,
                 &protocol_data,
                 phase,
@@ -1726,7 +1878,7 @@ where
                 .map_err(Error::from)?
         };
 
-        let executor = Executor::new(self.config);
+        let executor = Executor::new(self.config, WasmCache::clone(&self.wasm_cache));
 
         let mut named_keys = auction_contract.named_keys().to_owned();
         let base_key = Key::from(auction_contract_hash);
@@ -1818,6 +1970,36 @@ where
         Ok(GetBidsResult::Success { bids })
     }
 
+    pub fn get_withdraws(
+        &self,
+        correlation_id: CorrelationId,
+        get_withdraws_request: GetWithdrawsRequest,
+    ) -> Result<GetWithdrawsResult, Error> {
+        let tracking_copy = match self.tracking_copy(get_withdraws_request.state_hash())? {
+            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+            None => return Ok(GetWithdrawsResult::RootNotFound),
+        };
+
+        let mut tracking_copy = tracking_copy.borrow_mut();
+
+        let withdraw_keys = tracking_copy
+            .get_keys(correlation_id, &KeyTag::Withdraw)
+            .map_err(|err| Error::Exec(err.into()))?;
+
+        let mut withdraws = BTreeMap::new();
+
+        for key in withdraw_keys.iter() {
+            if let (Key::Withdraw(account_hash), Some(StoredValue::Withdraw(unbonding_purses))) = (
+                key,
+                tracking_copy.get(correlation_id, key).map_err(Into::into)?,
+            ) {
+                withdraws.insert(*account_hash, unbonding_purses);
+            };
+        }
+
+        Ok(GetWithdrawsResult::Success { withdraws })
+    }
+
     pub fn commit_step(
         &self,
         correlation_id: CorrelationId,
@@ -1837,7 +2019,7 @@ where
             Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
         };
 
-        let executor = Executor::new(self.config);
+        let executor = Executor::new(self.config, WasmCache::clone(&self.wasm_cache));
 
         let preprocessor = {
             let wasm_config = protocol_data.wasm_config();
@@ -1955,6 +2137,17 @@ where
             }
         };
 
+        for slash_item in &step_request.slash_items {
+            match &slash_item.evidence {
+                Some(evidence) => info!(
+                    validator = %slash_item.validator_id,
+                    evidence = %base16::encode_lower(evidence),
+                    "slashing validator"
+                ),
+                None => info!(validator = %slash_item.validator_id, "slashing validator"),
+            }
+        }
+
         let slash_args = {
             let mut runtime_args = RuntimeArgs::new();
             runtime_args
@@ -1,4 +1,7 @@
-use casper_types::{system::auction::Bids, Key};
+use casper_types::{
+    system::auction::{Bids, UnbondingPurses},
+    Key,
+};
 
 use crate::{
     core::tracking_copy::TrackingCopyQueryResult,
@@ -98,3 +101,37 @@ impl GetBidsResult {
         }
     }
 }
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetWithdrawsRequest {
+    state_hash: Blake2bHash,
+}
+
+impl GetWithdrawsRequest {
+    pub fn new(state_hash: Blake2bHash) -> Self {
+        GetWithdrawsRequest { state_hash }
+    }
+
+    pub fn state_hash(&self) -> Blake2bHash {
+        self.state_hash
+    }
+}
+
+#[derive(Debug)]
+pub enum GetWithdrawsResult {
+    RootNotFound,
+    Success { withdraws: UnbondingPurses },
+}
+
+impl GetWithdrawsResult {
+    pub fn success(withdraws: UnbondingPurses) -> Self {
+        GetWithdrawsResult::Success { withdraws }
+    }
+
+    pub fn withdraws(&self) -> Option<&UnbondingPurses> {
+        match self {
+            GetWithdrawsResult::RootNotFound => None,
+            GetWithdrawsResult::Success { withdraws } => Some(withdraws),
+        }
+    }
+}
@@ -15,11 +15,26 @@ use crate::{
 #[derive(Debug)]
 pub struct SlashItem {
     pub validator_id: PublicKey,
+    /// The serialized evidence of the validator's fault, if known to the consensus protocol.
+    ///
+    /// The evidence is only logged, and not written to global state: it is not part of the
+    /// finalized block, so nodes can hold different evidence against the same validator.
+    pub evidence: Option<Vec<u8>>,
 }
 
 impl SlashItem {
     pub fn new(validator_id: PublicKey) -> Self {
-        Self { validator_id }
+        Self {
+            validator_id,
+            evidence: None,
+        }
+    }
+
+    pub fn with_evidence(validator_id: PublicKey, evidence: Vec<u8>) -> Self {
+        Self {
+            validator_id,
+            evidence: Some(evidence),
+        }
     }
 }
 
@@ -88,6 +88,8 @@ pub struct UpgradeConfig {
     new_locked_funds_period_millis: Option<u64>,
     new_round_seigniorage_rate: Option<Ratio<u64>>,
     new_unbonding_delay: Option<u64>,
+    new_minimum_delegation_amount: Option<u64>,
+    new_max_delegators_per_validator: Option<u32>,
     global_state_update: BTreeMap<Key, StoredValue>,
 }
 
@@ -105,6 +107,8 @@ impl UpgradeConfig {
         new_locked_funds_period_millis: Option<u64>,
         new_round_seigniorage_rate: Option<Ratio<u64>>,
         new_unbonding_delay: Option<u64>,
+        new_minimum_delegation_amount: Option<u64>,
+        new_max_delegators_per_validator: Option<u32>,
         global_state_update: BTreeMap<Key, StoredValue>,
     ) -> Self {
         UpgradeConfig {
@@ -119,6 +123,8 @@ impl UpgradeConfig {
             new_locked_funds_period_millis,
             new_round_seigniorage_rate,
             new_unbonding_delay,
+            new_minimum_delegation_amount,
+            new_max_delegators_per_validator,
             global_state_update,
         }
     }
@@ -167,6 +173,14 @@ impl UpgradeConfig {
         self.new_unbonding_delay
     }
 
+    pub fn new_minimum_delegation_amount(&self) -> Option<u64> {
+        self.new_minimum_delegation_amount
+    }
+
+    pub fn new_max_delegators_per_validator(&self) -> Option<u32> {
+        self.new_max_delegators_per_validator
+    }
+
     pub fn global_state_update(&self) -> &BTreeMap<Key, StoredValue> {
         &self.global_state_update
     }
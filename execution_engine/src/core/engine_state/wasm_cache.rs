@@ -0,0 +1,252 @@
+use std::sync::{Arc, Mutex};
+
+use linked_hash_map::LinkedHashMap;
+use parity_wasm::elements::Module;
+
+use casper_types::{ContractWasmHash, ProtocolVersion};
+
+use crate::shared::wasm_config::WasmConfig;
+
+/// The number of lookups in a `WasmCache` which did and did not find the requested module.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct WasmCacheStats {
+    /// The number of lookups which found the requested module.
+    pub hits: u64,
+    /// The number of lookups which did not find the requested module.
+    pub misses: u64,
+}
+
+#[derive(Debug)]
+struct ModuleCache {
+    max_size: usize,
+    /// The Wasm config under which the cached modules were prepared.
+    wasm_config: Option<WasmConfig>,
+    /// The cached modules, least recently used first.
+    modules: LinkedHashMap<(ContractWasmHash, ProtocolVersion), Module>,
+    stats: WasmCacheStats,
+}
+
+impl ModuleCache {
+    /// Drops all cached modules if they were prepared under a different Wasm config.
+    fn check_wasm_config(&mut self, wasm_config: &WasmConfig) {
+        if self.wasm_config.as_ref() != Some(wasm_config) {
+            self.modules.clear();
+            self.wasm_config = Some(*wasm_config);
+        }
+    }
+}
+
+/// A bounded, least-recently-used cache of deserialized contract Wasm modules.
+///
+/// Modules are keyed by the hash of the contract Wasm they were deserialized from and the protocol
+/// version under which they are executed.  The cache is emptied whenever it is accessed with a
+/// different `WasmConfig`, or explicitly via `clear` when the chainspec changes.
+#[derive(Clone, Debug)]
+pub struct WasmCache(Arc<Mutex<ModuleCache>>);
+
+impl WasmCache {
+    /// Creates a new cache holding at most `max_size` modules.  A size of 0 disables the cache.
+    pub fn new(max_size: usize) -> Self {
+        WasmCache(Arc::new(Mutex::new(ModuleCache {
+            max_size,
+            wasm_config: None,
+            modules: LinkedHashMap::new(),
+            stats: WasmCacheStats::default(),
+        })))
+    }
+
+    /// Returns a clone of the module deserialized from `contract_wasm_hash` for use under
+    /// `protocol_version` and `wasm_config`, marking it as the most recently used.
+    pub fn get(
+        &self,
+        contract_wasm_hash: ContractWasmHash,
+        protocol_version: ProtocolVersion,
+        wasm_config: &WasmConfig,
+    ) -> Option<Module> {
+        let mut cache = self.0.lock().unwrap();
+        cache.check_wasm_config(wasm_config);
+        let maybe_module = cache
+            .modules
+            .get_refresh(&(contract_wasm_hash, protocol_version))
+            .cloned();
+        if maybe_module.is_some() {
+            cache.stats.hits += 1;
+        } else {
+            cache.stats.misses += 1;
+        }
+        maybe_module
+    }
+
+    /// Inserts `module`, evicting the least recently used module if the cache is full.
+    pub fn insert(
+        &self,
+        contract_wasm_hash: ContractWasmHash,
+        protocol_version: ProtocolVersion,
+        wasm_config: &WasmConfig,
+        module: Module,
+    ) {
+        let mut cache = self.0.lock().unwrap();
+        if cache.max_size == 0 {
+            return;
+        }
+        cache.check_wasm_config(wasm_config);
+        cache
+            .modules
+            .insert((contract_wasm_hash, protocol_version), module);
+        while cache.modules.len() > cache.max_size {
+            cache.modules.pop_front();
+        }
+    }
+
+    /// Returns the cached module, or the one returned by `deserialize` after inserting it into the
+    /// cache.
+    ///
+    /// The cache is not locked while `deserialize` runs.
+    pub fn get_or_try_insert_with<F, E>(
+        &self,
+        contract_wasm_hash: ContractWasmHash,
+        protocol_version: ProtocolVersion,
+        wasm_config: &WasmConfig,
+        deserialize: F,
+    ) -> Result<Module, E>
+    where
+        F: FnOnce() -> Result<Module, E>,
+    {
+        if let Some(module) = self.get(contract_wasm_hash, protocol_version, wasm_config) {
+            return Ok(module);
+        }
+        let module = deserialize()?;
+        self.insert(
+            contract_wasm_hash,
+            protocol_version,
+            wasm_config,
+            module.clone(),
+        );
+        Ok(module)
+    }
+
+    /// Removes all cached modules.
+    pub fn clear(&self) {
+        let mut cache = self.0.lock().unwrap();
+        cache.modules.clear();
+        cache.wasm_config = None;
+    }
+
+    /// Returns the number of cached modules.
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().modules.len()
+    }
+
+    /// Returns `true` if no modules are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of hits and misses since the last call, and resets them.
+    pub fn take_stats(&self) -> WasmCacheStats {
+        let mut cache = self.0.lock().unwrap();
+        std::mem::take(&mut cache.stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_wasm::elements::Module;
+
+    use casper_types::{ContractWasmHash, ProtocolVersion};
+
+    use super::{WasmCache, WasmCacheStats};
+    use crate::shared::wasm_config::WasmConfig;
+
+    const HASH_1: ContractWasmHash = ContractWasmHash::new([1; 32]);
+    const HASH_2: ContractWasmHash = ContractWasmHash::new([2; 32]);
+    const HASH_3: ContractWasmHash = ContractWasmHash::new([3; 32]);
+
+    fn insert(cache: &WasmCache, contract_wasm_hash: ContractWasmHash) {
+        cache.insert(
+            contract_wasm_hash,
+            ProtocolVersion::V1_0_0,
+            &WasmConfig::default(),
+            Module::default(),
+        );
+    }
+
+    fn contains(cache: &WasmCache, contract_wasm_hash: ContractWasmHash) -> bool {
+        cache
+            .get(
+                contract_wasm_hash,
+                ProtocolVersion::V1_0_0,
+                &WasmConfig::default(),
+            )
+            .is_some()
+    }
+
+    #[test]
+    fn should_get_inserted_module() {
+        let cache = WasmCache::new(2);
+        insert(&cache, HASH_1);
+
+        assert!(contains(&cache, HASH_1));
+        assert!(!contains(&cache, HASH_2));
+        assert_eq!(cache.take_stats(), WasmCacheStats { hits: 1, misses: 1 });
+        assert_eq!(cache.take_stats(), WasmCacheStats::default());
+    }
+
+    #[test]
+    fn should_evict_least_recently_used_module() {
+        let cache = WasmCache::new(2);
+        insert(&cache, HASH_1);
+        insert(&cache, HASH_2);
+        assert!(contains(&cache, HASH_1));
+
+        insert(&cache, HASH_3);
+
+        assert_eq!(cache.len(), 2);
+        assert!(contains(&cache, HASH_1));
+        assert!(!contains(&cache, HASH_2));
+        assert!(contains(&cache, HASH_3));
+    }
+
+    #[test]
+    fn should_not_cache_when_disabled() {
+        let cache = WasmCache::new(0);
+        insert(&cache, HASH_1);
+
+        assert!(cache.is_empty());
+        assert!(!contains(&cache, HASH_1));
+    }
+
+    #[test]
+    fn should_key_by_protocol_version() {
+        let cache = WasmCache::new(2);
+        insert(&cache, HASH_1);
+
+        let protocol_version = ProtocolVersion::from_parts(1, 1, 0);
+        assert!(cache
+            .get(HASH_1, protocol_version, &WasmConfig::default())
+            .is_none());
+    }
+
+    #[test]
+    fn should_invalidate_on_wasm_config_change() {
+        let cache = WasmCache::new(2);
+        insert(&cache, HASH_1);
+
+        let mut wasm_config = WasmConfig::default();
+        wasm_config.max_memory += 1;
+        assert!(cache
+            .get(HASH_1, ProtocolVersion::V1_0_0, &wasm_config)
+            .is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn should_clear() {
+        let cache = WasmCache::new(2);
+        insert(&cache, HASH_1);
+
+        cache.clear();
+
+        assert!(cache.is_empty());
+    }
+}
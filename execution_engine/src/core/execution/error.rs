@@ -104,6 +104,10 @@ pub enum Error {
     MissingArgument { name: String },
     #[error("Dictionary item key exceeded maximum length")]
     DictionaryItemKeyExceedsLength,
+    #[error("Event payload exceeded maximum length")]
+    EventPayloadExceedsLength,
+    #[error("Exceeded maximum number of events per deploy")]
+    TooManyEvents,
 }
 
 impl From<wasm_prep::PreprocessingError> for Error {
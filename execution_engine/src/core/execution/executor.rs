@@ -17,7 +17,7 @@ use crate::{
     core::{
         engine_state::{
             execution_effect::ExecutionEffect, execution_result::ExecutionResult,
-            system_contract_cache::SystemContractCache, EngineConfig,
+            system_contract_cache::SystemContractCache, wasm_cache::WasmCache, EngineConfig,
         },
         execution::{address_generator::AddressGenerator, Error},
         runtime::{extract_access_rights_from_keys, instance_and_memory, Runtime},
@@ -73,12 +73,13 @@ macro_rules! on_fail_charge {
 
 pub struct Executor {
     config: EngineConfig,
+    wasm_cache: WasmCache,
 }
 
 #[allow(clippy::too_many_arguments)]
 impl Executor {
-    pub fn new(config: EngineConfig) -> Self {
-        Executor { config }
+    pub fn new(config: EngineConfig, wasm_cache: WasmCache) -> Self {
+        Executor { config, wasm_cache }
     }
 
     pub fn config(&self) -> EngineConfig {
@@ -170,6 +171,7 @@ impl Executor {
         let mut runtime = Runtime::new(
             self.config,
             system_contract_cache,
+            WasmCache::clone(&self.wasm_cache),
             memory,
             module,
             context,
@@ -203,6 +205,7 @@ impl Executor {
                         effect: runtime.context().effect(),
                         transfers: runtime.context().transfers().to_owned(),
                         cost: runtime.context().gas_counter(),
+                        events: runtime.context().events().to_owned(),
                     };
                 }
                 Err(error) => {
@@ -228,6 +231,7 @@ impl Executor {
                         effect: runtime.context().effect(),
                         transfers: runtime.context().transfers().to_owned(),
                         cost: runtime.context().gas_counter(),
+                        events: runtime.context().events().to_owned(),
                     };
                 }
                 Err(error) => {
@@ -253,6 +257,7 @@ impl Executor {
                         effect: runtime.context().effect(),
                         transfers: runtime.context().transfers().to_owned(),
                         cost: runtime.context().gas_counter(),
+                        events: runtime.context().events().to_owned(),
                     }
                 }
                 Err(error) => {
@@ -276,6 +281,7 @@ impl Executor {
             effect: runtime.context().effect(),
             transfers: runtime.context().transfers().to_owned(),
             cost: runtime.context().gas_counter(),
+            events: runtime.context().events().to_owned(),
         }
     }
 
@@ -357,6 +363,7 @@ impl Executor {
                 effect: runtime.context().effect(),
                 transfers: runtime.context().transfers().to_owned(),
                 cost: runtime.context().gas_counter(),
+                events: runtime.context().events().to_owned(),
             },
             Err(error) => ExecutionResult::Failure {
                 error: error.into(),
@@ -656,6 +663,7 @@ impl Executor {
         let runtime = Runtime::new(
             self.config,
             system_contract_cache,
+            WasmCache::clone(&self.wasm_cache),
             memory,
             module,
             runtime_context,
@@ -762,6 +770,7 @@ impl DirectSystemContractCall {
                     effect: runtime.context().effect(),
                     transfers: runtime.context().transfers().to_owned(),
                     cost: runtime.context().gas_counter(),
+                    events: runtime.context().events().to_owned(),
                 }
                 .take_with_ret(ret),
                 Err(error) => ExecutionResult::Failure {
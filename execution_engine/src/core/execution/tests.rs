@@ -21,6 +21,7 @@ fn on_fail_charge_test_helper<T>(
         effect: Default::default(),
         transfers,
         cost: success_cost,
+        events: Vec::default(),
     }
 }
 
@@ -67,6 +68,7 @@ fn on_fail_charge_with_action() {
             effect: Default::default(),
             transfers: Vec::default(),
             cost: Gas::default(),
+            events: Vec::default(),
         }
     };
     match f() {
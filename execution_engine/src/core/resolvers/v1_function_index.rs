@@ -54,6 +54,11 @@ pub enum FunctionIndex {
     DictionaryGetFuncIndex,
     DictionaryPutFuncIndex,
     LoadCallStack,
+    EmitEventFuncIndex,
+    VerifySignatureFuncIndex,
+    RecoverSecp256k1FuncIndex,
+    Sha256FuncIndex,
+    Keccak256FuncIndex,
 }
 
 impl From<FunctionIndex> for usize {
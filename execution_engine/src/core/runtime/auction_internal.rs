@@ -1,5 +1,7 @@
 use std::collections::BTreeSet;
 
+use num_rational::Ratio;
+
 use casper_types::{
     account::{self, AccountHash},
     bytesrepr::{FromBytes, ToBytes},
@@ -7,7 +9,7 @@ use casper_types::{
     system::{
         auction::{
             AccountProvider, Auction, Bid, EraInfo, Error, MintProvider, RuntimeProvider,
-            StorageProvider, UnbondingPurse,
+            SlashHandling, StorageProvider, UnbondingPurse,
         },
         mint, CallStackElement,
     },
@@ -104,6 +106,19 @@ where
             .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))
     }
 
+    fn read_era_info(&mut self, era_id: EraId) -> Result<Option<EraInfo>, Error> {
+        match self.context.read_gs(&Key::EraInfo(era_id)) {
+            Ok(Some(StoredValue::EraInfo(era_info))) => Ok(Some(era_info)),
+            Ok(Some(_)) => Err(Error::Storage),
+            Ok(None) => Ok(None),
+            Err(execution::Error::BytesRepr(_)) => Err(Error::Serialization),
+            // NOTE: This extra condition is needed to correctly propagate GasLimit to the user. See
+            // also [`Runtime::reverter`] and [`to_auction_error`]
+            Err(execution::Error::GasLimit) => Err(Error::GasLimit),
+            Err(_) => Err(Error::Storage),
+        }
+    }
+
     fn record_era_info(&mut self, era_id: EraId, era_info: EraInfo) -> Result<(), Error> {
         Runtime::record_era_info(self, era_id, era_info)
             .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::RecordEraInfo))
@@ -134,6 +149,14 @@ where
     fn blake2b<T: AsRef<[u8]>>(&self, data: T) -> [u8; BLAKE2B_DIGEST_LENGTH] {
         account::blake2b(data)
     }
+
+    fn slashing_rate(&self) -> Ratio<u64> {
+        self.config.slashing_rate()
+    }
+
+    fn slash_handling(&self) -> SlashHandling {
+        self.config.slash_handling()
+    }
 }
 
 impl<'a, R> MintProvider for Runtime<'a, R>
@@ -240,6 +263,28 @@ where
         self.mint_reduce_total_supply(mint_contract, amount)
             .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::MintReward))
     }
+
+    fn burn(&mut self, purse: URef, amount: U512) -> Result<(), Error> {
+        let balance = MintProvider::get_balance(self, purse)?.ok_or(Error::GetBalance)?;
+        let new_balance = balance
+            .checked_sub(amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+        mint::StorageProvider::write_balance(self, purse, new_balance)
+            .map_err(|_| Error::BurnSlashedStake)?;
+        let mint_contract = self.get_mint_contract();
+        self.mint_reduce_total_supply(mint_contract, amount)
+            .map_err(|exec_error| {
+                <Option<Error>>::from(exec_error).unwrap_or(Error::BurnSlashedStake)
+            })
+    }
+
+    fn accumulate(&mut self, purse: URef, amount: U512) -> Result<(), Error> {
+        let accumulation_purse = self
+            .get_or_create_accumulation_purse()
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Transfer))?;
+        self.mint_transfer_direct(None, purse, accumulation_purse, amount, None)?
+            .map_err(|_| Error::Transfer)
+    }
 }
 
 impl<'a, R> AccountProvider for Runtime<'a, R>
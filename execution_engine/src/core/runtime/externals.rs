@@ -1,5 +1,7 @@
 use std::{collections::BTreeSet, convert::TryFrom};
 
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
 use wasmi::{Externals, RuntimeArgs, RuntimeValue, Trap};
 
 use casper_types::{
@@ -14,7 +16,7 @@ use casper_types::{
 
 use super::{args::Args, scoped_instrumenter::ScopedInstrumenter, Error, Runtime};
 use crate::{
-    core::resolvers::v1_function_index::FunctionIndex,
+    core::{engine_state::TraceEntry, resolvers::v1_function_index::FunctionIndex},
     shared::{
         gas::Gas,
         host_function_costs::{Cost, HostFunction, DEFAULT_HOST_FUNCTION_NEW_DICTIONARY},
@@ -36,6 +38,14 @@ where
         let func = FunctionIndex::try_from(index).expect("unknown function index");
         let mut scoped_instrumenter = ScopedInstrumenter::new(func);
 
+        // Calls to `gas` are traced as gas charges.
+        if func != FunctionIndex::GasFuncIndex {
+            self.context.record_trace(|| TraceEntry::HostFunctionCall {
+                name: format!("{:?}", func),
+                args: args.as_ref().iter().map(trace_arg).collect(),
+            });
+        }
+
         let host_function_costs = self
             .protocol_data()
             .wasm_config()
@@ -1046,6 +1056,160 @@ where
                 let ret = self.load_call_stack(call_stack_len_ptr, result_size_ptr)?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
+            FunctionIndex::EmitEventFuncIndex => {
+                // args(0) = pointer to topic bytes in Wasm memory
+                // args(1) = size of topic bytes in Wasm memory
+                // args(2) = pointer to serialized payload in Wasm memory
+                // args(3) = size of serialized payload in Wasm memory
+                let (topic_ptr, topic_size, value_ptr, value_size): (_, u32, _, u32) =
+                    Args::parse(args)?;
+                self.charge_host_function_call(
+                    &host_function_costs.emit_event,
+                    [topic_ptr, topic_size, value_ptr, value_size],
+                )?;
+                scoped_instrumenter.add_property("topic_size", topic_size);
+                scoped_instrumenter.add_property("value_size", value_size);
+                let ret = self.emit_event(topic_ptr, topic_size, value_ptr, value_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::VerifySignatureFuncIndex => {
+                // args(0) = pointer to serialized public key in Wasm memory
+                // args(1) = size of serialized public key in Wasm memory
+                // args(2) = pointer to message bytes in Wasm memory
+                // args(3) = size of message bytes in Wasm memory
+                // args(4) = pointer to serialized signature in Wasm memory
+                // args(5) = size of serialized signature in Wasm memory
+                let (
+                    public_key_ptr,
+                    public_key_size,
+                    message_ptr,
+                    message_size,
+                    signature_ptr,
+                    signature_size,
+                ): (_, _, _, u32, _, _) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    &host_function_costs.verify_signature,
+                    [
+                        public_key_ptr,
+                        public_key_size,
+                        message_ptr,
+                        message_size,
+                        signature_ptr,
+                        signature_size,
+                    ],
+                )?;
+                scoped_instrumenter.add_property("message_size", message_size);
+                let is_valid = self.verify_signature(
+                    public_key_ptr,
+                    public_key_size,
+                    message_ptr,
+                    message_size,
+                    signature_ptr,
+                    signature_size,
+                )?;
+                Ok(Some(RuntimeValue::I32(i32::from(is_valid))))
+            }
+
+            FunctionIndex::RecoverSecp256k1FuncIndex => {
+                // args(0) = pointer to message bytes in Wasm memory
+                // args(1) = size of message bytes in Wasm memory
+                // args(2) = pointer to serialized signature in Wasm memory
+                // args(3) = size of serialized signature in Wasm memory
+                // args(4) = recovery id
+                // args(5) = pointer to output buffer for the serialized public key
+                // args(6) = size of output buffer
+                let (
+                    message_ptr,
+                    message_size,
+                    signature_ptr,
+                    signature_size,
+                    recovery_id,
+                    out_ptr,
+                    out_size,
+                ): (_, u32, _, _, u32, _, _) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    &host_function_costs.recover_secp256k1,
+                    [
+                        message_ptr,
+                        message_size,
+                        signature_ptr,
+                        signature_size,
+                        recovery_id,
+                        out_ptr,
+                        out_size,
+                    ],
+                )?;
+                scoped_instrumenter.add_property("message_size", message_size);
+                let ret = self.recover_secp256k1(
+                    message_ptr,
+                    message_size,
+                    signature_ptr,
+                    signature_size,
+                    recovery_id,
+                    out_ptr,
+                    out_size,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::Sha256FuncIndex => {
+                // args(0) = pointer to input bytes in Wasm memory
+                // args(1) = size of input bytes in Wasm memory
+                // args(2) = pointer to output buffer for the digest
+                // args(3) = size of output buffer
+                let (in_ptr, in_size, out_ptr, out_size) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    &host_function_costs.sha256,
+                    [in_ptr, in_size, out_ptr, out_size],
+                )?;
+                scoped_instrumenter.add_property("in_size", in_size.to_string());
+                scoped_instrumenter.add_property("out_size", out_size.to_string());
+                let input: Vec<u8> = self.bytes_from_mem(in_ptr, in_size as usize)?;
+                let digest = Sha256::digest(&input);
+                if digest.len() != out_size as usize {
+                    let err_value = u32::from(api_error::ApiError::BufferTooSmall) as i32;
+                    return Ok(Some(RuntimeValue::I32(err_value)));
+                }
+                self.memory
+                    .set(out_ptr, &digest)
+                    .map_err(|error| Error::Interpreter(error.into()))?;
+                Ok(Some(RuntimeValue::I32(0)))
+            }
+
+            FunctionIndex::Keccak256FuncIndex => {
+                // args(0) = pointer to input bytes in Wasm memory
+                // args(1) = size of input bytes in Wasm memory
+                // args(2) = pointer to output buffer for the digest
+                // args(3) = size of output buffer
+                let (in_ptr, in_size, out_ptr, out_size) = Args::parse(args)?;
+                self.charge_host_function_call(
+                    &host_function_costs.keccak256,
+                    [in_ptr, in_size, out_ptr, out_size],
+                )?;
+                scoped_instrumenter.add_property("in_size", in_size.to_string());
+                scoped_instrumenter.add_property("out_size", out_size.to_string());
+                let input: Vec<u8> = self.bytes_from_mem(in_ptr, in_size as usize)?;
+                let digest = Keccak256::digest(&input);
+                if digest.len() != out_size as usize {
+                    let err_value = u32::from(api_error::ApiError::BufferTooSmall) as i32;
+                    return Ok(Some(RuntimeValue::I32(err_value)));
+                }
+                self.memory
+                    .set(out_ptr, &digest)
+                    .map_err(|error| Error::Interpreter(error.into()))?;
+                Ok(Some(RuntimeValue::I32(0)))
+            }
         }
     }
 }
+
+/// Converts a Wasm argument of a host function call for inclusion in an execution trace.
+fn trace_arg(value: &RuntimeValue) -> i64 {
+    match *value {
+        RuntimeValue::I32(value) => value.into(),
+        RuntimeValue::I64(value) => value,
+        // Floating point instructions are rejected when preprocessing Wasm.
+        RuntimeValue::F32(_) | RuntimeValue::F64(_) => 0,
+    }
+}
@@ -1,6 +1,11 @@
+use num_rational::Ratio;
+
 use casper_types::{
     account::AccountHash,
-    system::handle_payment::{Error, HandlePayment, MintProvider, RuntimeProvider},
+    system::{
+        handle_payment::{Error, FeeHandling, HandlePayment, MintProvider, RuntimeProvider},
+        mint::StorageProvider,
+    },
     BlockTime, Key, Phase, TransferredTo, URef, U512,
 };
 
@@ -60,6 +65,25 @@ where
         self.get_balance(purse)
             .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::GetBalance))
     }
+
+    fn create_purse(&mut self) -> Result<URef, Error> {
+        Runtime::create_purse(self)
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::CreatePurse))
+    }
+
+    fn burn(&mut self, purse: URef, amount: U512) -> Result<(), Error> {
+        let balance = MintProvider::balance(self, purse)?.ok_or(Error::GetBalance)?;
+        let new_balance = balance
+            .checked_sub(amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+        StorageProvider::write_balance(self, purse, new_balance)
+            .map_err(|_| Error::FailedToBurnFees)?;
+        let mint_contract = self.get_mint_contract();
+        self.mint_reduce_total_supply(mint_contract, amount)
+            .map_err(|exec_error| {
+                <Option<Error>>::from(exec_error).unwrap_or(Error::FailedToBurnFees)
+            })
+    }
 }
 
 // TODO: Update RuntimeProvider to better handle errors
@@ -95,6 +119,14 @@ where
     fn get_caller(&self) -> AccountHash {
         self.context.get_caller()
     }
+
+    fn refund_ratio(&self) -> Ratio<u64> {
+        self.config.refund_ratio()
+    }
+
+    fn fee_handling(&self) -> FeeHandling {
+        self.config.fee_handling()
+    }
 }
 
 impl<'a, R> HandlePayment for Runtime<'a, R>
@@ -110,6 +110,12 @@ where
         }
     }
 
+    fn balance_exists(&mut self, uref: URef) -> Result<bool, Error> {
+        self.context
+            .contains_key_direct(&Key::Balance(uref.addr()))
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))
+    }
+
     fn write_balance(&mut self, uref: URef, balance: U512) -> Result<(), Error> {
         let cl_value = CLValue::from_t(balance).map_err(|_| Error::CLValue)?;
         self.context
@@ -24,6 +24,7 @@ use casper_types::{
         self, Contract, ContractPackage, ContractPackageStatus, ContractVersion, ContractVersions,
         DisabledVersions, EntryPoint, EntryPointAccess, EntryPoints, Group, Groups, NamedKeys,
     },
+    crypto,
     system::{
         self,
         auction::{self, Auction, EraInfo},
@@ -34,13 +35,16 @@ use casper_types::{
     },
     AccessRights, ApiError, CLType, CLTyped, CLValue, ContractHash, ContractPackageHash,
     ContractVersionKey, ContractWasm, DeployHash, EntryPointType, EraId, Key, NamedArg, Parameter,
-    Phase, ProtocolVersion, PublicKey, RuntimeArgs, Transfer, TransferResult, TransferredTo, URef,
-    DICTIONARY_ITEM_KEY_MAX_LENGTH, U128, U256, U512,
+    Phase, ProtocolVersion, PublicKey, RuntimeArgs, Signature, Transfer, TransferResult,
+    TransferredTo, URef, DICTIONARY_ITEM_KEY_MAX_LENGTH, EVENT_TOPIC_MAX_LENGTH, U128, U256, U512,
 };
 
 use crate::{
     core::{
-        engine_state::{system_contract_cache::SystemContractCache, EngineConfig},
+        engine_state::{
+            system_contract_cache::SystemContractCache, wasm_cache::WasmCache, EngineConfig,
+            TraceEntry,
+        },
         execution::{self, Error},
         resolvers::{create_module_resolver, memory_resolver::MemoryResolver},
         runtime::scoped_instrumenter::ScopedInstrumenter,
@@ -59,6 +63,7 @@ use crate::{
 
 pub struct Runtime<'a, R> {
     system_contract_cache: SystemContractCache,
+    wasm_cache: WasmCache,
     config: EngineConfig,
     memory: MemoryRef,
     module: Module,
@@ -952,6 +957,13 @@ fn extract_urefs(cl_value: &CLValue) -> Result<Vec<URef>, Error> {
         },
         // TODO: nested matches for Tuple3?
         CLType::Tuple3(_) => Ok(vec![]),
+        CLType::Struct { .. } | CLType::Enum { .. } => {
+            let mut urefs = vec![];
+            for member in runtime_context::named_type_members(cl_value)? {
+                urefs.extend(extract_urefs(&member)?);
+            }
+            Ok(urefs)
+        }
         CLType::Key => {
             let key: Key = cl_value.to_owned().into_t()?; // TODO: optimize?
             Ok(key.into_uref().into_iter().collect())
@@ -971,6 +983,7 @@ where
     pub fn new(
         config: EngineConfig,
         system_contract_cache: SystemContractCache,
+        wasm_cache: WasmCache,
         memory: MemoryRef,
         module: Module,
         context: RuntimeContext<'a, R>,
@@ -979,6 +992,7 @@ where
         Runtime {
             config,
             system_contract_cache,
+            wasm_cache,
             memory,
             module,
             host_buffer: None,
@@ -1411,6 +1425,7 @@ where
         let mut mint_runtime = Runtime::new(
             self.config,
             SystemContractCache::clone(&self.system_contract_cache),
+            WasmCache::clone(&self.wasm_cache),
             self.memory.clone(),
             self.module.clone(),
             mint_context,
@@ -1558,6 +1573,7 @@ where
         let mut runtime = Runtime::new(
             self.config,
             SystemContractCache::clone(&self.system_contract_cache),
+            WasmCache::clone(&self.wasm_cache),
             self.memory.clone(),
             self.module.clone(),
             runtime_context,
@@ -1687,6 +1703,7 @@ where
         let mut runtime = Runtime::new(
             self.config,
             SystemContractCache::clone(&self.system_contract_cache),
+            WasmCache::clone(&self.wasm_cache),
             self.memory.clone(),
             self.module.clone(),
             runtime_context,
@@ -1760,6 +1777,22 @@ where
                 CLValue::from_t(result).map_err(Self::reverter)
             })(),
 
+            auction::METHOD_REDELEGATE => (|| {
+                runtime.charge_system_contract_call(auction_costs.redelegate)?;
+
+                let delegator = Self::get_named_argument(runtime_args, auction::ARG_DELEGATOR)?;
+                let validator = Self::get_named_argument(runtime_args, auction::ARG_VALIDATOR)?;
+                let amount = Self::get_named_argument(runtime_args, auction::ARG_AMOUNT)?;
+                let new_validator =
+                    Self::get_named_argument(runtime_args, auction::ARG_NEW_VALIDATOR)?;
+
+                let result = runtime
+                    .redelegate(delegator, validator, amount, new_validator)
+                    .map_err(Self::reverter)?;
+
+                CLValue::from_t(result).map_err(Self::reverter)
+            })(),
+
             auction::METHOD_RUN_AUCTION => (|| {
                 runtime.charge_system_contract_call(auction_costs.run_auction)?;
 
@@ -2037,6 +2070,12 @@ where
         entry_point: EntryPoint,
         protocol_version: ProtocolVersion,
     ) -> Result<CLValue, Error> {
+        self.context.record_trace(|| TraceEntry::ContractCall {
+            contract_hash,
+            entry_point: entry_point.name().to_string(),
+            call_stack: self.call_stack.clone(),
+        });
+
         // Check for major version compatibility before calling
         if !contract.is_compatible_protocol_version(protocol_version) {
             return Err(Error::IncompatibleProtocolMajorVersion {
@@ -2131,7 +2170,12 @@ where
             };
             match maybe_module {
                 Some(module) => module,
-                None => parity_wasm::deserialize_buffer(contract_wasm.bytes())?,
+                None => self.wasm_cache.get_or_try_insert_with(
+                    contract.contract_wasm_hash(),
+                    protocol_version,
+                    self.protocol_data().wasm_config(),
+                    || parity_wasm::deserialize_buffer(contract_wasm.bytes()),
+                )?,
             }
         };
 
@@ -2153,11 +2197,13 @@ where
 
         let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
 
+        let wasm_cache = WasmCache::clone(&self.wasm_cache);
+
         let config = self.config;
 
         let host_buffer = None;
 
-        let context = RuntimeContext::new(
+        let mut context = RuntimeContext::new(
             self.context.state(),
             entry_point.entry_point_type(),
             &mut named_keys,
@@ -2179,6 +2225,9 @@ where
             *self.context.protocol_data(),
             self.context.transfers().to_owned(),
         );
+        // The events emitted so far are handed to the sub-call and taken back once it returns, so
+        // that the limit on the number of events applies to the deploy as a whole.
+        context.events_mut().append(self.context.events_mut());
 
         let mut call_stack = self.call_stack.to_owned();
 
@@ -2197,6 +2246,7 @@ where
 
         let mut runtime = Runtime {
             system_contract_cache,
+            wasm_cache,
             config,
             memory,
             module,
@@ -2217,6 +2267,10 @@ where
             *transfers = runtime.context.transfers().to_owned();
         }
 
+        self.context
+            .events_mut()
+            .append(runtime.context.events_mut());
+
         let error = match result {
             Err(error) => error,
             // If `Ok` and the `host_buffer` is `None`, the contract's execution succeeded but did
@@ -2652,7 +2706,14 @@ where
             return Err(Error::InvalidContext);
         }
 
-        if self.context.phase() != Phase::Session {
+        // Transfers made while finalizing payment are only recorded if refunds or fees are handled
+        // differently from the default, so that execution results are otherwise unchanged.
+        let is_recorded = match self.context.phase() {
+            Phase::Session => true,
+            Phase::FinalizePayment => !self.config.has_default_payment_handling(),
+            _ => false,
+        };
+        if !is_recorded {
             return Ok(());
         }
 
@@ -2850,6 +2911,32 @@ where
         self.context.protocol_data().handle_payment()
     }
 
+    /// Returns the accumulation purse of the handle payment contract, creating it if it doesn't
+    /// exist yet.
+    fn get_or_create_accumulation_purse(&mut self) -> Result<URef, Error> {
+        let handle_payment_key = Key::from(self.get_handle_payment_contract());
+        let handle_payment_contract = match self.context.read_gs_direct(&handle_payment_key)? {
+            Some(StoredValue::Contract(contract)) => contract,
+            Some(_) => return Err(Error::UnexpectedStoredValueVariant),
+            None => return Err(Error::KeyNotFound(handle_payment_key)),
+        };
+        if let Some(Key::URef(accumulation_purse)) = handle_payment_contract
+            .named_keys()
+            .get(handle_payment::ACCUMULATION_PURSE_KEY)
+        {
+            return Ok(*accumulation_purse);
+        }
+
+        let accumulation_purse = self.create_purse()?;
+        let named_key = CLValue::from_t((
+            handle_payment::ACCUMULATION_PURSE_KEY.to_string(),
+            Key::URef(accumulation_purse),
+        ))?;
+        self.context
+            .metered_add_gs_unsafe(handle_payment_key, StoredValue::CLValue(named_key))?;
+        Ok(accumulation_purse)
+    }
+
     /// Looks up the public standard payment contract key in the context's protocol data.
     ///
     /// Returned URef is already attenuated depending on the calling account.
@@ -3589,6 +3676,77 @@ where
         }
         Ok(Ok(()))
     }
+
+    /// Records an event with the given `topic` and `value` emitted by the calling contract.
+    fn emit_event(
+        &mut self,
+        topic_ptr: u32,
+        topic_size: u32,
+        value_ptr: u32,
+        value_size: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let topic_bytes = self.bytes_from_mem(topic_ptr, topic_size as usize)?;
+        if topic_bytes.is_empty() || topic_bytes.len() > EVENT_TOPIC_MAX_LENGTH {
+            return Ok(Err(ApiError::InvalidArgument));
+        }
+        let topic = match String::from_utf8(topic_bytes) {
+            Ok(topic) => topic,
+            Err(_) => return Ok(Err(ApiError::InvalidArgument)),
+        };
+        let payload = self.cl_value_from_mem(value_ptr, value_size)?;
+        self.context.emit_event(topic, payload)?;
+        Ok(Ok(()))
+    }
+
+    /// Checks whether `signature` over the given message was produced by the secret key
+    /// corresponding to `public_key`.
+    fn verify_signature(
+        &mut self,
+        public_key_ptr: u32,
+        public_key_size: u32,
+        message_ptr: u32,
+        message_size: u32,
+        signature_ptr: u32,
+        signature_size: u32,
+    ) -> Result<bool, Trap> {
+        let public_key: PublicKey = self.t_from_mem(public_key_ptr, public_key_size)?;
+        let message = self.bytes_from_mem(message_ptr, message_size as usize)?;
+        let signature: Signature = self.t_from_mem(signature_ptr, signature_size)?;
+        Ok(crypto::verify(message, &signature, &public_key).is_ok())
+    }
+
+    /// Recovers the secp256k1 public key which produced `signature` over the given message and
+    /// writes it, serialized, into the output buffer.
+    #[allow(clippy::too_many_arguments)]
+    fn recover_secp256k1(
+        &mut self,
+        message_ptr: u32,
+        message_size: u32,
+        signature_ptr: u32,
+        signature_size: u32,
+        recovery_id: u32,
+        out_ptr: u32,
+        out_size: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let message = self.bytes_from_mem(message_ptr, message_size as usize)?;
+        let signature: Signature = self.t_from_mem(signature_ptr, signature_size)?;
+        let recovery_id = match u8::try_from(recovery_id) {
+            Ok(recovery_id) => recovery_id,
+            Err(_) => return Ok(Err(ApiError::InvalidArgument)),
+        };
+        let public_key = match crypto::recover_secp256k1(message, &signature, recovery_id) {
+            Ok(public_key) => public_key,
+            Err(_) => return Ok(Err(ApiError::InvalidArgument)),
+        };
+        let public_key_bytes = public_key.to_bytes().map_err(Error::BytesRepr)?;
+        if public_key_bytes.len() != out_size as usize {
+            return Ok(Err(ApiError::BufferTooSmall));
+        }
+        self.memory
+            .set(out_ptr, &public_key_bytes)
+            .map_err(|error| Error::Interpreter(error.into()))?;
+        Ok(Ok(()))
+    }
 }
 
 #[cfg(test)]
@@ -3603,7 +3761,10 @@ mod tests {
         result,
     };
 
-    use casper_types::{gens::*, AccessRights, CLType, CLValue, Key, PublicKey, SecretKey, URef};
+    use casper_types::{
+        bytesrepr::ToBytes, gens::*, AccessRights, CLType, CLValue, Key, PublicKey, SecretKey,
+        URef, U512,
+    };
 
     use super::extract_urefs;
 
@@ -3635,7 +3796,9 @@ mod tests {
                 | CLType::Tuple2(_)
                 | CLType::Tuple3(_)
                 | CLType::PublicKey
-                | CLType::Any => (),
+                | CLType::Any
+                | CLType::Struct { .. }
+                | CLType::Enum { .. } => (),
             }
         };
 
@@ -3769,4 +3932,45 @@ mod tests {
         let cl_value = CLValue::from_t(map).unwrap();
         assert_eq!(extract_urefs(&cl_value).unwrap(), vec![uref]);
     }
+
+    #[test]
+    fn extract_from_struct_fields() {
+        let purse = URef::new([43; 32], AccessRights::READ_ADD_WRITE);
+        let key = Key::from(URef::new([44; 32], AccessRights::READ));
+        let cl_type = CLType::Struct {
+            name: "Holder".to_string(),
+            fields: vec![
+                ("purse".to_string(), CLType::URef),
+                ("amounts".to_string(), CLType::List(Box::new(CLType::U512))),
+                ("key".to_string(), CLType::Option(Box::new(CLType::Key))),
+            ],
+        };
+        let mut bytes = purse.to_bytes().unwrap();
+        bytes.extend(vec![U512::one(), U512::MAX].to_bytes().unwrap());
+        bytes.extend(Some(key).to_bytes().unwrap());
+        let cl_value = CLValue::from_components(cl_type, bytes);
+        assert_eq!(
+            extract_urefs(&cl_value).unwrap(),
+            vec![purse, *key.as_uref().unwrap()]
+        );
+    }
+
+    #[test]
+    fn extract_from_enum_variant() {
+        let uref = URef::new([43; 32], AccessRights::READ_ADD_WRITE);
+        let cl_type = CLType::Enum {
+            name: "Target".to_string(),
+            variants: vec![
+                ("Nothing".to_string(), CLType::Unit),
+                ("Purse".to_string(), CLType::URef),
+            ],
+        };
+        let cl_value = CLValue::from_components(cl_type.clone(), vec![0]);
+        assert!(extract_urefs(&cl_value).unwrap().is_empty());
+
+        let mut bytes = vec![1];
+        bytes.extend(uref.to_bytes().unwrap());
+        let cl_value = CLValue::from_components(cl_type, bytes);
+        assert_eq!(extract_urefs(&cl_value).unwrap(), vec![uref]);
+    }
 }
@@ -147,6 +147,11 @@ impl Drop for ScopedInstrumenter {
             FunctionIndex::DictionaryGetFuncIndex => "host_dictionary_get",
             FunctionIndex::DictionaryPutFuncIndex => "host_dictionary_put",
             FunctionIndex::LoadCallStack => "host_load_call_stack",
+            FunctionIndex::EmitEventFuncIndex => "host_emit_event",
+            FunctionIndex::VerifySignatureFuncIndex => "host_verify_signature",
+            FunctionIndex::RecoverSecp256k1FuncIndex => "host_recover_secp256k1",
+            FunctionIndex::Sha256FuncIndex => "host_sha256",
+            FunctionIndex::Keccak256FuncIndex => "host_keccak256",
         };
 
         let mut properties = mem::take(&mut self.properties);
@@ -11,19 +11,21 @@ use casper_types::{
         AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetThresholdFailure,
         UpdateKeyFailure, Weight,
     },
-    bytesrepr,
-    bytesrepr::ToBytes,
+    bytesrepr::{
+        self, FromBytes, ToBytes, OPTION_NONE_TAG, OPTION_SOME_TAG, RESULT_ERR_TAG, RESULT_OK_TAG,
+    },
     contracts::NamedKeys,
     system::auction::EraInfo,
-    AccessRights, BlockTime, CLType, CLValue, Contract, ContractPackage, ContractPackageHash,
-    DeployHash, DeployInfo, EntryPointAccess, EntryPointType, Key, KeyTag, Phase, ProtocolVersion,
-    PublicKey, RuntimeArgs, Transfer, TransferAddr, URef, DICTIONARY_ITEM_KEY_MAX_LENGTH,
-    KEY_HASH_LENGTH,
+    AccessRights, BlockTime, CLType, CLValue, Contract, ContractEvent, ContractHash,
+    ContractPackage, ContractPackageHash, DeployHash, DeployInfo, EntryPointAccess, EntryPointType,
+    Key, KeyTag, Phase, ProtocolVersion, PublicKey, RuntimeArgs, Transfer, TransferAddr, URef,
+    DICTIONARY_ITEM_KEY_MAX_LENGTH, EVENT_PAYLOAD_MAX_LENGTH, KEY_HASH_LENGTH,
+    MAX_EVENTS_PER_DEPLOY, U128, U256, U512,
 };
 
 use crate::{
     core::{
-        engine_state::execution_effect::ExecutionEffect,
+        engine_state::{execution_effect::ExecutionEffect, execution_trace::TraceEntry},
         execution::{AddressGenerator, Error},
         runtime_context::dictionary::DictionaryValue,
         tracking_copy::{AddResult, TrackingCopy},
@@ -81,6 +83,129 @@ pub fn validate_entry_point_access_with(
     Ok(())
 }
 
+/// Returns the values of the fields of a struct, or the value held by the active variant of an
+/// enum, so that they can be inspected like any other `CLValue`.  Returns no values for other
+/// types.
+pub(crate) fn named_type_members(cl_value: &CLValue) -> Result<Vec<CLValue>, Error> {
+    match cl_value.cl_type() {
+        CLType::Struct { fields, .. } => {
+            let mut members = Vec::with_capacity(fields.len());
+            let mut stream = cl_value.inner_bytes().as_slice();
+            for (_name, field_cl_type) in fields {
+                let remainder = skip_serialized_value(field_cl_type, stream)?;
+                let (field_bytes, _) = stream.split_at(stream.len() - remainder.len());
+                members.push(CLValue::from_components(
+                    field_cl_type.clone(),
+                    field_bytes.to_vec(),
+                ));
+                stream = remainder;
+            }
+            Ok(members)
+        }
+        CLType::Enum { variants, .. } => {
+            let (index, remainder) = u8::from_bytes(cl_value.inner_bytes())?;
+            let (_name, variant_cl_type) = variants
+                .get(index as usize)
+                .ok_or(bytesrepr::Error::Formatting)?;
+            Ok(vec![CLValue::from_components(
+                variant_cl_type.clone(),
+                remainder.to_vec(),
+            )])
+        }
+        _ => Ok(vec![]),
+    }
+}
+
+/// Returns the bytes following the serialized value of the given type at the start of `bytes`.
+fn skip_serialized_value<'a>(
+    cl_type: &CLType,
+    bytes: &'a [u8],
+) -> Result<&'a [u8], bytesrepr::Error> {
+    fn skip<T: FromBytes>(bytes: &[u8]) -> Result<&[u8], bytesrepr::Error> {
+        T::from_bytes(bytes).map(|(_, remainder)| remainder)
+    }
+
+    match cl_type {
+        CLType::Bool => skip::<bool>(bytes),
+        CLType::I32 => skip::<i32>(bytes),
+        CLType::I64 => skip::<i64>(bytes),
+        CLType::U8 => skip::<u8>(bytes),
+        CLType::U32 => skip::<u32>(bytes),
+        CLType::U64 => skip::<u64>(bytes),
+        CLType::U128 => skip::<U128>(bytes),
+        CLType::U256 => skip::<U256>(bytes),
+        CLType::U512 => skip::<U512>(bytes),
+        CLType::Unit => skip::<()>(bytes),
+        CLType::String => skip::<String>(bytes),
+        CLType::Key => skip::<Key>(bytes),
+        CLType::URef => skip::<URef>(bytes),
+        CLType::PublicKey => skip::<PublicKey>(bytes),
+        CLType::Option(inner_cl_type) => {
+            let (tag, remainder) = u8::from_bytes(bytes)?;
+            match tag {
+                OPTION_NONE_TAG => Ok(remainder),
+                OPTION_SOME_TAG => skip_serialized_value(inner_cl_type, remainder),
+                _ => Err(bytesrepr::Error::Formatting),
+            }
+        }
+        CLType::List(inner_cl_type) => {
+            let (count, mut stream) = u32::from_bytes(bytes)?;
+            for _ in 0..count {
+                stream = skip_serialized_value(inner_cl_type, stream)?;
+            }
+            Ok(stream)
+        }
+        CLType::ByteArray(length) => {
+            let length = *length as usize;
+            if bytes.len() < length {
+                return Err(bytesrepr::Error::EarlyEndOfStream);
+            }
+            Ok(&bytes[length..])
+        }
+        CLType::Result { ok, err } => {
+            let (tag, remainder) = u8::from_bytes(bytes)?;
+            match tag {
+                RESULT_ERR_TAG => skip_serialized_value(err, remainder),
+                RESULT_OK_TAG => skip_serialized_value(ok, remainder),
+                _ => Err(bytesrepr::Error::Formatting),
+            }
+        }
+        CLType::Map { key, value } => {
+            let (count, mut stream) = u32::from_bytes(bytes)?;
+            for _ in 0..count {
+                stream = skip_serialized_value(key, stream)?;
+                stream = skip_serialized_value(value, stream)?;
+            }
+            Ok(stream)
+        }
+        CLType::Tuple1(cl_types) => skip_serialized_values(cl_types.iter().map(Box::as_ref), bytes),
+        CLType::Tuple2(cl_types) => skip_serialized_values(cl_types.iter().map(Box::as_ref), bytes),
+        CLType::Tuple3(cl_types) => skip_serialized_values(cl_types.iter().map(Box::as_ref), bytes),
+        CLType::Struct { fields, .. } => {
+            skip_serialized_values(fields.iter().map(|(_name, cl_type)| cl_type), bytes)
+        }
+        CLType::Enum { variants, .. } => {
+            let (index, remainder) = u8::from_bytes(bytes)?;
+            let (_name, variant_cl_type) = variants
+                .get(index as usize)
+                .ok_or(bytesrepr::Error::Formatting)?;
+            skip_serialized_value(variant_cl_type, remainder)
+        }
+        // The length of a value of unspecified type is unknown.
+        CLType::Any => Err(bytesrepr::Error::Formatting),
+    }
+}
+
+fn skip_serialized_values<'a, 'b>(
+    cl_types: impl Iterator<Item = &'b CLType>,
+    mut bytes: &'a [u8],
+) -> Result<&'a [u8], bytesrepr::Error> {
+    for cl_type in cl_types {
+        bytes = skip_serialized_value(cl_type, bytes)?;
+    }
+    Ok(bytes)
+}
+
 /// Holds information specific to the deployed contract.
 pub struct RuntimeContext<'a, R> {
     tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
@@ -108,6 +233,7 @@ pub struct RuntimeContext<'a, R> {
     protocol_data: ProtocolData,
     entry_point_type: EntryPointType,
     transfers: Vec<TransferAddr>,
+    events: Vec<ContractEvent>,
 }
 
 impl<'a, R> RuntimeContext<'a, R>
@@ -159,6 +285,7 @@ where
             phase,
             protocol_data,
             transfers,
+            events: Vec::new(),
         }
     }
 
@@ -415,6 +542,16 @@ where
             .map_err(Into::into)
     }
 
+    /// Returns `true` if a value is stored under `key`, without reading the value itself.
+    ///
+    /// DO NOT EXPOSE THIS VIA THE FFI
+    pub fn contains_key_direct(&mut self, key: &Key) -> Result<bool, Error> {
+        self.tracking_copy
+            .borrow_mut()
+            .contains_key(self.correlation_id, key)
+            .map_err(Into::into)
+    }
+
     /// This method is a wrapper over `read_gs` in the sense that it extracts the type held by a
     /// `StoredValue` stored in the global state in a type safe manner.
     ///
@@ -533,6 +670,38 @@ where
         &mut self.transfers
     }
 
+    pub fn events(&self) -> &Vec<ContractEvent> {
+        &self.events
+    }
+
+    pub fn events_mut(&mut self) -> &mut Vec<ContractEvent> {
+        &mut self.events
+    }
+
+    /// Records an event emitted by the currently executing stored contract.
+    ///
+    /// Only stored contracts may emit events, as each event is attributed to the emitting
+    /// contract's hash.  Payloads are limited to [`EVENT_PAYLOAD_MAX_LENGTH`] bytes, and at most
+    /// [`MAX_EVENTS_PER_DEPLOY`] events can be emitted while executing a deploy.
+    pub fn emit_event(&mut self, topic: String, payload: CLValue) -> Result<(), Error> {
+        let contract_hash = match self.base_key {
+            Key::Hash(contract_hash) => ContractHash::new(contract_hash),
+            _ => return Err(Error::InvalidContext),
+        };
+        if payload.serialized_length() > EVENT_PAYLOAD_MAX_LENGTH {
+            return Err(Error::EventPayloadExceedsLength);
+        }
+        if self.events.len() >= MAX_EVENTS_PER_DEPLOY {
+            return Err(Error::TooManyEvents);
+        }
+        self.events.push(ContractEvent {
+            contract_hash,
+            topic,
+            payload,
+        });
+        Ok(())
+    }
+
     fn validate_cl_value(&self, cl_value: &CLValue) -> Result<(), Error> {
         match cl_value.cl_type() {
             CLType::Bool
@@ -555,6 +724,9 @@ where
             | CLType::Tuple3(_)
             | CLType::Any
             | CLType::PublicKey => Ok(()),
+            CLType::Struct { .. } | CLType::Enum { .. } => named_type_members(cl_value)?
+                .iter()
+                .try_for_each(|member| self.validate_cl_value(member)),
             CLType::Key => {
                 let key: Key = cl_value.to_owned().into_t()?; // TODO: optimize?
                 self.validate_key(&key)
@@ -739,7 +911,7 @@ where
         let prev = self.gas_counter();
         let gas_limit = self.gas_limit();
         // gas charge overflow protection
-        match prev.checked_add(amount) {
+        let result = match prev.checked_add(amount) {
             None => {
                 self.set_gas_counter(gas_limit);
                 Err(Error::GasLimit)
@@ -752,7 +924,17 @@ where
                 self.set_gas_counter(val);
                 Ok(())
             }
-        }
+        };
+        self.record_trace(|| TraceEntry::GasCharge {
+            amount: amount.value(),
+            gas_used: self.gas_counter().value(),
+        });
+        result
+    }
+
+    /// Records the entry returned by `entry` in the execution trace if tracing is enabled.
+    pub(crate) fn record_trace<F: FnOnce() -> TraceEntry>(&self, entry: F) {
+        self.tracking_copy.borrow().record_trace(entry)
     }
 
     /// Checks if we are calling a system contract.
@@ -14,8 +14,8 @@ use casper_types::{
     },
     bytesrepr::ToBytes,
     contracts::NamedKeys,
-    AccessRights, BlockTime, CLValue, Contract, DeployHash, EntryPointType, EntryPoints, Key,
-    Phase, ProtocolVersion, RuntimeArgs, URef, KEY_HASH_LENGTH, U512,
+    AccessRights, BlockTime, CLType, CLValue, Contract, DeployHash, EntryPointType, EntryPoints,
+    Key, Phase, ProtocolVersion, RuntimeArgs, URef, KEY_HASH_LENGTH, U512,
 };
 
 use super::{Address, Error, RuntimeContext};
@@ -220,6 +220,67 @@ fn use_uref_forged() {
     assert_forged_reference(query_result);
 }
 
+fn struct_holding_uref(uref: URef) -> CLValue {
+    let cl_type = CLType::Struct {
+        name: "Holder".to_string(),
+        fields: vec![
+            ("name".to_string(), CLType::String),
+            ("purse".to_string(), CLType::URef),
+        ],
+    };
+    let mut bytes = "holder".to_string().to_bytes().unwrap();
+    bytes.extend(uref.to_bytes().unwrap());
+    CLValue::from_components(cl_type, bytes)
+}
+
+fn enum_holding_key(key: Key) -> CLValue {
+    let cl_type = CLType::Enum {
+        name: "Target".to_string(),
+        variants: vec![
+            ("Nothing".to_string(), CLType::Unit),
+            ("Something".to_string(), CLType::Key),
+        ],
+    };
+    let mut bytes = vec![1];
+    bytes.extend(key.to_bytes().unwrap());
+    CLValue::from_components(cl_type, bytes)
+}
+
+#[test]
+fn use_struct_holding_valid_uref() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let uref_key = create_uref(&mut rng, AccessRights::READ_WRITE);
+    let held_uref_key = create_uref(&mut rng, AccessRights::READ);
+    let access_rights = extract_access_rights_from_keys(vec![uref_key, held_uref_key]);
+    let value = StoredValue::CLValue(struct_holding_uref(*held_uref_key.as_uref().unwrap()));
+    let query_result = test(access_rights, |mut rc| rc.metered_write_gs(uref_key, value));
+    query_result.expect("writing a struct holding a valid uref should succeed");
+}
+
+#[test]
+fn use_struct_holding_forged_uref() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let uref_key = create_uref(&mut rng, AccessRights::READ_WRITE);
+    let forged_uref_key = create_uref(&mut rng, AccessRights::READ);
+    let access_rights = extract_access_rights_from_keys(vec![uref_key]);
+    let value = StoredValue::CLValue(struct_holding_uref(*forged_uref_key.as_uref().unwrap()));
+    let query_result = test(access_rights, |mut rc| rc.metered_write_gs(uref_key, value));
+
+    assert_forged_reference(query_result);
+}
+
+#[test]
+fn use_enum_holding_forged_uref() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let uref_key = create_uref(&mut rng, AccessRights::READ_WRITE);
+    let forged_uref_key = create_uref(&mut rng, AccessRights::READ);
+    let access_rights = extract_access_rights_from_keys(vec![uref_key]);
+    let value = StoredValue::CLValue(enum_holding_key(forged_uref_key));
+    let query_result = test(access_rights, |mut rc| rc.metered_write_gs(uref_key, value));
+
+    assert_forged_reference(query_result);
+}
+
 #[test]
 fn account_key_not_writeable() {
     let mut rng = rand::thread_rng();
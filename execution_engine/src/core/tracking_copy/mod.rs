@@ -5,9 +5,11 @@ pub(self) mod meter;
 mod tests;
 
 use std::{
+    cell::RefCell,
     collections::{BTreeSet, HashMap, HashSet, VecDeque},
     convert::{From, TryInto},
     iter,
+    rc::Rc,
 };
 
 use linked_hash_map::LinkedHashMap;
@@ -19,7 +21,11 @@ pub use self::ext::TrackingCopyExt;
 use self::meter::{heap_meter::HeapSize, Meter};
 use crate::{
     core::{
-        engine_state::{execution_effect::ExecutionEffect, op::Op},
+        engine_state::{
+            execution_effect::ExecutionEffect,
+            execution_trace::{ExecutionTrace, TraceEntry},
+            op::Op,
+        },
         runtime_context::dictionary,
     },
     shared::{
@@ -209,11 +215,50 @@ impl<M: Meter<Key, StoredValue>> TrackingCopyCache<M> {
     }
 }
 
+/// The keys, and prefixes of keys, read from the underlying state by a `TrackingCopy` and its
+/// forks.
+///
+/// Keys are recorded in their normalized form.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReadSet {
+    keys: BTreeSet<Key>,
+    keys_checked_for_existence: BTreeSet<Key>,
+    key_prefixes: BTreeSet<Vec<u8>>,
+}
+
+impl ReadSet {
+    /// Returns the keys whose values were read.
+    pub fn keys(&self) -> &BTreeSet<Key> {
+        &self.keys
+    }
+
+    /// Returns the keys which were only checked for the presence and type of their value, e.g.
+    /// before adding to them, and whose values were not read otherwise.
+    pub fn keys_checked_for_existence(&self) -> BTreeSet<Key> {
+        self.keys_checked_for_existence
+            .difference(&self.keys)
+            .copied()
+            .collect()
+    }
+
+    /// Returns the serialized key prefixes which were used to list keys.
+    pub fn key_prefixes(&self) -> &BTreeSet<Vec<u8>> {
+        &self.key_prefixes
+    }
+}
+
 pub struct TrackingCopy<R> {
     reader: R,
     cache: TrackingCopyCache<HeapSize>,
     ops: AdditiveMap<Key, Op>,
     fns: AdditiveMap<Key, Transform>,
+    /// Unlike `ops`, this is also updated by reads of missing keys and by reads of forks, and is
+    /// never rolled back.
+    read_set: RefCell<ReadSet>,
+    /// The execution trace shared with forks, or `None` if tracing is disabled.
+    trace: Option<Rc<RefCell<ExecutionTrace>>>,
+    /// The maximum number of entries recorded in the execution trace.
+    max_trace_entries: usize,
 }
 
 #[derive(Debug)]
@@ -247,6 +292,9 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
              * limit? */
             ops: AdditiveMap::new(),
             fns: AdditiveMap::new(),
+            read_set: RefCell::new(ReadSet::default()),
+            trace: None,
+            max_trace_entries: 0,
         }
     }
 
@@ -254,6 +302,31 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         &self.reader
     }
 
+    /// Returns the keys read from the underlying state by this `TrackingCopy` and its forks.
+    pub fn read_set(&self) -> ReadSet {
+        self.read_set.borrow().clone()
+    }
+
+    /// Enables recording of an execution trace of at most `max_trace_entries` entries, which is
+    /// shared with forks created afterwards.
+    pub fn enable_tracing(&mut self, max_trace_entries: usize) {
+        self.trace.get_or_insert_with(Default::default);
+        self.max_trace_entries = max_trace_entries;
+    }
+
+    /// Returns the execution trace recorded by this `TrackingCopy` and its forks, or `None` if
+    /// tracing is disabled.
+    pub fn trace(&self) -> Option<ExecutionTrace> {
+        self.trace.as_ref().map(|trace| trace.borrow().clone())
+    }
+
+    /// Records the entry returned by `entry` in the execution trace if tracing is enabled.
+    pub fn record_trace<F: FnOnce() -> TraceEntry>(&self, entry: F) {
+        if let Some(trace) = &self.trace {
+            trace.borrow_mut().push(entry, self.max_trace_entries);
+        }
+    }
+
     /// Creates a new TrackingCopy, using this one (including its mutations) as
     /// the base state to read against. The intended use case for this
     /// function is to "snapshot" the current `TrackingCopy` and produce a
@@ -267,13 +340,59 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
     /// forking, however we recognize this is sub-optimal and will revisit
     /// in the future.
     pub fn fork(&self) -> TrackingCopy<&TrackingCopy<R>> {
-        TrackingCopy::new(self)
+        let mut fork = TrackingCopy::new(self);
+        fork.trace = self.trace.clone();
+        fork.max_trace_entries = self.max_trace_entries;
+        fork
     }
 
     pub fn get(
         &mut self,
         correlation_id: CorrelationId,
         key: &Key,
+    ) -> Result<Option<StoredValue>, R::Error> {
+        let normalized_key = key.normalize();
+        // A value written by this `TrackingCopy` doesn't depend on the underlying state, unlike
+        // one which was added to.
+        if !matches!(self.fns.get(&normalized_key), Some(Transform::Write(_))) {
+            self.read_set.get_mut().keys.insert(normalized_key);
+        }
+        self.get_unrecorded(correlation_id, key)
+    }
+
+    /// Returns `true` if a value is stored under `key`.
+    ///
+    /// Unlike `read()`, the key is recorded in the read set as only checked for existence.
+    pub fn contains_key(
+        &mut self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<bool, R::Error> {
+        let normalized_key = key.normalize();
+        self.record_trace(|| TraceEntry::Read {
+            key: normalized_key,
+        });
+        self.read_set
+            .get_mut()
+            .keys_checked_for_existence
+            .insert(normalized_key);
+        if self
+            .get_unrecorded(correlation_id, &normalized_key)?
+            .is_some()
+        {
+            self.ops.insert_add(normalized_key, Op::Read);
+            self.fns.insert_add(normalized_key, Transform::Identity);
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Returns the value under `key` without recording it in the read set.
+    fn get_unrecorded(
+        &mut self,
+        correlation_id: CorrelationId,
+        key: &Key,
     ) -> Result<Option<StoredValue>, R::Error> {
         if let Some(value) = self.cache.get(key) {
             return Ok(Some(value.to_owned()));
@@ -296,6 +415,10 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
             Some(keys) => ret.extend(keys),
             None => {
                 let key_tag = key_tag.to_owned();
+                self.read_set
+                    .get_mut()
+                    .key_prefixes
+                    .insert(vec![key_tag as u8]);
                 let keys = self
                     .reader
                     .keys_with_prefix(correlation_id, &[key_tag as u8])?;
@@ -315,6 +438,9 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         key: &Key,
     ) -> Result<Option<StoredValue>, R::Error> {
         let normalized_key = key.normalize();
+        self.record_trace(|| TraceEntry::Read {
+            key: normalized_key,
+        });
         if let Some(value) = self.get(correlation_id, &normalized_key)? {
             self.ops.insert_add(normalized_key, Op::Read);
             self.fns.insert_add(normalized_key, Transform::Identity);
@@ -326,6 +452,9 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
 
     pub fn write(&mut self, key: Key, value: StoredValue) {
         let normalized_key = key.normalize();
+        self.record_trace(|| TraceEntry::Write {
+            key: normalized_key,
+        });
         self.cache.insert_write(normalized_key, value.clone());
         self.ops.insert_add(normalized_key, Op::Write);
         self.fns.insert_add(normalized_key, Transform::Write(value));
@@ -342,7 +471,16 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         value: StoredValue,
     ) -> Result<AddResult, R::Error> {
         let normalized_key = key.normalize();
-        let current_value = match self.get(correlation_id, &normalized_key)? {
+        self.record_trace(|| TraceEntry::Add {
+            key: normalized_key,
+        });
+        // Adding depends only on the presence and type of the current value, so the key is
+        // recorded as checked for existence rather than read.
+        self.read_set
+            .get_mut()
+            .keys_checked_for_existence
+            .insert(normalized_key);
+        let current_value = match self.get_unrecorded(correlation_id, &normalized_key)? {
             None => return Ok(AddResult::KeyNotFound(normalized_key)),
             Some(current_value) => current_value,
         };
@@ -550,6 +688,7 @@ impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for &Tracki
         if let Some(value) = self.cache.muts_cached.get(key) {
             return Ok(Some(value.to_owned()));
         }
+        self.read_set.borrow_mut().keys.insert(key.normalize());
         if let Some(value) = self.reader.read(correlation_id, key)? {
             Ok(Some(value))
         } else {
@@ -562,6 +701,7 @@ impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for &Tracki
         correlation_id: CorrelationId,
         key: &Key,
     ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
+        self.read_set.borrow_mut().keys.insert(key.normalize());
         self.reader.read_with_proof(correlation_id, key)
     }
 
@@ -570,6 +710,10 @@ impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for &Tracki
         correlation_id: CorrelationId,
         prefix: &[u8],
     ) -> Result<Vec<Key>, Self::Error> {
+        self.read_set
+            .borrow_mut()
+            .key_prefixes
+            .insert(prefix.to_vec());
         self.reader.keys_with_prefix(correlation_id, prefix)
     }
 }
@@ -311,6 +311,85 @@ fn tracking_copy_aw() {
     assert_eq!(tc.ops.get(&k), Some(&Op::Write));
 }
 
+#[test]
+fn tracking_copy_read_set() {
+    let correlation_id = CorrelationId::new();
+    let (global_state, root_hash) = InMemoryGlobalState::from_pairs(correlation_id, &[]).unwrap();
+    let reader = global_state.checkout(root_hash).unwrap().unwrap();
+    let mut tc = TrackingCopy::new(reader);
+    let read_key = Key::Hash([1u8; 32]);
+    let written_key = Key::Hash([2u8; 32]);
+    let forked_read_key = Key::Hash([3u8; 32]);
+
+    // reads of missing keys are recorded, although they produce no op
+    assert_eq!(tc.read(correlation_id, &read_key).unwrap(), None);
+    assert!(tc.ops.is_empty());
+
+    let value = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+    tc.write(written_key, value);
+    let _ = tc.get_keys(correlation_id, &KeyTag::URef).unwrap();
+
+    // reads through a fork are recorded by the forked tracking copy, unless the fork reads a value
+    // written by it
+    {
+        let mut fork = tc.fork();
+        let _ = fork.read(correlation_id, &written_key).unwrap();
+        let _ = fork.read(correlation_id, &forked_read_key).unwrap();
+    }
+
+    let read_set = tc.read_set();
+    assert_eq!(
+        read_set.keys().iter().copied().collect::<Vec<_>>(),
+        vec![read_key, forked_read_key]
+    );
+    assert_eq!(
+        read_set.key_prefixes().iter().cloned().collect::<Vec<_>>(),
+        vec![vec![KeyTag::URef as u8]]
+    );
+}
+
+#[test]
+fn tracking_copy_read_set_existence_checks() {
+    let correlation_id = CorrelationId::new();
+    let added_key = Key::Hash([1u8; 32]);
+    let checked_key = Key::Hash([2u8; 32]);
+    let added_and_read_key = Key::Hash([3u8; 32]);
+    let value = StoredValue::CLValue(CLValue::from_t(U512::one()).unwrap());
+    let (global_state, root_hash) = InMemoryGlobalState::from_pairs(
+        correlation_id,
+        &[
+            (added_key, value.clone()),
+            (checked_key, value.clone()),
+            (added_and_read_key, value.clone()),
+        ],
+    )
+    .unwrap();
+    let reader = global_state.checkout(root_hash).unwrap().unwrap();
+    let mut tc = TrackingCopy::new(reader);
+
+    // adding to a key only checks for its existence
+    let _ = tc.add(correlation_id, added_key, value.clone()).unwrap();
+    assert!(tc.contains_key(correlation_id, &checked_key).unwrap());
+    assert_eq!(tc.ops.get(&checked_key), Some(&Op::Read));
+
+    // reading a value which was added to depends on the underlying state
+    let _ = tc.add(correlation_id, added_and_read_key, value).unwrap();
+    let _ = tc.read(correlation_id, &added_and_read_key).unwrap();
+
+    let read_set = tc.read_set();
+    assert_eq!(
+        read_set.keys().iter().copied().collect::<Vec<_>>(),
+        vec![added_and_read_key]
+    );
+    assert_eq!(
+        read_set
+            .keys_checked_for_existence()
+            .into_iter()
+            .collect::<Vec<_>>(),
+        vec![added_key, checked_key]
+    );
+}
+
 proptest! {
     #[test]
     fn query_empty_path(k in key_arb(), missing_key in key_arb(), v in stored_value_arb()) {
@@ -24,6 +24,11 @@ const DEFAULT_CALL_CONTRACT_COST: u32 = 4_500;
 const DEFAULT_CALL_CONTRACT_ARGS_SIZE_WEIGHT: u32 = 420;
 
 const DEFAULT_CREATE_PURSE_COST: u32 = 170_000;
+
+const DEFAULT_EMIT_EVENT_COST: u32 = DEFAULT_FIXED_COST;
+const DEFAULT_EMIT_EVENT_TOPIC_SIZE_WEIGHT: u32 = 1_100;
+const DEFAULT_EMIT_EVENT_PAYLOAD_SIZE_WEIGHT: u32 = 980;
+
 const DEFAULT_GET_BALANCE_COST: u32 = 3_800;
 const DEFAULT_GET_BLOCKTIME_COST: u32 = 330;
 const DEFAULT_GET_CALLER_COST: u32 = 380;
@@ -35,6 +40,8 @@ const DEFAULT_GET_SYSTEM_CONTRACT_COST: u32 = 1_100;
 const DEFAULT_HAS_KEY_COST: u32 = 1_500;
 const DEFAULT_HAS_KEY_NAME_SIZE_WEIGHT: u32 = 840;
 const DEFAULT_IS_VALID_UREF_COST: u32 = 760;
+const DEFAULT_KECCAK256_COST: u32 = 1_000;
+const DEFAULT_KECCAK256_INPUT_SIZE_WEIGHT: u32 = 160;
 const DEFAULT_LOAD_NAMED_KEYS_COST: u32 = 42_000;
 const DEFAULT_NEW_UREF_COST: u32 = 17_000;
 const DEFAULT_NEW_UREF_VALUE_SIZE_WEIGHT: u32 = 590;
@@ -57,16 +64,26 @@ const DEFAULT_REMOVE_ASSOCIATED_KEY_COST: u32 = 4_200;
 const DEFAULT_REMOVE_KEY_COST: u32 = 61_000;
 const DEFAULT_REMOVE_KEY_NAME_SIZE_WEIGHT: u32 = 3_200;
 
+const DEFAULT_RECOVER_SECP256K1_COST: u32 = 1_300_000;
+const DEFAULT_RECOVER_SECP256K1_MESSAGE_SIZE_WEIGHT: u32 = 440;
+
 const DEFAULT_RET_COST: u32 = 23_000;
 const DEFAULT_RET_VALUE_SIZE_WEIGHT: u32 = 420;
 
 const DEFAULT_REVERT_COST: u32 = 500;
 const DEFAULT_SET_ACTION_THRESHOLD_COST: u32 = 74_000;
+
+const DEFAULT_SHA256_COST: u32 = 1_000;
+const DEFAULT_SHA256_INPUT_SIZE_WEIGHT: u32 = 120;
+
 const DEFAULT_TRANSFER_FROM_PURSE_TO_ACCOUNT_COST: u32 = 160_000;
 const DEFAULT_TRANSFER_FROM_PURSE_TO_PURSE_COST: u32 = 82_000;
 const DEFAULT_TRANSFER_TO_ACCOUNT_COST: u32 = 24_000;
 const DEFAULT_UPDATE_ASSOCIATED_KEY_COST: u32 = 4_200;
 
+const DEFAULT_VERIFY_SIGNATURE_COST: u32 = 1_000_000;
+const DEFAULT_VERIFY_SIGNATURE_MESSAGE_SIZE_WEIGHT: u32 = 440;
+
 const DEFAULT_WRITE_COST: u32 = 14_000;
 const DEFAULT_WRITE_VALUE_SIZE_WEIGHT: u32 = 980;
 
@@ -234,6 +251,11 @@ pub struct HostFunctionCosts {
     pub remove_contract_user_group_urefs: HostFunction<[Cost; 6]>,
     pub print: HostFunction<[Cost; 2]>,
     pub blake2b: HostFunction<[Cost; 4]>,
+    pub emit_event: HostFunction<[Cost; 4]>,
+    pub verify_signature: HostFunction<[Cost; 6]>,
+    pub recover_secp256k1: HostFunction<[Cost; 7]>,
+    pub sha256: HostFunction<[Cost; 4]>,
+    pub keccak256: HostFunction<[Cost; 4]>,
 }
 
 impl Default for HostFunctionCosts {
@@ -351,6 +373,56 @@ impl Default for HostFunctionCosts {
                 [NOT_USED, DEFAULT_PRINT_TEXT_SIZE_WEIGHT],
             ),
             blake2b: HostFunction::default(),
+            emit_event: HostFunction::new(
+                DEFAULT_EMIT_EVENT_COST,
+                [
+                    NOT_USED,
+                    DEFAULT_EMIT_EVENT_TOPIC_SIZE_WEIGHT,
+                    NOT_USED,
+                    DEFAULT_EMIT_EVENT_PAYLOAD_SIZE_WEIGHT,
+                ],
+            ),
+            verify_signature: HostFunction::new(
+                DEFAULT_VERIFY_SIGNATURE_COST,
+                [
+                    NOT_USED,
+                    NOT_USED,
+                    NOT_USED,
+                    DEFAULT_VERIFY_SIGNATURE_MESSAGE_SIZE_WEIGHT,
+                    NOT_USED,
+                    NOT_USED,
+                ],
+            ),
+            recover_secp256k1: HostFunction::new(
+                DEFAULT_RECOVER_SECP256K1_COST,
+                [
+                    NOT_USED,
+                    DEFAULT_RECOVER_SECP256K1_MESSAGE_SIZE_WEIGHT,
+                    NOT_USED,
+                    NOT_USED,
+                    NOT_USED,
+                    NOT_USED,
+                    NOT_USED,
+                ],
+            ),
+            sha256: HostFunction::new(
+                DEFAULT_SHA256_COST,
+                [
+                    NOT_USED,
+                    DEFAULT_SHA256_INPUT_SIZE_WEIGHT,
+                    NOT_USED,
+                    NOT_USED,
+                ],
+            ),
+            keccak256: HostFunction::new(
+                DEFAULT_KECCAK256_COST,
+                [
+                    NOT_USED,
+                    DEFAULT_KECCAK256_INPUT_SIZE_WEIGHT,
+                    NOT_USED,
+                    NOT_USED,
+                ],
+            ),
         }
     }
 }
@@ -400,6 +472,11 @@ impl ToBytes for HostFunctionCosts {
         ret.append(&mut self.remove_contract_user_group_urefs.to_bytes()?);
         ret.append(&mut self.print.to_bytes()?);
         ret.append(&mut self.blake2b.to_bytes()?);
+        ret.append(&mut self.emit_event.to_bytes()?);
+        ret.append(&mut self.verify_signature.to_bytes()?);
+        ret.append(&mut self.recover_secp256k1.to_bytes()?);
+        ret.append(&mut self.sha256.to_bytes()?);
+        ret.append(&mut self.keccak256.to_bytes()?);
         Ok(ret)
     }
 
@@ -446,11 +523,19 @@ impl ToBytes for HostFunctionCosts {
             + self.remove_contract_user_group_urefs.serialized_length()
             + self.print.serialized_length()
             + self.blake2b.serialized_length()
+            + self.emit_event.serialized_length()
+            + self.verify_signature.serialized_length()
+            + self.recover_secp256k1.serialized_length()
+            + self.sha256.serialized_length()
+            + self.keccak256.serialized_length()
     }
 }
 
-impl FromBytes for HostFunctionCosts {
-    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+impl HostFunctionCosts {
+    /// Deserializes the host function costs as stored in the protocol data of protocol version
+    /// 1.3.0 and earlier, which lacks the host functions added since.  Their costs take default
+    /// values.
+    pub(crate) fn legacy_from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
         let (read_value, rem) = FromBytes::from_bytes(bytes)?;
         let (dictionary_get, rem) = FromBytes::from_bytes(rem)?;
         let (write, rem) = FromBytes::from_bytes(rem)?;
@@ -537,6 +622,29 @@ impl FromBytes for HostFunctionCosts {
                 remove_contract_user_group_urefs,
                 print,
                 blake2b,
+                ..HostFunctionCosts::default()
+            },
+            rem,
+        ))
+    }
+}
+
+impl FromBytes for HostFunctionCosts {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (legacy_costs, rem) = HostFunctionCosts::legacy_from_bytes(bytes)?;
+        let (emit_event, rem) = FromBytes::from_bytes(rem)?;
+        let (verify_signature, rem) = FromBytes::from_bytes(rem)?;
+        let (recover_secp256k1, rem) = FromBytes::from_bytes(rem)?;
+        let (sha256, rem) = FromBytes::from_bytes(rem)?;
+        let (keccak256, rem) = FromBytes::from_bytes(rem)?;
+        Ok((
+            HostFunctionCosts {
+                emit_event,
+                verify_signature,
+                recover_secp256k1,
+                sha256,
+                keccak256,
+                ..legacy_costs
             },
             rem,
         ))
@@ -588,6 +696,11 @@ impl Distribution<HostFunctionCosts> for Standard {
             remove_contract_user_group_urefs: rng.gen(),
             print: rng.gen(),
             blake2b: rng.gen(),
+            emit_event: rng.gen(),
+            verify_signature: rng.gen(),
+            recover_secp256k1: rng.gen(),
+            sha256: rng.gen(),
+            keccak256: rng.gen(),
         }
     }
 }
@@ -646,6 +759,11 @@ pub mod gens {
             remove_contract_user_group_urefs in host_function_cost_arb(),
             print in host_function_cost_arb(),
             blake2b in host_function_cost_arb(),
+            emit_event in host_function_cost_arb(),
+            verify_signature in host_function_cost_arb(),
+            recover_secp256k1 in host_function_cost_arb(),
+            sha256 in host_function_cost_arb(),
+            keccak256 in host_function_cost_arb(),
         ) -> HostFunctionCosts {
             HostFunctionCosts {
                 read_value,
@@ -690,6 +808,11 @@ pub mod gens {
                 remove_contract_user_group_urefs,
                 print,
                 blake2b,
+                emit_event,
+                verify_signature,
+                recover_secp256k1,
+                sha256,
+                keccak256,
             }
         }
     }
@@ -735,6 +858,47 @@ mod tests {
 
         assert_eq!(lhs, Gas::new(rhs));
     }
+
+    /// Returns the given costs serialized as in the protocol data of protocol version 1.3.0, which
+    /// ended with the costs of `blake2b`.
+    fn legacy_bytes(costs: &HostFunctionCosts) -> Vec<u8> {
+        let added_length = costs.emit_event.serialized_length()
+            + costs.verify_signature.serialized_length()
+            + costs.recover_secp256k1.serialized_length()
+            + costs.sha256.serialized_length()
+            + costs.keccak256.serialized_length();
+        let mut bytes = costs.to_bytes().unwrap();
+        bytes.truncate(bytes.len() - added_length);
+        bytes
+    }
+
+    #[test]
+    fn should_use_default_signature_costs_when_decoding_legacy_costs() {
+        let costs: HostFunctionCosts = rand::thread_rng().gen();
+
+        let legacy_bytes = legacy_bytes(&costs);
+        let (decoded, rem) = HostFunctionCosts::legacy_from_bytes(&legacy_bytes).unwrap();
+
+        assert!(rem.is_empty());
+        assert_eq!(decoded.blake2b, costs.blake2b);
+        let default_costs = HostFunctionCosts::default();
+        assert_eq!(decoded.verify_signature, default_costs.verify_signature);
+        assert_eq!(decoded.recover_secp256k1, default_costs.recover_secp256k1);
+    }
+
+    #[test]
+    fn should_use_default_hashing_costs_when_decoding_legacy_costs() {
+        let costs: HostFunctionCosts = rand::thread_rng().gen();
+
+        let legacy_bytes = legacy_bytes(&costs);
+        let (decoded, rem) = HostFunctionCosts::legacy_from_bytes(&legacy_bytes).unwrap();
+
+        assert!(rem.is_empty());
+        assert_eq!(decoded.blake2b, costs.blake2b);
+        let default_costs = HostFunctionCosts::default();
+        assert_eq!(decoded.sha256, default_costs.sha256);
+        assert_eq!(decoded.keccak256, default_costs.keccak256);
+    }
 }
 
 #[cfg(test)]
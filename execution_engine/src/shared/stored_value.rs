@@ -4,10 +4,10 @@ use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
 use serde_bytes::ByteBuf;
 
 use casper_types::{
-    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH, U8_SERIALIZED_LENGTH},
     contracts::ContractPackage,
-    system::auction::{Bid, EraInfo, UnbondingPurse},
-    CLValue, Contract, ContractWasm, DeployInfo, Transfer,
+    system::auction::{Bid, EraInfo, SeigniorageAllocation, UnbondingPurse},
+    CLValue, Contract, ContractWasm, DeployInfo, EraId, PublicKey, Transfer, URef, U512,
 };
 
 use crate::shared::{account::Account, TypeMismatch};
@@ -21,9 +21,16 @@ enum Tag {
     ContractPackage = 4,
     Transfer = 5,
     DeployInfo = 6,
+    /// Era info without its slashed stakes, as serialized before slashed stakes were recorded.
     EraInfo = 7,
     Bid = 8,
+    /// Unbonding purses without their `new_validator` field, as serialized before redelegation
+    /// was added.
     Withdraw = 9,
+    /// Unbonding purses including their `new_validator` field.
+    WithdrawWithRedelegations = 10,
+    /// Era info including its slashed stakes.
+    EraInfoWithSlashedStakes = 11,
 }
 
 #[derive(Eq, PartialEq, Clone, Debug)]
@@ -272,10 +279,23 @@ impl ToBytes for StoredValue {
             }
             StoredValue::Transfer(transfer) => (Tag::Transfer, transfer.to_bytes()?),
             StoredValue::DeployInfo(deploy_info) => (Tag::DeployInfo, deploy_info.to_bytes()?),
-            StoredValue::EraInfo(era_info) => (Tag::EraInfo, era_info.to_bytes()?),
+            StoredValue::EraInfo(era_info) => {
+                if era_info.slashed_stakes().is_empty() {
+                    (Tag::EraInfo, legacy_era_info_to_bytes(era_info)?)
+                } else {
+                    (Tag::EraInfoWithSlashedStakes, era_info.to_bytes()?)
+                }
+            }
             StoredValue::Bid(bid) => (Tag::Bid, bid.to_bytes()?),
             StoredValue::Withdraw(unbonding_purses) => {
-                (Tag::Withdraw, unbonding_purses.to_bytes()?)
+                if has_redelegations(unbonding_purses) {
+                    (Tag::WithdrawWithRedelegations, unbonding_purses.to_bytes()?)
+                } else {
+                    (
+                        Tag::Withdraw,
+                        legacy_unbonding_purses_to_bytes(unbonding_purses)?,
+                    )
+                }
             }
         };
         result.push(tag as u8);
@@ -295,9 +315,21 @@ impl ToBytes for StoredValue {
                 }
                 StoredValue::Transfer(transfer) => transfer.serialized_length(),
                 StoredValue::DeployInfo(deploy_info) => deploy_info.serialized_length(),
-                StoredValue::EraInfo(era_info) => era_info.serialized_length(),
+                StoredValue::EraInfo(era_info) => {
+                    if era_info.slashed_stakes().is_empty() {
+                        legacy_era_info_serialized_length(era_info)
+                    } else {
+                        era_info.serialized_length()
+                    }
+                }
                 StoredValue::Bid(bid) => bid.serialized_length(),
-                StoredValue::Withdraw(unbonding_purses) => unbonding_purses.serialized_length(),
+                StoredValue::Withdraw(unbonding_purses) => {
+                    if has_redelegations(unbonding_purses) {
+                        unbonding_purses.serialized_length()
+                    } else {
+                        legacy_unbonding_purses_serialized_length(unbonding_purses)
+                    }
+                }
             }
     }
 }
@@ -326,11 +358,18 @@ impl FromBytes for StoredValue {
                 .map(|(transfer, remainder)| (StoredValue::Transfer(transfer), remainder)),
             tag if tag == Tag::DeployInfo as u8 => DeployInfo::from_bytes(remainder)
                 .map(|(deploy_info, remainder)| (StoredValue::DeployInfo(deploy_info), remainder)),
-            tag if tag == Tag::EraInfo as u8 => EraInfo::from_bytes(remainder)
-                .map(|(deploy_info, remainder)| (StoredValue::EraInfo(deploy_info), remainder)),
+            tag if tag == Tag::EraInfo as u8 => legacy_era_info_from_bytes(remainder)
+                .map(|(era_info, remainder)| (StoredValue::EraInfo(era_info), remainder)),
+            tag if tag == Tag::EraInfoWithSlashedStakes as u8 => EraInfo::from_bytes(remainder)
+                .map(|(era_info, remainder)| (StoredValue::EraInfo(era_info), remainder)),
             tag if tag == Tag::Bid as u8 => Bid::from_bytes(remainder)
                 .map(|(bid, remainder)| (StoredValue::Bid(Box::new(bid)), remainder)),
-            tag if tag == Tag::Withdraw as u8 => {
+            tag if tag == Tag::Withdraw as u8 => legacy_unbonding_purses_from_bytes(remainder).map(
+                |(unbonding_purses, remainder)| {
+                    (StoredValue::Withdraw(unbonding_purses), remainder)
+                },
+            ),
+            tag if tag == Tag::WithdrawWithRedelegations as u8 => {
                 Vec::<UnbondingPurse>::from_bytes(remainder).map(|(unbonding_purses, remainder)| {
                     (StoredValue::Withdraw(unbonding_purses), remainder)
                 })
@@ -340,6 +379,91 @@ impl FromBytes for StoredValue {
     }
 }
 
+// Withdraw values written before redelegation was added can't be told apart from current ones by
+// their unbonding purses alone, so they are versioned by their tag instead.  Values holding no
+// redelegations keep the legacy serialized form.
+
+fn has_redelegations(unbonding_purses: &[UnbondingPurse]) -> bool {
+    unbonding_purses
+        .iter()
+        .any(|unbonding_purse| unbonding_purse.new_validator().is_some())
+}
+
+fn legacy_unbonding_purse_serialized_length(unbonding_purse: &UnbondingPurse) -> usize {
+    unbonding_purse.bonding_purse().serialized_length()
+        + unbonding_purse.validator_public_key().serialized_length()
+        + unbonding_purse.unbonder_public_key().serialized_length()
+        + unbonding_purse.era_of_creation().serialized_length()
+        + unbonding_purse.amount().serialized_length()
+}
+
+fn legacy_unbonding_purses_serialized_length(unbonding_purses: &[UnbondingPurse]) -> usize {
+    U32_SERIALIZED_LENGTH
+        + unbonding_purses
+            .iter()
+            .map(legacy_unbonding_purse_serialized_length)
+            .sum::<usize>()
+}
+
+fn legacy_unbonding_purses_to_bytes(
+    unbonding_purses: &[UnbondingPurse],
+) -> Result<Vec<u8>, bytesrepr::Error> {
+    let mut result =
+        Vec::with_capacity(legacy_unbonding_purses_serialized_length(unbonding_purses));
+    result.append(&mut (unbonding_purses.len() as u32).to_bytes()?);
+    for unbonding_purse in unbonding_purses {
+        result.append(&mut unbonding_purse.bonding_purse().to_bytes()?);
+        result.append(&mut unbonding_purse.validator_public_key().to_bytes()?);
+        result.append(&mut unbonding_purse.unbonder_public_key().to_bytes()?);
+        result.append(&mut unbonding_purse.era_of_creation().to_bytes()?);
+        result.append(&mut unbonding_purse.amount().to_bytes()?);
+    }
+    Ok(result)
+}
+
+fn legacy_unbonding_purses_from_bytes(
+    bytes: &[u8],
+) -> Result<(Vec<UnbondingPurse>, &[u8]), bytesrepr::Error> {
+    let (count, mut stream) = u32::from_bytes(bytes)?;
+    let mut unbonding_purses = Vec::new();
+    for _ in 0..count {
+        let (bonding_purse, remainder) = URef::from_bytes(stream)?;
+        let (validator_public_key, remainder) = PublicKey::from_bytes(remainder)?;
+        let (unbonder_public_key, remainder) = PublicKey::from_bytes(remainder)?;
+        let (era_of_creation, remainder) = EraId::from_bytes(remainder)?;
+        let (amount, remainder) = U512::from_bytes(remainder)?;
+        unbonding_purses.push(UnbondingPurse::new(
+            bonding_purse,
+            validator_public_key,
+            unbonder_public_key,
+            era_of_creation,
+            amount,
+            None,
+        ));
+        stream = remainder;
+    }
+    Ok((unbonding_purses, stream))
+}
+
+// Era info written before slashed stakes were recorded consists of its seigniorage allocations
+// only.  Like withdraw values, it is versioned by its tag, and era info without slashed stakes
+// keeps the legacy serialized form.
+
+fn legacy_era_info_serialized_length(era_info: &EraInfo) -> usize {
+    era_info.seigniorage_allocations().serialized_length()
+}
+
+fn legacy_era_info_to_bytes(era_info: &EraInfo) -> Result<Vec<u8>, bytesrepr::Error> {
+    era_info.seigniorage_allocations().to_bytes()
+}
+
+fn legacy_era_info_from_bytes(bytes: &[u8]) -> Result<(EraInfo, &[u8]), bytesrepr::Error> {
+    let (seigniorage_allocations, remainder) = Vec::<SeigniorageAllocation>::from_bytes(bytes)?;
+    let mut era_info = EraInfo::new();
+    *era_info.seigniorage_allocations_mut() = seigniorage_allocations;
+    Ok((era_info, remainder))
+}
+
 impl Serialize for StoredValue {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         // The JSON representation of a StoredValue is just its bytesrepr
@@ -392,6 +516,8 @@ pub mod gens {
 mod tests {
     use proptest::proptest;
 
+    use casper_types::{system::auction::SlashedStake, AccessRights, SecretKey};
+
     use super::*;
 
     proptest! {
@@ -400,4 +526,93 @@ mod tests {
             bytesrepr::test_serialization_roundtrip(&v);
         }
     }
+
+    fn public_key(byte: u8) -> PublicKey {
+        let secret_key = SecretKey::ed25519_from_bytes([byte; SecretKey::ED25519_LENGTH]).unwrap();
+        PublicKey::from(&secret_key)
+    }
+
+    fn unbonding_purse(amount: u64, new_validator: Option<PublicKey>) -> UnbondingPurse {
+        UnbondingPurse::new(
+            URef::new([1; 32], AccessRights::READ_ADD_WRITE),
+            public_key(2),
+            public_key(3),
+            EraId::new(4),
+            U512::from(amount),
+            new_validator,
+        )
+    }
+
+    #[test]
+    fn should_decode_withdraw_serialized_before_redelegation() {
+        let unbonding_purses = vec![unbonding_purse(5, None), unbonding_purse(6, None)];
+
+        // Serialize the way unbonding purses were serialized before they had a `new_validator`.
+        let mut bytes = vec![Tag::Withdraw as u8];
+        bytes.append(&mut 2u32.to_bytes().unwrap());
+        for unbonding_purse in &unbonding_purses {
+            bytes.append(&mut unbonding_purse.bonding_purse().to_bytes().unwrap());
+            bytes.append(&mut unbonding_purse.validator_public_key().to_bytes().unwrap());
+            bytes.append(&mut unbonding_purse.unbonder_public_key().to_bytes().unwrap());
+            bytes.append(&mut unbonding_purse.era_of_creation().to_bytes().unwrap());
+            bytes.append(&mut unbonding_purse.amount().to_bytes().unwrap());
+        }
+
+        let stored_value: StoredValue = bytesrepr::deserialize(bytes.clone()).unwrap();
+        assert_eq!(stored_value, StoredValue::Withdraw(unbonding_purses));
+
+        // Without redelegations, the serialized form is unchanged.
+        assert_eq!(stored_value.to_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn should_roundtrip_withdraw_with_redelegations() {
+        let stored_value = StoredValue::Withdraw(vec![
+            unbonding_purse(5, None),
+            unbonding_purse(6, Some(public_key(7))),
+        ]);
+        let bytes = stored_value.to_bytes().unwrap();
+        assert_eq!(bytes[0], Tag::WithdrawWithRedelegations as u8);
+        bytesrepr::test_serialization_roundtrip(&stored_value);
+
+        bytesrepr::test_serialization_roundtrip(&StoredValue::Withdraw(vec![]));
+    }
+
+    fn era_info(slashed_stakes: Vec<SlashedStake>) -> EraInfo {
+        let mut era_info = EraInfo::new();
+        *era_info.seigniorage_allocations_mut() = vec![
+            SeigniorageAllocation::validator(public_key(2), U512::from(5)),
+            SeigniorageAllocation::delegator(public_key(3), public_key(2), U512::from(6)),
+        ];
+        *era_info.slashed_stakes_mut() = slashed_stakes;
+        era_info
+    }
+
+    #[test]
+    fn should_decode_era_info_serialized_before_slashed_stakes() {
+        let era_info = era_info(vec![]);
+
+        // Serialize the way era info was serialized before it had slashed stakes.
+        let mut bytes = vec![Tag::EraInfo as u8];
+        bytes.append(&mut era_info.seigniorage_allocations().to_bytes().unwrap());
+
+        let stored_value: StoredValue = bytesrepr::deserialize(bytes.clone()).unwrap();
+        assert_eq!(stored_value, StoredValue::EraInfo(era_info));
+
+        // Without slashed stakes, the serialized form is unchanged.
+        assert_eq!(stored_value.to_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn should_roundtrip_era_info_with_slashed_stakes() {
+        let stored_value = StoredValue::EraInfo(era_info(vec![
+            SlashedStake::validator(public_key(2), U512::from(7)),
+            SlashedStake::delegator(public_key(3), public_key(2), U512::from(8)),
+        ]));
+        let bytes = stored_value.to_bytes().unwrap();
+        assert_eq!(bytes[0], Tag::EraInfoWithSlashedStakes as u8);
+        bytesrepr::test_serialization_roundtrip(&stored_value);
+
+        bytesrepr::test_serialization_roundtrip(&StoredValue::EraInfo(EraInfo::new()));
+    }
 }
@@ -117,6 +117,30 @@ impl ToBytes for SystemConfig {
     }
 }
 
+impl SystemConfig {
+    /// Deserializes a system config as stored in the protocol data of protocol version 1.3.0 and
+    /// earlier.  See [`AuctionCosts::legacy_from_bytes`].
+    pub(crate) fn legacy_from_bytes(
+        bytes: &[u8],
+    ) -> Result<(Self, &[u8]), casper_types::bytesrepr::Error> {
+        let (wasmless_transfer_cost, rem) = FromBytes::from_bytes(bytes)?;
+        let (auction_costs, rem) = AuctionCosts::legacy_from_bytes(rem)?;
+        let (mint_costs, rem) = FromBytes::from_bytes(rem)?;
+        let (handle_payment_costs, rem) = FromBytes::from_bytes(rem)?;
+        let (standard_payment_costs, rem) = FromBytes::from_bytes(rem)?;
+        Ok((
+            SystemConfig::new(
+                wasmless_transfer_cost,
+                auction_costs,
+                mint_costs,
+                handle_payment_costs,
+                standard_payment_costs,
+            ),
+            rem,
+        ))
+    }
+}
+
 impl FromBytes for SystemConfig {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), casper_types::bytesrepr::Error> {
         let (wasmless_transfer_cost, rem) = FromBytes::from_bytes(bytes)?;
@@ -53,6 +53,27 @@ impl WasmConfig {
     pub fn take_host_function_costs(self) -> HostFunctionCosts {
         self.host_function_costs
     }
+
+    /// Deserializes a Wasm config as stored in the protocol data of protocol version 1.3.0 and
+    /// earlier.  See [`HostFunctionCosts::legacy_from_bytes`].
+    pub(crate) fn legacy_from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (max_memory, rem) = FromBytes::from_bytes(bytes)?;
+        let (max_stack_height, rem) = FromBytes::from_bytes(rem)?;
+        let (opcode_costs, rem) = FromBytes::from_bytes(rem)?;
+        let (storage_costs, rem) = FromBytes::from_bytes(rem)?;
+        let (host_function_costs, rem) = HostFunctionCosts::legacy_from_bytes(rem)?;
+
+        Ok((
+            WasmConfig {
+                max_memory,
+                max_stack_height,
+                opcode_costs,
+                storage_costs,
+                host_function_costs,
+            },
+            rem,
+        ))
+    }
 }
 
 impl Default for WasmConfig {
@@ -1,4 +1,11 @@
-use std::{ops::Deref, sync::Arc};
+use std::{
+    convert::TryFrom,
+    ops::Deref,
+    sync::{Arc, Mutex},
+};
+
+use lmdb::{Cursor, DatabaseFlags};
+use tracing::info;
 
 use crate::shared::{
     additive_map::AdditiveMap,
@@ -6,7 +13,7 @@ use crate::shared::{
     stored_value::StoredValue,
     transform::Transform,
 };
-use casper_types::{Key, ProtocolVersion};
+use casper_types::{bytesrepr, Key, ProtocolVersion};
 
 use crate::storage::{
     error,
@@ -19,16 +26,37 @@ use crate::storage::{
     trie_store::{
         lmdb::LmdbTrieStore,
         operations::{
-            keys_with_prefix, missing_trie_keys, put_trie, read, read_with_proof, ReadResult,
+            keys_with_prefix, mark_reachable_trie_keys, missing_trie_keys, put_trie, read,
+            read_with_proof, ReadResult,
         },
     },
 };
 
+/// The maximum number of trie nodes marked or swept per write transaction during garbage
+/// collection.
+const GARBAGE_COLLECTION_BATCH_SIZE: usize = 10_000;
+
+/// Name of the database holding the keys of the trie nodes marked as reachable during garbage
+/// collection.
+const GARBAGE_COLLECTION_MARKS_DB_NAME: &str = "GARBAGE_COLLECTION_MARKS";
+
 pub struct LmdbGlobalState {
     pub environment: Arc<LmdbEnvironment>,
     pub trie_store: Arc<LmdbTrieStore>,
     pub protocol_data_store: Arc<LmdbProtocolDataStore>,
     pub empty_root_hash: Blake2bHash,
+    /// Roots of tries written while a garbage collection is in progress, or `None` if there is no
+    /// garbage collection in progress.
+    garbage_collection_roots: Mutex<Option<Vec<Blake2bHash>>>,
+}
+
+/// The outcome of a global state garbage collection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GarbageCollectionResult {
+    /// The number of trie nodes reachable from the retained roots.
+    pub reachable: usize,
+    /// The number of unreachable trie nodes deleted.
+    pub deleted: usize,
 }
 
 /// Represents a "view" of global state at a particular root hash.
@@ -73,6 +101,152 @@ impl LmdbGlobalState {
             trie_store,
             protocol_data_store,
             empty_root_hash,
+            garbage_collection_roots: Mutex::new(None),
+        }
+    }
+
+    /// Deletes all trie nodes which are not reachable from any of the `retained_roots` or the empty
+    /// root.
+    ///
+    /// Reachable nodes are marked in a dedicated database rather than in memory, so the memory
+    /// required does not grow with the size of the global state. Both marking and sweeping are
+    /// done in batches of small write transactions, so concurrent commits are only blocked
+    /// briefly. Tries committed or put while the collection is in progress are retained, as long
+    /// as they are based on one of the retained roots.
+    ///
+    /// Returns `None` if another garbage collection is already in progress.
+    pub fn collect_garbage(
+        &self,
+        correlation_id: CorrelationId,
+        retained_roots: &[Blake2bHash],
+    ) -> Result<Option<GarbageCollectionResult>, error::Error> {
+        {
+            let mut garbage_collection_roots = self.garbage_collection_roots.lock()?;
+            if garbage_collection_roots.is_some() {
+                return Ok(None);
+            }
+            *garbage_collection_roots = Some(Vec::new());
+        }
+
+        let result = self.mark_and_sweep(correlation_id, retained_roots);
+        *self.garbage_collection_roots.lock()? = None;
+        result.map(Some)
+    }
+
+    fn mark_and_sweep(
+        &self,
+        correlation_id: CorrelationId,
+        retained_roots: &[Blake2bHash],
+    ) -> Result<GarbageCollectionResult, error::Error> {
+        let db = Store::<Blake2bHash, Trie<Key, StoredValue>>::handle(self.trie_store.deref());
+        let marks = self.environment.env().create_db(
+            Some(GARBAGE_COLLECTION_MARKS_DB_NAME),
+            DatabaseFlags::empty(),
+        )?;
+
+        // Marks may be left over from an interrupted collection.
+        let mut txn = self.environment.create_read_write_txn()?;
+        txn.clear_db(marks)?;
+        txn.commit()?;
+
+        let mut trie_keys_to_visit = retained_roots.to_vec();
+        trie_keys_to_visit.push(self.empty_root_hash);
+        info!(
+            retained_roots = trie_keys_to_visit.len(),
+            "marking reachable trie nodes"
+        );
+        let mut reachable = 0;
+        while !trie_keys_to_visit.is_empty() {
+            let mut txn = self.environment.create_read_write_txn()?;
+            reachable +=
+                mark_reachable_trie_keys::<Key, StoredValue, _, LmdbTrieStore, error::Error>(
+                    correlation_id,
+                    &mut txn,
+                    self.trie_store.deref(),
+                    marks,
+                    &mut trie_keys_to_visit,
+                    GARBAGE_COLLECTION_BATCH_SIZE,
+                )?;
+            txn.commit()?;
+        }
+
+        info!(reachable, "sweeping unreachable trie nodes");
+        let mut deleted = 0;
+        let mut resume_from: Option<Blake2bHash> = None;
+        loop {
+            // Holding the lock prevents commits from writing tries while the batch is swept.
+            let mut garbage_collection_roots = self.garbage_collection_roots.lock()?;
+            let mut txn = self.environment.create_read_write_txn()?;
+
+            // Tries written since marking may have recreated nodes which were unmarked before.
+            let mut new_roots = garbage_collection_roots
+                .as_mut()
+                .map(std::mem::take)
+                .unwrap_or_default();
+            reachable +=
+                mark_reachable_trie_keys::<Key, StoredValue, _, LmdbTrieStore, error::Error>(
+                    correlation_id,
+                    &mut txn,
+                    self.trie_store.deref(),
+                    marks,
+                    &mut new_roots,
+                    usize::MAX,
+                )?;
+
+            let mut unreachable = Vec::new();
+            let mut next_batch_start = None;
+            {
+                let mut cursor = lmdb::Transaction::open_ro_cursor(&txn, db)?;
+                let iter = match &resume_from {
+                    Some(trie_key) => cursor.iter_from(trie_key),
+                    None => cursor.iter_start(),
+                };
+                for (index, (raw_key, _)) in iter.enumerate() {
+                    let trie_key =
+                        Blake2bHash::try_from(raw_key).map_err(|_| bytesrepr::Error::Formatting)?;
+                    // The next batch resumes from this node, so it must not be deleted yet.
+                    if index == GARBAGE_COLLECTION_BATCH_SIZE {
+                        next_batch_start = Some(trie_key);
+                        break;
+                    }
+                    match lmdb::Transaction::get(&txn, marks, &trie_key) {
+                        Ok(_) => (),
+                        Err(lmdb::Error::NotFound) => unreachable.push(trie_key),
+                        Err(error) => return Err(error.into()),
+                    }
+                }
+            }
+
+            for trie_key in &unreachable {
+                txn.del(db, trie_key, None)?;
+            }
+            txn.commit()?;
+            deleted += unreachable.len();
+
+            match next_batch_start {
+                Some(trie_key) => resume_from = Some(trie_key),
+                None => break,
+            }
+        }
+
+        let mut txn = self.environment.create_read_write_txn()?;
+        txn.clear_db(marks)?;
+        txn.commit()?;
+
+        let result = GarbageCollectionResult { reachable, deleted };
+        info!(?result, "finished global state garbage collection");
+        Ok(result)
+    }
+
+    /// Records the root of a trie written while a garbage collection may be in progress.
+    ///
+    /// Must be called while holding the lock on `garbage_collection_roots`.
+    fn record_written_root(
+        garbage_collection_roots: &mut Option<Vec<Blake2bHash>>,
+        root: Blake2bHash,
+    ) {
+        if let Some(roots) = garbage_collection_roots.as_mut() {
+            roots.push(root);
         }
     }
 }
@@ -176,6 +350,7 @@ impl StateProvider for LmdbGlobalState {
         prestate_hash: Blake2bHash,
         effects: AdditiveMap<Key, Transform>,
     ) -> Result<CommitResult, Self::Error> {
+        let mut garbage_collection_roots = self.garbage_collection_roots.lock()?;
         let commit_result = commit::<LmdbEnvironment, LmdbTrieStore, _, Self::Error>(
             &self.environment,
             &self.trie_store,
@@ -183,6 +358,9 @@ impl StateProvider for LmdbGlobalState {
             prestate_hash,
             effects,
         )?;
+        if let CommitResult::Success { state_root, .. } = &commit_result {
+            Self::record_written_root(&mut garbage_collection_roots, *state_root);
+        }
         Ok(commit_result)
     }
 
@@ -227,6 +405,7 @@ impl StateProvider for LmdbGlobalState {
         correlation_id: CorrelationId,
         trie: &Trie<Key, StoredValue>,
     ) -> Result<Blake2bHash, Self::Error> {
+        let mut garbage_collection_roots = self.garbage_collection_roots.lock()?;
         let mut txn = self.environment.create_read_write_txn()?;
         let trie_hash = put_trie::<
             Key,
@@ -236,6 +415,7 @@ impl StateProvider for LmdbGlobalState {
             Self::Error,
         >(correlation_id, &mut txn, &self.trie_store, trie)?;
         txn.commit()?;
+        Self::record_written_root(&mut garbage_collection_roots, trie_hash);
         Ok(trie_hash)
     }
 
@@ -443,4 +623,95 @@ mod tests {
                 .unwrap()
         );
     }
+
+    #[test]
+    fn collect_garbage_deletes_unreachable_tries_only() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+
+        let (state, root_hash) = create_test_state();
+
+        let effects: AdditiveMap<Key, Transform> = {
+            let mut tmp = AdditiveMap::new();
+            for TestPair { key, value } in &test_pairs_updated {
+                tmp.insert(*key, Transform::Write(value.to_owned()));
+            }
+            tmp
+        };
+
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        // Retaining both roots does not delete anything.
+        let result = state
+            .collect_garbage(correlation_id, &[root_hash, updated_hash])
+            .unwrap()
+            .expect("should run garbage collection");
+        assert_eq!(result.deleted, 0);
+        assert!(state.checkout(root_hash).unwrap().is_some());
+
+        // Retaining only the updated root deletes the original one.
+        let result = state
+            .collect_garbage(correlation_id, &[updated_hash])
+            .unwrap()
+            .expect("should run garbage collection");
+        assert!(result.deleted > 0);
+        assert!(state.checkout(root_hash).unwrap().is_none());
+        assert!(state.checkout(state.empty_root_hash).unwrap().is_some());
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        for TestPair { key, value } in test_pairs_updated.iter().cloned() {
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn collect_garbage_retains_tries_committed_during_collection() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+        let (state, root_hash) = create_test_state();
+
+        // Simulate a collection in progress.
+        *state.garbage_collection_roots.lock().unwrap() = Some(Vec::new());
+        assert_eq!(
+            state.collect_garbage(correlation_id, &[root_hash]).unwrap(),
+            None
+        );
+
+        let effects: AdditiveMap<Key, Transform> = {
+            let mut tmp = AdditiveMap::new();
+            for TestPair { key, value } in &test_pairs_updated {
+                tmp.insert(*key, Transform::Write(value.to_owned()));
+            }
+            tmp
+        };
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+        assert_eq!(
+            *state.garbage_collection_roots.lock().unwrap(),
+            Some(vec![updated_hash])
+        );
+
+        // Finish the collection without retaining any root: the original root is deleted, but the
+        // trie committed during the collection must remain readable.
+        let result = state.mark_and_sweep(correlation_id, &[]).unwrap();
+        *state.garbage_collection_roots.lock().unwrap() = None;
+        assert!(result.deleted > 0);
+        assert!(state.checkout(root_hash).unwrap().is_none());
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        for TestPair { key, value } in test_pairs_updated.iter().cloned() {
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+    }
 }
@@ -10,6 +10,9 @@ use crate::shared::{system_config::SystemConfig, wasm_config::WasmConfig};
 const DEFAULT_ADDRESS: [u8; 32] = [0; 32];
 pub const DEFAULT_WASMLESS_TRANSFER_COST: u32 = 10_000;
 
+type WasmConfigFromBytes = fn(&[u8]) -> Result<(WasmConfig, &[u8]), bytesrepr::Error>;
+type SystemConfigFromBytes = fn(&[u8]) -> Result<(SystemConfig, &[u8]), bytesrepr::Error>;
+
 /// Represents a protocol's data. Intended to be associated with a given protocol version.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct ProtocolData {
@@ -171,10 +174,16 @@ impl ToBytes for ProtocolData {
     }
 }
 
-impl FromBytes for ProtocolData {
-    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
-        let (wasm_config, rem) = WasmConfig::from_bytes(bytes)?;
-        let (system_config, rem) = FromBytes::from_bytes(rem)?;
+impl ProtocolData {
+    /// Deserializes protocol data, using the given functions to deserialize its Wasm and system
+    /// configs.
+    fn from_bytes_with(
+        bytes: &[u8],
+        wasm_config_from_bytes: WasmConfigFromBytes,
+        system_config_from_bytes: SystemConfigFromBytes,
+    ) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (wasm_config, rem) = wasm_config_from_bytes(bytes)?;
+        let (system_config, rem) = system_config_from_bytes(rem)?;
         let (mint, rem) = HashAddr::from_bytes(rem)?;
         let (handle_payment, rem) = HashAddr::from_bytes(rem)?;
         let (standard_payment, rem) = HashAddr::from_bytes(rem)?;
@@ -194,6 +203,24 @@ impl FromBytes for ProtocolData {
     }
 }
 
+impl FromBytes for ProtocolData {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        match ProtocolData::from_bytes_with(bytes, WasmConfig::from_bytes, SystemConfig::from_bytes)
+        {
+            // Protocol data stored by protocol version 1.3.0 and earlier lacks the costs added to
+            // the Wasm and system configs since.  As protocol data consists of fixed-size fields
+            // only and is always stored on its own, it runs out of bytes when decoded with the
+            // current layout.
+            Err(bytesrepr::Error::EarlyEndOfStream) => ProtocolData::from_bytes_with(
+                bytes,
+                WasmConfig::legacy_from_bytes,
+                SystemConfig::legacy_from_bytes,
+            ),
+            result => result,
+        }
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod gens {
     use proptest::prop_compose;
@@ -229,9 +256,17 @@ pub(crate) mod gens {
 #[cfg(test)]
 mod tests {
     use proptest::proptest;
+    use rand::Rng;
 
-    use crate::shared::{system_config::SystemConfig, wasm_config::WasmConfig};
-    use casper_types::{bytesrepr, ContractHash};
+    use crate::shared::{
+        host_function_costs::HostFunctionCosts,
+        system_config::{auction_costs::AuctionCosts, SystemConfig},
+        wasm_config::WasmConfig,
+    };
+    use casper_types::{
+        bytesrepr::{self, ToBytes},
+        ContractHash,
+    };
 
     use super::{gens, ProtocolData};
 
@@ -301,6 +336,81 @@ mod tests {
         assert_eq!(actual[2], auction_reference);
     }
 
+    #[test]
+    fn should_deserialize_protocol_data_of_1_3_0() {
+        let mut rng = rand::thread_rng();
+
+        // The host functions added since protocol version 1.3.0 take default costs when decoding
+        // its protocol data.
+        let default_costs = HostFunctionCosts::default();
+        let random_costs: HostFunctionCosts = rng.gen();
+        let host_function_costs = HostFunctionCosts {
+            emit_event: default_costs.emit_event,
+            verify_signature: default_costs.verify_signature,
+            recover_secp256k1: default_costs.recover_secp256k1,
+            sha256: default_costs.sha256,
+            keccak256: default_costs.keccak256,
+            ..random_costs
+        };
+        let random_wasm_config: WasmConfig = rng.gen();
+        let wasm_config = WasmConfig::new(
+            random_wasm_config.max_memory,
+            random_wasm_config.max_stack_height,
+            random_wasm_config.opcode_costs(),
+            random_wasm_config.storage_costs(),
+            host_function_costs,
+        );
+        // So does the `redelegate` auction entry point.
+        let random_system_config: SystemConfig = rng.gen();
+        let auction_costs = AuctionCosts {
+            redelegate: AuctionCosts::default().redelegate,
+            ..*random_system_config.auction_costs()
+        };
+        let system_config = SystemConfig::new(
+            random_system_config.wasmless_transfer_cost(),
+            auction_costs,
+            *random_system_config.mint_costs(),
+            *random_system_config.handle_payment_costs(),
+            *random_system_config.standard_payment_costs(),
+        );
+        let protocol_data = ProtocolData::new(
+            wasm_config,
+            system_config,
+            [1u8; 32].into(),
+            [2u8; 32].into(),
+            [3u8; 32].into(),
+            [4u8; 32].into(),
+        );
+
+        // In 1.3.0, the Wasm config ended with the costs of `blake2b`.
+        let added_length = host_function_costs.emit_event.serialized_length()
+            + host_function_costs.verify_signature.serialized_length()
+            + host_function_costs.recover_secp256k1.serialized_length()
+            + host_function_costs.sha256.serialized_length()
+            + host_function_costs.keccak256.serialized_length();
+        let wasm_config_bytes = wasm_config.to_bytes().unwrap();
+        let mut legacy_bytes = wasm_config_bytes[..wasm_config_bytes.len() - added_length].to_vec();
+        // And the auction costs ended with the cost of `activate_bid`.
+        legacy_bytes.extend(system_config.wasmless_transfer_cost().to_bytes().unwrap());
+        let auction_costs_bytes = auction_costs.to_bytes().unwrap();
+        legacy_bytes.extend(
+            &auction_costs_bytes
+                [..auction_costs_bytes.len() - auction_costs.redelegate.serialized_length()],
+        );
+        legacy_bytes.extend(system_config.mint_costs().to_bytes().unwrap());
+        legacy_bytes.extend(system_config.handle_payment_costs().to_bytes().unwrap());
+        legacy_bytes.extend(system_config.standard_payment_costs().to_bytes().unwrap());
+        for contract_hash in protocol_data.system_contracts() {
+            legacy_bytes.extend(contract_hash.to_bytes().unwrap());
+        }
+
+        assert_eq!(
+            bytesrepr::deserialize::<ProtocolData>(legacy_bytes).unwrap(),
+            protocol_data
+        );
+        bytesrepr::test_serialization_roundtrip(&protocol_data);
+    }
+
     proptest! {
         #[test]
         fn should_serialize_and_deserialize_with_arbitrary_values(
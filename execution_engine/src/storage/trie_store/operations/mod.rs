@@ -295,6 +295,73 @@ where
     Ok(missing_descendants)
 }
 
+/// Marks the keys of all `Trie<K,V>` values reachable from `trie_keys_to_visit` by writing them to
+/// the `marks` database, and returns the number of keys newly marked.
+///
+/// Keys which are already marked are not descended into, which allows cheaply extending a
+/// previously computed mark set with the keys of new roots. Missing descendants are ignored.
+///
+/// Stops as soon as at least `max_marks` keys have been newly marked, leaving the keys yet to be
+/// visited in `trie_keys_to_visit`. This allows marking a large trie over several transactions.
+pub fn mark_reachable_trie_keys<K, V, T, S, E>(
+    _correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    marks: S::Handle,
+    trie_keys_to_visit: &mut Vec<Blake2bHash>,
+    max_marks: usize,
+) -> Result<usize, E>
+where
+    K: ToBytes + FromBytes + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + std::fmt::Debug,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Handle: Copy,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<bytesrepr::Error>,
+{
+    // Marks a key, returning `false` if it was already marked.
+    let mark = |txn: &mut T, trie_key: &Blake2bHash| -> Result<bool, S::Error> {
+        if txn.read(marks, trie_key.as_ref())?.is_some() {
+            return Ok(false);
+        }
+        txn.write(marks, trie_key.as_ref(), &[])?;
+        Ok(true)
+    };
+
+    let mut marked = 0;
+    while let Some(trie_key) = trie_keys_to_visit.pop() {
+        if !mark(txn, &trie_key)? {
+            continue;
+        }
+        marked += 1;
+        let maybe_retrieved_trie: Option<Trie<K, V>> = store.get(txn, &trie_key)?;
+        match maybe_retrieved_trie {
+            None | Some(Trie::Leaf { .. }) => (),
+            Some(Trie::Node { pointer_block }) => {
+                for (_, pointer) in pointer_block.to_indexed_pointers() {
+                    match pointer {
+                        // Leaves have no descendants, so there is no need to retrieve them.
+                        Pointer::LeafPointer(leaf_trie_key) => {
+                            if mark(txn, &leaf_trie_key)? {
+                                marked += 1;
+                            }
+                        }
+                        Pointer::NodePointer(node_trie_key) => {
+                            trie_keys_to_visit.push(node_trie_key)
+                        }
+                    }
+                }
+            }
+            Some(Trie::Extension { pointer, .. }) => trie_keys_to_visit.push(pointer.into_hash()),
+        }
+        if marked >= max_marks {
+            break;
+        }
+    }
+    Ok(marked)
+}
+
 #[cfg(test)]
 pub fn check_integrity<K, V, T, S, E>(
     _correlation_id: CorrelationId,
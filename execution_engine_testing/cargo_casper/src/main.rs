@@ -13,6 +13,8 @@ use once_cell::sync::Lazy;
 pub mod common;
 mod contract_package;
 pub mod dependency;
+#[cfg(feature = "schema")]
+mod schema;
 mod tests_package;
 mod travis_yml;
 
@@ -29,6 +31,21 @@ const ROOT_PATH_ARG_HELP: &str = "Path to new folder for contract and tests";
 const WORKSPACE_PATH_ARG_NAME: &str = "workspace-path";
 const WORKSPACE_PATH_ARG_LONG: &str = "workspace-path";
 
+const SCHEMA_ARG_NAME: &str = "schema";
+const SCHEMA_ARG_LONG: &str = "schema";
+const SCHEMA_ARG_VALUE_NAME: &str = "wasm";
+const SCHEMA_ARG_HELP: &str =
+    "Instead of creating a new project, install the contract by executing the given Wasm file and \
+     print a JSON schema of its entry points and named keys.  Requires cargo-casper to be built \
+     with the 'schema' feature";
+
+const CONTRACT_KEY_ARG_NAME: &str = "contract-key";
+const CONTRACT_KEY_ARG_LONG: &str = "contract-key";
+const CONTRACT_KEY_ARG_VALUE_NAME: &str = "name";
+const CONTRACT_KEY_ARG_HELP: &str =
+    "The named key under which the Wasm given via --schema stores the contract or contract \
+     package hash";
+
 const FAILURE_EXIT_CODE: i32 = 101;
 
 static USAGE: Lazy<String> = Lazy::new(|| {
@@ -37,7 +54,9 @@ static USAGE: Lazy<String> = Lazy::new(|| {
     rustup install {0}
     rustup target add --toolchain {0} wasm32-unknown-unknown
     cd <path>/tests
-    cargo test"#,
+    cargo test
+
+    cargo casper --schema <wasm> --contract-key <name>"#,
         TOOLCHAIN
     )
 });
@@ -45,8 +64,9 @@ static ARGS: Lazy<Args> = Lazy::new(Args::new);
 
 #[derive(Debug)]
 struct Args {
-    root_path: PathBuf,
+    root_path: Option<PathBuf>,
     workspace_path: Option<PathBuf>,
+    schema: Option<(PathBuf, String)>,
 }
 
 impl Args {
@@ -69,7 +89,8 @@ impl Args {
         });
 
         let root_path_arg = Arg::with_name(ROOT_PATH_ARG_NAME)
-            .required(true)
+            .required_unless(SCHEMA_ARG_NAME)
+            .conflicts_with(SCHEMA_ARG_NAME)
             .value_name(ROOT_PATH_ARG_VALUE_NAME)
             .help(ROOT_PATH_ARG_HELP);
 
@@ -78,39 +99,74 @@ impl Args {
             .takes_value(true)
             .hidden(true);
 
+        let schema_arg = Arg::with_name(SCHEMA_ARG_NAME)
+            .long(SCHEMA_ARG_LONG)
+            .takes_value(true)
+            .value_name(SCHEMA_ARG_VALUE_NAME)
+            .requires(CONTRACT_KEY_ARG_NAME)
+            .help(SCHEMA_ARG_HELP);
+
+        let contract_key_arg = Arg::with_name(CONTRACT_KEY_ARG_NAME)
+            .long(CONTRACT_KEY_ARG_LONG)
+            .takes_value(true)
+            .value_name(CONTRACT_KEY_ARG_VALUE_NAME)
+            .requires(SCHEMA_ARG_NAME)
+            .help(CONTRACT_KEY_ARG_HELP);
+
         let arg_matches = App::new(APP_NAME)
             .version(crate_version!())
             .about(ABOUT)
             .usage(USAGE.as_str())
             .arg(root_path_arg)
             .arg(workspace_path_arg)
+            .arg(schema_arg)
+            .arg(contract_key_arg)
             .get_matches_from(filtered_args_iter);
 
-        let root_path = arg_matches
-            .value_of(ROOT_PATH_ARG_NAME)
-            .expect("expected path")
-            .into();
+        let root_path = arg_matches.value_of(ROOT_PATH_ARG_NAME).map(PathBuf::from);
 
         let workspace_path = arg_matches
             .value_of(WORKSPACE_PATH_ARG_NAME)
             .map(PathBuf::from);
 
+        let schema = arg_matches.value_of(SCHEMA_ARG_NAME).map(|wasm_path| {
+            let contract_key = arg_matches
+                .value_of(CONTRACT_KEY_ARG_NAME)
+                .expect("expected contract key");
+            (PathBuf::from(wasm_path), contract_key.to_string())
+        });
+
         Args {
             root_path,
             workspace_path,
+            schema,
         }
     }
 
     pub fn root_path(&self) -> &Path {
-        &self.root_path
+        self.root_path
+            .as_deref()
+            .expect("expected path unless emitting a schema")
     }
 
     pub fn workspace_path(&self) -> Option<&Path> {
         self.workspace_path.as_deref()
     }
+
+    /// Returns the path to the Wasm file and the contract's named key if emitting a schema.
+    pub fn schema(&self) -> Option<(&Path, &str)> {
+        self.schema
+            .as_ref()
+            .map(|(wasm_path, contract_key)| (wasm_path.as_path(), contract_key.as_str()))
+    }
 }
 
 fn main() {
+    if let Some((wasm_path, contract_key)) = ARGS.schema() {
+        emit_schema(wasm_path, contract_key);
+        return;
+    }
+
     if ARGS.root_path().exists() {
         common::print_error_and_exit(&format!(
             ": destination '{}' already exists",
@@ -135,6 +191,18 @@ fn main() {
     travis_yml::create();
 }
 
+#[cfg(feature = "schema")]
+fn emit_schema(wasm_path: &Path, contract_key: &str) {
+    schema::emit(wasm_path, contract_key);
+}
+
+#[cfg(not(feature = "schema"))]
+fn emit_schema(_wasm_path: &Path, _contract_key: &str) {
+    common::print_error_and_exit(
+        ": --schema is unavailable as cargo-casper was built without the 'schema' feature",
+    );
+}
+
 #[cfg(test)]
 mod tests {
     use std::{env, fs};
@@ -0,0 +1,84 @@
+//! Functions used to emit the schema of a contract when running the tool with `--schema`.
+
+use std::{fs, path::Path};
+
+use casper_engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT,
+        DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use casper_types::{
+    runtime_args, Contract, ContractHash, ContractPackageHash, ContractSchema, Key, RuntimeArgs,
+};
+
+use crate::common;
+
+/// Installs the contract by executing the Wasm at `wasm_path` as session code on a fresh
+/// in-memory network, then prints the schema of the contract stored under the installing
+/// account's named key `contract_key` to stdout.
+pub fn emit(wasm_path: &Path, contract_key: &str) {
+    let module_bytes = match fs::read(wasm_path) {
+        Ok(module_bytes) => module_bytes,
+        Err(error) => common::print_error_and_exit(&format!(
+            ": failed to read '{}': {}",
+            wasm_path.display(),
+            error
+        )),
+    };
+
+    let deploy = DeployItemBuilder::new()
+        .with_address(*DEFAULT_ACCOUNT_ADDR)
+        .with_session_bytes(module_bytes, RuntimeArgs::new())
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+        .with_authorization_keys(&[*DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([1; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    builder.exec(exec_request).commit();
+    if let Some(error) = builder.get_error() {
+        common::print_error_and_exit(&format!(
+            ": failed to execute '{}': {}",
+            wasm_path.display(),
+            error
+        ));
+    }
+
+    let contract = get_contract(&builder, contract_key).unwrap_or_else(|| {
+        common::print_error_and_exit(&format!(
+            ": '{}' did not store a contract under the named key '{}'",
+            wasm_path.display(),
+            contract_key
+        ))
+    });
+
+    let schema = ContractSchema::from(&contract);
+    match serde_json::to_string_pretty(&schema) {
+        Ok(json) => println!("{}", json),
+        Err(error) => {
+            common::print_error_and_exit(&format!(": failed to encode schema: {}", error))
+        }
+    }
+}
+
+/// Returns the contract stored under the default account's named key `contract_key`.  If the
+/// named key refers to a contract package, its current version is returned.
+fn get_contract(builder: &InMemoryWasmTestBuilder, contract_key: &str) -> Option<Contract> {
+    let account = builder.get_account(*DEFAULT_ACCOUNT_ADDR)?;
+    let hash_addr = match account.named_keys().get(contract_key)? {
+        Key::Hash(hash_addr) => *hash_addr,
+        _ => return None,
+    };
+
+    if let Some(contract) = builder.get_contract(ContractHash::new(hash_addr)) {
+        return Some(contract);
+    }
+    let contract_hash = builder
+        .get_contract_package(ContractPackageHash::new(hash_addr))?
+        .current_contract_hash()?;
+    builder.get_contract(contract_hash)
+}
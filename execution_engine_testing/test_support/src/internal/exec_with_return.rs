@@ -135,12 +135,14 @@ where
     let wasm_config = *DEFAULT_WASM_CONFIG;
 
     let preprocessor = Preprocessor::new(wasm_config);
+    let wasm_cache = builder.get_engine_state().wasm_cache().clone();
     let parity_module = deploy_item
         .get_deploy_metadata(
             tracking_copy,
             &account,
             correlation_id,
             &preprocessor,
+            &wasm_cache,
             &protocol_version,
             &protocol_data,
             phase,
@@ -156,6 +158,7 @@ where
     let mut runtime = Runtime::new(
         config,
         Default::default(),
+        wasm_cache,
         memory,
         module,
         context,
@@ -38,6 +38,10 @@ pub const DEFAULT_AUCTION_DELAY: u64 = 3;
 pub const DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS: u64 = 90 * 24 * 60 * 60 * 1000;
 /// Default number of eras that need to pass to be able to withdraw unbonded funds.
 pub const DEFAULT_UNBONDING_DELAY: u64 = 14;
+/// Default minimum amount of a new delegation, where zero means there is no minimum.
+pub const DEFAULT_MINIMUM_DELEGATION_AMOUNT: u64 = 0;
+/// Default maximum number of delegators per validator, where zero means there is no limit.
+pub const DEFAULT_MAX_DELEGATORS_PER_VALIDATOR: u32 = 0;
 
 /// Default round seigniorage rate represented as a fractional number.
 ///
@@ -106,6 +110,8 @@ pub static DEFAULT_EXEC_CONFIG: Lazy<ExecConfig> = Lazy::new(|| {
         DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS,
         DEFAULT_ROUND_SEIGNIORAGE_RATE,
         DEFAULT_UNBONDING_DELAY,
+        DEFAULT_MINIMUM_DELEGATION_AMOUNT,
+        DEFAULT_MAX_DELEGATORS_PER_VALIDATOR,
         DEFAULT_GENESIS_TIMESTAMP_MILLIS,
     )
 });
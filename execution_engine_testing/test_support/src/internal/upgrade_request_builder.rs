@@ -24,6 +24,8 @@ pub struct UpgradeRequestBuilder {
     new_locked_funds_period_millis: Option<u64>,
     new_round_seigniorage_rate: Option<Ratio<u64>>,
     new_unbonding_delay: Option<u64>,
+    new_minimum_delegation_amount: Option<u64>,
+    new_max_delegators_per_validator: Option<u32>,
     global_state_update: BTreeMap<Key, StoredValue>,
 }
 
@@ -79,6 +81,19 @@ impl UpgradeRequestBuilder {
         self
     }
 
+    pub fn with_new_minimum_delegation_amount(mut self, minimum_delegation_amount: u64) -> Self {
+        self.new_minimum_delegation_amount = Some(minimum_delegation_amount);
+        self
+    }
+
+    pub fn with_new_max_delegators_per_validator(
+        mut self,
+        max_delegators_per_validator: u32,
+    ) -> Self {
+        self.new_max_delegators_per_validator = Some(max_delegators_per_validator);
+        self
+    }
+
     pub fn with_new_system_config(mut self, new_system_config: SystemConfig) -> Self {
         self.new_system_config = Some(new_system_config);
         self
@@ -110,6 +125,8 @@ impl UpgradeRequestBuilder {
             self.new_locked_funds_period_millis,
             self.new_round_seigniorage_rate,
             self.new_unbonding_delay,
+            self.new_minimum_delegation_amount,
+            self.new_max_delegators_per_validator,
             self.global_state_update,
         )
     }
@@ -22,8 +22,8 @@ use casper_execution_engine::{
             execution_result::ExecutionResult,
             run_genesis_request::RunGenesisRequest,
             step::{StepRequest, StepResult},
-            BalanceResult, EngineConfig, EngineState, GenesisResult, GetBidsRequest, QueryRequest,
-            QueryResult, UpgradeConfig, UpgradeResult,
+            BalanceResult, EngineConfig, EngineState, ExecutionTrace, GenesisResult,
+            GetBidsRequest, QueryRequest, QueryResult, UpgradeConfig, UpgradeResult,
         },
         execution,
     },
@@ -90,6 +90,9 @@ pub struct WasmTestBuilder<S> {
     engine_state: Rc<EngineState<S>>,
     /// [`ExecutionResult`] is wrapped in [`Rc`] to work around a missing [`Clone`] implementation
     exec_results: Vec<Vec<Rc<ExecutionResult>>>,
+    /// Execution traces, recorded if enabled in the [`EngineConfig`], i.e. `exec_traces[0]` is for
+    /// first exec call etc.
+    exec_traces: Vec<Vec<ExecutionTrace>>,
     upgrade_results: Vec<Result<UpgradeResult, engine_state::Error>>,
     genesis_hash: Option<Blake2bHash>,
     post_state_hash: Option<Blake2bHash>,
@@ -119,8 +122,15 @@ impl<S> WasmTestBuilder<S> {
 
 impl Default for InMemoryWasmTestBuilder {
     fn default() -> Self {
+        Self::new_with_config(EngineConfig::default())
+    }
+}
+
+impl InMemoryWasmTestBuilder {
+    /// Returns an [`InMemoryWasmTestBuilder`] with an empty global state and the given engine
+    /// configuration.
+    pub fn new_with_config(engine_config: EngineConfig) -> Self {
         Self::initialize_logging();
-        let engine_config = EngineConfig::default();
 
         let global_state = InMemoryGlobalState::empty().expect("should create global state");
         let engine_state = EngineState::new(global_state, engine_config);
@@ -128,6 +138,7 @@ impl Default for InMemoryWasmTestBuilder {
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_results: Vec::new(),
+            exec_traces: Vec::new(),
             upgrade_results: Vec::new(),
             genesis_hash: None,
             post_state_hash: None,
@@ -149,6 +160,7 @@ impl<S> Clone for WasmTestBuilder<S> {
         WasmTestBuilder {
             engine_state: Rc::clone(&self.engine_state),
             exec_results: self.exec_results.clone(),
+            exec_traces: self.exec_traces.clone(),
             upgrade_results: self.upgrade_results.clone(),
             genesis_hash: self.genesis_hash,
             post_state_hash: self.post_state_hash,
@@ -222,6 +234,7 @@ impl LmdbWasmTestBuilder {
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_results: Vec::new(),
+            exec_traces: Vec::new(),
             upgrade_results: Vec::new(),
             genesis_hash: None,
             post_state_hash: None,
@@ -298,6 +311,7 @@ impl LmdbWasmTestBuilder {
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_results: Vec::new(),
+            exec_traces: Vec::new(),
             upgrade_results: Vec::new(),
             genesis_hash: None,
             post_state_hash: Some(post_state_hash),
@@ -338,6 +352,7 @@ where
         WasmTestBuilder {
             engine_state: result.0.engine_state,
             exec_results: Vec::new(),
+            exec_traces: Vec::new(),
             upgrade_results: Vec::new(),
             genesis_hash: result.0.genesis_hash,
             post_state_hash: result.0.post_state_hash,
@@ -483,23 +498,20 @@ where
         };
         let maybe_exec_results = self
             .engine_state
-            .run_execute(CorrelationId::new(), exec_request);
+            .run_execute_with_traces(CorrelationId::new(), exec_request);
         assert!(maybe_exec_results.is_ok());
         // Parse deploy results
-        let execution_results = maybe_exec_results.as_ref().unwrap();
+        let (execution_results, execution_traces): (Vec<_>, Vec<_>) =
+            maybe_exec_results.unwrap().into_iter().unzip();
         // Cache transformations
         self.transforms.extend(
             execution_results
                 .iter()
                 .map(|res| res.effect().transforms.clone()),
         );
-        self.exec_results.push(
-            maybe_exec_results
-                .unwrap()
-                .into_iter()
-                .map(Rc::new)
-                .collect(),
-        );
+        self.exec_results
+            .push(execution_results.into_iter().map(Rc::new).collect());
+        self.exec_traces.push(execution_traces);
         self
     }
 
@@ -726,6 +738,12 @@ where
         self.exec_results.get(index)
     }
 
+    /// Returns the execution traces of the exec call at `index`, which are empty unless tracing is
+    /// enabled in the [`EngineConfig`].
+    pub fn get_exec_traces(&self, index: usize) -> Option<&Vec<ExecutionTrace>> {
+        self.exec_traces.get(index)
+    }
+
     pub fn get_exec_results_count(&self) -> usize {
         self.exec_results.len()
     }
@@ -0,0 +1,183 @@
+use assert_matches::assert_matches;
+
+use casper_engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use casper_execution_engine::core::{engine_state::Error, execution};
+use casper_types::{
+    runtime_args, CLValue, ContractHash, Key, RuntimeArgs, EVENT_PAYLOAD_MAX_LENGTH,
+    MAX_EVENTS_PER_DEPLOY,
+};
+
+const EMIT_EVENT_WASM: &str = "emit_event.wasm";
+const HASH_KEY_NAME: &str = "emit_event_hash";
+const EMIT_ENTRY_POINT: &str = "emit";
+const EMIT_FROM_SESSION_ENTRY_POINT: &str = "emit_from_session";
+const EMIT_MANY_ENTRY_POINT: &str = "emit_many";
+const EMIT_LARGE_ENTRY_POINT: &str = "emit_large";
+const ARG_TOPIC: &str = "topic";
+const ARG_VALUE: &str = "value";
+const ARG_COUNT: &str = "count";
+const ARG_SIZE: &str = "size";
+const TOPIC: &str = "transfer";
+const VALUE: u64 = 42;
+
+fn setup() -> (InMemoryWasmTestBuilder, ContractHash) {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let install_request =
+        ExecuteRequestBuilder::standard(*DEFAULT_ACCOUNT_ADDR, EMIT_EVENT_WASM, runtime_args! {})
+            .build();
+    builder.exec(install_request).commit().expect_success();
+
+    let contract_hash = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account")
+        .named_keys()
+        .get(HASH_KEY_NAME)
+        .cloned()
+        .and_then(Key::into_hash)
+        .map(ContractHash::new)
+        .expect("should have contract hash");
+
+    (builder, contract_hash)
+}
+
+#[ignore]
+#[test]
+fn should_record_event_emitted_by_stored_contract() {
+    let (mut builder, contract_hash) = setup();
+
+    let emit_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        contract_hash,
+        EMIT_ENTRY_POINT,
+        runtime_args! {
+            ARG_TOPIC => TOPIC,
+            ARG_VALUE => VALUE,
+        },
+    )
+    .build();
+    builder.exec(emit_request).commit().expect_success();
+
+    let exec_result = builder
+        .get_exec_results()
+        .last()
+        .and_then(|results| results.last())
+        .expect("should have exec result");
+    let events = exec_result.events();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].contract_hash, contract_hash);
+    assert_eq!(events[0].topic, TOPIC);
+    assert_eq!(events[0].payload, CLValue::from_t(VALUE).unwrap());
+}
+
+#[ignore]
+#[test]
+fn should_not_emit_event_outside_of_stored_contract() {
+    let (mut builder, contract_hash) = setup();
+
+    let emit_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        contract_hash,
+        EMIT_FROM_SESSION_ENTRY_POINT,
+        runtime_args! {
+            ARG_TOPIC => TOPIC,
+            ARG_VALUE => VALUE,
+        },
+    )
+    .build();
+    builder.exec(emit_request).commit();
+
+    let exec_result = builder
+        .get_exec_results()
+        .last()
+        .and_then(|results| results.last())
+        .expect("should have exec result");
+    assert!(exec_result.events().is_empty());
+    let error = exec_result.as_error().expect("should have error");
+    assert_matches!(error, Error::Exec(execution::Error::InvalidContext));
+}
+
+#[ignore]
+#[test]
+fn should_limit_number_of_events_per_deploy() {
+    let (mut builder, contract_hash) = setup();
+
+    let emit_request = |count: usize| {
+        ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            EMIT_MANY_ENTRY_POINT,
+            runtime_args! {
+                ARG_TOPIC => TOPIC,
+                ARG_VALUE => VALUE,
+                ARG_COUNT => count as u32,
+            },
+        )
+        .build()
+    };
+
+    builder
+        .exec(emit_request(MAX_EVENTS_PER_DEPLOY))
+        .commit()
+        .expect_success();
+    let exec_result = builder
+        .get_exec_results()
+        .last()
+        .and_then(|results| results.last())
+        .expect("should have exec result");
+    assert_eq!(exec_result.events().len(), MAX_EVENTS_PER_DEPLOY);
+
+    builder
+        .exec(emit_request(MAX_EVENTS_PER_DEPLOY + 1))
+        .commit();
+    let exec_result = builder
+        .get_exec_results()
+        .last()
+        .and_then(|results| results.last())
+        .expect("should have exec result");
+    let error = exec_result.as_error().expect("should have error");
+    assert_matches!(error, Error::Exec(execution::Error::TooManyEvents));
+}
+
+#[ignore]
+#[test]
+fn should_limit_event_payload_length() {
+    let (mut builder, contract_hash) = setup();
+
+    let emit_request = |size: usize| {
+        ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            EMIT_LARGE_ENTRY_POINT,
+            runtime_args! {
+                ARG_TOPIC => TOPIC,
+                ARG_SIZE => size as u32,
+            },
+        )
+        .build()
+    };
+
+    builder
+        .exec(emit_request(EVENT_PAYLOAD_MAX_LENGTH / 2))
+        .commit()
+        .expect_success();
+
+    builder
+        .exec(emit_request(EVENT_PAYLOAD_MAX_LENGTH))
+        .commit();
+    let exec_result = builder
+        .get_exec_results()
+        .last()
+        .and_then(|results| results.last())
+        .expect("should have exec result");
+    assert!(exec_result.events().is_empty());
+    let error = exec_result.as_error().expect("should have error");
+    assert_matches!(
+        error,
+        Error::Exec(execution::Error::EventPayloadExceedsLength)
+    );
+}
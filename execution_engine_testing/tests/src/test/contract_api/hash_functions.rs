@@ -0,0 +1,131 @@
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+use casper_engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use casper_execution_engine::shared::{gas::Gas, host_function_costs::HostFunctionCosts};
+use casper_types::{bytesrepr::Bytes, runtime_args, RuntimeArgs};
+
+const HASH_FUNCTIONS_WASM: &str = "hash_functions.wasm";
+const ARG_ALGORITHM: &str = "algorithm";
+const ARG_BYTES: &str = "bytes";
+const HASH_RESULT: &str = "hash_result";
+
+const ALGORITHM_SHA256: &str = "sha256";
+const ALGORITHM_KECCAK256: &str = "keccak256";
+
+const RUNS: usize = 20;
+const MAX_INPUT_LENGTH: usize = 256;
+
+fn exec_hash(builder: &mut InMemoryWasmTestBuilder, algorithm: &str, input: &[u8]) -> Gas {
+    let exec_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        HASH_FUNCTIONS_WASM,
+        runtime_args! {
+            ARG_ALGORITHM => algorithm,
+            ARG_BYTES => Bytes::from(input),
+        },
+    )
+    .build();
+
+    builder.exec(exec_request).commit().expect_success();
+    builder.last_exec_gas_cost()
+}
+
+fn get_digest(builder: &InMemoryWasmTestBuilder) -> [u8; 32] {
+    let account = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+
+    let uref = account
+        .named_keys()
+        .get(HASH_RESULT)
+        .expect("should have value");
+
+    builder
+        .query(None, *uref, &[])
+        .expect("should query")
+        .as_cl_value()
+        .cloned()
+        .expect("should be CLValue")
+        .into_t()
+        .expect("should convert")
+}
+
+fn random_input(rng: &mut impl Rng) -> Vec<u8> {
+    let length = rng.gen_range(0..=MAX_INPUT_LENGTH);
+    (0..length).map(|_| rng.gen()).collect()
+}
+
+#[ignore]
+#[test]
+fn should_hash_with_sha256() {
+    let mut rng = rand::thread_rng();
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    for _ in 0..RUNS {
+        let input = random_input(&mut rng);
+        exec_hash(&mut builder, ALGORITHM_SHA256, &input);
+
+        let digest = get_digest(&builder);
+        let expected_digest = Sha256::digest(&input);
+        assert_eq!(&digest[..], expected_digest.as_slice());
+    }
+}
+
+#[ignore]
+#[test]
+fn should_hash_with_keccak256() {
+    let mut rng = rand::thread_rng();
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    for _ in 0..RUNS {
+        let input = random_input(&mut rng);
+        exec_hash(&mut builder, ALGORITHM_KECCAK256, &input);
+
+        let digest = get_digest(&builder);
+        let expected_digest = Keccak256::digest(&input);
+        assert_eq!(&digest[..], expected_digest.as_slice());
+    }
+}
+
+#[ignore]
+#[test]
+fn should_charge_for_hashed_bytes() {
+    const SMALL_INPUT_LENGTH: usize = 1;
+    const LARGE_INPUT_LENGTH: usize = 1_001;
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let host_function_costs = HostFunctionCosts::default();
+
+    for (algorithm, host_function) in [
+        (ALGORITHM_SHA256, host_function_costs.sha256),
+        (ALGORITHM_KECCAK256, host_function_costs.keccak256),
+    ]
+    .iter()
+    {
+        let small_cost = exec_hash(&mut builder, algorithm, &[0; SMALL_INPUT_LENGTH]);
+        let large_cost = exec_hash(&mut builder, algorithm, &[0; LARGE_INPUT_LENGTH]);
+
+        let input_size_weight = host_function.arguments()[1];
+        let expected_minimum_difference = Gas::from(input_size_weight)
+            * Gas::from((LARGE_INPUT_LENGTH - SMALL_INPUT_LENGTH) as u64);
+        assert!(
+            large_cost - small_cost >= expected_minimum_difference,
+            "{} should charge at least {} more gas for the larger input, but charged {} more",
+            algorithm,
+            expected_minimum_difference,
+            large_cost - small_cost
+        );
+    }
+}
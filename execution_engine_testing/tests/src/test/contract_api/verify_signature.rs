@@ -0,0 +1,179 @@
+use casper_engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use casper_execution_engine::core::{engine_state::Error as EngineError, execution::Error};
+use casper_types::{
+    bytesrepr::{Bytes, FromBytes, ToBytes},
+    crypto, runtime_args, ApiError, CLTyped, PublicKey, RuntimeArgs, SecretKey, Signature,
+};
+
+const VERIFY_SIGNATURE_WASM: &str = "verify_signature.wasm";
+const ARG_PUBLIC_KEY: &str = "public_key";
+const ARG_MESSAGE: &str = "message";
+const ARG_SIGNATURE: &str = "signature";
+const ARG_RECOVERY_ID: &str = "recovery_id";
+const IS_VALID: &str = "is_valid";
+const RECOVERED_PUBLIC_KEY: &str = "recovered_public_key";
+
+const MESSAGE: &[u8] = b"message to sign";
+
+fn ed25519_key_pair() -> (SecretKey, PublicKey) {
+    let secret_key = SecretKey::ed25519_from_bytes([1; SecretKey::ED25519_LENGTH]).unwrap();
+    let public_key = PublicKey::from(&secret_key);
+    (secret_key, public_key)
+}
+
+fn secp256k1_key_pair() -> (SecretKey, PublicKey) {
+    let secret_key = SecretKey::secp256k1_from_bytes([1; SecretKey::SECP256K1_LENGTH]).unwrap();
+    let public_key = PublicKey::from(&secret_key);
+    (secret_key, public_key)
+}
+
+fn exec_verify_signature(
+    builder: &mut InMemoryWasmTestBuilder,
+    public_key: PublicKey,
+    message: &[u8],
+    signature: &Signature,
+    recovery_id: u8,
+) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        VERIFY_SIGNATURE_WASM,
+        runtime_args! {
+            ARG_PUBLIC_KEY => public_key,
+            ARG_MESSAGE => Bytes::from(message),
+            ARG_SIGNATURE => Bytes::from(signature.to_bytes().unwrap()),
+            ARG_RECOVERY_ID => recovery_id,
+        },
+    )
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+fn get_named_value<T: CLTyped + FromBytes>(builder: &InMemoryWasmTestBuilder, name: &str) -> T {
+    let account = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+
+    let uref = account.named_keys().get(name).expect("should have value");
+
+    builder
+        .query(None, *uref, &[])
+        .expect("should query")
+        .as_cl_value()
+        .cloned()
+        .expect("should be CLValue")
+        .into_t()
+        .expect("should convert")
+}
+
+fn find_recovery_id(signature: &Signature, public_key: &PublicKey) -> u8 {
+    (0..2)
+        .find(|recovery_id| {
+            crypto::recover_secp256k1(MESSAGE, signature, *recovery_id).ok()
+                == Some(public_key.clone())
+        })
+        .expect("should find recovery id")
+}
+
+#[ignore]
+#[test]
+fn should_verify_valid_ed25519_signature() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let (secret_key, public_key) = ed25519_key_pair();
+    let signature = crypto::sign(MESSAGE, &secret_key, &public_key);
+
+    exec_verify_signature(&mut builder, public_key, MESSAGE, &signature, 0);
+    builder.expect_success();
+
+    let is_valid: bool = get_named_value(&builder, IS_VALID);
+    assert!(is_valid);
+}
+
+#[ignore]
+#[test]
+fn should_not_verify_ed25519_signature_of_other_message() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let (secret_key, public_key) = ed25519_key_pair();
+    let signature = crypto::sign(b"other message", &secret_key, &public_key);
+
+    exec_verify_signature(&mut builder, public_key, MESSAGE, &signature, 0);
+    builder.expect_success();
+
+    let is_valid: bool = get_named_value(&builder, IS_VALID);
+    assert!(!is_valid);
+}
+
+#[ignore]
+#[test]
+fn should_verify_secp256k1_signature_and_recover_public_key() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let (secret_key, public_key) = secp256k1_key_pair();
+    let signature = crypto::sign(MESSAGE, &secret_key, &public_key);
+    let recovery_id = find_recovery_id(&signature, &public_key);
+
+    exec_verify_signature(
+        &mut builder,
+        public_key.clone(),
+        MESSAGE,
+        &signature,
+        recovery_id,
+    );
+    builder.expect_success();
+
+    let is_valid: bool = get_named_value(&builder, IS_VALID);
+    assert!(is_valid);
+    let recovered_public_key: PublicKey = get_named_value(&builder, RECOVERED_PUBLIC_KEY);
+    assert_eq!(recovered_public_key, public_key);
+}
+
+#[ignore]
+#[test]
+fn should_not_verify_signature_with_mismatched_key_type() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let (secret_key, public_key) = secp256k1_key_pair();
+    let signature = crypto::sign(MESSAGE, &secret_key, &public_key);
+    let (_, ed25519_public_key) = ed25519_key_pair();
+
+    exec_verify_signature(&mut builder, ed25519_public_key, MESSAGE, &signature, 0);
+    builder.expect_success();
+
+    let is_valid: bool = get_named_value(&builder, IS_VALID);
+    assert!(!is_valid);
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_recover_public_key_with_invalid_recovery_id() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let (secret_key, public_key) = secp256k1_key_pair();
+    let signature = crypto::sign(MESSAGE, &secret_key, &public_key);
+
+    exec_verify_signature(&mut builder, public_key, MESSAGE, &signature, 4);
+
+    let exec_results = builder
+        .get_exec_results()
+        .last()
+        .expect("should have results");
+    assert_eq!(exec_results.len(), 1);
+    let error = exec_results[0].as_error().expect("should have error");
+    assert!(
+        matches!(
+            error,
+            EngineError::Exec(Error::Revert(ApiError::InvalidArgument))
+        ),
+        "Received error {:?}",
+        error
+    );
+}
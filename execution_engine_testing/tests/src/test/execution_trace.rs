@@ -0,0 +1,117 @@
+use casper_engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use casper_execution_engine::core::engine_state::{EngineConfig, ExecutionTrace, TraceEntry};
+use casper_types::{bytesrepr::Bytes, runtime_args, system::CallStackElement, RuntimeArgs};
+
+const HASH_FUNCTIONS_WASM: &str = "hash_functions.wasm";
+const ARG_ALGORITHM: &str = "algorithm";
+const ARG_BYTES: &str = "bytes";
+const ALGORITHM_SHA256: &str = "sha256";
+const HASH_RESULT: &str = "hash_result";
+
+fn exec_and_get_trace(builder: &mut InMemoryWasmTestBuilder) -> ExecutionTrace {
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        HASH_FUNCTIONS_WASM,
+        runtime_args! {
+            ARG_ALGORITHM => ALGORITHM_SHA256,
+            ARG_BYTES => Bytes::from(vec![1, 2, 3]),
+        },
+    )
+    .build();
+
+    builder.exec(exec_request).commit().expect_success();
+
+    let mut traces = builder
+        .get_exec_traces(0)
+        .expect("should have traces")
+        .clone();
+    assert_eq!(traces.len(), 1);
+    traces.remove(0)
+}
+
+#[ignore]
+#[test]
+fn should_record_execution_trace() {
+    let engine_config = EngineConfig::default().with_trace_execution(true);
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+
+    let trace = exec_and_get_trace(&mut builder);
+    let entries = trace.entries();
+
+    let sha256_call_position = entries
+        .iter()
+        .position(|entry| match entry {
+            TraceEntry::HostFunctionCall { name, args } => {
+                name == "Sha256FuncIndex" && args.len() == 4
+            }
+            _ => false,
+        })
+        .expect("should trace call to sha256");
+
+    let hash_result_key = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(HASH_RESULT)
+        .expect("should have hash result")
+        .normalize();
+    let hash_result_write_position = entries
+        .iter()
+        .position(|entry| {
+            *entry
+                == TraceEntry::Write {
+                    key: hash_result_key,
+                }
+        })
+        .expect("should trace write of hash result");
+    assert!(sha256_call_position < hash_result_write_position);
+
+    assert!(entries.iter().any(|entry| match entry {
+        TraceEntry::ContractCall {
+            entry_point,
+            call_stack,
+            ..
+        } => {
+            entry_point == "get_payment_purse"
+                && call_stack.first() == Some(&CallStackElement::session(*DEFAULT_ACCOUNT_ADDR))
+        }
+        _ => false,
+    }));
+
+    assert!(entries.iter().any(|entry| match entry {
+        TraceEntry::GasCharge { amount, gas_used } => !amount.is_zero() && gas_used >= amount,
+        _ => false,
+    }));
+    assert!(!trace.is_truncated());
+}
+
+#[ignore]
+#[test]
+fn should_truncate_execution_trace() {
+    const MAX_TRACE_ENTRIES: usize = 10;
+
+    let engine_config = EngineConfig::default()
+        .with_trace_execution(true)
+        .with_max_trace_entries(MAX_TRACE_ENTRIES);
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+
+    let trace = exec_and_get_trace(&mut builder);
+
+    assert_eq!(trace.entries().len(), MAX_TRACE_ENTRIES);
+    assert!(trace.is_truncated());
+}
+
+#[ignore]
+#[test]
+fn should_not_record_execution_trace_by_default() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    let trace = exec_and_get_trace(&mut builder);
+
+    assert!(trace.entries().is_empty());
+}
@@ -3,6 +3,7 @@ mod contract_api;
 mod contract_context;
 mod counter;
 mod deploy;
+mod execution_trace;
 mod explorer;
 mod gas_counter;
 mod get_balance;
@@ -15,4 +16,5 @@ mod storage_costs;
 mod system_contracts;
 mod system_costs;
 mod upgrade;
+mod wasm_cache;
 mod wasmless_transfer;
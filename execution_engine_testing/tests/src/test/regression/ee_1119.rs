@@ -249,8 +249,9 @@ fn should_run_ee_1119_dont_slash_delegated_validators() {
 
     let total_supply_after_slashing: U512 =
         builder.get_value(builder.get_mint_contract_hash(), TOTAL_SUPPLY_KEY);
+    // The remaining delegated stake is burned along with the undelegated amount.
     assert_eq!(
         total_supply_before_slashing - total_supply_after_slashing,
-        U512::from(VALIDATOR_1_STAKE + UNDELEGATE_AMOUNT_1),
+        U512::from(VALIDATOR_1_STAKE + DELEGATE_AMOUNT_1),
     );
 }
@@ -0,0 +1,362 @@
+use once_cell::sync::Lazy;
+
+use casper_engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_AUCTION_DELAY, DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_GENESIS_TIMESTAMP_MILLIS,
+        DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_ROUND_SEIGNIORAGE_RATE, DEFAULT_RUN_GENESIS_REQUEST, DEFAULT_SYSTEM_CONFIG,
+        DEFAULT_UNBONDING_DELAY, DEFAULT_VALIDATOR_SLOTS, DEFAULT_WASM_CONFIG, SYSTEM_ADDR,
+    },
+    DEFAULT_ACCOUNT_ADDR, MINIMUM_ACCOUNT_CREATION_BALANCE,
+};
+use casper_execution_engine::{
+    core::{
+        engine_state::{
+            self,
+            genesis::{ExecConfig, GenesisAccount, GenesisValidator},
+            run_genesis_request::RunGenesisRequest,
+            ExecuteRequest,
+        },
+        execution,
+    },
+    shared::motes::Motes,
+};
+use casper_types::{
+    account::AccountHash,
+    api_error::ApiError,
+    runtime_args,
+    system::auction::{
+        self, DelegationRate, ARG_AMOUNT, ARG_DELEGATION_RATE, ARG_DELEGATOR, ARG_PUBLIC_KEY,
+        ARG_VALIDATOR, MAX_DELEGATORS_PER_VALIDATOR_KEY, MINIMUM_DELEGATION_AMOUNT_KEY,
+    },
+    EraId, ProtocolVersion, PublicKey, RuntimeArgs, SecretKey, U512,
+};
+
+const ARG_TARGET: &str = "target";
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const CONTRACT_ADD_BID: &str = "add_bid.wasm";
+const CONTRACT_DELEGATE: &str = "delegate.wasm";
+
+const TRANSFER_AMOUNT: u64 = MINIMUM_ACCOUNT_CREATION_BALANCE + 1000;
+const SYSTEM_TRANSFER_AMOUNT: u64 = MINIMUM_ACCOUNT_CREATION_BALANCE;
+
+const ADD_BID_AMOUNT: u64 = 95_000;
+const ADD_BID_DELEGATION_RATE: DelegationRate = 10;
+
+const MINIMUM_DELEGATION_AMOUNT: u64 = 100_000;
+const MAX_DELEGATORS_PER_VALIDATOR: u32 = 1;
+
+const GENESIS_VALIDATOR_BALANCE: u64 = 1_000_000_000;
+const GENESIS_VALIDATOR_BOND: u64 = 1_000_000;
+const GENESIS_DELEGATOR_BALANCE: u64 = 1_000_000_000;
+
+static VALIDATOR_PK: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([3; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+static VALIDATOR_ADDR: Lazy<AccountHash> = Lazy::new(|| AccountHash::from(&*VALIDATOR_PK));
+
+static DELEGATOR_1_PK: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([204; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+static DELEGATOR_1_ADDR: Lazy<AccountHash> = Lazy::new(|| AccountHash::from(&*DELEGATOR_1_PK));
+
+static DELEGATOR_2_PK: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([205; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+static DELEGATOR_2_ADDR: Lazy<AccountHash> = Lazy::new(|| AccountHash::from(&*DELEGATOR_2_PK));
+
+fn run_genesis_request(
+    accounts: Vec<GenesisAccount>,
+    minimum_delegation_amount: u64,
+    max_delegators_per_validator: u32,
+) -> RunGenesisRequest {
+    let exec_config = ExecConfig::new(
+        accounts,
+        *DEFAULT_WASM_CONFIG,
+        *DEFAULT_SYSTEM_CONFIG,
+        DEFAULT_VALIDATOR_SLOTS,
+        DEFAULT_AUCTION_DELAY,
+        DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS,
+        DEFAULT_ROUND_SEIGNIORAGE_RATE,
+        DEFAULT_UNBONDING_DELAY,
+        minimum_delegation_amount,
+        max_delegators_per_validator,
+        DEFAULT_GENESIS_TIMESTAMP_MILLIS,
+    );
+    RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    )
+}
+
+fn delegate_request(
+    delegator_addr: AccountHash,
+    delegator_pk: &PublicKey,
+    amount: u64,
+    protocol_version: ProtocolVersion,
+) -> ExecuteRequest {
+    ExecuteRequestBuilder::standard(
+        delegator_addr,
+        CONTRACT_DELEGATE,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(amount),
+            ARG_VALIDATOR => VALIDATOR_PK.clone(),
+            ARG_DELEGATOR => delegator_pk.clone(),
+        },
+    )
+    .with_protocol_version(protocol_version)
+    .build()
+}
+
+/// Funds the validator and both delegators, and adds a bid for the validator.
+fn setup(builder: &mut InMemoryWasmTestBuilder, protocol_version: ProtocolVersion) {
+    let fund_request = |target: AccountHash, amount: u64| {
+        ExecuteRequestBuilder::standard(
+            *DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            runtime_args! {
+                ARG_TARGET => target,
+                ARG_AMOUNT => U512::from(amount)
+            },
+        )
+        .with_protocol_version(protocol_version)
+        .build()
+    };
+
+    let add_bid_request = ExecuteRequestBuilder::standard(
+        *VALIDATOR_ADDR,
+        CONTRACT_ADD_BID,
+        runtime_args! {
+            ARG_PUBLIC_KEY => VALIDATOR_PK.clone(),
+            ARG_AMOUNT => U512::from(ADD_BID_AMOUNT),
+            ARG_DELEGATION_RATE => ADD_BID_DELEGATION_RATE,
+        },
+    )
+    .with_protocol_version(protocol_version)
+    .build();
+
+    let post_genesis_requests = vec![
+        fund_request(*SYSTEM_ADDR, SYSTEM_TRANSFER_AMOUNT),
+        fund_request(*VALIDATOR_ADDR, TRANSFER_AMOUNT),
+        fund_request(*DELEGATOR_1_ADDR, TRANSFER_AMOUNT),
+        fund_request(*DELEGATOR_2_ADDR, TRANSFER_AMOUNT),
+        add_bid_request,
+    ];
+
+    for request in post_genesis_requests {
+        builder.exec(request).commit().expect_success();
+    }
+}
+
+fn setup_with_limits() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request(
+        DEFAULT_ACCOUNTS.clone(),
+        MINIMUM_DELEGATION_AMOUNT,
+        MAX_DELEGATORS_PER_VALIDATOR,
+    ));
+    setup(&mut builder, *DEFAULT_PROTOCOL_VERSION);
+    builder
+}
+
+fn assert_auction_error(builder: &InMemoryWasmTestBuilder, expected: auction::Error) {
+    let error = builder.get_error().expect("should have error");
+    assert!(matches!(
+        error,
+        engine_state::Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == expected as u8
+    ));
+}
+
+fn delegated_amount(builder: &mut InMemoryWasmTestBuilder, delegator: &PublicKey) -> Option<U512> {
+    builder
+        .get_bids()
+        .get(&*VALIDATOR_PK)
+        .expect("should have bid")
+        .delegators()
+        .get(delegator)
+        .map(|delegator| *delegator.staked_amount())
+}
+
+#[ignore]
+#[test]
+fn should_reject_delegation_below_minimum_amount() {
+    let mut builder = setup_with_limits();
+
+    builder
+        .exec(delegate_request(
+            *DELEGATOR_1_ADDR,
+            &*DELEGATOR_1_PK,
+            MINIMUM_DELEGATION_AMOUNT - 1,
+            *DEFAULT_PROTOCOL_VERSION,
+        ))
+        .commit();
+
+    assert_auction_error(&builder, auction::Error::DelegationAmountTooSmall);
+    assert_eq!(delegated_amount(&mut builder, &*DELEGATOR_1_PK), None);
+}
+
+#[ignore]
+#[test]
+fn should_allow_increasing_existing_delegation_by_less_than_minimum_amount() {
+    let mut builder = setup_with_limits();
+
+    builder
+        .exec(delegate_request(
+            *DELEGATOR_1_ADDR,
+            &*DELEGATOR_1_PK,
+            MINIMUM_DELEGATION_AMOUNT,
+            *DEFAULT_PROTOCOL_VERSION,
+        ))
+        .commit()
+        .expect_success();
+    builder
+        .exec(delegate_request(
+            *DELEGATOR_1_ADDR,
+            &*DELEGATOR_1_PK,
+            1,
+            *DEFAULT_PROTOCOL_VERSION,
+        ))
+        .commit()
+        .expect_success();
+
+    assert_eq!(
+        delegated_amount(&mut builder, &*DELEGATOR_1_PK),
+        Some(U512::from(MINIMUM_DELEGATION_AMOUNT + 1))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_delegation_exceeding_max_delegators_per_validator() {
+    let mut builder = setup_with_limits();
+
+    builder
+        .exec(delegate_request(
+            *DELEGATOR_1_ADDR,
+            &*DELEGATOR_1_PK,
+            MINIMUM_DELEGATION_AMOUNT,
+            *DEFAULT_PROTOCOL_VERSION,
+        ))
+        .commit()
+        .expect_success();
+    builder
+        .exec(delegate_request(
+            *DELEGATOR_2_ADDR,
+            &*DELEGATOR_2_PK,
+            MINIMUM_DELEGATION_AMOUNT,
+            *DEFAULT_PROTOCOL_VERSION,
+        ))
+        .commit();
+
+    assert_auction_error(&builder, auction::Error::ExceededDelegatorSizeLimit);
+    assert_eq!(delegated_amount(&mut builder, &*DELEGATOR_2_PK), None);
+}
+
+#[ignore]
+#[test]
+fn should_apply_delegation_limits_set_by_upgrade() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let old_protocol_version = *DEFAULT_PROTOCOL_VERSION;
+    let sem_ver = old_protocol_version.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(old_protocol_version)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(EraId::from(0))
+        .with_new_minimum_delegation_amount(MINIMUM_DELEGATION_AMOUNT)
+        .with_new_max_delegators_per_validator(MAX_DELEGATORS_PER_VALIDATOR)
+        .build();
+    builder
+        .upgrade_with_upgrade_request(&mut upgrade_request)
+        .expect_upgrade_success();
+
+    let auction_hash = builder.get_auction_contract_hash();
+    let minimum_delegation_amount: u64 =
+        builder.get_value(auction_hash, MINIMUM_DELEGATION_AMOUNT_KEY);
+    assert_eq!(minimum_delegation_amount, MINIMUM_DELEGATION_AMOUNT);
+    let max_delegators_per_validator: u32 =
+        builder.get_value(auction_hash, MAX_DELEGATORS_PER_VALIDATOR_KEY);
+    assert_eq!(max_delegators_per_validator, MAX_DELEGATORS_PER_VALIDATOR);
+
+    setup(&mut builder, new_protocol_version);
+
+    builder
+        .exec(delegate_request(
+            *DELEGATOR_1_ADDR,
+            &*DELEGATOR_1_PK,
+            MINIMUM_DELEGATION_AMOUNT - 1,
+            new_protocol_version,
+        ))
+        .commit();
+
+    assert_auction_error(&builder, auction::Error::DelegationAmountTooSmall);
+}
+
+#[should_panic(expected = "DelegatedAmountTooSmall")]
+#[ignore]
+#[test]
+fn should_validate_genesis_delegators_minimum_amount() {
+    let mut accounts = DEFAULT_ACCOUNTS.clone();
+    accounts.push(GenesisAccount::account(
+        VALIDATOR_PK.clone(),
+        Motes::new(GENESIS_VALIDATOR_BALANCE.into()),
+        Some(GenesisValidator::new(
+            Motes::new(GENESIS_VALIDATOR_BOND.into()),
+            ADD_BID_DELEGATION_RATE,
+        )),
+    ));
+    accounts.push(GenesisAccount::delegator(
+        VALIDATOR_PK.clone(),
+        DELEGATOR_1_PK.clone(),
+        Motes::new(GENESIS_DELEGATOR_BALANCE.into()),
+        Motes::new((MINIMUM_DELEGATION_AMOUNT - 1).into()),
+    ));
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request(
+        accounts,
+        MINIMUM_DELEGATION_AMOUNT,
+        MAX_DELEGATORS_PER_VALIDATOR,
+    ));
+}
+
+#[should_panic(expected = "ExceededDelegatorSizeLimit")]
+#[ignore]
+#[test]
+fn should_validate_genesis_max_delegators_per_validator() {
+    let mut accounts = DEFAULT_ACCOUNTS.clone();
+    accounts.push(GenesisAccount::account(
+        VALIDATOR_PK.clone(),
+        Motes::new(GENESIS_VALIDATOR_BALANCE.into()),
+        Some(GenesisValidator::new(
+            Motes::new(GENESIS_VALIDATOR_BOND.into()),
+            ADD_BID_DELEGATION_RATE,
+        )),
+    ));
+    for delegator_pk in [DELEGATOR_1_PK.clone(), DELEGATOR_2_PK.clone()].iter() {
+        accounts.push(GenesisAccount::delegator(
+            VALIDATOR_PK.clone(),
+            delegator_pk.clone(),
+            Motes::new(GENESIS_DELEGATOR_BALANCE.into()),
+            Motes::new(MINIMUM_DELEGATION_AMOUNT.into()),
+        ));
+    }
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request(
+        accounts,
+        MINIMUM_DELEGATION_AMOUNT,
+        MAX_DELEGATORS_PER_VALIDATOR,
+    ));
+}
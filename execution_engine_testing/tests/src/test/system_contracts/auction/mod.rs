@@ -1,2 +1,5 @@
 mod bids;
+mod delegation_limits;
 mod distribute;
+mod redelegate;
+mod slashing;
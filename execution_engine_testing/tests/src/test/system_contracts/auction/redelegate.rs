@@ -0,0 +1,308 @@
+use once_cell::sync::Lazy;
+
+use casper_engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GENESIS_TIMESTAMP_MILLIS,
+        DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS, DEFAULT_RUN_GENESIS_REQUEST, DEFAULT_UNBONDING_DELAY,
+        SYSTEM_ADDR, TIMESTAMP_MILLIS_INCREMENT,
+    },
+    DEFAULT_ACCOUNT_ADDR, MINIMUM_ACCOUNT_CREATION_BALANCE,
+};
+use casper_execution_engine::core::{
+    engine_state::{self, ExecuteRequest},
+    execution,
+};
+use casper_types::{
+    account::AccountHash,
+    api_error::ApiError,
+    runtime_args,
+    system::auction::{
+        self, DelegationRate, ARG_AMOUNT, ARG_DELEGATION_RATE, ARG_DELEGATOR, ARG_NEW_VALIDATOR,
+        ARG_PUBLIC_KEY, ARG_VALIDATOR,
+    },
+    PublicKey, RuntimeArgs, SecretKey, URef, U512,
+};
+
+const ARG_TARGET: &str = "target";
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const CONTRACT_ADD_BID: &str = "add_bid.wasm";
+const CONTRACT_DELEGATE: &str = "delegate.wasm";
+const CONTRACT_REDELEGATE: &str = "redelegate.wasm";
+
+const TRANSFER_AMOUNT: u64 = MINIMUM_ACCOUNT_CREATION_BALANCE + 1000;
+const SYSTEM_TRANSFER_AMOUNT: u64 = MINIMUM_ACCOUNT_CREATION_BALANCE;
+
+const ADD_BID_AMOUNT: u64 = 95_000;
+const ADD_BID_DELEGATION_RATE: DelegationRate = 10;
+
+const DELEGATE_AMOUNT: u64 = 125_000;
+const REDELEGATE_AMOUNT: u64 = 35_000;
+
+static VALIDATOR_1_PK: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([3; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+static VALIDATOR_1_ADDR: Lazy<AccountHash> = Lazy::new(|| AccountHash::from(&*VALIDATOR_1_PK));
+
+static VALIDATOR_2_PK: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([4; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+static VALIDATOR_2_ADDR: Lazy<AccountHash> = Lazy::new(|| AccountHash::from(&*VALIDATOR_2_PK));
+
+static UNKNOWN_VALIDATOR_PK: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([5; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+
+static DELEGATOR_PK: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([204; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+static DELEGATOR_ADDR: Lazy<AccountHash> = Lazy::new(|| AccountHash::from(&*DELEGATOR_PK));
+
+/// Sets up two validators with bids and a delegator delegating `DELEGATE_AMOUNT` to validator 1.
+///
+/// Returns the builder along with the era-end timestamp of the next auction to run.
+fn setup() -> (InMemoryWasmTestBuilder, u64) {
+    let fund_request = |target: AccountHash, amount: u64| {
+        ExecuteRequestBuilder::standard(
+            *DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            runtime_args! {
+                ARG_TARGET => target,
+                ARG_AMOUNT => U512::from(amount)
+            },
+        )
+        .build()
+    };
+
+    let add_bid_request = |validator_addr: AccountHash, validator_pk: &PublicKey| {
+        ExecuteRequestBuilder::standard(
+            validator_addr,
+            CONTRACT_ADD_BID,
+            runtime_args! {
+                ARG_PUBLIC_KEY => validator_pk.clone(),
+                ARG_AMOUNT => U512::from(ADD_BID_AMOUNT),
+                ARG_DELEGATION_RATE => ADD_BID_DELEGATION_RATE,
+            },
+        )
+        .build()
+    };
+
+    let delegate_request = ExecuteRequestBuilder::standard(
+        *DELEGATOR_ADDR,
+        CONTRACT_DELEGATE,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(DELEGATE_AMOUNT),
+            ARG_VALIDATOR => VALIDATOR_1_PK.clone(),
+            ARG_DELEGATOR => DELEGATOR_PK.clone(),
+        },
+    )
+    .build();
+
+    let post_genesis_requests = vec![
+        fund_request(*SYSTEM_ADDR, SYSTEM_TRANSFER_AMOUNT),
+        fund_request(*VALIDATOR_1_ADDR, TRANSFER_AMOUNT),
+        fund_request(*VALIDATOR_2_ADDR, TRANSFER_AMOUNT),
+        fund_request(*DELEGATOR_ADDR, TRANSFER_AMOUNT),
+        add_bid_request(*VALIDATOR_1_ADDR, &*VALIDATOR_1_PK),
+        add_bid_request(*VALIDATOR_2_ADDR, &*VALIDATOR_2_PK),
+        delegate_request,
+    ];
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    for request in post_genesis_requests {
+        builder.exec(request).commit().expect_success();
+    }
+
+    let mut timestamp_millis =
+        DEFAULT_GENESIS_TIMESTAMP_MILLIS + DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS;
+    for _ in 0..5 {
+        builder.run_auction(timestamp_millis, Vec::new());
+        timestamp_millis += TIMESTAMP_MILLIS_INCREMENT;
+    }
+
+    (builder, timestamp_millis)
+}
+
+fn redelegate_request(new_validator: &PublicKey) -> ExecuteRequest {
+    ExecuteRequestBuilder::standard(
+        *DELEGATOR_ADDR,
+        CONTRACT_REDELEGATE,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(REDELEGATE_AMOUNT),
+            ARG_VALIDATOR => VALIDATOR_1_PK.clone(),
+            ARG_DELEGATOR => DELEGATOR_PK.clone(),
+            ARG_NEW_VALIDATOR => new_validator.clone(),
+        },
+    )
+    .build()
+}
+
+fn slash(builder: &mut InMemoryWasmTestBuilder, validator: &PublicKey) {
+    let slash_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *SYSTEM_ADDR,
+        builder.get_auction_contract_hash(),
+        auction::METHOD_SLASH,
+        runtime_args! {
+            auction::ARG_VALIDATOR_PUBLIC_KEYS => vec![validator.clone()]
+        },
+    )
+    .build();
+
+    builder.exec(slash_request).expect_success().commit();
+}
+
+/// Runs enough auctions for the unbonding delay of a request created in the current era to elapse.
+fn run_auctions_until_unbonded(builder: &mut InMemoryWasmTestBuilder, mut timestamp_millis: u64) {
+    for _ in 0..=DEFAULT_UNBONDING_DELAY {
+        builder.run_auction(timestamp_millis, Vec::new());
+        timestamp_millis += TIMESTAMP_MILLIS_INCREMENT;
+    }
+}
+
+fn delegated_amount(builder: &mut InMemoryWasmTestBuilder, validator: &PublicKey) -> Option<U512> {
+    builder
+        .get_bids()
+        .get(validator)
+        .expect("should have bid")
+        .delegators()
+        .get(&*DELEGATOR_PK)
+        .map(|delegator| *delegator.staked_amount())
+}
+
+fn delegator_main_purse(builder: &InMemoryWasmTestBuilder) -> URef {
+    builder
+        .get_account(*DELEGATOR_ADDR)
+        .expect("should have delegator account")
+        .main_purse()
+}
+
+#[ignore]
+#[test]
+fn should_redelegate_after_unbonding_delay() {
+    let (mut builder, timestamp_millis) = setup();
+
+    builder
+        .exec(redelegate_request(&*VALIDATOR_2_PK))
+        .commit()
+        .expect_success();
+
+    assert_eq!(
+        delegated_amount(&mut builder, &*VALIDATOR_1_PK),
+        Some(U512::from(DELEGATE_AMOUNT - REDELEGATE_AMOUNT))
+    );
+    assert_eq!(delegated_amount(&mut builder, &*VALIDATOR_2_PK), None);
+
+    let withdraws = builder.get_withdraws();
+    let unbonding_purses = withdraws
+        .get(&*VALIDATOR_1_ADDR)
+        .expect("should have unbonding purses");
+    assert_eq!(unbonding_purses.len(), 1);
+    assert_eq!(unbonding_purses[0].amount(), &U512::from(REDELEGATE_AMOUNT));
+    assert_eq!(
+        unbonding_purses[0].new_validator(),
+        &Some(VALIDATOR_2_PK.clone())
+    );
+
+    let main_purse = delegator_main_purse(&builder);
+    let main_purse_balance_before = builder.get_purse_balance(main_purse);
+
+    run_auctions_until_unbonded(&mut builder, timestamp_millis);
+
+    assert_eq!(
+        delegated_amount(&mut builder, &*VALIDATOR_2_PK),
+        Some(U512::from(REDELEGATE_AMOUNT))
+    );
+    assert!(builder
+        .get_withdraws()
+        .get(&*VALIDATOR_1_ADDR)
+        .expect("should have unbonding purses")
+        .is_empty());
+    // The redelegated amount is not paid out.
+    assert_eq!(
+        builder.get_purse_balance(main_purse),
+        main_purse_balance_before
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_redelegate_to_unknown_validator() {
+    let (mut builder, _) = setup();
+
+    builder
+        .exec(redelegate_request(&*UNKNOWN_VALIDATOR_PK))
+        .commit();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(matches!(
+        error,
+        engine_state::Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == auction::Error::ValidatorNotFound as u8
+    ));
+
+    assert_eq!(
+        delegated_amount(&mut builder, &*VALIDATOR_1_PK),
+        Some(U512::from(DELEGATE_AMOUNT))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_burn_redelegated_amount_if_validator_slashed_during_unbonding() {
+    let (mut builder, timestamp_millis) = setup();
+
+    builder
+        .exec(redelegate_request(&*VALIDATOR_2_PK))
+        .commit()
+        .expect_success();
+
+    slash(&mut builder, &*VALIDATOR_1_PK);
+
+    assert!(builder
+        .get_withdraws()
+        .get(&*VALIDATOR_1_ADDR)
+        .expect("should have unbonding purses")
+        .is_empty());
+
+    let main_purse = delegator_main_purse(&builder);
+    let main_purse_balance_before = builder.get_purse_balance(main_purse);
+
+    run_auctions_until_unbonded(&mut builder, timestamp_millis);
+
+    assert_eq!(delegated_amount(&mut builder, &*VALIDATOR_2_PK), None);
+    assert_eq!(
+        builder.get_purse_balance(main_purse),
+        main_purse_balance_before
+    );
+}
+
+#[ignore]
+#[test]
+fn should_pay_out_redelegated_amount_if_new_validator_slashed_during_unbonding() {
+    let (mut builder, timestamp_millis) = setup();
+
+    builder
+        .exec(redelegate_request(&*VALIDATOR_2_PK))
+        .commit()
+        .expect_success();
+
+    slash(&mut builder, &*VALIDATOR_2_PK);
+
+    let main_purse = delegator_main_purse(&builder);
+    let main_purse_balance_before = builder.get_purse_balance(main_purse);
+
+    run_auctions_until_unbonded(&mut builder, timestamp_millis);
+
+    assert_eq!(delegated_amount(&mut builder, &*VALIDATOR_2_PK), None);
+    assert_eq!(
+        builder.get_purse_balance(main_purse),
+        main_purse_balance_before + U512::from(REDELEGATE_AMOUNT)
+    );
+}
@@ -0,0 +1,231 @@
+use num_rational::Ratio;
+use num_traits::Zero;
+use once_cell::sync::Lazy;
+
+use casper_engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS, SYSTEM_ADDR,
+    },
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE, MINIMUM_ACCOUNT_CREATION_BALANCE,
+};
+use casper_execution_engine::{
+    core::engine_state::{
+        genesis::{GenesisAccount, GenesisValidator},
+        EngineConfig,
+    },
+    shared::motes::Motes,
+};
+use casper_types::{
+    runtime_args,
+    system::{
+        auction::{
+            Bids, DelegationRate, EraInfo, SlashHandling, SlashedStake, ARG_VALIDATOR_PUBLIC_KEYS,
+            METHOD_SLASH,
+        },
+        handle_payment::ACCUMULATION_PURSE_KEY,
+    },
+    Key, PublicKey, RuntimeArgs, SecretKey, U512,
+};
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+
+const TRANSFER_AMOUNT: u64 = MINIMUM_ACCOUNT_CREATION_BALANCE;
+
+const VALIDATOR_STAKE: u64 = 1_000_000;
+const DELEGATOR_STAKE: u64 = 500_000;
+
+static VALIDATOR: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([3; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+
+static DELEGATOR: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([204; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+
+fn setup(slashing_rate: Ratio<u64>, slash_handling: SlashHandling) -> InMemoryWasmTestBuilder {
+    let accounts = {
+        let validator = GenesisAccount::account(
+            VALIDATOR.clone(),
+            Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE.into()),
+            Some(GenesisValidator::new(
+                Motes::new(VALIDATOR_STAKE.into()),
+                DelegationRate::zero(),
+            )),
+        );
+        let delegator = GenesisAccount::delegator(
+            VALIDATOR.clone(),
+            DELEGATOR.clone(),
+            Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE.into()),
+            Motes::new(DELEGATOR_STAKE.into()),
+        );
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        tmp.push(validator);
+        tmp.push(delegator);
+        tmp
+    };
+    let run_genesis_request = utils::create_run_genesis_request(accounts);
+
+    let engine_config = EngineConfig::default()
+        .with_slashing_rate(slashing_rate)
+        .with_slash_handling(slash_handling);
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder.run_genesis(&run_genesis_request);
+
+    let fund_system_exec_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            "target" => *SYSTEM_ADDR,
+            "amount" => U512::from(TRANSFER_AMOUNT)
+        },
+    )
+    .build();
+
+    builder
+        .exec(fund_system_exec_request)
+        .expect_success()
+        .commit();
+
+    builder
+}
+
+fn slash_validator(builder: &mut InMemoryWasmTestBuilder) {
+    let auction = builder.get_auction_contract_hash();
+    let slash_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *SYSTEM_ADDR,
+        auction,
+        METHOD_SLASH,
+        runtime_args! {
+            ARG_VALIDATOR_PUBLIC_KEYS => vec![VALIDATOR.clone()]
+        },
+    )
+    .build();
+
+    builder.exec(slash_request).expect_success().commit();
+}
+
+fn get_era_info(builder: &mut InMemoryWasmTestBuilder) -> EraInfo {
+    let era = builder.get_era();
+    builder
+        .query(None, Key::EraInfo(era), &[])
+        .expect("should have value")
+        .as_era_info()
+        .cloned()
+        .expect("should be era info")
+}
+
+#[ignore]
+#[test]
+fn should_partially_slash_validator_and_delegators() {
+    let slashing_rate = Ratio::new(1, 10);
+    let mut builder = setup(slashing_rate, SlashHandling::Burn);
+
+    let total_supply_before = builder.total_supply(None);
+
+    slash_validator(&mut builder);
+
+    let validator_slashed_amount = U512::from(VALIDATOR_STAKE / 10);
+    let delegator_slashed_amount = U512::from(DELEGATOR_STAKE / 10);
+
+    let bids: Bids = builder.get_bids();
+    let validator_bid = bids.get(&VALIDATOR).expect("should have bid");
+    assert!(!validator_bid.inactive());
+    assert_eq!(
+        *validator_bid.staked_amount(),
+        U512::from(VALIDATOR_STAKE) - validator_slashed_amount
+    );
+    assert_eq!(
+        builder.get_purse_balance(*validator_bid.bonding_purse()),
+        U512::from(VALIDATOR_STAKE) - validator_slashed_amount
+    );
+
+    let delegator = validator_bid
+        .delegators()
+        .get(&DELEGATOR)
+        .expect("should have delegator");
+    assert_eq!(
+        *delegator.staked_amount(),
+        U512::from(DELEGATOR_STAKE) - delegator_slashed_amount
+    );
+
+    assert_eq!(
+        builder.total_supply(None),
+        total_supply_before - validator_slashed_amount - delegator_slashed_amount
+    );
+
+    let era_info = get_era_info(&mut builder);
+    assert_eq!(
+        era_info.slashed_stakes(),
+        &[
+            SlashedStake::validator(VALIDATOR.clone(), validator_slashed_amount),
+            SlashedStake::delegator(
+                DELEGATOR.clone(),
+                VALIDATOR.clone(),
+                delegator_slashed_amount
+            ),
+        ]
+    );
+}
+
+#[ignore]
+#[test]
+fn should_accumulate_slashed_stake() {
+    let mut builder = setup(Ratio::new(1, 2), SlashHandling::Accumulate);
+
+    let total_supply_before = builder.total_supply(None);
+
+    slash_validator(&mut builder);
+
+    let handle_payment = builder.get_handle_payment_contract();
+    let accumulation_purse = handle_payment
+        .named_keys()
+        .get(ACCUMULATION_PURSE_KEY)
+        .and_then(|key| key.into_uref())
+        .expect("should have accumulation purse");
+
+    assert_eq!(
+        builder.get_purse_balance(accumulation_purse),
+        U512::from((VALIDATOR_STAKE + DELEGATOR_STAKE) / 2)
+    );
+    assert_eq!(builder.total_supply(None), total_supply_before);
+}
+
+#[ignore]
+#[test]
+fn should_deactivate_fully_slashed_validator() {
+    let mut builder = setup(Ratio::new(1, 1), SlashHandling::Burn);
+
+    slash_validator(&mut builder);
+
+    let bids: Bids = builder.get_bids();
+    let validator_bid = bids.get(&VALIDATOR).expect("should have bid");
+    assert!(validator_bid.inactive());
+    assert!(validator_bid.staked_amount().is_zero());
+    assert!(validator_bid
+        .delegators()
+        .values()
+        .all(|delegator| delegator.staked_amount().is_zero()));
+
+    let era_info = get_era_info(&mut builder);
+    assert_eq!(era_info.slashed_stakes().len(), 2);
+    assert!(era_info
+        .slashed_stakes()
+        .iter()
+        .all(|slashed_stake| slashed_stake.validator_public_key() == &*VALIDATOR));
+}
+
+#[ignore]
+#[test]
+fn should_not_slash_with_zero_slashing_rate() {
+    let mut builder = setup(Ratio::new(0, 1), SlashHandling::Burn);
+
+    let total_supply_before = builder.total_supply(None);
+    let bids_before: Bids = builder.get_bids();
+
+    slash_validator(&mut builder);
+
+    assert_eq!(builder.get_bids(), bids_before);
+    assert_eq!(builder.total_supply(None), total_supply_before);
+}
@@ -0,0 +1,162 @@
+use num_rational::Ratio;
+
+use casper_engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_GAS_PRICE, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use casper_execution_engine::{
+    core::engine_state::{EngineConfig, DEFAULT_MAX_QUERY_DEPTH, DEFAULT_REFUND_RATIO},
+    shared::motes::Motes,
+};
+use casper_types::{
+    system::handle_payment::{FeeHandling, ACCUMULATION_PURSE_KEY},
+    RuntimeArgs, U512,
+};
+
+const DO_NOTHING_WASM: &str = "do_nothing.wasm";
+
+fn setup(refund_ratio: Ratio<u64>, fee_handling: FeeHandling) -> InMemoryWasmTestBuilder {
+    let engine_config = EngineConfig::new(DEFAULT_MAX_QUERY_DEPTH, refund_ratio, fee_handling);
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    builder
+}
+
+fn exec_do_nothing(builder: &mut InMemoryWasmTestBuilder) -> U512 {
+    let exec_request =
+        ExecuteRequestBuilder::standard(*DEFAULT_ACCOUNT_ADDR, DO_NOTHING_WASM, RuntimeArgs::new())
+            .build();
+
+    builder.exec(exec_request).expect_success().commit();
+
+    Motes::from_gas(builder.last_exec_gas_cost(), DEFAULT_GAS_PRICE)
+        .expect("should have motes")
+        .value()
+}
+
+fn default_account_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let account = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    builder.get_purse_balance(account.main_purse())
+}
+
+#[ignore]
+#[test]
+fn should_refund_ratio_of_unspent_payment() {
+    let refund_ratio = Ratio::new(1, 2);
+    let mut builder = setup(refund_ratio, FeeHandling::PayToProposer);
+
+    let account_balance_before = default_account_balance(&builder);
+    let proposer_balance_before = builder.get_proposer_purse_balance();
+
+    let cost = exec_do_nothing(&mut builder);
+
+    let unspent = *DEFAULT_PAYMENT - cost;
+    let refund = unspent * U512::from(*refund_ratio.numer()) / U512::from(*refund_ratio.denom());
+    assert!(!refund.is_zero());
+
+    let account_balance_after = default_account_balance(&builder);
+    let proposer_balance_after = builder.get_proposer_purse_balance();
+
+    assert_eq!(
+        account_balance_after,
+        account_balance_before - *DEFAULT_PAYMENT + refund
+    );
+    assert_eq!(
+        proposer_balance_after,
+        proposer_balance_before + *DEFAULT_PAYMENT - refund
+    );
+}
+
+#[ignore]
+#[test]
+fn should_burn_fees() {
+    let mut builder = setup(DEFAULT_REFUND_RATIO, FeeHandling::Burn);
+
+    let total_supply_before = builder.total_supply(None);
+    let proposer_balance_before = builder.get_proposer_purse_balance();
+    let account_balance_before = default_account_balance(&builder);
+
+    exec_do_nothing(&mut builder);
+
+    let total_supply_after = builder.total_supply(None);
+    let proposer_balance_after = builder.get_proposer_purse_balance();
+    let account_balance_after = default_account_balance(&builder);
+
+    assert_eq!(
+        account_balance_after,
+        account_balance_before - *DEFAULT_PAYMENT
+    );
+    assert_eq!(total_supply_after, total_supply_before - *DEFAULT_PAYMENT);
+    assert_eq!(proposer_balance_after, proposer_balance_before);
+}
+
+#[ignore]
+#[test]
+fn should_accumulate_fees() {
+    let mut builder = setup(DEFAULT_REFUND_RATIO, FeeHandling::Accumulate);
+
+    let total_supply_before = builder.total_supply(None);
+    let proposer_balance_before = builder.get_proposer_purse_balance();
+
+    exec_do_nothing(&mut builder);
+
+    let handle_payment = builder.get_handle_payment_contract();
+    let accumulation_purse = handle_payment
+        .named_keys()
+        .get(ACCUMULATION_PURSE_KEY)
+        .and_then(|key| key.into_uref())
+        .expect("should have accumulation purse");
+
+    assert_eq!(
+        builder.get_purse_balance(accumulation_purse),
+        *DEFAULT_PAYMENT
+    );
+    assert_eq!(
+        builder.get_proposer_purse_balance(),
+        proposer_balance_before
+    );
+    assert_eq!(builder.total_supply(None), total_supply_before);
+
+    let transfers = builder
+        .get_exec_results()
+        .last()
+        .expect("should have results")[0]
+        .transfers()
+        .clone();
+    let to_accumulation_purse = transfers.into_iter().any(|transfer_addr| {
+        let transfer = builder
+            .get_transfer(transfer_addr)
+            .expect("should have transfer");
+        transfer.target.addr() == accumulation_purse.addr()
+    });
+    assert!(to_accumulation_purse);
+
+    // The accumulation purse is reused by subsequent deploys.
+    exec_do_nothing(&mut builder);
+
+    assert_eq!(
+        builder.get_purse_balance(accumulation_purse),
+        *DEFAULT_PAYMENT * 2
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_record_payment_transfers_with_default_handling() {
+    let mut builder = setup(DEFAULT_REFUND_RATIO, FeeHandling::default());
+
+    exec_do_nothing(&mut builder);
+
+    let transfers = builder
+        .get_exec_results()
+        .last()
+        .expect("should have results")[0]
+        .transfers()
+        .clone();
+    assert!(transfers.is_empty());
+}
@@ -829,6 +829,11 @@ fn should_verify_wasm_add_bid_wasm_cost_is_not_recursive() {
         remove_contract_user_group_urefs: HostFunction::fixed(0),
         print: HostFunction::fixed(0),
         blake2b: HostFunction::fixed(0),
+        emit_event: HostFunction::fixed(0),
+        verify_signature: HostFunction::fixed(0),
+        recover_secp256k1: HostFunction::fixed(0),
+        sha256: HostFunction::fixed(0),
+        keccak256: HostFunction::fixed(0),
     };
 
     let new_wasm_config = WasmConfig::new(
@@ -0,0 +1,75 @@
+use casper_engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use casper_execution_engine::{core::engine_state::EngineConfig, shared::gas::Gas};
+use casper_types::{runtime_args, RuntimeArgs};
+
+const CONTRACT_COUNTER_DEFINE: &str = "counter_define.wasm";
+const CONTRACT_NAME: &str = "counter_package_hash";
+const ENTRYPOINT_SESSION: &str = "session";
+const COUNTER_CONTRACT_HASH_KEY_NAME: &str = "counter_contract_hash";
+
+/// Installs the counter contract and calls it twice, returning the costs of the calls.
+fn call_counter_twice(builder: &mut InMemoryWasmTestBuilder) -> Vec<Gas> {
+    let install_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_COUNTER_DEFINE,
+        RuntimeArgs::new(),
+    )
+    .build();
+
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(install_request)
+        .expect_success()
+        .commit();
+
+    let counter_contract_hash_key = *builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(COUNTER_CONTRACT_HASH_KEY_NAME)
+        .expect("should have counter contract hash key");
+
+    (1..=2)
+        .map(|exec_index| {
+            let call_request = ExecuteRequestBuilder::versioned_contract_call_by_name(
+                *DEFAULT_ACCOUNT_ADDR,
+                CONTRACT_NAME,
+                None,
+                ENTRYPOINT_SESSION,
+                runtime_args! { COUNTER_CONTRACT_HASH_KEY_NAME => counter_contract_hash_key },
+            )
+            .build();
+            builder.exec(call_request).expect_success().commit();
+            builder.exec_costs(exec_index)[0]
+        })
+        .collect()
+}
+
+#[ignore]
+#[test]
+fn should_reuse_cached_contract_modules() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    call_counter_twice(&mut builder);
+
+    let wasm_cache = builder.get_engine_state().wasm_cache();
+    assert!(!wasm_cache.is_empty());
+    assert!(wasm_cache.take_stats().hits > 0);
+}
+
+#[ignore]
+#[test]
+fn should_charge_same_cost_with_wasm_cache_disabled() {
+    let mut cached_builder = InMemoryWasmTestBuilder::default();
+    let cached_costs = call_counter_twice(&mut cached_builder);
+
+    let engine_config = EngineConfig::default().with_max_wasm_cache_size(0);
+    let mut uncached_builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    let uncached_costs = call_counter_twice(&mut uncached_builder);
+
+    assert!(uncached_builder.get_engine_state().wasm_cache().is_empty());
+    assert_eq!(cached_costs, uncached_costs);
+}
@@ -0,0 +1,104 @@
+use thiserror::Error;
+
+use casper_types::{crypto, EraId, Key, U512};
+
+use crate::BlockHash;
+
+/// Error returned when verifying a bundle or a state proof.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The bundle doesn't contain the header of the trusted block.
+    #[error("bundle doesn't contain the trusted block header")]
+    MissingTrustedBlock,
+
+    /// A block header doesn't have the hash it was expected to have.
+    #[error("expected block header with {expected}, found {actual}")]
+    UnexpectedBlockHash {
+        /// The expected block hash.
+        expected: BlockHash,
+        /// The hash of the block header found.
+        actual: BlockHash,
+    },
+
+    /// None of the trusted block and its ancestors in the bundle is a switch block.
+    #[error("bundle doesn't contain a switch block at or below the trusted block")]
+    MissingTrustedSwitchBlock,
+
+    /// A block in the bundle's chain of switch blocks is not a switch block.
+    #[error("{0} is not a switch block")]
+    NotASwitchBlock(BlockHash),
+
+    /// A block is not in the era whose validators are known.
+    #[error("expected {block_hash} to be in {expected}, found {actual}")]
+    UnexpectedEra {
+        /// The hash of the block.
+        block_hash: BlockHash,
+        /// The era whose validators are known.
+        expected: EraId,
+        /// The era of the block.
+        actual: EraId,
+    },
+
+    /// A finality signature is invalid.
+    #[error("invalid finality signature for {block_hash}: {error}")]
+    InvalidSignature {
+        /// The hash of the block.
+        block_hash: BlockHash,
+        /// The underlying error.
+        error: crypto::Error,
+    },
+
+    /// The validators which signed a block don't exceed the finality threshold.
+    #[error(
+        "{block_hash} signed by validators with weight {signed_weight} of {total_weight}, which \
+        doesn't exceed the finality threshold"
+    )]
+    InsufficientWeight {
+        /// The hash of the block.
+        block_hash: BlockHash,
+        /// The combined weight of the validators which signed the block.
+        signed_weight: U512,
+        /// The total weight of the era's validators.
+        total_weight: U512,
+    },
+
+    /// The tip of the bundle is below the latest verified block.
+    #[error("tip {tip} at height {tip_height} is below the verified block at height {height}")]
+    StaleTip {
+        /// The hash of the tip.
+        tip: BlockHash,
+        /// The height of the tip.
+        tip_height: u64,
+        /// The height of the latest verified block.
+        height: u64,
+    },
+
+    /// No bundle has been verified yet, so there is no state root hash to check proofs against.
+    #[error("no bundle has been verified yet")]
+    NoVerifiedTip,
+
+    /// The key of a state proof couldn't be parsed.
+    #[error("failed to parse key: {0}")]
+    ParseKey(String),
+
+    /// The merkle proofs of a state proof couldn't be decoded.
+    #[error("failed to decode merkle proof: {0}")]
+    DecodeProof(String),
+
+    /// A merkle proof is for a different key than the one the query leads to.
+    #[error("expected merkle proof for {expected}, found proof for {actual}")]
+    UnexpectedKey {
+        /// The key the query leads to.
+        expected: Key,
+        /// The key of the merkle proof.
+        actual: Key,
+    },
+
+    /// A merkle proof doesn't lead to the state root hash.
+    #[error("merkle proof for {0} doesn't match the state root hash")]
+    StateRootHashMismatch(Key),
+
+    /// The merkle proofs don't follow the path of a state query.
+    #[error("merkle proofs don't follow the query path: {0}")]
+    PathMismatch(String),
+}
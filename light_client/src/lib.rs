@@ -0,0 +1,501 @@
+//! # Casper light client
+//!
+//! Verifies the linear chain and global state of a Casper network without running a node.
+//!
+//! Nodes serve bundles of block headers and finality signatures via the `light_client_get_bundle`
+//! JSON-RPC.  Starting from a block hash trusted by the user, a [`LightClient`] follows the chain
+//! back to the closest switch block, which announces the validators of the following era.  Each
+//! switch block after that must be signed by validators of the era it ends, with their combined
+//! weight exceeding the finality threshold, and announces the validators of the era after it.  The
+//! tip of the bundle is checked in the same way, after which the global state proofs served with
+//! the bundle can be verified against its state root hash.
+
+#![doc(
+    html_favicon_url = "https://raw.githubusercontent.com/casper-network/casper-node/master/images/CasperLabs_Logo_Favicon_RGB_50px.png",
+    html_logo_url = "https://raw.githubusercontent.com/casper-network/casper-node/master/images/CasperLabs_Logo_Symbol_RGB.png",
+    test(attr(forbid(warnings)))
+)]
+#![warn(
+    missing_docs,
+    trivial_casts,
+    trivial_numeric_casts,
+    unused_qualifications
+)]
+
+mod error;
+mod state_proof;
+mod types;
+
+use std::collections::BTreeMap;
+
+use num_rational::Ratio;
+
+use casper_types::{crypto, EraId, PublicKey, Signature, U512};
+
+pub use error::Error;
+pub use state_proof::{verify_state_proof, ProvenValue};
+pub use types::{
+    BlockHash, BlockHeader, Digest, EraEnd, EraReport, LightClientBundle, MerkleProof, Proof,
+    Reward, SignedBlockHeader, StateProof, Timestamp, ValidatorWeight,
+};
+
+/// The fraction of an era's total validator weight which must be exceeded by the signers of a
+/// block, unless configured otherwise.
+pub const DEFAULT_FINALITY_THRESHOLD: Ratio<u64> = Ratio::new_raw(1, 3);
+
+/// A light client following the linear chain from a trusted block.
+#[derive(Clone, Debug)]
+pub struct LightClient {
+    /// The hash of the latest block trusted by the light client.
+    trusted_block_hash: BlockHash,
+    /// The fraction of the total weight the signers of a block must exceed.
+    finality_threshold: Ratio<u64>,
+    /// The header of the tip of the latest verified bundle.
+    verified_tip: Option<BlockHeader>,
+}
+
+impl LightClient {
+    /// Creates a light client trusting the block with the given hash.
+    pub fn new(trusted_block_hash: BlockHash) -> Self {
+        LightClient {
+            trusted_block_hash,
+            finality_threshold: DEFAULT_FINALITY_THRESHOLD,
+            verified_tip: None,
+        }
+    }
+
+    /// Sets the fraction of an era's total validator weight which must be exceeded by the signers
+    /// of a block.
+    pub fn with_finality_threshold(mut self, finality_threshold: Ratio<u64>) -> Self {
+        self.finality_threshold = finality_threshold;
+        self
+    }
+
+    /// Returns the hash of the latest block trusted by the light client.
+    ///
+    /// This is the hash to request the next bundle for.
+    pub fn trusted_block_hash(&self) -> &BlockHash {
+        &self.trusted_block_hash
+    }
+
+    /// Returns the header of the tip of the latest verified bundle.
+    pub fn verified_tip(&self) -> Option<&BlockHeader> {
+        self.verified_tip.as_ref()
+    }
+
+    /// Verifies a bundle served for the trusted block hash, and trusts its tip from then on.
+    pub fn verify_bundle(&mut self, bundle: &LightClientBundle) -> Result<&BlockHeader, Error> {
+        let tip = verify_bundle(&self.trusted_block_hash, bundle, self.finality_threshold)?;
+        self.trusted_block_hash = tip.hash();
+        Ok(self.verified_tip.insert(tip))
+    }
+
+    /// Verifies a state proof against the global state of the latest verified tip, returning the
+    /// proven value.
+    pub fn verify_state_proof(&self, state_proof: &StateProof) -> Result<ProvenValue, Error> {
+        let tip = self.verified_tip.as_ref().ok_or(Error::NoVerifiedTip)?;
+        verify_state_proof(&tip.state_root_hash, state_proof)
+    }
+}
+
+/// Verifies a bundle served for the given trusted block hash, returning the header of its tip.
+pub fn verify_bundle(
+    trusted_block_hash: &BlockHash,
+    bundle: &LightClientBundle,
+    finality_threshold: Ratio<u64>,
+) -> Result<BlockHeader, Error> {
+    // The trusted block and its ancestors are verified by their hashes alone.
+    let trusted_ancestors = &bundle.trusted_ancestors;
+    let mut expected_hash = *trusted_block_hash;
+    for block_header in trusted_ancestors {
+        let actual = block_header.hash();
+        if actual != expected_hash {
+            return Err(Error::UnexpectedBlockHash {
+                expected: expected_hash,
+                actual,
+            });
+        }
+        expected_hash = block_header.parent_hash;
+    }
+    let trusted_block_header = trusted_ancestors
+        .first()
+        .ok_or(Error::MissingTrustedBlock)?;
+    let trusted_switch_block_header = trusted_ancestors
+        .iter()
+        .find(|block_header| block_header.is_switch_block())
+        .ok_or(Error::MissingTrustedSwitchBlock)?;
+
+    let mut era_id = trusted_switch_block_header.era_id.successor();
+    let mut validator_weights = next_era_validator_weights(trusted_switch_block_header)?;
+    let mut latest_block_header = trusted_block_header;
+
+    // Each switch block is signed by the validators announced in its predecessor.
+    for signed_block_header in &bundle.switch_blocks {
+        verify_finality(
+            signed_block_header,
+            era_id,
+            &validator_weights,
+            finality_threshold,
+        )?;
+        let block_header = &signed_block_header.header;
+        era_id = block_header.era_id.successor();
+        validator_weights = next_era_validator_weights(block_header)?;
+        latest_block_header = block_header;
+    }
+
+    let tip = &bundle.tip.header;
+    if tip.hash() == latest_block_header.hash() {
+        return Ok(tip.clone());
+    }
+    verify_finality(&bundle.tip, era_id, &validator_weights, finality_threshold)?;
+    if tip.height < latest_block_header.height {
+        return Err(Error::StaleTip {
+            tip: tip.hash(),
+            tip_height: tip.height,
+            height: latest_block_header.height,
+        });
+    }
+    Ok(tip.clone())
+}
+
+/// Returns the validator weights announced in a switch block.
+fn next_era_validator_weights(
+    block_header: &BlockHeader,
+) -> Result<BTreeMap<PublicKey, U512>, Error> {
+    block_header
+        .next_era_validator_weights()
+        .ok_or_else(|| Error::NotASwitchBlock(block_header.hash()))
+}
+
+/// Checks that a block is in the given era, and that its signers exceed the finality threshold of
+/// the era's total validator weight.
+///
+/// The signatures are checked against the hash computed from the header rather than the hash it is
+/// served with, and each validator is counted once however many of its signatures are served.
+fn verify_finality(
+    signed_block_header: &SignedBlockHeader,
+    era_id: EraId,
+    validator_weights: &BTreeMap<PublicKey, U512>,
+    finality_threshold: Ratio<u64>,
+) -> Result<(), Error> {
+    let block_header = &signed_block_header.header;
+    let block_hash = block_header.hash();
+    if block_header.era_id != era_id {
+        return Err(Error::UnexpectedEra {
+            block_hash,
+            expected: era_id,
+            actual: block_header.era_id,
+        });
+    }
+    let signatures: BTreeMap<&PublicKey, &Signature> = signed_block_header
+        .proofs
+        .iter()
+        .map(|proof| (&proof.public_key, &proof.signature))
+        .collect();
+    let signed_message = finality_signature_message(&block_hash, era_id);
+    for (public_key, signature) in &signatures {
+        crypto::verify(&signed_message, signature, public_key)
+            .map_err(|error| Error::InvalidSignature { block_hash, error })?;
+    }
+
+    let total_weight = validator_weights
+        .values()
+        .fold(U512::zero(), |sum, weight| sum + *weight);
+    let signed_weight = signatures
+        .keys()
+        .filter_map(|public_key| validator_weights.get(*public_key))
+        .fold(U512::zero(), |sum, weight| sum + *weight);
+    if signed_weight * U512::from(*finality_threshold.denom())
+        <= total_weight * U512::from(*finality_threshold.numer())
+    {
+        return Err(Error::InsufficientWeight {
+            block_hash,
+            signed_weight,
+            total_weight,
+        });
+    }
+    Ok(())
+}
+
+/// Returns the message a validator signs to finalize a block: the block hash followed by the era.
+fn finality_signature_message(block_hash: &BlockHash, era_id: EraId) -> Vec<u8> {
+    let mut message = block_hash.inner().as_ref().to_vec();
+    message.extend_from_slice(&era_id.to_le_bytes());
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use casper_types::SecretKey;
+
+    use super::*;
+
+    const ERA_LENGTH: u64 = 3;
+
+    /// Returns the secret keys of the validators of an era, alternating between two sets.
+    fn validator_keys(era_id: u64) -> Vec<SecretKey> {
+        let offset = if era_id % 2 == 0 { 1 } else { 5 };
+        (offset..offset + 4)
+            .map(|index| SecretKey::ed25519_from_bytes([index; SecretKey::ED25519_LENGTH]).unwrap())
+            .collect()
+    }
+
+    /// Returns the validator weights of an era, with all validators having the same weight.
+    fn validator_weights(era_id: u64) -> BTreeMap<PublicKey, U512> {
+        validator_keys(era_id)
+            .iter()
+            .map(|secret_key| (PublicKey::from(secret_key), U512::from(10)))
+            .collect()
+    }
+
+    /// Creates the JSON headers of a chain, ending each era with a switch block.
+    fn chain(era_count: u64) -> Vec<Value> {
+        let mut headers: Vec<Value> = Vec::new();
+        for height in 0..era_count * ERA_LENGTH {
+            let era_id = height / ERA_LENGTH;
+            let parent_hash = match headers.last() {
+                Some(parent) => to_block_header(parent).hash(),
+                None => BlockHash::new(Digest::from([0; Digest::LENGTH])),
+            };
+            let era_end = if height % ERA_LENGTH == ERA_LENGTH - 1 {
+                let weights: Vec<Value> = validator_weights(era_id + 1)
+                    .into_iter()
+                    .map(|(validator, weight)| json!({ "validator": validator, "weight": weight }))
+                    .collect();
+                json!({
+                    "era_report": {
+                        "equivocators": [],
+                        "rewards": [],
+                        "inactive_validators": []
+                    },
+                    "next_era_validator_weights": weights
+                })
+            } else {
+                Value::Null
+            };
+            headers.push(json!({
+                "parent_hash": parent_hash,
+                "state_root_hash": Digest::from([height as u8; Digest::LENGTH]),
+                "body_hash": Digest::from([0; Digest::LENGTH]),
+                "random_bit": false,
+                "accumulated_seed": Digest::from([0; Digest::LENGTH]),
+                "era_end": era_end,
+                "timestamp": "2021-06-17T00:00:00.000Z",
+                "era_id": era_id,
+                "height": height,
+                "protocol_version": "1.0.0"
+            }));
+        }
+        headers
+    }
+
+    fn to_block_header(header: &Value) -> BlockHeader {
+        serde_json::from_value(header.clone()).unwrap()
+    }
+
+    /// Signs a JSON header with the given keys.
+    fn signed(header: &Value, signers: &[SecretKey]) -> Value {
+        let block_header = to_block_header(header);
+        let proofs: Vec<Value> = signers
+            .iter()
+            .map(|secret_key| {
+                let public_key = PublicKey::from(secret_key);
+                let message = finality_signature_message(&block_header.hash(), block_header.era_id);
+                let signature = crypto::sign(message, secret_key, &public_key);
+                json!({ "public_key": public_key, "signature": signature })
+            })
+            .collect();
+        json!({ "hash": block_header.hash(), "header": header, "proofs": proofs })
+    }
+
+    /// Signs a JSON header by two of the four validators of its era.
+    fn finalized(header: &Value) -> Value {
+        let era_id = to_block_header(header).era_id.value();
+        signed(header, &validator_keys(era_id)[..2])
+    }
+
+    fn new_bundle(
+        trusted_ancestors: Vec<&Value>,
+        switch_blocks: Vec<Value>,
+        tip: Value,
+    ) -> LightClientBundle {
+        serde_json::from_value(json!({
+            "trusted_ancestors": trusted_ancestors,
+            "switch_blocks": switch_blocks,
+            "tip": tip
+        }))
+        .unwrap()
+    }
+
+    /// Creates the bundle a node would serve for the block at `height`, ending at the last block.
+    fn bundle_from(chain: &[Value], height: usize) -> LightClientBundle {
+        let switch_block_height = (0..=height)
+            .rev()
+            .find(|height| to_block_header(&chain[*height]).is_switch_block())
+            .unwrap();
+        let trusted_ancestors = (switch_block_height..=height)
+            .rev()
+            .map(|height| &chain[height])
+            .collect();
+        let switch_blocks = (height + 1..chain.len())
+            .filter(|height| to_block_header(&chain[*height]).is_switch_block())
+            .map(|height| finalized(&chain[height]))
+            .collect();
+        new_bundle(
+            trusted_ancestors,
+            switch_blocks,
+            finalized(chain.last().unwrap()),
+        )
+    }
+
+    #[test]
+    fn should_follow_validator_set_changes() {
+        let chain = chain(4);
+        let trusted_block_hash = to_block_header(&chain[4]).hash();
+        let mut light_client = LightClient::new(trusted_block_hash);
+        assert!(matches!(
+            light_client.verify_state_proof(&StateProof {
+                key: String::new(),
+                path: vec![],
+                merkle_proofs: vec![]
+            }),
+            Err(Error::NoVerifiedTip)
+        ));
+
+        let bundle = new_bundle(
+            vec![&chain[4], &chain[3], &chain[2]],
+            vec![finalized(&chain[5]), finalized(&chain[8])],
+            finalized(&chain[10]),
+        );
+        let tip = light_client.verify_bundle(&bundle).unwrap().clone();
+        assert_eq!(tip, to_block_header(&chain[10]));
+        assert_eq!(light_client.trusted_block_hash(), &tip.hash());
+        assert_eq!(light_client.verified_tip(), Some(&tip));
+
+        // The next bundle starts from the verified tip.
+        let bundle = bundle_from(&chain, 10);
+        assert_eq!(light_client.verify_bundle(&bundle).unwrap().height, 11);
+    }
+
+    #[test]
+    fn should_reject_insufficient_weight() {
+        let chain = chain(4);
+        let trusted_block_hash = to_block_header(&chain[4]).hash();
+
+        // One of four validators with equal weight doesn't exceed a third of the total weight.
+        let bundle = new_bundle(
+            vec![&chain[4], &chain[3], &chain[2]],
+            vec![signed(&chain[5], &validator_keys(1)[..1])],
+            finalized(&chain[7]),
+        );
+        assert!(matches!(
+            verify_bundle(&trusted_block_hash, &bundle, DEFAULT_FINALITY_THRESHOLD),
+            Err(Error::InsufficientWeight { .. })
+        ));
+
+        // Repeated signatures of a validator count once.
+        let mut switch_block = signed(&chain[5], &validator_keys(1)[..1]);
+        let proof = switch_block["proofs"][0].clone();
+        switch_block["proofs"].as_array_mut().unwrap().push(proof);
+        let bundle = new_bundle(
+            vec![&chain[4], &chain[3], &chain[2]],
+            vec![switch_block],
+            finalized(&chain[7]),
+        );
+        assert!(matches!(
+            verify_bundle(&trusted_block_hash, &bundle, DEFAULT_FINALITY_THRESHOLD),
+            Err(Error::InsufficientWeight { .. })
+        ));
+
+        // Validators of other eras don't count.
+        let bundle = new_bundle(
+            vec![&chain[4], &chain[3], &chain[2]],
+            vec![signed(&chain[5], &validator_keys(2))],
+            finalized(&chain[7]),
+        );
+        assert!(matches!(
+            verify_bundle(&trusted_block_hash, &bundle, DEFAULT_FINALITY_THRESHOLD),
+            Err(Error::InsufficientWeight { .. })
+        ));
+
+        // A higher threshold requires more signers.
+        let bundle = bundle_from(&chain, 4);
+        assert!(matches!(
+            verify_bundle(&trusted_block_hash, &bundle, Ratio::new(2, 3)),
+            Err(Error::InsufficientWeight { .. })
+        ));
+    }
+
+    #[test]
+    fn should_reject_skipped_switch_blocks() {
+        let chain = chain(4);
+        let trusted_block_hash = to_block_header(&chain[4]).hash();
+
+        let bundle = new_bundle(
+            vec![&chain[4], &chain[3], &chain[2]],
+            vec![finalized(&chain[5])],
+            finalized(&chain[10]),
+        );
+        assert!(matches!(
+            verify_bundle(&trusted_block_hash, &bundle, DEFAULT_FINALITY_THRESHOLD),
+            Err(Error::UnexpectedEra { .. })
+        ));
+    }
+
+    #[test]
+    fn should_reject_untrusted_ancestors() {
+        let chain = chain(4);
+        let trusted_block_hash = to_block_header(&chain[4]).hash();
+
+        let bundle = new_bundle(
+            vec![&chain[5], &chain[4], &chain[3], &chain[2]],
+            vec![finalized(&chain[8])],
+            finalized(&chain[10]),
+        );
+        assert!(matches!(
+            verify_bundle(&trusted_block_hash, &bundle, DEFAULT_FINALITY_THRESHOLD),
+            Err(Error::UnexpectedBlockHash { .. })
+        ));
+
+        let bundle = new_bundle(
+            vec![&chain[4], &chain[2]],
+            vec![finalized(&chain[5])],
+            finalized(&chain[7]),
+        );
+        assert!(matches!(
+            verify_bundle(&trusted_block_hash, &bundle, DEFAULT_FINALITY_THRESHOLD),
+            Err(Error::UnexpectedBlockHash { .. })
+        ));
+
+        let bundle = new_bundle(
+            vec![&chain[4], &chain[3]],
+            vec![finalized(&chain[5])],
+            finalized(&chain[7]),
+        );
+        assert!(matches!(
+            verify_bundle(&trusted_block_hash, &bundle, DEFAULT_FINALITY_THRESHOLD),
+            Err(Error::MissingTrustedSwitchBlock)
+        ));
+    }
+
+    #[test]
+    fn should_reject_signatures_for_other_blocks() {
+        let chain = chain(4);
+        let trusted_block_hash = to_block_header(&chain[4]).hash();
+
+        // Signatures are checked against the hash of the header they're served with.
+        let mut forged_switch_block = finalized(&chain[6]);
+        forged_switch_block["header"] = chain[5].clone();
+        let bundle = new_bundle(
+            vec![&chain[4], &chain[3], &chain[2]],
+            vec![forged_switch_block],
+            finalized(&chain[7]),
+        );
+        assert!(matches!(
+            verify_bundle(&trusted_block_hash, &bundle, DEFAULT_FINALITY_THRESHOLD),
+            Err(Error::InvalidSignature { .. })
+        ));
+    }
+}
@@ -0,0 +1,528 @@
+//! Verification of global state proofs.
+//!
+//! Global state is a merkle trie whose leaves hold serialized `StoredValue`s.  Proofs are verified
+//! by recomputing the hashes of the trie's nodes from a leaf up to the state root, the same way the
+//! execution engine computes them, so this crate doesn't need to depend on the engine.
+
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, Bytes, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    contracts::NamedKeys,
+    CLType, CLValue, Contract, Key,
+};
+
+use crate::{Digest, Error, MerkleProof, StateProof};
+
+/// Tag of a leaf in the serialization of a trie.
+const TRIE_LEAF_TAG: u8 = 0;
+/// Tag of a node in the serialization of a trie.
+const TRIE_NODE_TAG: u8 = 1;
+/// Tag of an extension in the serialization of a trie.
+const TRIE_EXTENSION_TAG: u8 = 2;
+/// The number of children of a trie node.
+const RADIX: usize = 256;
+
+/// Tag of a pointer to a leaf.
+const LEAF_POINTER_TAG: u8 = 0;
+/// Tag of a pointer to a node or an extension.
+const NODE_POINTER_TAG: u8 = 1;
+
+/// Tag of a proof step through a node.
+const PROOF_STEP_NODE_TAG: u8 = 0;
+/// Tag of a proof step through an extension.
+const PROOF_STEP_EXTENSION_TAG: u8 = 1;
+
+/// Tag of a `StoredValue::CLValue`.
+const CL_VALUE_TAG: u8 = 0;
+/// Tag of a `StoredValue::Account`.
+const ACCOUNT_TAG: u8 = 1;
+/// Tag of a `StoredValue::Contract`.
+const CONTRACT_TAG: u8 = 3;
+
+/// A value proven to be stored in global state.
+///
+/// The value is kept in the serialization of the execution engine's `StoredValue`.  As when a node
+/// queries a dictionary item, the value of a dictionary item is the value the item wraps.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProvenValue(Vec<u8>);
+
+impl ProvenValue {
+    /// Returns the serialized `StoredValue`.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns the value if it is a `CLValue`.
+    pub fn to_cl_value(&self) -> Option<CLValue> {
+        match self.0.split_first() {
+            Some((&CL_VALUE_TAG, bytes)) => bytesrepr::deserialize(bytes.to_vec()).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// Verifies the merkle proofs of a state query against a state root hash, returning the value found
+/// at the end of the query's path.
+///
+/// Mirrors how a node follows the path of a query: named keys of accounts and contracts consume a
+/// path component, while values holding a key are followed without consuming one.
+pub fn verify_state_proof(
+    state_root_hash: &Digest,
+    state_proof: &StateProof,
+) -> Result<ProvenValue, Error> {
+    let base_key = Key::from_formatted_str(&state_proof.key)
+        .map_err(|error| Error::ParseKey(error.to_string()))?;
+    let (last_proof, intermediate_proofs) = state_proof
+        .merkle_proofs
+        .split_last()
+        .ok_or_else(|| Error::DecodeProof("no merkle proofs".to_string()))?;
+
+    let mut expected_key = base_key;
+    let mut remaining_path = state_proof.path.as_slice();
+    for proof in intermediate_proofs {
+        let value = verify_merkle_proof(state_root_hash, proof, &expected_key)?;
+        let (next_key, path) = follow(&expected_key, &value, remaining_path)?;
+        expected_key = next_key;
+        remaining_path = path;
+    }
+    let value = verify_merkle_proof(state_root_hash, last_proof, &expected_key)?;
+    if let Some(name) = remaining_path.first() {
+        return Err(Error::PathMismatch(format!(
+            "no merkle proof for path component {}",
+            name
+        )));
+    }
+
+    unwrap_dictionary_value(&expected_key, value).map(ProvenValue)
+}
+
+/// Checks that a merkle proof is for the expected key and leads to the state root hash, returning
+/// the serialized value it proves.
+fn verify_merkle_proof(
+    state_root_hash: &Digest,
+    proof: &MerkleProof,
+    expected_key: &Key,
+) -> Result<Vec<u8>, Error> {
+    let key =
+        Key::from_formatted_str(&proof.key).map_err(|error| Error::ParseKey(error.to_string()))?;
+    if key != *expected_key {
+        return Err(Error::UnexpectedKey {
+            expected: *expected_key,
+            actual: key,
+        });
+    }
+    let value = decode_hex(&proof.value)?;
+    let proof_steps: Vec<ProofStep> = bytesrepr::deserialize(decode_hex(&proof.proof_steps)?)
+        .map_err(|error| Error::DecodeProof(error.to_string()))?;
+    let computed_state_root_hash = compute_state_root_hash(&key, &value, &proof_steps)
+        .map_err(|error| Error::DecodeProof(error.to_string()))?;
+    if computed_state_root_hash != *state_root_hash {
+        return Err(Error::StateRootHashMismatch(key));
+    }
+    Ok(value)
+}
+
+fn decode_hex(hex_bytes: &str) -> Result<Vec<u8>, Error> {
+    hex::decode(hex_bytes).map_err(|error| Error::DecodeProof(error.to_string()))
+}
+
+/// Hashes the trie from the leaf holding `key` and `value` up to the state root.
+fn compute_state_root_hash(
+    key: &Key,
+    value: &[u8],
+    proof_steps: &[ProofStep],
+) -> Result<Digest, bytesrepr::Error> {
+    let mut leaf_bytes = vec![TRIE_LEAF_TAG];
+    leaf_bytes.extend(key.to_bytes()?);
+    leaf_bytes.extend_from_slice(value);
+    let mut hash = Digest::hash(&leaf_bytes);
+
+    for (proof_step_index, proof_step) in proof_steps.iter().enumerate() {
+        let pointer = Pointer {
+            tag: if proof_step_index == 0 {
+                LEAF_POINTER_TAG
+            } else {
+                NODE_POINTER_TAG
+            },
+            hash,
+        };
+        let proof_step_bytes = match proof_step {
+            ProofStep::Node {
+                hole_index,
+                indexed_pointers_with_hole,
+            } => {
+                let mut pointer_block = [None; RADIX];
+                for (index, indexed_pointer) in indexed_pointers_with_hole {
+                    pointer_block[*index as usize] = Some(*indexed_pointer);
+                }
+                pointer_block[*hole_index as usize] = Some(pointer);
+                let mut node_bytes = vec![TRIE_NODE_TAG];
+                for maybe_pointer in pointer_block.iter() {
+                    node_bytes.extend(maybe_pointer.to_bytes()?);
+                }
+                node_bytes
+            }
+            ProofStep::Extension { affix } => {
+                let mut extension_bytes = vec![TRIE_EXTENSION_TAG];
+                extension_bytes.extend(affix.to_bytes()?);
+                extension_bytes.extend(pointer.to_bytes()?);
+                extension_bytes
+            }
+        };
+        hash = Digest::hash(&proof_step_bytes);
+    }
+    Ok(hash)
+}
+
+/// Returns the key the query continues with after the value of a proof, along with the remaining
+/// path.
+fn follow<'a>(key: &Key, value: &[u8], path: &'a [String]) -> Result<(Key, &'a [String]), Error> {
+    // A node stops following the path once all its components are consumed.
+    let (name, remaining_path) = path
+        .split_first()
+        .ok_or_else(|| Error::PathMismatch(format!("unexpected merkle proof after {}", key)))?;
+
+    let decode_error = |error: bytesrepr::Error| Error::DecodeProof(error.to_string());
+    let named_keys = match value.split_first() {
+        Some((&ACCOUNT_TAG, bytes)) => {
+            // The named keys of an account follow its account hash.
+            let (_account_hash, bytes) = AccountHash::from_bytes(bytes).map_err(decode_error)?;
+            NamedKeys::from_bytes(bytes).map_err(decode_error)?.0
+        }
+        Some((&CONTRACT_TAG, bytes)) => Contract::from_bytes(bytes)
+            .map_err(decode_error)?
+            .0
+            .take_named_keys(),
+        Some((&CL_VALUE_TAG, bytes)) => {
+            let (cl_value, _) = CLValue::from_bytes(bytes).map_err(decode_error)?;
+            if *cl_value.cl_type() != CLType::Key {
+                return Err(Error::PathMismatch(format!(
+                    "value of {} is not an account, contract nor key",
+                    key
+                )));
+            }
+            let key = cl_value
+                .into_t::<Key>()
+                .map_err(|error| Error::PathMismatch(error.to_string()))?;
+            return Ok((key, path));
+        }
+        _ => {
+            return Err(Error::PathMismatch(format!(
+                "value of {} is not an account, contract nor key",
+                key
+            )))
+        }
+    };
+    let next_key = named_keys
+        .get(name)
+        .copied()
+        .ok_or_else(|| Error::PathMismatch(format!("{} has no named key {}", key, name)))?;
+    Ok((next_key, remaining_path))
+}
+
+/// Returns the value wrapped by a dictionary item, the way a node does when querying one.
+fn unwrap_dictionary_value(key: &Key, value: Vec<u8>) -> Result<Vec<u8>, Error> {
+    match (key, value.split_first()) {
+        (Key::Dictionary(_), Some((&CL_VALUE_TAG, bytes))) => {
+            let decode_error = |error: bytesrepr::Error| Error::DecodeProof(error.to_string());
+            let (wrapped, _) = CLValue::from_bytes(bytes).map_err(decode_error)?;
+            // The wrapped value is serialized first, followed by the dictionary's seed and the
+            // item's key.
+            let (cl_value, _) = CLValue::from_bytes(wrapped.inner_bytes()).map_err(decode_error)?;
+            let mut unwrapped = vec![CL_VALUE_TAG];
+            unwrapped.extend(cl_value.to_bytes().map_err(decode_error)?);
+            Ok(unwrapped)
+        }
+        _ => Ok(value),
+    }
+}
+
+/// A pointer from a trie node or extension to one of its children.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Pointer {
+    tag: u8,
+    hash: Digest,
+}
+
+impl ToBytes for Pointer {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::unchecked_allocate_buffer(self);
+        ret.push(self.tag);
+        ret.extend_from_slice(self.hash.as_ref());
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH + Digest::LENGTH
+    }
+}
+
+impl FromBytes for Pointer {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, rem) = u8::from_bytes(bytes)?;
+        if tag != LEAF_POINTER_TAG && tag != NODE_POINTER_TAG {
+            return Err(bytesrepr::Error::Formatting);
+        }
+        let (hash, rem) = <[u8; Digest::LENGTH]>::from_bytes(rem)?;
+        Ok((
+            Pointer {
+                tag,
+                hash: Digest::from(hash),
+            },
+            rem,
+        ))
+    }
+}
+
+/// A step of a merkle proof, from a child up to its parent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum ProofStep {
+    /// The parent is a node, with the child at `hole_index`.
+    Node {
+        hole_index: u8,
+        indexed_pointers_with_hole: Vec<(u8, Pointer)>,
+    },
+    /// The parent is an extension.
+    Extension { affix: Bytes },
+}
+
+impl FromBytes for ProofStep {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, rem) = u8::from_bytes(bytes)?;
+        match tag {
+            PROOF_STEP_NODE_TAG => {
+                let (hole_index, rem) = u8::from_bytes(rem)?;
+                let (indexed_pointers_with_hole, rem) = Vec::<(u8, Pointer)>::from_bytes(rem)?;
+                Ok((
+                    ProofStep::Node {
+                        hole_index,
+                        indexed_pointers_with_hole,
+                    },
+                    rem,
+                ))
+            }
+            PROOF_STEP_EXTENSION_TAG => {
+                let (affix, rem) = Bytes::from_bytes(rem)?;
+                Ok((ProofStep::Extension { affix }, rem))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_execution_engine::{
+        shared::{account::Account, newtypes::CorrelationId, stored_value::StoredValue},
+        storage::{
+            global_state::{in_memory::InMemoryGlobalState, StateProvider, StateReader},
+            trie::merkle_proof::TrieMerkleProof,
+        },
+    };
+    use casper_types::{AccessRights, URef};
+
+    use super::*;
+
+    struct Fixture {
+        state_root_hash: Digest,
+        account_key: Key,
+        counter_key: Key,
+        pointer_key: Key,
+        state: InMemoryGlobalState,
+    }
+
+    /// Creates global state with an account, a counter named by the account and a pointer to the
+    /// account.
+    fn fixture() -> Fixture {
+        let counter_uref = URef::new([2; 32], AccessRights::READ_ADD_WRITE);
+        let counter_key = Key::URef(counter_uref);
+        let mut named_keys = NamedKeys::new();
+        named_keys.insert("counter".to_string(), counter_key);
+        let account_hash = AccountHash::new([1; 32]);
+        let account = Account::create(
+            account_hash,
+            named_keys,
+            URef::new([3; 32], AccessRights::READ_ADD_WRITE),
+        );
+        let account_key = Key::Account(account_hash);
+        let pointer_key = Key::Hash([4; 32]);
+
+        let pairs = [
+            (account_key, StoredValue::Account(account)),
+            (
+                counter_key,
+                StoredValue::CLValue(CLValue::from_t(42u64).unwrap()),
+            ),
+            (
+                pointer_key,
+                StoredValue::CLValue(CLValue::from_t(account_key).unwrap()),
+            ),
+        ];
+        let (state, root_hash) = InMemoryGlobalState::from_pairs(CorrelationId::new(), &pairs)
+            .expect("should create global state");
+        Fixture {
+            state_root_hash: Digest::from(root_hash.value()),
+            account_key,
+            counter_key,
+            pointer_key,
+            state,
+        }
+    }
+
+    impl Fixture {
+        fn proof(&self, key: &Key) -> MerkleProof {
+            let proof: TrieMerkleProof<Key, StoredValue> = self
+                .state
+                .checkout(self.state_root_hash.to_array().into())
+                .unwrap()
+                .expect("should check out state root")
+                .read_with_proof(CorrelationId::new(), key)
+                .unwrap()
+                .expect("should have key");
+            MerkleProof {
+                key: proof.key().to_formatted_string(),
+                value: hex::encode(proof.value().to_bytes().unwrap()),
+                proof_steps: hex::encode(proof.proof_steps().to_bytes().unwrap()),
+            }
+        }
+
+        fn state_proof(&self, key: &Key, path: &[&str], proven_keys: &[Key]) -> StateProof {
+            StateProof {
+                key: key.to_formatted_string(),
+                path: path.iter().map(|name| name.to_string()).collect(),
+                merkle_proofs: proven_keys.iter().map(|key| self.proof(key)).collect(),
+            }
+        }
+    }
+
+    fn counter_value() -> CLValue {
+        CLValue::from_t(42u64).unwrap()
+    }
+
+    #[test]
+    fn should_verify_proofs_following_path() {
+        let fixture = fixture();
+
+        let state_proof = fixture.state_proof(
+            &fixture.account_key,
+            &["counter"],
+            &[fixture.account_key, fixture.counter_key],
+        );
+        let value = verify_state_proof(&fixture.state_root_hash, &state_proof).unwrap();
+        assert_eq!(value.to_cl_value(), Some(counter_value()));
+        assert_eq!(
+            value.as_bytes(),
+            StoredValue::CLValue(counter_value()).to_bytes().unwrap()
+        );
+
+        // Keys held by values are followed without consuming a path component.
+        let state_proof = fixture.state_proof(
+            &fixture.pointer_key,
+            &["counter"],
+            &[
+                fixture.pointer_key,
+                fixture.account_key,
+                fixture.counter_key,
+            ],
+        );
+        let value = verify_state_proof(&fixture.state_root_hash, &state_proof).unwrap();
+        assert_eq!(value.to_cl_value(), Some(counter_value()));
+    }
+
+    #[test]
+    fn should_reject_proofs_for_other_state_root_hash() {
+        let fixture = fixture();
+        let state_proof = fixture.state_proof(&fixture.counter_key, &[], &[fixture.counter_key]);
+
+        let other_state_root_hash = Digest::from([9; Digest::LENGTH]);
+        assert!(matches!(
+            verify_state_proof(&other_state_root_hash, &state_proof),
+            Err(Error::StateRootHashMismatch(_))
+        ));
+
+        // A proof for a value other than the one stored.
+        let mut state_proof = state_proof;
+        state_proof.merkle_proofs[0].value = hex::encode(
+            StoredValue::CLValue(CLValue::from_t(43u64).unwrap())
+                .to_bytes()
+                .unwrap(),
+        );
+        assert!(matches!(
+            verify_state_proof(&fixture.state_root_hash, &state_proof),
+            Err(Error::StateRootHashMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn should_reject_proofs_not_following_path() {
+        let fixture = fixture();
+
+        // A proof for a key other than the one the path leads to.
+        let state_proof = fixture.state_proof(
+            &fixture.account_key,
+            &["counter"],
+            &[fixture.account_key, fixture.pointer_key],
+        );
+        assert!(matches!(
+            verify_state_proof(&fixture.state_root_hash, &state_proof),
+            Err(Error::UnexpectedKey { .. })
+        ));
+
+        // A proof for a different base key.
+        let state_proof = fixture.state_proof(&fixture.counter_key, &[], &[fixture.pointer_key]);
+        assert!(matches!(
+            verify_state_proof(&fixture.state_root_hash, &state_proof),
+            Err(Error::UnexpectedKey { .. })
+        ));
+
+        // Proofs stopping before the end of the path.
+        let state_proof =
+            fixture.state_proof(&fixture.account_key, &["counter"], &[fixture.account_key]);
+        assert!(matches!(
+            verify_state_proof(&fixture.state_root_hash, &state_proof),
+            Err(Error::PathMismatch(_))
+        ));
+
+        // Proofs continuing past the end of the path.
+        let state_proof = fixture.state_proof(
+            &fixture.account_key,
+            &[],
+            &[fixture.account_key, fixture.counter_key],
+        );
+        assert!(matches!(
+            verify_state_proof(&fixture.state_root_hash, &state_proof),
+            Err(Error::PathMismatch(_))
+        ));
+
+        // A path component the account doesn't have.
+        let state_proof = fixture.state_proof(
+            &fixture.account_key,
+            &["missing"],
+            &[fixture.account_key, fixture.counter_key],
+        );
+        assert!(matches!(
+            verify_state_proof(&fixture.state_root_hash, &state_proof),
+            Err(Error::PathMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn should_reject_malformed_proofs() {
+        let fixture = fixture();
+        let mut state_proof =
+            fixture.state_proof(&fixture.counter_key, &[], &[fixture.counter_key]);
+
+        let proof_steps = &mut state_proof.merkle_proofs[0].proof_steps;
+        proof_steps.truncate(proof_steps.len() - 2);
+        assert!(matches!(
+            verify_state_proof(&fixture.state_root_hash, &state_proof),
+            Err(Error::DecodeProof(_))
+        ));
+
+        state_proof.merkle_proofs.clear();
+        assert!(matches!(
+            verify_state_proof(&fixture.state_root_hash, &state_proof),
+            Err(Error::DecodeProof(_))
+        ));
+    }
+}
@@ -0,0 +1,372 @@
+//! The linear chain data and global state proofs served to light clients.
+//!
+//! These types define the JSON format of the `light_client_get_bundle` JSON-RPC, and are used by
+//! the node to serve it.  Block headers are hashed exactly as the node hashes them, so a light
+//! client doesn't need to depend on the node itself.
+
+// TODO - remove once schemars stops causing warning.
+#![allow(clippy::field_reassign_with_default)]
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Debug, Display, Formatter},
+    str::FromStr,
+    time::{Duration, SystemTime},
+};
+
+use schemars::JsonSchema;
+use serde::{de::Error as SerdeError, Deserialize, Deserializer, Serialize, Serializer};
+
+use casper_types::{
+    account,
+    bytesrepr::{self, ToBytes},
+    EraId, ProtocolVersion, PublicKey, Signature, U512,
+};
+
+/// A blake2b hash digest.
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, JsonSchema)]
+#[schemars(with = "String", description = "Hex-encoded hash digest.")]
+pub struct Digest(#[schemars(skip, with = "String")] [u8; Digest::LENGTH]);
+
+impl Digest {
+    /// Length of `Digest` in bytes.
+    pub const LENGTH: usize = 32;
+
+    /// Hashes the given data.
+    pub fn hash<T: AsRef<[u8]>>(data: T) -> Self {
+        Digest(account::blake2b(data))
+    }
+
+    /// Returns a copy of the wrapped byte array.
+    pub fn to_array(&self) -> [u8; Digest::LENGTH] {
+        self.0
+    }
+}
+
+impl From<[u8; Digest::LENGTH]> for Digest {
+    fn from(inner: [u8; Digest::LENGTH]) -> Self {
+        Digest(inner)
+    }
+}
+
+impl AsRef<[u8]> for Digest {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl Debug for Digest {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}", hex::encode(self.0))
+    }
+}
+
+impl Display for Digest {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}", hex::encode(self.0))
+    }
+}
+
+impl Serialize for Digest {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        hex::encode(self.0).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Digest {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex_digest = String::deserialize(deserializer)?;
+        let mut digest = [0; Digest::LENGTH];
+        hex::decode_to_slice(hex_digest, &mut digest).map_err(SerdeError::custom)?;
+        Ok(Digest(digest))
+    }
+}
+
+/// The hash of a block.
+#[derive(
+    Copy,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Debug,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(transparent)]
+pub struct BlockHash(Digest);
+
+impl BlockHash {
+    /// Creates a block hash from its digest.
+    pub fn new(digest: Digest) -> Self {
+        BlockHash(digest)
+    }
+
+    /// Returns the digest of the block hash.
+    pub fn inner(&self) -> &Digest {
+        &self.0
+    }
+}
+
+impl Display for BlockHash {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "block hash {}", self.0)
+    }
+}
+
+/// A timestamp in milliseconds since the Unix epoch.
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, JsonSchema)]
+#[schemars(with = "String", description = "Timestamp formatted as per RFC 3339")]
+pub struct Timestamp(u64);
+
+impl Timestamp {
+    /// Returns the number of milliseconds since the Unix epoch.
+    pub fn millis(&self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for Timestamp {
+    fn from(millis: u64) -> Self {
+        Timestamp(millis)
+    }
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        let system_time = SystemTime::UNIX_EPOCH
+            .checked_add(Duration::from_millis(self.0))
+            .expect("should be within system time limits");
+        write!(
+            formatter,
+            "{}",
+            humantime::format_rfc3339_millis(system_time)
+        )
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let system_time =
+            humantime::parse_rfc3339_weak(value).map_err(|error| error.to_string())?;
+        let millis = system_time
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_err(|_| "timestamp before the Unix epoch".to_string())?
+            .as_millis() as u64;
+        Ok(Timestamp(millis))
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_string().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Timestamp::from_str(&value).map_err(SerdeError::custom)
+    }
+}
+
+/// A validator's reward for an era.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Reward {
+    /// The validator.
+    pub validator: PublicKey,
+    /// The validator's reward.
+    pub amount: u64,
+}
+
+/// A validator's weight in an era.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ValidatorWeight {
+    /// The validator.
+    pub validator: PublicKey,
+    /// The validator's weight.
+    pub weight: U512,
+}
+
+/// Equivocation and reward information included in a switch block.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct EraReport {
+    /// The validators which equivocated in the era.
+    pub equivocators: Vec<PublicKey>,
+    /// The validators' rewards for the era.
+    pub rewards: Vec<Reward>,
+    /// The validators which were inactive in the era.
+    pub inactive_validators: Vec<PublicKey>,
+}
+
+/// The end of an era, announcing the validators of the following era.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct EraEnd {
+    /// The era's report.
+    pub era_report: EraReport,
+    /// The validator weights for the following era.
+    pub next_era_validator_weights: Vec<ValidatorWeight>,
+}
+
+impl EraEnd {
+    /// Returns the validator weights for the following era, keyed by validator.
+    fn validator_weights(&self) -> BTreeMap<PublicKey, U512> {
+        self.next_era_validator_weights
+            .iter()
+            .map(|validator_weight| (validator_weight.validator.clone(), validator_weight.weight))
+            .collect()
+    }
+}
+
+/// A block header.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct BlockHeader {
+    /// The hash of the parent block.
+    pub parent_hash: BlockHash,
+    /// The root hash of global state after executing the block.
+    pub state_root_hash: Digest,
+    /// The hash of the block's body.
+    pub body_hash: Digest,
+    /// The block's random bit.
+    pub random_bit: bool,
+    /// The accumulated seed of the block.
+    pub accumulated_seed: Digest,
+    /// The end of the era, if the block is a switch block.
+    pub era_end: Option<EraEnd>,
+    /// The block's timestamp.
+    pub timestamp: Timestamp,
+    /// The era the block belongs to.
+    pub era_id: EraId,
+    /// The height of the block.
+    pub height: u64,
+    /// The protocol version of the network when the block was created.
+    pub protocol_version: ProtocolVersion,
+}
+
+impl BlockHeader {
+    /// Computes the hash of the block, the same way a node does.
+    pub fn hash(&self) -> BlockHash {
+        let serialized_header = self
+            .serialize()
+            .unwrap_or_else(|error| panic!("should serialize block header: {}", error));
+        BlockHash::new(Digest::hash(&serialized_header))
+    }
+
+    /// Returns `true` if the block ends an era.
+    pub fn is_switch_block(&self) -> bool {
+        self.era_end.is_some()
+    }
+
+    /// Returns the validator weights of the following era, if the block is a switch block.
+    pub fn next_era_validator_weights(&self) -> Option<BTreeMap<PublicKey, U512>> {
+        self.era_end.as_ref().map(EraEnd::validator_weights)
+    }
+
+    /// Serializes the block header as the node's `BlockHeader`.
+    fn serialize(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = Vec::new();
+        buffer.extend(self.parent_hash.inner().0.to_bytes()?);
+        buffer.extend(self.state_root_hash.0.to_bytes()?);
+        buffer.extend(self.body_hash.0.to_bytes()?);
+        buffer.extend(self.random_bit.to_bytes()?);
+        buffer.extend(self.accumulated_seed.0.to_bytes()?);
+        match &self.era_end {
+            None => buffer.push(0),
+            Some(era_end) => {
+                buffer.push(1);
+                let era_report = &era_end.era_report;
+                let rewards: BTreeMap<PublicKey, u64> = era_report
+                    .rewards
+                    .iter()
+                    .map(|reward| (reward.validator.clone(), reward.amount))
+                    .collect();
+                buffer.extend(era_report.equivocators.to_bytes()?);
+                buffer.extend(rewards.to_bytes()?);
+                buffer.extend(era_report.inactive_validators.to_bytes()?);
+                buffer.extend(era_end.validator_weights().to_bytes()?);
+            }
+        }
+        buffer.extend(self.timestamp.0.to_bytes()?);
+        buffer.extend(self.era_id.to_bytes()?);
+        buffer.extend(self.height.to_bytes()?);
+        buffer.extend(self.protocol_version.to_bytes()?);
+        Ok(buffer)
+    }
+}
+
+/// A validator's finality signature of a block.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Proof {
+    /// The validator.
+    pub public_key: PublicKey,
+    /// The validator's signature of the block hash and era.
+    pub signature: Signature,
+}
+
+/// A block header together with the finality signatures of its block.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct SignedBlockHeader {
+    /// The hash of the block as claimed by the sender.
+    pub hash: BlockHash,
+    /// The block header.
+    pub header: BlockHeader,
+    /// The finality signatures of the block.
+    pub proofs: Vec<Proof>,
+}
+
+/// Block headers and finality signatures allowing a light client to follow the validator set from
+/// a trusted block to a recent block of the linear chain.
+///
+/// The validators of an era are announced in the switch block of the previous era.  Starting from
+/// the closest switch block at or below the trusted block, each following switch block is signed by
+/// the validators announced in its predecessor, up to the tip, which is signed by the validators
+/// announced in the last switch block.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct LightClientBundle {
+    /// Headers of the trusted block and its ancestors down to the closest switch block, highest
+    /// first.
+    pub trusted_ancestors: Vec<BlockHeader>,
+    /// Headers and finality signatures of the switch blocks following the trusted block, lowest
+    /// first.
+    pub switch_blocks: Vec<SignedBlockHeader>,
+    /// Header and finality signatures of the most recent block covered by the bundle.
+    pub tip: SignedBlockHeader,
+}
+
+/// A merkle proof of a single global state entry.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct MerkleProof {
+    /// `casper_types::Key` as formatted string.
+    pub key: String,
+    /// The hex-encoded serialized `StoredValue` found under the key.
+    pub value: String,
+    /// The hex-encoded serialized steps from the entry's leaf up to the state root.
+    pub proof_steps: String,
+}
+
+/// Merkle proofs of a global state query against the state root hash of a bundle's tip.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct StateProof {
+    /// `casper_types::Key` as formatted string.
+    pub key: String,
+    /// The path components starting from the key as base.
+    pub path: Vec<String>,
+    /// The merkle proofs of the values read while following the path.
+    pub merkle_proofs: Vec<MerkleProof>,
+}
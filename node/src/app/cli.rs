@@ -5,7 +5,7 @@
 pub mod arglang;
 
 use std::{
-    env, fs,
+    env, fs, io,
     path::{Path, PathBuf},
     str::FromStr,
 };
@@ -25,6 +25,7 @@ use casper_node::{
         pid_file::{PidFile, PidFileOutcome},
         WithDir,
     },
+    CaptureFilter, TrafficDirection,
 };
 use prometheus::Registry;
 
@@ -75,6 +76,55 @@ pub enum Cli {
         #[structopt(long)]
         new_config: PathBuf,
     },
+    /// Delete global state which is not required for the most recent blocks.
+    ///
+    /// Uses the `[contract_runtime.garbage_collection]` section of the configuration file.  The
+    /// node must not be running.
+    PruneGlobalState {
+        /// Path to configuration file.
+        config: PathBuf,
+    },
+    /// Print messages recorded by the network traffic recorder, or a summary of them.
+    InspectCapture {
+        /// Path to a capture file or a directory containing capture files.
+        path: PathBuf,
+        /// Only include messages exchanged with peers whose hex-encoded node ID starts with this.
+        #[structopt(long)]
+        peer: Option<String>,
+        /// Only include messages in this direction, 'incoming' or 'outgoing'.
+        #[structopt(long)]
+        direction: Option<TrafficDirection>,
+        /// Only include messages of this kind, e.g. 'consensus', 'deploy_gossip' or
+        /// 'block_transfer'.
+        #[structopt(long)]
+        kind: Option<String>,
+        /// Print the number and size of messages per peer, direction and kind instead.
+        #[structopt(long)]
+        summary: bool,
+    },
+    /// Run the validator node, replaying recorded network traffic instead of connecting to peers.
+    ///
+    /// The node neither accepts nor initiates any connections. Once it participates, the incoming
+    /// messages of the capture are fed to it, keeping their original delays. To reproduce the
+    /// recorded behavior, use a copy of the storage of the recording node and no trusted hash.
+    ReplayCapture {
+        /// Path to configuration file.
+        config: PathBuf,
+
+        /// Path to a capture file or a directory containing capture files.
+        capture: PathBuf,
+
+        #[structopt(
+            short = "C",
+            long,
+            env = "NODE_CONFIG",
+            use_delimiter(true),
+            value_delimiter(";")
+        )]
+        /// Overrides and extensions for configuration file entries in the form
+        /// <SECTION>.<KEY>=<VALUE>.  For example, '-C=node.chainspec_config_path=chainspec.toml'
+        config_ext: Vec<ConfigExt>,
+    },
 }
 
 #[derive(Debug)]
@@ -86,6 +136,15 @@ pub struct ConfigExt {
 }
 
 impl ConfigExt {
+    /// Creates an extension setting a string value, quoted to be never parsed as another type.
+    fn string(section: &str, key: &str, value: &str) -> Self {
+        ConfigExt {
+            section: section.to_string(),
+            key: key.to_string(),
+            value: format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\"")),
+        }
+    }
+
     /// Updates TOML table with updated or extended key value pairs.
     ///
     /// Returns errors if the respective sections to be updated are not TOML tables or if parsing
@@ -142,107 +201,7 @@ impl Cli {
     /// Executes selected CLI command.
     pub async fn run(self) -> anyhow::Result<i32> {
         match self {
-            Cli::Validator { config, config_ext } => {
-                // Setup UNIX signal hooks.
-                setup_signal_hooks();
-
-                let validator_config = Self::init(&config, config_ext)?;
-                info!(version = %casper_node::VERSION_STRING.as_str(), "node starting up");
-
-                let pidfile_outcome = {
-                    // Determine storage directory to store pidfile in.
-                    let storage_config = validator_config.map_ref(|cfg| cfg.storage.clone());
-                    let root = storage_config.with_dir(storage_config.value().path.clone());
-
-                    // Create directory if it does not exist, similar to how the storage component
-                    // would do it.
-                    if !root.exists() {
-                        fs::create_dir_all(&root).context("create storage directory")?;
-                    }
-
-                    PidFile::acquire(root.join("initializer.pid"))
-                };
-
-                // Note: Do not change `_pidfile` to `_`, or it will be dropped prematurely.
-                // Instantiating `pidfile` guarantees that it will be dropped _after_ any reactor,
-                // which is what we want.
-                let (_pidfile, crashed) = match pidfile_outcome {
-                    PidFileOutcome::AnotherNodeRunning(_) => {
-                        anyhow::bail!("another node instance is running (pidfile is locked)");
-                    }
-                    PidFileOutcome::Crashed(pidfile) => {
-                        warn!("previous node instance seems to have crashed, integrity checks may be run");
-                        (pidfile, true)
-                    }
-                    PidFileOutcome::Clean(pidfile) => {
-                        info!("no previous crash detected");
-                        (pidfile, false)
-                    }
-                    PidFileOutcome::PidFileError(err) => {
-                        return Err(anyhow::anyhow!(err));
-                    }
-                };
-
-                // We use a `ChaCha20Rng` for the production node. For one, we want to completely
-                // eliminate any chance of runtime failures, regardless of how small (these
-                // exist with `OsRng`). Additionally, we want to limit the number of syscalls for
-                // performance reasons.
-                let mut rng = casper_node::new_rng();
-
-                // The metrics are shared across all reactors.
-                let registry = Registry::new();
-
-                let mut initializer_runner = Runner::<initializer::Reactor>::with_metrics(
-                    (crashed, validator_config),
-                    &mut rng,
-                    &registry,
-                )
-                .await?;
-
-                // let mut initializer2_runner = Runner::<initializer2::Initializer>::with_metrics(
-                //     WithDir::new(root.clone(), validator_config),
-                //     &mut rng,
-                //     &registry,
-                // )
-                // .await?;
-                // initializer2_runner.run(&mut rng).await;
-
-                match initializer_runner.run(&mut rng).await {
-                    ReactorExit::ProcessShouldExit(exit_code) => return Ok(exit_code as i32),
-                    ReactorExit::ProcessShouldContinue => info!("finished initialization"),
-                }
-
-                let initializer = initializer_runner.drain_into_inner().await;
-                let root = config
-                    .parent()
-                    .map(|path| path.to_owned())
-                    .unwrap_or_else(|| "/".into());
-                let mut joiner_runner = Runner::<joiner::Reactor>::with_metrics(
-                    WithDir::new(root, initializer),
-                    &mut rng,
-                    &registry,
-                )
-                .await?;
-                match joiner_runner.run(&mut rng).await {
-                    ReactorExit::ProcessShouldExit(exit_code) => return Ok(exit_code as i32),
-                    ReactorExit::ProcessShouldContinue => info!("finished joining"),
-                }
-
-                let joiner_reactor = joiner_runner.drain_into_inner().await;
-                let config = joiner_reactor.into_participating_config().await?;
-
-                let mut validator_runner =
-                    Runner::<participating::Reactor>::with_metrics(config, &mut rng, &registry)
-                        .await?;
-
-                match validator_runner.run(&mut rng).await {
-                    ReactorExit::ProcessShouldExit(exit_code) => Ok(exit_code as i32),
-                    reactor_exit => {
-                        error!("validator should not exit with {:?}", reactor_exit);
-                        Ok(ExitCode::Abort as i32)
-                    }
-                }
-            }
+            Cli::Validator { config, config_ext } => Self::run_validator(config, config_ext).await,
             Cli::MigrateConfig {
                 old_config,
                 new_config,
@@ -281,6 +240,147 @@ impl Cli {
                 casper_node::migrate_data(WithDir::new(old_root, old_config), new_config)?;
                 Ok(ExitCode::Success as i32)
             }
+            Cli::PruneGlobalState { config } => {
+                let config = Self::init(&config, vec![])?;
+
+                info!(version = %env!("CARGO_PKG_VERSION"), "pruning global state");
+                casper_node::prune_global_state(config)?;
+                Ok(ExitCode::Success as i32)
+            }
+            Cli::InspectCapture {
+                path,
+                peer,
+                direction,
+                kind,
+                summary,
+            } => {
+                let filter = CaptureFilter {
+                    peer,
+                    direction,
+                    kind,
+                };
+                let stdout = io::stdout();
+                casper_node::inspect_capture(&path, &filter, summary, &mut stdout.lock())?;
+                Ok(ExitCode::Success as i32)
+            }
+            Cli::ReplayCapture {
+                config,
+                capture,
+                mut config_ext,
+            } => {
+                let capture = fs::canonicalize(&capture)
+                    .context("could not find capture")
+                    .with_context(|| capture.display().to_string())?;
+                config_ext.push(ConfigExt::string(
+                    "network",
+                    "replay_capture",
+                    &capture.display().to_string(),
+                ));
+                Self::run_validator(config, config_ext).await
+            }
+        }
+    }
+
+    /// Runs the validator node, consecutively running the initializer, joiner and participating
+    /// reactors.
+    async fn run_validator(config: PathBuf, config_ext: Vec<ConfigExt>) -> anyhow::Result<i32> {
+        // Setup UNIX signal hooks.
+        setup_signal_hooks();
+
+        let validator_config = Self::init(&config, config_ext)?;
+        info!(version = %casper_node::VERSION_STRING.as_str(), "node starting up");
+
+        let pidfile_outcome = {
+            // Determine storage directory to store pidfile in.
+            let storage_config = validator_config.map_ref(|cfg| cfg.storage.clone());
+            let root = storage_config.with_dir(storage_config.value().path.clone());
+
+            // Create directory if it does not exist, similar to how the storage component
+            // would do it.
+            if !root.exists() {
+                fs::create_dir_all(&root).context("create storage directory")?;
+            }
+
+            PidFile::acquire(root.join("initializer.pid"))
+        };
+
+        // Note: Do not change `_pidfile` to `_`, or it will be dropped prematurely.
+        // Instantiating `pidfile` guarantees that it will be dropped _after_ any reactor,
+        // which is what we want.
+        let (_pidfile, crashed) = match pidfile_outcome {
+            PidFileOutcome::AnotherNodeRunning(_) => {
+                anyhow::bail!("another node instance is running (pidfile is locked)");
+            }
+            PidFileOutcome::Crashed(pidfile) => {
+                warn!("previous node instance seems to have crashed, integrity checks may be run");
+                (pidfile, true)
+            }
+            PidFileOutcome::Clean(pidfile) => {
+                info!("no previous crash detected");
+                (pidfile, false)
+            }
+            PidFileOutcome::PidFileError(err) => {
+                return Err(anyhow::anyhow!(err));
+            }
+        };
+
+        // We use a `ChaCha20Rng` for the production node. For one, we want to completely
+        // eliminate any chance of runtime failures, regardless of how small (these
+        // exist with `OsRng`). Additionally, we want to limit the number of syscalls for
+        // performance reasons.
+        let mut rng = casper_node::new_rng();
+
+        // The metrics are shared across all reactors.
+        let registry = Registry::new();
+
+        let mut initializer_runner = Runner::<initializer::Reactor>::with_metrics(
+            (crashed, validator_config),
+            &mut rng,
+            &registry,
+        )
+        .await?;
+
+        // let mut initializer2_runner = Runner::<initializer2::Initializer>::with_metrics(
+        //     WithDir::new(root.clone(), validator_config),
+        //     &mut rng,
+        //     &registry,
+        // )
+        // .await?;
+        // initializer2_runner.run(&mut rng).await;
+
+        match initializer_runner.run(&mut rng).await {
+            ReactorExit::ProcessShouldExit(exit_code) => return Ok(exit_code as i32),
+            ReactorExit::ProcessShouldContinue => info!("finished initialization"),
+        }
+
+        let initializer = initializer_runner.drain_into_inner().await;
+        let root = config
+            .parent()
+            .map(|path| path.to_owned())
+            .unwrap_or_else(|| "/".into());
+        let mut joiner_runner = Runner::<joiner::Reactor>::with_metrics(
+            WithDir::new(root, initializer),
+            &mut rng,
+            &registry,
+        )
+        .await?;
+        match joiner_runner.run(&mut rng).await {
+            ReactorExit::ProcessShouldExit(exit_code) => return Ok(exit_code as i32),
+            ReactorExit::ProcessShouldContinue => info!("finished joining"),
+        }
+
+        let joiner_reactor = joiner_runner.drain_into_inner().await;
+        let config = joiner_reactor.into_participating_config().await?;
+
+        let mut validator_runner =
+            Runner::<participating::Reactor>::with_metrics(config, &mut rng, &registry).await?;
+
+        match validator_runner.run(&mut rng).await {
+            ReactorExit::ProcessShouldExit(exit_code) => Ok(exit_code as i32),
+            reactor_exit => {
+                error!("validator should not exit with {:?}", reactor_exit);
+                Ok(ExitCode::Abort as i32)
+            }
         }
     }
 
@@ -7,6 +7,7 @@ mod config;
 mod deploy_sets;
 mod event;
 mod metrics;
+mod priority_index;
 #[cfg(test)]
 mod tests;
 
@@ -333,12 +334,10 @@ impl BlockProposerReady {
 
         if hash.is_transfer() {
             self.sets
-                .pending_transfers
-                .insert(*hash.deploy_hash(), (deploy_info, current_instant));
+                .add_pending_transfer(*hash.deploy_hash(), deploy_info, current_instant);
         } else {
             self.sets
-                .pending_deploys
-                .insert(*hash.deploy_hash(), (deploy_info, current_instant));
+                .add_pending_deploy(*hash.deploy_hash(), deploy_info, current_instant);
         }
 
         info!(%hash, "added deploy to the buffer");
@@ -352,10 +351,10 @@ impl BlockProposerReady {
         for deploy_hash in deploys.into_iter() {
             let (hash, remove_result) = match deploy_hash {
                 DeployOrTransferHash::Deploy(hash) => {
-                    (hash, self.sets.pending_deploys.remove(&hash))
+                    (hash, self.sets.remove_pending_deploy(&hash))
                 }
                 DeployOrTransferHash::Transfer(hash) => {
-                    (hash, self.sets.pending_transfers.remove(&hash))
+                    (hash, self.sets.remove_pending_transfer(&hash))
                 }
             };
             match remove_result {
@@ -412,7 +411,29 @@ impl BlockProposerReady {
             .all(|dep| past_deploys.contains(dep) || self.contains_finalized(dep))
     }
 
+    /// Checks if a pending deploy is already included in the given past deploys or finalized.
+    fn is_included(&self, hash: &DeployHash, past_deploys: &HashSet<DeployHash>) -> bool {
+        past_deploys.contains(hash) || self.contains_finalized(hash)
+    }
+
+    /// Checks if a pending deploy is eligible for inclusion in a block with the given timestamp.
+    fn is_eligible(
+        &self,
+        deploy_info: &DeployInfo,
+        received_time: Timestamp,
+        block_timestamp: Timestamp,
+        past_deploys: &HashSet<DeployHash>,
+    ) -> bool {
+        deploy_info.header.gas_price() >= self.local_config.min_gas_price
+            && self.deps_resolved(&deploy_info.header, past_deploys)
+            && block_timestamp.saturating_diff(received_time) >= self.local_config.deploy_delay
+    }
+
     /// Returns a list of candidates for inclusion into a block.
+    ///
+    /// Pending deploys and transfers are considered in order of priority: the highest gas price is
+    /// preferred, while each account's deploys are kept in the order in which they were created.
+    /// Once one of an account's deploys can't be included, none of its later ones are proposed.
     fn propose_block_payload(
         &mut self,
         deploy_config: DeployConfig,
@@ -420,7 +441,7 @@ impl BlockProposerReady {
         accusations: Vec<PublicKey>,
         random_bit: bool,
     ) -> Arc<BlockPayload> {
-        let past_deploys = context
+        let past_deploys: HashSet<DeployHash> = context
             .ancestor_values()
             .iter()
             .flat_map(|block_payload| block_payload.deploys_and_transfers_iter())
@@ -431,16 +452,27 @@ impl BlockProposerReady {
         let mut appendable_block = AppendableBlock::new(deploy_config, block_timestamp);
 
         // We prioritize transfers over deploys, so we try to include them first.
-        for (hash, (deploy_info, received_time)) in &self.sets.pending_transfers {
-            if !self.deps_resolved(&deploy_info.header, &past_deploys)
-                || past_deploys.contains(hash)
-                || self.contains_finalized(hash)
-                || block_timestamp.saturating_diff(*received_time) < self.local_config.deploy_delay
-            {
+        // Accounts with a transfer that is not included, whose later transfers are skipped.
+        let mut skipped_accounts = HashSet::new();
+        for hash in self.sets.transfers_by_priority.iter() {
+            let (deploy_info, received_time) = match self.sets.pending_transfers.get(&hash) {
+                Some(pending) => pending,
+                None => {
+                    error!(%hash, "transfer in priority index is not pending");
+                    continue;
+                }
+            };
+            let account = deploy_info.header.account();
+            if skipped_accounts.contains(account) || self.is_included(&hash, &past_deploys) {
+                continue;
+            }
+            if !self.is_eligible(deploy_info, *received_time, block_timestamp, &past_deploys) {
+                skipped_accounts.insert(account);
                 continue;
             }
 
-            if let Err(err) = appendable_block.add_transfer(*hash, deploy_info) {
+            if let Err(err) = appendable_block.add_transfer(hash, deploy_info) {
+                skipped_accounts.insert(account);
                 match err {
                     // We added the maximum number of transfers.
                     AddError::TransferCount | AddError::GasLimit | AddError::BlockSize => break,
@@ -455,16 +487,27 @@ impl BlockProposerReady {
         }
 
         // Now we try to add other deploys to the block.
-        for (hash, (deploy_info, received_time)) in &self.sets.pending_deploys {
-            if !self.deps_resolved(&deploy_info.header, &past_deploys)
-                || past_deploys.contains(hash)
-                || self.contains_finalized(hash)
-                || block_timestamp.saturating_diff(*received_time) < self.local_config.deploy_delay
-            {
+        // Accounts with a deploy that is not included, whose later deploys are skipped.
+        let mut skipped_accounts = HashSet::new();
+        for hash in self.sets.deploys_by_priority.iter() {
+            let (deploy_info, received_time) = match self.sets.pending_deploys.get(&hash) {
+                Some(pending) => pending,
+                None => {
+                    error!(%hash, "deploy in priority index is not pending");
+                    continue;
+                }
+            };
+            let account = deploy_info.header.account();
+            if skipped_accounts.contains(account) || self.is_included(&hash, &past_deploys) {
+                continue;
+            }
+            if !self.is_eligible(deploy_info, *received_time, block_timestamp, &past_deploys) {
+                skipped_accounts.insert(account);
                 continue;
             }
 
-            if let Err(err) = appendable_block.add_deploy(*hash, deploy_info) {
+            if let Err(err) = appendable_block.add_deploy(hash, deploy_info) {
+                skipped_accounts.insert(account);
                 match err {
                     // We added the maximum number of deploys.
                     AddError::DeployCount => break,
@@ -13,12 +13,16 @@ pub struct Config {
     /// other nodes, and don't have to be requested from the proposer afterwards.
     #[serde(default = "default_deploy_delay")]
     pub deploy_delay: TimeDiff,
+    /// The minimum gas price of deploys accepted from clients and proposed in a new block.
+    #[serde(default = "default_min_gas_price")]
+    pub min_gas_price: u64,
 }
 
 impl Default for Config {
     fn default() -> Config {
         Config {
             deploy_delay: default_deploy_delay(),
+            min_gas_price: default_min_gas_price(),
         }
     }
 }
@@ -26,3 +30,7 @@ impl Default for Config {
 fn default_deploy_delay() -> TimeDiff {
     "1min".parse().unwrap()
 }
+
+fn default_min_gas_price() -> u64 {
+    1
+}
@@ -5,7 +5,7 @@ use std::{
 
 use datasize::DataSize;
 
-use super::{event::DeployInfo, BlockHeight, FinalizationQueue};
+use super::{event::DeployInfo, priority_index::PriorityIndex, BlockHeight, FinalizationQueue};
 use crate::types::{DeployHash, DeployHeader, Timestamp};
 
 /// Stores the internal state of the BlockProposer.
@@ -17,6 +17,10 @@ pub(super) struct BlockProposerDeploySets {
     /// The collection of transfers pending for inclusion in a block, with a timestamp of when we
     /// received them.
     pub(super) pending_transfers: HashMap<DeployHash, (DeployInfo, Timestamp)>,
+    /// The pending deploys, ordered by their priority for inclusion in a block.
+    pub(super) deploys_by_priority: PriorityIndex,
+    /// The pending transfers, ordered by their priority for inclusion in a block.
+    pub(super) transfers_by_priority: PriorityIndex,
     /// The deploys that have already been included in a finalized block.
     pub(super) finalized_deploys: HashMap<DeployHash, DeployHeader>,
     /// The next block height we expect to be finalized.
@@ -43,6 +47,64 @@ impl BlockProposerDeploySets {
     }
 }
 
+impl BlockProposerDeploySets {
+    /// Adds a deploy to the pending deploys.
+    pub(super) fn add_pending_deploy(
+        &mut self,
+        hash: DeployHash,
+        deploy_info: DeployInfo,
+        received_time: Timestamp,
+    ) {
+        add_pending(
+            &mut self.pending_deploys,
+            &mut self.deploys_by_priority,
+            hash,
+            deploy_info,
+            received_time,
+        )
+    }
+
+    /// Adds a transfer to the pending transfers.
+    pub(super) fn add_pending_transfer(
+        &mut self,
+        hash: DeployHash,
+        deploy_info: DeployInfo,
+        received_time: Timestamp,
+    ) {
+        add_pending(
+            &mut self.pending_transfers,
+            &mut self.transfers_by_priority,
+            hash,
+            deploy_info,
+            received_time,
+        )
+    }
+
+    /// Removes a deploy from the pending deploys, returning it if it was present.
+    pub(super) fn remove_pending_deploy(
+        &mut self,
+        hash: &DeployHash,
+    ) -> Option<(DeployInfo, Timestamp)> {
+        remove_pending(
+            &mut self.pending_deploys,
+            &mut self.deploys_by_priority,
+            hash,
+        )
+    }
+
+    /// Removes a transfer from the pending transfers, returning it if it was present.
+    pub(super) fn remove_pending_transfer(
+        &mut self,
+        hash: &DeployHash,
+    ) -> Option<(DeployInfo, Timestamp)> {
+        remove_pending(
+            &mut self.pending_transfers,
+            &mut self.transfers_by_priority,
+            hash,
+        )
+    }
+}
+
 impl Display for BlockProposerDeploySets {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(
@@ -58,8 +120,16 @@ impl BlockProposerDeploySets {
     /// Prunes expired deploy information from the BlockProposerState, returns the total deploys
     /// pruned
     pub(crate) fn prune(&mut self, current_instant: Timestamp) -> usize {
-        let pending_deploys = prune_pending_deploys(&mut self.pending_deploys, current_instant);
-        let pending_transfers = prune_pending_deploys(&mut self.pending_transfers, current_instant);
+        let pending_deploys = prune_pending_deploys(
+            &mut self.pending_deploys,
+            &mut self.deploys_by_priority,
+            current_instant,
+        );
+        let pending_transfers = prune_pending_deploys(
+            &mut self.pending_transfers,
+            &mut self.transfers_by_priority,
+            current_instant,
+        );
         let finalized = prune_deploys(&mut self.finalized_deploys, current_instant);
         pending_deploys + pending_transfers + finalized
     }
@@ -76,13 +146,43 @@ pub(super) fn prune_deploys(
     initial_len - deploys.len()
 }
 
-/// Prunes expired deploy information from an individual pending deploy collection, returns the
-/// total deploys pruned
+/// Prunes expired deploy information from an individual pending deploy collection and its priority
+/// index, returns the total deploys pruned
 pub(super) fn prune_pending_deploys(
     deploys: &mut HashMap<DeployHash, (DeployInfo, Timestamp)>,
+    priority_index: &mut PriorityIndex,
     current_instant: Timestamp,
 ) -> usize {
     let initial_len = deploys.len();
-    deploys.retain(|_hash, (deploy_info, _)| !deploy_info.header.expired(current_instant));
+    deploys.retain(|hash, (deploy_info, _)| {
+        let expired = deploy_info.header.expired(current_instant);
+        if expired {
+            priority_index.remove(*hash, &deploy_info.header);
+        }
+        !expired
+    });
     initial_len - deploys.len()
 }
+
+/// Adds a deploy to an individual pending deploy collection and its priority index.
+fn add_pending(
+    deploys: &mut HashMap<DeployHash, (DeployInfo, Timestamp)>,
+    priority_index: &mut PriorityIndex,
+    hash: DeployHash,
+    deploy_info: DeployInfo,
+    received_time: Timestamp,
+) {
+    priority_index.insert(hash, &deploy_info.header);
+    deploys.insert(hash, (deploy_info, received_time));
+}
+
+/// Removes a deploy from an individual pending deploy collection and its priority index.
+fn remove_pending(
+    deploys: &mut HashMap<DeployHash, (DeployInfo, Timestamp)>,
+    priority_index: &mut PriorityIndex,
+    hash: &DeployHash,
+) -> Option<(DeployInfo, Timestamp)> {
+    let removed = deploys.remove(hash)?;
+    priority_index.remove(*hash, &removed.0.header);
+    Some(removed)
+}
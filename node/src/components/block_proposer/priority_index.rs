@@ -0,0 +1,132 @@
+use std::{
+    cmp::Ordering,
+    collections::{btree_set, BTreeSet, BinaryHeap, HashMap},
+};
+
+use datasize::DataSize;
+
+use casper_types::PublicKey;
+
+use crate::types::{DeployHash, DeployHeader, Timestamp};
+
+/// The position of a pending deploy in the queue of the account which created it.
+#[derive(Clone, Copy, DataSize, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct DeployPriority {
+    /// The creation timestamp of the deploy, determining its position within the account's queue.
+    timestamp: Timestamp,
+    /// The gas price the deploy offers to pay.
+    gas_price: u64,
+    /// The hash of the deploy.
+    hash: DeployHash,
+}
+
+impl DeployPriority {
+    fn new(hash: DeployHash, header: &DeployHeader) -> Self {
+        DeployPriority {
+            timestamp: header.timestamp(),
+            gas_price: header.gas_price(),
+            hash,
+        }
+    }
+}
+
+/// An index of pending deploys, ordered by their priority for inclusion in a block.
+///
+/// Each account's deploys are queued in the order they were created, so that a later deploy never
+/// overtakes an earlier one from the same account. Across accounts, the deploy at the front of the
+/// queue offering the highest gas price is proposed first, with ties going to the oldest deploy.
+#[derive(Clone, DataSize, Debug, Default)]
+pub(super) struct PriorityIndex {
+    /// The queue of pending deploys of each account.
+    by_account: HashMap<PublicKey, BTreeSet<DeployPriority>>,
+}
+
+impl PriorityIndex {
+    /// Adds a deploy to the index.
+    pub(super) fn insert(&mut self, hash: DeployHash, header: &DeployHeader) {
+        self.by_account
+            .entry(header.account().clone())
+            .or_default()
+            .insert(DeployPriority::new(hash, header));
+    }
+
+    /// Removes a deploy from the index.
+    pub(super) fn remove(&mut self, hash: DeployHash, header: &DeployHeader) {
+        if let Some(queue) = self.by_account.get_mut(header.account()) {
+            queue.remove(&DeployPriority::new(hash, header));
+            if queue.is_empty() {
+                self.by_account.remove(header.account());
+            }
+        }
+    }
+
+    /// Returns the number of deploys in the index.
+    #[cfg(test)]
+    pub(super) fn len(&self) -> usize {
+        self.by_account.values().map(BTreeSet::len).sum()
+    }
+
+    /// Returns an iterator over the hashes of the indexed deploys, in order of priority.
+    pub(super) fn iter(&self) -> PriorityIter<'_> {
+        let heads = self
+            .by_account
+            .values()
+            .filter_map(|queue| QueueHead::new(queue.iter()))
+            .collect();
+        PriorityIter { heads }
+    }
+}
+
+/// The front of an account's queue of deploys, ordered by priority across accounts.
+struct QueueHead<'a> {
+    head: &'a DeployPriority,
+    rest: btree_set::Iter<'a, DeployPriority>,
+}
+
+impl<'a> QueueHead<'a> {
+    fn new(mut queue: btree_set::Iter<'a, DeployPriority>) -> Option<Self> {
+        queue.next().map(|head| QueueHead { head, rest: queue })
+    }
+}
+
+impl<'a> Ord for QueueHead<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // `BinaryHeap` is a max-heap: higher gas prices, then older deploys, come first.
+        self.head
+            .gas_price
+            .cmp(&other.head.gas_price)
+            .then_with(|| other.head.timestamp.cmp(&self.head.timestamp))
+            .then_with(|| other.head.hash.cmp(&self.head.hash))
+    }
+}
+
+impl<'a> PartialOrd for QueueHead<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> PartialEq for QueueHead<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a> Eq for QueueHead<'a> {}
+
+/// An iterator over the hashes of the deploys in a `PriorityIndex`, in order of priority.
+pub(super) struct PriorityIter<'a> {
+    heads: BinaryHeap<QueueHead<'a>>,
+}
+
+impl<'a> Iterator for PriorityIter<'a> {
+    type Item = DeployHash;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let QueueHead { head, rest } = self.heads.pop()?;
+        if let Some(next_head) = QueueHead::new(rest) {
+            self.heads.push(next_head);
+        }
+        Some(head.hash)
+    }
+}
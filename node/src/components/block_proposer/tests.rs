@@ -63,6 +63,24 @@ fn generate_deploy(
     gas_price: u64,
 ) -> Deploy {
     let secret_key = SecretKey::random(rng);
+    generate_deploy_from(
+        &secret_key,
+        timestamp,
+        ttl,
+        dependencies,
+        payment_amount,
+        gas_price,
+    )
+}
+
+fn generate_deploy_from(
+    secret_key: &SecretKey,
+    timestamp: Timestamp,
+    ttl: TimeDiff,
+    dependencies: Vec<DeployHash>,
+    payment_amount: Gas,
+    gas_price: u64,
+) -> Deploy {
     let chain_name = "chain".to_string();
     let args = runtime_args! {
         ARG_AMOUNT => payment_amount.value()
@@ -84,13 +102,16 @@ fn generate_deploy(
         chain_name,
         payment,
         session,
-        &secret_key,
+        secret_key,
     )
 }
 
 fn create_test_proposer(deploy_delay: TimeDiff) -> BlockProposerReady {
     BlockProposerReady {
-        local_config: Config { deploy_delay },
+        local_config: Config {
+            deploy_delay,
+            ..Default::default()
+        },
         ..Default::default()
     }
 }
@@ -325,6 +346,7 @@ fn should_successfully_prune() {
     proposer.finalized_deploys(vec![deploy1.deploy_or_transfer_hash()]);
 
     assert_eq!(proposer.sets.pending_deploys.len(), 3);
+    assert_eq!(proposer.sets.deploys_by_priority.len(), 3);
     assert!(proposer.sets.finalized_deploys.contains_key(deploy1.id()));
 
     // test for retained values
@@ -340,6 +362,7 @@ fn should_successfully_prune() {
     assert_eq!(pruned, 3);
 
     assert_eq!(proposer.sets.pending_deploys.len(), 1); // deploy4 is still valid
+    assert_eq!(proposer.sets.deploys_by_priority.len(), 1);
     assert_eq!(proposer.sets.finalized_deploys.len(), 0);
 }
 
@@ -754,3 +777,223 @@ fn should_respect_deploy_delay() {
     );
     assert_eq!(&vec![*deploy.id()], block.deploy_hashes());
 }
+
+#[test]
+fn should_propose_deploys_with_highest_gas_price_first() {
+    let mut rng = crate::new_rng();
+    let creation_time = Timestamp::from(100);
+    let ttl = TimeDiff::from(Duration::from_millis(100));
+    let block_time = Timestamp::from(120);
+
+    let mut proposer = create_test_proposer(0.into());
+    let mut deploy_config = DeployConfig::default();
+    deploy_config.block_max_deploy_count = 2;
+
+    let deploys = [3, 1, 5, 2]
+        .iter()
+        .map(|gas_price| {
+            generate_deploy(
+                &mut rng,
+                creation_time,
+                ttl,
+                vec![],
+                default_gas_payment(),
+                *gas_price,
+            )
+        })
+        .collect_vec();
+    for deploy in &deploys {
+        proposer.add_deploy(
+            creation_time,
+            deploy.deploy_or_transfer_hash(),
+            deploy.deploy_info().unwrap(),
+        );
+    }
+
+    let block = proposer.propose_block_payload(
+        deploy_config,
+        BlockContext::new(block_time, vec![]),
+        vec![],
+        true,
+    );
+    assert_eq!(
+        &vec![*deploys[2].id(), *deploys[0].id()],
+        block.deploy_hashes()
+    );
+}
+
+#[test]
+fn should_keep_account_deploys_in_creation_order() {
+    let mut rng = crate::new_rng();
+    let ttl = TimeDiff::from(Duration::from_millis(100));
+    let block_time = Timestamp::from(120);
+
+    let mut proposer = create_test_proposer(0.into());
+    let mut deploy_config = DeployConfig::default();
+    deploy_config.block_max_deploy_count = 2;
+
+    // The later deploy of account 1 offers the highest gas price, but can't overtake the earlier.
+    let secret_key1 = SecretKey::random(&mut rng);
+    let secret_key2 = SecretKey::random(&mut rng);
+    let deploy1 = generate_deploy_from(
+        &secret_key1,
+        Timestamp::from(100),
+        ttl,
+        vec![],
+        default_gas_payment(),
+        1,
+    );
+    let deploy2 = generate_deploy_from(
+        &secret_key1,
+        Timestamp::from(101),
+        ttl,
+        vec![],
+        default_gas_payment(),
+        10,
+    );
+    let deploy3 = generate_deploy_from(
+        &secret_key2,
+        Timestamp::from(100),
+        ttl,
+        vec![],
+        default_gas_payment(),
+        5,
+    );
+    for deploy in &[&deploy2, &deploy3, &deploy1] {
+        proposer.add_deploy(
+            Timestamp::from(100),
+            deploy.deploy_or_transfer_hash(),
+            deploy.deploy_info().unwrap(),
+        );
+    }
+
+    let block = proposer.propose_block_payload(
+        deploy_config,
+        BlockContext::new(block_time, vec![]),
+        vec![],
+        true,
+    );
+    assert_eq!(&vec![*deploy3.id(), *deploy1.id()], block.deploy_hashes());
+}
+
+#[test]
+fn should_not_propose_deploys_below_min_gas_price() {
+    let mut rng = crate::new_rng();
+    let creation_time = Timestamp::from(100);
+    let ttl = TimeDiff::from(Duration::from_millis(100));
+    let block_time = Timestamp::from(120);
+
+    let mut proposer = BlockProposerReady {
+        local_config: Config {
+            deploy_delay: 0.into(),
+            min_gas_price: 2,
+        },
+        ..Default::default()
+    };
+
+    let cheap_deploy = generate_deploy(
+        &mut rng,
+        creation_time,
+        ttl,
+        vec![],
+        default_gas_payment(),
+        1,
+    );
+    let deploy = generate_deploy(
+        &mut rng,
+        creation_time,
+        ttl,
+        vec![],
+        default_gas_payment(),
+        2,
+    );
+    for deploy in &[&cheap_deploy, &deploy] {
+        proposer.add_deploy(
+            creation_time,
+            deploy.deploy_or_transfer_hash(),
+            deploy.deploy_info().unwrap(),
+        );
+    }
+
+    let block = proposer.propose_block_payload(
+        DeployConfig::default(),
+        BlockContext::new(block_time, vec![]),
+        vec![],
+        true,
+    );
+    assert_eq!(&vec![*deploy.id()], block.deploy_hashes());
+}
+
+#[test]
+fn should_not_propose_account_deploys_after_an_ineligible_one() {
+    let mut rng = crate::new_rng();
+    let ttl = TimeDiff::from(Duration::from_millis(100));
+    let block_time = Timestamp::from(120);
+
+    let mut proposer = create_test_proposer(0.into());
+
+    // The first deploy of account 1 depends on an unknown deploy, so it's not eligible, and its
+    // later deploy must not overtake it.
+    let unknown_deploy = generate_deploy(
+        &mut rng,
+        Timestamp::from(100),
+        ttl,
+        vec![],
+        default_gas_payment(),
+        1,
+    );
+    let secret_key1 = SecretKey::random(&mut rng);
+    let secret_key2 = SecretKey::random(&mut rng);
+    let deploy1 = generate_deploy_from(
+        &secret_key1,
+        Timestamp::from(100),
+        ttl,
+        vec![*unknown_deploy.id()],
+        default_gas_payment(),
+        1,
+    );
+    let deploy2 = generate_deploy_from(
+        &secret_key1,
+        Timestamp::from(101),
+        ttl,
+        vec![],
+        default_gas_payment(),
+        1,
+    );
+    let deploy3 = generate_deploy_from(
+        &secret_key2,
+        Timestamp::from(100),
+        ttl,
+        vec![],
+        default_gas_payment(),
+        1,
+    );
+    for deploy in &[&deploy1, &deploy2, &deploy3] {
+        proposer.add_deploy(
+            Timestamp::from(100),
+            deploy.deploy_or_transfer_hash(),
+            deploy.deploy_info().unwrap(),
+        );
+    }
+
+    let block = proposer.propose_block_payload(
+        DeployConfig::default(),
+        BlockContext::new(block_time, vec![]),
+        vec![],
+        true,
+    );
+    assert_eq!(&vec![*deploy3.id()], block.deploy_hashes());
+
+    // Once the dependency is finalized, both deploys of account 1 are proposed in order.
+    proposer.finalized_deploys(vec![unknown_deploy.deploy_or_transfer_hash()]);
+    let block = proposer.propose_block_payload(
+        DeployConfig::default(),
+        BlockContext::new(block_time, vec![]),
+        vec![],
+        true,
+    );
+    let hashes = block.deploy_hashes();
+    let position = |deploy: &Deploy| hashes.iter().position(|hash| hash == deploy.id());
+    assert_eq!(hashes.len(), 3);
+    assert!(position(&deploy1) < position(&deploy2));
+}
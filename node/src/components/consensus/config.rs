@@ -1,6 +1,7 @@
 use std::{path::Path, sync::Arc};
 
 use datasize::DataSize;
+use num::rational::Ratio;
 use serde::Deserialize;
 
 use casper_types::{ProtocolVersion, PublicKey, SecretKey};
@@ -56,6 +57,9 @@ pub(crate) struct ProtocolConfig {
     /// auction_delay + 1
     pub(crate) auction_delay: u64,
     pub(crate) unbonding_delay: u64,
+    /// The fraction of an equivocator's stake which is slashed.
+    #[data_size(skip)]
+    pub(crate) slashing_rate: Ratio<u64>,
     /// The network protocol version.
     #[data_size(skip)]
     pub(crate) protocol_version: ProtocolVersion,
@@ -77,6 +81,7 @@ impl From<&Chainspec> for ProtocolConfig {
             minimum_era_height: chainspec.core_config.minimum_era_height,
             auction_delay: chainspec.core_config.auction_delay,
             unbonding_delay: chainspec.core_config.unbonding_delay,
+            slashing_rate: chainspec.core_config.slashing_rate,
             protocol_version: chainspec.protocol_config.version,
             last_activation_point: chainspec.protocol_config.activation_point.era_id(),
             name: chainspec.network_config.name.clone(),
@@ -232,6 +232,9 @@ pub(crate) trait ConsensusProtocol<I, C: Context>: Send {
     /// Returns whether the validator `vid` is known to be faulty.
     fn has_evidence(&self, vid: &C::ValidatorId) -> bool;
 
+    /// Returns the serialized evidence against the validator `vid`, if it is known to be faulty.
+    fn evidence(&self, vid: &C::ValidatorId) -> Option<Vec<u8>>;
+
     /// Marks the validator `vid` as faulty, based on evidence from a different instance.
     fn mark_faulty(&mut self, vid: &C::ValidatorId);
 
@@ -32,17 +32,20 @@ use tracing::{debug, error, info, trace, warn};
 use casper_types::{AsymmetricType, EraId, PublicKey, SecretKey, U512};
 
 use crate::{
-    components::consensus::{
-        cl_context::{ClContext, Keypair},
-        config::ProtocolConfig,
-        consensus_protocol::{
-            ConsensusProtocol, EraReport, FinalizedBlock as CpFinalizedBlock, ProposedBlock,
-            ProtocolOutcome, ProtocolOutcomes,
+    components::{
+        consensus::{
+            cl_context::{ClContext, Keypair},
+            config::ProtocolConfig,
+            consensus_protocol::{
+                ConsensusProtocol, EraReport, FinalizedBlock as CpFinalizedBlock, ProposedBlock,
+                ProtocolOutcome, ProtocolOutcomes,
+            },
+            metrics::ConsensusMetrics,
+            traits::NodeIdT,
+            ActionId, Config, ConsensusMessage, Event, NewBlockPayload, ReactorEventT,
+            ResolveValidity, TimerId,
         },
-        metrics::ConsensusMetrics,
-        traits::NodeIdT,
-        ActionId, Config, ConsensusMessage, Event, NewBlockPayload, ReactorEventT, ResolveValidity,
-        TimerId,
+        small_network::Offense,
     },
     crypto::hash::Digest,
     effect::{
@@ -945,7 +948,7 @@ where
                 era = %era_id.value(),
                 "invalid consensus value; disconnecting from the sender"
             );
-            effects.extend(self.disconnect(sender));
+            effects.extend(self.disconnect(sender, Offense::InvalidConsensusValue));
         }
         if self
             .era_supervisor
@@ -978,6 +981,25 @@ where
             .any(|eid| self.era(eid).consensus.has_evidence(&pub_key))
     }
 
+    /// Returns the serialized evidence against each of the given validators that is known in any
+    /// of the bonded eras up to `era_id`.
+    fn equivocation_evidence(
+        &self,
+        era_id: EraId,
+        pub_keys: &[PublicKey],
+    ) -> BTreeMap<PublicKey, Vec<u8>> {
+        pub_keys
+            .iter()
+            .filter_map(|pub_key| {
+                let evidence = self
+                    .era_supervisor
+                    .iter_past(era_id, self.era_supervisor.bonded_eras())
+                    .find_map(|eid| self.era(eid).consensus.evidence(pub_key))?;
+                Some((pub_key.clone(), evidence))
+            })
+            .collect()
+    }
+
     /// Returns the era with the specified ID. Panics if it does not exist.
     fn era(&self, era_id: EraId) -> &Era<I> {
         &self.era_supervisor.active_eras[&era_id]
@@ -1001,14 +1023,14 @@ where
                     %error,
                     "invalid incoming message to consensus instance; disconnecting from the sender"
                 );
-                self.disconnect(sender)
+                self.disconnect(sender, Offense::ProtocolViolation)
             }
             ProtocolOutcome::Disconnect(sender) => {
                 warn!(
                     %sender,
                     "disconnecting from the sender of invalid data"
                 );
-                self.disconnect(sender)
+                self.disconnect(sender, Offense::InvalidConsensusValue)
             }
             ProtocolOutcome::CreatedGossipMessage(payload) => {
                 let message = ConsensusMessage::Protocol { era_id, payload };
@@ -1076,28 +1098,43 @@ where
                 let era = self.era_supervisor.active_eras.get_mut(&era_id).unwrap();
                 era.add_accusations(&equivocators);
                 era.add_accusations(value.accusations());
+                let accusations = era.accusations();
+                let height = era.start_height + relative_height;
                 // If this is the era's last block, it contains rewards. Everyone who is accused in
-                // the block or seen as equivocating via the consensus protocol gets faulty.
+                // the block or seen as equivocating via the consensus protocol gets slashed, and is
+                // also reported as inactive so that they are evicted.
+                // Slashing stays disabled as long as the chainspec's slashing rate is zero, in
+                // which case equivocators are only evicted.
+                let slashing_enabled =
+                    *self.era_supervisor.protocol_config.slashing_rate.numer() != 0;
+                let equivocators = if slashing_enabled {
+                    accusations.clone()
+                } else {
+                    vec![]
+                };
                 let era_end = terminal_block_data.map(|tbd| EraReport {
                     rewards: tbd.rewards,
-                    // TODO: This is a temporary change to disable slashing; we just report all
-                    // equivocators as "inactive" instead. Change this back and put
-                    // era.accusations() into equivocators instead of inactive_validators.
-                    equivocators: vec![],
+                    equivocators,
                     inactive_validators: tbd
                         .inactive_validators
                         .into_iter()
-                        .chain(era.accusations())
+                        .chain(accusations.iter().cloned())
                         .collect(),
                 });
+                let equivocation_evidence = if era_end.is_some() && slashing_enabled {
+                    self.equivocation_evidence(era_id, &accusations)
+                } else {
+                    BTreeMap::new()
+                };
                 let finalized_block = FinalizedBlock::new(
                     Arc::try_unwrap(value).unwrap_or_else(|arc| (*arc).clone()),
                     era_end,
                     timestamp,
                     era_id,
-                    era.start_height + relative_height,
+                    height,
                     proposer,
-                );
+                )
+                .with_equivocation_evidence(equivocation_evidence);
                 info!(?finalized_block, "finalized block");
                 self.era_supervisor
                     .metrics
@@ -1114,7 +1151,7 @@ where
                 if finalized_block.era_report().is_some() {
                     // This was the era's last block. Schedule deactivating this era.
                     let delay = Timestamp::now().saturating_diff(timestamp).into();
-                    let faulty_num = era.consensus.validators_with_evidence().len();
+                    let faulty_num = self.era(era_id).consensus.validators_with_evidence().len();
                     let deactivate_era = move |_| Event::DeactivateEra {
                         era_id,
                         faulty_num,
@@ -1256,9 +1293,9 @@ where
         responder.respond(Some((public_key, round_length))).ignore()
     }
 
-    fn disconnect(&self, sender: I) -> Effects<Event<I>> {
+    fn disconnect(&self, sender: I, offense: Offense) -> Effects<Event<I>> {
         self.effect_builder
-            .announce_disconnect_from_peer(sender)
+            .announce_offense_committed(sender, offense)
             .ignore()
     }
 
@@ -901,6 +901,12 @@ where
         self.highway.has_evidence(vid)
     }
 
+    fn evidence(&self, vid: &C::ValidatorId) -> Option<Vec<u8>> {
+        let vidx = self.highway.validators().get_index(vid)?;
+        let evidence = self.highway.state().maybe_evidence(vidx)?;
+        Some(bincode::serialize(evidence).expect("should serialize evidence"))
+    }
+
     fn mark_faulty(&mut self, vid: &C::ValidatorId) {
         self.highway.mark_faulty(vid);
     }
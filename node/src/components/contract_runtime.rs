@@ -1,5 +1,6 @@
 //! Contract Runtime component.
 mod config;
+mod deploy_execution;
 mod operations;
 mod types;
 
@@ -10,23 +11,24 @@ use std::{
     time::Instant,
 };
 
-pub use config::Config;
+pub use config::{Config, DeployExecutionMode, GarbageCollectionConfig};
 use smallvec::SmallVec;
+use tokio::task;
 
-pub use types::{EraValidatorsRequest, ValidatorWeightsByEraIdRequest};
+pub use types::{EraValidatorsRequest, GasEstimate, ValidatorWeightsByEraIdRequest};
 
 use datasize::DataSize;
 use derive_more::From;
 use lmdb::DatabaseFlags;
-use prometheus::{self, Histogram, HistogramOpts, IntGauge, Registry};
+use prometheus::{self, Histogram, HistogramOpts, IntCounter, IntGauge, Registry};
 use thiserror::Error;
-use tracing::{debug, error, trace};
+use tracing::{debug, error, info, trace};
 
 use casper_execution_engine::{
     core::engine_state::{
-        self, genesis::GenesisResult, step::EvictItem, DeployItem, EngineConfig, EngineState,
-        ExecuteRequest, GetEraValidatorsError, GetEraValidatorsRequest, RewardItem, SlashItem,
-        StepRequest, StepResult,
+        self, genesis::GenesisResult, step::EvictItem, EngineConfig, EngineState,
+        GetEraValidatorsError, GetEraValidatorsRequest, RewardItem, SlashItem, StepRequest,
+        StepResult, WasmCacheStats,
     },
     shared::newtypes::{Blake2bHash, CorrelationId},
     storage::{
@@ -48,8 +50,8 @@ use crate::{
         EffectBuilder, EffectExt, Effects,
     },
     types::{
-        Block, BlockHash, BlockHeader, Chainspec, Deploy, DeployHash, DeployHeader, FinalizedBlock,
-        NodeId,
+        chainspec::CoreConfig, Block, BlockHash, BlockHeader, Chainspec, Deploy, DeployHash,
+        DeployHeader, FinalizedBlock, NodeId,
     },
     utils::WithDir,
     NodeRng, StorageConfig,
@@ -139,6 +141,8 @@ type BlockHeight = u64;
 pub struct ContractRuntime {
     initial_state: InitialState,
     engine_state: Arc<EngineState<LmdbGlobalState>>,
+    /// An engine state sharing the global state of `engine_state`, with execution tracing enabled.
+    tracing_engine_state: Arc<EngineState<LmdbGlobalState>>,
     metrics: Arc<ContractRuntimeMetrics>,
 
     protocol_version: ProtocolVersion,
@@ -152,6 +156,15 @@ pub struct ContractRuntime {
 
     /// Finalized blocks waiting for their pre-state hash to start executing.
     exec_queue: HashMap<BlockHeight, (FinalizedBlock, VecDeque<Deploy>)>,
+
+    /// Global state garbage collection configuration.
+    garbage_collection: GarbageCollectionConfig,
+    /// Post-state hashes of the most recently executed blocks, oldest first.
+    recent_state_roots: VecDeque<Digest>,
+    /// The number of blocks executed since the last garbage collection was started.
+    blocks_since_garbage_collection: u64,
+    /// How the deploys of a block are executed.
+    deploy_execution_mode: DeployExecutionMode,
 }
 
 impl Debug for ContractRuntime {
@@ -173,11 +186,21 @@ pub struct ContractRuntimeMetrics {
     get_era_validators: Histogram,
     get_era_validator_weights_by_era_id: Histogram,
     get_bids: Histogram,
+    get_withdraws: Histogram,
     missing_trie_keys: Histogram,
     put_trie: Histogram,
     read_trie: Histogram,
+    collect_garbage: Histogram,
     /// The current chain height.
     pub chain_height: IntGauge,
+    /// The number of deploys re-executed due to conflicts during parallel execution.
+    parallel_reexecuted_deploys: IntCounter,
+    /// The number of blocks for which parallel and sequential execution gave different results.
+    parallel_execution_mismatches: IntCounter,
+    /// The number of contract Wasm modules found in the Wasm cache.
+    wasm_cache_hits: IntCounter,
+    /// The number of contract Wasm modules not found in the Wasm cache.
+    wasm_cache_misses: IntCounter,
 }
 
 /// Value of upper bound of histogram.
@@ -215,12 +238,26 @@ const GET_ERA_VALIDATORS_WEIGHT_BY_ERA_ID_HELP: &str =
     "tracking run of engine_state.get_era_validator_weights_by_era_id in seconds.";
 const GET_BIDS_NAME: &str = "contract_runtime_get_bids";
 const GET_BIDS_HELP: &str = "tracking run of engine_state.get_bids in seconds.";
+const GET_WITHDRAWS_NAME: &str = "contract_runtime_get_withdraws";
+const GET_WITHDRAWS_HELP: &str = "tracking run of engine_state.get_withdraws in seconds.";
 const READ_TRIE_NAME: &str = "contract_runtime_read_trie";
 const READ_TRIE_HELP: &str = "tracking run of engine_state.read_trie in seconds.";
 const PUT_TRIE_NAME: &str = "contract_runtime_put_trie";
 const PUT_TRIE_HELP: &str = "tracking run of engine_state.put_trie in seconds.";
 const MISSING_TRIE_KEYS_NAME: &str = "contract_runtime_missing_trie_keys";
 const MISSING_TRIE_KEYS_HELP: &str = "tracking run of engine_state.missing_trie_keys in seconds.";
+const COLLECT_GARBAGE_NAME: &str = "contract_runtime_collect_garbage";
+const COLLECT_GARBAGE_HELP: &str = "tracking run of global state garbage collection in seconds.";
+const PARALLEL_REEXECUTED_DEPLOYS_NAME: &str = "contract_runtime_parallel_reexecuted_deploys";
+const PARALLEL_REEXECUTED_DEPLOYS_HELP: &str =
+    "number of deploys re-executed due to conflicts during parallel execution.";
+const PARALLEL_EXECUTION_MISMATCHES_NAME: &str = "contract_runtime_parallel_execution_mismatches";
+const PARALLEL_EXECUTION_MISMATCHES_HELP: &str =
+    "number of blocks for which parallel and sequential execution gave different results.";
+const WASM_CACHE_HITS_NAME: &str = "contract_runtime_wasm_cache_hits";
+const WASM_CACHE_HITS_HELP: &str = "number of contract Wasm modules found in the Wasm cache.";
+const WASM_CACHE_MISSES_NAME: &str = "contract_runtime_wasm_cache_misses";
+const WASM_CACHE_MISSES_HELP: &str = "number of contract Wasm modules not found in the Wasm cache.";
 
 /// Create prometheus Histogram and register.
 fn register_histogram_metric(
@@ -244,8 +281,26 @@ impl ContractRuntimeMetrics {
     fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
         let chain_height = IntGauge::new("chain_height", "current chain height")?;
         registry.register(Box::new(chain_height.clone()))?;
+        let parallel_reexecuted_deploys = IntCounter::new(
+            PARALLEL_REEXECUTED_DEPLOYS_NAME,
+            PARALLEL_REEXECUTED_DEPLOYS_HELP,
+        )?;
+        registry.register(Box::new(parallel_reexecuted_deploys.clone()))?;
+        let parallel_execution_mismatches = IntCounter::new(
+            PARALLEL_EXECUTION_MISMATCHES_NAME,
+            PARALLEL_EXECUTION_MISMATCHES_HELP,
+        )?;
+        registry.register(Box::new(parallel_execution_mismatches.clone()))?;
+        let wasm_cache_hits = IntCounter::new(WASM_CACHE_HITS_NAME, WASM_CACHE_HITS_HELP)?;
+        registry.register(Box::new(wasm_cache_hits.clone()))?;
+        let wasm_cache_misses = IntCounter::new(WASM_CACHE_MISSES_NAME, WASM_CACHE_MISSES_HELP)?;
+        registry.register(Box::new(wasm_cache_misses.clone()))?;
         Ok(ContractRuntimeMetrics {
             chain_height,
+            parallel_reexecuted_deploys,
+            parallel_execution_mismatches,
+            wasm_cache_hits,
+            wasm_cache_misses,
             run_execute: register_histogram_metric(registry, RUN_EXECUTE_NAME, RUN_EXECUTE_HELP)?,
             apply_effect: register_histogram_metric(
                 registry,
@@ -276,6 +331,11 @@ impl ContractRuntimeMetrics {
                 GET_ERA_VALIDATORS_WEIGHT_BY_ERA_ID_HELP,
             )?,
             get_bids: register_histogram_metric(registry, GET_BIDS_NAME, GET_BIDS_HELP)?,
+            get_withdraws: register_histogram_metric(
+                registry,
+                GET_WITHDRAWS_NAME,
+                GET_WITHDRAWS_HELP,
+            )?,
             read_trie: register_histogram_metric(registry, READ_TRIE_NAME, READ_TRIE_HELP)?,
             put_trie: register_histogram_metric(registry, PUT_TRIE_NAME, PUT_TRIE_HELP)?,
             missing_trie_keys: register_histogram_metric(
@@ -283,8 +343,19 @@ impl ContractRuntimeMetrics {
                 MISSING_TRIE_KEYS_NAME,
                 MISSING_TRIE_KEYS_HELP,
             )?,
+            collect_garbage: register_histogram_metric(
+                registry,
+                COLLECT_GARBAGE_NAME,
+                COLLECT_GARBAGE_HELP,
+            )?,
         })
     }
+
+    /// Records the hits and misses of the Wasm cache since they were last recorded.
+    fn record_wasm_cache_stats(&self, stats: WasmCacheStats) {
+        self.wasm_cache_hits.inc_by(stats.hits);
+        self.wasm_cache_misses.inc_by(stats.misses);
+    }
 }
 
 impl<REv: ReactorEventT> Component<REv> for ContractRuntime
@@ -379,6 +450,78 @@ where
                         }
                         .ignore()
                     }
+                    ContractRuntimeRequest::SpeculativeExecute {
+                        execute_request,
+                        responder,
+                    } => {
+                        trace!(?execute_request, "speculative execute");
+                        let engine_state = Arc::clone(&self.engine_state);
+                        let metrics = Arc::clone(&self.metrics);
+                        async move {
+                            let result = operations::speculative_execute(
+                                engine_state,
+                                metrics,
+                                execute_request,
+                            )
+                            .await;
+                            trace!(?result, "speculative execute result");
+                            responder.respond(result).await
+                        }
+                        .ignore()
+                    }
+                    ContractRuntimeRequest::TraceDeploy {
+                        block,
+                        pre_state_root_hash,
+                        preceding_deploys,
+                        deploy,
+                        responder,
+                    } => {
+                        trace!(block_hash = %block.hash(), deploy_hash = %deploy.id(), "trace deploy");
+                        let engine_state = Arc::clone(&self.engine_state);
+                        let tracing_engine_state = Arc::clone(&self.tracing_engine_state);
+                        let metrics = Arc::clone(&self.metrics);
+                        let block_params = deploy_execution::BlockParams {
+                            block_time: block.header().timestamp().millis(),
+                            protocol_version: block.protocol_version(),
+                            proposer: block.body().proposer().clone(),
+                        };
+                        async move {
+                            let result = deploy_execution::trace_deploy(
+                                engine_state,
+                                tracing_engine_state,
+                                metrics,
+                                &block_params,
+                                pre_state_root_hash,
+                                preceding_deploys,
+                                *deploy,
+                            )
+                            .await;
+                            trace!(?result, "trace deploy result");
+                            responder.respond(result).await
+                        }
+                        .ignore()
+                    }
+                    ContractRuntimeRequest::EstimateGas {
+                        execute_request,
+                        safety_margin_percent,
+                        responder,
+                    } => {
+                        trace!(?execute_request, "estimate gas");
+                        let engine_state = Arc::clone(&self.engine_state);
+                        let metrics = Arc::clone(&self.metrics);
+                        async move {
+                            let result = operations::estimate_gas(
+                                engine_state,
+                                metrics,
+                                execute_request,
+                                safety_margin_percent,
+                            )
+                            .await;
+                            trace!(?result, "estimate gas result");
+                            responder.respond(result).await
+                        }
+                        .ignore()
+                    }
                     ContractRuntimeRequest::IsBonded {
                         state_root_hash,
                         era_id,
@@ -548,6 +691,24 @@ where
                         }
                         .ignore()
                     }
+                    ContractRuntimeRequest::GetWithdraws {
+                        get_withdraws_request,
+                        responder,
+                    } => {
+                        trace!(?get_withdraws_request, "get withdraws request");
+                        let engine_state = Arc::clone(&self.engine_state);
+                        let metrics = Arc::clone(&self.metrics);
+                        async move {
+                            let correlation_id = CorrelationId::new();
+                            let start = Instant::now();
+                            let result =
+                                engine_state.get_withdraws(correlation_id, get_withdraws_request);
+                            metrics.get_withdraws.observe(start.elapsed().as_secs_f64());
+                            trace!(?result, "get withdraws result");
+                            responder.respond(result).await
+                        }
+                        .ignore()
+                    }
                     ContractRuntimeRequest::MissingTrieKeys {
                         trie_key,
                         responder,
@@ -656,33 +817,32 @@ impl ContractRuntime {
         initial_state_root_hash: Digest,
         initial_block_header: Option<&BlockHeader>,
         protocol_version: ProtocolVersion,
+        core_config: &CoreConfig,
         storage_config: WithDir<StorageConfig>,
         contract_runtime_config: &Config,
         registry: &Registry,
     ) -> Result<Self, ConfigError> {
         let initial_state = InitialState::new(initial_state_root_hash, initial_block_header);
-        let path = storage_config.with_dir(storage_config.value().path.clone());
-        let environment = Arc::new(LmdbEnvironment::new(
-            path.as_path(),
-            contract_runtime_config.max_global_state_size(),
-            contract_runtime_config.max_readers(),
-        )?);
-
-        let trie_store = Arc::new(LmdbTrieStore::new(
-            &environment,
-            None,
-            DatabaseFlags::empty(),
-        )?);
-
-        let protocol_data_store = Arc::new(LmdbProtocolDataStore::new(
-            &environment,
-            None,
-            DatabaseFlags::empty(),
-        )?);
-
-        let global_state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store)?;
-        let engine_config = EngineConfig::new(contract_runtime_config.max_query_depth());
+        let global_state = open_global_state(&storage_config, contract_runtime_config)?;
+        let engine_config = EngineConfig::new(
+            contract_runtime_config.max_query_depth(),
+            core_config.refund_ratio,
+            core_config.fee_handling,
+        )
+        .with_slashing_rate(core_config.slashing_rate)
+        .with_slash_handling(core_config.slash_handling)
+        .with_max_wasm_cache_size(contract_runtime_config.max_wasm_cache_size());
 
+        // Shares the global state of `engine_state`, but records execution traces.  Only used to
+        // re-execute deploys for debugging.
+        let tracing_engine_state = Arc::new(EngineState::new(
+            LmdbGlobalState::empty(
+                Arc::clone(&global_state.environment),
+                Arc::clone(&global_state.trie_store),
+                Arc::clone(&global_state.protocol_data_store),
+            )?,
+            engine_config.with_trace_execution(true),
+        ));
         let engine_state = Arc::new(EngineState::new(global_state, engine_config));
 
         let metrics = Arc::new(ContractRuntimeMetrics::new(registry)?);
@@ -692,7 +852,12 @@ impl ContractRuntime {
             parent_map: HashMap::new(),
             exec_queue: HashMap::new(),
             engine_state,
+            tracing_engine_state,
             metrics,
+            garbage_collection: contract_runtime_config.garbage_collection().clone(),
+            recent_state_roots: VecDeque::new(),
+            blocks_since_garbage_collection: 0,
+            deploy_execution_mode: contract_runtime_config.deploy_execution_mode(),
         })
     }
 
@@ -819,15 +984,17 @@ impl ContractRuntime {
         self.metrics
             .chain_height
             .set(state.finalized_block.height() as i64);
+        let state_root_hash = state.state_root_hash;
         let block = self.create_block(
             state.finalized_block,
-            state.state_root_hash,
+            state_root_hash,
             next_era_validator_weights,
         );
 
         let mut effects = effect_builder
             .announce_linear_chain_block(block, state.execution_results)
             .ignore();
+        effects.extend(self.maybe_collect_garbage(effect_builder, state_root_hash));
         // If the child is already finalized, start execution.
         if let Some((finalized_block, deploys)) = self.exec_queue.remove(&next_height) {
             effects.extend(self.handle_get_deploys_result(
@@ -839,6 +1006,76 @@ impl ContractRuntime {
         effects
     }
 
+    /// Records the post-state hash of an executed block, and starts a global state garbage
+    /// collection in the background if one is due.
+    ///
+    /// Besides the pinned state roots, the collection retains the post-state hashes of the
+    /// configured number of most recent blocks, of all blocks kept by the storage retention policy
+    /// and of all switch blocks. Post-state hashes of recently executed blocks are also tracked
+    /// here, as those blocks may not have been stored yet.
+    fn maybe_collect_garbage<REv: ReactorEventT>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        state_root_hash: Digest,
+    ) -> Effects<Event> {
+        if !self.garbage_collection.is_online_enabled() {
+            return Effects::new();
+        }
+
+        let keep_blocks = self.garbage_collection.keep_blocks;
+        self.recent_state_roots.push_back(state_root_hash);
+        while self.recent_state_roots.len() as u64 > keep_blocks {
+            let _ = self.recent_state_roots.pop_front();
+        }
+        self.blocks_since_garbage_collection += 1;
+        if self.blocks_since_garbage_collection < self.garbage_collection.interval_blocks {
+            return Effects::new();
+        }
+        self.blocks_since_garbage_collection = 0;
+
+        let recent_state_roots: Vec<Digest> = self
+            .recent_state_roots
+            .iter()
+            .chain(self.garbage_collection.pinned_state_roots.iter())
+            .copied()
+            .collect();
+        let engine_state = Arc::clone(&self.engine_state);
+        let metrics = Arc::clone(&self.metrics);
+        async move {
+            let retained_roots: Vec<Blake2bHash> = effect_builder
+                .get_retained_state_root_hashes_from_storage(keep_blocks)
+                .await
+                .into_iter()
+                .chain(recent_state_roots)
+                .map(Blake2bHash::from)
+                .collect();
+            // The collection may take a long time, so it must not occupy one of the reactor's
+            // worker threads.
+            let result = task::spawn_blocking(move || {
+                let start = Instant::now();
+                let result = engine_state
+                    .state()
+                    .collect_garbage(CorrelationId::new(), &retained_roots);
+                metrics
+                    .collect_garbage
+                    .observe(start.elapsed().as_secs_f64());
+                result
+            })
+            .await;
+            match result {
+                Ok(Ok(Some(result))) => info!(
+                    reachable = result.reachable,
+                    deleted = result.deleted,
+                    "collected global state garbage"
+                ),
+                Ok(Ok(None)) => debug!("global state garbage collection already in progress"),
+                Ok(Err(error)) => error!(%error, "global state garbage collection failed"),
+                Err(error) => error!(%error, "global state garbage collection task failed"),
+            }
+        }
+        .ignore()
+    }
+
     fn execute_all_deploys_or_finalize_block_or_step<REv: ReactorEventT>(
         &mut self,
         effect_builder: EffectBuilder<REv>,
@@ -870,7 +1107,12 @@ impl ContractRuntime {
         let slash_items = era_end
             .equivocators
             .iter()
-            .map(|vid| SlashItem::new(vid.clone()))
+            .map(
+                |vid| match state.finalized_block.equivocation_evidence().get(vid) {
+                    Some(evidence) => SlashItem::with_evidence(vid.clone(), evidence.clone()),
+                    None => SlashItem::new(vid.clone()),
+                },
+            )
             .collect();
         let evict_items = era_end
             .inactive_validators
@@ -899,55 +1141,52 @@ impl ContractRuntime {
     fn execute_all_deploys_in_block(&mut self, mut state: Box<RequestState>) -> Effects<Event> {
         let engine_state = Arc::clone(&self.engine_state);
         let metrics = Arc::clone(&self.metrics);
-        let protocol_version = self.protocol_version;
-        let block_time = state.finalized_block.timestamp().millis();
-        let proposer = state.finalized_block.proposer();
+        let deploy_execution_mode = self.deploy_execution_mode;
+        let block_params = deploy_execution::BlockParams {
+            block_time: state.finalized_block.timestamp().millis(),
+            protocol_version: self.protocol_version,
+            proposer: state.finalized_block.proposer(),
+        };
         async move {
-            for deploy in state.remaining_deploys.drain(..) {
-                let deploy_hash = *deploy.id();
-                let deploy_header = deploy.header().clone();
-                let deploy_item = DeployItem::from(deploy);
-
-                let execute_request = ExecuteRequest::new(
-                    state.state_root_hash.into(),
-                    block_time,
-                    vec![deploy_item],
-                    protocol_version,
-                    proposer.clone(),
-                );
-
-                // TODO: this is currently working coincidentally because we are passing only one
-                // deploy_item per exec. The execution results coming back from the ee lacks the
-                // mapping between deploy_hash and execution result, and this outer logic is
-                // enriching it with the deploy hash. If we were passing multiple deploys per exec
-                // the relation between the deploy and the execution results would be lost.
-                let result =
-                    operations::execute(engine_state.clone(), metrics.clone(), execute_request)
-                        .await;
-
-                trace!(%deploy_hash, ?result, "deploy execution result");
-                // As for now a given state is expected to exist.
-                let execution_results = result.unwrap();
-                match operations::commit_execution_effects(
-                    engine_state.clone(),
-                    metrics.clone(),
-                    state.state_root_hash,
-                    deploy_hash,
-                    execution_results,
-                )
-                .await
-                {
-                    Ok((state_hash, execution_result)) => {
-                        state
-                            .execution_results
-                            .insert(deploy_hash, (deploy_header, execution_result));
-                        state.state_root_hash = state_hash;
-                    }
-                    // When commit fails we panic as we'll not be able to execute the next
-                    // block.
-                    Err(_err) => panic!("unable to commit"),
+            let deploys: Vec<Deploy> = state.remaining_deploys.drain(..).collect();
+            let pre_state_root_hash = state.state_root_hash;
+            let wasm_cache = engine_state.wasm_cache().clone();
+            let wasm_cache_metrics = Arc::clone(&metrics);
+            let (state_root_hash, execution_results) = match deploy_execution_mode {
+                DeployExecutionMode::Sequential => {
+                    deploy_execution::execute_sequentially(
+                        engine_state,
+                        metrics,
+                        &block_params,
+                        pre_state_root_hash,
+                        deploys,
+                    )
+                    .await
                 }
-            }
+                DeployExecutionMode::Parallel => {
+                    deploy_execution::execute_in_parallel(
+                        engine_state,
+                        metrics,
+                        &block_params,
+                        pre_state_root_hash,
+                        deploys,
+                    )
+                    .await
+                }
+                DeployExecutionMode::Compare => {
+                    deploy_execution::execute_and_compare(
+                        engine_state,
+                        metrics,
+                        &block_params,
+                        pre_state_root_hash,
+                        deploys,
+                    )
+                    .await
+                }
+            };
+            wasm_cache_metrics.record_wasm_cache_stats(wasm_cache.take_stats());
+            state.execution_results.extend(execution_results);
+            state.state_root_hash = state_root_hash;
             state
         }
         .event(|state| Event::Result(Box::new(ContractRuntimeResult::ExecutedAndCommitted(state))))
@@ -1093,6 +1332,37 @@ impl ContractRuntime {
     }
 }
 
+/// Opens the global state kept in the storage directory.
+pub(crate) fn open_global_state(
+    storage_config: &WithDir<StorageConfig>,
+    contract_runtime_config: &Config,
+) -> Result<LmdbGlobalState, ConfigError> {
+    let path = storage_config.with_dir(storage_config.value().path.clone());
+    let environment = Arc::new(LmdbEnvironment::new(
+        path.as_path(),
+        contract_runtime_config.max_global_state_size(),
+        contract_runtime_config.max_readers(),
+    )?);
+
+    let trie_store = Arc::new(LmdbTrieStore::new(
+        &environment,
+        None,
+        DatabaseFlags::empty(),
+    )?);
+
+    let protocol_data_store = Arc::new(LmdbProtocolDataStore::new(
+        &environment,
+        None,
+        DatabaseFlags::empty(),
+    )?);
+
+    Ok(LmdbGlobalState::empty(
+        environment,
+        trie_store,
+        protocol_data_store,
+    )?)
+}
+
 /// Holds the state of an ongoing execute-commit cycle spawned from a given `Event::Request`.
 #[derive(Debug)]
 pub struct RequestState {
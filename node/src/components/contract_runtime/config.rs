@@ -1,14 +1,87 @@
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
-use casper_execution_engine::shared::utils;
+use casper_execution_engine::{core::engine_state::DEFAULT_MAX_WASM_CACHE_SIZE, shared::utils};
+
+use crate::crypto::hash::Digest;
 
 const DEFAULT_MAX_GLOBAL_STATE_SIZE: usize = 805_306_368_000; // 750 GiB
 const DEFAULT_MAX_READERS: u32 = 512;
 const DEFAULT_MAX_QUERY_DEPTH: u64 = 5;
+const DEFAULT_GARBAGE_COLLECTION_INTERVAL_BLOCKS: u64 = 1000;
+
+/// Global state garbage collection configuration.
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct GarbageCollectionConfig {
+    /// The number of most recent blocks whose global state is retained.
+    ///
+    /// The global state of all switch blocks and of all blocks kept by the storage retention
+    /// policy is always retained.
+    ///
+    /// Defaults to 0, which disables garbage collection.
+    #[serde(default)]
+    pub keep_blocks: u64,
+    /// The number of blocks executed between two garbage collections run in the background.
+    ///
+    /// Defaults to 1000.  0 disables garbage collection while the node is running, leaving only
+    /// the `prune-global-state` subcommand.
+    #[serde(default = "default_garbage_collection_interval_blocks")]
+    pub interval_blocks: u64,
+    /// State root hashes which are always retained in addition to those of the most recent blocks.
+    #[serde(default)]
+    pub pinned_state_roots: Vec<Digest>,
+}
+
+fn default_garbage_collection_interval_blocks() -> u64 {
+    DEFAULT_GARBAGE_COLLECTION_INTERVAL_BLOCKS
+}
+
+impl GarbageCollectionConfig {
+    /// Returns `true` if the global state should be garbage collected.
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.keep_blocks > 0
+    }
+
+    /// Returns `true` if the global state should be garbage collected in the background.
+    pub(crate) fn is_online_enabled(&self) -> bool {
+        self.is_enabled() && self.interval_blocks > 0
+    }
+}
+
+impl Default for GarbageCollectionConfig {
+    fn default() -> Self {
+        GarbageCollectionConfig {
+            keep_blocks: 0,
+            interval_blocks: DEFAULT_GARBAGE_COLLECTION_INTERVAL_BLOCKS,
+            pinned_state_roots: Vec::new(),
+        }
+    }
+}
+
+/// The strategy used to execute the deploys of a block.
+#[derive(Clone, Copy, DataSize, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeployExecutionMode {
+    /// Deploys are executed one at a time, each against the post-state of the previous one.
+    Sequential,
+    /// Deploys are executed concurrently against the block's pre-state.  Those which read global
+    /// state modified by a preceding deploy in the block are then re-executed in order.
+    Parallel,
+    /// Deploys are executed both sequentially and in parallel, and any difference in the results
+    /// is logged.  The results of the sequential execution are used.
+    Compare,
+}
+
+impl Default for DeployExecutionMode {
+    fn default() -> Self {
+        DeployExecutionMode::Sequential
+    }
+}
 
 /// Contract runtime configuration.
-#[derive(Clone, Copy, DataSize, Debug, Deserialize, Serialize)]
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
 #[serde(deny_unknown_fields)]
 pub struct Config {
@@ -26,6 +99,20 @@ pub struct Config {
     ///
     /// Defaults to 5.
     max_query_depth: Option<u64>,
+    /// Global state garbage collection.
+    ///
+    /// Disabled by default.
+    #[serde(default)]
+    garbage_collection: GarbageCollectionConfig,
+    /// The strategy used to execute the deploys of a block.
+    ///
+    /// Defaults to sequential execution.
+    #[serde(default)]
+    deploy_execution_mode: DeployExecutionMode,
+    /// The maximum number of deserialized contract Wasm modules kept in memory for reuse.
+    ///
+    /// Defaults to 256.  0 disables the cache.
+    max_wasm_cache_size: Option<usize>,
 }
 
 impl Config {
@@ -44,6 +131,19 @@ impl Config {
     pub(crate) fn max_query_depth(&self) -> u64 {
         self.max_query_depth.unwrap_or(DEFAULT_MAX_QUERY_DEPTH)
     }
+
+    pub(crate) fn garbage_collection(&self) -> &GarbageCollectionConfig {
+        &self.garbage_collection
+    }
+
+    pub(crate) fn deploy_execution_mode(&self) -> DeployExecutionMode {
+        self.deploy_execution_mode
+    }
+
+    pub(crate) fn max_wasm_cache_size(&self) -> usize {
+        self.max_wasm_cache_size
+            .unwrap_or(DEFAULT_MAX_WASM_CACHE_SIZE)
+    }
 }
 
 impl Default for Config {
@@ -52,6 +152,9 @@ impl Default for Config {
             max_global_state_size: Some(DEFAULT_MAX_GLOBAL_STATE_SIZE),
             max_readers: Some(DEFAULT_MAX_READERS),
             max_query_depth: Some(DEFAULT_MAX_QUERY_DEPTH),
+            garbage_collection: GarbageCollectionConfig::default(),
+            deploy_execution_mode: DeployExecutionMode::default(),
+            max_wasm_cache_size: Some(DEFAULT_MAX_WASM_CACHE_SIZE),
         }
     }
 }
@@ -0,0 +1,682 @@
+//! Execution of the deploys of a block, either sequentially or optimistically in parallel.
+
+use std::{
+    collections::{BTreeSet, HashMap, VecDeque},
+    sync::Arc,
+    time::Instant,
+};
+
+use futures::future;
+use itertools::Itertools;
+use tokio::task;
+use tracing::{debug, error, trace};
+
+use casper_execution_engine::{
+    core::engine_state::{
+        self, DeployItem, EngineState, ExecuteRequest, ExecutionResult as EngineExecutionResult,
+        ExecutionTrace, ReadSet,
+    },
+    shared::{newtypes::CorrelationId, stored_value::StoredValue, transform::Transform},
+    storage::global_state::{lmdb::LmdbGlobalState, StateProvider, StateReader},
+};
+use casper_types::{bytesrepr::ToBytes, ExecutionResult, Key, ProtocolVersion, PublicKey};
+
+use super::{operations, ContractRuntimeMetrics};
+use crate::{
+    crypto::hash::Digest,
+    types::{Deploy, DeployHash, DeployHeader},
+};
+
+/// The results of executing the deploys of a block, keyed by deploy hash.
+pub(super) type DeployExecutionResults = HashMap<DeployHash, (DeployHeader, ExecutionResult)>;
+
+/// The parameters of the block whose deploys are being executed.
+#[derive(Clone, Debug)]
+pub(super) struct BlockParams {
+    pub(super) block_time: u64,
+    pub(super) protocol_version: ProtocolVersion,
+    pub(super) proposer: PublicKey,
+}
+
+impl BlockParams {
+    fn execute_request(&self, state_root_hash: Digest, deploy: Deploy) -> ExecuteRequest {
+        // TODO: this is currently working coincidentally because we are passing only one
+        // deploy_item per exec. The execution results coming back from the ee lacks the mapping
+        // between deploy_hash and execution result, and the callers are enriching it with the
+        // deploy hash. If we were passing multiple deploys per exec the relation between the
+        // deploy and the execution results would be lost.
+        ExecuteRequest::new(
+            state_root_hash.into(),
+            self.block_time,
+            vec![DeployItem::from(deploy)],
+            self.protocol_version,
+            self.proposer.clone(),
+        )
+    }
+}
+
+/// Executes the deploys one after another, each against the post-state of the previous one.
+///
+/// Returns the post-state root hash of the last deploy along with the execution results.
+pub(super) async fn execute_sequentially(
+    engine_state: Arc<EngineState<LmdbGlobalState>>,
+    metrics: Arc<ContractRuntimeMetrics>,
+    block_params: &BlockParams,
+    pre_state_root_hash: Digest,
+    deploys: Vec<Deploy>,
+) -> (Digest, DeployExecutionResults) {
+    let mut state_root_hash = pre_state_root_hash;
+    let mut execution_results = HashMap::new();
+    for deploy in deploys {
+        let deploy_hash = *deploy.id();
+        let deploy_header = deploy.header().clone();
+        let execute_request = block_params.execute_request(state_root_hash, deploy);
+
+        let result =
+            operations::execute(engine_state.clone(), metrics.clone(), execute_request).await;
+
+        trace!(%deploy_hash, ?result, "deploy execution result");
+        // As for now a given state is expected to exist.
+        let ee_execution_results = result.unwrap();
+        let (post_state_root_hash, execution_result) = commit(
+            engine_state.clone(),
+            metrics.clone(),
+            state_root_hash,
+            deploy_hash,
+            ee_execution_results,
+        )
+        .await;
+        execution_results.insert(deploy_hash, (deploy_header, execution_result));
+        state_root_hash = post_state_root_hash;
+    }
+    (state_root_hash, execution_results)
+}
+
+/// Executes the deploys optimistically in parallel.
+///
+/// All deploys are first executed concurrently against the pre-state of the block, recording the
+/// global state reads of each. The results are then committed in block order. A deploy's result
+/// is only kept if none of the keys it read or wrote has changed value due to the deploys
+/// committed before it, and none of the key prefixes it listed matches a key those deploys
+/// modified: it would then have executed identically against their post-state. Unsigned
+/// additions commute, so keys which both the deploy and the deploys before it only added to are
+/// exempt, e.g. the proposer's purse receiving the fees of every deploy. Any other deploy is
+/// re-executed against that post-state, so the results are identical to those of
+/// `execute_sequentially`.
+pub(super) async fn execute_in_parallel(
+    engine_state: Arc<EngineState<LmdbGlobalState>>,
+    metrics: Arc<ContractRuntimeMetrics>,
+    block_params: &BlockParams,
+    pre_state_root_hash: Digest,
+    deploys: Vec<Deploy>,
+) -> (Digest, DeployExecutionResults) {
+    let speculative_executions = deploys.iter().cloned().map(|deploy| {
+        let engine_state = Arc::clone(&engine_state);
+        let metrics = Arc::clone(&metrics);
+        let execute_request = block_params.execute_request(pre_state_root_hash, deploy);
+        task::spawn_blocking(move || {
+            execute_recording_reads(&engine_state, &metrics, execute_request)
+        })
+    });
+    let speculative_results = future::join_all(speculative_executions).await;
+
+    let mut state_root_hash = pre_state_root_hash;
+    let mut execution_results = HashMap::new();
+    // The keys modified by the deploys committed so far, and those among them modified other than
+    // by unsigned additions.
+    let mut modified_keys = BTreeSet::new();
+    let mut overwritten_keys = BTreeSet::new();
+    let mut reexecuted_count = 0;
+    for (deploy, speculative_result) in deploys.into_iter().zip(speculative_results) {
+        let deploy_hash = *deploy.id();
+        let deploy_header = deploy.header().clone();
+        // As for now a given state is expected to exist.
+        let (speculative_result, read_set) = speculative_result
+            .expect("deploy execution task should not panic")
+            .unwrap();
+
+        let ee_execution_result = if state_root_hash == pre_state_root_hash
+            || is_unaffected(
+                &engine_state,
+                &speculative_result,
+                &read_set,
+                &modified_keys,
+                &overwritten_keys,
+                pre_state_root_hash,
+                state_root_hash,
+            ) {
+            speculative_result
+        } else {
+            debug!(%deploy_hash, "re-executing conflicting deploy");
+            reexecuted_count += 1;
+            let execute_request = block_params.execute_request(state_root_hash, deploy);
+            operations::execute(engine_state.clone(), metrics.clone(), execute_request)
+                .await
+                .unwrap()
+                .into_iter()
+                .exactly_one()
+                .expect("should only be one exec result")
+        };
+
+        trace!(%deploy_hash, ?ee_execution_result, "deploy execution result");
+        for (key, transform) in ee_execution_result.effect().transforms.iter() {
+            if *transform == Transform::Identity {
+                continue;
+            }
+            modified_keys.insert(*key);
+            if !is_unsigned_addition(transform) {
+                overwritten_keys.insert(*key);
+            }
+        }
+        let (post_state_root_hash, execution_result) = commit(
+            engine_state.clone(),
+            metrics.clone(),
+            state_root_hash,
+            deploy_hash,
+            VecDeque::from(vec![ee_execution_result]),
+        )
+        .await;
+        execution_results.insert(deploy_hash, (deploy_header, execution_result));
+        state_root_hash = post_state_root_hash;
+    }
+
+    metrics.parallel_reexecuted_deploys.inc_by(reexecuted_count);
+    debug!(
+        deploy_count = execution_results.len(),
+        reexecuted_count, "executed deploys in parallel"
+    );
+    (state_root_hash, execution_results)
+}
+
+/// Executes the deploys both in parallel and sequentially, reporting any difference between the
+/// two.
+///
+/// Returns the results of the sequential execution.
+pub(super) async fn execute_and_compare(
+    engine_state: Arc<EngineState<LmdbGlobalState>>,
+    metrics: Arc<ContractRuntimeMetrics>,
+    block_params: &BlockParams,
+    pre_state_root_hash: Digest,
+    deploys: Vec<Deploy>,
+) -> (Digest, DeployExecutionResults) {
+    let (parallel_state_root_hash, parallel_execution_results) = execute_in_parallel(
+        Arc::clone(&engine_state),
+        Arc::clone(&metrics),
+        block_params,
+        pre_state_root_hash,
+        deploys.clone(),
+    )
+    .await;
+    let (state_root_hash, execution_results) = execute_sequentially(
+        engine_state,
+        Arc::clone(&metrics),
+        block_params,
+        pre_state_root_hash,
+        deploys,
+    )
+    .await;
+
+    if parallel_state_root_hash != state_root_hash {
+        error!(
+            %pre_state_root_hash,
+            %parallel_state_root_hash,
+            sequential_state_root_hash = %state_root_hash,
+            "parallel and sequential execution of deploys resulted in different state root hashes"
+        );
+        metrics.parallel_execution_mismatches.inc();
+    } else if parallel_execution_results != execution_results {
+        error!(
+            %pre_state_root_hash,
+            "parallel and sequential execution of deploys resulted in different execution results"
+        );
+        metrics.parallel_execution_mismatches.inc();
+    }
+    (state_root_hash, execution_results)
+}
+
+/// Re-executes a deploy of a block with execution tracing enabled, without committing its effects.
+///
+/// The deploys preceding it in the block are first re-executed on top of the block's pre-state and
+/// committed, which only recreates tries already created by the block's original execution.
+pub(super) async fn trace_deploy(
+    engine_state: Arc<EngineState<LmdbGlobalState>>,
+    tracing_engine_state: Arc<EngineState<LmdbGlobalState>>,
+    metrics: Arc<ContractRuntimeMetrics>,
+    block_params: &BlockParams,
+    pre_state_root_hash: Digest,
+    preceding_deploys: Vec<Deploy>,
+    deploy: Deploy,
+) -> Result<(ExecutionResult, ExecutionTrace), engine_state::Error> {
+    let mut state_root_hash = pre_state_root_hash;
+    for preceding_deploy in preceding_deploys {
+        let deploy_hash = *preceding_deploy.id();
+        let execute_request = block_params.execute_request(state_root_hash, preceding_deploy);
+        let ee_execution_results =
+            operations::execute(engine_state.clone(), metrics.clone(), execute_request).await?;
+        // Failures are logged by `commit_execution_effects`.
+        let (post_state_root_hash, _) = operations::commit_execution_effects(
+            engine_state.clone(),
+            metrics.clone(),
+            state_root_hash,
+            deploy_hash,
+            ee_execution_results,
+        )
+        .await
+        .map_err(|()| engine_state::Error::RootNotFound(state_root_hash.into()))?;
+        state_root_hash = post_state_root_hash;
+    }
+
+    let execute_request = block_params.execute_request(state_root_hash, deploy);
+    let correlation_id = CorrelationId::new();
+    let start = Instant::now();
+    let result = tracing_engine_state.run_execute_with_traces(correlation_id, execute_request);
+    metrics.run_execute.observe(start.elapsed().as_secs_f64());
+    let (ee_execution_result, execution_trace) = result?
+        .into_iter()
+        .exactly_one()
+        .expect("should only be one exec result");
+    Ok((ExecutionResult::from(&ee_execution_result), execution_trace))
+}
+
+/// Executes a single deploy, returning its result along with the global state it read.
+fn execute_recording_reads(
+    engine_state: &EngineState<LmdbGlobalState>,
+    metrics: &ContractRuntimeMetrics,
+    execute_request: ExecuteRequest,
+) -> Result<(EngineExecutionResult, ReadSet), engine_state::Error> {
+    let correlation_id = CorrelationId::new();
+    let start = Instant::now();
+    let result = engine_state.run_execute_recording_reads(correlation_id, execute_request);
+    metrics.run_execute.observe(start.elapsed().as_secs_f64());
+    Ok(result?
+        .into_iter()
+        .exactly_one()
+        .expect("should only be one exec result"))
+}
+
+/// Commits the result of a deploy's execution, returning the post-state root hash.
+async fn commit(
+    engine_state: Arc<EngineState<LmdbGlobalState>>,
+    metrics: Arc<ContractRuntimeMetrics>,
+    state_root_hash: Digest,
+    deploy_hash: DeployHash,
+    ee_execution_results: engine_state::ExecutionResults,
+) -> (Digest, ExecutionResult) {
+    match operations::commit_execution_effects(
+        engine_state,
+        metrics,
+        state_root_hash,
+        deploy_hash,
+        ee_execution_results,
+    )
+    .await
+    {
+        Ok(committed) => committed,
+        // When commit fails we panic as we'll not be able to execute the next block.
+        Err(_err) => panic!("unable to commit"),
+    }
+}
+
+/// Returns `true` if the transform adds an unsigned integer, commuting with other such additions.
+///
+/// Signed additions are excluded, as they can overflow depending on their order.
+fn is_unsigned_addition(transform: &Transform) -> bool {
+    matches!(
+        transform,
+        Transform::AddUInt64(_)
+            | Transform::AddUInt128(_)
+            | Transform::AddUInt256(_)
+            | Transform::AddUInt512(_)
+    )
+}
+
+/// Returns `true` if a deploy executed against `pre_state_root_hash` would have executed
+/// identically against `state_root_hash`, given the keys modified in between, and those among
+/// them modified other than by unsigned additions.
+fn is_unaffected(
+    engine_state: &EngineState<LmdbGlobalState>,
+    speculative_result: &EngineExecutionResult,
+    read_set: &ReadSet,
+    modified_keys: &BTreeSet<Key>,
+    overwritten_keys: &BTreeSet<Key>,
+    pre_state_root_hash: Digest,
+    state_root_hash: Digest,
+) -> bool {
+    let listed_modified_key = read_set.key_prefixes().iter().any(|prefix| {
+        modified_keys.iter().any(|key| match key.to_bytes() {
+            Ok(bytes) => bytes.starts_with(prefix),
+            Err(_) => true,
+        })
+    });
+    if listed_modified_key {
+        return false;
+    }
+
+    // Values read must be unchanged.  Keys only checked for existence, e.g. before adding to them,
+    // still exist if only added to in between.
+    let keys_checked_for_existence = read_set.keys_checked_for_existence();
+    let read_keys = read_set
+        .keys()
+        .iter()
+        .filter(|key| modified_keys.contains(key));
+    let existence_checked_keys = keys_checked_for_existence
+        .iter()
+        .filter(|key| overwritten_keys.contains(key));
+    // Written keys are checked as well, since writing a key's current value is reduced to a read.
+    // Unsigned additions commute with those made in between.
+    let written_keys = speculative_result
+        .effect()
+        .transforms
+        .iter()
+        .filter(|(key, transform)| {
+            modified_keys.contains(key)
+                && !(is_unsigned_addition(transform) && !overwritten_keys.contains(key))
+        })
+        .map(|(key, _)| key);
+    let keys_to_check: BTreeSet<&Key> = read_keys
+        .chain(existence_checked_keys)
+        .chain(written_keys)
+        .collect();
+    if keys_to_check.is_empty() {
+        return true;
+    }
+
+    match (
+        read_values(engine_state, pre_state_root_hash, &keys_to_check),
+        read_values(engine_state, state_root_hash, &keys_to_check),
+    ) {
+        (Some(pre_state_values), Some(values)) => pre_state_values == values,
+        // If in doubt, treat the deploy as conflicting.
+        _ => false,
+    }
+}
+
+/// Reads the values of the given keys under the given state root hash.
+fn read_values(
+    engine_state: &EngineState<LmdbGlobalState>,
+    state_root_hash: Digest,
+    keys: &BTreeSet<&Key>,
+) -> Option<Vec<Option<StoredValue>>> {
+    let correlation_id = CorrelationId::new();
+    let reader = match engine_state.state().checkout(state_root_hash.into()) {
+        Ok(Some(reader)) => reader,
+        Ok(None) => {
+            error!(%state_root_hash, "missing state root while validating deploy execution");
+            return None;
+        }
+        Err(error) => {
+            error!(%state_root_hash, ?error, "failed to check out global state");
+            return None;
+        }
+    };
+    keys.iter()
+        .map(|key| {
+            reader.read(correlation_id, key).map_err(|error| {
+                error!(%state_root_hash, ?key, ?error, "failed to read global state");
+            })
+        })
+        .collect::<Result<_, _>>()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use lmdb::{Cursor, DatabaseFlags, Transaction};
+    use prometheus::Registry;
+    use tempfile::TempDir;
+
+    use casper_execution_engine::{
+        core::engine_state::{
+            executable_deploy_item::ExecutableDeployItem, EngineConfig, ExecConfig, GenesisResult,
+        },
+        shared::{motes::Motes, newtypes::Blake2bHash},
+        storage::{
+            protocol_data_store::lmdb::LmdbProtocolDataStore, store::Store,
+            transaction_source::lmdb::LmdbEnvironment, trie::Trie, trie_store::lmdb::LmdbTrieStore,
+        },
+    };
+    use casper_types::{
+        bytesrepr::Bytes, runtime_args, system::auction::DelegationRate, RuntimeArgs, SecretKey,
+        U512,
+    };
+
+    use super::*;
+    use crate::types::{
+        chainspec::{AccountConfig, AccountsConfig, ValidatorConfig},
+        Chainspec, TimeDiff, Timestamp,
+    };
+
+    const MAX_GLOBAL_STATE_SIZE: usize = 1024 * 1024 * 1024;
+    const MAX_READERS: u32 = 512;
+    const ACCOUNT_BALANCE: u64 = 1_000_000_000_000_000;
+    const TRANSFER_AMOUNT: u64 = 2_500_000_000;
+
+    struct Fixture {
+        _tempdir: TempDir,
+        engine_state: Arc<EngineState<LmdbGlobalState>>,
+        metrics: Arc<ContractRuntimeMetrics>,
+        chainspec: Chainspec,
+        block_params: BlockParams,
+        pre_state_root_hash: Digest,
+    }
+
+    impl Fixture {
+        /// Commits genesis for a network funding an account for each of the given keys.  The
+        /// first key is the only validator and proposes the block.
+        fn new(secret_keys: &[SecretKey]) -> Self {
+            let tempdir = tempfile::tempdir().expect("should create tempdir");
+            let environment = Arc::new(
+                LmdbEnvironment::new(tempdir.path(), MAX_GLOBAL_STATE_SIZE, MAX_READERS)
+                    .expect("should create lmdb environment"),
+            );
+            let trie_store = Arc::new(
+                LmdbTrieStore::new(&environment, None, DatabaseFlags::empty())
+                    .expect("should create trie store"),
+            );
+            let protocol_data_store = Arc::new(
+                LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty())
+                    .expect("should create protocol data store"),
+            );
+            let global_state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store)
+                .expect("should create global state");
+            let engine_state = Arc::new(EngineState::new(global_state, EngineConfig::default()));
+            let metrics = Arc::new(
+                ContractRuntimeMetrics::new(&Registry::new()).expect("should create metrics"),
+            );
+
+            let mut chainspec = Chainspec::from_resources("local");
+            let accounts = secret_keys
+                .iter()
+                .enumerate()
+                .map(|(index, secret_key)| {
+                    let validator_config = if index == 0 {
+                        Some(ValidatorConfig::new(
+                            Motes::new(U512::from(ACCOUNT_BALANCE)),
+                            DelegationRate::default(),
+                        ))
+                    } else {
+                        None
+                    };
+                    AccountConfig::new(
+                        PublicKey::from(secret_key),
+                        Motes::new(U512::from(ACCOUNT_BALANCE)),
+                        validator_config,
+                    )
+                })
+                .collect();
+            chainspec.network_config.accounts_config = AccountsConfig::new(accounts, vec![]);
+
+            let protocol_version = chainspec.protocol_config.version;
+            let genesis_result = engine_state
+                .commit_genesis(
+                    CorrelationId::new(),
+                    chainspec.hash().into(),
+                    protocol_version,
+                    &ExecConfig::from(&chainspec),
+                )
+                .expect("should commit genesis");
+            let pre_state_root_hash = match genesis_result {
+                GenesisResult::Success {
+                    post_state_hash, ..
+                } => post_state_hash.into(),
+                other => panic!("genesis should succeed: {}", other),
+            };
+
+            let block_params = BlockParams {
+                block_time: Timestamp::now().millis(),
+                protocol_version,
+                proposer: PublicKey::from(&secret_keys[0]),
+            };
+
+            Fixture {
+                _tempdir: tempdir,
+                engine_state,
+                metrics,
+                chainspec,
+                block_params,
+                pre_state_root_hash,
+            }
+        }
+
+        fn transfer(&self, source: &SecretKey, target: &SecretKey, id: u64) -> Deploy {
+            let payment = ExecutableDeployItem::ModuleBytes {
+                module_bytes: Bytes::new(),
+                args: RuntimeArgs::new(),
+            };
+            let session = ExecutableDeployItem::Transfer {
+                args: runtime_args! {
+                    "amount" => U512::from(TRANSFER_AMOUNT),
+                    "target" => PublicKey::from(target).to_account_hash(),
+                    "id" => Some(id),
+                },
+            };
+            Deploy::new(
+                Timestamp::now(),
+                TimeDiff::from(60_000),
+                1,
+                vec![],
+                self.chainspec.network_config.name.clone(),
+                payment,
+                session,
+                source,
+            )
+        }
+
+        /// Returns the number of tries stored in global state.
+        fn trie_count(&self) -> usize {
+            let global_state = self.engine_state.state();
+            let db = <LmdbTrieStore as Store<Blake2bHash, Trie<Key, StoredValue>>>::handle(
+                &global_state.trie_store,
+            );
+            let txn = global_state
+                .environment
+                .env()
+                .begin_ro_txn()
+                .expect("should begin read transaction");
+            let mut cursor = txn.open_ro_cursor(db).expect("should open cursor");
+            cursor.iter_start().count()
+        }
+    }
+
+    #[tokio::test]
+    async fn parallel_execution_should_match_sequential_execution() {
+        let secret_keys: Vec<SecretKey> = (1..=5)
+            .map(|byte| SecretKey::ed25519_from_bytes([byte; SecretKey::ED25519_LENGTH]).unwrap())
+            .collect();
+        let fixture = Fixture::new(&secret_keys);
+
+        let deploys = vec![
+            fixture.transfer(&secret_keys[1], &secret_keys[2], 1),
+            // Shares nothing with the first transfer but the proposer's purse.
+            fixture.transfer(&secret_keys[3], &secret_keys[4], 2),
+            // Spends from the same purse as the first transfer.
+            fixture.transfer(&secret_keys[1], &secret_keys[2], 3),
+            // Spends from the purse credited by the first transfer.
+            fixture.transfer(&secret_keys[2], &secret_keys[3], 4),
+        ];
+
+        let (sequential_state_root_hash, sequential_results) = execute_sequentially(
+            Arc::clone(&fixture.engine_state),
+            Arc::clone(&fixture.metrics),
+            &fixture.block_params,
+            fixture.pre_state_root_hash,
+            deploys.clone(),
+        )
+        .await;
+        assert_eq!(sequential_results.len(), deploys.len());
+        assert!(sequential_results
+            .values()
+            .all(|(_, result)| matches!(result, ExecutionResult::Success { .. })));
+
+        let (parallel_state_root_hash, parallel_results) = execute_in_parallel(
+            Arc::clone(&fixture.engine_state),
+            Arc::clone(&fixture.metrics),
+            &fixture.block_params,
+            fixture.pre_state_root_hash,
+            deploys.clone(),
+        )
+        .await;
+        assert_eq!(parallel_state_root_hash, sequential_state_root_hash);
+        assert_eq!(parallel_results, sequential_results);
+        // Only the two transfers spending from purses touched by the first one are re-executed.
+        assert_eq!(fixture.metrics.parallel_reexecuted_deploys.get(), 2);
+
+        let (compared_state_root_hash, compared_results) = execute_and_compare(
+            Arc::clone(&fixture.engine_state),
+            Arc::clone(&fixture.metrics),
+            &fixture.block_params,
+            fixture.pre_state_root_hash,
+            deploys,
+        )
+        .await;
+        assert_eq!(compared_state_root_hash, sequential_state_root_hash);
+        assert_eq!(compared_results, sequential_results);
+        assert_eq!(fixture.metrics.parallel_execution_mismatches.get(), 0);
+    }
+
+    #[tokio::test]
+    async fn speculative_execution_should_not_commit() {
+        let secret_keys: Vec<SecretKey> = (1..=3)
+            .map(|byte| SecretKey::ed25519_from_bytes([byte; SecretKey::ED25519_LENGTH]).unwrap())
+            .collect();
+        let fixture = Fixture::new(&secret_keys);
+
+        // Execute a block, then speculatively execute a deploy against its post-state.
+        let (post_state_root_hash, _) = execute_sequentially(
+            Arc::clone(&fixture.engine_state),
+            Arc::clone(&fixture.metrics),
+            &fixture.block_params,
+            fixture.pre_state_root_hash,
+            vec![fixture.transfer(&secret_keys[1], &secret_keys[2], 1)],
+        )
+        .await;
+        let deploy = fixture.transfer(&secret_keys[2], &secret_keys[1], 2);
+        let trie_count = fixture.trie_count();
+        let speculative_result = operations::speculative_execute(
+            Arc::clone(&fixture.engine_state),
+            Arc::clone(&fixture.metrics),
+            fixture
+                .block_params
+                .execute_request(post_state_root_hash, deploy.clone()),
+        )
+        .await
+        .expect("should execute speculatively");
+        assert!(matches!(
+            speculative_result,
+            ExecutionResult::Success { .. }
+        ));
+        assert_eq!(fixture.trie_count(), trie_count);
+
+        // Executing the deploy for real yields the same result, and does write to global state.
+        let (_, execution_results) = execute_sequentially(
+            Arc::clone(&fixture.engine_state),
+            Arc::clone(&fixture.metrics),
+            &fixture.block_params,
+            post_state_root_hash,
+            vec![deploy.clone()],
+        )
+        .await;
+        assert_eq!(execution_results[deploy.id()].1, speculative_result);
+        assert!(fixture.trie_count() > trie_count);
+    }
+}
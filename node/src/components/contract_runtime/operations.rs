@@ -1,15 +1,25 @@
 use std::{collections::VecDeque, sync::Arc, time::Instant};
 
-use super::ContractRuntimeMetrics;
+use super::{ContractRuntimeMetrics, GasEstimate};
 use crate::{crypto::hash::Digest, types::DeployHash};
 use casper_execution_engine::{
-    core::engine_state::{
-        self, EngineState, ExecutionResult as EngineExecutionResult, ExecutionResults,
+    core::{
+        engine_state::{
+            self, DeployItem, EngineState, ExecutableDeployItem,
+            ExecutionResult as EngineExecutionResult, ExecutionResults, QueryRequest, QueryResult,
+        },
+        execution,
+    },
+    shared::{
+        additive_map::AdditiveMap, newtypes::CorrelationId, stored_value::StoredValue,
+        transform::Transform,
     },
-    shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
     storage::global_state::{lmdb::LmdbGlobalState, CommitResult},
 };
-use casper_types::{ExecutionResult, Key};
+use casper_types::{
+    bytesrepr::Bytes, runtime_args, system::standard_payment, ExecutionResult, Key, RuntimeArgs,
+    U512,
+};
 use engine_state::ExecuteRequest;
 use itertools::Itertools;
 use tracing::{debug, error, trace};
@@ -99,3 +109,216 @@ pub(super) async fn execute(
     trace!(?result, "execute result");
     result
 }
+
+/// Executes a single deploy without committing its effects.
+pub(super) async fn speculative_execute(
+    engine_state: Arc<EngineState<LmdbGlobalState>>,
+    metrics: Arc<ContractRuntimeMetrics>,
+    execute_request: ExecuteRequest,
+) -> Result<ExecutionResult, engine_state::Error> {
+    let execution_results = execute(engine_state, metrics, execute_request).await?;
+    let ee_execution_result =
+        execution_results
+            .into_iter()
+            .exactly_one()
+            .map_err(|execution_results| {
+                error!(
+                    count = execution_results.count(),
+                    "expected exactly one speculative execution result"
+                );
+                engine_state::Error::Deploy
+            })?;
+    Ok(ExecutionResult::from(&ee_execution_result))
+}
+
+/// Maximum number of executions when searching for the lowest sufficient payment amount.
+const MAX_GAS_ESTIMATION_ATTEMPTS: usize = 32;
+
+/// Estimates the gas required to execute the single deploy of `execute_request`.
+///
+/// The deploy's payment is replaced by the standard payment.  The deploy is first executed with
+/// the entire balance of the account's main purse as payment, which measures the gas used exactly
+/// unless the execution fails.  As the failure may be caused by the payment depleting the main
+/// purse, a binary search then looks for the lowest payment amount which does not run out of gas.
+///
+/// The suggested payment amount covers the gas used plus `safety_margin_percent`.
+pub(super) async fn estimate_gas(
+    engine_state: Arc<EngineState<LmdbGlobalState>>,
+    metrics: Arc<ContractRuntimeMetrics>,
+    mut execute_request: ExecuteRequest,
+    safety_margin_percent: u64,
+) -> Result<GasEstimate, engine_state::Error> {
+    let deploy_item = execute_request
+        .take_deploys()
+        .into_iter()
+        .exactly_one()
+        .map_err(|deploy_items| {
+            error!(
+                count = deploy_items.count(),
+                "expected exactly one deploy item to estimate gas for"
+            );
+            engine_state::Error::Deploy
+        })?;
+    let gas_price = U512::from(deploy_item.gas_price);
+
+    let max_payment_amount = main_purse_balance(
+        &engine_state,
+        &execute_request,
+        Key::Account(deploy_item.address),
+    )?
+    .unwrap_or_default();
+    let mut result = execute_with_standard_payment(
+        &engine_state,
+        &metrics,
+        &execute_request,
+        &deploy_item,
+        max_payment_amount,
+    )
+    .await?;
+
+    if result.is_failure() && !is_out_of_gas(&result) {
+        // Anything less than the gas used before failing is assumed to be insufficient.
+        let mut lower = result.cost().value() * gas_price;
+        let mut upper = max_payment_amount;
+        for _ in 0..MAX_GAS_ESTIMATION_ATTEMPTS {
+            if upper <= lower + gas_price {
+                break;
+            }
+            let payment_amount = lower + (upper - lower) / 2;
+            let attempt = execute_with_standard_payment(
+                &engine_state,
+                &metrics,
+                &execute_request,
+                &deploy_item,
+                payment_amount,
+            )
+            .await?;
+            if is_out_of_gas(&attempt) {
+                lower = payment_amount;
+            } else {
+                upper = payment_amount;
+                let succeeded = attempt.is_success();
+                result = attempt;
+                if succeeded {
+                    break;
+                }
+            }
+        }
+    }
+
+    let gas_used = result.cost().value();
+    let payment_amount = suggested_payment_amount(gas_used, gas_price, safety_margin_percent);
+    debug!(%gas_used, %payment_amount, "gas estimate");
+    Ok(GasEstimate::new(
+        gas_used,
+        payment_amount,
+        ExecutionResult::from(&result),
+    ))
+}
+
+/// Returns the payment amount in motes covering `gas_used` plus `safety_margin_percent`, rounded
+/// up to whole units of gas.
+fn suggested_payment_amount(gas_used: U512, gas_price: U512, safety_margin_percent: u64) -> U512 {
+    let margin = (gas_used * U512::from(safety_margin_percent) + U512::from(99)) / U512::from(100);
+    (gas_used + margin) * gas_price
+}
+
+/// Executes the deploy, paying `payment_amount` via the standard payment.
+async fn execute_with_standard_payment(
+    engine_state: &Arc<EngineState<LmdbGlobalState>>,
+    metrics: &Arc<ContractRuntimeMetrics>,
+    execute_request: &ExecuteRequest,
+    deploy_item: &DeployItem,
+    payment_amount: U512,
+) -> Result<EngineExecutionResult, engine_state::Error> {
+    let mut deploy_item = deploy_item.clone();
+    deploy_item.payment = ExecutableDeployItem::ModuleBytes {
+        module_bytes: Bytes::new(),
+        args: runtime_args! {
+            standard_payment::ARG_AMOUNT => payment_amount,
+        },
+    };
+    let execute_request = ExecuteRequest::new(
+        execute_request.parent_state_hash,
+        execute_request.block_time,
+        vec![deploy_item],
+        execute_request.protocol_version,
+        execute_request.proposer.clone(),
+    );
+    let execution_results = execute(
+        Arc::clone(engine_state),
+        Arc::clone(metrics),
+        execute_request,
+    )
+    .await?;
+    Ok(execution_results
+        .into_iter()
+        .exactly_one()
+        .expect("should only be one exec result"))
+}
+
+/// Returns the balance of the main purse of the given account, or `None` if it doesn't exist.
+fn main_purse_balance(
+    engine_state: &EngineState<LmdbGlobalState>,
+    execute_request: &ExecuteRequest,
+    account_key: Key,
+) -> Result<Option<U512>, engine_state::Error> {
+    let state_hash = execute_request.parent_state_hash;
+    let query_request = QueryRequest::new(state_hash, account_key, vec![]);
+    let main_purse = match engine_state.run_query(CorrelationId::new(), query_request)? {
+        QueryResult::Success { value, .. } => match *value {
+            StoredValue::Account(account) => account.main_purse(),
+            _ => return Ok(None),
+        },
+        _ => return Ok(None),
+    };
+    let balance_result =
+        engine_state.get_purse_balance(CorrelationId::new(), state_hash, main_purse)?;
+    Ok(balance_result.motes().cloned())
+}
+
+/// Returns `true` if the execution failed because the payment didn't cover the gas required.
+fn is_out_of_gas(result: &EngineExecutionResult) -> bool {
+    matches!(
+        result,
+        EngineExecutionResult::Failure {
+            error: engine_state::Error::Exec(execution::Error::GasLimit)
+                | engine_state::Error::GasConversionOverflow,
+            ..
+        }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggested_payment_amount_should_include_safety_margin() {
+        let gas_used = U512::from(1_000);
+        assert_eq!(
+            suggested_payment_amount(gas_used, U512::one(), 0),
+            U512::from(1_000)
+        );
+        assert_eq!(
+            suggested_payment_amount(gas_used, U512::one(), 10),
+            U512::from(1_100)
+        );
+        assert_eq!(
+            suggested_payment_amount(gas_used, U512::from(3), 10),
+            U512::from(3_300)
+        );
+    }
+
+    #[test]
+    fn suggested_payment_amount_should_round_margin_up() {
+        assert_eq!(
+            suggested_payment_amount(U512::from(101), U512::one(), 10),
+            U512::from(112)
+        );
+        assert_eq!(
+            suggested_payment_amount(U512::one(), U512::from(2), 1),
+            U512::from(4)
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use casper_execution_engine::{
     core::engine_state::GetEraValidatorsRequest, shared::newtypes::Blake2bHash,
 };
-use casper_types::{EraId, ProtocolVersion};
+use casper_types::{EraId, ExecutionResult, ProtocolVersion, U512};
 
 /// Request for validator weights for a specific era.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -75,3 +75,38 @@ impl From<EraValidatorsRequest> for GetEraValidatorsRequest {
         GetEraValidatorsRequest::new(input.state_hash, input.protocol_version)
     }
 }
+
+/// Estimate of the gas required to execute a deploy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasEstimate {
+    gas_used: U512,
+    payment_amount: U512,
+    execution_result: ExecutionResult,
+}
+
+impl GasEstimate {
+    /// Constructs a new GasEstimate.
+    pub fn new(gas_used: U512, payment_amount: U512, execution_result: ExecutionResult) -> Self {
+        GasEstimate {
+            gas_used,
+            payment_amount,
+            execution_result,
+        }
+    }
+
+    /// Get the gas used by the deploy, including the standard payment.
+    pub fn gas_used(&self) -> U512 {
+        self.gas_used
+    }
+
+    /// Get the suggested standard payment amount in motes, including the safety margin.
+    pub fn payment_amount(&self) -> U512 {
+        self.payment_amount
+    }
+
+    /// Returns the result of executing the deploy with a sufficient payment, consuming the
+    /// estimate.
+    pub fn take_execution_result(self) -> ExecutionResult {
+        self.execution_result
+    }
+}
@@ -34,6 +34,14 @@ pub enum Error {
     /// A deploy was sent from account with insufficient balance.
     #[error("insufficient balance")]
     InsufficientBalance,
+    /// A deploy was sent with a gas price below the minimum accepted by this node.
+    #[error("gas price {got} is below the minimum of {min_gas_price}")]
+    GasPriceTooLow {
+        /// The minimum gas price accepted by this node.
+        min_gas_price: u64,
+        /// The gas price of the deploy.
+        got: u64,
+    },
 }
 
 /// A helper trait constraining `DeployAcceptor` compatible reactor events.
@@ -65,14 +73,16 @@ pub struct DeployAcceptor {
     chain_name: String,
     deploy_config: DeployConfig,
     verify_accounts: bool,
+    min_gas_price: u64,
 }
 
 impl DeployAcceptor {
-    pub(crate) fn new(config: Config, chainspec: &Chainspec) -> Self {
+    pub(crate) fn new(config: Config, chainspec: &Chainspec, min_gas_price: u64) -> Self {
         DeployAcceptor {
             chain_name: chainspec.network_config.name.clone(),
             deploy_config: chainspec.deploy_config,
             verify_accounts: config.verify_accounts(),
+            min_gas_price,
         }
     }
 
@@ -104,6 +114,29 @@ impl DeployAcceptor {
             return effects;
         }
 
+        // Deploys from peers may have been accepted by nodes with a lower minimum gas price, so the
+        // minimum is only enforced on deploys received from clients.
+        let gas_price = deploy.header().gas_price();
+        if source.from_client() && gas_price < self.min_gas_price {
+            debug!(
+                deploy_hash = %deploy.id(),
+                %gas_price,
+                min_gas_price = %self.min_gas_price,
+                "rejecting deploy with gas price below minimum"
+            );
+            if let Some(responder) = maybe_responder {
+                effects.extend(
+                    responder
+                        .respond(Err(Error::GasPriceTooLow {
+                            min_gas_price: self.min_gas_price,
+                            got: gas_price,
+                        }))
+                        .ignore(),
+                );
+            }
+            return effects;
+        }
+
         let account_key = deploy.header().account().to_account_hash().into();
 
         // Verify account if deploy received from client and node is configured to do so.
@@ -38,7 +38,7 @@ use tokio::sync::{
 };
 use tracing::{info, warn};
 
-use casper_types::ProtocolVersion;
+use casper_types::{ExecutionResult, ProtocolVersion};
 
 use super::Component;
 use crate::{
@@ -185,15 +185,31 @@ where
                 deploy_header,
                 block_hash,
                 execution_result,
-            } => self.broadcast(SseData::DeployProcessed {
-                deploy_hash: Box::new(deploy_hash),
-                account: Box::new(deploy_header.account().clone()),
-                timestamp: deploy_header.timestamp(),
-                ttl: deploy_header.ttl(),
-                dependencies: deploy_header.dependencies().clone(),
-                block_hash: Box::new(block_hash),
-                execution_result,
-            }),
+            } => {
+                // Events emitted by contracts are additionally streamed individually, following
+                // the deploy which emitted them.
+                let contract_events = match &*execution_result {
+                    ExecutionResult::Success { events, .. } => events.clone(),
+                    ExecutionResult::Failure { .. } => Vec::new(),
+                };
+                let mut effects = self.broadcast(SseData::DeployProcessed {
+                    deploy_hash: Box::new(deploy_hash),
+                    account: Box::new(deploy_header.account().clone()),
+                    timestamp: deploy_header.timestamp(),
+                    ttl: deploy_header.ttl(),
+                    dependencies: deploy_header.dependencies().clone(),
+                    block_hash: Box::new(block_hash),
+                    execution_result,
+                });
+                for contract_event in contract_events {
+                    effects.extend(self.broadcast(SseData::ContractEvent {
+                        deploy_hash: Box::new(deploy_hash),
+                        block_hash: Box::new(block_hash),
+                        contract_event: Box::new(contract_event),
+                    }));
+                }
+                effects
+            }
             Event::Fault {
                 era_id,
                 public_key,
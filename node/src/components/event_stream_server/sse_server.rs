@@ -34,7 +34,9 @@ use warp::{
     Filter, Reply,
 };
 
-use casper_types::{EraId, ExecutionEffect, ExecutionResult, ProtocolVersion, PublicKey};
+use casper_types::{
+    ContractEvent, EraId, ExecutionEffect, ExecutionResult, ProtocolVersion, PublicKey,
+};
 
 use super::DeployGetter;
 use crate::types::{
@@ -52,6 +54,8 @@ pub const SSE_API_MAIN_PATH: &str = "main";
 pub const SSE_API_DEPLOYS_PATH: &str = "deploys";
 /// The URL path part to subscribe to only `FinalitySignature` events.
 pub const SSE_API_SIGNATURES_PATH: &str = "sigs";
+/// The URL path part to subscribe to only `ContractEvent` events.
+pub const SSE_API_CONTRACT_EVENTS_PATH: &str = "contract";
 /// The URL query string field name.
 pub const QUERY_FIELD: &str = "start_from";
 
@@ -66,6 +70,8 @@ const MAIN_FILTER: [EventFilter; 4] = [
 const DEPLOYS_FILTER: [EventFilter; 1] = [EventFilter::DeployAccepted];
 /// The filter associated with `/events/sigs` path.
 const SIGNATURES_FILTER: [EventFilter; 1] = [EventFilter::FinalitySignature];
+/// The filter associated with `/events/contract` path.
+const CONTRACT_EVENTS_FILTER: [EventFilter; 1] = [EventFilter::ContractEvent];
 
 /// The max time to wait for getting a deploy before trying a second and final time.
 const GET_DEPLOY_TIMEOUT: Duration = Duration::from_secs(1);
@@ -114,6 +120,13 @@ pub enum SseData {
         #[data_size(skip)]
         execution_effect: ExecutionEffect,
     },
+    /// The given event has been emitted by a contract while executing the given deploy.
+    ContractEvent {
+        deploy_hash: Box<DeployHash>,
+        block_hash: Box<BlockHash>,
+        #[data_size(skip)]
+        contract_event: Box<ContractEvent>,
+    },
 }
 
 impl SseData {
@@ -126,6 +139,7 @@ impl SseData {
             SseData::Fault { .. } => filter.contains(&EventFilter::Fault),
             SseData::FinalitySignature(_) => filter.contains(&EventFilter::FinalitySignature),
             SseData::Step { .. } => filter.contains(&EventFilter::Step),
+            SseData::ContractEvent { .. } => filter.contains(&EventFilter::ContractEvent),
         }
     }
 }
@@ -203,6 +217,15 @@ impl SseData {
             execution_effect,
         }
     }
+
+    /// Returns a random `SseData::ContractEvent`.
+    pub(super) fn random_contract_event(rng: &mut TestRng) -> Self {
+        SseData::ContractEvent {
+            deploy_hash: Box::new(DeployHash::random(rng)),
+            block_hash: Box::new(BlockHash::random(rng)),
+            contract_event: Box::new(rng.gen()),
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -260,6 +283,7 @@ pub(super) enum EventFilter {
     Fault,
     FinalitySignature,
     Step,
+    ContractEvent,
 }
 
 /// Filters the `event`, mapping it to a warp event, or `None` if it should be filtered out.
@@ -301,7 +325,8 @@ async fn filter_map_server_sent_event(
         | &SseData::DeployProcessed { .. }
         | &SseData::Fault { .. }
         | &SseData::Step { .. }
-        | &SseData::FinalitySignature(_) => Some(Ok(WarpServerSentEvent::default()
+        | &SseData::FinalitySignature(_)
+        | &SseData::ContractEvent { .. } => Some(Ok(WarpServerSentEvent::default()
             .json_data(&event.data)
             .unwrap_or_else(|error| {
                 warn!(%error, ?event, "failed to jsonify sse event");
@@ -342,6 +367,7 @@ pub(super) fn get_filter(path_param: &str) -> Option<&'static [EventFilter]> {
         SSE_API_MAIN_PATH => Some(&MAIN_FILTER[..]),
         SSE_API_DEPLOYS_PATH => Some(&DEPLOYS_FILTER[..]),
         SSE_API_SIGNATURES_PATH => Some(&SIGNATURES_FILTER[..]),
+        SSE_API_CONTRACT_EVENTS_PATH => Some(&CONTRACT_EVENTS_FILTER[..]),
         _ => None,
     }
 }
@@ -371,11 +397,13 @@ fn parse_query(query: HashMap<String, String>) -> Result<Option<Id>, Response> {
 /// Creates a 404 response with a useful error message in the body.
 fn create_404() -> Response {
     let mut response = Response::new(Body::from(format!(
-        "invalid path: expected '/{root}/{main}', '/{root}/{deploys}' or '/{root}/{sigs}'\n",
+        "invalid path: expected '/{root}/{main}', '/{root}/{deploys}', '/{root}/{sigs}' or \
+        '/{root}/{contract}'\n",
         root = SSE_API_ROOT_PATH,
         main = SSE_API_MAIN_PATH,
         deploys = SSE_API_DEPLOYS_PATH,
-        sigs = SSE_API_SIGNATURES_PATH
+        sigs = SSE_API_SIGNATURES_PATH,
+        contract = SSE_API_CONTRACT_EVENTS_PATH
     )));
     *response.status_mut() = StatusCode::NOT_FOUND;
     response
@@ -641,8 +669,13 @@ mod tests {
             id: Some(rng.gen()),
             data: SseData::random_step(&mut rng),
         };
+        let contract_event = ServerSentEvent {
+            id: Some(rng.gen()),
+            data: SseData::random_contract_event(&mut rng),
+        };
 
-        // `EventFilter::Main` should only filter out `DeployAccepted`s and `FinalitySignature`s.
+        // `EventFilter::Main` should only filter out `DeployAccepted`s, `FinalitySignature`s and
+        // `ContractEvent`s.
         should_not_filter_out(&api_version, &MAIN_FILTER[..], getter.clone()).await;
         should_not_filter_out(&block_added, &MAIN_FILTER[..], getter.clone()).await;
         should_not_filter_out(&deploy_processed, &MAIN_FILTER[..], getter.clone()).await;
@@ -651,6 +684,7 @@ mod tests {
 
         should_filter_out(&deploy_accepted, &MAIN_FILTER[..], getter.clone()).await;
         should_filter_out(&finality_signature, &MAIN_FILTER[..], getter.clone()).await;
+        should_filter_out(&contract_event, &MAIN_FILTER[..], getter.clone()).await;
 
         // `EventFilter::DeployAccepted` should filter out everything except `ApiVersion`s and
         // `DeployAccepted`s.
@@ -662,6 +696,7 @@ mod tests {
         should_filter_out(&fault, &DEPLOYS_FILTER[..], getter.clone()).await;
         should_filter_out(&finality_signature, &DEPLOYS_FILTER[..], getter.clone()).await;
         should_filter_out(&step, &DEPLOYS_FILTER[..], getter.clone()).await;
+        should_filter_out(&contract_event, &DEPLOYS_FILTER[..], getter.clone()).await;
 
         // `EventFilter::Signatures` should filter out everything except `ApiVersion`s and
         // `FinalitySignature`s.
@@ -672,7 +707,35 @@ mod tests {
         should_filter_out(&deploy_accepted, &SIGNATURES_FILTER[..], getter.clone()).await;
         should_filter_out(&deploy_processed, &SIGNATURES_FILTER[..], getter.clone()).await;
         should_filter_out(&fault, &SIGNATURES_FILTER[..], getter.clone()).await;
-        should_filter_out(&step, &SIGNATURES_FILTER[..], getter).await;
+        should_filter_out(&step, &SIGNATURES_FILTER[..], getter.clone()).await;
+        should_filter_out(&contract_event, &SIGNATURES_FILTER[..], getter.clone()).await;
+
+        // `EventFilter::ContractEvent` should filter out everything except `ApiVersion`s and
+        // `ContractEvent`s.
+        should_not_filter_out(&api_version, &CONTRACT_EVENTS_FILTER[..], getter.clone()).await;
+        should_not_filter_out(&contract_event, &CONTRACT_EVENTS_FILTER[..], getter.clone()).await;
+
+        should_filter_out(&block_added, &CONTRACT_EVENTS_FILTER[..], getter.clone()).await;
+        should_filter_out(
+            &deploy_accepted,
+            &CONTRACT_EVENTS_FILTER[..],
+            getter.clone(),
+        )
+        .await;
+        should_filter_out(
+            &deploy_processed,
+            &CONTRACT_EVENTS_FILTER[..],
+            getter.clone(),
+        )
+        .await;
+        should_filter_out(&fault, &CONTRACT_EVENTS_FILTER[..], getter.clone()).await;
+        should_filter_out(
+            &finality_signature,
+            &CONTRACT_EVENTS_FILTER[..],
+            getter.clone(),
+        )
+        .await;
+        should_filter_out(&step, &CONTRACT_EVENTS_FILTER[..], getter).await;
     }
 
     /// This test checks that events with incorrect IDs (i.e. no types have an ID except for
@@ -714,11 +777,16 @@ mod tests {
             id: None,
             data: SseData::random_step(&mut rng),
         };
+        let malformed_contract_event = ServerSentEvent {
+            id: None,
+            data: SseData::random_contract_event(&mut rng),
+        };
 
         for filter in &[
             &MAIN_FILTER[..],
             &DEPLOYS_FILTER[..],
             &SIGNATURES_FILTER[..],
+            &CONTRACT_EVENTS_FILTER[..],
         ] {
             should_filter_out(&malformed_api_version, filter, getter.clone()).await;
             should_filter_out(&malformed_block_added, filter, getter.clone()).await;
@@ -727,6 +795,7 @@ mod tests {
             should_filter_out(&malformed_fault, filter, getter.clone()).await;
             should_filter_out(&malformed_finality_signature, filter, getter.clone()).await;
             should_filter_out(&malformed_step, filter, getter.clone()).await;
+            should_filter_out(&malformed_contract_event, filter, getter.clone()).await;
         }
     }
 
@@ -752,6 +821,7 @@ mod tests {
                             event
                         }
                         SSE_API_SIGNATURES_PATH => SseData::random_finality_signature(rng),
+                        SSE_API_CONTRACT_EVENTS_PATH => SseData::random_contract_event(rng),
                         _ => unreachable!(),
                     };
                     ServerSentEvent { id: Some(id), data }
@@ -909,4 +979,11 @@ mod tests {
     async fn should_filter_duplicate_signature_events() {
         should_filter_duplicate_events(SSE_API_SIGNATURES_PATH).await
     }
+
+    /// This test checks that contract events from the initial stream which are duplicated in the
+    /// ongoing stream are filtered out.
+    #[tokio::test]
+    async fn should_filter_duplicate_contract_events() {
+        should_filter_duplicate_events(SSE_API_CONTRACT_EVENTS_PATH).await
+    }
 }
@@ -17,8 +17,9 @@ use casper_execution_engine::{
 use casper_types::Key;
 
 use crate::{
-    components::{fetcher::event::FetchResponder, Component},
+    components::{fetcher::event::FetchResponder, small_network::Offense, Component},
     effect::{
+        announcements::BlocklistAnnouncement,
         requests::{ContractRuntimeRequest, LinearChainRequest, NetworkRequest, StorageRequest},
         EffectBuilder, EffectExt, Effects,
     },
@@ -40,6 +41,7 @@ pub trait ReactorEventT<T>:
     + From<ContractRuntimeRequest>
     // Won't be needed when we implement "get block by height" feature in storage.
     + From<LinearChainRequest<NodeId>>
+    + From<BlocklistAnnouncement<NodeId>>
     + Send
     + 'static
 where
@@ -57,6 +59,7 @@ where
         + From<StorageRequest>
         + From<ContractRuntimeRequest>
         + From<LinearChainRequest<NodeId>>
+        + From<BlocklistAnnouncement<NodeId>>
         + Send
         + 'static,
 {
@@ -360,7 +363,20 @@ where
             Event::TimeoutPeer { id, peer } => {
                 info!(%id, %peer, "request timed out");
                 self.metrics.timeouts.inc();
-                self.signal(id, None, peer)
+                // Only penalize the peer if it has not answered the request in the meantime.
+                let unanswered = self
+                    .responders()
+                    .get(&id)
+                    .map_or(false, |responders| responders.contains_key(&peer));
+                let mut effects = self.signal(id, None, peer);
+                if unanswered {
+                    effects.extend(
+                        effect_builder
+                            .announce_offense_committed(peer, Offense::FetchTimeout)
+                            .ignore(),
+                    );
+                }
+                effects
             }
         }
     }
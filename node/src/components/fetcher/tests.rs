@@ -15,7 +15,7 @@ use super::*;
 use crate::{
     components::{deploy_acceptor, in_memory_network::NetworkController, storage},
     effect::{
-        announcements::{DeployAcceptorAnnouncement, NetworkAnnouncement},
+        announcements::{BlocklistAnnouncement, DeployAcceptorAnnouncement, NetworkAnnouncement},
         Responder,
     },
     protocol::Message,
@@ -107,6 +107,8 @@ reactor!(Reactor {
         // from a client.
         RpcServerAnnouncement -> [deploy_acceptor];
         ChainspecLoaderAnnouncement -> [!];
+        // Peers are not penalized in this test.
+        BlocklistAnnouncement<NodeId> -> [#];
     }
 });
 
@@ -20,6 +20,7 @@ use casper_types::ProtocolVersion;
 use super::*;
 use crate::{
     components::{
+        block_proposer,
         contract_runtime::{self, ContractRuntime},
         deploy_acceptor::{self, DeployAcceptor},
         in_memory_network::{self, InMemoryNetwork, NetworkController},
@@ -40,7 +41,7 @@ use crate::{
         network::{Network, NetworkedReactor},
         ConditionCheckReactor, TestRng,
     },
-    types::{Chainspec, Deploy, NodeId, Tag},
+    types::{Chainspec, Deploy, NodeId, Tag, TimeDiff},
     utils::{Loadable, WithDir},
     NodeRng,
 };
@@ -186,15 +187,18 @@ impl reactor::Reactor for Reactor {
             &storage_withdir,
             None,
             ProtocolVersion::from_parts(1, 0, 0),
+            TimeDiff::default(),
             false,
         )
         .unwrap();
 
         let contract_runtime_config = contract_runtime::Config::default();
+        let chainspec = Chainspec::from_resources("local");
         let contract_runtime = ContractRuntime::new(
             Digest::random(rng),
             None,
             ProtocolVersion::from_parts(1, 0, 0),
+            &chainspec.core_config,
             storage_withdir,
             &contract_runtime_config,
             registry,
@@ -203,7 +207,8 @@ impl reactor::Reactor for Reactor {
 
         let deploy_acceptor = DeployAcceptor::new(
             deploy_acceptor::Config::new(false),
-            &Chainspec::from_resources("local"),
+            &chainspec,
+            block_proposer::Config::default().min_gas_price,
         );
         let deploy_gossiper = Gossiper::new_for_partial_items(
             "deploy_gossiper",
@@ -12,7 +12,7 @@ mod tests;
 mod tests_bulk_gossip;
 
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     env,
     fmt::{self, Debug, Display, Formatter},
     marker::PhantomData,
@@ -1018,6 +1018,23 @@ impl<REv: ReactorEventT<P>, P: PayloadT> Component<REv> for Network<REv, P> {
                         .collect();
                     responder.respond(peers).ignore()
                 }
+                // Peers are not banned by the libp2p networking component.
+                NetworkInfoRequest::GetBlocklist { responder } => {
+                    responder.respond(BTreeMap::new()).ignore()
+                }
+                NetworkInfoRequest::BanPeer {
+                    peer, responder, ..
+                } => {
+                    warn!(%peer, "banning peers is not supported by libp2p networking");
+                    responder.respond(None).ignore()
+                }
+                NetworkInfoRequest::UnbanPeer { responder, .. } => {
+                    responder.respond(false).ignore()
+                }
+                // No capabilities are negotiated by the libp2p networking component.
+                NetworkInfoRequest::GetPeerCapabilities { responder, .. } => {
+                    responder.respond(None).ignore()
+                }
             },
         }
     }
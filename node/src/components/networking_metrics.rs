@@ -50,6 +50,25 @@ pub(super) struct NetworkingMetrics {
     /// Volume in bytes of outgoing messages with other payload.
     pub(super) out_bytes_other: IntCounter,
 
+    /// Volume in bytes of outgoing messages that are protocol overhead, before compression.
+    pub(super) out_bytes_uncompressed_protocol: IntCounter,
+    /// Volume in bytes of outgoing messages with consensus payload, before compression.
+    pub(super) out_bytes_uncompressed_consensus: IntCounter,
+    /// Volume in bytes of outgoing messages with deploy gossiper payload, before compression.
+    pub(super) out_bytes_uncompressed_deploy_gossip: IntCounter,
+    /// Volume in bytes of outgoing messages with address gossiper payload, before compression.
+    pub(super) out_bytes_uncompressed_address_gossip: IntCounter,
+    /// Volume in bytes of outgoing messages with deploy request/response payload, before
+    /// compression.
+    pub(super) out_bytes_uncompressed_deploy_transfer: IntCounter,
+    /// Volume in bytes of outgoing messages with block request/response payload, before
+    /// compression.
+    pub(super) out_bytes_uncompressed_block_transfer: IntCounter,
+    /// Volume in bytes of outgoing messages with other payload, before compression.
+    pub(super) out_bytes_uncompressed_other: IntCounter,
+    /// Number of messages not written to traffic captures because the recorder fell behind.
+    pub(super) traffic_capture_dropped_records: IntCounter,
+
     // Potentially temporary metrics, not supported by all networking components:
     /// Number of do-nothing futures that have not finished executing for read requests.
     pub(super) read_futures_in_flight: prometheus::Gauge,
@@ -139,6 +158,39 @@ impl NetworkingMetrics {
             "volume in bytes of outgoing messages with other payload",
         )?;
 
+        let out_bytes_uncompressed_protocol = IntCounter::new(
+            "net_out_bytes_uncompressed_protocol",
+            "volume in bytes of outgoing messages that are protocol overhead, before compression",
+        )?;
+        let out_bytes_uncompressed_consensus = IntCounter::new(
+            "net_out_bytes_uncompressed_consensus",
+            "volume in bytes of outgoing messages with consensus payload, before compression",
+        )?;
+        let out_bytes_uncompressed_deploy_gossip = IntCounter::new(
+            "net_out_bytes_uncompressed_deploy_gossip",
+            "volume in bytes of outgoing messages with deploy gossiper payload, before compression",
+        )?;
+        let out_bytes_uncompressed_address_gossip = IntCounter::new(
+            "net_out_bytes_uncompressed_address_gossip",
+            "volume in bytes of outgoing messages with address gossiper payload, before compression",
+        )?;
+        let out_bytes_uncompressed_deploy_transfer = IntCounter::new(
+            "net_out_bytes_uncompressed_deploy_transfer",
+            "volume in bytes of outgoing messages with deploy request/response payload, before compression",
+        )?;
+        let out_bytes_uncompressed_block_transfer = IntCounter::new(
+            "net_out_bytes_uncompressed_block_transfer",
+            "volume in bytes of outgoing messages with block request/response payload, before compression",
+        )?;
+        let out_bytes_uncompressed_other = IntCounter::new(
+            "net_out_bytes_uncompressed_other",
+            "volume in bytes of outgoing messages with other payload, before compression",
+        )?;
+        let traffic_capture_dropped_records = IntCounter::new(
+            "net_traffic_capture_dropped_records",
+            "number of messages not written to traffic captures because the recorder fell behind",
+        )?;
+
         let read_futures_in_flight = prometheus::Gauge::new(
             "owm_read_futures_in_flight",
             "number of do-nothing futures in flight created by `Codec::read_response`",
@@ -178,6 +230,15 @@ impl NetworkingMetrics {
         registry.register(Box::new(out_bytes_block_transfer.clone()))?;
         registry.register(Box::new(out_bytes_other.clone()))?;
 
+        registry.register(Box::new(out_bytes_uncompressed_protocol.clone()))?;
+        registry.register(Box::new(out_bytes_uncompressed_consensus.clone()))?;
+        registry.register(Box::new(out_bytes_uncompressed_deploy_gossip.clone()))?;
+        registry.register(Box::new(out_bytes_uncompressed_address_gossip.clone()))?;
+        registry.register(Box::new(out_bytes_uncompressed_deploy_transfer.clone()))?;
+        registry.register(Box::new(out_bytes_uncompressed_block_transfer.clone()))?;
+        registry.register(Box::new(out_bytes_uncompressed_other.clone()))?;
+        registry.register(Box::new(traffic_capture_dropped_records.clone()))?;
+
         registry.register(Box::new(read_futures_in_flight.clone()))?;
         registry.register(Box::new(read_futures_total.clone()))?;
         registry.register(Box::new(write_futures_in_flight.clone()))?;
@@ -203,6 +264,14 @@ impl NetworkingMetrics {
             out_bytes_deploy_transfer,
             out_bytes_block_transfer,
             out_bytes_other,
+            out_bytes_uncompressed_protocol,
+            out_bytes_uncompressed_consensus,
+            out_bytes_uncompressed_deploy_gossip,
+            out_bytes_uncompressed_address_gossip,
+            out_bytes_uncompressed_deploy_transfer,
+            out_bytes_uncompressed_block_transfer,
+            out_bytes_uncompressed_other,
+            traffic_capture_dropped_records,
             read_futures_in_flight,
             read_futures_total,
             write_futures_in_flight,
@@ -211,36 +280,62 @@ impl NetworkingMetrics {
         })
     }
 
-    /// Records an outgoing payload.
-    pub(crate) fn record_payload_out(this: &mut Weak<Self>, kind: MessageKind, size: u64) {
+    /// Records an outgoing payload, given its size on the wire and its size before compression.
+    pub(crate) fn record_payload_out(
+        this: &mut Weak<Self>,
+        kind: MessageKind,
+        size: u64,
+        uncompressed_size: u64,
+    ) {
         if let Some(metrics) = this.upgrade() {
             match kind {
                 MessageKind::Protocol => {
                     metrics.out_bytes_protocol.inc_by(size);
+                    metrics
+                        .out_bytes_uncompressed_protocol
+                        .inc_by(uncompressed_size);
                     metrics.out_count_protocol.inc();
                 }
                 MessageKind::Consensus => {
                     metrics.out_bytes_consensus.inc_by(size);
+                    metrics
+                        .out_bytes_uncompressed_consensus
+                        .inc_by(uncompressed_size);
                     metrics.out_count_consensus.inc();
                 }
                 MessageKind::DeployGossip => {
                     metrics.out_bytes_deploy_gossip.inc_by(size);
+                    metrics
+                        .out_bytes_uncompressed_deploy_gossip
+                        .inc_by(uncompressed_size);
                     metrics.out_count_deploy_gossip.inc();
                 }
                 MessageKind::AddressGossip => {
                     metrics.out_bytes_address_gossip.inc_by(size);
+                    metrics
+                        .out_bytes_uncompressed_address_gossip
+                        .inc_by(uncompressed_size);
                     metrics.out_count_address_gossip.inc();
                 }
                 MessageKind::DeployTransfer => {
                     metrics.out_bytes_deploy_transfer.inc_by(size);
+                    metrics
+                        .out_bytes_uncompressed_deploy_transfer
+                        .inc_by(uncompressed_size);
                     metrics.out_count_deploy_transfer.inc();
                 }
                 MessageKind::BlockTransfer => {
                     metrics.out_bytes_block_transfer.inc_by(size);
+                    metrics
+                        .out_bytes_uncompressed_block_transfer
+                        .inc_by(uncompressed_size);
                     metrics.out_count_block_transfer.inc();
                 }
                 MessageKind::Other => {
                     metrics.out_bytes_other.inc_by(size);
+                    metrics
+                        .out_bytes_uncompressed_other
+                        .inc_by(uncompressed_size);
                     metrics.out_count_other.inc();
                 }
             }
@@ -272,6 +367,14 @@ impl Drop for NetworkingMetrics {
         unregister_metric!(self.registry, self.out_bytes_deploy_transfer);
         unregister_metric!(self.registry, self.out_bytes_block_transfer);
         unregister_metric!(self.registry, self.out_bytes_other);
+        unregister_metric!(self.registry, self.out_bytes_uncompressed_protocol);
+        unregister_metric!(self.registry, self.out_bytes_uncompressed_consensus);
+        unregister_metric!(self.registry, self.out_bytes_uncompressed_deploy_gossip);
+        unregister_metric!(self.registry, self.out_bytes_uncompressed_address_gossip);
+        unregister_metric!(self.registry, self.out_bytes_uncompressed_deploy_transfer);
+        unregister_metric!(self.registry, self.out_bytes_uncompressed_block_transfer);
+        unregister_metric!(self.registry, self.out_bytes_uncompressed_other);
+        unregister_metric!(self.registry, self.traffic_capture_dropped_records);
 
         unregister_metric!(self.registry, self.read_futures_in_flight);
         unregister_metric!(self.registry, self.read_futures_total);
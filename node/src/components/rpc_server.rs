@@ -26,18 +26,18 @@ use futures::join;
 
 use casper_execution_engine::{
     core::engine_state::{
-        self, BalanceRequest, BalanceResult, GetBidsRequest, GetEraValidatorsError, QueryRequest,
-        QueryResult,
+        self, BalanceRequest, BalanceResult, DeployItem, ExecuteRequest, GetBidsRequest,
+        GetEraValidatorsError, GetWithdrawsRequest, QueryRequest, QueryResult,
     },
     storage::protocol_data::ProtocolData,
 };
-use casper_types::{system::auction::EraValidators, Key, ProtocolVersion, URef};
+use casper_types::{system::auction::EraValidators, ExecutionResult, Key, ProtocolVersion, URef};
 
 use self::rpcs::chain::BlockIdentifier;
 
 use super::Component;
 use crate::{
-    components::contract_runtime::EraValidatorsRequest,
+    components::contract_runtime::{EraValidatorsRequest, GasEstimate},
     crypto::hash::Digest,
     effect::{
         announcements::RpcServerAnnouncement,
@@ -47,7 +47,7 @@ use crate::{
         },
         EffectBuilder, EffectExt, Effects, Responder,
     },
-    types::{NodeId, StatusFeed},
+    types::{Block, Deploy, NodeId, StatusFeed},
     utils::{self, ListeningError},
     NodeRng,
 };
@@ -105,6 +105,8 @@ impl RpcServer {
             effect_builder,
             api_version,
             config.qps_limit,
+            config.enable_admin_rpcs,
+            config.enable_debug_rpcs,
         ));
 
         Ok(RpcServer {})
@@ -174,6 +176,50 @@ impl RpcServer {
                 main_responder: responder,
             })
     }
+
+    fn handle_speculative_execute<REv: ReactorEventT>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        block: Block,
+        deploy: Deploy,
+        responder: Responder<Result<ExecutionResult, engine_state::Error>>,
+    ) -> Effects<Event> {
+        let execute_request = new_execute_request(&block, deploy);
+        effect_builder
+            .speculative_execute(execute_request)
+            .event(move |result| Event::SpeculativeExecuteResult {
+                result,
+                main_responder: responder,
+            })
+    }
+
+    fn handle_estimate_gas<REv: ReactorEventT>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        block: Block,
+        deploy: Deploy,
+        safety_margin_percent: u64,
+        responder: Responder<Result<GasEstimate, engine_state::Error>>,
+    ) -> Effects<Event> {
+        let execute_request = new_execute_request(&block, deploy);
+        effect_builder
+            .estimate_gas(execute_request, safety_margin_percent)
+            .event(move |result| Event::EstimateGasResult {
+                result,
+                main_responder: responder,
+            })
+    }
+}
+
+/// Creates a request to execute the deploy on top of the global state of the given block.
+fn new_execute_request(block: &Block, deploy: Deploy) -> ExecuteRequest {
+    ExecuteRequest::new(
+        (*block.header().state_root_hash()).into(),
+        block.header().timestamp().millis(),
+        vec![DeployItem::from(deploy)],
+        block.header().protocol_version(),
+        block.body().proposer().clone(),
+    )
 }
 
 impl<REv> Component<REv> for RpcServer
@@ -265,11 +311,40 @@ where
                         main_responder: responder,
                     })
             }
+            Event::RpcRequest(RpcRequest::GetWithdraws {
+                state_root_hash,
+                responder,
+            }) => {
+                let get_withdraws_request = GetWithdrawsRequest::new(state_root_hash.into());
+                effect_builder
+                    .get_withdraws(get_withdraws_request)
+                    .event(move |result| Event::GetWithdrawsResult {
+                        result,
+                        main_responder: responder,
+                    })
+            }
             Event::RpcRequest(RpcRequest::GetBalance {
                 state_root_hash,
                 purse_uref,
                 responder,
             }) => self.handle_get_balance(effect_builder, state_root_hash, purse_uref, responder),
+            Event::RpcRequest(RpcRequest::SpeculativeExecute {
+                block,
+                deploy,
+                responder,
+            }) => self.handle_speculative_execute(effect_builder, *block, *deploy, responder),
+            Event::RpcRequest(RpcRequest::EstimateGas {
+                block,
+                deploy,
+                safety_margin_percent,
+                responder,
+            }) => self.handle_estimate_gas(
+                effect_builder,
+                *block,
+                *deploy,
+                safety_margin_percent,
+                responder,
+            ),
             Event::RpcRequest(RpcRequest::GetDeploy { hash, responder }) => effect_builder
                 .get_deploy_and_metadata_from_storage(hash)
                 .event(move |result| Event::GetDeployResult {
@@ -327,10 +402,22 @@ where
                 result,
                 main_responder,
             } => main_responder.respond(result).ignore(),
+            Event::GetWithdrawsResult {
+                result,
+                main_responder,
+            } => main_responder.respond(result).ignore(),
             Event::GetBalanceResult {
                 result,
                 main_responder,
             } => main_responder.respond(result).ignore(),
+            Event::SpeculativeExecuteResult {
+                result,
+                main_responder,
+            } => main_responder.respond(result).ignore(),
+            Event::EstimateGasResult {
+                result,
+                main_responder,
+            } => main_responder.respond(result).ignore(),
             Event::GetDeployResult {
                 hash: _,
                 result,
@@ -18,6 +18,14 @@ pub struct Config {
 
     /// Max rate limit in qps.
     pub qps_limit: u64,
+
+    /// Whether to serve the admin RPCs, e.g. for banning peers.
+    #[serde(default)]
+    pub enable_admin_rpcs: bool,
+
+    /// Whether to serve the debug RPCs, e.g. for tracing the execution of deploys.
+    #[serde(default)]
+    pub enable_debug_rpcs: bool,
 }
 
 impl Config {
@@ -26,6 +34,8 @@ impl Config {
         Config {
             address: DEFAULT_ADDRESS.to_string(),
             qps_limit: DEFAULT_QPS_LIMIT,
+            enable_admin_rpcs: false,
+            enable_debug_rpcs: false,
         }
     }
 }
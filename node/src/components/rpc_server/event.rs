@@ -6,12 +6,15 @@ use std::{
 use derive_more::From;
 
 use casper_execution_engine::{
-    core::engine_state::{self, BalanceResult, GetBidsResult, GetEraValidatorsError, QueryResult},
+    core::engine_state::{
+        self, BalanceResult, GetBidsResult, GetEraValidatorsError, GetWithdrawsResult, QueryResult,
+    },
     storage::protocol_data::ProtocolData,
 };
-use casper_types::{system::auction::EraValidators, Transfer};
+use casper_types::{system::auction::EraValidators, ExecutionResult, Transfer};
 
 use crate::{
+    components::contract_runtime::GasEstimate,
     effect::{requests::RpcRequest, Responder},
     rpcs::chain::BlockIdentifier,
     types::{Block, BlockHash, BlockSignatures, Deploy, DeployHash, DeployMetadata, NodeId},
@@ -47,6 +50,10 @@ pub enum Event {
         result: Result<GetBidsResult, engine_state::Error>,
         main_responder: Responder<Result<GetBidsResult, engine_state::Error>>,
     },
+    GetWithdrawsResult {
+        result: Result<GetWithdrawsResult, engine_state::Error>,
+        main_responder: Responder<Result<GetWithdrawsResult, engine_state::Error>>,
+    },
     GetDeployResult {
         hash: DeployHash,
         result: Box<Option<(Deploy, DeployMetadata)>>,
@@ -64,6 +71,14 @@ pub enum Event {
         result: Result<BalanceResult, engine_state::Error>,
         main_responder: Responder<Result<BalanceResult, engine_state::Error>>,
     },
+    SpeculativeExecuteResult {
+        result: Result<ExecutionResult, engine_state::Error>,
+        main_responder: Responder<Result<ExecutionResult, engine_state::Error>>,
+    },
+    EstimateGasResult {
+        result: Result<GasEstimate, engine_state::Error>,
+        main_responder: Responder<Result<GasEstimate, engine_state::Error>>,
+    },
 }
 
 impl Display for Event {
@@ -104,9 +119,18 @@ impl Display for Event {
             Event::GetBidsResult { result, .. } => {
                 write!(formatter, "get bids result: {:?}", result)
             }
+            Event::GetWithdrawsResult { result, .. } => {
+                write!(formatter, "get withdraws result: {:?}", result)
+            }
             Event::GetBalanceResult { result, .. } => {
                 write!(formatter, "balance result: {:?}", result)
             }
+            Event::SpeculativeExecuteResult { result, .. } => {
+                write!(formatter, "speculative execute result: {:?}", result)
+            }
+            Event::EstimateGasResult { result, .. } => {
+                write!(formatter, "estimate gas result: {:?}", result)
+            }
             Event::GetDeployResult { hash, result, .. } => {
                 write!(formatter, "get deploy result for {}: {:?}", hash, result)
             }
@@ -50,6 +50,8 @@ pub(super) async fn run<REv: ReactorEventT>(
     effect_builder: EffectBuilder<REv>,
     api_version: ProtocolVersion,
     qps_limit: u64,
+    enable_admin_rpcs: bool,
+    enable_debug_rpcs: bool,
 ) {
     // RPC filters.
     let rpc_put_deploy = rpcs::account::PutDeploy::create_filter(effect_builder, api_version);
@@ -59,6 +61,8 @@ pub(super) async fn run<REv: ReactorEventT>(
     let rpc_get_state_root_hash =
         rpcs::chain::GetStateRootHash::create_filter(effect_builder, api_version);
     let rpc_get_item = rpcs::state::GetItem::create_filter(effect_builder, api_version);
+    let rpc_get_contract_schema =
+        rpcs::state::GetContractSchema::create_filter(effect_builder, api_version);
     let rpc_get_balance = rpcs::state::GetBalance::create_filter(effect_builder, api_version);
     let rpc_get_account_info =
         rpcs::state::GetAccountInfo::create_filter(effect_builder, api_version);
@@ -67,11 +71,49 @@ pub(super) async fn run<REv: ReactorEventT>(
     let rpc_get_status = rpcs::info::GetStatus::create_filter(effect_builder, api_version);
     let rpc_get_era_info =
         rpcs::chain::GetEraInfoBySwitchBlock::create_filter(effect_builder, api_version);
+    let rpc_get_contract_events =
+        rpcs::chain::GetContractEvents::create_filter(effect_builder, api_version);
+    let rpc_get_rewards = rpcs::chain::GetRewards::create_filter(effect_builder, api_version);
     let rpc_get_auction_info =
         rpcs::state::GetAuctionInfo::create_filter(effect_builder, api_version);
+    let rpc_get_unbonding_purses =
+        rpcs::state::GetUnbondingPurses::create_filter(effect_builder, api_version);
+    let rpc_get_light_client_bundle =
+        rpcs::light_client::GetBundle::create_filter(effect_builder, api_version);
     let rpc_get_rpcs = rpcs::docs::ListRpcs::create_filter(effect_builder, api_version);
     let rpc_get_dictionary_item =
         rpcs::state::GetDictionaryItem::create_filter(effect_builder, api_version);
+    let rpc_speculative_exec =
+        rpcs::speculative_exec::SpeculativeExec::create_filter(effect_builder, api_version);
+    let rpc_estimate_gas =
+        rpcs::speculative_exec::EstimateGas::create_filter(effect_builder, api_version);
+    let rpc_trace_deploy = rpcs::debug::TraceDeploy::create_filter(effect_builder, api_version);
+    let rpc_ban_peer = rpcs::admin::BanPeer::create_filter(effect_builder, api_version);
+    let rpc_unban_peer = rpcs::admin::UnbanPeer::create_filter(effect_builder, api_version);
+
+    // Unless enabled in the config, admin RPCs are rejected and reported as unknown methods.
+    let rpc_admin = warp::any()
+        .and_then(move || {
+            future::ready(if enable_admin_rpcs {
+                Ok(())
+            } else {
+                Err(warp::reject::not_found())
+            })
+        })
+        .untuple_one()
+        .and(rpc_ban_peer.or(rpc_unban_peer).unify());
+
+    // Likewise for debug RPCs, which re-execute deploys and so are expensive to serve.
+    let rpc_debug = warp::any()
+        .and_then(move || {
+            future::ready(if enable_debug_rpcs {
+                Ok(())
+            } else {
+                Err(warp::reject::not_found())
+            })
+        })
+        .untuple_one()
+        .and(rpc_trace_deploy);
 
     // Catch requests where the method is not one we handle.
     let unknown_method = warp::path(RPC_API_PATH)
@@ -97,15 +139,24 @@ pub(super) async fn run<REv: ReactorEventT>(
             .or(rpc_get_block_transfers)
             .or(rpc_get_state_root_hash)
             .or(rpc_get_item)
+            .or(rpc_get_contract_schema)
             .or(rpc_get_balance)
             .or(rpc_get_deploy)
             .or(rpc_get_peers)
             .or(rpc_get_status)
             .or(rpc_get_era_info)
+            .or(rpc_get_contract_events)
+            .or(rpc_get_rewards)
             .or(rpc_get_auction_info)
+            .or(rpc_get_unbonding_purses)
             .or(rpc_get_account_info)
+            .or(rpc_get_light_client_bundle)
             .or(rpc_get_rpcs)
             .or(rpc_get_dictionary_item)
+            .or(rpc_speculative_exec)
+            .or(rpc_estimate_gas)
+            .or(rpc_debug)
+            .or(rpc_admin)
             .or(unknown_method)
             .or(parse_failure),
     );
@@ -3,9 +3,13 @@
 //! See <https://github.com/CasperLabs/ceps/blob/master/text/0009-client-api.md#rpcs> for info.
 
 pub mod account;
+pub mod admin;
 pub mod chain;
+pub mod debug;
 pub mod docs;
 pub mod info;
+pub mod light_client;
+pub mod speculative_exec;
 pub mod state;
 
 use std::str;
@@ -49,6 +53,15 @@ enum ErrorCode {
     NoSuchAccount = -32009,
     FailedToGetDictionaryURef = -32010,
     NoDictionaryName = -32011,
+    BlockPruned = -32012,
+    DeployPruned = -32013,
+    SpeculativeExecFailed = -32014,
+    DeployNotExecuted = -32015,
+    TraceDeployFailed = -32016,
+    NoSuchContract = -32017,
+    InvalidEraRange = -32018,
+    BanningNotSupported = -32019,
+    TooManyStateQueries = -32020,
 }
 
 #[derive(Debug)]
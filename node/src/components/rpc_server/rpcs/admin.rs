@@ -0,0 +1,182 @@
+//! RPCs for administering the node.
+//!
+//! These are only served if `enable_admin_rpcs` is set in the RPC server config.
+
+// TODO - remove once schemars stops causing warning.
+#![allow(clippy::field_reassign_with_default)]
+
+use std::str;
+
+use futures::{future::BoxFuture, FutureExt};
+use http::Response;
+use hyper::Body;
+use once_cell::sync::Lazy;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use warp_json_rpc::Builder;
+
+use casper_types::ProtocolVersion;
+
+use super::{
+    docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
+    Error, ErrorCode, ReactorEventT, RpcWithParams, RpcWithParamsExt,
+};
+use crate::{
+    effect::EffectBuilder,
+    types::{NodeId, TimeDiff, Timestamp},
+};
+
+static BAN_PEER_PARAMS: Lazy<BanPeerParams> = Lazy::new(|| BanPeerParams {
+    node_id: *NodeId::doc_example(),
+    duration: Some(TimeDiff::from_seconds(3600)),
+});
+static BAN_PEER_RESULT: Lazy<BanPeerResult> = Lazy::new(|| BanPeerResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    banned_until: *Timestamp::doc_example() + TimeDiff::from_seconds(3600),
+});
+static UNBAN_PEER_PARAMS: Lazy<UnbanPeerParams> = Lazy::new(|| UnbanPeerParams {
+    node_id: *NodeId::doc_example(),
+});
+static UNBAN_PEER_RESULT: Lazy<UnbanPeerResult> = Lazy::new(|| UnbanPeerResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    was_banned: true,
+});
+
+/// Params for "admin_ban_peer" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BanPeerParams {
+    /// The node ID of the peer to ban.
+    pub node_id: NodeId,
+    /// The duration of the ban. Defaults to the configured ban duration if not provided.
+    #[serde(default)]
+    pub duration: Option<TimeDiff>,
+}
+
+impl DocExample for BanPeerParams {
+    fn doc_example() -> &'static Self {
+        &*BAN_PEER_PARAMS
+    }
+}
+
+/// Result for "admin_ban_peer" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BanPeerResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The time until which the peer is banned.
+    pub banned_until: Timestamp,
+}
+
+impl DocExample for BanPeerResult {
+    fn doc_example() -> &'static Self {
+        &*BAN_PEER_RESULT
+    }
+}
+
+/// "admin_ban_peer" RPC.
+///
+/// Disconnects from the given peer and refuses any connections to or from it until the ban ends.
+/// An existing longer ban is left in place.
+pub struct BanPeer {}
+
+impl RpcWithParams for BanPeer {
+    const METHOD: &'static str = "admin_ban_peer";
+    type RequestParams = BanPeerParams;
+    type ResponseResult = BanPeerResult;
+}
+
+impl RpcWithParamsExt for BanPeer {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+        api_version: ProtocolVersion,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let node_id = params.node_id;
+            let banned_until = match effect_builder.ban_peer(node_id, params.duration).await {
+                Some(banned_until) => banned_until,
+                None => {
+                    info!(%node_id, "failed to ban peer");
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::BanningNotSupported as i64,
+                        "the networking component does not support banning peers",
+                    ))?);
+                }
+            };
+
+            let result = Self::ResponseResult {
+                api_version,
+                banned_until,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// Params for "admin_unban_peer" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UnbanPeerParams {
+    /// The node ID of the peer to unban.
+    pub node_id: NodeId,
+}
+
+impl DocExample for UnbanPeerParams {
+    fn doc_example() -> &'static Self {
+        &*UNBAN_PEER_PARAMS
+    }
+}
+
+/// Result for "admin_unban_peer" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UnbanPeerResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// Whether the peer was banned before this request.
+    pub was_banned: bool,
+}
+
+impl DocExample for UnbanPeerResult {
+    fn doc_example() -> &'static Self {
+        &*UNBAN_PEER_RESULT
+    }
+}
+
+/// "admin_unban_peer" RPC.
+///
+/// Lifts the ban on the given peer and resets its reputation score.
+pub struct UnbanPeer {}
+
+impl RpcWithParams for UnbanPeer {
+    const METHOD: &'static str = "admin_unban_peer";
+    type RequestParams = UnbanPeerParams;
+    type ResponseResult = UnbanPeerResult;
+}
+
+impl RpcWithParamsExt for UnbanPeer {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+        api_version: ProtocolVersion,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let was_banned = effect_builder.unban_peer(params.node_id).await;
+
+            let result = Self::ResponseResult {
+                api_version,
+                was_banned,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
@@ -3,6 +3,7 @@
 // TODO - remove once schemars stops causing warning.
 #![allow(clippy::field_reassign_with_default)]
 
+mod era_rewards;
 mod era_summary;
 
 use std::str;
@@ -16,22 +17,36 @@ use serde::{Deserialize, Serialize};
 use tracing::info;
 use warp_json_rpc::Builder;
 
-use casper_types::{Key, ProtocolVersion, Transfer};
+use casper_execution_engine::core::engine_state::QueryResult;
+use casper_types::{
+    AsymmetricType, ContractHash, EraId, ExecutionResult, Key, ProtocolVersion, PublicKey, Transfer,
+};
 
 use super::{
     docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
     Error, ErrorCode, ReactorEventT, RpcRequest, RpcWithOptionalParams, RpcWithOptionalParamsExt,
+    RpcWithParams, RpcWithParamsExt,
 };
 use crate::{
     crypto::hash::Digest,
     effect::EffectBuilder,
     reactor::QueueKind,
     rpcs::common::{self},
-    types::{Block, BlockHash, BlockSignatures, Item, JsonBlock},
+    types::{
+        json_compatibility::StoredValue, Block, BlockHash, BlockSignatures, ContractEventCursor,
+        Deploy, EmittedContractEvent, Item, JsonBlock,
+    },
 };
+pub use era_rewards::EraRewards;
+use era_rewards::ERA_REWARDS;
 pub use era_summary::EraSummary;
 use era_summary::ERA_SUMMARY;
 
+/// The maximum number of eras whose rewards can be requested via "chain_get_rewards".
+const MAX_REWARDS_ERA_RANGE: u64 = 1_000;
+/// The maximum number of events returned by "chain_get_contract_events".
+const MAX_CONTRACT_EVENTS_LIMIT: u32 = 1_000;
+
 static GET_BLOCK_PARAMS: Lazy<GetBlockParams> = Lazy::new(|| GetBlockParams {
     block_identifier: BlockIdentifier::Hash(Block::doc_example().id()),
 });
@@ -65,6 +80,48 @@ static GET_ERA_INFO_RESULT: Lazy<GetEraInfoResult> = Lazy::new(|| GetEraInfoResu
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     era_summary: Some(ERA_SUMMARY.clone()),
 });
+static GET_REWARDS_PARAMS: Lazy<GetRewardsParams> = Lazy::new(|| GetRewardsParams {
+    public_key: PublicKey::from_hex(
+        "01e1b46a25baa8a5c28beb3c9cfb79b572effa04076f00befa57eb70b016153f18",
+    )
+    .unwrap(),
+    start_era_id: EraId::from(40),
+    end_era_id: EraId::from(42),
+    block_identifier: Some(BlockIdentifier::Hash(Block::doc_example().id())),
+});
+static GET_REWARDS_RESULT: Lazy<GetRewardsResult> = Lazy::new(|| GetRewardsResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    rewards: vec![ERA_REWARDS.clone()],
+});
+static GET_CONTRACT_EVENTS_PARAMS: Lazy<GetContractEventsParams> = Lazy::new(|| {
+    let event = &GET_CONTRACT_EVENTS_RESULT.events[0].event;
+    GetContractEventsParams {
+        contract_hash: event.contract_hash,
+        topic: Some(event.topic.clone()),
+        after: None,
+        limit: Some(MAX_CONTRACT_EVENTS_LIMIT),
+    }
+});
+static GET_CONTRACT_EVENTS_RESULT: Lazy<GetContractEventsResult> = Lazy::new(|| {
+    let events = match ExecutionResult::example() {
+        ExecutionResult::Success { events, .. } => events.clone(),
+        ExecutionResult::Failure { .. } => unreachable!("example should be a success"),
+    };
+    GetContractEventsResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        events: (0..)
+            .zip(events)
+            .map(|(event_index, event)| EmittedContractEvent {
+                block_hash: Block::doc_example().id(),
+                block_height: Block::doc_example().height(),
+                deploy_hash: *Deploy::doc_example().id(),
+                event_index,
+                event,
+            })
+            .collect(),
+        next: None,
+    }
+});
 
 /// Identifier for possible ways to retrieve a block.
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, JsonSchema)]
@@ -433,7 +490,248 @@ impl RpcWithOptionalParamsExt for GetEraInfoBySwitchBlock {
     }
 }
 
-async fn get_block<REv: ReactorEventT>(
+/// Params for "chain_get_rewards" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetRewardsParams {
+    /// The public key of the validator or delegator.
+    pub public_key: PublicKey,
+    /// The first era of the range of eras, inclusive.
+    pub start_era_id: EraId,
+    /// The last era of the range of eras, inclusive.
+    pub end_era_id: EraId,
+    /// The block identifier.
+    pub block_identifier: Option<BlockIdentifier>,
+}
+
+impl DocExample for GetRewardsParams {
+    fn doc_example() -> &'static Self {
+        &*GET_REWARDS_PARAMS
+    }
+}
+
+/// Result for "chain_get_rewards" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetRewardsResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The rewards paid out in each era of the range, omitting eras without rewards.
+    pub rewards: Vec<EraRewards>,
+}
+
+impl DocExample for GetRewardsResult {
+    fn doc_example() -> &'static Self {
+        &*GET_REWARDS_RESULT
+    }
+}
+
+/// "chain_get_rewards" RPC.
+pub struct GetRewards {}
+
+impl RpcWithParams for GetRewards {
+    const METHOD: &'static str = "chain_get_rewards";
+    type RequestParams = GetRewardsParams;
+    type ResponseResult = GetRewardsResult;
+}
+
+impl RpcWithParamsExt for GetRewards {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+        api_version: ProtocolVersion,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let start_era_id = params.start_era_id;
+            let end_era_id = params.end_era_id;
+            if start_era_id > end_era_id
+                || end_era_id.value() - start_era_id.value() >= MAX_REWARDS_ERA_RANGE
+            {
+                let error_msg = format!(
+                    "invalid era range {}..={}: must be non-empty and span at most {} eras",
+                    start_era_id.value(),
+                    end_era_id.value(),
+                    MAX_REWARDS_ERA_RANGE
+                );
+                info!("{}", error_msg);
+                return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                    ErrorCode::InvalidEraRange as i64,
+                    error_msg,
+                ))?);
+            }
+
+            let block = match get_block(params.block_identifier, effect_builder).await {
+                Ok(Some(block)) => block,
+                Ok(None) => {
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::NoSuchBlock as i64,
+                        "block not known",
+                    ))?)
+                }
+                Err(error) => return Ok(response_builder.error(error)?),
+            };
+            let state_root_hash = *block.state_root_hash();
+
+            let mut rewards = vec![];
+            for era_id in (start_era_id.value()..=end_era_id.value()).map(EraId::from) {
+                let base_key = Key::EraInfo(era_id);
+                let query_result = effect_builder
+                    .make_request(
+                        |responder| RpcRequest::QueryGlobalState {
+                            state_root_hash,
+                            base_key,
+                            path: vec![],
+                            responder,
+                        },
+                        QueueKind::Api,
+                    )
+                    .await;
+
+                // Eras which have not ended yet have no era info.
+                if let Ok(QueryResult::ValueNotFound(_)) = query_result {
+                    continue;
+                }
+
+                let era_info = match common::extract_query_result(query_result) {
+                    Ok((StoredValue::EraInfo(era_info), _)) => era_info,
+                    Ok((stored_value, _)) => {
+                        let error_msg = format!(
+                            "get-rewards expected era info for era {}, got {:?}",
+                            era_id.value(),
+                            stored_value
+                        );
+                        info!("{}", error_msg);
+                        return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                            ErrorCode::QueryFailed as i64,
+                            error_msg,
+                        ))?);
+                    }
+                    Err((error_code, error_msg)) => {
+                        info!("{}", error_msg);
+                        return Ok(response_builder
+                            .error(warp_json_rpc::Error::custom(error_code as i64, error_msg))?);
+                    }
+                };
+
+                let seigniorage_allocations: Vec<_> = era_info
+                    .select(params.public_key.clone())
+                    .cloned()
+                    .collect();
+                if !seigniorage_allocations.is_empty() {
+                    rewards.push(EraRewards {
+                        era_id,
+                        seigniorage_allocations,
+                    });
+                }
+            }
+
+            let result = Self::ResponseResult {
+                api_version,
+                rewards,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// Params for "chain_get_contract_events" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetContractEventsParams {
+    /// The hash of the contract which emitted the events.
+    pub contract_hash: ContractHash,
+    /// If provided, only events emitted under this topic are returned.
+    pub topic: Option<String>,
+    /// If provided, only events emitted after the event at this position are returned.
+    ///
+    /// Pass the `next` cursor of the previous response to retrieve the following events.
+    #[serde(default)]
+    pub after: Option<ContractEventCursor>,
+    /// The maximum number of events to return, capped at and defaulting to 1,000.
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+impl DocExample for GetContractEventsParams {
+    fn doc_example() -> &'static Self {
+        &*GET_CONTRACT_EVENTS_PARAMS
+    }
+}
+
+/// Result for "chain_get_contract_events" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetContractEventsResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The events emitted by the contract in chronological order, along with the blocks and
+    /// deploys emitting them.
+    pub events: Vec<EmittedContractEvent>,
+    /// The position of the last returned event if the limit was reached, after which further
+    /// events may follow.
+    pub next: Option<ContractEventCursor>,
+}
+
+impl DocExample for GetContractEventsResult {
+    fn doc_example() -> &'static Self {
+        &*GET_CONTRACT_EVENTS_RESULT
+    }
+}
+
+/// "chain_get_contract_events" RPC.
+pub struct GetContractEvents {}
+
+impl RpcWithParams for GetContractEvents {
+    const METHOD: &'static str = "chain_get_contract_events";
+    type RequestParams = GetContractEventsParams;
+    type ResponseResult = GetContractEventsResult;
+}
+
+impl RpcWithParamsExt for GetContractEvents {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+        api_version: ProtocolVersion,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let limit = params
+                .limit
+                .unwrap_or(MAX_CONTRACT_EVENTS_LIMIT)
+                .min(MAX_CONTRACT_EVENTS_LIMIT) as usize;
+
+            // Events of pruned blocks are no longer available, and are silently omitted.
+            let events = effect_builder
+                .get_contract_events_from_storage(
+                    params.contract_hash,
+                    params.topic,
+                    params.after,
+                    limit,
+                )
+                .await;
+
+            let next = if events.len() == limit {
+                events.last().map(EmittedContractEvent::cursor)
+            } else {
+                None
+            };
+            let result = Self::ResponseResult {
+                api_version,
+                events,
+                next,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// Returns the specified block, or the highest block if `maybe_id` is `None`.
+pub(super) async fn get_block<REv: ReactorEventT>(
     maybe_id: Option<BlockIdentifier>,
     effect_builder: EffectBuilder<REv>,
 ) -> Result<Option<Block>, warp_json_rpc::Error> {
@@ -452,7 +750,6 @@ async fn get_block_with_metadata<REv: ReactorEventT>(
     effect_builder: EffectBuilder<REv>,
 ) -> Result<Option<(Block, BlockSignatures)>, warp_json_rpc::Error> {
     // Get the block from storage or the latest from the linear chain.
-    let getting_specific_block = maybe_id.is_some();
     let maybe_result = effect_builder
         .make_request(
             |responder| RpcRequest::GetBlock {
@@ -463,8 +760,27 @@ async fn get_block_with_metadata<REv: ReactorEventT>(
         )
         .await;
 
-    if maybe_result.is_none() && getting_specific_block {
-        info!("failed to get {:?} from storage", maybe_id.unwrap());
+    if let (None, Some(block_id)) = (&maybe_result, maybe_id) {
+        info!("failed to get {:?} from storage", block_id);
+        // The header outlives the rest of the block if the block has been pruned.
+        let maybe_header = match block_id {
+            BlockIdentifier::Hash(block_hash) => {
+                effect_builder
+                    .get_block_header_from_storage(block_hash)
+                    .await
+            }
+            BlockIdentifier::Height(height) => {
+                effect_builder
+                    .get_block_header_at_height_from_storage(height)
+                    .await
+            }
+        };
+        if maybe_header.is_some() {
+            return Err(warp_json_rpc::Error::custom(
+                ErrorCode::BlockPruned as i64,
+                "block has been pruned",
+            ));
+        }
         return Err(warp_json_rpc::Error::custom(
             ErrorCode::NoSuchBlock as i64,
             "block not known",
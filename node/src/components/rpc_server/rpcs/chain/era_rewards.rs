@@ -0,0 +1,35 @@
+use once_cell::sync::Lazy;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use casper_types::{
+    system::auction::SeigniorageAllocation, AsymmetricType, EraId, PublicKey, U512,
+};
+
+pub(super) static ERA_REWARDS: Lazy<EraRewards> = Lazy::new(|| {
+    let delegator_public_key =
+        PublicKey::from_hex("01e1b46a25baa8a5c28beb3c9cfb79b572effa04076f00befa57eb70b016153f18")
+            .unwrap();
+    let validator_public_key =
+        PublicKey::from_hex("012a1732addc639ea43a89e25d3ad912e40232156dcaa4b9edfc709f43d2fb0876")
+            .unwrap();
+    let delegator = SeigniorageAllocation::delegator(
+        delegator_public_key,
+        validator_public_key,
+        U512::from(1000),
+    );
+    EraRewards {
+        era_id: EraId::from(42),
+        seigniorage_allocations: vec![delegator],
+    }
+});
+
+/// The rewards paid out to a validator or delegator at the end of an era.
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct EraRewards {
+    /// The era id
+    pub era_id: EraId,
+    /// The seigniorage allocations of the era which were paid out to the validator or delegator
+    pub seigniorage_allocations: Vec<SeigniorageAllocation>,
+}
@@ -0,0 +1,206 @@
+//! RPCs for debugging the execution of deploys.
+//!
+//! These are only served if `enable_debug_rpcs` is set in the RPC server config.
+
+// TODO - remove once schemars stops causing warning.
+#![allow(clippy::field_reassign_with_default)]
+
+use std::str;
+
+use futures::{future::BoxFuture, FutureExt};
+use http::Response;
+use hyper::Body;
+use once_cell::sync::Lazy;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use warp_json_rpc::Builder;
+
+use casper_execution_engine::core::engine_state::{ExecutionTrace, TraceEntry};
+use casper_types::{ExecutionResult, Key, ProtocolVersion, U512};
+
+use super::{
+    docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
+    Error, ErrorCode, ReactorEventT, RpcWithParams, RpcWithParamsExt,
+};
+use crate::{
+    effect::EffectBuilder,
+    types::{Block, BlockHash, Deploy, DeployHash},
+};
+
+static TRACE_DEPLOY_PARAMS: Lazy<TraceDeployParams> = Lazy::new(|| TraceDeployParams {
+    deploy_hash: *Deploy::doc_example().id(),
+});
+static TRACE_DEPLOY_RESULT: Lazy<TraceDeployResult> = Lazy::new(|| TraceDeployResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    block_hash: Block::doc_example().id(),
+    execution_result: ExecutionResult::example().clone(),
+    trace: ExecutionTrace::new(vec![
+        TraceEntry::HostFunctionCall {
+            name: "ReadFuncIndex".to_string(),
+            args: vec![1_048_576, 33, 1_048_616],
+        },
+        TraceEntry::GasCharge {
+            amount: U512::from(10_000),
+            gas_used: U512::from(123_456),
+        },
+        TraceEntry::Read {
+            key: Key::Hash([1; 32]),
+        },
+    ]),
+});
+
+/// Params for "debug_trace_deploy" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TraceDeployParams {
+    /// The hash of the executed deploy to trace.
+    pub deploy_hash: DeployHash,
+}
+
+impl DocExample for TraceDeployParams {
+    fn doc_example() -> &'static Self {
+        &*TRACE_DEPLOY_PARAMS
+    }
+}
+
+/// Result for "debug_trace_deploy" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TraceDeployResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// Hash of the block in which the deploy was executed.
+    pub block_hash: BlockHash,
+    /// The result of re-executing the deploy.
+    pub execution_result: ExecutionResult,
+    /// The host function calls, contract calls, gas charges and global state accesses recorded
+    /// while re-executing the deploy, truncated after a maximum number of entries.
+    pub trace: ExecutionTrace,
+}
+
+impl DocExample for TraceDeployResult {
+    fn doc_example() -> &'static Self {
+        &*TRACE_DEPLOY_RESULT
+    }
+}
+
+/// "debug_trace_deploy" RPC.
+///
+/// Re-executes a deploy on top of the global state of the parent of the block in which it was
+/// executed, after the deploys preceding it in that block.
+pub struct TraceDeploy {}
+
+impl RpcWithParams for TraceDeploy {
+    const METHOD: &'static str = "debug_trace_deploy";
+    type RequestParams = TraceDeployParams;
+    type ResponseResult = TraceDeployResult;
+}
+
+impl RpcWithParamsExt for TraceDeploy {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+        api_version: ProtocolVersion,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let deploy_hash = params.deploy_hash;
+
+            let (deploy, metadata) = match effect_builder
+                .get_deploy_and_metadata_from_storage(deploy_hash)
+                .await
+            {
+                Some(deploy_and_metadata) => deploy_and_metadata,
+                None => {
+                    info!(%deploy_hash, "failed to get deploy and metadata from storage");
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::NoSuchDeploy as i64,
+                        "deploy not known",
+                    ))?);
+                }
+            };
+
+            let block_hash = match metadata.execution_results.keys().next() {
+                Some(block_hash) => *block_hash,
+                None => {
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::DeployNotExecuted as i64,
+                        "deploy has not been executed",
+                    ))?);
+                }
+            };
+
+            let block = match effect_builder.get_block_from_storage(block_hash).await {
+                Some(block) => block,
+                None => {
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::NoSuchBlock as i64,
+                        "block not known",
+                    ))?);
+                }
+            };
+
+            let pre_state_root_hash = match effect_builder
+                .get_block_header_from_storage(*block.header().parent_hash())
+                .await
+            {
+                Some(parent_header) => *parent_header.state_root_hash(),
+                None => {
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::NoSuchBlock as i64,
+                        "parent block not known",
+                    ))?);
+                }
+            };
+
+            // The deploys of a block are executed before its transfers.
+            let preceding_deploy_hashes = block
+                .deploy_hashes()
+                .iter()
+                .chain(block.transfer_hashes())
+                .take_while(|hash| **hash != deploy_hash)
+                .copied()
+                .collect();
+            let preceding_deploys = match effect_builder
+                .get_deploys_from_storage(preceding_deploy_hashes)
+                .await
+                .into_iter()
+                .collect::<Option<Vec<_>>>()
+            {
+                Some(preceding_deploys) => preceding_deploys,
+                None => {
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::DeployPruned as i64,
+                        "deploy preceding the traced deploy in its block has been pruned",
+                    ))?);
+                }
+            };
+
+            let trace_deploy_result = effect_builder
+                .trace_deploy(block, pre_state_root_hash, preceding_deploys, deploy)
+                .await;
+
+            match trace_deploy_result {
+                Ok((execution_result, trace)) => {
+                    let result = Self::ResponseResult {
+                        api_version,
+                        block_hash,
+                        execution_result,
+                        trace,
+                    };
+                    Ok(response_builder.success(result)?)
+                }
+                Err(error) => {
+                    info!(%deploy_hash, %block_hash, %error, "tracing deploy failed");
+                    Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::TraceDeployFailed as i64,
+                        error.to_string(),
+                    ))?)
+                }
+            }
+        }
+        .boxed()
+    }
+}
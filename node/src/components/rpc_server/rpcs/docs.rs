@@ -20,9 +20,13 @@ use casper_types::ProtocolVersion;
 
 use super::{
     account::PutDeploy,
-    chain::{GetBlock, GetBlockTransfers, GetStateRootHash},
+    admin::{BanPeer, UnbanPeer},
+    chain::{GetBlock, GetBlockTransfers, GetContractEvents, GetRewards, GetStateRootHash},
+    debug::TraceDeploy,
     info::{GetDeploy, GetPeers, GetStatus},
-    state::{GetAuctionInfo, GetBalance, GetItem},
+    light_client::GetBundle,
+    speculative_exec::{EstimateGas, SpeculativeExec},
+    state::{GetAuctionInfo, GetBalance, GetContractSchema, GetItem, GetUnbondingPurses},
     Error, ReactorEventT, RpcWithOptionalParams, RpcWithParams, RpcWithoutParams,
     RpcWithoutParamsExt,
 };
@@ -88,13 +92,37 @@ pub(crate) static OPEN_RPC_SCHEMA: Lazy<OpenRpcSchema> = Lazy::new(|| {
         "returns a state root hash at a given Block",
     );
     schema.push_with_params::<GetItem>("returns a stored value from the network");
+    schema.push_with_params::<GetContractSchema>(
+        "returns the entry points and named keys of a stored contract",
+    );
     schema.push_with_params::<GetBalance>("returns a purse's balance from the network");
     schema.push_with_optional_params::<GetEraInfoBySwitchBlock>(
         "returns an EraInfo from the network",
     );
+    schema.push_with_params::<GetContractEvents>(
+        "returns the events emitted by a contract, optionally restricted to a topic",
+    );
+    schema.push_with_params::<GetRewards>(
+        "returns the rewards paid out to a validator or delegator in each era of a range of eras",
+    );
     schema.push_with_optional_params::<GetAuctionInfo>(
         "returns the bids and validators as of either a specific block (by height or hash), or the most recently added block",
     );
+    schema.push_with_params::<GetUnbondingPurses>(
+        "returns the pending unbonding purses of a validator or delegator",
+    );
+    schema.push_with_params::<GetBundle>(
+        "returns the block headers and finality signatures a light client needs to follow the validator set from a trusted block, along with merkle proofs of global state keys",
+    );
+    schema.push_with_params::<SpeculativeExec>(
+        "executes a Deploy on top of a Block's global state without committing its effects",
+    );
+    schema.push_with_params::<EstimateGas>(
+        "estimates the gas used by a Deploy and suggests a standard payment amount",
+    );
+    schema.push_with_params::<TraceDeploy>(
+        "re-executes an executed Deploy, returning a trace of its host function calls, contract calls, gas charges and global state accesses",
+    );
 
     schema
 });
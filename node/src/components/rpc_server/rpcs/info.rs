@@ -24,7 +24,10 @@ use super::{
 use crate::{
     effect::EffectBuilder,
     reactor::QueueKind,
-    types::{Block, BlockHash, Deploy, DeployHash, GetStatusResult, Item, PeersMap},
+    types::{
+        Block, BlockHash, Deploy, DeployHash, GetStatusResult, Item, NodeId, PeersMap, TimeDiff,
+        Timestamp,
+    },
 };
 
 static GET_DEPLOY_PARAMS: Lazy<GetDeployParams> = Lazy::new(|| GetDeployParams {
@@ -41,6 +44,11 @@ static GET_DEPLOY_RESULT: Lazy<GetDeployResult> = Lazy::new(|| GetDeployResult {
 static GET_PEERS_RESULT: Lazy<GetPeersResult> = Lazy::new(|| GetPeersResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     peers: GetStatusResult::doc_example().peers.clone(),
+    blocklist: vec![JsonPeerReputation {
+        node_id: *NodeId::doc_example(),
+        score: 100,
+        banned_until: Some(*Timestamp::doc_example() + TimeDiff::from_seconds(3600)),
+    }],
 });
 
 /// Params for "info_get_deploy" RPC request.
@@ -121,6 +129,17 @@ impl RpcWithParamsExt for GetDeploy {
                         "failed to get {} and metadata from storage",
                         params.deploy_hash
                     );
+                    // The deploy index outlives the deploy if its block has been pruned.
+                    if effect_builder
+                        .get_block_header_for_deploy_from_storage(params.deploy_hash)
+                        .await
+                        .is_some()
+                    {
+                        return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                            ErrorCode::DeployPruned as i64,
+                            "deploy has been pruned",
+                        ))?);
+                    }
                     return Ok(response_builder.error(warp_json_rpc::Error::custom(
                         ErrorCode::NoSuchDeploy as i64,
                         "deploy not known",
@@ -155,6 +174,20 @@ pub struct GetPeersResult {
     pub api_version: ProtocolVersion,
     /// The node ID and network address of each connected peer.
     pub peers: PeersMap,
+    /// The reputation of each peer which recently committed an offense or is banned.
+    pub blocklist: Vec<JsonPeerReputation>,
+}
+
+/// The reputation of a peer as tracked by the node's blocklist.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct JsonPeerReputation {
+    /// The node ID of the peer.
+    pub node_id: NodeId,
+    /// The reputation score of the peer. Higher scores are worse.
+    pub score: u32,
+    /// The time until which the peer is banned, if it is currently banned.
+    pub banned_until: Option<Timestamp>,
 }
 
 impl DocExample for GetPeersResult {
@@ -185,9 +218,22 @@ impl RpcWithoutParamsExt for GetPeers {
                 )
                 .await;
 
+            let now = Timestamp::now();
+            let blocklist = effect_builder
+                .network_blocklist()
+                .await
+                .into_iter()
+                .map(|(node_id, reputation)| JsonPeerReputation {
+                    node_id,
+                    score: reputation.score(),
+                    banned_until: reputation.banned_until(now),
+                })
+                .collect();
+
             let result = Self::ResponseResult {
                 api_version,
                 peers: PeersMap::from(peers),
+                blocklist,
             };
             Ok(response_builder.success(result)?)
         }
@@ -0,0 +1,270 @@
+//! RPCs serving light clients.
+
+// TODO - remove once schemars stops causing warning.
+#![allow(clippy::field_reassign_with_default)]
+
+use futures::{future::BoxFuture, FutureExt};
+use http::Response;
+use hyper::Body;
+use once_cell::sync::Lazy;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use warp_json_rpc::Builder;
+
+use casper_execution_engine::{
+    core::engine_state::QueryResult, shared::stored_value::StoredValue,
+    storage::trie::merkle_proof::TrieMerkleProof,
+};
+use casper_light_client::{MerkleProof, StateProof};
+use casper_types::{
+    bytesrepr::{self, ToBytes},
+    Key, ProtocolVersion,
+};
+
+use super::{
+    docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
+    Error, ErrorCode, ReactorEventT, RpcRequest, RpcWithParams, RpcWithParamsExt,
+};
+use crate::{
+    crypto::hash::Digest,
+    effect::EffectBuilder,
+    reactor::QueueKind,
+    rpcs::common::MERKLE_PROOF,
+    types::{Block, BlockHash, LightClientBundle},
+};
+
+/// The maximum number of switch blocks included in a bundle served via
+/// "light_client_get_bundle".
+pub const MAX_SWITCH_BLOCKS_PER_BUNDLE: u32 = 100;
+
+/// The maximum number of global state keys which can be proven in a single
+/// "light_client_get_bundle" request.
+pub const MAX_STATE_QUERIES_PER_BUNDLE: usize = 100;
+
+static GET_BUNDLE_PARAMS: Lazy<GetBundleParams> = Lazy::new(|| GetBundleParams {
+    trusted_block_hash: *Block::doc_example().hash(),
+    max_switch_blocks: Some(10),
+    state_queries: vec![StateQuery::doc_example().clone()],
+});
+static GET_BUNDLE_RESULT: Lazy<GetBundleResult> = Lazy::new(|| GetBundleResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    bundle: LightClientBundle::doc_example().clone(),
+    state_proofs: vec![StateProof::doc_example().clone()],
+});
+static STATE_QUERY: Lazy<StateQuery> = Lazy::new(|| StateQuery {
+    key: "deploy-af684263911154d26fa05be9963171802801a0b6aff8f199b7391eacb8edc9e1".to_string(),
+    path: vec![],
+});
+static STATE_PROOF: Lazy<StateProof> = Lazy::new(|| {
+    let proofs: Vec<TrieMerkleProof<Key, StoredValue>> =
+        bytesrepr::deserialize(hex::decode(&*MERKLE_PROOF).expect("should decode merkle proof"))
+            .expect("should deserialize merkle proof");
+    StateProof {
+        key: STATE_QUERY.key.clone(),
+        path: STATE_QUERY.path.clone(),
+        merkle_proofs: to_merkle_proofs(&proofs).expect("should serialize merkle proof"),
+    }
+});
+
+/// A global state key to prove, and the path to follow from it.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct StateQuery {
+    /// `casper_types::Key` as formatted string.
+    pub key: String,
+    /// The path components starting from the key as base.
+    #[serde(default)]
+    pub path: Vec<String>,
+}
+
+impl DocExample for StateQuery {
+    fn doc_example() -> &'static Self {
+        &*STATE_QUERY
+    }
+}
+
+impl DocExample for StateProof {
+    fn doc_example() -> &'static Self {
+        &*STATE_PROOF
+    }
+}
+
+/// Params for "light_client_get_bundle" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetBundleParams {
+    /// Hash of the block trusted by the light client.
+    pub trusted_block_hash: BlockHash,
+    /// The maximum number of switch blocks following the trusted block to include, capped at
+    /// 100.  If more switch blocks follow, the bundle ends at the last one included.
+    #[serde(default)]
+    pub max_switch_blocks: Option<u32>,
+    /// Global state keys to prove against the state root hash of the bundle's tip.
+    #[serde(default)]
+    pub state_queries: Vec<StateQuery>,
+}
+
+impl DocExample for GetBundleParams {
+    fn doc_example() -> &'static Self {
+        &*GET_BUNDLE_PARAMS
+    }
+}
+
+/// Result for "light_client_get_bundle" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetBundleResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The block headers and finality signatures from the trusted block to the tip.
+    pub bundle: LightClientBundle,
+    /// The proofs of the requested global state keys, in the order they were requested.
+    pub state_proofs: Vec<StateProof>,
+}
+
+impl DocExample for GetBundleResult {
+    fn doc_example() -> &'static Self {
+        &*GET_BUNDLE_RESULT
+    }
+}
+
+/// "light_client_get_bundle" RPC.
+pub struct GetBundle {}
+
+impl RpcWithParams for GetBundle {
+    const METHOD: &'static str = "light_client_get_bundle";
+    type RequestParams = GetBundleParams;
+    type ResponseResult = GetBundleResult;
+}
+
+impl RpcWithParamsExt for GetBundle {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+        api_version: ProtocolVersion,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            if params.state_queries.len() > MAX_STATE_QUERIES_PER_BUNDLE {
+                let error_msg = format!(
+                    "cannot prove more than {} state queries per bundle",
+                    MAX_STATE_QUERIES_PER_BUNDLE
+                );
+                info!("{}", error_msg);
+                return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                    ErrorCode::TooManyStateQueries as i64,
+                    error_msg,
+                ))?);
+            }
+
+            // Parse all keys before doing any work.
+            let mut queries = Vec::with_capacity(params.state_queries.len());
+            for query in params.state_queries {
+                match Key::from_formatted_str(&query.key) {
+                    Ok(base_key) => queries.push((base_key, query)),
+                    Err(error) => {
+                        let error_msg = format!("failed to parse key: {}", error);
+                        info!("{}", error_msg);
+                        return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                            ErrorCode::ParseQueryKey as i64,
+                            error_msg,
+                        ))?);
+                    }
+                }
+            }
+
+            let max_switch_blocks = params
+                .max_switch_blocks
+                .unwrap_or(MAX_SWITCH_BLOCKS_PER_BUNDLE)
+                .clamp(1, MAX_SWITCH_BLOCKS_PER_BUNDLE);
+            let bundle = match effect_builder
+                .get_light_client_bundle_from_storage(params.trusted_block_hash, max_switch_blocks)
+                .await
+            {
+                Some(bundle) => bundle,
+                None => {
+                    let error_msg = format!("block {} not known", params.trusted_block_hash);
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::NoSuchBlock as i64,
+                        error_msg,
+                    ))?);
+                }
+            };
+
+            // Prove the queries against the global state of the tip.
+            let state_root_hash = Digest::from(bundle.tip.header.state_root_hash.to_array());
+            let mut state_proofs = Vec::with_capacity(queries.len());
+            for (base_key, query) in queries {
+                let query_result = effect_builder
+                    .make_request(
+                        |responder| RpcRequest::QueryGlobalState {
+                            state_root_hash,
+                            base_key,
+                            path: query.path.clone(),
+                            responder,
+                        },
+                        QueueKind::Api,
+                    )
+                    .await;
+
+                let merkle_proofs = match query_result {
+                    Ok(QueryResult::Success { proofs, .. }) => {
+                        to_merkle_proofs(&proofs).map_err(|error| {
+                            (
+                                ErrorCode::QueryFailed,
+                                format!("failed to encode merkle proof: {:?}", error),
+                            )
+                        })
+                    }
+                    Ok(query_result) => Err((
+                        ErrorCode::QueryFailed,
+                        format!("state query failed: {:?}", query_result),
+                    )),
+                    Err(error) => Err((
+                        ErrorCode::QueryFailedToExecute,
+                        format!("state query failed to execute: {:?}", error),
+                    )),
+                };
+                match merkle_proofs {
+                    Ok(merkle_proofs) => state_proofs.push(StateProof {
+                        key: query.key,
+                        path: query.path,
+                        merkle_proofs,
+                    }),
+                    Err((error_code, error_msg)) => {
+                        info!("{}", error_msg);
+                        return Ok(response_builder
+                            .error(warp_json_rpc::Error::custom(error_code as i64, error_msg))?);
+                    }
+                }
+            }
+
+            let result = Self::ResponseResult {
+                api_version,
+                bundle,
+                state_proofs,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
+/// Converts the merkle proofs of a state query into the form served to light clients.
+fn to_merkle_proofs(
+    proofs: &[TrieMerkleProof<Key, StoredValue>],
+) -> Result<Vec<MerkleProof>, bytesrepr::Error> {
+    proofs
+        .iter()
+        .map(|proof| {
+            Ok(MerkleProof {
+                key: proof.key().to_formatted_string(),
+                value: hex::encode(proof.value().to_bytes()?),
+                proof_steps: hex::encode(proof.proof_steps().to_bytes()?),
+            })
+        })
+        .collect()
+}
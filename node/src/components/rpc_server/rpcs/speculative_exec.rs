@@ -0,0 +1,293 @@
+//! RPCs related to speculative execution of deploys.
+
+// TODO - remove once schemars stops causing warning.
+#![allow(clippy::field_reassign_with_default)]
+
+use std::str;
+
+use futures::{future::BoxFuture, FutureExt};
+use http::Response;
+use hyper::Body;
+use once_cell::sync::Lazy;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use warp_json_rpc::Builder;
+
+use casper_types::{ExecutionResult, ProtocolVersion, U512};
+
+use super::{
+    chain::{self, BlockIdentifier},
+    docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
+    Error, ErrorCode, ReactorEventT, RpcRequest, RpcWithParams, RpcWithParamsExt,
+};
+use crate::{
+    effect::EffectBuilder,
+    reactor::QueueKind,
+    types::{Block, BlockHash, Deploy},
+};
+
+/// The safety margin in percent added to the suggested payment amount if not specified.
+pub const DEFAULT_SAFETY_MARGIN_PERCENT: u64 = 10;
+
+static SPECULATIVE_EXEC_PARAMS: Lazy<SpeculativeExecParams> = Lazy::new(|| SpeculativeExecParams {
+    block_identifier: Some(BlockIdentifier::Hash(Block::doc_example().id())),
+    deploy: Deploy::doc_example().clone(),
+});
+static SPECULATIVE_EXEC_RESULT: Lazy<SpeculativeExecResult> = Lazy::new(|| SpeculativeExecResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    block_hash: Block::doc_example().id(),
+    execution_result: ExecutionResult::example().clone(),
+});
+static ESTIMATE_GAS_PARAMS: Lazy<EstimateGasParams> = Lazy::new(|| EstimateGasParams {
+    block_identifier: Some(BlockIdentifier::Hash(Block::doc_example().id())),
+    deploy: Deploy::doc_example().clone(),
+    safety_margin_percent: Some(DEFAULT_SAFETY_MARGIN_PERCENT),
+});
+static ESTIMATE_GAS_RESULT: Lazy<EstimateGasResult> = Lazy::new(|| EstimateGasResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    block_hash: Block::doc_example().id(),
+    gas_used: U512::from(123_456),
+    payment_amount: U512::from(135_802),
+    execution_result: ExecutionResult::example().clone(),
+});
+
+/// Params for "speculative_exec" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SpeculativeExecParams {
+    /// Block hash or height on top of whose global state the deploy is executed.  Defaults to the
+    /// highest block if not provided.
+    pub block_identifier: Option<BlockIdentifier>,
+    /// The `Deploy` to execute.
+    pub deploy: Deploy,
+}
+
+impl DocExample for SpeculativeExecParams {
+    fn doc_example() -> &'static Self {
+        &*SPECULATIVE_EXEC_PARAMS
+    }
+}
+
+/// Result for "speculative_exec" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SpeculativeExecResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// Hash of the block on top of whose global state the deploy was executed.
+    pub block_hash: BlockHash,
+    /// The result of executing the deploy.
+    pub execution_result: ExecutionResult,
+}
+
+impl DocExample for SpeculativeExecResult {
+    fn doc_example() -> &'static Self {
+        &*SPECULATIVE_EXEC_RESULT
+    }
+}
+
+/// "speculative_exec" RPC
+pub struct SpeculativeExec {}
+
+impl RpcWithParams for SpeculativeExec {
+    const METHOD: &'static str = "speculative_exec";
+    type RequestParams = SpeculativeExecParams;
+    type ResponseResult = SpeculativeExecResult;
+}
+
+impl RpcWithParamsExt for SpeculativeExec {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+        api_version: ProtocolVersion,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let SpeculativeExecParams {
+                block_identifier,
+                mut deploy,
+            } = params;
+            let deploy_hash = *deploy.id();
+
+            let block =
+                match validate_deploy_and_get_block(effect_builder, block_identifier, &mut deploy)
+                    .await
+                {
+                    Ok(block) => block,
+                    Err(error) => return Ok(response_builder.error(error)?),
+                };
+            let block_hash = *block.hash();
+
+            // Execute the deploy against the block's global state, discarding its effects.
+            let speculative_exec_result = effect_builder
+                .make_request(
+                    |responder| RpcRequest::SpeculativeExecute {
+                        block: Box::new(block),
+                        deploy: Box::new(deploy),
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            match speculative_exec_result {
+                Ok(execution_result) => {
+                    let result = Self::ResponseResult {
+                        api_version,
+                        block_hash,
+                        execution_result,
+                    };
+                    Ok(response_builder.success(result)?)
+                }
+                Err(error) => {
+                    info!(%deploy_hash, %block_hash, %error, "speculative execution failed");
+                    Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::SpeculativeExecFailed as i64,
+                        error.to_string(),
+                    ))?)
+                }
+            }
+        }
+        .boxed()
+    }
+}
+
+/// Params for "estimate_gas" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct EstimateGasParams {
+    /// Block hash or height on top of whose global state the deploy is executed.  Defaults to the
+    /// highest block if not provided.
+    pub block_identifier: Option<BlockIdentifier>,
+    /// The `Deploy` to estimate the gas of.  Its payment is replaced by the standard payment.
+    pub deploy: Deploy,
+    /// The safety margin in percent added to the suggested payment amount.  Defaults to 10 if not
+    /// provided.
+    pub safety_margin_percent: Option<u64>,
+}
+
+impl DocExample for EstimateGasParams {
+    fn doc_example() -> &'static Self {
+        &*ESTIMATE_GAS_PARAMS
+    }
+}
+
+/// Result for "estimate_gas" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct EstimateGasResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// Hash of the block on top of whose global state the deploy was executed.
+    pub block_hash: BlockHash,
+    /// The gas used by the deploy, including the standard payment.
+    pub gas_used: U512,
+    /// The suggested `payment_amount` of the standard payment in motes, including the safety
+    /// margin.
+    pub payment_amount: U512,
+    /// The result of executing the deploy with the lowest payment amount found to be sufficient.
+    pub execution_result: ExecutionResult,
+}
+
+impl DocExample for EstimateGasResult {
+    fn doc_example() -> &'static Self {
+        &*ESTIMATE_GAS_RESULT
+    }
+}
+
+/// "estimate_gas" RPC
+pub struct EstimateGas {}
+
+impl RpcWithParams for EstimateGas {
+    const METHOD: &'static str = "estimate_gas";
+    type RequestParams = EstimateGasParams;
+    type ResponseResult = EstimateGasResult;
+}
+
+impl RpcWithParamsExt for EstimateGas {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+        api_version: ProtocolVersion,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let EstimateGasParams {
+                block_identifier,
+                mut deploy,
+                safety_margin_percent,
+            } = params;
+            let deploy_hash = *deploy.id();
+
+            let block =
+                match validate_deploy_and_get_block(effect_builder, block_identifier, &mut deploy)
+                    .await
+                {
+                    Ok(block) => block,
+                    Err(error) => return Ok(response_builder.error(error)?),
+                };
+            let block_hash = *block.hash();
+
+            let estimate_gas_result = effect_builder
+                .make_request(
+                    |responder| RpcRequest::EstimateGas {
+                        block: Box::new(block),
+                        deploy: Box::new(deploy),
+                        safety_margin_percent: safety_margin_percent
+                            .unwrap_or(DEFAULT_SAFETY_MARGIN_PERCENT),
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            match estimate_gas_result {
+                Ok(gas_estimate) => {
+                    let result = Self::ResponseResult {
+                        api_version,
+                        block_hash,
+                        gas_used: gas_estimate.gas_used(),
+                        payment_amount: gas_estimate.payment_amount(),
+                        execution_result: gas_estimate.take_execution_result(),
+                    };
+                    Ok(response_builder.success(result)?)
+                }
+                Err(error) => {
+                    info!(%deploy_hash, %block_hash, %error, "gas estimation failed");
+                    Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::SpeculativeExecFailed as i64,
+                        error.to_string(),
+                    ))?)
+                }
+            }
+        }
+        .boxed()
+    }
+}
+
+/// Checks the validity of the deploy and retrieves the block on top of whose global state it is to
+/// be executed.
+async fn validate_deploy_and_get_block<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    maybe_id: Option<BlockIdentifier>,
+    deploy: &mut Deploy,
+) -> Result<Block, warp_json_rpc::Error> {
+    if let Err(error) = deploy.is_valid() {
+        info!(deploy_hash = %deploy.id(), %error, "invalid deploy submitted for speculative execution");
+        return Err(warp_json_rpc::Error::custom(
+            ErrorCode::InvalidDeploy as i64,
+            error.to_string(),
+        ));
+    }
+
+    match chain::get_block(maybe_id, effect_builder).await? {
+        Some(block) => Ok(block),
+        None => Err(warp_json_rpc::Error::custom(
+            ErrorCode::NoSuchBlock as i64,
+            "block not known",
+        )),
+    }
+}
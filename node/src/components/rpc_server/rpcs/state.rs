@@ -14,9 +14,16 @@ use serde::{Deserialize, Serialize};
 use tracing::{error, info};
 use warp_json_rpc::Builder;
 
-use casper_execution_engine::core::engine_state::{BalanceResult, GetBidsResult};
+use casper_execution_engine::{
+    core::engine_state::{BalanceResult, GetBidsResult, GetWithdrawsResult, QueryResult},
+    shared::stored_value::StoredValue as ExecutionEngineStoredValue,
+};
 use casper_types::{
-    bytesrepr::ToBytes, CLType, CLValue, Key, ProtocolVersion, PublicKey, SecretKey, URef, U512,
+    bytesrepr::ToBytes,
+    contracts::{EntryPoints, NamedKeys},
+    system::auction::UnbondingPurse,
+    AccessRights, CLType, CLValue, Contract, ContractHash, ContractPackageHash, ContractSchema,
+    ContractWasmHash, EntryPoint, EraId, Key, ProtocolVersion, PublicKey, SecretKey, URef, U512,
 };
 
 use super::{
@@ -29,7 +36,7 @@ use crate::{
     effect::EffectBuilder,
     reactor::QueueKind,
     rpcs::{
-        chain::BlockIdentifier,
+        chain::{self, BlockIdentifier},
         common::{self, MERKLE_PROOF},
         RpcWithOptionalParamsExt,
     },
@@ -49,6 +56,32 @@ static GET_ITEM_RESULT: Lazy<GetItemResult> = Lazy::new(|| GetItemResult {
     stored_value: StoredValue::CLValue(CLValue::from_t(1u64).unwrap()),
     merkle_proof: MERKLE_PROOF.clone(),
 });
+static GET_CONTRACT_SCHEMA_PARAMS: Lazy<GetContractSchemaParams> =
+    Lazy::new(|| GetContractSchemaParams {
+        state_root_hash: *Block::doc_example().header().state_root_hash(),
+        contract_hash: ContractHash::new([1; 32]),
+    });
+static GET_CONTRACT_SCHEMA_RESULT: Lazy<GetContractSchemaResult> = Lazy::new(|| {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::default_with_name("call"));
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(
+        "counter".to_string(),
+        Key::URef(URef::new([2; 32], Default::default())),
+    );
+    let contract = Contract::new(
+        ContractPackageHash::new([3; 32]),
+        ContractWasmHash::new([4; 32]),
+        named_keys,
+        entry_points,
+        DOCS_EXAMPLE_PROTOCOL_VERSION,
+    );
+    GetContractSchemaResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        schema: ContractSchema::from(&contract),
+        merkle_proof: MERKLE_PROOF.clone(),
+    }
+});
 static GET_BALANCE_PARAMS: Lazy<GetBalanceParams> = Lazy::new(|| GetBalanceParams {
     state_root_hash: *Block::doc_example().header().state_root_hash(),
     purse_uref: "uref-09480c3248ef76b603d386f3f4f8a5f87f597d4eaffd475433f861af187ab5db-007"
@@ -66,6 +99,29 @@ static GET_AUCTION_INFO_RESULT: Lazy<GetAuctionInfoResult> = Lazy::new(|| GetAuc
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     auction_state: AuctionState::doc_example().clone(),
 });
+static GET_UNBONDING_PURSES_PARAMS: Lazy<GetUnbondingPursesParams> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([0; 32]).unwrap();
+    let public_key = PublicKey::from(&secret_key);
+    GetUnbondingPursesParams {
+        public_key,
+        block_identifier: Some(BlockIdentifier::Hash(*Block::doc_example().hash())),
+    }
+});
+static GET_UNBONDING_PURSES_RESULT: Lazy<GetUnbondingPursesResult> = Lazy::new(|| {
+    let validator_secret_key = SecretKey::ed25519_from_bytes([1; 32]).unwrap();
+    let unbonding_purse = UnbondingPurse::new(
+        URef::new([2; 32], AccessRights::READ_ADD_WRITE),
+        PublicKey::from(&validator_secret_key),
+        GET_UNBONDING_PURSES_PARAMS.public_key.clone(),
+        EraId::from(42),
+        U512::from(1_000_000),
+        None,
+    );
+    GetUnbondingPursesResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        unbonding_purses: vec![unbonding_purse],
+    }
+});
 static GET_ACCOUNT_INFO_PARAMS: Lazy<GetAccountInfoParams> = Lazy::new(|| {
     let secret_key = SecretKey::ed25519_from_bytes([0; 32]).unwrap();
     let public_key = PublicKey::from(&secret_key);
@@ -201,6 +257,124 @@ impl RpcWithParamsExt for GetItem {
     }
 }
 
+/// Params for "state_get_contract_schema" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetContractSchemaParams {
+    /// Hash of the state root.
+    pub state_root_hash: Digest,
+    /// The hash of the stored contract.
+    pub contract_hash: ContractHash,
+}
+
+impl DocExample for GetContractSchemaParams {
+    fn doc_example() -> &'static Self {
+        &*GET_CONTRACT_SCHEMA_PARAMS
+    }
+}
+
+/// Result for "state_get_contract_schema" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetContractSchemaResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The entry points and named keys of the contract.
+    pub schema: ContractSchema,
+    /// The merkle proof of the stored contract.
+    pub merkle_proof: String,
+}
+
+impl DocExample for GetContractSchemaResult {
+    fn doc_example() -> &'static Self {
+        &*GET_CONTRACT_SCHEMA_RESULT
+    }
+}
+
+/// "state_get_contract_schema" RPC.
+pub struct GetContractSchema {}
+
+impl RpcWithParams for GetContractSchema {
+    const METHOD: &'static str = "state_get_contract_schema";
+    type RequestParams = GetContractSchemaParams;
+    type ResponseResult = GetContractSchemaResult;
+}
+
+impl RpcWithParamsExt for GetContractSchema {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+        api_version: ProtocolVersion,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let contract_hash = params.contract_hash;
+
+            // Run the query.
+            let query_result = effect_builder
+                .make_request(
+                    |responder| RpcRequest::QueryGlobalState {
+                        state_root_hash: params.state_root_hash,
+                        base_key: Key::from(contract_hash),
+                        path: vec![],
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let (value, proofs) = match query_result {
+                Ok(QueryResult::Success { value, proofs }) => (value, proofs),
+                Ok(query_result) => {
+                    let error_msg = format!("state query failed: {:?}", query_result);
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::QueryFailed as i64,
+                        error_msg,
+                    ))?);
+                }
+                Err(error) => {
+                    let error_msg = format!("state query failed to execute: {:?}", error);
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::QueryFailedToExecute as i64,
+                        error_msg,
+                    ))?);
+                }
+            };
+
+            let schema = match &*value {
+                ExecutionEngineStoredValue::Contract(contract) => ContractSchema::from(contract),
+                _ => {
+                    let error_msg = format!("{} is not a stored contract", contract_hash);
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::NoSuchContract as i64,
+                        error_msg,
+                    ))?);
+                }
+            };
+
+            let proof_bytes = match proofs.to_bytes() {
+                Ok(proof_bytes) => proof_bytes,
+                Err(error) => {
+                    info!("failed to encode stored value: {}", error);
+                    return Ok(response_builder.error(warp_json_rpc::Error::INTERNAL_ERROR)?);
+                }
+            };
+
+            let result = Self::ResponseResult {
+                api_version,
+                schema,
+                merkle_proof: hex::encode(proof_bytes),
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
 /// Params for "state_get_balance" RPC request.
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -446,6 +620,124 @@ impl RpcWithOptionalParamsExt for GetAuctionInfo {
     }
 }
 
+/// Params for "state_get_unbonding_purses" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetUnbondingPursesParams {
+    /// The public key of the validator or delegator which is unbonding.
+    pub public_key: PublicKey,
+    /// The block identifier.
+    pub block_identifier: Option<BlockIdentifier>,
+}
+
+impl DocExample for GetUnbondingPursesParams {
+    fn doc_example() -> &'static Self {
+        &*GET_UNBONDING_PURSES_PARAMS
+    }
+}
+
+/// Result for "state_get_unbonding_purses" RPC response.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetUnbondingPursesResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The pending unbonding purses of the given public key.
+    pub unbonding_purses: Vec<UnbondingPurse>,
+}
+
+impl DocExample for GetUnbondingPursesResult {
+    fn doc_example() -> &'static Self {
+        &*GET_UNBONDING_PURSES_RESULT
+    }
+}
+
+/// "state_get_unbonding_purses" RPC.
+pub struct GetUnbondingPurses {}
+
+impl RpcWithParams for GetUnbondingPurses {
+    const METHOD: &'static str = "state_get_unbonding_purses";
+    type RequestParams = GetUnbondingPursesParams;
+    type ResponseResult = GetUnbondingPursesResult;
+}
+
+impl RpcWithParamsExt for GetUnbondingPurses {
+    fn handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        response_builder: Builder,
+        params: Self::RequestParams,
+        api_version: ProtocolVersion,
+    ) -> BoxFuture<'static, Result<Response<Body>, Error>> {
+        async move {
+            let block = match chain::get_block(params.block_identifier, effect_builder).await {
+                Ok(Some(block)) => block,
+                Ok(None) => {
+                    let error_msg = "get-unbonding-purses failed to get block".to_string();
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::NoSuchBlock as i64,
+                        error_msg,
+                    ))?);
+                }
+                Err(error) => return Ok(response_builder.error(error)?),
+            };
+
+            let state_root_hash = *block.header().state_root_hash();
+
+            let get_withdraws_result = effect_builder
+                .make_request(
+                    |responder| RpcRequest::GetWithdraws {
+                        state_root_hash,
+                        responder,
+                    },
+                    QueueKind::Api,
+                )
+                .await;
+
+            let withdraws = match get_withdraws_result {
+                Ok(GetWithdrawsResult::Success { withdraws }) => withdraws,
+                Ok(GetWithdrawsResult::RootNotFound) => {
+                    let error_msg = format!(
+                        "get-unbonding-purses failed to find state root hash {}",
+                        state_root_hash
+                    );
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::QueryFailed as i64,
+                        error_msg,
+                    ))?);
+                }
+                Err(error) => {
+                    let error_msg = format!("get-unbonding-purses failed to execute: {}", error);
+                    info!("{}", error_msg);
+                    return Ok(response_builder.error(warp_json_rpc::Error::custom(
+                        ErrorCode::QueryFailedToExecute as i64,
+                        error_msg,
+                    ))?);
+                }
+            };
+
+            // Unbonding purses are stored under the account of the validator they were bonded
+            // to, so the purses of a delegator may be spread across several validators.
+            let unbonding_purses = withdraws
+                .into_iter()
+                .flat_map(|(_, unbonding_purses)| unbonding_purses)
+                .filter(|unbonding_purse| {
+                    *unbonding_purse.unbonder_public_key() == params.public_key
+                })
+                .collect();
+
+            let result = Self::ResponseResult {
+                api_version,
+                unbonding_purses,
+            };
+            Ok(response_builder.success(result)?)
+        }
+        .boxed()
+    }
+}
+
 /// Params for "state_get_account_info" RPC request
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -23,7 +23,10 @@
 //! Nodes gossip their public listening addresses periodically, and will try to establish and
 //! maintain an outgoing connection to any new address learned.
 
+mod blocklist;
+mod capabilities;
 mod chain_info;
+mod compression;
 mod config;
 mod counting_format;
 mod error;
@@ -37,13 +40,16 @@ mod symmetry;
 pub(crate) mod tasks;
 #[cfg(test)]
 mod tests;
+mod traffic_recorder;
 
 use std::{
+    borrow::Cow,
     collections::{BTreeMap, HashMap, HashSet},
     convert::Infallible,
     fmt::{self, Debug, Display, Formatter},
     io, mem,
     net::{SocketAddr, TcpListener},
+    path::PathBuf,
     result,
     sync::{Arc, Weak},
     time::{Duration, Instant},
@@ -52,6 +58,7 @@ use std::{
 use casper_types::{EraId, PublicKey};
 use datasize::DataSize;
 use futures::{future::BoxFuture, FutureExt};
+use once_cell::sync::OnceCell;
 use openssl::{error::ErrorStack as OpenSslErrorStack, pkey};
 use pkey::{PKey, Private};
 use prometheus::Registry;
@@ -71,6 +78,8 @@ use tokio_util::codec::LengthDelimitedCodec;
 use tracing::{debug, error, info, trace, warn, Instrument, Span};
 
 use self::{
+    blocklist::Blocklist,
+    compression::Compression,
     counting_format::{ConnectionId, CountingFormat, Role},
     error::{ConnectionError, Result},
     event::{IncomingConnection, OutgoingConnection},
@@ -80,11 +89,19 @@ use self::{
     outgoing::{DialOutcome, DialRequest, OutgoingConfig, OutgoingManager},
     symmetry::ConnectionSymmetry,
     tasks::NetworkContext,
+    traffic_recorder::TrafficRecorder,
+};
+pub use self::{
+    blocklist::{Offense, PeerReputation},
+    capabilities::{Capabilities, Capability},
+    compression::CompressionAlgorithm,
+    traffic_recorder::{CaptureError, Direction},
 };
 pub(crate) use self::{
     event::Event,
     gossiped_address::GossipedAddress,
     message::{Message, MessageKind, Payload},
+    traffic_recorder::{capture_files, CaptureReader, CapturedMessage},
 };
 use super::consensus;
 use crate::{
@@ -93,18 +110,20 @@ use crate::{
         announcements::{BlocklistAnnouncement, LinearChainAnnouncement, NetworkAnnouncement},
         requests::{
             ChainspecLoaderRequest, ContractRuntimeRequest, NetworkInfoRequest, NetworkRequest,
-            StorageRequest,
+            StateStoreRequest, StorageRequest,
         },
         EffectBuilder, EffectExt, Effects,
     },
     reactor::{EventQueueHandle, Finalize, ReactorEvent},
     tls::{self, TlsCert, ValidationError},
-    types::NodeId,
+    types::{NodeId, Timestamp},
     utils::{self, display_error, WithDir},
     NodeRng,
 };
 use chain_info::ChainInfo;
-pub use config::Config;
+pub use config::{
+    BlocklistConfig, CompressionConfig, CompressionThresholds, Config, TrafficRecorderConfig,
+};
 pub use error::Error;
 
 const MAX_ASYMMETRIC_TIME: Duration = Duration::from_secs(60);
@@ -151,6 +170,8 @@ where
 {
     /// Initial configuration values.
     cfg: Config,
+    /// The capture to replay instead of connecting to peers, resolved relative to the config file.
+    replay_capture: Option<PathBuf>,
     /// Read-only networking information shared across tasks.
     context: Arc<NetworkContext<REv>>,
 
@@ -158,6 +179,8 @@ where
     outgoing_manager: OutgoingManager<OutgoingHandle<P>, ConnectionError>,
     /// Tracks whether a connection is symmetric or not.
     connection_symmetries: HashMap<NodeId, ConnectionSymmetry>,
+    /// Reputations of misbehaving peers, including those banned by node ID.
+    blocklist: Blocklist,
 
     /// Channel signaling a shutdown of the small network.
     // Note: This channel is closed when `SmallNetwork` is dropped, signalling the receivers that
@@ -201,19 +224,28 @@ where
         + From<NetworkAnnouncement<NodeId, P>>
         + From<ContractRuntimeRequest>
         + From<StorageRequest>
+        + From<StateStoreRequest>
         + From<ChainspecLoaderRequest>,
 {
     /// Creates a new small network component instance.
     #[allow(clippy::type_complexity)]
     pub(crate) fn new<C: Into<ChainInfo>>(
         event_queue: EventQueueHandle<REv>,
-        cfg: Config,
+        cfg: WithDir<Config>,
         consensus_cfg: Option<WithDir<&consensus::Config>>,
         registry: &Registry,
         small_network_identity: SmallNetworkIdentity,
         chain_info_source: C,
         initial_era: Option<EraId>,
     ) -> Result<(SmallNetwork<REv, P>, Effects<Event<P>>)> {
+        let replay_capture = cfg
+            .value()
+            .replay_capture
+            .clone()
+            .map(|path| cfg.with_dir(path));
+        let traffic_capture_dir = cfg.with_dir(cfg.value().traffic_recorder.path.clone());
+        let (_, cfg) = cfg.into_parts();
+
         let mut known_addresses = HashSet::new();
         for address in &cfg.known_addresses {
             match utils::resolve_address(address) {
@@ -228,8 +260,12 @@ where
             }
         }
 
-        // Assert we have at least one known address in the config.
-        if known_addresses.is_empty() {
+        // A replaying node does not connect to any peers.
+        if replay_capture.is_some() {
+            info!("replaying captured traffic, ignoring known addresses");
+            known_addresses.clear();
+        } else if known_addresses.is_empty() {
+            // Assert we have at least one known address in the config.
             warn!("no known addresses provided via config or all failed DNS resolution");
             return Err(Error::EmptyKnownHosts);
         }
@@ -289,6 +325,28 @@ where
             .map_err(Error::LoadConsensusKeys)?
             .map(|(secret_key, public_key)| ConsensusKeyPair::new(secret_key, public_key));
 
+        let mut capabilities = Capabilities::default();
+        if !cfg.compression.algorithms.is_empty() {
+            capabilities.insert(Capability::Compression);
+            for algorithm in &cfg.compression.algorithms {
+                capabilities.insert(algorithm.capability());
+            }
+        }
+
+        let traffic_recorder = if cfg.traffic_recorder.enabled {
+            info!(dir = %traffic_capture_dir.display(), "recording network traffic");
+            Some(
+                TrafficRecorder::start(
+                    traffic_capture_dir,
+                    &cfg.traffic_recorder,
+                    net_metrics.traffic_capture_dropped_records.clone(),
+                )
+                .map_err(Error::TrafficRecorder)?,
+            )
+        } else {
+            None
+        };
+
         let context = Arc::new(NetworkContext {
             event_queue,
             our_id: NodeId::from(&small_network_identity),
@@ -298,29 +356,38 @@ where
             chain_info: chain_info_source.into(),
             public_addr,
             consensus_keys,
+            compression: cfg.compression.clone(),
+            capabilities,
+            traffic_recorder,
         });
 
-        // Run the server task.
+        // Run the server task, unless replaying, as a replaying node must not accept connections
+        // either.
         // We spawn it ourselves instead of through an effect to get a hold of the join handle,
         // which we need to shutdown cleanly later on.
-        info!(%local_addr, %public_addr, "starting server background task");
-
         let (server_shutdown_sender, server_shutdown_receiver) = watch::channel(());
         let shutdown_receiver = server_shutdown_receiver.clone();
-        let server_join_handle = tokio::spawn(tasks::server(
-            context.clone(),
-            tokio::net::TcpListener::from_std(listener).map_err(Error::ListenerConversion)?,
-            server_shutdown_receiver,
-        ));
+        let server_join_handle = if replay_capture.is_none() {
+            info!(%local_addr, %public_addr, "starting server background task");
+            Some(tokio::spawn(tasks::server(
+                context.clone(),
+                tokio::net::TcpListener::from_std(listener).map_err(Error::ListenerConversion)?,
+                server_shutdown_receiver,
+            )))
+        } else {
+            None
+        };
 
         let mut component = SmallNetwork {
             cfg,
+            replay_capture,
             context,
             outgoing_manager,
             connection_symmetries: HashMap::new(),
+            blocklist: Blocklist::default(),
             shutdown_sender: Some(server_shutdown_sender),
             shutdown_receiver,
-            server_join_handle: Some(server_join_handle),
+            server_join_handle,
             net_metrics,
             highest_era_seen: EraId::new(0),
             outgoing_limiter,
@@ -343,6 +410,15 @@ where
 
         effects.extend(component.process_dial_requests(dial_requests));
 
+        // Restore the blocklist persisted by a previous run.
+        effects.extend(
+            effect_builder
+                .load_state(component.blocklist_state_key())
+                .event(|maybe_blocklist| Event::BlocklistLoaded {
+                    maybe_blocklist: Box::new(maybe_blocklist),
+                }),
+        );
+
         // Start broadcasting our public listening address.
         effects.extend(
             effect_builder
@@ -360,6 +436,21 @@ where
         Ok((component, effects))
     }
 
+    /// Replays the incoming messages of the capture given in the `replay_capture` config option.
+    ///
+    /// Only called by the participating reactor, so a replaying node stays isolated while joining.
+    pub(crate) fn replay_capture(&self) -> Effects<Event<P>> {
+        match self.replay_capture {
+            Some(ref path) => tasks::replay_capture(
+                self.context.clone(),
+                path.clone(),
+                self.shutdown_receiver.clone(),
+            )
+            .ignore(),
+            None => Effects::new(),
+        }
+    }
+
     /// Queues a message to be sent to all nodes.
     fn broadcast_message(&self, msg: Arc<Message<P>>) {
         for peer_id in self.outgoing_manager.connected_peers() {
@@ -434,16 +525,19 @@ where
             }
             IncomingConnection::Failed {
                 peer_addr: _,
-                peer_id: _,
+                peer_id,
                 ref error,
             } => {
-                // TODO: At this point, we could consider blocking peers by [`PeerID`], but this
-                //       feature is not implemented yet.
                 debug!(
                     err = display_error(error),
                     "incoming connection failed after TLS setup"
                 );
-                Effects::new()
+                // The same offenses that get outgoing addresses blocked count against the peer.
+                if self.is_blockable_offense_for_outgoing(error) {
+                    self.handle_offense(effect_builder, peer_id, Offense::ProtocolViolation)
+                } else {
+                    Effects::new()
+                }
             }
             IncomingConnection::Loopback => {
                 // Loopback connections are closed immediately, but will be marked as such by the
@@ -459,6 +553,12 @@ where
                 peer_consensus_public_key,
                 stream,
             } => {
+                if self.blocklist.is_banned(&peer_id, Timestamp::now()) {
+                    // Dropping the stream closes the connection.
+                    info!("dropping incoming connection from banned peer");
+                    return Effects::new();
+                }
+
                 info!("new incoming connection established");
 
                 // Learn the address the peer gave us.
@@ -603,9 +703,10 @@ where
                 peer_addr,
                 peer_id,
                 peer_consensus_public_key,
+                peer_capabilities,
                 sink,
             } => {
-                info!("new outgoing connection established");
+                info!(%peer_capabilities, "new outgoing connection established");
 
                 let (sender, receiver) = mpsc::unbounded_channel();
                 let handle = OutgoingHandle { peer_addr, sender };
@@ -616,8 +717,19 @@ where
                         addr: peer_addr,
                         handle,
                         node_id: peer_id,
+                        capabilities: peer_capabilities,
                     });
 
+                if self.blocklist.is_banned(&peer_id, Timestamp::now()) {
+                    // Blocking the address disconnects again, dropping the sink closes the
+                    // connection.
+                    info!("blocking outgoing connection to banned peer");
+                    let requests = request
+                        .into_iter()
+                        .chain(self.outgoing_manager.block_addr(peer_addr, now));
+                    return self.process_dial_requests(requests);
+                }
+
                 let mut effects = self.process_dial_requests(request);
 
                 // Update connection symmetries.
@@ -637,6 +749,8 @@ where
                         self.outgoing_limiter
                             .create_handle(peer_id, peer_consensus_public_key),
                         self.net_metrics.queued_messages.clone(),
+                        peer_id,
+                        self.context.traffic_recorder.clone(),
                     )
                     .instrument(span)
                     .event(move |_| Event::OutgoingDropped {
@@ -746,13 +860,17 @@ where
     where
         REv: From<NetworkAnnouncement<NodeId, P>>,
     {
+        if self.blocklist.is_banned(&peer_id, Timestamp::now()) {
+            span.in_scope(|| debug!("dropping message from banned peer"));
+            return Effects::new();
+        }
+
         span.in_scope(|| match msg {
             Message::Handshake { .. } => {
-                // We should never receive a handshake message on an established connection. Simply
-                // discard it. This may be too lenient, so we may consider simply dropping the
-                // connection in the future instead.
+                // We should never receive a handshake message on an established connection. Discard
+                // it and count it against the peer's reputation.
                 warn!("received unexpected handshake");
-                Effects::new()
+                self.handle_offense(effect_builder, peer_id, Offense::ProtocolViolation)
             }
             Message::Payload(payload) => effect_builder
                 .announce_message_received(peer_id, payload)
@@ -815,6 +933,62 @@ where
         )
     }
 
+    /// Returns the key under which the blocklist is persisted.
+    fn blocklist_state_key(&self) -> Cow<'static, [u8]> {
+        format!(
+            "small_network_blocklist:network_name={}",
+            self.context.chain_info.network_name
+        )
+        .into_bytes()
+        .into()
+    }
+
+    /// Persists the blocklist, so bans survive a restart.
+    fn save_blocklist(&self, effect_builder: EffectBuilder<REv>) -> Effects<Event<P>> {
+        effect_builder
+            .save_state(self.blocklist_state_key(), self.blocklist.clone())
+            .ignore()
+    }
+
+    /// Records an offense committed by a peer, banning it if its reputation dropped too low.
+    fn handle_offense(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        offender: NodeId,
+        offense: Offense,
+    ) -> Effects<Event<P>> {
+        let now = Timestamp::now();
+        match self
+            .blocklist
+            .record_offense(offender, offense, &self.cfg.blocklist, now)
+        {
+            Some(until) => {
+                warn!(%offender, %offense, %until, "banning peer after transgression");
+                let mut effects = self.disconnect_banned_peer(offender);
+                effects.extend(self.save_blocklist(effect_builder));
+                effects
+            }
+            None => {
+                debug!(%offender, %offense, "peer committed offense");
+                Effects::new()
+            }
+        }
+    }
+
+    /// Disconnects from a banned peer.
+    ///
+    /// Only the outgoing connection is closed, messages received on incoming connections are
+    /// discarded for as long as the ban lasts.
+    fn disconnect_banned_peer(&mut self, peer_id: NodeId) -> Effects<Event<P>> {
+        match self.outgoing_manager.get_addr(peer_id) {
+            Some(addr) => {
+                let requests = self.outgoing_manager.block_addr(addr, Instant::now());
+                self.process_dial_requests(requests)
+            }
+            None => Effects::new(),
+        }
+    }
+
     /// Emits an announcement that a connection has been completed.
     fn connection_completed(
         &self,
@@ -890,6 +1064,7 @@ where
         + From<NetworkAnnouncement<NodeId, P>>
         + From<ContractRuntimeRequest>
         + From<StorageRequest>
+        + From<StateStoreRequest>
         + From<ChainspecLoaderRequest>,
     P: Payload,
 {
@@ -963,6 +1138,39 @@ where
                 NetworkInfoRequest::GetPeers { responder } => {
                     responder.respond(self.peers()).ignore()
                 }
+                NetworkInfoRequest::GetPeerCapabilities { peer, responder } => responder
+                    .respond(self.outgoing_manager.get_capabilities(*peer))
+                    .ignore(),
+                NetworkInfoRequest::GetBlocklist { responder } => responder
+                    .respond(
+                        self.blocklist
+                            .reputations(&self.cfg.blocklist, Timestamp::now()),
+                    )
+                    .ignore(),
+                NetworkInfoRequest::BanPeer {
+                    peer,
+                    duration,
+                    responder,
+                } => {
+                    let now = Timestamp::now();
+                    let until = self.blocklist.ban(
+                        *peer,
+                        now + duration.unwrap_or(self.cfg.blocklist.ban_duration),
+                        now,
+                    );
+                    info!(%peer, %until, "banning peer on request");
+                    let mut effects = self.disconnect_banned_peer(*peer);
+                    effects.extend(self.save_blocklist(effect_builder));
+                    effects.extend(responder.respond(Some(until)).ignore());
+                    effects
+                }
+                NetworkInfoRequest::UnbanPeer { peer, responder } => {
+                    let was_banned = self.blocklist.unban(&peer, Timestamp::now());
+                    info!(%peer, %was_banned, "unbanning peer on request");
+                    let mut effects = self.save_blocklist(effect_builder);
+                    effects.extend(responder.respond(was_banned).ignore());
+                    effects
+                }
             },
             Event::PeerAddressReceived(gossiped_address) => {
                 let requests = self.outgoing_manager.learn_addr(
@@ -972,18 +1180,24 @@ where
                 );
                 self.process_dial_requests(requests)
             }
-            Event::BlocklistAnnouncement(BlocklistAnnouncement::OffenseCommitted(peer_id)) => {
-                // TODO: We do not have a proper by-node-ID blocklist, but rather only block the
-                // current outgoing address of a peer.
-                warn!(%peer_id, "adding peer to blocklist after transgression");
-
-                if let Some(addr) = self.outgoing_manager.get_addr(*peer_id) {
-                    let requests = self.outgoing_manager.block_addr(addr, Instant::now());
-                    self.process_dial_requests(requests)
-                } else {
-                    // Peer got away with it, no longer an outgoing connection.
-                    Effects::new()
+            Event::BlocklistAnnouncement(BlocklistAnnouncement::OffenseCommitted {
+                offender,
+                offense,
+            }) => self.handle_offense(effect_builder, *offender, offense),
+            Event::BlocklistLoaded { maybe_blocklist } => {
+                let mut effects = Effects::new();
+                if let Some(blocklist) = *maybe_blocklist {
+                    let now = Timestamp::now();
+                    self.blocklist.merge(blocklist);
+                    self.blocklist.purge(&self.cfg.blocklist, now);
+
+                    let banned_peers: Vec<_> = self.blocklist.banned_peers(now).collect();
+                    info!(count = banned_peers.len(), "restored banned peers");
+                    for peer_id in banned_peers {
+                        effects.extend(self.disconnect_banned_peer(peer_id));
+                    }
                 }
+                effects
             }
 
             Event::GossipOurAddress => {
@@ -1014,6 +1228,9 @@ where
                 let requests = self.outgoing_manager.perform_housekeeping(now);
                 let mut effects = self.process_dial_requests(requests);
 
+                // Piggyback on the sweep to forget peers whose reputation has recovered.
+                self.blocklist.purge(&self.cfg.blocklist, Timestamp::now());
+
                 effects.extend(
                     effect_builder
                         .set_timeout(OUTGOING_MANAGER_SWEEP_INTERVAL)
@@ -1126,6 +1343,7 @@ fn framed<P>(
     stream: Transport,
     role: Role,
     maximum_net_message_size: u32,
+    compression: Arc<OnceCell<Compression>>,
 ) -> FramedTransport<P>
 where
     for<'de> P: Serialize + Deserialize<'de>,
@@ -1140,7 +1358,7 @@ where
 
     tokio_serde::Framed::new(
         length_delimited,
-        CountingFormat::new(metrics, connection_id, role, MessagePackFormat),
+        CountingFormat::new(metrics, connection_id, role, MessagePackFormat, compression),
     )
 }
 
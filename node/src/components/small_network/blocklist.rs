@@ -0,0 +1,319 @@
+//! Node ID based peer blocklist.
+//!
+//! Every offense a peer commits, e.g. sending invalid consensus values or deploys or failing to
+//! answer fetch requests, adds a penalty to its reputation score. Scores recover over time, but
+//! once a peer's score reaches the configured threshold it is banned by its node ID for a
+//! configurable duration. Unlike blocked addresses, bans apply to all connections of a peer and
+//! are persisted across restarts.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+};
+
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+use super::config::BlocklistConfig;
+use crate::types::{NodeId, TimeDiff, Timestamp};
+
+/// An offense committed by a peer, lowering its reputation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Offense {
+    /// The peer violated the networking or consensus protocol.
+    ProtocolViolation,
+    /// The peer sent an invalid consensus vertex or proposed an invalid block.
+    InvalidConsensusValue,
+    /// The peer sent an invalid deploy.
+    InvalidDeploy,
+    /// The peer did not answer a fetch request in time.
+    FetchTimeout,
+}
+
+impl Offense {
+    /// Returns the amount the offender's reputation score is increased by.
+    fn penalty(self) -> u32 {
+        match self {
+            Offense::ProtocolViolation | Offense::InvalidConsensusValue => 100,
+            Offense::InvalidDeploy => 20,
+            Offense::FetchTimeout => 5,
+        }
+    }
+}
+
+impl Display for Offense {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Offense::ProtocolViolation => write!(f, "protocol violation"),
+            Offense::InvalidConsensusValue => write!(f, "invalid consensus value"),
+            Offense::InvalidDeploy => write!(f, "invalid deploy"),
+            Offense::FetchTimeout => write!(f, "fetch timeout"),
+        }
+    }
+}
+
+/// The reputation of a single peer.
+#[derive(Copy, Clone, DataSize, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerReputation {
+    /// The reputation score as of `last_updated`. Higher scores are worse.
+    score: u32,
+    /// The last time the score was updated.
+    last_updated: Timestamp,
+    /// The end of the ban, if the peer has been banned.
+    banned_until: Option<Timestamp>,
+}
+
+impl PeerReputation {
+    fn new(now: Timestamp) -> Self {
+        PeerReputation {
+            score: 0,
+            last_updated: now,
+            banned_until: None,
+        }
+    }
+
+    /// Returns the reputation score. Higher scores are worse.
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    /// Returns the time until which the peer is banned, if the ban is still in effect at `now`.
+    pub fn banned_until(&self, now: Timestamp) -> Option<Timestamp> {
+        self.banned_until.filter(|until| *until > now)
+    }
+
+    /// Reduces the score by one for every `decay_interval` passed since the last update.
+    fn decay(&mut self, decay_interval: TimeDiff, now: Timestamp) {
+        if decay_interval.millis() == 0 {
+            self.last_updated = now;
+            return;
+        }
+        let recovered = now.saturating_diff(self.last_updated).millis() / decay_interval.millis();
+        if recovered >= u64::from(self.score) {
+            self.score = 0;
+            self.last_updated = now;
+        } else {
+            self.score -= recovered as u32;
+            self.last_updated += decay_interval * recovered;
+        }
+    }
+}
+
+/// The reputations of all peers which committed offenses recently or are banned.
+#[derive(Clone, DataSize, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Blocklist {
+    peers: BTreeMap<NodeId, PeerReputation>,
+}
+
+impl Blocklist {
+    /// Records an offense committed by `peer`.
+    ///
+    /// Returns the end of the ban if the offense caused the peer to be banned.
+    pub(super) fn record_offense(
+        &mut self,
+        peer: NodeId,
+        offense: Offense,
+        config: &BlocklistConfig,
+        now: Timestamp,
+    ) -> Option<Timestamp> {
+        let reputation = self
+            .peers
+            .entry(peer)
+            .or_insert_with(|| PeerReputation::new(now));
+        reputation.decay(config.score_decay_interval, now);
+        reputation.score = reputation.score.saturating_add(offense.penalty());
+
+        if reputation.score < config.ban_threshold || reputation.banned_until(now).is_some() {
+            return None;
+        }
+        let until = now + config.ban_duration;
+        reputation.banned_until = Some(until);
+        Some(until)
+    }
+
+    /// Returns whether `peer` is banned at `now`.
+    pub(super) fn is_banned(&self, peer: &NodeId, now: Timestamp) -> bool {
+        self.peers
+            .get(peer)
+            .and_then(|reputation| reputation.banned_until(now))
+            .is_some()
+    }
+
+    /// Bans `peer` until the given time, regardless of its score.
+    ///
+    /// An existing ban is only ever extended, never shortened. Returns the end of the ban.
+    pub(super) fn ban(&mut self, peer: NodeId, until: Timestamp, now: Timestamp) -> Timestamp {
+        let reputation = self
+            .peers
+            .entry(peer)
+            .or_insert_with(|| PeerReputation::new(now));
+        match reputation.banned_until(now) {
+            Some(current) if current >= until => current,
+            _ => {
+                reputation.banned_until = Some(until);
+                until
+            }
+        }
+    }
+
+    /// Lifts the ban on `peer` and resets its score.
+    ///
+    /// Returns `true` if the peer was banned.
+    pub(super) fn unban(&mut self, peer: &NodeId, now: Timestamp) -> bool {
+        self.peers
+            .remove(peer)
+            .and_then(|reputation| reputation.banned_until(now))
+            .is_some()
+    }
+
+    /// Adds all peers from `other` which are not already known.
+    ///
+    /// Used to combine the persisted blocklist with offenses recorded before it was loaded.
+    pub(super) fn merge(&mut self, other: Blocklist) {
+        for (peer, reputation) in other.peers {
+            self.peers.entry(peer).or_insert(reputation);
+        }
+    }
+
+    /// Returns all peers banned at `now`.
+    pub(super) fn banned_peers(&self, now: Timestamp) -> impl Iterator<Item = NodeId> + '_ {
+        self.peers
+            .iter()
+            .filter(move |(_, reputation)| reputation.banned_until(now).is_some())
+            .map(|(peer, _)| *peer)
+    }
+
+    /// Updates all scores and removes peers which are neither banned nor have a positive score.
+    pub(super) fn purge(&mut self, config: &BlocklistConfig, now: Timestamp) {
+        self.peers.retain(|_, reputation| {
+            reputation.decay(config.score_decay_interval, now);
+            reputation.score > 0 || reputation.banned_until(now).is_some()
+        });
+    }
+
+    /// Returns the up-to-date reputations of all peers.
+    pub(super) fn reputations(
+        &self,
+        config: &BlocklistConfig,
+        now: Timestamp,
+    ) -> BTreeMap<NodeId, PeerReputation> {
+        let mut blocklist = self.clone();
+        blocklist.purge(config, now);
+        blocklist.peers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> BlocklistConfig {
+        BlocklistConfig {
+            ban_threshold: 100,
+            ban_duration: TimeDiff::from_seconds(3600),
+            score_decay_interval: TimeDiff::from_seconds(60),
+        }
+    }
+
+    #[test]
+    fn should_ban_after_reaching_threshold() {
+        let mut rng = crate::new_rng();
+        let peer = NodeId::random_tls(&mut rng);
+        let config = config();
+        let now = Timestamp::from(1_000_000);
+        let mut blocklist = Blocklist::default();
+
+        for _ in 0..4 {
+            assert!(blocklist
+                .record_offense(peer, Offense::InvalidDeploy, &config, now)
+                .is_none());
+        }
+        assert!(!blocklist.is_banned(&peer, now));
+
+        let until = blocklist
+            .record_offense(peer, Offense::InvalidDeploy, &config, now)
+            .expect("should be banned");
+        assert_eq!(until, now + config.ban_duration);
+        assert!(blocklist.is_banned(&peer, now));
+        assert!(!blocklist.is_banned(&peer, until));
+
+        // A further offense while banned does not extend the ban.
+        assert!(blocklist
+            .record_offense(peer, Offense::ProtocolViolation, &config, now)
+            .is_none());
+    }
+
+    #[test]
+    fn should_ban_immediately_for_protocol_violation() {
+        let mut rng = crate::new_rng();
+        let peer = NodeId::random_tls(&mut rng);
+        let config = config();
+        let now = Timestamp::from(1_000_000);
+        let mut blocklist = Blocklist::default();
+
+        assert!(blocklist
+            .record_offense(peer, Offense::ProtocolViolation, &config, now)
+            .is_some());
+        assert_eq!(blocklist.banned_peers(now).collect::<Vec<_>>(), vec![peer]);
+    }
+
+    #[test]
+    fn scores_should_decay() {
+        let mut rng = crate::new_rng();
+        let peer = NodeId::random_tls(&mut rng);
+        let config = config();
+        let mut now = Timestamp::from(1_000_000);
+        let mut blocklist = Blocklist::default();
+
+        blocklist.record_offense(peer, Offense::InvalidDeploy, &config, now);
+        now += config.score_decay_interval * 5;
+        assert_eq!(blocklist.reputations(&config, now)[&peer].score(), 15);
+
+        now += config.score_decay_interval * 15;
+        assert!(blocklist.reputations(&config, now).is_empty());
+        blocklist.purge(&config, now);
+        assert_eq!(blocklist, Blocklist::default());
+    }
+
+    #[test]
+    fn should_ban_and_unban_manually() {
+        let mut rng = crate::new_rng();
+        let peer = NodeId::random_tls(&mut rng);
+        let config = config();
+        let now = Timestamp::from(1_000_000);
+        let mut blocklist = Blocklist::default();
+
+        blocklist.ban(peer, now + config.ban_duration, now);
+        assert!(blocklist.is_banned(&peer, now));
+
+        // Shorter bans do not override longer ones.
+        assert_eq!(
+            blocklist.ban(peer, now + TimeDiff::from_seconds(1), now),
+            now + config.ban_duration
+        );
+        assert!(blocklist.is_banned(&peer, now + TimeDiff::from_seconds(10)));
+
+        assert!(blocklist.unban(&peer, now));
+        assert!(!blocklist.is_banned(&peer, now));
+        assert!(!blocklist.unban(&peer, now));
+    }
+
+    #[test]
+    fn should_roundtrip_through_bincode() {
+        let mut rng = crate::new_rng();
+        let config = config();
+        let now = Timestamp::from(1_000_000);
+        let mut blocklist = Blocklist::default();
+        blocklist.record_offense(
+            NodeId::random(&mut rng),
+            Offense::FetchTimeout,
+            &config,
+            now,
+        );
+        blocklist.ban(NodeId::random(&mut rng), now + config.ban_duration, now);
+
+        let serialized = bincode::serialize(&blocklist).unwrap();
+        let deserialized: Blocklist = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(blocklist, deserialized);
+    }
+}
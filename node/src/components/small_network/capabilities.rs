@@ -0,0 +1,115 @@
+//! Optional features of the networking protocol.
+//!
+//! Nodes announce the capabilities they support in their handshake. Capabilities unknown to a node
+//! are ignored, so new optional features, e.g. additional kinds of messages, can be introduced
+//! without a hard fork. Components must check whether a peer has the required capability before
+//! sending it an optional message.
+
+use std::fmt::{self, Display, Formatter};
+
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+/// An optional feature of the networking protocol.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Capability {
+    /// The node supports compressed messages.
+    Compression,
+    /// The node can decompress messages compressed using zstd.
+    Zstd,
+    /// The node can decompress messages compressed using lz4.
+    Lz4,
+}
+
+impl Capability {
+    /// All capabilities known to this node.
+    const ALL: [Capability; 3] = [Capability::Compression, Capability::Zstd, Capability::Lz4];
+
+    /// Returns the bit representing the capability in a set of capabilities.
+    ///
+    /// Bits must never be reassigned, as they are part of the handshake.
+    fn bit(self) -> u64 {
+        match self {
+            Capability::Compression => 1,
+            Capability::Zstd => 1 << 1,
+            Capability::Lz4 => 1 << 2,
+        }
+    }
+}
+
+impl Display for Capability {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Capability::Compression => f.write_str("compression"),
+            Capability::Zstd => f.write_str("zstd"),
+            Capability::Lz4 => f.write_str("lz4"),
+        }
+    }
+}
+
+/// A set of capabilities.
+///
+/// Encoded as a bit field, leaving bits of capabilities unknown to this node untouched.
+#[derive(Copy, Clone, DataSize, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Capabilities(u64);
+
+impl Capabilities {
+    /// Adds a capability to the set.
+    pub fn insert(&mut self, capability: Capability) {
+        self.0 |= capability.bit();
+    }
+
+    /// Returns whether the set contains the given capability.
+    pub fn contains(&self, capability: Capability) -> bool {
+        self.0 & capability.bit() != 0
+    }
+
+    /// Iterates over all capabilities in the set known to this node.
+    pub fn iter(&self) -> impl Iterator<Item = Capability> + '_ {
+        Capability::ALL
+            .iter()
+            .copied()
+            .filter(move |capability| self.contains(*capability))
+    }
+}
+
+impl Display for Capabilities {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        for (index, capability) in self.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", capability)?;
+        }
+        f.write_str("]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_ignore_unknown_capabilities() {
+        let from_future_node = Capabilities(Capability::Compression.bit() | 1 << 63);
+
+        assert!(from_future_node.contains(Capability::Compression));
+        assert_eq!(
+            from_future_node.iter().collect::<Vec<_>>(),
+            vec![Capability::Compression]
+        );
+        assert_eq!(from_future_node.to_string(), "[compression]");
+    }
+
+    #[test]
+    fn should_insert_capabilities() {
+        let mut capabilities = Capabilities::default();
+        assert!(!capabilities.contains(Capability::Compression));
+        assert_eq!(capabilities.to_string(), "[]");
+
+        capabilities.insert(Capability::Compression);
+        assert!(capabilities.contains(Capability::Compression));
+    }
+}
@@ -9,6 +9,7 @@ use casper_types::ProtocolVersion;
 use datasize::DataSize;
 
 use super::{
+    capabilities::Capabilities,
     counting_format::ConnectionId,
     message::{ConsensusCertificate, ConsensusKeyPair},
     Message,
@@ -46,6 +47,7 @@ impl ChainInfo {
         public_addr: SocketAddr,
         consensus_keys: Option<&ConsensusKeyPair>,
         connection_id: ConnectionId,
+        capabilities: Capabilities,
     ) -> Message<P> {
         Message::Handshake {
             network_name: self.network_name.clone(),
@@ -53,6 +55,7 @@ impl ChainInfo {
             protocol_version: self.protocol_version,
             consensus_certificate: consensus_keys
                 .map(|key_pair| ConsensusCertificate::create(connection_id, key_pair)),
+            capabilities,
         }
     }
 }
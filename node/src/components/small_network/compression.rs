@@ -0,0 +1,331 @@
+//! Compression of network messages.
+//!
+//! Nodes supporting compressed messages announce the `Compression` capability in their handshake,
+//! along with a capability for each algorithm they support. If both sides of a connection announce
+//! the capability, every message following the handshake is prefixed with a single byte identifying
+//! the algorithm its remainder was compressed with, if any. The sender picks its most preferred
+//! algorithm supported by the receiver, but only compresses messages reaching the size threshold
+//! configured for their kind. Peers which do not announce the capability, e.g. because they run an
+//! older version, are sent messages without a prefix.
+
+use std::{
+    convert::TryInto,
+    fmt::{self, Display, Formatter},
+    io,
+};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    capabilities::{Capabilities, Capability},
+    config::{CompressionConfig, CompressionThresholds},
+    MessageKind,
+};
+
+/// Prefix of messages sent uncompressed.
+const UNCOMPRESSED_TAG: u8 = 0;
+/// Prefix of messages compressed using zstd.
+const ZSTD_TAG: u8 = 1;
+/// Prefix of messages compressed using lz4.
+const LZ4_TAG: u8 = 2;
+
+/// Length of the little-endian decompressed size lz4 messages are prefixed with.
+const LZ4_SIZE_LENGTH: usize = 4;
+
+/// A compression algorithm for network messages.
+#[derive(Copy, Clone, DataSize, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompressionAlgorithm {
+    /// zstd, compressing better at the expense of speed.
+    Zstd,
+    /// lz4, compressing faster at the expense of ratio.
+    Lz4,
+}
+
+impl CompressionAlgorithm {
+    /// Returns the capability announcing support for the algorithm.
+    pub(super) fn capability(self) -> Capability {
+        match self {
+            CompressionAlgorithm::Zstd => Capability::Zstd,
+            CompressionAlgorithm::Lz4 => Capability::Lz4,
+        }
+    }
+}
+
+impl Display for CompressionAlgorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CompressionAlgorithm::Zstd => f.write_str("zstd"),
+            CompressionAlgorithm::Lz4 => f.write_str("lz4"),
+        }
+    }
+}
+
+/// The compression negotiated for the messages following the handshake of a connection.
+#[derive(Clone, Debug)]
+pub(super) struct Compression {
+    /// The algorithm outgoing messages are compressed with, `None` if there is no algorithm both
+    /// sides support.
+    algorithm: Option<CompressionAlgorithm>,
+    /// The zstd compression level.
+    zstd_level: i32,
+    /// The minimum size of messages to compress.
+    thresholds: CompressionThresholds,
+    /// The maximum size of a decompressed message.
+    max_decompressed_size: usize,
+}
+
+impl Compression {
+    /// Negotiates the compression for a connection, given the capabilities announced by the peer.
+    ///
+    /// Returns `None` if either side does not support compressed messages.
+    pub(super) fn negotiate(
+        config: &CompressionConfig,
+        peer_capabilities: Capabilities,
+        max_decompressed_size: u32,
+    ) -> Option<Self> {
+        if config.algorithms.is_empty() || !peer_capabilities.contains(Capability::Compression) {
+            return None;
+        }
+
+        let algorithm = config
+            .algorithms
+            .iter()
+            .find(|algorithm| peer_capabilities.contains(algorithm.capability()))
+            .copied();
+
+        Some(Compression {
+            algorithm,
+            zstd_level: config.zstd_level,
+            thresholds: config.thresholds,
+            max_decompressed_size: max_decompressed_size as usize,
+        })
+    }
+
+    /// Returns the algorithm outgoing messages are compressed with.
+    pub(super) fn algorithm(&self) -> Option<CompressionAlgorithm> {
+        self.algorithm
+    }
+
+    /// Prefixes a serialized message, compressing it if it is large enough.
+    pub(super) fn encode(&self, kind: MessageKind, serialized: &[u8]) -> io::Result<Bytes> {
+        let algorithm = self
+            .algorithm
+            .filter(|_| serialized.len() >= self.thresholds.for_kind(kind) as usize);
+
+        let (tag, compressed) = match algorithm {
+            Some(CompressionAlgorithm::Zstd) => {
+                (ZSTD_TAG, zstd::bulk::compress(serialized, self.zstd_level)?)
+            }
+            Some(CompressionAlgorithm::Lz4) => {
+                (LZ4_TAG, lz4_flex::compress_prepend_size(serialized))
+            }
+            None => return Ok(prefixed(UNCOMPRESSED_TAG, serialized)),
+        };
+
+        // Incompressible messages are sent as they are.
+        if compressed.len() >= serialized.len() {
+            return Ok(prefixed(UNCOMPRESSED_TAG, serialized));
+        }
+        Ok(prefixed(tag, &compressed))
+    }
+
+    /// Removes the prefix from a received message, decompressing it if necessary.
+    pub(super) fn decode(&self, frame: &[u8]) -> io::Result<BytesMut> {
+        let (tag, remainder) = frame
+            .split_first()
+            .ok_or_else(|| invalid_data("empty message"))?;
+
+        match *tag {
+            UNCOMPRESSED_TAG => Ok(BytesMut::from(remainder)),
+            ZSTD_TAG => {
+                // The sender's frames always carry their content size, which allows decompressing
+                // straight into a buffer of the right size.
+                let size = zstd::zstd_safe::get_frame_content_size(remainder);
+                if size == zstd::zstd_safe::CONTENTSIZE_UNKNOWN
+                    || size == zstd::zstd_safe::CONTENTSIZE_ERROR
+                {
+                    return Err(invalid_data("zstd message without content size"));
+                }
+                if size > self.max_decompressed_size as u64 {
+                    return Err(invalid_data("decompressed zstd message too large"));
+                }
+                let mut decompressed = BytesMut::new();
+                decompressed.resize(size as usize, 0);
+                let written = zstd::bulk::decompress_to_buffer(remainder, &mut decompressed)?;
+                decompressed.truncate(written);
+                Ok(decompressed)
+            }
+            LZ4_TAG => {
+                if remainder.len() < LZ4_SIZE_LENGTH {
+                    return Err(invalid_data("lz4 message too short"));
+                }
+                let (size, compressed) = remainder.split_at(LZ4_SIZE_LENGTH);
+                let size = u32::from_le_bytes(size.try_into().expect("should have 4 bytes"));
+                if size as usize > self.max_decompressed_size {
+                    return Err(invalid_data("decompressed lz4 message too large"));
+                }
+                let decompressed = lz4_flex::decompress(compressed, size as usize)
+                    .map_err(|err| invalid_data(err.to_string()))?;
+                Ok(BytesMut::from(decompressed.as_slice()))
+            }
+            other => Err(invalid_data(format!("unknown compression tag {}", other))),
+        }
+    }
+}
+
+/// Prefixes `data` with `tag`.
+fn prefixed(tag: u8, data: &[u8]) -> Bytes {
+    let mut buffer = BytesMut::with_capacity(data.len() + 1);
+    buffer.put_u8(tag);
+    buffer.put_slice(data);
+    buffer.freeze()
+}
+
+/// Creates an `InvalidData` IO error.
+fn invalid_data<E: Into<Box<dyn std::error::Error + Send + Sync>>>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_SIZE: u32 = 1024 * 1024;
+
+    fn config(algorithms: Vec<CompressionAlgorithm>) -> CompressionConfig {
+        CompressionConfig {
+            algorithms,
+            ..Default::default()
+        }
+    }
+
+    fn peer(algorithms: &[CompressionAlgorithm]) -> Capabilities {
+        let mut capabilities = Capabilities::default();
+        capabilities.insert(Capability::Compression);
+        for algorithm in algorithms {
+            capabilities.insert(algorithm.capability());
+        }
+        capabilities
+    }
+
+    fn compressible_message() -> Vec<u8> {
+        b"casper".iter().copied().cycle().take(64 * 1024).collect()
+    }
+
+    #[test]
+    fn should_negotiate_most_preferred_common_algorithm() {
+        let ours = config(vec![CompressionAlgorithm::Zstd, CompressionAlgorithm::Lz4]);
+
+        let compression =
+            Compression::negotiate(&ours, peer(&[CompressionAlgorithm::Lz4]), MAX_SIZE).unwrap();
+        assert_eq!(compression.algorithm(), Some(CompressionAlgorithm::Lz4));
+
+        let compression = Compression::negotiate(
+            &ours,
+            peer(&[CompressionAlgorithm::Lz4, CompressionAlgorithm::Zstd]),
+            MAX_SIZE,
+        )
+        .unwrap();
+        assert_eq!(compression.algorithm(), Some(CompressionAlgorithm::Zstd));
+
+        let compression = Compression::negotiate(
+            &config(vec![CompressionAlgorithm::Zstd]),
+            peer(&[CompressionAlgorithm::Lz4]),
+            MAX_SIZE,
+        )
+        .unwrap();
+        assert_eq!(compression.algorithm(), None);
+    }
+
+    #[test]
+    fn should_not_negotiate_without_support_on_both_sides() {
+        assert!(Compression::negotiate(
+            &config(vec![]),
+            peer(&[CompressionAlgorithm::Zstd]),
+            MAX_SIZE
+        )
+        .is_none());
+
+        let mut without_compression = Capabilities::default();
+        without_compression.insert(Capability::Zstd);
+        assert!(Compression::negotiate(
+            &config(vec![CompressionAlgorithm::Zstd]),
+            without_compression,
+            MAX_SIZE
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn should_roundtrip_compressed_messages() {
+        let message = compressible_message();
+        for algorithm in &[CompressionAlgorithm::Zstd, CompressionAlgorithm::Lz4] {
+            let compression =
+                Compression::negotiate(&config(vec![*algorithm]), peer(&[*algorithm]), MAX_SIZE)
+                    .unwrap();
+            let encoded = compression
+                .encode(MessageKind::BlockTransfer, &message)
+                .unwrap();
+            assert!(encoded.len() < message.len());
+            let decoded = compression.decode(&encoded).unwrap();
+            assert_eq!(decoded.as_ref(), message.as_slice());
+        }
+    }
+
+    #[test]
+    fn should_not_compress_below_threshold() {
+        let compression = Compression::negotiate(
+            &config(vec![CompressionAlgorithm::Zstd]),
+            peer(&[CompressionAlgorithm::Zstd]),
+            MAX_SIZE,
+        )
+        .unwrap();
+        let message = vec![0; compression.thresholds.consensus as usize - 1];
+
+        let encoded = compression
+            .encode(MessageKind::Consensus, &message)
+            .unwrap();
+        assert_eq!(encoded[0], UNCOMPRESSED_TAG);
+        assert_eq!(&encoded[1..], message.as_slice());
+        assert_eq!(compression.decode(&encoded).unwrap().as_ref(), message);
+    }
+
+    #[test]
+    fn should_reject_zstd_messages_without_content_size() {
+        let message = compressible_message();
+        let compression = Compression::negotiate(
+            &config(vec![CompressionAlgorithm::Zstd]),
+            peer(&[CompressionAlgorithm::Zstd]),
+            MAX_SIZE,
+        )
+        .unwrap();
+
+        let mut encoder = zstd::Encoder::new(vec![ZSTD_TAG], 0).unwrap();
+        encoder.include_contentsize(false).unwrap();
+        io::Write::write_all(&mut encoder, &message).unwrap();
+        let encoded = encoder.finish().unwrap();
+
+        assert!(compression.decode(&encoded).is_err());
+    }
+
+    #[test]
+    fn should_reject_oversized_decompressed_messages() {
+        let message = compressible_message();
+        for algorithm in &[CompressionAlgorithm::Zstd, CompressionAlgorithm::Lz4] {
+            let sender =
+                Compression::negotiate(&config(vec![*algorithm]), peer(&[*algorithm]), MAX_SIZE)
+                    .unwrap();
+            let receiver = Compression::negotiate(
+                &config(vec![*algorithm]),
+                peer(&[*algorithm]),
+                message.len() as u32 - 1,
+            )
+            .unwrap();
+            let encoded = sender.encode(MessageKind::BlockTransfer, &message).unwrap();
+            assert!(receiver.decode(&encoded).is_err());
+        }
+    }
+}
@@ -1,10 +1,11 @@
 #[cfg(test)]
 use std::net::{Ipv4Addr, SocketAddr};
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
+use super::{compression::CompressionAlgorithm, MessageKind};
 use crate::types::TimeDiff;
 
 /// Default binding address.
@@ -20,6 +21,33 @@ const DEFAULT_PUBLIC_ADDRESS: &str = "127.0.0.1:0";
 /// Default interval for gossiping network addresses.
 const DEFAULT_GOSSIP_INTERVAL: Duration = Duration::from_secs(30);
 
+/// Default reputation score at which a peer is banned.
+const DEFAULT_BAN_THRESHOLD: u32 = 100;
+
+/// Default duration of a ban.
+const DEFAULT_BAN_DURATION: TimeDiff = TimeDiff::from_seconds(60 * 60);
+
+/// Default interval after which a peer's reputation score is reduced by one.
+const DEFAULT_SCORE_DECAY_INTERVAL: TimeDiff = TimeDiff::from_seconds(60);
+
+/// Default zstd compression level.
+const DEFAULT_ZSTD_LEVEL: i32 = 3;
+
+/// Default minimum size in bytes of a serialized message to be compressed.
+const DEFAULT_COMPRESSION_THRESHOLD: u32 = 1024;
+
+/// Default directory traffic captures are written to, relative to the config file.
+const DEFAULT_TRAFFIC_CAPTURE_PATH: &str = "traffic_capture";
+
+/// Default maximum size in bytes of a single traffic capture file.
+const DEFAULT_MAX_CAPTURE_FILE_SIZE: u64 = 100 * 1024 * 1024;
+
+/// Default maximum number of traffic capture files kept.
+const DEFAULT_MAX_CAPTURE_FILES: u32 = 10;
+
+/// Default maximum number of messages waiting to be written to traffic captures.
+const DEFAULT_CAPTURE_QUEUE_SIZE: u32 = 4096;
+
 // Default values for networking configuration:
 impl Default for Config {
     fn default() -> Self {
@@ -33,6 +61,10 @@ impl Default for Config {
             max_addr_pending_time: TimeDiff::from_seconds(60),
             max_outgoing_byte_rate_non_validators: 0,
             max_incoming_message_rate_non_validators: 0,
+            blocklist: BlocklistConfig::default(),
+            compression: CompressionConfig::default(),
+            traffic_recorder: TrafficRecorderConfig::default(),
+            replay_capture: None,
         }
     }
 }
@@ -63,6 +95,144 @@ pub struct Config {
     pub max_outgoing_byte_rate_non_validators: u32,
     /// Maximum of requests answered from non-validating peers. Unlimited if 0.
     pub max_incoming_message_rate_non_validators: u32,
+    /// Peer reputation and blocklist configuration.
+    #[serde(default)]
+    pub blocklist: BlocklistConfig,
+    /// Message compression configuration.
+    #[serde(default)]
+    pub compression: CompressionConfig,
+    /// Network traffic recorder configuration.
+    #[serde(default)]
+    pub traffic_recorder: TrafficRecorderConfig,
+    /// Capture file or directory of capture files to replay instead of connecting to any peers.
+    ///
+    /// Set by the `replay-capture` subcommand rather than in config files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replay_capture: Option<PathBuf>,
+}
+
+/// Peer reputation and blocklist configuration.
+#[derive(DataSize, Debug, Clone, Copy, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct BlocklistConfig {
+    /// Reputation score at which a peer is banned.
+    pub ban_threshold: u32,
+    /// Duration for which a peer is banned.
+    pub ban_duration: TimeDiff,
+    /// Interval after which a peer's reputation score is reduced by one. Scores never decay if 0.
+    pub score_decay_interval: TimeDiff,
+}
+
+impl Default for BlocklistConfig {
+    fn default() -> Self {
+        BlocklistConfig {
+            ban_threshold: DEFAULT_BAN_THRESHOLD,
+            ban_duration: DEFAULT_BAN_DURATION,
+            score_decay_interval: DEFAULT_SCORE_DECAY_INTERVAL,
+        }
+    }
+}
+
+/// Message compression configuration.
+#[derive(DataSize, Debug, Clone, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct CompressionConfig {
+    /// Compression algorithms to use, in order of preference. Compression is disabled if empty.
+    pub algorithms: Vec<CompressionAlgorithm>,
+    /// zstd compression level.
+    pub zstd_level: i32,
+    /// Minimum size in bytes of serialized messages to be compressed, by kind of message.
+    pub thresholds: CompressionThresholds,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        CompressionConfig {
+            algorithms: vec![CompressionAlgorithm::Zstd, CompressionAlgorithm::Lz4],
+            zstd_level: DEFAULT_ZSTD_LEVEL,
+            thresholds: CompressionThresholds::default(),
+        }
+    }
+}
+
+/// Minimum size in bytes of serialized messages to be compressed, by kind of message.
+#[derive(DataSize, Debug, Clone, Copy, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct CompressionThresholds {
+    /// Threshold for consensus messages.
+    pub consensus: u32,
+    /// Threshold for deploy gossip messages.
+    pub deploy_gossip: u32,
+    /// Threshold for address gossip messages.
+    pub address_gossip: u32,
+    /// Threshold for deploys transferred via requests.
+    pub deploy_transfer: u32,
+    /// Threshold for blocks and finality signatures transferred via requests.
+    pub block_transfer: u32,
+    /// Threshold for any other messages.
+    pub other: u32,
+}
+
+impl CompressionThresholds {
+    /// Returns the threshold for the given kind of message.
+    ///
+    /// Protocol messages are never compressed.
+    pub(super) fn for_kind(&self, kind: MessageKind) -> u32 {
+        match kind {
+            MessageKind::Protocol => u32::MAX,
+            MessageKind::Consensus => self.consensus,
+            MessageKind::DeployGossip => self.deploy_gossip,
+            MessageKind::AddressGossip => self.address_gossip,
+            MessageKind::DeployTransfer => self.deploy_transfer,
+            MessageKind::BlockTransfer => self.block_transfer,
+            MessageKind::Other => self.other,
+        }
+    }
+}
+
+impl Default for CompressionThresholds {
+    fn default() -> Self {
+        CompressionThresholds {
+            consensus: DEFAULT_COMPRESSION_THRESHOLD,
+            deploy_gossip: DEFAULT_COMPRESSION_THRESHOLD,
+            address_gossip: DEFAULT_COMPRESSION_THRESHOLD,
+            deploy_transfer: DEFAULT_COMPRESSION_THRESHOLD,
+            block_transfer: DEFAULT_COMPRESSION_THRESHOLD,
+            other: DEFAULT_COMPRESSION_THRESHOLD,
+        }
+    }
+}
+
+/// Network traffic recorder configuration.
+#[derive(DataSize, Debug, Clone, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct TrafficRecorderConfig {
+    /// Whether to record all messages exchanged with peers.
+    pub enabled: bool,
+    /// Directory capture files are written to, relative to the config file if not absolute.
+    pub path: PathBuf,
+    /// Size in bytes at which a new capture file is started.
+    pub max_file_size: u64,
+    /// Maximum number of capture files kept, the oldest are deleted first.
+    pub max_files: u32,
+    /// Maximum number of messages waiting to be written, further messages are not recorded.
+    pub queue_size: u32,
+}
+
+impl Default for TrafficRecorderConfig {
+    fn default() -> Self {
+        TrafficRecorderConfig {
+            enabled: false,
+            path: PathBuf::from(DEFAULT_TRAFFIC_CAPTURE_PATH),
+            max_file_size: DEFAULT_MAX_CAPTURE_FILE_SIZE,
+            max_files: DEFAULT_MAX_CAPTURE_FILES,
+            queue_size: DEFAULT_CAPTURE_QUEUE_SIZE,
+        }
+    }
 }
 
 #[cfg(test)]
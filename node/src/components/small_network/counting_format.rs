@@ -4,16 +4,21 @@
 //! unique ID per established connection that can be independently derive by peers on either of a
 //! connection. [`TraceId`] identifies a single message, distinguishing even messages that are sent
 //! to the same peer with equal contents.
+//!
+//! Compression of messages is also applied here, after serialization, so that both the compressed
+//! and uncompressed sizes can be recorded.
 
 use std::{
     convert::TryFrom,
     fmt::{self, Display, Formatter},
+    io,
     pin::Pin,
     sync::{Arc, Weak},
 };
 
 use bytes::{Bytes, BytesMut};
 use hex_fmt::HexFmt;
+use once_cell::sync::OnceCell;
 use openssl::ssl::SslRef;
 use pin_project::pin_project;
 #[cfg(test)]
@@ -22,7 +27,7 @@ use static_assertions::const_assert;
 use tokio_serde::{Deserializer, Serializer};
 use tracing::{error, trace, warn};
 
-use super::{tls::KeyFingerprint, Message, Payload};
+use super::{compression::Compression, tls::KeyFingerprint, Message, Payload};
 #[cfg(test)]
 use crate::testing::TestRng;
 use crate::{
@@ -65,6 +70,10 @@ pub struct CountingFormat<F> {
     role: Role,
     /// Metrics to update.
     metrics: Weak<NetworkingMetrics>,
+    /// Compression negotiated during the handshake.
+    ///
+    /// Until it is set, messages are neither compressed nor prefixed.
+    compression: Arc<OnceCell<Compression>>,
 }
 
 impl<F> CountingFormat<F> {
@@ -75,6 +84,7 @@ impl<F> CountingFormat<F> {
         connection_id: ConnectionId,
         role: Role,
         inner: F,
+        compression: Arc<OnceCell<Compression>>,
     ) -> Self {
         Self {
             metrics,
@@ -83,6 +93,7 @@ impl<F> CountingFormat<F> {
             in_count: 0,
             role,
             inner,
+            compression,
         }
     }
 }
@@ -90,6 +101,7 @@ impl<F> CountingFormat<F> {
 impl<F, P> Serializer<Arc<Message<P>>> for CountingFormat<F>
 where
     F: Serializer<Arc<Message<P>>>,
+    F::Error: From<io::Error>,
     P: Payload,
 {
     type Error = F::Error;
@@ -100,9 +112,19 @@ where
         let projection: Pin<&mut F> = this.inner;
 
         let serialized = F::serialize(projection, item)?;
-        let msg_size = serialized.len() as u64;
+        let msg_size_uncompressed = serialized.len() as u64;
         let msg_kind = item.classify();
-        NetworkingMetrics::record_payload_out(this.metrics, msg_kind, msg_size);
+        let serialized = match this.compression.get() {
+            Some(compression) => compression.encode(msg_kind, &serialized)?,
+            None => serialized,
+        };
+        let msg_size = serialized.len() as u64;
+        NetworkingMetrics::record_payload_out(
+            this.metrics,
+            msg_kind,
+            msg_size,
+            msg_size_uncompressed,
+        );
 
         let trace_id = this
             .connection_id
@@ -112,6 +134,7 @@ where
         trace!(target: "net_out",
             msg_id = %trace_id,
             msg_size,
+            msg_size_uncompressed,
             msg_kind = %msg_kind, "sending");
 
         Ok(serialized)
@@ -121,6 +144,7 @@ where
 impl<F, P> Deserializer<Message<P>> for CountingFormat<F>
 where
     F: Deserializer<Message<P>>,
+    F::Error: From<io::Error>,
     P: Payload,
 {
     type Error = F::Error;
@@ -132,7 +156,13 @@ where
 
         let msg_size = src.len() as u64;
 
-        let deserialized = F::deserialize(projection, src)?;
+        let (deserialized, msg_size_uncompressed) = match this.compression.get() {
+            Some(compression) => {
+                let decoded = compression.decode(src)?;
+                (F::deserialize(projection, &decoded)?, decoded.len() as u64)
+            }
+            None => (F::deserialize(projection, src)?, msg_size),
+        };
         let msg_kind = deserialized.classify();
 
         let trace_id = this
@@ -143,6 +173,7 @@ where
         trace!(target: "net_in",
             msg_id = %trace_id,
             msg_size,
+            msg_size_uncompressed,
             msg_kind = %msg_kind, "received");
 
         Ok(deserialized)
@@ -66,6 +66,13 @@ pub enum Error {
         #[source]
         ResolveAddressError,
     ),
+    /// Failed to start recording network traffic.
+    #[error("failed to start traffic recorder")]
+    TrafficRecorder(
+        #[serde(skip_serializing)]
+        #[source]
+        io::Error,
+    ),
 
     /// Instantiating metrics failed.
     #[error(transparent)]
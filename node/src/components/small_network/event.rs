@@ -13,7 +13,10 @@ use serde::Serialize;
 use static_assertions::const_assert;
 use tracing::Span;
 
-use super::{error::ConnectionError, FramedTransport, GossipedAddress, Message, NodeId};
+use super::{
+    blocklist::Blocklist, capabilities::Capabilities, error::ConnectionError, FramedTransport,
+    GossipedAddress, Message, NodeId,
+};
 use crate::{
     effect::{
         announcements::{BlocklistAnnouncement, LinearChainAnnouncement},
@@ -90,6 +93,10 @@ pub enum Event<P> {
     /// Blocklist announcement
     #[from]
     BlocklistAnnouncement(BlocklistAnnouncement<NodeId>),
+    /// The blocklist persisted by a previous run has been loaded.
+    BlocklistLoaded {
+        maybe_blocklist: Box<Option<Blocklist>>,
+    },
 
     /// Announcement from the linear chain.
     ///
@@ -146,6 +153,13 @@ impl<P: Display> Display for Event<P> {
             Event::BlocklistAnnouncement(ann) => {
                 write!(f, "handling blocklist announcement: {}", ann)
             }
+            Event::BlocklistLoaded { maybe_blocklist } => {
+                if maybe_blocklist.is_some() {
+                    write!(f, "loaded persisted blocklist")
+                } else {
+                    write!(f, "no persisted blocklist")
+                }
+            }
             Event::SweepOutgoing => {
                 write!(f, "sweep outgoing connections")
             }
@@ -271,6 +285,8 @@ pub enum OutgoingConnection<P> {
         peer_id: NodeId,
         /// The public key the peer is validating with, if any.
         peer_consensus_public_key: Option<PublicKey>,
+        /// The optional protocol features the peer supports.
+        peer_capabilities: Capabilities,
         /// Sink for outgoing messages.
         #[serde(skip_serializing)]
         sink: SplitSink<FramedTransport<P>, Arc<Message<P>>>,
@@ -293,15 +309,18 @@ impl<P> Display for OutgoingConnection<P> {
                 peer_addr,
                 peer_id,
                 peer_consensus_public_key,
+                peer_capabilities,
                 sink: _,
             } => {
                 write!(f, "connection established to {}/{}", peer_addr, peer_id)?;
 
                 if let Some(public_key) = peer_consensus_public_key {
-                    write!(f, " [{}]", public_key)
+                    write!(f, " [{}]", public_key)?;
                 } else {
-                    f.write_str(" [no validator id]")
+                    f.write_str(" [no validator id]")?;
                 }
+
+                write!(f, " capabilities: {}", peer_capabilities)
             }
         }
     }
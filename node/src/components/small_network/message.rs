@@ -9,7 +9,7 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use crate::crypto;
 
-use super::counting_format::ConnectionId;
+use super::{capabilities::Capabilities, counting_format::ConnectionId};
 
 /// The default protocol version to use in absence of one in the protocol version field.
 #[inline]
@@ -30,6 +30,9 @@ pub enum Message<P> {
         /// A self-signed certificate indicating validator status.
         #[serde(default)]
         consensus_certificate: Option<ConsensusCertificate>,
+        /// Optional protocol features the node supports.
+        #[serde(default)]
+        capabilities: Capabilities,
     },
     Payload(P),
 }
@@ -37,7 +40,7 @@ pub enum Message<P> {
 impl<P: Payload> Message<P> {
     /// Classifies a message based on its payload.
     #[inline]
-    pub(super) fn classify(&self) -> MessageKind {
+    pub(crate) fn classify(&self) -> MessageKind {
         match self {
             Message::Handshake { .. } => MessageKind::Protocol,
             Message::Payload(payload) => payload.classify(),
@@ -113,6 +116,7 @@ impl<P: Display> Display for Message<P> {
                 public_addr,
                 protocol_version,
                 consensus_certificate,
+                capabilities,
             } => {
                 write!(
                     f,
@@ -121,10 +125,12 @@ impl<P: Display> Display for Message<P> {
                 )?;
 
                 if let Some(cert) = consensus_certificate {
-                    write!(f, "{}", cert)
+                    write!(f, "{}", cert)?;
                 } else {
-                    f.write_str("-")
+                    f.write_str("-")?;
                 }
+
+                write!(f, ", capabilities: {}", capabilities)
             }
             Message::Payload(payload) => write!(f, "payload: {}", payload),
         }
@@ -189,9 +195,9 @@ mod tests {
     use casper_types::ProtocolVersion;
     use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-    use crate::protocol;
+    use crate::{components::small_network::capabilities::Capability, protocol};
 
-    use super::Message;
+    use super::{Capabilities, Message};
 
     /// Version 1.0.0 network level message.
     ///
@@ -297,6 +303,7 @@ mod tests {
             protocol_version: ProtocolVersion::from_parts(5, 6, 7),
             // TODO: Test _with_ handshake instead.
             consensus_certificate: None,
+            capabilities: Capabilities::default(),
         };
 
         let legacy_handshake: V1_0_0_Message = roundtrip_message(&modern_handshake);
@@ -315,6 +322,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn current_handshake_roundtrips_capabilities() {
+        let mut capabilities = Capabilities::default();
+        capabilities.insert(Capability::Compression);
+        capabilities.insert(Capability::Lz4);
+
+        let handshake = Message::<protocol::Message>::Handshake {
+            network_name: "example-handshake".to_string(),
+            public_addr: ([12, 34, 56, 78], 12346).into(),
+            protocol_version: ProtocolVersion::from_parts(5, 6, 7),
+            consensus_certificate: None,
+            capabilities,
+        };
+
+        let roundtripped: Message<protocol::Message> = roundtrip_message(&handshake);
+
+        match roundtripped {
+            Message::Handshake {
+                capabilities: roundtripped_capabilities,
+                ..
+            } => {
+                assert_eq!(roundtripped_capabilities, capabilities);
+            }
+            Message::Payload(_) => {
+                panic!("did not expect modern handshake to deserialize to payload")
+            }
+        }
+    }
+
     #[test]
     fn current_handshake_decodes_from_v1_0_0() {
         let legacy_handshake = V1_0_0_Message::Handshake {
@@ -330,11 +366,13 @@ mod tests {
                 public_addr,
                 protocol_version,
                 consensus_certificate,
+                capabilities,
             } => {
                 assert_eq!(network_name, "example-handshake");
                 assert_eq!(public_addr, ([12, 34, 56, 78], 12346).into());
                 assert_eq!(protocol_version, ProtocolVersion::V1_0_0);
                 assert!(consensus_certificate.is_none());
+                assert_eq!(capabilities, Capabilities::default());
             }
             Message::Payload(_) => {
                 panic!("did not expect modern handshake to deserialize to payload")
@@ -352,11 +390,13 @@ mod tests {
                 public_addr,
                 protocol_version,
                 consensus_certificate,
+                capabilities,
             } => {
                 assert_eq!(network_name, "serialization-test");
                 assert_eq!(public_addr, ([12, 34, 56, 78], 12346).into());
                 assert_eq!(protocol_version, ProtocolVersion::V1_0_0);
                 assert!(consensus_certificate.is_none());
+                assert_eq!(capabilities, Capabilities::default());
             }
             Message::Payload(_) => {
                 panic!("did not expect modern handshake to deserialize to payload")
@@ -103,7 +103,7 @@ use datasize::DataSize;
 
 use tracing::{debug, error_span, field::Empty, info, trace, warn, Span};
 
-use super::{display_error, NodeId};
+use super::{capabilities::Capabilities, display_error, NodeId};
 
 /// An outgoing connection/address in various states.
 #[derive(DataSize, Debug)]
@@ -151,6 +151,8 @@ where
         ///
         /// Can be a channel to decouple sending, or even a direct connection handle.
         handle: H,
+        /// The optional protocol features the peer announced during the handshake.
+        capabilities: Capabilities,
     },
     /// The address was blocked and will not be retried.
     Blocked { since: Instant },
@@ -191,6 +193,8 @@ pub enum DialOutcome<H, E> {
         handle: H,
         /// The remote peer's authenticated node ID.
         node_id: NodeId,
+        /// The optional protocol features the peer announced during the handshake.
+        capabilities: Capabilities,
     },
     /// The connection attempt failed.
     Failed {
@@ -418,6 +422,17 @@ where
         }
     }
 
+    /// Retrieves the optional protocol features supported by a connected peer.
+    pub(crate) fn get_capabilities(&self, peer_id: NodeId) -> Option<Capabilities> {
+        let outgoing = self.outgoing.get(self.routes.get(&peer_id)?)?;
+
+        if let OutgoingState::Connected { capabilities, .. } = outgoing.state {
+            Some(capabilities)
+        } else {
+            None
+        }
+    }
+
     /// Iterates over all connected peer IDs.
     #[allow(clippy::needless_lifetimes)]
     pub(crate) fn connected_peers<'a>(&'a self) -> impl Iterator<Item = NodeId> + 'a {
@@ -662,7 +677,7 @@ where
                 addr,
                 handle,
                 node_id,
-                ..
+                capabilities,
             } => {
                 info!("established outgoing connection");
 
@@ -681,6 +696,7 @@ where
                         OutgoingState::Connected {
                             peer_id: node_id,
                             handle,
+                            capabilities,
                         },
                     );
                     None
@@ -784,7 +800,7 @@ mod tests {
     use datasize::DataSize;
     use thiserror::Error;
 
-    use super::{DialOutcome, DialRequest, NodeId, OutgoingConfig, OutgoingManager};
+    use super::{Capabilities, DialOutcome, DialRequest, NodeId, OutgoingConfig, OutgoingManager};
     use crate::testing::{init_logging, test_clock::TestClock};
 
     /// Error for test dialer.
@@ -885,11 +901,16 @@ mod tests {
                 addr: addr_a,
                 handle: 99,
                 node_id: id_a,
+                capabilities: Capabilities::default(),
             },)
             .is_none());
 
         // The routing table should have been updated and should return the handle.
         assert_eq!(manager.get_route(id_a), Some(&99));
+        assert_eq!(
+            manager.get_capabilities(id_a),
+            Some(Capabilities::default())
+        );
         assert_eq!(manager.get_addr(id_a), Some(addr_a));
 
         // Time passes, and our connection drops. Reconnecting should be immediate.
@@ -902,6 +923,7 @@ mod tests {
 
         // The route should have been cleared.
         assert!(manager.get_route(id_a).is_none());
+        assert!(manager.get_capabilities(id_a).is_none());
         assert!(manager.get_addr(id_a).is_none());
 
         // Reconnection is already in progress, so we do not expect another request on housekeeping.
@@ -1084,6 +1106,7 @@ mod tests {
                 addr: addr_b,
                 handle: 101,
                 node_id: id_b,
+                capabilities: Capabilities::default(),
             },)
             .is_none());
         assert_eq!(manager.get_route(id_b), Some(&101));
@@ -1112,6 +1135,7 @@ mod tests {
                 addr: addr_c,
                 handle: 42,
                 node_id: id_c,
+                capabilities: Capabilities::default(),
             },)
         ));
 
@@ -1137,6 +1161,7 @@ mod tests {
                 addr: addr_b,
                 handle: 77,
                 node_id: id_b,
+                capabilities: Capabilities::default(),
             },)
             .is_none());
         assert!(manager
@@ -1144,6 +1169,7 @@ mod tests {
                 addr: addr_a,
                 handle: 66,
                 node_id: id_a,
+                capabilities: Capabilities::default(),
             },)
             .is_none());
 
@@ -1211,11 +1237,13 @@ mod tests {
             addr: addr_a,
             handle: 22,
             node_id: id_a,
+            capabilities: Capabilities::default(),
         });
         manager.handle_dial_outcome(DialOutcome::Successful {
             addr: addr_b,
             handle: 33,
             node_id: id_b,
+            capabilities: Capabilities::default(),
         });
 
         let mut peer_ids: Vec<_> = manager.connected_peers().collect();
@@ -1263,6 +1291,7 @@ mod tests {
                 addr: addr_a,
                 handle: 2,
                 node_id: id_a,
+                capabilities: Capabilities::default(),
             })
             .is_none());
 
@@ -1276,6 +1305,7 @@ mod tests {
                 addr: addr_a,
                 handle: 1,
                 node_id: id_a,
+                capabilities: Capabilities::default(),
             })
             .is_none());
 
@@ -5,6 +5,7 @@ use std::{
     fmt::Display,
     io,
     net::SocketAddr,
+    path::PathBuf,
     pin::Pin,
     sync::{Arc, Weak},
     time::Duration,
@@ -16,6 +17,7 @@ use futures::{
     stream::{SplitSink, SplitStream},
     Future, SinkExt, StreamExt,
 };
+use once_cell::sync::OnceCell;
 use openssl::{
     pkey::{PKey, Private},
     ssl::Ssl,
@@ -25,6 +27,7 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use tokio::{
     net::TcpStream,
     sync::{mpsc::UnboundedReceiver, watch},
+    time::Instant,
 };
 use tokio_openssl::SslStream;
 use tracing::{
@@ -34,20 +37,24 @@ use tracing::{
 };
 
 use super::{
+    capabilities::Capabilities,
     chain_info::ChainInfo,
+    compression::Compression,
+    config::CompressionConfig,
     counting_format::{ConnectionId, Role},
     error::{ConnectionError, IoError},
     event::{IncomingConnection, OutgoingConnection},
     framed,
     limiter::LimiterHandle,
     message::ConsensusKeyPair,
+    traffic_recorder::{self, CaptureReader, Direction, TrafficRecorder},
     Event, FramedTransport, Message, Payload, Transport,
 };
 use crate::{
     components::networking_metrics::NetworkingMetrics,
     reactor::{EventQueueHandle, QueueKind},
     tls::{self, TlsCert},
-    types::NodeId,
+    types::{NodeId, Timestamp},
     utils::display_error,
 };
 
@@ -123,17 +130,19 @@ where
 
     // Setup connection sink and stream.
     let connection_id = ConnectionId::from_connection(transport.ssl(), context.our_id, peer_id);
+    let compression = Arc::new(OnceCell::new());
     let mut transport = framed::<P>(
         context.net_metrics.clone(),
         connection_id,
         transport,
         Role::Dialer,
         context.chain_info.maximum_net_message_size,
+        compression.clone(),
     );
 
     // Negotiate the handshake, concluding the incoming connection process.
-    match negotiate_handshake(&context, &mut transport, connection_id).await {
-        Ok((public_addr, peer_consensus_public_key)) => {
+    match negotiate_handshake(&context, &mut transport, connection_id, &compression).await {
+        Ok((public_addr, peer_consensus_public_key, peer_capabilities)) => {
             if let Some(ref public_key) = peer_consensus_public_key {
                 Span::current().record("validator_id", &field::display(public_key));
             }
@@ -150,6 +159,7 @@ where
                 peer_addr,
                 peer_id,
                 peer_consensus_public_key,
+                peer_capabilities,
                 sink,
             }
         }
@@ -182,6 +192,12 @@ where
     pub(super) public_addr: SocketAddr,
     /// Optional set of consensus keys, to identify as a validator during handshake.
     pub(super) consensus_keys: Option<ConsensusKeyPair>,
+    /// Message compression configuration, announced during handshake.
+    pub(super) compression: CompressionConfig,
+    /// Our own capabilities, announced during handshake.
+    pub(super) capabilities: Capabilities,
+    /// Recorder of all messages exchanged with peers, if enabled.
+    pub(super) traffic_recorder: Option<TrafficRecorder>,
 }
 
 /// Handles an incoming connection.
@@ -218,17 +234,19 @@ where
 
     // Setup connection sink and stream.
     let connection_id = ConnectionId::from_connection(transport.ssl(), context.our_id, peer_id);
+    let compression = Arc::new(OnceCell::new());
     let mut transport = framed::<P>(
         context.net_metrics.clone(),
         connection_id,
         transport,
         Role::Listener,
         context.chain_info.maximum_net_message_size,
+        compression.clone(),
     );
 
     // Negotiate the handshake, concluding the incoming connection process.
-    match negotiate_handshake(&context, &mut transport, connection_id).await {
-        Ok((public_addr, peer_consensus_public_key)) => {
+    match negotiate_handshake(&context, &mut transport, connection_id, &compression).await {
+        Ok((public_addr, peer_consensus_public_key, _peer_capabilities)) => {
             if let Some(ref public_key) = peer_consensus_public_key {
                 Span::current().record("validator_id", &field::display(public_key));
             }
@@ -314,11 +332,15 @@ where
     }
 }
 
+/// Exchanges handshakes with the peer.
+///
+/// Enables the compression negotiated with the peer, if any, for all subsequent messages.
 async fn negotiate_handshake<P, REv>(
     context: &NetworkContext<REv>,
     transport: &mut FramedTransport<P>,
     connection_id: ConnectionId,
-) -> Result<(SocketAddr, Option<PublicKey>), ConnectionError>
+    compression: &OnceCell<Compression>,
+) -> Result<(SocketAddr, Option<PublicKey>, Capabilities), ConnectionError>
 where
     P: Payload,
 {
@@ -327,6 +349,7 @@ where
         context.public_addr,
         context.consensus_keys.as_ref(),
         connection_id,
+        context.capabilities,
     );
 
     io_timeout(HANDSHAKE_TIMEOUT, transport.send(Arc::new(handshake)))
@@ -342,9 +365,10 @@ where
         public_addr,
         protocol_version,
         consensus_certificate,
+        capabilities,
     } = remote_handshake
     {
-        debug!(%protocol_version, "handshake received");
+        debug!(%protocol_version, %capabilities, "handshake received");
 
         // The handshake was valid, we can check the network name.
         if network_name != context.chain_info.network_name {
@@ -358,7 +382,18 @@ where
             })
             .transpose()?;
 
-        Ok((public_addr, peer_consensus_public_key))
+        if let Some(negotiated) = Compression::negotiate(
+            &context.compression,
+            capabilities,
+            context.chain_info.maximum_net_message_size,
+        ) {
+            debug!(algorithm = ?negotiated.algorithm(), "compression negotiated");
+            // The transport is not shared until the handshake is complete, so this is the only
+            // place the compression is set.
+            let _ = compression.set(negotiated);
+        }
+
+        Ok((public_addr, peer_consensus_public_key, capabilities))
     } else {
         // Received a non-handshake, this is an error.
         Err(ConnectionError::DidNotSendHandshake)
@@ -458,6 +493,9 @@ where
             match msg_result {
                 Ok(msg) => {
                     trace!(%msg, "message received");
+                    if let Some(ref recorder) = context.traffic_recorder {
+                        recorder.record(Direction::Incoming, peer_id, &msg);
+                    }
                     // We've received a message. Ensure we have the proper amount of resources,
                     // then push it to the reactor.
 
@@ -508,6 +546,8 @@ pub(super) async fn message_sender<P>(
     mut sink: SplitSink<FramedTransport<P>, Arc<Message<P>>>,
     limiter: Box<dyn LimiterHandle>,
     counter: IntGauge,
+    peer_id: NodeId,
+    traffic_recorder: Option<TrafficRecorder>,
 ) where
     P: Payload,
 {
@@ -522,6 +562,10 @@ pub(super) async fn message_sender<P>(
             .unwrap_or(0) as u32;
         limiter.request_allowance(estimated_wire_size).await;
 
+        if let Some(ref recorder) = traffic_recorder {
+            recorder.record(Direction::Outgoing, peer_id, &message);
+        }
+
         // We simply error-out if the sink fails, it means that our connection broke.
         if let Err(ref err) = sink.send(message).await {
             info!(
@@ -532,3 +576,79 @@ pub(super) async fn message_sender<P>(
         };
     }
 }
+
+/// Network traffic replayer.
+///
+/// Schedules the incoming messages of a capture as if they had been received from the recorded
+/// peers, keeping the delays between them, until the capture ends or an error occurs. Outgoing
+/// messages in the capture are skipped.
+pub(super) async fn replay_capture<REv, P>(
+    context: Arc<NetworkContext<REv>>,
+    path: PathBuf,
+    mut shutdown_receiver: watch::Receiver<()>,
+) where
+    P: Payload,
+    REv: From<Event<P>>,
+{
+    let replay_messages = async move {
+        let files = match traffic_recorder::capture_files(&path) {
+            Ok(files) => files,
+            Err(ref err) => {
+                warn!(path = %path.display(), err = display_error(err), "failed to list capture files");
+                return;
+            }
+        };
+
+        // The recorded time of the first replayed message and the time it was replayed.
+        let mut start: Option<(Timestamp, Instant)> = None;
+        let mut count = 0;
+        for file in files {
+            let reader = match CaptureReader::<Message<P>>::open(&file) {
+                Ok(reader) => reader,
+                Err(ref err) => {
+                    warn!(err = display_error(err), "failed to open capture file");
+                    return;
+                }
+            };
+            for result in reader {
+                let captured = match result {
+                    Ok((captured, _)) => captured,
+                    Err(ref err) => {
+                        warn!(err = display_error(err), "failed to read capture file");
+                        return;
+                    }
+                };
+                if captured.direction != Direction::Incoming {
+                    continue;
+                }
+
+                let (recorded_start, replay_start) =
+                    *start.get_or_insert_with(|| (captured.timestamp, Instant::now()));
+                let delay = captured.timestamp.saturating_diff(recorded_start);
+                tokio::time::sleep_until(replay_start + Duration::from(delay)).await;
+
+                let peer_id = captured.peer;
+                context
+                    .event_queue
+                    .schedule(
+                        Event::IncomingMessage {
+                            peer_id: Box::new(peer_id),
+                            msg: Box::new(captured.message),
+                            span: error_span!("replay", %peer_id),
+                        },
+                        QueueKind::NetworkIncoming,
+                    )
+                    .await;
+                count += 1;
+            }
+        }
+        info!(count, "finished replaying captured messages");
+    };
+
+    let shutdown_messages = async move { while shutdown_receiver.changed().await.is_ok() {} };
+
+    match future::select(Box::pin(shutdown_messages), Box::pin(replay_messages)).await {
+        Either::Left(_) => info!("shutting down traffic replay"),
+        Either::Right(_) => (),
+    }
+}
@@ -5,6 +5,7 @@
 
 use std::{
     collections::{HashMap, HashSet},
+    env,
     fmt::{self, Debug, Display, Formatter},
     time::{Duration, Instant},
 };
@@ -29,9 +30,10 @@ use crate::{
     effect::{
         announcements::{ControlAnnouncement, GossiperAnnouncement, NetworkAnnouncement},
         requests::{
-            ChainspecLoaderRequest, ContractRuntimeRequest, NetworkRequest, StorageRequest,
+            ChainspecLoaderRequest, ContractRuntimeRequest, NetworkRequest, StateStoreRequest,
+            StorageRequest,
         },
-        EffectBuilder, Effects,
+        EffectBuilder, EffectExt, Effects,
     },
     protocol,
     reactor::{self, EventQueueHandle, Finalize, Reactor, Runner},
@@ -41,7 +43,7 @@ use crate::{
         ConditionCheckReactor,
     },
     types::NodeId,
-    utils::Source,
+    utils::{Source, WithDir},
     NodeRng,
 };
 
@@ -60,6 +62,8 @@ enum Event {
     NetworkAnnouncement(#[serde(skip_serializing)] NetworkAnnouncement<NodeId, Message>),
     #[from]
     AddressGossiperAnnouncement(#[serde(skip_serializing)] GossiperAnnouncement<GossipedAddress>),
+    #[from]
+    StateStoreRequest(StateStoreRequest),
 }
 
 impl ReactorEvent for Event {
@@ -160,7 +164,7 @@ impl Reactor for TestReactor {
         let small_network_identity = SmallNetworkIdentity::new()?;
         let (net, effects) = SmallNetwork::new(
             event_queue,
-            cfg,
+            WithDir::new(env::temp_dir(), cfg),
             None,
             registry,
             small_network_identity,
@@ -237,6 +241,13 @@ impl Reactor for TestReactor {
                 // We do not care about the announcement of gossiping finished in this test.
                 Effects::new()
             }
+            // The blocklist is not persisted in this test.
+            Event::StateStoreRequest(StateStoreRequest::Save { responder, .. }) => {
+                responder.respond(()).ignore()
+            }
+            Event::StateStoreRequest(StateStoreRequest::Load { responder, .. }) => {
+                responder.respond(None).ignore()
+            }
         }
     }
 
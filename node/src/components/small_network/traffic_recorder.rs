@@ -0,0 +1,559 @@
+//! Recording of network traffic for debugging.
+//!
+//! If enabled, every message exchanged with a peer after the handshake is written to a capture
+//! file, together with the time and the peer's node ID. Files are rotated once they reach the
+//! configured size, keeping only the most recent ones. Captures can be inspected and replayed using
+//! the `inspect-capture` and `replay-capture` subcommands of the node binary.
+//!
+//! Records are buffered and only flushed when a file is rotated or the recorder stops, so the most
+//! recent records are lost if the node crashes.
+//!
+//! A capture file starts with a magic header, followed by a sequence of records, each consisting of
+//! its length as a little-endian `u32` and a MessagePack encoded [`CapturedMessage`].
+
+use std::{
+    fmt::{self, Display, Formatter},
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader, BufWriter, ErrorKind, Read, Write},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    str::FromStr,
+    thread,
+};
+
+use prometheus::IntCounter;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::mpsc::{self, error::TrySendError, Receiver, Sender};
+use tracing::{error, warn};
+
+use super::{config::TrafficRecorderConfig, Message};
+use crate::{
+    types::{NodeId, Timestamp},
+    utils::display_error,
+};
+
+/// Magic bytes every capture file starts with, including the version of the format.
+const MAGIC: &[u8; 8] = b"CSPRCAP\x01";
+
+/// Prefix of the names of capture files.
+const FILE_PREFIX: &str = "traffic-";
+
+/// Extension of capture files.
+const FILE_EXTENSION: &str = "capture";
+
+/// Length of the little-endian size each record is prefixed with.
+const RECORD_SIZE_LENGTH: u64 = 4;
+
+/// The direction of a recorded message.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Direction {
+    /// The message was received from the peer.
+    Incoming,
+    /// The message was sent to the peer.
+    Outgoing,
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Direction::Incoming => f.write_str("incoming"),
+            Direction::Outgoing => f.write_str("outgoing"),
+        }
+    }
+}
+
+/// Error parsing a [`Direction`].
+#[derive(Debug, Error)]
+#[error("invalid direction '{0}', expected 'incoming' or 'outgoing'")]
+pub struct ParseDirectionError(String);
+
+impl FromStr for Direction {
+    type Err = ParseDirectionError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "incoming" => Ok(Direction::Incoming),
+            "outgoing" => Ok(Direction::Outgoing),
+            _ => Err(ParseDirectionError(input.to_string())),
+        }
+    }
+}
+
+/// A single message in a capture.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CapturedMessage<M> {
+    /// The time the message was received or sent.
+    pub(crate) timestamp: Timestamp,
+    /// Whether the message was received or sent.
+    pub(crate) direction: Direction,
+    /// The peer the message was received from or sent to.
+    pub(crate) peer: NodeId,
+    /// The message itself.
+    pub(crate) message: M,
+}
+
+/// Handle to the background thread writing recorded messages to capture files.
+///
+/// Messages are dropped rather than queued if the background thread falls behind, so recording
+/// never holds up networking.
+#[derive(Clone, Debug)]
+pub(super) struct TrafficRecorder {
+    sender: Sender<Vec<u8>>,
+    /// Counts messages dropped because the queue was full.
+    dropped_records: IntCounter,
+}
+
+impl TrafficRecorder {
+    /// Starts recording to capture files in the given directory, creating it if necessary.
+    ///
+    /// The background thread stops once all handles have been dropped.
+    pub(super) fn start(
+        dir: PathBuf,
+        config: &TrafficRecorderConfig,
+        dropped_records: IntCounter,
+    ) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        let mut files = RotatingFiles::new(dir, config.max_file_size, config.max_files);
+
+        let (sender, receiver) = mpsc::channel(config.queue_size.max(1) as usize);
+        thread::Builder::new()
+            .name("traffic-recorder".to_string())
+            .spawn(move || write_records(receiver, &mut files))?;
+
+        Ok(TrafficRecorder {
+            sender,
+            dropped_records,
+        })
+    }
+
+    /// Records a message received from or sent to `peer`.
+    pub(super) fn record<P: Serialize>(
+        &self,
+        direction: Direction,
+        peer: NodeId,
+        message: &Message<P>,
+    ) {
+        let captured = CapturedMessage {
+            timestamp: Timestamp::now(),
+            direction,
+            peer,
+            message,
+        };
+        match rmp_serde::to_vec(&captured) {
+            Ok(record) => match self.sender.try_send(record) {
+                Ok(()) => (),
+                Err(TrySendError::Full(_)) => self.dropped_records.inc(),
+                // The writer stopped, which it has already logged.
+                Err(TrySendError::Closed(_)) => (),
+            },
+            Err(err) => {
+                warn!(
+                    err = display_error(&err),
+                    "failed to serialize message for traffic capture"
+                )
+            }
+        }
+    }
+}
+
+/// Writes all records received until the channel is closed or an error occurs.
+fn write_records(mut receiver: Receiver<Vec<u8>>, files: &mut RotatingFiles) {
+    while let Some(record) = receiver.blocking_recv() {
+        if let Err(err) = files.write(&record) {
+            error!(
+                err = display_error(&err),
+                "failed to write traffic capture, stopping recorder"
+            );
+            return;
+        }
+    }
+    if let Err(err) = files.flush() {
+        error!(err = display_error(&err), "failed to flush traffic capture");
+    }
+}
+
+/// An open capture file.
+struct CaptureFile {
+    writer: BufWriter<File>,
+    size: u64,
+}
+
+/// A sequence of capture files of limited size and number.
+struct RotatingFiles {
+    dir: PathBuf,
+    max_file_size: u64,
+    max_files: usize,
+    /// Distinguishes files created within the same millisecond.
+    sequence_number: u64,
+    current: Option<CaptureFile>,
+}
+
+impl RotatingFiles {
+    fn new(dir: PathBuf, max_file_size: u64, max_files: u32) -> Self {
+        RotatingFiles {
+            dir,
+            max_file_size,
+            max_files: max_files.max(1) as usize,
+            sequence_number: 0,
+            current: None,
+        }
+    }
+
+    /// Appends a record to the current file, starting a new one if it would grow too large.
+    ///
+    /// Every file contains at least one record, even if that exceeds the maximum file size.
+    fn write(&mut self, record: &[u8]) -> io::Result<()> {
+        let record_size = RECORD_SIZE_LENGTH + record.len() as u64;
+        let rotate = match self.current {
+            None => true,
+            Some(ref file) => {
+                file.size > MAGIC.len() as u64 && file.size + record_size > self.max_file_size
+            }
+        };
+        if rotate {
+            self.rotate()?;
+        }
+
+        let file = self.current.as_mut().expect("should have a current file");
+        file.writer
+            .write_all(&(record.len() as u32).to_le_bytes())?;
+        file.writer.write_all(record)?;
+        file.size += record_size;
+        Ok(())
+    }
+
+    /// Flushes the records buffered for the current file.
+    fn flush(&mut self) -> io::Result<()> {
+        match self.current {
+            Some(ref mut file) => file.writer.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Starts a new file, deleting the oldest files beyond the maximum number.
+    fn rotate(&mut self) -> io::Result<()> {
+        self.flush()?;
+        self.current = None;
+
+        let name = format!(
+            "{}{:020}-{:06}.{}",
+            FILE_PREFIX,
+            Timestamp::now().millis(),
+            self.sequence_number,
+            FILE_EXTENSION
+        );
+        self.sequence_number += 1;
+        let mut writer = BufWriter::new(
+            OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(self.dir.join(name))?,
+        );
+        writer.write_all(MAGIC)?;
+        self.current = Some(CaptureFile {
+            writer,
+            size: MAGIC.len() as u64,
+        });
+
+        let files = capture_files(&self.dir)?;
+        let excess = files.len().saturating_sub(self.max_files);
+        for path in &files[..excess] {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the capture files at the given path, in chronological order.
+///
+/// If the path is a directory, all capture files in it are returned, otherwise the path itself.
+pub(crate) fn capture_files(path: &Path) -> io::Result<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut files = Vec::new();
+    for entry in fs::read_dir(path)? {
+        let path = entry?.path();
+        let is_capture = path.extension().map_or(false, |ext| ext == FILE_EXTENSION)
+            && path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map_or(false, |name| name.starts_with(FILE_PREFIX));
+        if is_capture {
+            files.push(path);
+        }
+    }
+    // File names start with the creation time, so they sort chronologically.
+    files.sort();
+    Ok(files)
+}
+
+/// Error reading a capture file.
+#[derive(Debug, Error)]
+pub enum CaptureError {
+    /// Failed to read the file.
+    #[error("failed to read {}: {error}", path.display())]
+    Io {
+        /// The path of the capture file.
+        path: PathBuf,
+        /// The underlying error.
+        #[source]
+        error: io::Error,
+    },
+    /// The file does not start with the expected magic bytes.
+    #[error("{} is not a capture file or uses an unsupported format", path.display())]
+    NotACapture {
+        /// The path of the file.
+        path: PathBuf,
+    },
+    /// The file ends within a record, e.g. because the node crashed while writing it.
+    #[error("{} ends with an incomplete record", path.display())]
+    Truncated {
+        /// The path of the capture file.
+        path: PathBuf,
+    },
+    /// A record could not be decoded.
+    #[error("failed to decode record in {}: {error}", path.display())]
+    Decode {
+        /// The path of the capture file.
+        path: PathBuf,
+        /// The underlying error.
+        #[source]
+        error: rmp_serde::decode::Error,
+    },
+}
+
+/// Reader of the messages in a capture file.
+pub(crate) struct CaptureReader<M> {
+    path: PathBuf,
+    reader: BufReader<File>,
+    _message: PhantomData<M>,
+}
+
+impl<M: DeserializeOwned> CaptureReader<M> {
+    /// Opens a capture file, checking its header.
+    pub(crate) fn open(path: &Path) -> Result<Self, CaptureError> {
+        let io_error = |error| CaptureError::Io {
+            path: path.to_path_buf(),
+            error,
+        };
+        let mut reader = BufReader::new(File::open(path).map_err(io_error)?);
+
+        let mut magic = [0; MAGIC.len()];
+        match reader.read_exact(&mut magic) {
+            Ok(()) if magic == *MAGIC => (),
+            Ok(()) => {
+                return Err(CaptureError::NotACapture {
+                    path: path.to_path_buf(),
+                })
+            }
+            Err(error) if error.kind() == ErrorKind::UnexpectedEof => {
+                return Err(CaptureError::NotACapture {
+                    path: path.to_path_buf(),
+                })
+            }
+            Err(error) => return Err(io_error(error)),
+        }
+
+        Ok(CaptureReader {
+            path: path.to_path_buf(),
+            reader,
+            _message: PhantomData,
+        })
+    }
+
+    /// Reads the next record, returning `None` at the end of the file.
+    fn read_record(&mut self) -> Result<Option<Vec<u8>>, CaptureError> {
+        let mut size = [0; RECORD_SIZE_LENGTH as usize];
+        let mut read = 0;
+        while read < size.len() {
+            match self.reader.read(&mut size[read..]) {
+                Ok(0) if read == 0 => return Ok(None),
+                Ok(0) => return Err(self.truncated()),
+                Ok(count) => read += count,
+                Err(error) if error.kind() == ErrorKind::Interrupted => (),
+                Err(error) => return Err(self.io_error(error)),
+            }
+        }
+
+        let mut record = vec![0; u32::from_le_bytes(size) as usize];
+        match self.reader.read_exact(&mut record) {
+            Ok(()) => Ok(Some(record)),
+            Err(error) if error.kind() == ErrorKind::UnexpectedEof => Err(self.truncated()),
+            Err(error) => Err(self.io_error(error)),
+        }
+    }
+
+    fn io_error(&self, error: io::Error) -> CaptureError {
+        CaptureError::Io {
+            path: self.path.clone(),
+            error,
+        }
+    }
+
+    fn truncated(&self) -> CaptureError {
+        CaptureError::Truncated {
+            path: self.path.clone(),
+        }
+    }
+}
+
+impl<M: DeserializeOwned> Iterator for CaptureReader<M> {
+    /// A captured message and the size of its record.
+    type Item = Result<(CapturedMessage<M>, usize), CaptureError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = match self.read_record() {
+            Ok(Some(record)) => record,
+            Ok(None) => return None,
+            Err(error) => return Some(Err(error)),
+        };
+        let result = rmp_serde::from_read_ref(&record)
+            .map(|captured| (captured, record.len()))
+            .map_err(|error| CaptureError::Decode {
+                path: self.path.clone(),
+                error,
+            });
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    type TestMessage = Message<String>;
+
+    fn read_all(dir: &Path) -> Vec<CapturedMessage<TestMessage>> {
+        capture_files(dir)
+            .unwrap()
+            .iter()
+            .flat_map(|path| CaptureReader::<TestMessage>::open(path).unwrap())
+            .map(|result| result.unwrap().0)
+            .collect()
+    }
+
+    fn wait_for_records(dir: &Path, count: usize) -> Vec<CapturedMessage<TestMessage>> {
+        for _ in 0..100 {
+            let captured = read_all(dir);
+            if captured.len() >= count {
+                return captured;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        panic!("recorder did not write {} records in time", count);
+    }
+
+    #[test]
+    fn should_roundtrip_recorded_messages() {
+        let mut rng = crate::new_rng();
+        let tempdir = tempfile::tempdir().unwrap();
+        let dir = tempdir.path().join("traffic");
+        let peer = NodeId::random(&mut rng);
+
+        let dropped_records = IntCounter::new("dropped", "dropped records").unwrap();
+        let recorder = TrafficRecorder::start(
+            dir.clone(),
+            &TrafficRecorderConfig::default(),
+            dropped_records.clone(),
+        )
+        .expect("should start recorder");
+        recorder.record(
+            Direction::Incoming,
+            peer,
+            &Message::Payload("ping".to_string()),
+        );
+        recorder.record(
+            Direction::Outgoing,
+            peer,
+            &Message::Payload("pong".to_string()),
+        );
+        // Records are flushed once the recorder stops.
+        drop(recorder);
+
+        let captured = wait_for_records(&dir, 2);
+        assert_eq!(captured.len(), 2);
+        assert_eq!(captured[0].direction, Direction::Incoming);
+        assert_eq!(captured[0].peer, peer);
+        assert!(matches!(&captured[0].message, Message::Payload(payload) if payload == "ping"));
+        assert_eq!(captured[1].direction, Direction::Outgoing);
+        assert!(matches!(&captured[1].message, Message::Payload(payload) if payload == "pong"));
+        assert_eq!(dropped_records.get(), 0);
+    }
+
+    #[test]
+    fn should_count_records_dropped_when_queue_is_full() {
+        let mut rng = crate::new_rng();
+        let peer = NodeId::random(&mut rng);
+        let (sender, _receiver) = mpsc::channel(1);
+        let recorder = TrafficRecorder {
+            sender,
+            dropped_records: IntCounter::new("dropped", "dropped records").unwrap(),
+        };
+
+        for _ in 0..3 {
+            recorder.record(
+                Direction::Incoming,
+                peer,
+                &Message::Payload("ping".to_string()),
+            );
+        }
+
+        assert_eq!(recorder.dropped_records.get(), 2);
+    }
+
+    #[test]
+    fn should_rotate_and_delete_old_files() {
+        let mut rng = crate::new_rng();
+        let tempdir = tempfile::tempdir().unwrap();
+        let peer = NodeId::random(&mut rng);
+        let mut files = RotatingFiles::new(tempdir.path().to_path_buf(), 100, 2);
+
+        for index in 0..5 {
+            let captured = CapturedMessage {
+                timestamp: Timestamp::now(),
+                direction: Direction::Incoming,
+                peer,
+                message: Message::Payload(format!("{:080}", index)),
+            };
+            files.write(&rmp_serde::to_vec(&captured).unwrap()).unwrap();
+        }
+        files.flush().unwrap();
+
+        // Every record exceeds the maximum file size, so each is written to a new file.
+        let captured = read_all(tempdir.path());
+        assert_eq!(capture_files(tempdir.path()).unwrap().len(), 2);
+        assert_eq!(captured.len(), 2);
+        assert!(
+            matches!(&captured[0].message, Message::Payload(payload) if payload.ends_with('3'))
+        );
+        assert!(
+            matches!(&captured[1].message, Message::Payload(payload) if payload.ends_with('4'))
+        );
+    }
+
+    #[test]
+    fn should_reject_truncated_and_foreign_files() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("foreign.capture");
+        fs::write(&path, b"not a capture").unwrap();
+        assert!(matches!(
+            CaptureReader::<TestMessage>::open(&path),
+            Err(CaptureError::NotACapture { .. })
+        ));
+
+        let mut contents = MAGIC.to_vec();
+        contents.extend_from_slice(&10u32.to_le_bytes());
+        contents.extend_from_slice(b"short");
+        fs::write(&path, contents).unwrap();
+        let mut reader = CaptureReader::<TestMessage>::open(&path).unwrap();
+        assert!(matches!(
+            reader.next(),
+            Some(Err(CaptureError::Truncated { .. }))
+        ));
+    }
+}
@@ -8,7 +8,8 @@
 //! * [temporary until refactored] holding `DeployMetadata` for each deploy,
 //! * holding a read-only copy of the chainspec,
 //! * keeping an index of blocks by height and
-//! * [unimplemented] managing disk usage by pruning blocks and deploys from storage.
+//! * managing disk usage by pruning blocks and deploys from storage according to a configurable
+//!   retention policy.
 //!
 //! Any I/O performed by the component is done on the event handling thread, this is on purpose as
 //! the assumption is that caching by LMDB will offset any gains from offloading it onto a separate
@@ -28,8 +29,21 @@
 //!
 //! ## Indices
 //!
-//! The current implementation keeps only in-memory indices, which are not persisted, based upon the
-//! estimate that they are reasonably quick to rebuild on start-up and do not take up much memory.
+//! Secondary indices (blocks by height, switch blocks by era ID and blocks by contained deploy) are
+//! persisted in their own LMDB databases and updated in the same transaction as the block itself.
+//! They are only rebuilt from the block store if they are missing, e.g. after upgrading from a
+//! version which kept them in memory, after a hard reset, or if an integrity check finds them to
+//! disagree with the block store.
+//!
+//! Events emitted by contracts are indexed by contract hash and topic, and the index is updated in
+//! the same transaction as the execution results containing the events.
+//!
+//! ## Retention
+//!
+//! If a retention policy is configured, block bodies, deploys, execution results and transfers of
+//! blocks outside of the retention window are pruned after each block is stored. Block headers,
+//! finality signatures, switch blocks and the deploy index are always kept, so that requests for
+//! pruned data can be told apart from requests for unknown data.
 //!
 //! ## Errors
 //!
@@ -37,7 +51,10 @@
 //! Corruption, temporary resource exhaustion and potential bugs.
 
 mod blob_cache;
+mod indices;
+mod legacy;
 mod lmdb_ext;
+mod retention;
 
 #[cfg(test)]
 mod tests;
@@ -45,7 +62,7 @@ mod tests;
 #[cfg(test)]
 use std::{collections::BTreeSet, convert::TryFrom};
 use std::{
-    collections::{btree_map::Entry, BTreeMap, HashSet},
+    collections::{HashMap, HashSet},
     fmt::{self, Display, Formatter},
     fs, io, mem,
     path::{Path, PathBuf},
@@ -55,7 +72,8 @@ use std::{
 use datasize::DataSize;
 use derive_more::From;
 use lmdb::{
-    Cursor, Database, DatabaseFlags, Environment, EnvironmentFlags, Transaction, WriteFlags,
+    Cursor, Database, DatabaseFlags, Environment, EnvironmentFlags, RwTransaction, Transaction,
+    WriteFlags,
 };
 use serde::{Deserialize, Serialize};
 use static_assertions::const_assert;
@@ -65,7 +83,7 @@ use thiserror::Error;
 use tracing::{debug, error, info};
 
 use casper_execution_engine::shared::newtypes::Blake2bHash;
-use casper_types::{EraId, ExecutionResult, ProtocolVersion, Transfer, Transform};
+use casper_types::{ContractHash, EraId, ExecutionResult, ProtocolVersion, Transfer, Transform};
 
 use super::Component;
 #[cfg(test)]
@@ -78,14 +96,18 @@ use crate::{
     fatal,
     reactor::ReactorEvent,
     types::{
-        Block, BlockBody, BlockHash, BlockHeader, BlockHeaderWithMetadata, BlockSignatures, Deploy,
-        DeployHash, DeployHeader, DeployMetadata, Item, SharedObject, TimeDiff,
+        Block, BlockBody, BlockHash, BlockHeader, BlockHeaderWithMetadata, BlockSignatures,
+        ContractEventCursor, Deploy, DeployHash, DeployHeader, DeployMetadata,
+        EmittedContractEvent, Item, LightClientBundle, SharedObject, TimeDiff,
     },
     utils::{display_error, WithDir},
     NodeRng,
 };
 use blob_cache::BlobCache;
+use indices::Indices;
+use legacy::LegacyDeployMetadata;
 use lmdb_ext::{LmdbExtError, TransactionExt, WriteTransactionExt};
+use retention::RetentionConfig;
 
 /// Filename for the LMDB database created by the Storage component.
 const STORAGE_DB_FILENAME: &str = "storage.lmdb";
@@ -106,7 +128,7 @@ const DEFAULT_MAX_DEPLOY_METADATA_STORE_SIZE: usize = 300 * GIB;
 /// Default max state store size.
 const DEFAULT_MAX_STATE_STORE_SIZE: usize = 10 * GIB;
 /// Maximum number of allowed dbs.
-const MAX_DB_COUNT: u32 = 7;
+const MAX_DB_COUNT: u32 = 11;
 
 /// OS-specific lmdb flags.
 #[cfg(not(target_os = "macos"))]
@@ -207,12 +229,14 @@ pub struct Storage {
     /// The state storage database.
     #[data_size(skip)]
     state_store_db: Database,
-    /// A map of block height to block ID.
-    block_height_index: BTreeMap<u64, BlockHash>,
-    /// A map of era ID to switch block ID.
-    switch_block_era_id_index: BTreeMap<EraId, BlockHash>,
-    /// A map of deploy hashes to hashes of blocks containing them.
-    deploy_hash_index: BTreeMap<DeployHash, BlockHash>,
+    /// The persisted secondary indices of the block store.
+    #[data_size(skip)]
+    indices: Indices,
+    /// Retention policy for historical block data.
+    retention: RetentionConfig,
+    /// The maximum time-to-live of deploys. Blocks younger than this are never pruned, as their
+    /// deploys are still required to detect replays.
+    deploy_max_ttl: TimeDiff,
     /// Whether or not memory deduplication is enabled.
     enable_mem_deduplication: bool,
     /// Pool of loaded items.
@@ -259,6 +283,7 @@ impl Storage {
         cfg: &WithDir<Config>,
         hard_reset_to_start_of_era: Option<EraId>,
         protocol_version: ProtocolVersion,
+        deploy_max_ttl: TimeDiff,
         should_check_integrity: bool,
     ) -> Result<Self, Error> {
         let config = cfg.value();
@@ -298,64 +323,47 @@ impl Storage {
         let state_store_db = env.create_db(Some("state_store"), DatabaseFlags::empty())?;
         let block_body_db = env.create_db(Some("block_body"), DatabaseFlags::empty())?;
 
-        // We now need to restore the block-height index. Log messages allow timing here.
-        info!("reindexing block store");
-        let mut block_height_index = BTreeMap::new();
-        let mut switch_block_era_id_index = BTreeMap::new();
-        let mut deploy_hash_index = BTreeMap::new();
-        let mut block_txn = env.begin_rw_txn()?;
-        let mut cursor = block_txn.open_rw_cursor(block_header_db)?;
-
-        let mut deleted_block_hashes = HashSet::new();
-        // Note: `iter_start` has an undocumented panic if called on an empty database. We rely on
-        //       the iterator being at the start when created.
-        for (raw_key, raw_val) in cursor.iter() {
-            let block: BlockHeader = lmdb_ext::deserialize(raw_val)?;
-            if let Some(invalid_era) = hard_reset_to_start_of_era {
+        let indices = Indices::open(&env)?;
+
+        let mut deleted_block_hashes = HashMap::new();
+        if let Some(invalid_era) = hard_reset_to_start_of_era {
+            info!("purging block store for hard reset");
+            let mut block_txn = env.begin_rw_txn()?;
+            let mut cursor = block_txn.open_rw_cursor(block_header_db)?;
+
+            // Note: `iter_start` has an undocumented panic if called on an empty database. We rely
+            //       on the iterator being at the start when created.
+            for (_, raw_val) in cursor.iter() {
+                let block: BlockHeader = lmdb_ext::deserialize(raw_val)?;
                 // Remove blocks that are in to-be-upgraded eras, but have obsolete protocol
                 // versions - they were most likely created before the upgrade and should be
                 // reverted.
                 if block.era_id() >= invalid_era && block.protocol_version() < protocol_version {
-                    let _ = deleted_block_hashes.insert(block.hash());
+                    let _ = deleted_block_hashes.insert(block.hash(), block.height());
                     cursor.del(WriteFlags::empty())?;
-                    continue;
                 }
             }
-
-            if should_check_integrity {
-                assert_eq!(
-                    raw_key,
-                    block.hash().as_ref(),
-                    "found corrupt block in database"
-                );
-            }
-
-            insert_to_block_header_indices(
-                &mut block_height_index,
-                &mut switch_block_era_id_index,
-                &block,
-            )?;
-
-            let mut body_txn = env.begin_ro_txn()?;
-            let block_body: BlockBody = body_txn
-                .get_value(block_body_db, block.body_hash())?
-                .expect("non-existent block body referred to by header");
-
-            if should_check_integrity {
-                assert_eq!(
-                    *block.body_hash(),
-                    block_body.hash(),
-                    "found corrupt block body in database"
-                );
+            drop(cursor);
+            // Pruning restarts from genesis, as blocks may be re-added below the current progress.
+            if !deleted_block_hashes.is_empty() {
+                let _ = block_txn.del_value(state_store_db, &retention::PRUNING_PROGRESS_KEY)?;
             }
+            block_txn.commit()?;
+        }
 
-            insert_to_deploy_index(&mut deploy_hash_index, block.hash(), &block_body)?;
+        // The indices only need to be rebuilt if they are known to be stale or if they are missing
+        // entirely, which is the case when opening a database written by a version that kept them
+        // in memory. A full integrity check also verifies every block against the indices.
+        let needs_reindex = if should_check_integrity {
+            !indices.check(&env, block_header_db, block_body_db)?
+        } else {
+            !deleted_block_hashes.is_empty() || indices.are_missing(&env, block_header_db)?
+        };
+        if needs_reindex {
+            indices.rebuild(&env, block_header_db, block_body_db)?;
         }
-        info!("block store reindexing complete");
-        drop(cursor);
-        block_txn.commit()?;
 
-        let deleted_block_hashes_raw = deleted_block_hashes.iter().map(BlockHash::as_ref).collect();
+        let deleted_block_hashes_raw = deleted_block_hashes.keys().map(BlockHash::as_ref).collect();
 
         initialize_block_body_db(
             &env,
@@ -369,7 +377,13 @@ impl Storage {
             &deleted_block_hashes_raw,
             should_check_integrity,
         )?;
-        initialize_deploy_metadata_db(&env, &deploy_metadata_db, &deleted_block_hashes)?;
+        initialize_deploy_metadata_db(
+            &env,
+            &deploy_metadata_db,
+            &state_store_db,
+            &indices,
+            &deleted_block_hashes,
+        )?;
 
         Ok(Storage {
             root,
@@ -381,9 +395,9 @@ impl Storage {
             deploy_metadata_db,
             transfer_db,
             state_store_db,
-            block_height_index,
-            switch_block_era_id_index,
-            deploy_hash_index,
+            indices,
+            retention: config.retention,
+            deploy_max_ttl,
             enable_mem_deduplication: config.enable_mem_deduplication,
             deploy_cache: BlobCache::new(config.mem_pool_prune_interval),
         })
@@ -485,17 +499,10 @@ impl Storage {
                     txn.abort();
                     return Ok(responder.respond(false).ignore());
                 }
+                self.indices
+                    .insert_block(&mut txn, block.header(), block.body())?;
                 txn.commit()?;
-                insert_to_block_header_indices(
-                    &mut self.block_height_index,
-                    &mut self.switch_block_era_id_index,
-                    block.header(),
-                )?;
-                insert_to_deploy_index(
-                    &mut self.deploy_hash_index,
-                    block.header().hash(),
-                    block.body(),
-                )?;
+                self.prune_old_blocks()?;
                 responder.respond(true).ignore()
             }
             StorageRequest::GetBlock {
@@ -539,12 +546,10 @@ impl Storage {
                 let mut txn = self.env.begin_ro_txn()?;
                 responder
                     .respond(
-                        self.switch_block_era_id_index
-                            .keys()
-                            .last()
-                            .and_then(|&era_id| {
-                                self.get_switch_block_by_era_id(&mut txn, era_id)
-                                    .transpose()
+                        self.indices
+                            .highest_switch_block_hash(&mut txn)?
+                            .and_then(|block_hash| {
+                                self.get_single_block(&mut txn, &block_hash).transpose()
                             })
                             .transpose()?,
                     )
@@ -554,12 +559,8 @@ impl Storage {
                 block_hash,
                 responder,
             } => responder
-                // TODO: Find a solution for efficiently retrieving the blocker header without the
-                // block. Deserialization that allows trailing bytes could be a possible solution.
-                .respond(
-                    self.get_single_block(&mut self.env.begin_ro_txn()?, &block_hash)?
-                        .map(|block| block.header().clone()),
-                )
+                // Note: The header is retrieved on its own, as the block body may have been pruned.
+                .respond(self.get_single_block_header(&mut self.env.begin_ro_txn()?, &block_hash)?)
                 .ignore(),
             StorageRequest::GetBlockTransfers {
                 block_hash,
@@ -593,6 +594,7 @@ impl Storage {
                 .ignore(),
             StorageRequest::PutExecutionResults {
                 block_hash,
+                block_height,
                 execution_results,
                 responder,
             } => {
@@ -611,9 +613,28 @@ impl Storage {
                             continue;
                         } else {
                             debug!(%deploy_hash, %block_hash, "different execution result");
+                            if let ExecutionResult::Success { events, .. } = prev {
+                                self.indices.remove_contract_events(
+                                    &mut txn,
+                                    block_height,
+                                    *block_hash,
+                                    deploy_hash,
+                                    events,
+                                )?;
+                            }
                         }
                     }
 
+                    if let ExecutionResult::Success { events, .. } = &execution_result {
+                        self.indices.insert_contract_events(
+                            &mut txn,
+                            block_height,
+                            *block_hash,
+                            deploy_hash,
+                            events,
+                        )?;
+                    }
+
                     if let ExecutionResult::Success { effect, .. } = execution_result.clone() {
                         for transform_entry in effect.transforms {
                             if let Transform::WriteTransfer(transfer) = transform_entry.transform {
@@ -652,6 +673,23 @@ impl Storage {
                 txn.commit()?;
                 responder.respond(()).ignore()
             }
+            StorageRequest::GetContractEvents {
+                contract_hash,
+                topic,
+                after,
+                limit,
+                responder,
+            } => {
+                let mut txn = self.env.begin_ro_txn()?;
+                let events = self.get_contract_events(
+                    &mut txn,
+                    &contract_hash,
+                    topic.as_deref(),
+                    after.as_ref(),
+                    limit,
+                )?;
+                responder.respond(events).ignore()
+            }
             StorageRequest::GetDeployAndMetadata {
                 deploy_hash,
                 responder,
@@ -716,13 +754,7 @@ impl Storage {
             }
             StorageRequest::GetHighestBlockWithMetadata { responder } => {
                 let mut txn = self.env.begin_ro_txn()?;
-                let highest_block: Block = if let Some(block) = self
-                    .block_height_index
-                    .keys()
-                    .last()
-                    .and_then(|&height| self.get_block_by_height(&mut txn, height).transpose())
-                    .transpose()?
-                {
+                let highest_block: Block = if let Some(block) = self.get_highest_block(&mut txn)? {
                     block
                 } else {
                     return Ok(responder.respond(None).ignore());
@@ -772,25 +804,162 @@ impl Storage {
             StorageRequest::GetFinalizedDeploys { ttl, responder } => {
                 responder.respond(self.get_finalized_deploys(ttl)?).ignore()
             }
+            StorageRequest::GetRetainedStateRootHashes {
+                keep_blocks,
+                responder,
+            } => responder
+                .respond(self.read_retained_state_root_hashes(keep_blocks)?)
+                .ignore(),
+            StorageRequest::GetLightClientBundle {
+                trusted_block_hash,
+                max_switch_blocks,
+                responder,
+            } => {
+                let mut txn = self.env.begin_ro_txn()?;
+                let bundle =
+                    self.get_light_client_bundle(&mut txn, &trusted_block_hash, max_switch_blocks)?;
+                responder.respond(bundle).ignore()
+            }
         })
     }
 
+    /// Prunes block bodies, deploys, execution results and transfers of blocks which have fallen
+    /// outside of the retention window.
+    ///
+    /// At most `max_blocks_per_pass` blocks are considered per call. Progress is persisted, so
+    /// every block is only ever visited once.
+    fn prune_old_blocks(&self) -> Result<(), Error> {
+        if !self.retention.is_enabled() {
+            return Ok(());
+        }
+
+        let mut txn = self.env.begin_rw_txn()?;
+        let highest_block_header = match self.indices.highest_block_hash(&mut txn)? {
+            Some(block_hash) => match self.get_single_block_header(&mut txn, &block_hash)? {
+                Some(block_header) => block_header,
+                None => return Ok(()),
+            },
+            None => return Ok(()),
+        };
+
+        let first_height_of_kept_eras = if self.retention.keep_eras > 0 {
+            match highest_block_header
+                .era_id()
+                .checked_sub(self.retention.keep_eras)
+            {
+                // The switch block of the era preceding the oldest kept era.
+                Some(era_id) => self
+                    .get_switch_block_header_by_era_id(&mut txn, era_id)?
+                    .map(|switch_block_header| switch_block_header.height() + 1),
+                None => Some(0),
+            }
+        } else {
+            None
+        };
+        let prune_below = self
+            .retention
+            .prune_below_height(highest_block_header.height(), first_height_of_kept_eras);
+
+        let mut next_height: u64 = txn
+            .get_value(self.state_store_db, &retention::PRUNING_PROGRESS_KEY)?
+            .unwrap_or_default();
+        let pass_end =
+            prune_below.min(next_height.saturating_add(self.retention.max_blocks_per_pass));
+        if next_height >= pass_end {
+            return Ok(());
+        }
+
+        while next_height < pass_end {
+            if let Some(block_header) = self.get_block_header_by_height(&mut txn, next_height)? {
+                if block_header.timestamp().elapsed() < self.deploy_max_ttl {
+                    break;
+                }
+                if !block_header.is_switch_block() {
+                    self.prune_block(&mut txn, &block_header)?;
+                }
+            }
+            next_height += 1;
+        }
+
+        let _ = txn.put_value(
+            self.state_store_db,
+            &retention::PRUNING_PROGRESS_KEY,
+            &next_height,
+            true,
+        )?;
+        txn.commit()?;
+        debug!(%next_height, "pruned old blocks");
+        Ok(())
+    }
+
+    /// Deletes the body of a block along with its deploys, their execution results and the block's
+    /// transfers. The header and finality signatures are retained.
+    fn prune_block(
+        &self,
+        txn: &mut RwTransaction,
+        block_header: &BlockHeader,
+    ) -> Result<(), Error> {
+        let block_body: BlockBody =
+            match txn.get_value(self.block_body_db, block_header.body_hash())? {
+                Some(block_body) => block_body,
+                None => return Ok(()),
+            };
+
+        // Bodies without any deploys can be shared by several blocks of the same proposer, and are
+        // small enough to be kept anyway.
+        if block_body.deploy_hashes().is_empty() && block_body.transfer_hashes().is_empty() {
+            return Ok(());
+        }
+
+        for deploy_hash in block_body
+            .deploy_hashes()
+            .iter()
+            .chain(block_body.transfer_hashes().iter())
+        {
+            let _ = txn.del_value(self.deploy_db, deploy_hash)?;
+            if let Some(metadata) = self.get_deploy_metadata(txn, deploy_hash)? {
+                for (block_hash, execution_result) in &metadata.execution_results {
+                    let events = match execution_result {
+                        ExecutionResult::Success { events, .. } if !events.is_empty() => events,
+                        _ => continue,
+                    };
+                    // Events are indexed under the height of their block, whose header is retained.
+                    let block_height = match self.get_single_block_header(txn, block_hash)? {
+                        Some(header) => header.height(),
+                        None => continue,
+                    };
+                    self.indices.remove_contract_events(
+                        txn,
+                        block_height,
+                        *block_hash,
+                        *deploy_hash,
+                        events,
+                    )?;
+                }
+            }
+            let _ = txn.del_value(self.deploy_metadata_db, deploy_hash)?;
+        }
+        let _ = txn.del_value(self.transfer_db, &block_header.hash())?;
+        let _ = txn.del_value(self.block_body_db, block_header.body_hash())?;
+        Ok(())
+    }
+
     /// Retrieves single block header by height by looking it up in the index and returning it.
     fn get_block_header_and_metadata_by_height<Tx: Transaction>(
         &self,
         tx: &mut Tx,
         height: u64,
     ) -> Result<Option<BlockHeaderWithMetadata>, Error> {
-        let block_hash = match self.block_height_index.get(&height) {
+        let block_hash = match self.indices.block_hash_by_height(tx, height)? {
             None => return Ok(None),
             Some(block_hash) => block_hash,
         };
-        let block_header = match self.get_single_block_header(tx, block_hash)? {
+        let block_header = match self.get_single_block_header(tx, &block_hash)? {
             None => return Ok(None),
             Some(block_header) => block_header,
         };
-        let block_signatures = match self.get_finality_signatures(tx, block_hash)? {
-            None => BlockSignatures::new(*block_hash, block_header.era_id()),
+        let block_signatures = match self.get_finality_signatures(tx, &block_hash)? {
+            None => BlockSignatures::new(block_hash, block_header.era_id()),
             Some(signatures) => signatures,
         };
         Ok(Some(BlockHeaderWithMetadata {
@@ -799,6 +968,94 @@ impl Storage {
         }))
     }
 
+    /// Attaches the stored finality signatures of its block to a block header.
+    fn get_block_header_with_metadata<Tx: Transaction>(
+        &self,
+        tx: &mut Tx,
+        block_header: BlockHeader,
+    ) -> Result<BlockHeaderWithMetadata, Error> {
+        let block_hash = block_header.hash();
+        let block_signatures = match self.get_finality_signatures(tx, &block_hash)? {
+            None => BlockSignatures::new(block_hash, block_header.era_id()),
+            Some(signatures) => signatures,
+        };
+        Ok(BlockHeaderWithMetadata {
+            block_header,
+            block_signatures,
+        })
+    }
+
+    /// Retrieves the block headers and finality signatures allowing a light client to follow the
+    /// validator set from the trusted block to the highest block, or to the last of at most
+    /// `max_switch_blocks` switch blocks following the trusted block.
+    fn get_light_client_bundle<Tx: Transaction>(
+        &self,
+        tx: &mut Tx,
+        trusted_block_hash: &BlockHash,
+        max_switch_blocks: u32,
+    ) -> Result<Option<LightClientBundle>, Error> {
+        let trusted_block_header = match self.get_single_block_header(tx, trusted_block_hash)? {
+            None => return Ok(None),
+            Some(block_header) => block_header,
+        };
+
+        // Walk back to the closest switch block, which announces the validators of the following
+        // era.
+        let mut trusted_ancestors = vec![trusted_block_header];
+        loop {
+            let parent_hash = match trusted_ancestors.last() {
+                Some(lowest) if !lowest.is_switch_block() && lowest.height() > 0 => {
+                    *lowest.parent_hash()
+                }
+                _ => break,
+            };
+            match self.get_single_block_header(tx, &parent_hash)? {
+                Some(parent) => trusted_ancestors.push(parent),
+                None => break,
+            }
+        }
+
+        let mut next_era_id = match trusted_ancestors.last() {
+            Some(lowest) if lowest.is_switch_block() => lowest.era_id().successor(),
+            Some(lowest) => lowest.era_id(),
+            None => return Ok(None),
+        };
+        let mut switch_blocks = Vec::new();
+        while switch_blocks.len() < max_switch_blocks as usize {
+            let switch_block_header =
+                match self.get_switch_block_header_by_era_id(tx, next_era_id)? {
+                    None => break,
+                    Some(block_header) => block_header,
+                };
+            next_era_id = switch_block_header.era_id().successor();
+            switch_blocks.push(self.get_block_header_with_metadata(tx, switch_block_header)?);
+        }
+
+        // If the switch blocks were cut short, the bundle ends at the last one included, otherwise
+        // at the highest block.
+        let tip = match switch_blocks.last() {
+            Some(last_switch_block) if switch_blocks.len() >= max_switch_blocks as usize => {
+                last_switch_block.clone()
+            }
+            _ => {
+                let highest_block_header = match self.indices.highest_block_hash(tx)? {
+                    None => return Ok(None),
+                    Some(block_hash) => match self.get_single_block_header(tx, &block_hash)? {
+                        None => return Ok(None),
+                        Some(block_header) => block_header,
+                    },
+                };
+                self.get_block_header_with_metadata(tx, highest_block_header)?
+            }
+        };
+
+        Ok(Some(LightClientBundle {
+            trusted_ancestors: trusted_ancestors.iter().map(Into::into).collect(),
+            switch_blocks: switch_blocks.into_iter().map(Into::into).collect(),
+            tip: tip.into(),
+        }))
+    }
+
     // Retrieves a block header to handle a network request.
     pub fn read_block_header_and_finality_signatures_by_height(
         &self,
@@ -817,9 +1074,9 @@ impl Storage {
         tx: &mut Tx,
         height: u64,
     ) -> Result<Option<BlockHeader>, LmdbExtError> {
-        self.block_height_index
-            .get(&height)
-            .and_then(|block_hash| self.get_single_block_header(tx, block_hash).transpose())
+        self.indices
+            .block_hash_by_height(tx, height)?
+            .and_then(|block_hash| self.get_single_block_header(tx, &block_hash).transpose())
             .transpose()
     }
 
@@ -829,9 +1086,9 @@ impl Storage {
         tx: &mut Tx,
         height: u64,
     ) -> Result<Option<Block>, LmdbExtError> {
-        self.block_height_index
-            .get(&height)
-            .and_then(|block_hash| self.get_single_block(tx, block_hash).transpose())
+        self.indices
+            .block_hash_by_height(tx, height)?
+            .and_then(|block_hash| self.get_single_block(tx, &block_hash).transpose())
             .transpose()
     }
 
@@ -842,9 +1099,9 @@ impl Storage {
         tx: &mut Tx,
         era_id: EraId,
     ) -> Result<Option<BlockHeader>, LmdbExtError> {
-        self.switch_block_era_id_index
-            .get(&era_id)
-            .and_then(|block_hash| self.get_single_block_header(tx, block_hash).transpose())
+        self.indices
+            .switch_block_hash_by_era_id(tx, era_id)?
+            .and_then(|block_hash| self.get_single_block_header(tx, &block_hash).transpose())
             .transpose()
     }
 
@@ -855,9 +1112,9 @@ impl Storage {
         tx: &mut Tx,
         deploy_hash: DeployHash,
     ) -> Result<Option<BlockHeader>, LmdbExtError> {
-        self.deploy_hash_index
-            .get(&deploy_hash)
-            .and_then(|block_hash| self.get_single_block_header(tx, block_hash).transpose())
+        self.indices
+            .block_hash_by_deploy_hash(tx, &deploy_hash)?
+            .and_then(|block_hash| self.get_single_block_header(tx, &block_hash).transpose())
             .transpose()
     }
 
@@ -867,10 +1124,9 @@ impl Storage {
         &self,
         txn: &mut Tx,
     ) -> Result<Option<Block>, LmdbExtError> {
-        self.block_height_index
-            .keys()
-            .last()
-            .and_then(|&height| self.get_block_by_height(txn, height).transpose())
+        self.indices
+            .highest_block_hash(txn)?
+            .and_then(|block_hash| self.get_single_block(txn, &block_hash).transpose())
             .transpose()
     }
 
@@ -939,9 +1195,9 @@ impl Storage {
         tx: &mut Tx,
         era_id: EraId,
     ) -> Result<Option<Block>, LmdbExtError> {
-        self.switch_block_era_id_index
-            .get(&era_id)
-            .and_then(|block_hash| self.get_single_block(tx, block_hash).transpose())
+        self.indices
+            .switch_block_hash_by_era_id(tx, era_id)?
+            .and_then(|block_hash| self.get_single_block(tx, &block_hash).transpose())
             .transpose()
     }
 
@@ -968,6 +1224,52 @@ impl Storage {
         Some(blake_hashes)
     }
 
+    /// Retrieves the state root hashes whose global state must be retained when collecting
+    /// garbage.
+    ///
+    /// These are the state root hashes of the `keep_blocks` highest blocks, of all blocks whose
+    /// data has not been pruned by the retention policy, and of all switch blocks.
+    pub(crate) fn read_retained_state_root_hashes(
+        &self,
+        keep_blocks: u64,
+    ) -> Result<Vec<Digest>, Error> {
+        let mut txn = self.env.begin_ro_txn()?;
+        let highest_height = match self.indices.highest_block_hash(&mut txn)? {
+            Some(block_hash) => match self.get_single_block_header(&mut txn, &block_hash)? {
+                Some(block_header) => block_header.height(),
+                None => return Ok(Vec::new()),
+            },
+            None => return Ok(Vec::new()),
+        };
+
+        // Blocks at or above the pruning progress still have their data available.
+        let lowest_unpruned_height: u64 = if self.retention.is_enabled() {
+            txn.get_value(self.state_store_db, &retention::PRUNING_PROGRESS_KEY)?
+                .unwrap_or_default()
+        } else {
+            0
+        };
+        let lowest_height = (highest_height + 1)
+            .saturating_sub(keep_blocks)
+            .min(lowest_unpruned_height);
+
+        let mut state_root_hashes = Vec::new();
+        for height in lowest_height..=highest_height {
+            if let Some(block_header) = self.get_block_header_by_height(&mut txn, height)? {
+                state_root_hashes.push(*block_header.state_root_hash());
+            }
+        }
+        // Joining nodes synchronize the global state of switch blocks, so it is never discarded.
+        for block_hash in self.indices.switch_block_hashes(&mut txn)? {
+            if let Some(block_header) = self.get_single_block_header(&mut txn, &block_hash)? {
+                if block_header.height() < lowest_height {
+                    state_root_hashes.push(*block_header.state_root_hash());
+                }
+            }
+        }
+        Ok(state_root_hashes)
+    }
+
     /// Retrieves a single block header in a separate transaction from storage.
     fn get_single_block_header<Tx: Transaction>(
         &self,
@@ -1059,6 +1361,44 @@ impl Storage {
         Ok(tx.get_value(self.deploy_metadata_db, deploy_hash)?)
     }
 
+    /// Retrieves up to `limit` events emitted by the given contract in chronological order,
+    /// optionally restricted to a topic and to the events following the `after` cursor.
+    ///
+    /// Events whose execution results have since been pruned are skipped.
+    fn get_contract_events<Tx: Transaction>(
+        &self,
+        tx: &mut Tx,
+        contract_hash: &ContractHash,
+        topic: Option<&str>,
+        after: Option<&ContractEventCursor>,
+        limit: usize,
+    ) -> Result<Vec<EmittedContractEvent>, Error> {
+        let mut emitted_events = Vec::new();
+        for (block_height, block_hash, deploy_hash, event_index) in self
+            .indices
+            .contract_event_locations(tx, contract_hash, topic, after, limit)?
+        {
+            let metadata = match self.get_deploy_metadata(tx, &deploy_hash)? {
+                Some(metadata) => metadata,
+                None => continue,
+            };
+            if let Some(ExecutionResult::Success { events, .. }) =
+                metadata.execution_results.get(&block_hash)
+            {
+                if let Some(event) = events.get(event_index as usize) {
+                    emitted_events.push(EmittedContractEvent {
+                        block_hash,
+                        block_height,
+                        deploy_hash,
+                        event_index,
+                        event: event.clone(),
+                    });
+                }
+            }
+        }
+        Ok(emitted_events)
+    }
+
     /// Retrieves transfers associated with block.
     ///
     /// If no transfers are stored for the block, an empty transfers instance will be
@@ -1087,82 +1427,6 @@ impl Storage {
     }
 }
 
-/// Inserts the relevant entries to the two indices.
-///
-/// If a duplicate entry is encountered, neither index is updated and an error is returned.
-fn insert_to_block_header_indices(
-    block_height_index: &mut BTreeMap<u64, BlockHash>,
-    switch_block_era_id_index: &mut BTreeMap<EraId, BlockHash>,
-    block_header: &BlockHeader,
-) -> Result<(), Error> {
-    let block_hash = block_header.hash();
-    if let Some(first) = block_height_index.get(&block_header.height()) {
-        if *first != block_hash {
-            return Err(Error::DuplicateBlockIndex {
-                height: block_header.height(),
-                first: *first,
-                second: block_hash,
-            });
-        }
-    }
-
-    if block_header.is_switch_block() {
-        match switch_block_era_id_index.entry(block_header.era_id()) {
-            Entry::Vacant(entry) => {
-                let _ = entry.insert(block_hash);
-            }
-            Entry::Occupied(entry) => {
-                if *entry.get() != block_hash {
-                    return Err(Error::DuplicateEraIdIndex {
-                        era_id: block_header.era_id(),
-                        first: *entry.get(),
-                        second: block_hash,
-                    });
-                }
-            }
-        }
-    }
-
-    let _ = block_height_index.insert(block_header.height(), block_hash);
-    Ok(())
-}
-
-/// Inserts the relevant entries to the index.
-///
-/// If a duplicate entry is encountered, index is not updated and an error is returned.
-fn insert_to_deploy_index(
-    deploy_hash_index: &mut BTreeMap<DeployHash, BlockHash>,
-    block_hash: BlockHash,
-    block_body: &BlockBody,
-) -> Result<(), Error> {
-    if let Some(hash) = block_body
-        .deploy_hashes()
-        .iter()
-        .chain(block_body.transfer_hashes().iter())
-        .find(|hash| {
-            deploy_hash_index
-                .get(hash)
-                .map_or(false, |old_block_hash| *old_block_hash != block_hash)
-        })
-    {
-        return Err(Error::DuplicateDeployIndex {
-            deploy_hash: *hash,
-            first: deploy_hash_index[hash],
-            second: block_hash,
-        });
-    }
-
-    for hash in block_body
-        .deploy_hashes()
-        .iter()
-        .chain(block_body.transfer_hashes().iter())
-    {
-        deploy_hash_index.insert(*hash, block_hash);
-    }
-
-    Ok(())
-}
-
 /// On-disk storage configuration.
 #[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
@@ -1191,6 +1455,9 @@ pub struct Config {
     enable_mem_deduplication: bool,
     /// How many loads before memory duplication checks for dead references.
     mem_pool_prune_interval: u16,
+    /// Retention policy for block bodies, deploys and execution results.
+    #[serde(default)]
+    retention: RetentionConfig,
 }
 
 impl Default for Config {
@@ -1204,6 +1471,7 @@ impl Default for Config {
             max_state_store_size: DEFAULT_MAX_STATE_STORE_SIZE,
             enable_mem_deduplication: false,
             mem_pool_prune_interval: 1024,
+            retention: RetentionConfig::default(),
         }
     }
 }
@@ -1443,32 +1711,81 @@ fn initialize_block_metadata_db(
 fn initialize_deploy_metadata_db(
     env: &Environment,
     deploy_metadata_db: &Database,
-    deleted_block_hashes: &HashSet<BlockHash>,
-) -> Result<(), LmdbExtError> {
+    state_store_db: &Database,
+    indices: &Indices,
+    deleted_block_hashes: &HashMap<BlockHash, u64>,
+) -> Result<(), Error> {
     info!("initializing deploy metadata database");
     let mut txn = env.begin_rw_txn()?;
+
+    // Databases written before the introduction of contract events store execution results in a
+    // different layout, and need to be migrated.
+    let is_legacy_format = txn
+        .get_value::<_, u32>(*state_store_db, &legacy::DEPLOY_METADATA_FORMAT_VERSION_KEY)?
+        .is_none();
+    if is_legacy_format {
+        info!("migrating deploy metadata from legacy format");
+    }
+
+    let mut removed_events = Vec::new();
     let mut cursor = txn.open_rw_cursor(*deploy_metadata_db)?;
 
     for (raw_key, raw_val) in cursor.iter() {
-        let mut deploy_metadata: DeployMetadata = lmdb_ext::deserialize(raw_val)?;
+        let mut deploy_metadata: DeployMetadata = if is_legacy_format {
+            lmdb_ext::deserialize::<LegacyDeployMetadata>(raw_val)?.into()
+        } else {
+            lmdb_ext::deserialize(raw_val)?
+        };
         let len_before = deploy_metadata.execution_results.len();
 
+        let mut removed_results = Vec::new();
         deploy_metadata.execution_results = deploy_metadata
             .execution_results
             .drain()
-            .filter(|(block_hash, _)| !deleted_block_hashes.contains(block_hash))
+            .filter_map(|(block_hash, execution_result)| {
+                if let Some(block_height) = deleted_block_hashes.get(&block_hash) {
+                    removed_results.push((*block_height, block_hash, execution_result));
+                    None
+                } else {
+                    Some((block_hash, execution_result))
+                }
+            })
             .collect();
 
+        if !removed_results.is_empty() {
+            let deploy_hash = DeployHash::new(
+                Digest::try_from(raw_key)
+                    .map_err(|err| LmdbExtError::DataCorrupted(Box::new(err)))?,
+            );
+            for (block_height, block_hash, execution_result) in removed_results {
+                if let ExecutionResult::Success { events, .. } = execution_result {
+                    removed_events.push((block_height, block_hash, deploy_hash, events));
+                }
+            }
+        }
+
         // If the deploy's execution results are now empty, we just remove them entirely.
         if deploy_metadata.execution_results.is_empty() {
             cursor.del(WriteFlags::empty())?;
-        } else if len_before != deploy_metadata.execution_results.len() {
+        } else if is_legacy_format || len_before != deploy_metadata.execution_results.len() {
             let buffer = lmdb_ext::serialize(&deploy_metadata)?;
             cursor.put(&raw_key, &buffer, WriteFlags::empty())?;
         }
     }
 
     drop(cursor);
+
+    for (block_height, block_hash, deploy_hash, events) in removed_events {
+        indices.remove_contract_events(&mut txn, block_height, block_hash, deploy_hash, &events)?;
+    }
+    if is_legacy_format {
+        let _ = txn.put_value(
+            *state_store_db,
+            &legacy::DEPLOY_METADATA_FORMAT_VERSION_KEY,
+            &legacy::DEPLOY_METADATA_FORMAT_VERSION,
+            true,
+        )?;
+    }
     txn.commit()?;
 
     info!("deploy metadata database initialized");
@@ -0,0 +1,508 @@
+//! Persisted secondary indices.
+//!
+//! The block store is keyed by block hash only. To allow efficient lookups by height, era ID or
+//! deploy hash, the storage component maintains a set of secondary indices in their own LMDB
+//! databases, which map onto the hash of the relevant block:
+//!
+//! * block height -> block hash,
+//! * era ID -> switch block hash and
+//! * deploy hash -> hash of the block containing the deploy.
+//!
+//! Indices are updated in the same write transaction as the block they refer to, so they never
+//! diverge from the block store during regular operation. Heights and era IDs are encoded as
+//! big-endian integers, which makes LMDB's lexicographical key order coincide with numerical order
+//! and allows retrieving the highest entry directly.
+//!
+//! Additionally, events emitted by contracts are indexed by contract hash, and by contract hash and
+//! topic, mapping onto the block height, block hash, deploy hash and position of the event within
+//! the deploy's execution result.  Within a contract or topic, events are keyed in chronological
+//! order by block height, deploy hash and position.  This index is derived from the execution
+//! results rather than the block store, and is updated in the same write transaction as the
+//! execution results it refers to.
+
+use std::{collections::HashSet, convert::TryFrom};
+
+use lmdb::{Cursor, Database, DatabaseFlags, Environment, RwTransaction, Transaction};
+use tracing::info;
+
+use casper_types::{ContractEvent, ContractHash, EraId};
+
+use super::{
+    lmdb_ext::{self, LmdbExtError, TransactionExt, WriteTransactionExt},
+    Error,
+};
+use crate::{
+    crypto::hash::{self, Digest},
+    types::{BlockBody, BlockHash, BlockHeader, ContractEventCursor, DeployHash},
+};
+
+/// Name of the block height index database.
+const BLOCK_HEIGHT_INDEX_DB_NAME: &str = "block_height_index";
+/// Name of the switch block era ID index database.
+const SWITCH_BLOCK_ERA_ID_INDEX_DB_NAME: &str = "switch_block_era_id_index";
+/// Name of the deploy hash index database.
+const DEPLOY_HASH_INDEX_DB_NAME: &str = "deploy_hash_index";
+/// Name of the contract event index database.
+const CONTRACT_EVENT_INDEX_DB_NAME: &str = "contract_event_index";
+
+/// Handles to the databases holding the secondary indices of the block store.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Indices {
+    /// A map of block height to block ID.
+    block_height_db: Database,
+    /// A map of era ID to switch block ID.
+    switch_block_era_id_db: Database,
+    /// A map of deploy hashes to hashes of blocks containing them.
+    deploy_hash_db: Database,
+    /// A map of contract hash and topic to the locations of the events emitted under them.
+    contract_event_db: Database,
+}
+
+/// The location of an event emitted by a contract: the height and hash of the block, the hash of
+/// the deploy and the position of the event among the events emitted by the deploy.
+pub(super) type ContractEventLocation = (u64, BlockHash, DeployHash, u32);
+
+/// Tag of the keys indexing the events of a contract regardless of their topic.
+const CONTRACT_EVENT_ALL_TOPICS_TAG: u8 = 0;
+/// Tag of the keys indexing the events of a contract under a single topic.
+const CONTRACT_EVENT_SINGLE_TOPIC_TAG: u8 = 1;
+
+/// Returns the key under which a block is indexed by its height.
+fn height_key(height: u64) -> [u8; 8] {
+    height.to_be_bytes()
+}
+
+/// Returns the key under which a switch block is indexed by its era ID.
+fn era_id_key(era_id: EraId) -> [u8; 8] {
+    era_id.value().to_be_bytes()
+}
+
+/// Returns the prefix shared by the keys of all events emitted by a contract, optionally
+/// restricted to a single topic.
+///
+/// Topics are hashed to give every key prefix of a single topic the same length.
+fn contract_event_key_prefix(contract_hash: &ContractHash, topic: Option<&str>) -> Vec<u8> {
+    let mut prefix = contract_hash.as_bytes().to_vec();
+    match topic {
+        None => prefix.push(CONTRACT_EVENT_ALL_TOPICS_TAG),
+        Some(topic) => {
+            prefix.push(CONTRACT_EVENT_SINGLE_TOPIC_TAG);
+            prefix.extend_from_slice(hash::hash(topic).as_ref());
+        }
+    }
+    prefix
+}
+
+/// Returns the suffix of the key of an event, ordering the events sharing a key prefix
+/// chronologically.
+fn contract_event_key_suffix(
+    block_height: u64,
+    deploy_hash: &DeployHash,
+    event_index: u32,
+) -> Vec<u8> {
+    let mut suffix = block_height.to_be_bytes().to_vec();
+    suffix.extend_from_slice(deploy_hash.as_ref());
+    suffix.extend_from_slice(&event_index.to_be_bytes());
+    suffix
+}
+
+/// Returns the keys under which an event is indexed, among all events of the contract and among
+/// the events of the contract under the event's topic.
+fn contract_event_keys(event: &ContractEvent, location: &ContractEventLocation) -> [Vec<u8>; 2] {
+    let (block_height, _block_hash, deploy_hash, event_index) = location;
+    let suffix = contract_event_key_suffix(*block_height, deploy_hash, *event_index);
+    let key = |topic| {
+        let mut key = contract_event_key_prefix(&event.contract_hash, topic);
+        key.extend_from_slice(&suffix);
+        key
+    };
+    [key(None), key(Some(event.topic.as_str()))]
+}
+
+impl Indices {
+    /// Opens the index databases, creating them if they do not exist yet.
+    pub(super) fn open(env: &Environment) -> Result<Self, lmdb::Error> {
+        Ok(Indices {
+            block_height_db: env
+                .create_db(Some(BLOCK_HEIGHT_INDEX_DB_NAME), DatabaseFlags::empty())?,
+            switch_block_era_id_db: env.create_db(
+                Some(SWITCH_BLOCK_ERA_ID_INDEX_DB_NAME),
+                DatabaseFlags::empty(),
+            )?,
+            deploy_hash_db: env
+                .create_db(Some(DEPLOY_HASH_INDEX_DB_NAME), DatabaseFlags::empty())?,
+            contract_event_db: env
+                .create_db(Some(CONTRACT_EVENT_INDEX_DB_NAME), DatabaseFlags::empty())?,
+        })
+    }
+
+    /// Retrieves the hash of the block at the given height.
+    pub(super) fn block_hash_by_height<Tx: Transaction>(
+        &self,
+        tx: &mut Tx,
+        height: u64,
+    ) -> Result<Option<BlockHash>, LmdbExtError> {
+        tx.get_value(self.block_height_db, &height_key(height))
+    }
+
+    /// Retrieves the hash of the switch block of the given era.
+    pub(super) fn switch_block_hash_by_era_id<Tx: Transaction>(
+        &self,
+        tx: &mut Tx,
+        era_id: EraId,
+    ) -> Result<Option<BlockHash>, LmdbExtError> {
+        tx.get_value(self.switch_block_era_id_db, &era_id_key(era_id))
+    }
+
+    /// Retrieves the hash of the block containing the given deploy.
+    pub(super) fn block_hash_by_deploy_hash<Tx: Transaction>(
+        &self,
+        tx: &mut Tx,
+        deploy_hash: &DeployHash,
+    ) -> Result<Option<BlockHash>, LmdbExtError> {
+        tx.get_value(self.deploy_hash_db, deploy_hash)
+    }
+
+    /// Retrieves the hash of the highest block.
+    pub(super) fn highest_block_hash<Tx: Transaction>(
+        &self,
+        tx: &mut Tx,
+    ) -> Result<Option<BlockHash>, LmdbExtError> {
+        tx.get_last_value(self.block_height_db)
+    }
+
+    /// Retrieves the hash of the switch block with the highest era ID.
+    pub(super) fn highest_switch_block_hash<Tx: Transaction>(
+        &self,
+        tx: &mut Tx,
+    ) -> Result<Option<BlockHash>, LmdbExtError> {
+        tx.get_last_value(self.switch_block_era_id_db)
+    }
+
+    /// Retrieves the hashes of all switch blocks, in order of their era ID.
+    pub(super) fn switch_block_hashes<Tx: Transaction>(
+        &self,
+        tx: &mut Tx,
+    ) -> Result<Vec<BlockHash>, LmdbExtError> {
+        let mut cursor = tx.open_ro_cursor(self.switch_block_era_id_db)?;
+        let mut block_hashes = Vec::new();
+        for (_, raw_value) in cursor.iter() {
+            block_hashes.push(lmdb_ext::deserialize(raw_value)?);
+        }
+        Ok(block_hashes)
+    }
+
+    /// Inserts the index entries for a block.
+    ///
+    /// If a duplicate entry is encountered, an error is returned and the transaction must be
+    /// aborted, as some of the indices may already have been updated.
+    pub(super) fn insert_block(
+        &self,
+        txn: &mut RwTransaction,
+        block_header: &BlockHeader,
+        block_body: &BlockBody,
+    ) -> Result<(), Error> {
+        let block_hash = block_header.hash();
+        self.insert_to_block_header_indices(txn, block_hash, block_header)?;
+        self.insert_to_deploy_index(txn, block_hash, block_body)
+    }
+
+    /// Inserts the relevant entries to the block height and switch block indices.
+    fn insert_to_block_header_indices(
+        &self,
+        txn: &mut RwTransaction,
+        block_hash: BlockHash,
+        block_header: &BlockHeader,
+    ) -> Result<(), Error> {
+        let height = block_header.height();
+        if let Some(first) = self.block_hash_by_height(txn, height)? {
+            if first != block_hash {
+                return Err(Error::DuplicateBlockIndex {
+                    height,
+                    first,
+                    second: block_hash,
+                });
+            }
+        }
+
+        if block_header.is_switch_block() {
+            let era_id = block_header.era_id();
+            if let Some(first) = self.switch_block_hash_by_era_id(txn, era_id)? {
+                if first != block_hash {
+                    return Err(Error::DuplicateEraIdIndex {
+                        era_id,
+                        first,
+                        second: block_hash,
+                    });
+                }
+            }
+            let _ = txn.put_value(
+                self.switch_block_era_id_db,
+                &era_id_key(era_id),
+                &block_hash,
+                true,
+            )?;
+        }
+
+        let _ = txn.put_value(self.block_height_db, &height_key(height), &block_hash, true)?;
+        Ok(())
+    }
+
+    /// Inserts the relevant entries to the deploy hash index.
+    fn insert_to_deploy_index(
+        &self,
+        txn: &mut RwTransaction,
+        block_hash: BlockHash,
+        block_body: &BlockBody,
+    ) -> Result<(), Error> {
+        for deploy_hash in block_body
+            .deploy_hashes()
+            .iter()
+            .chain(block_body.transfer_hashes().iter())
+        {
+            if let Some(first) = self.block_hash_by_deploy_hash(txn, deploy_hash)? {
+                if first != block_hash {
+                    return Err(Error::DuplicateDeployIndex {
+                        deploy_hash: *deploy_hash,
+                        first,
+                        second: block_hash,
+                    });
+                }
+            }
+            let _ = txn.put_value(self.deploy_hash_db, deploy_hash, &block_hash, true)?;
+        }
+        Ok(())
+    }
+
+    /// Retrieves the locations of up to `limit` events emitted by the given contract, optionally
+    /// restricted to a single topic, following the event at the `after` cursor if given.
+    ///
+    /// Events are ordered chronologically by block height, then by deploy hash and finally by their
+    /// position within the deploy's execution result.
+    pub(super) fn contract_event_locations<Tx: Transaction>(
+        &self,
+        txn: &Tx,
+        contract_hash: &ContractHash,
+        topic: Option<&str>,
+        after: Option<&ContractEventCursor>,
+        limit: usize,
+    ) -> Result<Vec<ContractEventLocation>, Error> {
+        let prefix = contract_event_key_prefix(contract_hash, topic);
+        let mut start = prefix.clone();
+        if let Some(after) = after {
+            start.extend(contract_event_key_suffix(
+                after.block_height,
+                &after.deploy_hash,
+                after.event_index,
+            ));
+        }
+        let cursor = txn.open_ro_cursor(self.contract_event_db)?;
+        let mut locations = Vec::new();
+        // Positioning the cursor explicitly avoids the panic of `iter_from` on empty databases.
+        let mut entry = cursor.get(Some(start.as_slice()), None, lmdb_sys::MDB_SET_RANGE);
+        while locations.len() < limit {
+            match entry {
+                // The event at the `after` cursor itself has already been retrieved.
+                Ok((Some(raw_key), _)) if after.is_some() && raw_key == start.as_slice() => {}
+                Ok((Some(raw_key), raw_val)) if raw_key.starts_with(&prefix) => {
+                    locations.push(lmdb_ext::deserialize(raw_val)?);
+                }
+                Ok(_) | Err(lmdb::Error::NotFound) => break,
+                Err(err) => return Err(err.into()),
+            }
+            entry = cursor.get(None, None, lmdb_sys::MDB_NEXT);
+        }
+        Ok(locations)
+    }
+
+    /// Inserts the index entries for the events emitted while executing a deploy.
+    pub(super) fn insert_contract_events(
+        &self,
+        txn: &mut RwTransaction,
+        block_height: u64,
+        block_hash: BlockHash,
+        deploy_hash: DeployHash,
+        events: &[ContractEvent],
+    ) -> Result<(), Error> {
+        for (event_index, event) in (0..).zip(events) {
+            let location: ContractEventLocation =
+                (block_height, block_hash, deploy_hash, event_index);
+            for key in contract_event_keys(event, &location).iter() {
+                let _ = txn.put_value(self.contract_event_db, key, &location, true)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes the index entries for the events emitted while executing a deploy.
+    pub(super) fn remove_contract_events(
+        &self,
+        txn: &mut RwTransaction,
+        block_height: u64,
+        block_hash: BlockHash,
+        deploy_hash: DeployHash,
+        events: &[ContractEvent],
+    ) -> Result<(), Error> {
+        for (event_index, event) in (0..).zip(events) {
+            let location: ContractEventLocation =
+                (block_height, block_hash, deploy_hash, event_index);
+            for key in contract_event_keys(event, &location).iter() {
+                let _ = txn.del_value(self.contract_event_db, key)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes all entries from the indices.
+    pub(super) fn clear(&self, txn: &mut RwTransaction) -> Result<(), lmdb::Error> {
+        txn.clear_db(self.block_height_db)?;
+        txn.clear_db(self.switch_block_era_id_db)?;
+        txn.clear_db(self.deploy_hash_db)
+    }
+
+    /// Returns `true` if the indices are empty although the block store is not.
+    ///
+    /// This is the case for databases created by versions which only kept the indices in memory.
+    pub(super) fn are_missing(
+        &self,
+        env: &Environment,
+        block_header_db: Database,
+    ) -> Result<bool, Error> {
+        let txn = env.begin_ro_txn()?;
+        let has_blocks = txn.open_ro_cursor(block_header_db)?.iter().next().is_some();
+        let has_index_entries = txn
+            .open_ro_cursor(self.block_height_db)?
+            .iter()
+            .next()
+            .is_some();
+        Ok(has_blocks && !has_index_entries)
+    }
+
+    /// Checks whether the indices agree with the contents of the block store.
+    ///
+    /// Walks every block header and body, asserting that they are stored under their respective
+    /// hashes, and verifies that every expected index entry is present and that there are no
+    /// surplus entries.
+    pub(super) fn check(
+        &self,
+        env: &Environment,
+        block_header_db: Database,
+        block_body_db: Database,
+    ) -> Result<bool, Error> {
+        info!("checking storage indices");
+        let mut txn = env.begin_ro_txn()?;
+
+        let mut heights = HashSet::new();
+        let mut switch_block_count = 0usize;
+        let mut deploy_count = 0usize;
+        let mut has_pruned_bodies = false;
+        let mut consistent = true;
+
+        for block_hash in block_hashes(&txn, block_header_db)? {
+            let block_header: BlockHeader = txn
+                .get_value(block_header_db, &block_hash)?
+                .expect("block header to exist");
+            assert_eq!(
+                block_hash,
+                block_header.hash(),
+                "found corrupt block in database"
+            );
+
+            let _ = heights.insert(block_header.height());
+            consistent &=
+                self.block_hash_by_height(&mut txn, block_header.height())? == Some(block_hash);
+
+            if block_header.is_switch_block() {
+                switch_block_count += 1;
+                consistent &= self.switch_block_hash_by_era_id(&mut txn, block_header.era_id())?
+                    == Some(block_hash);
+            }
+
+            // The body may have been pruned, in which case its deploy index entries are retained
+            // but can no longer be verified.
+            let block_body: BlockBody =
+                match txn.get_value(block_body_db, block_header.body_hash())? {
+                    Some(block_body) => block_body,
+                    None => {
+                        has_pruned_bodies = true;
+                        continue;
+                    }
+                };
+            assert_eq!(
+                *block_header.body_hash(),
+                block_body.hash(),
+                "found corrupt block body in database"
+            );
+
+            for deploy_hash in block_body
+                .deploy_hashes()
+                .iter()
+                .chain(block_body.transfer_hashes().iter())
+            {
+                deploy_count += 1;
+                consistent &=
+                    self.block_hash_by_deploy_hash(&mut txn, deploy_hash)? == Some(block_hash);
+            }
+        }
+
+        let deploy_entry_count = entry_count(&txn, self.deploy_hash_db)?;
+        consistent &= entry_count(&txn, self.block_height_db)? == heights.len()
+            && entry_count(&txn, self.switch_block_era_id_db)? == switch_block_count
+            && (deploy_entry_count == deploy_count
+                || has_pruned_bodies && deploy_entry_count > deploy_count);
+
+        info!(%consistent, "finished checking storage indices");
+        Ok(consistent)
+    }
+
+    /// Discards the current indices and rebuilds them from the block store.
+    ///
+    /// Deploys of blocks whose body has been pruned cannot be reindexed and are subsequently
+    /// reported as unknown rather than pruned.
+    pub(super) fn rebuild(
+        &self,
+        env: &Environment,
+        block_header_db: Database,
+        block_body_db: Database,
+    ) -> Result<(), Error> {
+        info!("rebuilding storage indices");
+        let mut txn = env.begin_rw_txn()?;
+        self.clear(&mut txn)?;
+
+        // Only the hashes are collected up front, as the cursor borrows the transaction.
+        for block_hash in block_hashes(&txn, block_header_db)? {
+            let block_header: BlockHeader = txn
+                .get_value(block_header_db, &block_hash)?
+                .expect("block header to exist");
+            let block_hash = block_header.hash();
+            self.insert_to_block_header_indices(&mut txn, block_hash, &block_header)?;
+            if let Some(block_body) = txn.get_value(block_body_db, block_header.body_hash())? {
+                self.insert_to_deploy_index(&mut txn, block_hash, &block_body)?;
+            }
+        }
+
+        txn.commit()?;
+        info!("storage indices rebuilt");
+        Ok(())
+    }
+}
+
+/// Returns the hashes of all blocks in the block header database.
+fn block_hashes<Tx: Transaction>(
+    txn: &Tx,
+    block_header_db: Database,
+) -> Result<Vec<BlockHash>, Error> {
+    let mut cursor = txn.open_ro_cursor(block_header_db)?;
+    let mut block_hashes = Vec::new();
+    for (raw_key, _) in cursor.iter() {
+        let digest =
+            Digest::try_from(raw_key).map_err(|err| LmdbExtError::DataCorrupted(Box::new(err)))?;
+        block_hashes.push(BlockHash::new(digest));
+    }
+    Ok(block_hashes)
+}
+
+/// Returns the number of entries in a database.
+fn entry_count<Tx: Transaction>(txn: &Tx, db: Database) -> Result<usize, Error> {
+    Ok(txn.open_ro_cursor(db)?.iter().count())
+}
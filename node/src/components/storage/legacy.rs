@@ -0,0 +1,85 @@
+//! Formats of data written by previous versions of the storage component.
+//!
+//! Values are serialized using bincode, which does not tolerate changes to their layout. Whenever
+//! the layout of a stored type changes, its previous layout is retained here so that existing
+//! databases can be migrated on start-up.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use casper_types::{ExecutionEffect, ExecutionResult, TransferAddr, U512};
+
+use crate::types::{BlockHash, DeployMetadata};
+
+/// Key under which the version of the deploy metadata format is persisted in the state store.
+///
+/// Databases written by versions prior to the introduction of contract events lack this key.
+pub(super) const DEPLOY_METADATA_FORMAT_VERSION_KEY: &[u8] = b"deploy_metadata_format_version";
+
+/// The current version of the deploy metadata format.
+pub(super) const DEPLOY_METADATA_FORMAT_VERSION: u32 = 1;
+
+/// Deploy metadata as written by versions prior to the introduction of contract events.
+#[derive(Deserialize)]
+#[cfg_attr(test, derive(serde::Serialize))]
+pub(super) struct LegacyDeployMetadata {
+    pub(super) execution_results: HashMap<BlockHash, LegacyExecutionResult>,
+}
+
+/// An execution result as written by versions prior to the introduction of contract events.
+#[derive(Deserialize)]
+#[cfg_attr(test, derive(serde::Serialize))]
+pub(super) enum LegacyExecutionResult {
+    Failure {
+        effect: ExecutionEffect,
+        transfers: Vec<TransferAddr>,
+        cost: U512,
+        error_message: String,
+    },
+    Success {
+        effect: ExecutionEffect,
+        transfers: Vec<TransferAddr>,
+        cost: U512,
+    },
+}
+
+impl From<LegacyExecutionResult> for ExecutionResult {
+    fn from(legacy_execution_result: LegacyExecutionResult) -> Self {
+        match legacy_execution_result {
+            LegacyExecutionResult::Failure {
+                effect,
+                transfers,
+                cost,
+                error_message,
+            } => ExecutionResult::Failure {
+                effect,
+                transfers,
+                cost,
+                error_message,
+            },
+            LegacyExecutionResult::Success {
+                effect,
+                transfers,
+                cost,
+            } => ExecutionResult::Success {
+                effect,
+                transfers,
+                cost,
+                events: Vec::new(),
+            },
+        }
+    }
+}
+
+impl From<LegacyDeployMetadata> for DeployMetadata {
+    fn from(legacy_deploy_metadata: LegacyDeployMetadata) -> Self {
+        DeployMetadata {
+            execution_results: legacy_deploy_metadata
+                .execution_results
+                .into_iter()
+                .map(|(block_hash, execution_result)| (block_hash, execution_result.into()))
+                .collect(),
+        }
+    }
+}
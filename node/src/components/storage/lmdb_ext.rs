@@ -11,7 +11,7 @@
 //! interchange of the serialization format if desired.
 
 use crate::{crypto::hash::Digest, types::BlockHash};
-use lmdb::{Database, RwTransaction, Transaction, WriteFlags};
+use lmdb::{Cursor, Database, RwTransaction, Transaction, WriteFlags};
 use serde::{de::DeserializeOwned, Serialize};
 use thiserror::Error;
 
@@ -98,6 +98,12 @@ pub(super) trait TransactionExt {
         db: Database,
         key: &K,
     ) -> Result<Option<V>, LmdbExtError>;
+
+    /// Helper function to load the value stored under the highest key from a database.
+    fn get_last_value<V: DeserializeOwned>(
+        &mut self,
+        db: Database,
+    ) -> Result<Option<V>, LmdbExtError>;
 }
 
 /// Additional methods on write transactions.
@@ -114,6 +120,11 @@ pub(super) trait WriteTransactionExt {
         value: &V,
         overwrite: bool,
     ) -> Result<bool, LmdbExtError>;
+
+    /// Helper function to delete a value from a database.
+    ///
+    /// Returns `true` if the value has actually been deleted, `false` if the key did not exist.
+    fn del_value<K: AsRef<[u8]>>(&mut self, db: Database, key: &K) -> Result<bool, LmdbExtError>;
 }
 
 impl<T> TransactionExt for T
@@ -133,6 +144,18 @@ where
             Err(err) => Err(err.into()),
         }
     }
+
+    fn get_last_value<V: DeserializeOwned>(
+        &mut self,
+        db: Database,
+    ) -> Result<Option<V>, LmdbExtError> {
+        let cursor = self.open_ro_cursor(db)?;
+        match cursor.get(None, None, lmdb_sys::MDB_LAST) {
+            Ok((_, raw)) => deserialize(raw).map(Some),
+            Err(lmdb::Error::NotFound) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
 }
 
 impl WriteTransactionExt for RwTransaction<'_> {
@@ -158,6 +181,14 @@ impl WriteTransactionExt for RwTransaction<'_> {
             Err(err) => Err(err.into()),
         }
     }
+
+    fn del_value<K: AsRef<[u8]>>(&mut self, db: Database, key: &K) -> Result<bool, LmdbExtError> {
+        match self.del(db, key, None) {
+            Ok(()) => Ok(true),
+            Err(lmdb::Error::NotFound) => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
 }
 
 /// Deserializes from a buffer.
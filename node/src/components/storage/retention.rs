@@ -0,0 +1,137 @@
+//! Retention policy for historical block data.
+//!
+//! By default the storage component keeps every block, deploy and execution result forever. A
+//! retention policy allows limiting this to the most recent eras and/or blocks: Once a block falls
+//! outside of the retention window, its body, its deploys, their execution results and the block's
+//! transfers are deleted. Block headers, finality signatures and switch blocks are always kept, as
+//! they are required to validate the chain.
+
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+/// Key under which the height of the lowest block not yet considered for pruning is persisted in
+/// the state store.
+pub(super) const PRUNING_PROGRESS_KEY: &[u8] = b"storage_pruning_progress";
+
+/// Default maximum number of blocks considered for pruning per pass.
+const DEFAULT_MAX_BLOCKS_PER_PASS: u64 = 100;
+
+/// Retention policy configuration.
+///
+/// A block is pruned only if it falls outside of every enabled retention window. If neither
+/// `keep_eras` nor `keep_blocks` is set, all data is retained.
+#[derive(Clone, Copy, DataSize, Debug, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct RetentionConfig {
+    /// The number of most recent eras for which all block data is kept, `0` to disable.
+    #[serde(default)]
+    pub keep_eras: u64,
+    /// The number of most recent blocks for which all block data is kept, `0` to disable.
+    #[serde(default)]
+    pub keep_blocks: u64,
+    /// The maximum number of blocks considered for pruning each time a new block is stored.
+    ///
+    /// Bounds the time spent pruning on the storage event handling thread.
+    #[serde(default = "default_max_blocks_per_pass")]
+    pub max_blocks_per_pass: u64,
+}
+
+fn default_max_blocks_per_pass() -> u64 {
+    DEFAULT_MAX_BLOCKS_PER_PASS
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        RetentionConfig {
+            keep_eras: 0,
+            keep_blocks: 0,
+            max_blocks_per_pass: DEFAULT_MAX_BLOCKS_PER_PASS,
+        }
+    }
+}
+
+impl RetentionConfig {
+    /// Returns `true` if any data is ever pruned under this policy.
+    pub(super) fn is_enabled(&self) -> bool {
+        self.keep_eras > 0 || self.keep_blocks > 0
+    }
+
+    /// Returns the height below which blocks fall outside of the retention window.
+    ///
+    /// `highest_height` is the height of the highest stored block. `first_height_of_kept_eras` is
+    /// the height of the first block of the oldest era to be kept, or `None` if it cannot be
+    /// determined, in which case nothing is pruned by era.
+    pub(super) fn prune_below_height(
+        &self,
+        highest_height: u64,
+        first_height_of_kept_eras: Option<u64>,
+    ) -> u64 {
+        if !self.is_enabled() {
+            return 0;
+        }
+
+        // The highest block itself is always kept.
+        let mut prune_below = highest_height;
+        if self.keep_blocks > 0 {
+            prune_below = prune_below.min(
+                highest_height
+                    .saturating_add(1)
+                    .saturating_sub(self.keep_blocks),
+            );
+        }
+        if self.keep_eras > 0 {
+            prune_below = prune_below.min(first_height_of_kept_eras.unwrap_or(0));
+        }
+        prune_below
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_not_prune_when_disabled() {
+        let config = RetentionConfig::default();
+        assert!(!config.is_enabled());
+        assert_eq!(config.prune_below_height(1000, Some(500)), 0);
+    }
+
+    #[test]
+    fn should_keep_last_blocks() {
+        let config = RetentionConfig {
+            keep_blocks: 10,
+            ..Default::default()
+        };
+        assert_eq!(config.prune_below_height(100, None), 91);
+        assert_eq!(config.prune_below_height(5, None), 0);
+    }
+
+    #[test]
+    fn should_keep_last_eras() {
+        let config = RetentionConfig {
+            keep_eras: 2,
+            ..Default::default()
+        };
+        assert_eq!(config.prune_below_height(100, Some(80)), 80);
+        assert_eq!(config.prune_below_height(100, None), 0);
+    }
+
+    #[test]
+    fn should_keep_union_of_windows() {
+        let config = RetentionConfig {
+            keep_eras: 1,
+            keep_blocks: 50,
+            ..Default::default()
+        };
+        assert_eq!(config.prune_below_height(100, Some(95)), 51);
+
+        let config = RetentionConfig {
+            keep_eras: 3,
+            keep_blocks: 5,
+            ..Default::default()
+        };
+        assert_eq!(config.prune_below_height(100, Some(70)), 70);
+    }
+}
@@ -1,26 +1,36 @@
 //! Unit tests for the storage component.
 
-use std::{borrow::Cow, collections::HashMap};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+};
 
 use lmdb::Transaction;
 use rand::{prelude::SliceRandom, Rng};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use smallvec::smallvec;
 
-use casper_types::{EraId, ExecutionResult, ProtocolVersion, PublicKey, SecretKey};
+use casper_types::{
+    CLValue, ContractEvent, ContractHash, EraId, ExecutionEffect, ExecutionResult, ProtocolVersion,
+    PublicKey, SecretKey, U512,
+};
 
-use super::{Config, Storage};
+use super::{
+    legacy::{self, LegacyDeployMetadata, LegacyExecutionResult},
+    Config, RetentionConfig, Storage,
+};
 use crate::{
-    components::storage::lmdb_ext::WriteTransactionExt,
-    crypto::AsymmetricKeyExt,
+    components::storage::lmdb_ext::{TransactionExt, WriteTransactionExt},
+    crypto::{hash::Digest, AsymmetricKeyExt},
     effect::{
         requests::{StateStoreRequest, StorageRequest},
         Multiple,
     },
     testing::{ComponentHarness, TestRng, UnitTestEvent},
     types::{
-        Block, BlockHash, BlockHeader, BlockSignatures, Deploy, DeployHash, DeployMetadata,
-        FinalitySignature,
+        Block, BlockHash, BlockHeader, BlockSignatures, ContractEventCursor, Deploy, DeployHash,
+        DeployMetadata, EmittedContractEvent, FinalitySignature, FinalizedBlock, LightClientBundle,
+        TimeDiff,
     },
     utils::WithDir,
 };
@@ -37,6 +47,7 @@ fn new_config(harness: &ComponentHarness<UnitTestEvent>) -> Config {
         max_state_store_size: 50 * MIB,
         enable_mem_deduplication: false,
         mem_pool_prune_interval: 1024,
+        retention: Default::default(),
     }
 }
 
@@ -53,6 +64,7 @@ fn storage_fixture(harness: &ComponentHarness<UnitTestEvent>) -> Storage {
         &WithDir::new(harness.tmp.path(), cfg),
         None,
         ProtocolVersion::from_parts(1, 0, 0),
+        TimeDiff::default(),
         false,
     )
     .expect("could not create storage component fixture")
@@ -74,6 +86,51 @@ fn storage_fixture_with_hard_reset(
         &WithDir::new(harness.tmp.path(), cfg),
         Some(reset_era_id),
         ProtocolVersion::from_parts(1, 1, 0),
+        TimeDiff::default(),
+        false,
+    )
+    .expect("could not create storage component fixture")
+}
+
+/// Storage component test fixture.
+///
+/// Creates a storage component in a temporary directory, running the integrity checks on start-up.
+///
+/// # Panics
+///
+/// Panics if setting up the storage fixture fails.
+fn storage_fixture_with_integrity_check(harness: &ComponentHarness<UnitTestEvent>) -> Storage {
+    let cfg = new_config(harness);
+    Storage::new(
+        &WithDir::new(harness.tmp.path(), cfg),
+        None,
+        ProtocolVersion::from_parts(1, 0, 0),
+        TimeDiff::default(),
+        true,
+    )
+    .expect("could not create storage component fixture")
+}
+
+/// Storage component test fixture.
+///
+/// Creates a storage component in a temporary directory, using the given retention policy.
+///
+/// # Panics
+///
+/// Panics if setting up the storage fixture fails.
+fn storage_fixture_with_retention(
+    harness: &ComponentHarness<UnitTestEvent>,
+    retention: RetentionConfig,
+) -> Storage {
+    let cfg = Config {
+        retention,
+        ..new_config(harness)
+    };
+    Storage::new(
+        &WithDir::new(harness.tmp.path(), cfg),
+        None,
+        ProtocolVersion::from_parts(1, 0, 0),
+        TimeDiff::default(),
         false,
     )
     .expect("could not create storage component fixture")
@@ -104,6 +161,38 @@ fn random_signatures(rng: &mut TestRng, block: &Block) -> BlockSignatures {
     block_signatures
 }
 
+/// Creates a chain of random blocks, ending each era of `era_length` blocks with a switch block.
+fn random_chain(rng: &mut TestRng, era_count: u64, era_length: u64) -> Vec<Block> {
+    let mut blocks: Vec<Block> = Vec::new();
+    for height in 0..era_count * era_length {
+        let is_switch = height % era_length == era_length - 1;
+        let finalized_block = FinalizedBlock::random_with_specifics(
+            rng,
+            EraId::from(height / era_length),
+            height,
+            is_switch,
+        );
+        let parent_hash = match blocks.last() {
+            Some(parent) => *parent.hash(),
+            None => BlockHash::new(Digest::random(rng)),
+        };
+        let next_era_validator_weights = if is_switch {
+            Some(BTreeMap::new())
+        } else {
+            None
+        };
+        blocks.push(Block::new(
+            parent_hash,
+            Digest::random(rng),
+            Digest::random(rng),
+            finalized_block,
+            next_era_validator_weights,
+            ProtocolVersion::V1_0_0,
+        ));
+    }
+    blocks
+}
+
 /// Requests block header at a specific height from a storage component.
 fn get_block_header_at_height(
     harness: &mut ComponentHarness<UnitTestEvent>,
@@ -198,6 +287,49 @@ fn get_deploy_and_metadata(
     response
 }
 
+/// Loads up to `limit` events emitted by a contract from the storage component.
+fn get_contract_events(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    contract_hash: ContractHash,
+    topic: Option<&str>,
+    after: Option<ContractEventCursor>,
+    limit: usize,
+) -> Vec<EmittedContractEvent> {
+    let topic = topic.map(str::to_string);
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::GetContractEvents {
+            contract_hash,
+            topic,
+            after,
+            limit,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
+/// Requests a light client bundle from a storage component.
+fn get_light_client_bundle(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    trusted_block_hash: BlockHash,
+    max_switch_blocks: u32,
+) -> Option<LightClientBundle> {
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::GetLightClientBundle {
+            trusted_block_hash,
+            max_switch_blocks,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
 /// Requests the highest block from a storage component.
 fn get_highest_block(
     harness: &mut ComponentHarness<UnitTestEvent>,
@@ -276,11 +408,13 @@ fn put_execution_results(
     harness: &mut ComponentHarness<UnitTestEvent>,
     storage: &mut Storage,
     block_hash: BlockHash,
+    block_height: u64,
     execution_results: HashMap<DeployHash, ExecutionResult>,
 ) {
     let response = harness.send_request(storage, move |responder| {
         StorageRequest::PutExecutionResults {
             block_hash: Box::new(block_hash),
+            block_height,
             execution_results,
             responder,
         }
@@ -717,7 +851,7 @@ fn store_execution_results_for_two_blocks() {
     let first_result: ExecutionResult = harness.rng.gen();
     let mut first_results = HashMap::new();
     first_results.insert(*deploy.id(), first_result.clone());
-    put_execution_results(&mut harness, &mut storage, block_hash_a, first_results);
+    put_execution_results(&mut harness, &mut storage, block_hash_a, 0, first_results);
 
     // Retrieve and check if correct.
     let (first_deploy, first_metadata) =
@@ -732,7 +866,7 @@ fn store_execution_results_for_two_blocks() {
     let second_result: ExecutionResult = harness.rng.gen();
     let mut second_results = HashMap::new();
     second_results.insert(*deploy.id(), second_result.clone());
-    put_execution_results(&mut harness, &mut storage, block_hash_b, second_results);
+    put_execution_results(&mut harness, &mut storage, block_hash_b, 1, second_results);
 
     // Retrieve the deploy again, should now contain both.
     let (second_deploy, second_metadata) =
@@ -820,7 +954,7 @@ fn store_random_execution_results() {
         assert_eq!(block_results.len(), unique_count + shared_deploys.len());
 
         // Now we can submit the block's execution results.
-        put_execution_results(harness, storage, *block_hash, block_results);
+        put_execution_results(harness, storage, *block_hash, 0, block_results);
     }
 
     setup_block(
@@ -865,10 +999,10 @@ fn store_execution_results_twice_for_same_block_deploy_pair() {
     let mut exec_result_2 = HashMap::new();
     exec_result_2.insert(deploy_hash, harness.rng.gen());
 
-    put_execution_results(&mut harness, &mut storage, block_hash, exec_result_1);
+    put_execution_results(&mut harness, &mut storage, block_hash, 0, exec_result_1);
 
     // Storing a second execution result for the same deploy on the same block should panic.
-    put_execution_results(&mut harness, &mut storage, block_hash, exec_result_2);
+    put_execution_results(&mut harness, &mut storage, block_hash, 0, exec_result_2);
 }
 
 #[test]
@@ -882,10 +1016,16 @@ fn store_identical_execution_results() {
     let mut exec_result = HashMap::new();
     exec_result.insert(deploy_hash, harness.rng.gen());
 
-    put_execution_results(&mut harness, &mut storage, block_hash, exec_result.clone());
+    put_execution_results(
+        &mut harness,
+        &mut storage,
+        block_hash,
+        0,
+        exec_result.clone(),
+    );
 
     // We should be fine storing the exact same result twice.
-    put_execution_results(&mut harness, &mut storage, block_hash, exec_result);
+    put_execution_results(&mut harness, &mut storage, block_hash, 0, exec_result);
 }
 
 /// Example state used in storage.
@@ -895,6 +1035,280 @@ struct StateData {
     b: i32,
 }
 
+#[test]
+fn store_and_query_contract_events() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let contract_a = ContractHash::new(harness.rng.gen());
+    let contract_b = ContractHash::new(harness.rng.gen());
+    let event = |contract_hash, topic: &str, value: u64| ContractEvent {
+        contract_hash,
+        topic: topic.to_string(),
+        payload: CLValue::from_t(value).unwrap(),
+    };
+    let success = |events: Vec<ContractEvent>| ExecutionResult::Success {
+        effect: Default::default(),
+        transfers: Vec::new(),
+        cost: U512::from(100),
+        events,
+    };
+
+    // The later block is stored first, to check that events are ordered by block height.
+    let late_events = vec![event(contract_a, "mint", 5), event(contract_a, "burn", 6)];
+    let late_deploy_hash = DeployHash::random(&mut harness.rng);
+    let late_block_hash = BlockHash::random(&mut harness.rng);
+    let mut execution_results = HashMap::new();
+    execution_results.insert(late_deploy_hash, success(late_events.clone()));
+    put_execution_results(
+        &mut harness,
+        &mut storage,
+        late_block_hash,
+        7,
+        execution_results,
+    );
+
+    let events = vec![
+        event(contract_a, "mint", 1),
+        event(contract_b, "mint", 2),
+        event(contract_a, "burn", 3),
+        event(contract_a, "mint", 4),
+    ];
+    let deploy_hash = DeployHash::random(&mut harness.rng);
+    let block_hash = BlockHash::random(&mut harness.rng);
+    let mut execution_results = HashMap::new();
+    execution_results.insert(deploy_hash, success(events.clone()));
+    put_execution_results(&mut harness, &mut storage, block_hash, 3, execution_results);
+
+    let emitted = |event: &ContractEvent, event_index: u32| EmittedContractEvent {
+        block_hash,
+        block_height: 3,
+        deploy_hash,
+        event_index,
+        event: event.clone(),
+    };
+    let emitted_late = |event: &ContractEvent, event_index: u32| EmittedContractEvent {
+        block_hash: late_block_hash,
+        block_height: 7,
+        deploy_hash: late_deploy_hash,
+        event_index,
+        event: event.clone(),
+    };
+
+    // Events are returned in the order they were emitted, across topics and blocks.
+    assert_eq!(
+        get_contract_events(
+            &mut harness,
+            &mut storage,
+            contract_a,
+            Some("mint"),
+            None,
+            10
+        ),
+        vec![
+            emitted(&events[0], 0),
+            emitted(&events[3], 3),
+            emitted_late(&late_events[0], 0)
+        ]
+    );
+    let all_events_of_a = vec![
+        emitted(&events[0], 0),
+        emitted(&events[2], 2),
+        emitted(&events[3], 3),
+        emitted_late(&late_events[0], 0),
+        emitted_late(&late_events[1], 1),
+    ];
+    assert_eq!(
+        get_contract_events(&mut harness, &mut storage, contract_a, None, None, 10),
+        all_events_of_a
+    );
+    assert_eq!(
+        get_contract_events(&mut harness, &mut storage, contract_b, None, None, 10),
+        vec![emitted(&events[1], 1)]
+    );
+    assert!(get_contract_events(
+        &mut harness,
+        &mut storage,
+        contract_b,
+        Some("burn"),
+        None,
+        10
+    )
+    .is_empty());
+
+    // Events can be paged through using the cursor of the last event of the previous page.
+    let mut paged_events = Vec::new();
+    let mut after = None;
+    loop {
+        let page = get_contract_events(&mut harness, &mut storage, contract_a, None, after, 2);
+        assert!(page.len() <= 2);
+        after = match page.last() {
+            Some(last_event) => Some(last_event.cursor()),
+            None => break,
+        };
+        paged_events.extend(page);
+    }
+    assert_eq!(paged_events, all_events_of_a);
+
+    // Replacing the execution result also replaces its events in the index.
+    let mut execution_results = HashMap::new();
+    execution_results.insert(deploy_hash, success(vec![events[1].clone()]));
+    put_execution_results(&mut harness, &mut storage, block_hash, 3, execution_results);
+    assert_eq!(
+        get_contract_events(&mut harness, &mut storage, contract_a, None, None, 10),
+        vec![
+            emitted_late(&late_events[0], 0),
+            emitted_late(&late_events[1], 1)
+        ]
+    );
+    assert_eq!(
+        get_contract_events(
+            &mut harness,
+            &mut storage,
+            contract_b,
+            Some("mint"),
+            None,
+            10
+        ),
+        vec![emitted(&events[1], 0)]
+    );
+}
+
+#[test]
+fn should_get_light_client_bundle() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    // Four eras of three blocks each, with switch blocks at heights 2, 5, 8 and 11.
+    let blocks = random_chain(&mut harness.rng, 4, 3);
+    for block in &blocks {
+        assert!(put_block(
+            &mut harness,
+            &mut storage,
+            Box::new(block.clone())
+        ));
+        let signatures = random_signatures(&mut harness.rng, block);
+        assert!(put_block_signatures(&mut harness, &mut storage, signatures));
+    }
+    let hashes_at = |heights: &[usize]| {
+        heights
+            .iter()
+            .map(|height| {
+                casper_light_client::BlockHash::new(
+                    blocks[*height].hash().inner().to_array().into(),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    let ancestor_hashes = |bundle: &LightClientBundle| {
+        bundle
+            .trusted_ancestors
+            .iter()
+            .map(|header| header.hash())
+            .collect::<Vec<_>>()
+    };
+    let switch_block_hashes = |bundle: &LightClientBundle| {
+        bundle
+            .switch_blocks
+            .iter()
+            .map(|switch_block| switch_block.header.hash())
+            .collect::<Vec<_>>()
+    };
+
+    // The bundle should walk back to the switch block preceding the trusted block, and end at the
+    // highest block.
+    let bundle = get_light_client_bundle(&mut harness, &mut storage, *blocks[4].hash(), 10)
+        .expect("should get bundle");
+    assert_eq!(ancestor_hashes(&bundle), hashes_at(&[4, 3, 2]));
+    assert_eq!(switch_block_hashes(&bundle), hashes_at(&[5, 8, 11]));
+    assert_eq!(bundle.tip.header.hash(), hashes_at(&[11])[0]);
+    assert_eq!(bundle.tip.hash, hashes_at(&[11])[0]);
+    assert_eq!(bundle.tip.proofs.len(), 3);
+
+    // If the switch blocks are cut short, the bundle should end at the last one included.
+    let bundle = get_light_client_bundle(&mut harness, &mut storage, *blocks[4].hash(), 2)
+        .expect("should get bundle");
+    assert_eq!(switch_block_hashes(&bundle), hashes_at(&[5, 8]));
+    assert_eq!(bundle.tip.header.hash(), hashes_at(&[8])[0]);
+
+    // A trusted switch block should need no ancestors.
+    let bundle = get_light_client_bundle(&mut harness, &mut storage, *blocks[8].hash(), 10)
+        .expect("should get bundle");
+    assert_eq!(ancestor_hashes(&bundle), hashes_at(&[8]));
+    assert_eq!(switch_block_hashes(&bundle), hashes_at(&[11]));
+
+    let unknown_block_hash = BlockHash::random(&mut harness.rng);
+    assert!(get_light_client_bundle(&mut harness, &mut storage, unknown_block_hash, 10).is_none());
+}
+
+#[test]
+fn should_migrate_legacy_deploy_metadata() {
+    let mut harness = ComponentHarness::default();
+    let storage = storage_fixture(&harness);
+
+    // Write deploy metadata in the layout used prior to the introduction of contract events.
+    let deploy_hash = DeployHash::random(&mut harness.rng);
+    let block_hash = BlockHash::random(&mut harness.rng);
+    let mut legacy_execution_results = HashMap::new();
+    legacy_execution_results.insert(
+        block_hash,
+        LegacyExecutionResult::Success {
+            effect: ExecutionEffect::default(),
+            transfers: Vec::new(),
+            cost: U512::from(42),
+        },
+    );
+    let legacy_metadata = LegacyDeployMetadata {
+        execution_results: legacy_execution_results,
+    };
+    let mut txn = storage.env().begin_rw_txn().unwrap();
+    assert!(txn
+        .put_value(
+            storage.deploy_metadata_db,
+            &deploy_hash,
+            &legacy_metadata,
+            true
+        )
+        .unwrap());
+    let _ = txn
+        .del_value(
+            storage.state_store_db,
+            &legacy::DEPLOY_METADATA_FORMAT_VERSION_KEY,
+        )
+        .unwrap();
+    txn.commit().unwrap();
+    drop(storage);
+
+    let (on_disk, rng) = harness.into_parts();
+    let harness = ComponentHarness::builder()
+        .on_disk(on_disk)
+        .rng(rng)
+        .build();
+    let storage = storage_fixture(&harness);
+
+    let mut txn = storage.env().begin_ro_txn().unwrap();
+    let metadata: DeployMetadata = txn
+        .get_value(storage.deploy_metadata_db, &deploy_hash)
+        .unwrap()
+        .expect("deploy metadata was not migrated");
+    assert_eq!(
+        metadata.execution_results.get(&block_hash),
+        Some(&ExecutionResult::Success {
+            effect: ExecutionEffect::default(),
+            transfers: Vec::new(),
+            cost: U512::from(42),
+            events: Vec::new(),
+        })
+    );
+    let version: Option<u32> = txn
+        .get_value(
+            storage.state_store_db,
+            &legacy::DEPLOY_METADATA_FORMAT_VERSION_KEY,
+        )
+        .unwrap();
+    assert_eq!(version, Some(legacy::DEPLOY_METADATA_FORMAT_VERSION));
+}
+
 #[test]
 fn store_and_load_state_data() {
     let key1 = b"sample-key-1".to_vec();
@@ -1001,7 +1415,13 @@ fn persist_blocks_deploys_and_deploy_metadata_across_instantiations() {
     put_block(&mut harness, &mut storage, block.clone());
     let mut execution_results = HashMap::new();
     execution_results.insert(*deploy.id(), execution_result.clone());
-    put_execution_results(&mut harness, &mut storage, *block.hash(), execution_results);
+    put_execution_results(
+        &mut harness,
+        &mut storage,
+        *block.hash(),
+        block.height(),
+        execution_results,
+    );
 
     assert_eq!(
         get_block_at_height(&mut harness, &mut storage, 42).expect("block not indexed properly"),
@@ -1037,6 +1457,184 @@ fn persist_blocks_deploys_and_deploy_metadata_across_instantiations() {
     );
 }
 
+#[test]
+fn should_rebuild_missing_indices() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let block = random_block_at_height(&mut harness.rng, 42);
+    put_block(&mut harness, &mut storage, block.clone());
+
+    // Remove all index entries, which is the state of a database written by a version that only
+    // kept the indices in memory.
+    let mut txn = storage.env().begin_rw_txn().unwrap();
+    storage.indices.clear(&mut txn).unwrap();
+    txn.commit().unwrap();
+    assert!(get_block_at_height(&mut harness, &mut storage, 42).is_none());
+
+    let (on_disk, rng) = harness.into_parts();
+    let mut harness = ComponentHarness::builder()
+        .on_disk(on_disk)
+        .rng(rng)
+        .build();
+    let mut storage = storage_fixture(&harness);
+
+    assert_eq!(
+        get_block_at_height(&mut harness, &mut storage, 42).expect("block index was not rebuilt"),
+        *block
+    );
+    assert_eq!(get_highest_block(&mut harness, &mut storage), Some(*block));
+}
+
+#[test]
+fn should_rebuild_inconsistent_indices_on_integrity_check() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let block = random_block_at_height(&mut harness.rng, 42);
+    put_block(&mut harness, &mut storage, block.clone());
+
+    // Index a block which is not part of the block store.
+    let orphan = random_block_at_height(&mut harness.rng, 43);
+    let mut txn = storage.env().begin_rw_txn().unwrap();
+    storage
+        .indices
+        .insert_block(&mut txn, orphan.header(), orphan.body())
+        .unwrap();
+    txn.commit().unwrap();
+    assert!(get_highest_block(&mut harness, &mut storage).is_none());
+
+    let (on_disk, rng) = harness.into_parts();
+    let mut harness = ComponentHarness::builder()
+        .on_disk(on_disk)
+        .rng(rng)
+        .build();
+    let mut storage = storage_fixture_with_integrity_check(&harness);
+
+    assert!(get_block_at_height(&mut harness, &mut storage, 43).is_none());
+    assert_eq!(get_highest_block(&mut harness, &mut storage), Some(*block));
+}
+
+#[test]
+fn should_prune_blocks_outside_of_retention_window() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture_with_retention(
+        &harness,
+        RetentionConfig {
+            keep_blocks: 2,
+            ..Default::default()
+        },
+    );
+
+    // Create and store 5 blocks in era 0, each containing at least one deploy. Block 1 is a switch
+    // block.
+    let blocks: Vec<Block> = (0..5)
+        .map(|height| loop {
+            let block = Block::random_with_specifics(
+                &mut harness.rng,
+                EraId::from(0),
+                height,
+                ProtocolVersion::V1_0_0,
+                height == 1,
+            );
+            if !block.body().deploy_hashes().is_empty() {
+                break block;
+            }
+        })
+        .collect();
+    for block in &blocks {
+        assert!(put_block(
+            &mut harness,
+            &mut storage,
+            Box::new(block.clone())
+        ));
+    }
+
+    // Blocks 0 and 2 have been pruned, but their headers are retained.
+    for height in &[0, 2] {
+        let block = &blocks[*height as usize];
+        assert!(get_block(&mut harness, &mut storage, *block.hash()).is_none());
+        assert_eq!(
+            get_block_header_at_height(&mut harness, &mut storage, *height),
+            Some(block.header().clone())
+        );
+    }
+
+    // The switch block and the last two blocks are kept in full.
+    for height in &[1, 3, 4] {
+        let block = &blocks[*height as usize];
+        assert_eq!(
+            get_block(&mut harness, &mut storage, *block.hash()),
+            Some(block.clone())
+        );
+    }
+
+    // The pruned bodies do not cause a reindex or an integrity check failure on restart.
+    let (on_disk, rng) = harness.into_parts();
+    let mut harness = ComponentHarness::builder()
+        .on_disk(on_disk)
+        .rng(rng)
+        .build();
+    let mut storage = storage_fixture_with_integrity_check(&harness);
+    assert_eq!(
+        get_highest_block(&mut harness, &mut storage),
+        Some(blocks[4].clone())
+    );
+}
+
+#[test]
+fn should_retain_state_roots_of_switch_blocks_and_unpruned_blocks() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture_with_retention(
+        &harness,
+        RetentionConfig {
+            keep_blocks: 2,
+            ..Default::default()
+        },
+    );
+
+    // Blocks 2, 5 and 8 are switch blocks. Blocks below height 7 fall outside of the retention
+    // window.
+    let blocks = random_chain(&mut harness.rng, 3, 3);
+    for block in &blocks {
+        assert!(put_block(
+            &mut harness,
+            &mut storage,
+            Box::new(block.clone())
+        ));
+    }
+    let state_root_hashes = |heights: &[usize]| -> Vec<Digest> {
+        heights
+            .iter()
+            .map(|height| *blocks[*height].header().state_root_hash())
+            .collect()
+    };
+
+    assert_eq!(
+        storage.read_retained_state_root_hashes(1).unwrap(),
+        state_root_hashes(&[7, 8, 2, 5])
+    );
+    assert_eq!(
+        storage.read_retained_state_root_hashes(3).unwrap(),
+        state_root_hashes(&[6, 7, 8, 2, 5])
+    );
+
+    // Without a retention policy, all blocks are kept.
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+    for block in &blocks {
+        assert!(put_block(
+            &mut harness,
+            &mut storage,
+            Box::new(block.clone())
+        ));
+    }
+    assert_eq!(
+        storage.read_retained_state_root_hashes(1).unwrap(),
+        state_root_hashes(&[0, 1, 2, 3, 4, 5, 6, 7, 8])
+    );
+}
+
 #[test]
 fn should_hard_reset() {
     let blocks_count = 8_usize;
@@ -1080,7 +1678,7 @@ fn should_hard_reset() {
     // and so on.
     let mut deploys = vec![];
     let mut execution_results = vec![];
-    for block_hash in blocks.iter().map(|block| block.hash()) {
+    for block in blocks.iter() {
         let deploy = Deploy::random(&mut harness.rng);
         let execution_result: ExecutionResult = harness.rng.gen();
         let mut exec_results = HashMap::new();
@@ -1089,7 +1687,8 @@ fn should_hard_reset() {
         put_execution_results(
             &mut harness,
             &mut storage,
-            *block_hash,
+            *block.hash(),
+            block.height(),
             exec_results.clone(),
         );
         deploys.push(deploy);
@@ -9,6 +9,7 @@ use casper_execution_engine::shared::newtypes::Blake2bHash;
 use casper_types::{ProtocolVersion, PublicKey, SecretKey, Signature};
 
 use crate::{
+    components::storage::{self, Storage},
     crypto,
     reactor::participating::Config,
     types::{chainspec, Chainspec},
@@ -83,6 +84,10 @@ pub enum Error {
     /// Error loading the chainspec.
     #[error("error loading chainspec: {0}")]
     LoadChainspec(chainspec::Error),
+
+    /// Error migrating the storage.
+    #[error("error migrating storage: {0}")]
+    MigrateStorage(storage::Error),
 }
 
 #[derive(Serialize, Deserialize)]
@@ -201,16 +206,26 @@ pub fn migrate_data(
     new_config: WithDir<Config>,
 ) -> Result<(), Error> {
     let (new_root, new_config) = new_config.into_parts();
-    let new_protocol_version = Chainspec::from_path(&new_root)
-        .map_err(Error::LoadChainspec)?
-        .protocol_config
-        .version;
+    let new_chainspec = Chainspec::from_path(&new_root).map_err(Error::LoadChainspec)?;
+    let new_protocol_version = new_chainspec.protocol_config.version;
     let secret_key = new_config
         .consensus
         .secret_key_path
         .load(&new_root)
         .map_err(Error::LoadSecretKey)?;
 
+    // Opening the storage persists its secondary indices if they have not been written yet, so that
+    // the node does not need to build them on its first start after the upgrade.
+    let storage_config = WithDir::new(&new_root, new_config.storage.clone());
+    let _storage = Storage::new(
+        &storage_config,
+        None,
+        new_protocol_version,
+        new_chainspec.deploy_config.max_ttl,
+        false,
+    )
+    .map_err(Error::MigrateStorage)?;
+
     // Get this by actually migrating the global state data.
     let state_hash = Blake2bHash::default();
 
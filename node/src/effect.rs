@@ -89,15 +89,16 @@ use casper_execution_engine::{
         genesis::GenesisResult,
         step::{StepRequest, StepResult},
         upgrade::{UpgradeConfig, UpgradeResult},
-        BalanceRequest, BalanceResult, GetBidsRequest, GetBidsResult, QueryRequest, QueryResult,
+        BalanceRequest, BalanceResult, ExecuteRequest, ExecutionTrace, GetBidsRequest,
+        GetBidsResult, GetWithdrawsRequest, GetWithdrawsResult, QueryRequest, QueryResult,
         MAX_PAYMENT,
     },
     shared::{newtypes::Blake2bHash, stored_value::StoredValue},
     storage::{protocol_data::ProtocolData, trie::Trie},
 };
 use casper_types::{
-    system::auction::EraValidators, EraId, ExecutionResult, Key, ProtocolVersion, PublicKey,
-    Transfer, U512,
+    system::auction::EraValidators, ContractHash, EraId, ExecutionResult, Key, ProtocolVersion,
+    PublicKey, Transfer, U512,
 };
 
 use crate::{
@@ -105,18 +106,19 @@ use crate::{
         block_validator::ValidatingBlock,
         chainspec_loader::{CurrentRunInfo, NextUpgrade},
         consensus::{BlockContext, ClContext},
-        contract_runtime::EraValidatorsRequest,
+        contract_runtime::{EraValidatorsRequest, GasEstimate},
         deploy_acceptor,
         fetcher::FetchResult,
-        small_network::GossipedAddress,
+        small_network::{Capabilities, GossipedAddress, Offense, PeerReputation},
     },
     crypto::hash::Digest,
     effect::requests::LinearChainRequest,
     reactor::{EventQueueHandle, QueueKind},
     types::{
         Block, BlockByHeight, BlockHash, BlockHeader, BlockPayload, BlockSignatures, Chainspec,
-        ChainspecInfo, Deploy, DeployHash, DeployHeader, DeployMetadata, FinalitySignature,
-        FinalizedBlock, Item, TimeDiff, Timestamp,
+        ChainspecInfo, ContractEventCursor, Deploy, DeployHash, DeployHeader, DeployMetadata,
+        EmittedContractEvent, FinalitySignature, FinalizedBlock, Item, LightClientBundle, TimeDiff,
+        Timestamp,
     },
     utils::Source,
 };
@@ -585,6 +587,78 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Gets the optional protocol features supported by a connected peer.
+    ///
+    /// Optional messages should only be sent to peers supporting the corresponding capability.
+    /// Returns `None` if there is no outgoing connection to the peer.
+    // TODO - remove once used.
+    #[allow(unused)]
+    pub(crate) async fn get_peer_capabilities<I>(self, peer: I) -> Option<Capabilities>
+    where
+        REv: From<NetworkInfoRequest<I>>,
+        I: Send + 'static,
+    {
+        self.make_request(
+            |responder| NetworkInfoRequest::GetPeerCapabilities {
+                peer: Box::new(peer),
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Gets the reputations of all peers which committed offenses recently or are banned.
+    pub(crate) async fn network_blocklist<I>(self) -> BTreeMap<I, PeerReputation>
+    where
+        REv: From<NetworkInfoRequest<I>>,
+        I: Send + 'static,
+    {
+        self.make_request(
+            |responder| NetworkInfoRequest::GetBlocklist { responder },
+            QueueKind::Api,
+        )
+        .await
+    }
+
+    /// Bans a peer for the given duration, or the configured ban duration if `None`.
+    ///
+    /// Returns the time until which the peer is banned, or `None` if the networking component does
+    /// not support banning peers.
+    pub(crate) async fn ban_peer<I>(self, peer: I, duration: Option<TimeDiff>) -> Option<Timestamp>
+    where
+        REv: From<NetworkInfoRequest<I>>,
+        I: Send + 'static,
+    {
+        self.make_request(
+            |responder| NetworkInfoRequest::BanPeer {
+                peer: Box::new(peer),
+                duration,
+                responder,
+            },
+            QueueKind::Api,
+        )
+        .await
+    }
+
+    /// Lifts the ban on a peer and resets its reputation.
+    ///
+    /// Returns `true` if the peer was banned.
+    pub(crate) async fn unban_peer<I>(self, peer: I) -> bool
+    where
+        REv: From<NetworkInfoRequest<I>>,
+        I: Send + 'static,
+    {
+        self.make_request(
+            |responder| NetworkInfoRequest::UnbanPeer {
+                peer: Box::new(peer),
+                responder,
+            },
+            QueueKind::Api,
+        )
+        .await
+    }
+
     /// Announces that a network message has been received.
     pub(crate) async fn announce_message_received<I, P>(self, sender: I, payload: P)
     where
@@ -788,7 +862,6 @@ impl<REv> EffectBuilder<REv> {
     }
 
     /// Gets the requested block header from the linear block store.
-    #[allow(unused)]
     pub(crate) async fn get_block_header_from_storage(
         self,
         block_hash: BlockHash,
@@ -1023,6 +1096,7 @@ impl<REv> EffectBuilder<REv> {
     pub(crate) async fn put_execution_results_to_storage(
         self,
         block_hash: BlockHash,
+        block_height: u64,
         execution_results: HashMap<DeployHash, ExecutionResult>,
     ) where
         REv: From<StorageRequest>,
@@ -1030,6 +1104,7 @@ impl<REv> EffectBuilder<REv> {
         self.make_request(
             |responder| StorageRequest::PutExecutionResults {
                 block_hash: Box::new(block_hash),
+                block_height,
                 execution_results,
                 responder,
             },
@@ -1056,6 +1131,31 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Gets up to `limit` events emitted by the given contract from storage in chronological order,
+    /// optionally restricted to a single topic and to the events following the `after` cursor.
+    pub(crate) async fn get_contract_events_from_storage(
+        self,
+        contract_hash: ContractHash,
+        topic: Option<String>,
+        after: Option<ContractEventCursor>,
+        limit: usize,
+    ) -> Vec<EmittedContractEvent>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetContractEvents {
+                contract_hash,
+                topic,
+                after,
+                limit,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Gets the requested block and its associated metadata.
     pub(crate) async fn get_block_at_height_with_metadata_from_storage(
         self,
@@ -1106,6 +1206,46 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Gets the block headers and finality signatures allowing a light client to follow the
+    /// validator set from the given trusted block.
+    pub(crate) async fn get_light_client_bundle_from_storage(
+        self,
+        trusted_block_hash: BlockHash,
+        max_switch_blocks: u32,
+    ) -> Option<LightClientBundle>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetLightClientBundle {
+                trusted_block_hash,
+                max_switch_blocks,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Gets the state root hashes whose global state must be retained when collecting garbage,
+    /// including those of the `keep_blocks` highest blocks.
+    pub(crate) async fn get_retained_state_root_hashes_from_storage(
+        self,
+        keep_blocks: u64,
+    ) -> Vec<Digest>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetRetainedStateRootHashes {
+                keep_blocks,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Gets the requested deploy using the `DeployFetcher`.
     pub(crate) async fn fetch_deploy<I>(
         self,
@@ -1275,14 +1415,19 @@ impl<REv> EffectBuilder<REv> {
             .await
     }
 
-    /// Announce the intent to disconnect from a specific peer, which consensus thinks is faulty.
-    pub(crate) async fn announce_disconnect_from_peer<I>(self, peer: I)
+    /// Announce that a specific peer committed an offense, lowering its reputation.
+    ///
+    /// Peers whose reputation drops too low are disconnected from and banned.
+    pub(crate) async fn announce_offense_committed<I>(self, offender: I, offense: Offense)
     where
         REv: From<BlocklistAnnouncement<I>>,
     {
         self.0
             .schedule(
-                BlocklistAnnouncement::OffenseCommitted(Box::new(peer)),
+                BlocklistAnnouncement::OffenseCommitted {
+                    offender: Box::new(offender),
+                    offense,
+                },
                 QueueKind::Regular,
             )
             .await
@@ -1512,6 +1657,72 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Requests a deploy be executed by the Contract Runtime component without committing its
+    /// effects.
+    pub(crate) async fn speculative_execute(
+        self,
+        execute_request: ExecuteRequest,
+    ) -> Result<ExecutionResult, engine_state::Error>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::SpeculativeExecute {
+                execute_request,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Requests the Contract Runtime component to re-execute a deploy of a stored block with
+    /// execution tracing enabled.
+    ///
+    /// The deploys preceding it in the block are re-executed on top of `pre_state_root_hash` first.
+    pub(crate) async fn trace_deploy(
+        self,
+        block: Block,
+        pre_state_root_hash: Digest,
+        preceding_deploys: Vec<Deploy>,
+        deploy: Deploy,
+    ) -> Result<(ExecutionResult, ExecutionTrace), engine_state::Error>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::TraceDeploy {
+                block: Box::new(block),
+                pre_state_root_hash,
+                preceding_deploys,
+                deploy: Box::new(deploy),
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
+    /// Requests the Contract Runtime component to estimate the gas required to execute a deploy.
+    pub(crate) async fn estimate_gas(
+        self,
+        execute_request: ExecuteRequest,
+        safety_margin_percent: u64,
+    ) -> Result<GasEstimate, engine_state::Error>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::EstimateGas {
+                execute_request,
+                safety_margin_percent,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Returns `ProtocolData` by `ProtocolVersion`.
     ///
     /// This operation is read only.
@@ -1567,6 +1778,24 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Requests the unbonding purses from the Contract Runtime component.
+    pub(crate) async fn get_withdraws(
+        self,
+        get_withdraws_request: GetWithdrawsRequest,
+    ) -> Result<GetWithdrawsResult, engine_state::Error>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::GetWithdraws {
+                get_withdraws_request,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Runs the end of era step using the system smart contract.
     pub(crate) async fn run_step(
         self,
@@ -14,7 +14,9 @@ use casper_types::{EraId, ExecutionEffect, ExecutionResult, PublicKey};
 
 use crate::{
     components::{
-        chainspec_loader::NextUpgrade, deploy_acceptor::Error, small_network::GossipedAddress,
+        chainspec_loader::NextUpgrade,
+        deploy_acceptor::Error,
+        small_network::{GossipedAddress, Offense},
     },
     effect::Responder,
     types::{
@@ -199,7 +201,12 @@ impl Display for ConsensusAnnouncement {
 #[derive(Debug, Serialize)]
 pub enum BlocklistAnnouncement<I> {
     /// A given peer committed a blockable offense.
-    OffenseCommitted(Box<I>),
+    OffenseCommitted {
+        /// The peer which committed the offense.
+        offender: Box<I>,
+        /// The offense committed.
+        offense: Offense,
+    },
 }
 
 impl<I> Display for BlocklistAnnouncement<I>
@@ -208,8 +215,8 @@ where
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
-            BlocklistAnnouncement::OffenseCommitted(peer) => {
-                write!(f, "peer {} committed offense", peer)
+            BlocklistAnnouncement::OffenseCommitted { offender, offense } => {
+                write!(f, "peer {} committed offense: {}", offender, offense)
             }
         }
     }
@@ -21,8 +21,13 @@ use casper_execution_engine::{
         self,
         balance::{BalanceRequest, BalanceResult},
         era_validators::GetEraValidatorsError,
+        execute_request::ExecuteRequest,
+        execution_trace::ExecutionTrace,
         genesis::GenesisResult,
-        query::{GetBidsRequest, GetBidsResult, QueryRequest, QueryResult},
+        query::{
+            GetBidsRequest, GetBidsResult, GetWithdrawsRequest, GetWithdrawsResult, QueryRequest,
+            QueryResult,
+        },
         step::{StepRequest, StepResult},
         upgrade::{UpgradeConfig, UpgradeResult},
     },
@@ -31,7 +36,7 @@ use casper_execution_engine::{
 };
 use casper_types::{
     system::auction::{EraValidators, ValidatorWeights},
-    EraId, ExecutionResult, Key, ProtocolVersion, PublicKey, Transfer, URef,
+    ContractHash, EraId, ExecutionResult, Key, ProtocolVersion, PublicKey, Transfer, URef,
 };
 
 use super::Responder;
@@ -40,16 +45,18 @@ use crate::{
         block_validator::ValidatingBlock,
         chainspec_loader::CurrentRunInfo,
         consensus::{BlockContext, ClContext},
-        contract_runtime::{EraValidatorsRequest, ValidatorWeightsByEraIdRequest},
+        contract_runtime::{EraValidatorsRequest, GasEstimate, ValidatorWeightsByEraIdRequest},
         deploy_acceptor::Error,
         fetcher::FetchResult,
+        small_network::{Capabilities, PeerReputation},
     },
     crypto::hash::Digest,
     rpcs::{chain::BlockIdentifier, docs::OpenRpcSchema},
     types::{
         Block as LinearBlock, Block, BlockHash, BlockHeader, BlockPayload, BlockSignatures,
-        Chainspec, ChainspecInfo, Deploy, DeployHash, DeployHeader, DeployMetadata, FinalizedBlock,
-        Item, NodeId, StatusFeed, TimeDiff,
+        Chainspec, ChainspecInfo, ContractEventCursor, Deploy, DeployHash, DeployHeader,
+        DeployMetadata, EmittedContractEvent, FinalizedBlock, Item, LightClientBundle, NodeId,
+        StatusFeed, TimeDiff, Timestamp,
     },
     utils::DisplayIter,
 };
@@ -184,6 +191,36 @@ pub enum NetworkInfoRequest<I> {
         // TODO - change the `String` field to a `libp2p::Multiaddr` once small_network is removed.
         responder: Responder<BTreeMap<I, String>>,
     },
+    /// Get the optional protocol features supported by a connected peer.
+    GetPeerCapabilities {
+        /// The peer to query.
+        peer: Box<I>,
+        /// Responder to be called with the peer's capabilities, or `None` if there is no outgoing
+        /// connection to the peer.
+        responder: Responder<Option<Capabilities>>,
+    },
+    /// Get the reputations of all peers which committed offenses recently or are banned.
+    GetBlocklist {
+        /// Responder to be called with the reputation of each peer.
+        responder: Responder<BTreeMap<I, PeerReputation>>,
+    },
+    /// Ban a peer.
+    BanPeer {
+        /// The peer to ban.
+        peer: Box<I>,
+        /// The duration of the ban, or `None` to use the configured ban duration.
+        duration: Option<TimeDiff>,
+        /// Responder to be called with the time until which the peer is banned, or `None` if the
+        /// networking component does not support banning peers.
+        responder: Responder<Option<Timestamp>>,
+    },
+    /// Lift the ban on a peer and reset its reputation.
+    UnbanPeer {
+        /// The peer to unban.
+        peer: Box<I>,
+        /// Responder to be called with whether the peer was banned.
+        responder: Responder<bool>,
+    },
 }
 
 impl<I> Display for NetworkInfoRequest<I>
@@ -193,6 +230,16 @@ where
     fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
         match self {
             NetworkInfoRequest::GetPeers { responder: _ } => write!(formatter, "get peers"),
+            NetworkInfoRequest::GetPeerCapabilities { peer, .. } => {
+                write!(formatter, "get capabilities of {}", peer)
+            }
+            NetworkInfoRequest::GetBlocklist { responder: _ } => {
+                write!(formatter, "get blocklist")
+            }
+            NetworkInfoRequest::BanPeer { peer, .. } => write!(formatter, "ban peer {}", peer),
+            NetworkInfoRequest::UnbanPeer { peer, .. } => {
+                write!(formatter, "unban peer {}", peer)
+            }
         }
     }
 }
@@ -319,6 +366,8 @@ pub enum StorageRequest {
     PutExecutionResults {
         /// Hash of block.
         block_hash: Box<BlockHash>,
+        /// Height of block.
+        block_height: u64,
         /// Mapping of deploys to execution results of the block.
         execution_results: HashMap<DeployHash, ExecutionResult>,
         /// Responder to call when done storing.
@@ -331,6 +380,20 @@ pub enum StorageRequest {
         /// Responder to call with the results.
         responder: Responder<Option<(Deploy, DeployMetadata)>>,
     },
+    /// Retrieve the events emitted by a contract.
+    GetContractEvents {
+        /// Hash of the contract which emitted the events.
+        contract_hash: ContractHash,
+        /// Topic of the events to retrieve, or `None` to retrieve the events of all topics.
+        topic: Option<String>,
+        /// The position of the event after which to retrieve events, or `None` to retrieve events
+        /// from the first one.
+        after: Option<ContractEventCursor>,
+        /// Maximum number of events to retrieve.
+        limit: usize,
+        /// Responder to call with the results.
+        responder: Responder<Vec<EmittedContractEvent>>,
+    },
     /// Retrieve block and its metadata by its hash.
     GetBlockAndMetadataByHash {
         /// The hash of the block.
@@ -365,6 +428,26 @@ pub enum StorageRequest {
         /// stored.
         responder: Responder<bool>,
     },
+    /// Retrieve the block headers and finality signatures a light client needs to follow the
+    /// validator set from a trusted block.
+    GetLightClientBundle {
+        /// Hash of the block trusted by the light client.
+        trusted_block_hash: BlockHash,
+        /// Maximum number of switch blocks following the trusted block to include.
+        max_switch_blocks: u32,
+        /// Responder to call with the result.  Returns `None` if the trusted block doesn't exist
+        /// in local storage.
+        responder: Responder<Option<LightClientBundle>>,
+    },
+    /// Retrieve the state root hashes whose global state must be retained when collecting
+    /// garbage.
+    GetRetainedStateRootHashes {
+        /// Number of most recent blocks whose state root hashes are retained regardless of the
+        /// retention policy.
+        keep_blocks: u64,
+        /// Responder to call with the result.
+        responder: Responder<Vec<Digest>>,
+    },
 }
 
 impl Display for StorageRequest {
@@ -412,6 +495,9 @@ impl Display for StorageRequest {
             StorageRequest::GetDeployAndMetadata { deploy_hash, .. } => {
                 write!(formatter, "get deploy and metadata for {}", deploy_hash)
             }
+            StorageRequest::GetContractEvents { contract_hash, .. } => {
+                write!(formatter, "get events of {}", contract_hash)
+            }
             StorageRequest::GetBlockAndMetadataByHash { block_hash, .. } => {
                 write!(
                     formatter,
@@ -439,9 +525,25 @@ impl Display for StorageRequest {
             StorageRequest::PutBlockSignatures { .. } => {
                 write!(formatter, "put finality signatures")
             }
+            StorageRequest::GetLightClientBundle {
+                trusted_block_hash, ..
+            } => {
+                write!(
+                    formatter,
+                    "get light client bundle from {}",
+                    trusted_block_hash
+                )
+            }
             StorageRequest::GetFinalizedDeploys { ttl, .. } => {
                 write!(formatter, "get finalized deploys, ttl: {:?}", ttl)
             }
+            StorageRequest::GetRetainedStateRootHashes { keep_blocks, .. } => {
+                write!(
+                    formatter,
+                    "get retained state root hashes, keep blocks: {}",
+                    keep_blocks
+                )
+            }
         }
     }
 }
@@ -584,6 +686,13 @@ pub enum RpcRequest<I> {
         /// Responder to call with the result.
         responder: Responder<Result<GetBidsResult, engine_state::Error>>,
     },
+    /// Get the unbonding purses at the given root hash.
+    GetWithdraws {
+        /// The global state hash.
+        state_root_hash: Digest,
+        /// Responder to call with the result.
+        responder: Responder<Result<GetWithdrawsResult, engine_state::Error>>,
+    },
     /// Query the contract runtime for protocol version data.
     QueryProtocolData {
         /// The protocol version.
@@ -600,6 +709,28 @@ pub enum RpcRequest<I> {
         /// Responder to call with the result.
         responder: Responder<Result<BalanceResult, engine_state::Error>>,
     },
+    /// Execute a deploy on top of the global state of the given block without committing its
+    /// effects.
+    SpeculativeExecute {
+        /// The block on top of which the deploy is executed.
+        block: Box<Block>,
+        /// The deploy to execute.
+        deploy: Box<Deploy>,
+        /// Responder to call with the result.
+        responder: Responder<Result<ExecutionResult, engine_state::Error>>,
+    },
+    /// Estimate the gas required to execute a deploy on top of the global state of the given
+    /// block.
+    EstimateGas {
+        /// The block on top of which the deploy is executed.
+        block: Box<Block>,
+        /// The deploy to execute.
+        deploy: Box<Deploy>,
+        /// The safety margin in percent added to the suggested payment amount.
+        safety_margin_percent: u64,
+        /// Responder to call with the result.
+        responder: Responder<Result<GasEstimate, engine_state::Error>>,
+    },
     /// Return the specified deploy and metadata if it exists, else `None`.
     GetDeploy {
         /// The hash of the deploy to be retrieved.
@@ -661,6 +792,11 @@ impl<I> Display for RpcRequest<I> {
             } => {
                 write!(formatter, "bids {}", state_root_hash)
             }
+            RpcRequest::GetWithdraws {
+                state_root_hash, ..
+            } => {
+                write!(formatter, "withdraws {}", state_root_hash)
+            }
             RpcRequest::GetBalance {
                 state_root_hash,
                 purse_uref,
@@ -670,6 +806,18 @@ impl<I> Display for RpcRequest<I> {
                 "balance {}, purse_uref: {}",
                 state_root_hash, purse_uref
             ),
+            RpcRequest::SpeculativeExecute { block, deploy, .. } => write!(
+                formatter,
+                "speculatively execute {} on top of {}",
+                deploy.id(),
+                block.hash()
+            ),
+            RpcRequest::EstimateGas { block, deploy, .. } => write!(
+                formatter,
+                "estimate gas of {} on top of {}",
+                deploy.id(),
+                block.hash()
+            ),
             RpcRequest::GetDeploy { hash, .. } => write!(formatter, "get {}", hash),
             RpcRequest::GetPeers { .. } => write!(formatter, "get peers"),
             RpcRequest::GetStatus { .. } => write!(formatter, "get status"),
@@ -757,6 +905,38 @@ pub enum ContractRuntimeRequest {
         /// Responder to call with the balance result.
         responder: Responder<Result<BalanceResult, engine_state::Error>>,
     },
+    /// Execute a deploy without committing its effects.
+    SpeculativeExecute {
+        /// Execute request.
+        #[serde(skip_serializing)]
+        execute_request: ExecuteRequest,
+        /// Responder to call with the execution result.
+        responder: Responder<Result<ExecutionResult, engine_state::Error>>,
+    },
+    /// Re-execute a deploy of a stored block with execution tracing enabled.
+    TraceDeploy {
+        /// The block in which the deploy was executed.
+        block: Box<Block>,
+        /// The state root hash on top of which the block's deploys were executed.
+        pre_state_root_hash: Digest,
+        /// The deploys of the block executed before the deploy to trace, in order.
+        #[serde(skip_serializing)]
+        preceding_deploys: Vec<Deploy>,
+        /// The deploy to trace.
+        deploy: Box<Deploy>,
+        /// Responder to call with the execution result and trace.
+        responder: Responder<Result<(ExecutionResult, ExecutionTrace), engine_state::Error>>,
+    },
+    /// Estimate the gas required to execute a deploy.
+    EstimateGas {
+        /// Execute request.
+        #[serde(skip_serializing)]
+        execute_request: ExecuteRequest,
+        /// The safety margin in percent added to the suggested payment amount.
+        safety_margin_percent: u64,
+        /// Responder to call with the gas estimate.
+        responder: Responder<Result<GasEstimate, engine_state::Error>>,
+    },
     /// Returns validator weights.
     GetEraValidators {
         /// Get validators weights request.
@@ -781,6 +961,14 @@ pub enum ContractRuntimeRequest {
         /// Responder to call with the result.
         responder: Responder<Result<GetBidsResult, engine_state::Error>>,
     },
+    /// Return unbonding purses at a given state root hash
+    GetWithdraws {
+        /// Get withdraws request.
+        #[serde(skip_serializing)]
+        get_withdraws_request: GetWithdrawsRequest,
+        /// Responder to call with the result.
+        responder: Responder<Result<GetWithdrawsResult, engine_state::Error>>,
+    },
     /// Performs a step consisting of calculating rewards, slashing and running the auction at the
     /// end of an era.
     Step {
@@ -852,6 +1040,25 @@ impl Display for ContractRuntimeRequest {
                 balance_request, ..
             } => write!(formatter, "balance request: {:?}", balance_request),
 
+            ContractRuntimeRequest::SpeculativeExecute {
+                execute_request, ..
+            } => write!(
+                formatter,
+                "speculative execute request: {:?}",
+                execute_request
+            ),
+
+            ContractRuntimeRequest::EstimateGas {
+                execute_request, ..
+            } => write!(formatter, "estimate gas request: {:?}", execute_request),
+
+            ContractRuntimeRequest::TraceDeploy { block, deploy, .. } => write!(
+                formatter,
+                "trace deploy {} in block {}",
+                deploy.id(),
+                block.hash()
+            ),
+
             ContractRuntimeRequest::GetEraValidators { request, .. } => {
                 write!(formatter, "get era validators: {:?}", request)
             }
@@ -866,6 +1073,17 @@ impl Display for ContractRuntimeRequest {
                 write!(formatter, "get bids request: {:?}", get_bids_request)
             }
 
+            ContractRuntimeRequest::GetWithdraws {
+                get_withdraws_request,
+                ..
+            } => {
+                write!(
+                    formatter,
+                    "get withdraws request: {:?}",
+                    get_withdraws_request
+                )
+            }
+
             ContractRuntimeRequest::Step { step_request, .. } => {
                 write!(formatter, "step: {:?}", step_request)
             }
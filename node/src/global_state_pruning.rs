@@ -0,0 +1,96 @@
+//! Offline garbage collection of the global state.
+//!
+//! Deletes all trie nodes which are neither reachable from the state roots of the most recent
+//! blocks nor from any of the pinned state roots, as configured in the
+//! `[contract_runtime.garbage_collection]` section.  The state roots of all switch blocks and of
+//! all blocks kept by the storage retention policy are retained as well.  Must not be run while the
+//! node is running.
+
+use thiserror::Error;
+use tracing::info;
+
+use casper_execution_engine::{
+    shared::newtypes::{Blake2bHash, CorrelationId},
+    storage::error::lmdb::Error as GlobalStateError,
+};
+
+use crate::{
+    components::{
+        contract_runtime::{self, ConfigError},
+        storage::{self, Storage},
+    },
+    reactor::participating::Config,
+    types::{chainspec, Chainspec},
+    utils::WithDir,
+};
+
+/// Error returned as a result of pruning the global state.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Garbage collection is not configured.
+    #[error("global state garbage collection is disabled, set `keep_blocks` to enable it")]
+    Disabled,
+
+    /// Error loading the chainspec.
+    #[error("error loading chainspec: {0}")]
+    LoadChainspec(chainspec::Error),
+
+    /// Error opening the storage.
+    #[error("error opening storage: {0}")]
+    OpenStorage(storage::Error),
+
+    /// Error reading the retained state root hashes.
+    #[error("error reading retained state root hashes: {0}")]
+    ReadStateRootHashes(storage::Error),
+
+    /// Error opening the global state.
+    #[error("error opening global state: {0}")]
+    OpenGlobalState(ConfigError),
+
+    /// Error collecting garbage.
+    #[error("error collecting global state garbage: {0}")]
+    CollectGarbage(GlobalStateError),
+}
+
+/// Deletes all global state which is not required to execute or query the most recent blocks.
+pub fn prune_global_state(config: WithDir<Config>) -> Result<(), Error> {
+    let (root, config) = config.into_parts();
+    let garbage_collection = config.contract_runtime.garbage_collection();
+    if !garbage_collection.is_enabled() {
+        return Err(Error::Disabled);
+    }
+
+    let chainspec = Chainspec::from_path(&root).map_err(Error::LoadChainspec)?;
+    let storage_config = WithDir::new(&root, config.storage.clone());
+    let storage = Storage::new(
+        &storage_config,
+        None,
+        chainspec.protocol_config.version,
+        chainspec.deploy_config.max_ttl,
+        false,
+    )
+    .map_err(Error::OpenStorage)?;
+
+    let retained_roots: Vec<Blake2bHash> = storage
+        .read_retained_state_root_hashes(garbage_collection.keep_blocks)
+        .map_err(Error::ReadStateRootHashes)?
+        .into_iter()
+        .chain(garbage_collection.pinned_state_roots.iter().copied())
+        .map(Blake2bHash::from)
+        .collect();
+
+    let global_state =
+        contract_runtime::open_global_state(&storage_config, &config.contract_runtime)
+            .map_err(Error::OpenGlobalState)?;
+    if let Some(result) = global_state
+        .collect_garbage(CorrelationId::new(), &retained_roots)
+        .map_err(Error::CollectGarbage)?
+    {
+        info!(
+            reachable = result.reachable,
+            deleted = result.deleted,
+            "pruned global state"
+        );
+    }
+    Ok(())
+}
@@ -29,12 +29,14 @@ mod config_migration;
 pub mod crypto;
 mod data_migration;
 pub mod effect;
+mod global_state_pruning;
 pub mod logging;
 pub mod protocol;
 pub mod reactor;
 #[cfg(test)]
 pub mod testing;
 pub mod tls;
+mod traffic_capture;
 pub mod types;
 pub mod utils;
 
@@ -63,11 +65,15 @@ pub use components::{
     linear_chain_sync::Config as LinearChainSyncConfig,
     rest_server::Config as RestServerConfig,
     rpc_server::{rpcs, Config as RpcServerConfig},
-    small_network::{Config as SmallNetworkConfig, Error as SmallNetworkError},
+    small_network::{
+        Config as SmallNetworkConfig, Direction as TrafficDirection, Error as SmallNetworkError,
+    },
     storage::{Config as StorageConfig, Error as StorageError},
 };
 pub use config_migration::{migrate_config, Error as ConfigMigrationError};
 pub use data_migration::{migrate_data, Error as DataMigrationError};
+pub use global_state_pruning::{prune_global_state, Error as GlobalStatePruningError};
+pub use traffic_capture::{inspect_capture, CaptureFilter, Error as TrafficCaptureError};
 pub use types::NodeRng;
 pub use utils::OS_PAGE_SIZE;
 
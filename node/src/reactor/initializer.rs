@@ -195,6 +195,7 @@ impl Reactor {
             &storage_config,
             hard_reset_to_start_of_era,
             chainspec_loader.chainspec().protocol_config.version,
+            chainspec_loader.chainspec().deploy_config.max_ttl,
             crashed,
         )?;
 
@@ -202,6 +203,7 @@ impl Reactor {
             chainspec_loader.initial_state_root_hash(),
             chainspec_loader.initial_block_header(),
             chainspec_loader.chainspec().protocol_config.version,
+            &chainspec_loader.chainspec().core_config,
             storage_config,
             &config.value().contract_runtime,
             registry,
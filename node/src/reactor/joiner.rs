@@ -34,15 +34,15 @@ use crate::{
         metrics::Metrics,
         network::{self, Network, NetworkIdentity, ENABLE_LIBP2P_NET_ENV_VAR},
         rest_server::{self, RestServer},
-        small_network::{self, GossipedAddress, SmallNetwork, SmallNetworkIdentity},
+        small_network::{self, GossipedAddress, Offense, SmallNetwork, SmallNetworkIdentity},
         storage::{self, Storage},
         Component,
     },
     effect::{
         announcements::{
-            ChainspecLoaderAnnouncement, ContractRuntimeAnnouncement, ControlAnnouncement,
-            DeployAcceptorAnnouncement, GossiperAnnouncement, LinearChainAnnouncement,
-            LinearChainBlock, NetworkAnnouncement,
+            BlocklistAnnouncement, ChainspecLoaderAnnouncement, ContractRuntimeAnnouncement,
+            ControlAnnouncement, DeployAcceptorAnnouncement, GossiperAnnouncement,
+            LinearChainAnnouncement, LinearChainBlock, NetworkAnnouncement,
         },
         requests::{
             BlockProposerRequest, BlockValidationRequest, ChainspecLoaderRequest, ConsensusRequest,
@@ -201,6 +201,10 @@ pub enum Event {
     /// Consensus request.
     #[from]
     ConsensusRequest(#[serde(skip_serializing)] ConsensusRequest),
+
+    /// Blocklist announcement.
+    #[from]
+    BlocklistAnnouncement(BlocklistAnnouncement<NodeId>),
 }
 
 impl ReactorEvent for Event {
@@ -306,6 +310,7 @@ impl Display for Event {
             }
             Event::StateStoreRequest(req) => write!(f, "state store request: {}", req),
             Event::ConsensusRequest(req) => write!(f, "consensus request: {:?}", req),
+            Event::BlocklistAnnouncement(ann) => write!(f, "blocklist announcement: {}", ann),
         }
     }
 }
@@ -388,7 +393,7 @@ impl reactor::Reactor for Reactor {
         )?;
         let (small_network, small_network_effects) = SmallNetwork::new(
             event_queue,
-            config.network.clone(),
+            WithDir::new(&root, config.network.clone()),
             Some(WithDir::new(&root, &config.consensus)),
             registry,
             small_network_identity,
@@ -467,8 +472,11 @@ impl reactor::Reactor for Reactor {
         let block_header_by_hash_fetcher: Fetcher<BlockHeader> =
             Fetcher::new("block_header_by_hash", config.fetcher, registry)?;
 
-        let deploy_acceptor =
-            DeployAcceptor::new(config.deploy_acceptor, &*chainspec_loader.chainspec());
+        let deploy_acceptor = DeployAcceptor::new(
+            config.deploy_acceptor,
+            &*chainspec_loader.chainspec(),
+            config.block_proposer.min_gas_price,
+        );
 
         contract_runtime.set_initial_state(
             chainspec_loader.initial_state_root_hash(),
@@ -676,7 +684,17 @@ impl reactor::Reactor for Reactor {
                 let deploy_hash = *deploy.id();
                 let peer = source;
                 warn!(?deploy_hash, ?peer, "Invalid deploy received from a peer.");
-                Effects::new()
+                match peer {
+                    Source::Peer(offender) => {
+                        let reactor_event =
+                            Event::BlocklistAnnouncement(BlocklistAnnouncement::OffenseCommitted {
+                                offender: Box::new(offender),
+                                offense: Offense::InvalidDeploy,
+                            });
+                        self.dispatch_event(effect_builder, rng, reactor_event)
+                    }
+                    Source::Client | Source::Ourself => Effects::new(),
+                }
             }
             Event::Storage(event) => reactor::wrap_effects(
                 Event::Storage,
@@ -886,6 +904,9 @@ impl reactor::Reactor for Reactor {
                 // no consensus, respond with None
                 responder.respond(None).ignore()
             }
+            Event::BlocklistAnnouncement(ann) => {
+                self.dispatch_event(effect_builder, rng, Event::SmallNetwork(ann.into()))
+            }
         }
     }
 
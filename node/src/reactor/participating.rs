@@ -41,7 +41,7 @@ use crate::{
         network::{self, Network, NetworkIdentity, ENABLE_LIBP2P_NET_ENV_VAR},
         rest_server::{self, RestServer},
         rpc_server::{self, RpcServer},
-        small_network::{self, GossipedAddress, SmallNetwork, SmallNetworkIdentity},
+        small_network::{self, GossipedAddress, Offense, SmallNetwork, SmallNetworkIdentity},
         storage::{self, Storage},
         Component,
     },
@@ -428,8 +428,11 @@ impl reactor::Reactor for Reactor {
             *protocol_version,
         )?;
 
-        let deploy_acceptor =
-            DeployAcceptor::new(config.deploy_acceptor, &*chainspec_loader.chainspec());
+        let deploy_acceptor = DeployAcceptor::new(
+            config.deploy_acceptor,
+            &*chainspec_loader.chainspec(),
+            config.block_proposer.min_gas_price,
+        );
         let deploy_fetcher = Fetcher::new("deploy", config.fetcher, registry)?;
         let deploy_gossiper = Gossiper::new_for_partial_items(
             "deploy_gossiper",
@@ -455,7 +458,7 @@ impl reactor::Reactor for Reactor {
 
         let (small_network, small_network_effects) = SmallNetwork::new(
             event_queue,
-            config.network,
+            WithDir::new(&root, config.network),
             Some(WithDir::new(&root, &config.consensus)),
             registry,
             small_network_identity,
@@ -501,6 +504,11 @@ impl reactor::Reactor for Reactor {
             Event::SmallNetwork,
             small_network_effects,
         ));
+        // Captured traffic is only replayed once the node participates.
+        effects.extend(reactor::wrap_effects(
+            Event::SmallNetwork,
+            small_network.replay_capture(),
+        ));
         effects.extend(reactor::wrap_effects(
             Event::ChainspecLoader,
             chainspec_loader.start_checking_for_upgrades(effect_builder),
@@ -951,8 +959,18 @@ impl reactor::Reactor for Reactor {
             }
             Event::DeployAcceptorAnnouncement(DeployAcceptorAnnouncement::InvalidDeploy {
                 deploy: _,
-                source: _,
-            }) => Effects::new(),
+                source,
+            }) => match source {
+                Source::Peer(offender) => {
+                    let reactor_event =
+                        Event::BlocklistAnnouncement(BlocklistAnnouncement::OffenseCommitted {
+                            offender: Box::new(offender),
+                            offense: Offense::InvalidDeploy,
+                        });
+                    self.dispatch_event(effect_builder, rng, reactor_event)
+                }
+                Source::Client | Source::Ourself => Effects::new(),
+            },
             Event::ConsensusAnnouncement(consensus_announcement) => match consensus_announcement {
                 ConsensusAnnouncement::Finalized(block) => {
                     let reactor_event =
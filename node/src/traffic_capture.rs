@@ -0,0 +1,176 @@
+//! Offline inspection of network traffic captures.
+//!
+//! Captures are written by the networking component if enabled in the `[network.traffic_recorder]`
+//! config section.  Each message can be printed on a single line, or a summary of the number and
+//! size of messages can be printed per peer, direction and kind of message.
+
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use thiserror::Error;
+
+use crate::{
+    components::small_network::{
+        capture_files, CaptureError, CaptureReader, CapturedMessage, Direction, Message,
+    },
+    protocol,
+    types::{NodeId, Timestamp},
+};
+
+/// Error returned when inspecting a capture.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Error listing the capture files in a directory.
+    #[error("failed to list capture files in {}: {error}", path.display())]
+    ListFiles {
+        /// The path of the directory.
+        path: PathBuf,
+        /// The underlying error.
+        #[source]
+        error: io::Error,
+    },
+
+    /// Error reading a capture file.
+    #[error(transparent)]
+    Capture(#[from] CaptureError),
+
+    /// Error writing the output.
+    #[error("failed to write output: {0}")]
+    Output(#[source] io::Error),
+}
+
+/// Criteria selecting the messages of a capture to inspect.
+#[derive(Debug, Default)]
+pub struct CaptureFilter {
+    /// Prefix of the hex-encoded node ID of the peer the messages were exchanged with.
+    pub peer: Option<String>,
+    /// The direction of the messages.
+    pub direction: Option<Direction>,
+    /// The kind of the messages, e.g. `consensus` or `deploy_gossip`.
+    pub kind: Option<String>,
+}
+
+impl CaptureFilter {
+    fn matches(&self, captured: &CapturedMessage<Message<protocol::Message>>, kind: &str) -> bool {
+        let peer_matches = self.peer.as_ref().map_or(true, |prefix| {
+            captured.peer.hash_bytes().map_or(false, |bytes| {
+                hex::encode(bytes).starts_with(&prefix.to_lowercase())
+            })
+        });
+        peer_matches
+            && self
+                .direction
+                .map_or(true, |direction| direction == captured.direction)
+            && self.kind.as_ref().map_or(true, |wanted| wanted == kind)
+    }
+}
+
+/// Number and total size of messages.
+#[derive(Default)]
+struct Totals {
+    count: u64,
+    bytes: u64,
+}
+
+impl Totals {
+    fn add(&mut self, size: usize) {
+        self.count += 1;
+        self.bytes += size as u64;
+    }
+}
+
+/// Summary of the messages in a capture.
+#[derive(Default)]
+struct Summary {
+    first: Option<Timestamp>,
+    last: Option<Timestamp>,
+    total: Totals,
+    by_peer: BTreeMap<(NodeId, Direction, String), Totals>,
+}
+
+impl Summary {
+    fn add(
+        &mut self,
+        captured: &CapturedMessage<Message<protocol::Message>>,
+        kind: String,
+        size: usize,
+    ) {
+        self.first.get_or_insert(captured.timestamp);
+        self.last = Some(captured.timestamp);
+        self.total.add(size);
+        self.by_peer
+            .entry((captured.peer, captured.direction, kind))
+            .or_default()
+            .add(size);
+    }
+
+    fn write<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        match (self.first, self.last) {
+            (Some(first), Some(last)) => writeln!(
+                output,
+                "{} messages, {} bytes, from {} to {}",
+                self.total.count, self.total.bytes, first, last
+            )?,
+            _ => return writeln!(output, "no matching messages"),
+        }
+        for ((peer, direction, kind), totals) in &self.by_peer {
+            writeln!(
+                output,
+                "{} {} {}: {} messages, {} bytes",
+                peer, direction, kind, totals.count, totals.bytes
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the messages of a capture matching the filter to `output`, one per line, or a summary of
+/// them if `summarize` is set.
+///
+/// The path is either a single capture file or a directory containing capture files.
+pub fn inspect_capture<W: Write>(
+    path: &Path,
+    filter: &CaptureFilter,
+    summarize: bool,
+    output: &mut W,
+) -> Result<(), Error> {
+    let files = capture_files(path).map_err(|error| Error::ListFiles {
+        path: path.to_path_buf(),
+        error,
+    })?;
+
+    let mut summary = Summary::default();
+    for file in files {
+        for result in CaptureReader::<Message<protocol::Message>>::open(&file)? {
+            let (captured, size) = result?;
+            let kind = captured.message.classify().to_string();
+            if !filter.matches(&captured, &kind) {
+                continue;
+            }
+
+            if summarize {
+                summary.add(&captured, kind, size);
+            } else {
+                writeln!(
+                    output,
+                    "{} {} {} {} {} bytes: {}",
+                    captured.timestamp,
+                    captured.direction,
+                    captured.peer,
+                    kind,
+                    size,
+                    captured.message
+                )
+                .map_err(Error::Output)?;
+            }
+        }
+    }
+
+    if summarize {
+        summary.write(output).map_err(Error::Output)?;
+    }
+    Ok(())
+}
@@ -7,6 +7,7 @@ mod deploy;
 mod exit_code;
 mod item;
 pub mod json_compatibility;
+mod light_client;
 mod node_config;
 mod node_id;
 mod peers_map;
@@ -23,12 +24,14 @@ pub use block::{
     BlockValidationError, FinalitySignature,
 };
 pub(crate) use block::{BlockByHeight, BlockHeaderWithMetadata, BlockPayload, FinalizedBlock};
+pub use casper_light_client::LightClientBundle;
 pub(crate) use chainspec::ActivationPoint;
 pub use chainspec::Chainspec;
 pub use datasize::DataSize;
 pub use deploy::{
-    Approval, Deploy, DeployHash, DeployHeader, DeployMetadata, DeployOrTransferHash,
-    DeployValidationFailure, Error as DeployError, ExcessiveSizeError as ExcessiveSizeDeployError,
+    Approval, ContractEventCursor, Deploy, DeployHash, DeployHeader, DeployMetadata,
+    DeployOrTransferHash, DeployValidationFailure, EmittedContractEvent, Error as DeployError,
+    ExcessiveSizeError as ExcessiveSizeDeployError,
 };
 pub use exit_code::ExitCode;
 pub use item::{Item, Tag};
@@ -325,6 +325,9 @@ pub struct FinalizedBlock {
     era_id: EraId,
     height: u64,
     proposer: PublicKey,
+    /// The serialized evidence against the equivocators of the era report, if known locally.  This
+    /// is not part of the block, so it is empty for blocks which were not finalized by this node.
+    equivocation_evidence: BTreeMap<PublicKey, Vec<u8>>,
 }
 
 impl FinalizedBlock {
@@ -345,9 +348,19 @@ impl FinalizedBlock {
             era_id,
             height,
             proposer,
+            equivocation_evidence: BTreeMap::new(),
         }
     }
 
+    /// Sets the serialized evidence against the equivocators of the era report.
+    pub(crate) fn with_equivocation_evidence(
+        mut self,
+        equivocation_evidence: BTreeMap<PublicKey, Vec<u8>>,
+    ) -> Self {
+        self.equivocation_evidence = equivocation_evidence;
+        self
+    }
+
     /// The timestamp from when the block was proposed.
     pub(crate) fn timestamp(&self) -> Timestamp {
         self.timestamp
@@ -373,6 +386,11 @@ impl FinalizedBlock {
         self.proposer.clone()
     }
 
+    /// Returns the serialized evidence against the equivocators of the era report, if known.
+    pub(crate) fn equivocation_evidence(&self) -> &BTreeMap<PublicKey, Vec<u8>> {
+        &self.equivocation_evidence
+    }
+
     /// Returns an iterator over all deploy and transfer hashes.
     pub(crate) fn deploys_and_transfers_iter(
         &self,
@@ -475,6 +493,7 @@ impl From<Block> for FinalizedBlock {
             era_id: block.header.era_id,
             height: block.header.height,
             proposer: block.body.proposer,
+            equivocation_evidence: BTreeMap::new(),
         }
     }
 }
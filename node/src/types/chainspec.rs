@@ -80,7 +80,9 @@ impl Chainspec {
             warn!("era duration is less than minimum era height * round length!");
         }
 
-        self.protocol_config.is_valid() && self.highway_config.is_valid()
+        self.protocol_config.is_valid()
+            && self.core_config.is_valid()
+            && self.highway_config.is_valid()
     }
 
     /// Serializes `self` and hashes the resulting bytes.
@@ -192,6 +194,8 @@ impl From<&Chainspec> for ExecConfig {
             chainspec.core_config.locked_funds_period.millis(),
             chainspec.core_config.round_seigniorage_rate,
             chainspec.core_config.unbonding_delay,
+            chainspec.core_config.minimum_delegation_amount,
+            chainspec.core_config.max_delegators_per_validator,
             chainspec
                 .protocol_config
                 .activation_point
@@ -216,7 +220,10 @@ mod tests {
         stored_value::StoredValue,
         wasm_config::WasmConfig,
     };
-    use casper_types::{EraId, ProtocolVersion, U512};
+    use casper_types::{
+        system::{auction::SlashHandling, handle_payment::FeeHandling},
+        EraId, ProtocolVersion, U512,
+    };
 
     use super::*;
     use crate::{
@@ -268,6 +275,11 @@ mod tests {
             remove_contract_user_group_urefs: HostFunction::new(131, [0, 1, 2, 3, 4, 5]),
             print: HostFunction::new(123, [0, 1]),
             blake2b: HostFunction::new(133, [0, 1, 2, 3]),
+            emit_event: HostFunction::new(142, [0, 1, 2, 3]),
+            verify_signature: HostFunction::new(143, [0, 1, 2, 3, 4, 5]),
+            recover_secp256k1: HostFunction::new(144, [0, 1, 2, 3, 4, 5, 6]),
+            sha256: HostFunction::new(145, [0, 1, 2, 3]),
+            keccak256: HostFunction::new(146, [0, 1, 2, 3]),
         });
     static EXPECTED_GENESIS_WASM_COSTS: Lazy<WasmConfig> = Lazy::new(|| {
         WasmConfig::new(
@@ -353,6 +365,12 @@ mod tests {
 
         assert_eq!(spec.core_config.era_duration, TimeDiff::from(180000));
         assert_eq!(spec.core_config.minimum_era_height, 9);
+        assert_eq!(spec.core_config.refund_ratio, Ratio::new(1, 4));
+        assert_eq!(spec.core_config.fee_handling, FeeHandling::Accumulate);
+        assert_eq!(spec.core_config.slashing_rate, Ratio::new(1, 10));
+        assert_eq!(spec.core_config.slash_handling, SlashHandling::Accumulate);
+        assert_eq!(spec.core_config.minimum_delegation_amount, 10);
+        assert_eq!(spec.core_config.max_delegators_per_validator, 1200);
         assert_eq!(
             spec.highway_config.finality_threshold_fraction,
             Ratio::new(2, 25)
@@ -3,8 +3,12 @@ use num::rational::Ratio;
 #[cfg(test)]
 use rand::Rng;
 use serde::{Deserialize, Serialize};
+use tracing::error;
 
-use casper_types::bytesrepr::{self, FromBytes, ToBytes};
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    system::{auction::SlashHandling, handle_payment::FeeHandling},
+};
 
 #[cfg(test)]
 use crate::testing::TestRng;
@@ -25,9 +29,48 @@ pub struct CoreConfig {
     pub(crate) locked_funds_period: TimeDiff,
     /// The delay in number of eras for paying out the the unbonding amount.
     pub(crate) unbonding_delay: u64,
+    /// The minimum amount of motes of a new delegation.
+    pub(crate) minimum_delegation_amount: u64,
+    /// The maximum number of delegators per validator, where zero means there is no limit.
+    pub(crate) max_delegators_per_validator: u32,
     /// Round seigniorage rate represented as a fractional number.
     #[data_size(skip)]
     pub(crate) round_seigniorage_rate: Ratio<u64>,
+    /// The ratio of unspent payment which is refunded to the caller, as a fractional number.
+    #[data_size(skip)]
+    pub(crate) refund_ratio: Ratio<u64>,
+    /// How fees are handled once payment is finalized.
+    #[data_size(skip)]
+    pub(crate) fee_handling: FeeHandling,
+    /// The fraction of an equivocating validator's and its delegators' stakes which is slashed.
+    #[data_size(skip)]
+    pub(crate) slashing_rate: Ratio<u64>,
+    /// How slashed stake is handled.
+    #[data_size(skip)]
+    pub(crate) slash_handling: SlashHandling,
+}
+
+impl CoreConfig {
+    /// Checks whether the values set in the config make sense and returns `false` if they don't.
+    pub(super) fn is_valid(&self) -> bool {
+        if self.refund_ratio > Ratio::new(1, 1) {
+            error!(
+                refund_ratio = %self.refund_ratio,
+                "refund ratio is not in the range [0, 1]",
+            );
+            return false;
+        }
+
+        if self.slashing_rate > Ratio::new(1, 1) {
+            error!(
+                slashing_rate = %self.slashing_rate,
+                "slashing rate is not in the range [0, 1]",
+            );
+            return false;
+        }
+
+        true
+    }
 }
 
 #[cfg(test)]
@@ -40,10 +83,30 @@ impl CoreConfig {
         let auction_delay = rng.gen::<u32>() as u64;
         let locked_funds_period = TimeDiff::from(rng.gen_range(600_000..604_800_000));
         let unbonding_delay = rng.gen_range(1..1_000_000_000);
+        let minimum_delegation_amount = rng.gen::<u32>() as u64;
+        let max_delegators_per_validator = rng.gen();
         let round_seigniorage_rate = Ratio::new(
             rng.gen_range(1..1_000_000_000),
             rng.gen_range(1..1_000_000_000),
         );
+        let refund_ratio = {
+            let denominator = rng.gen_range(1..1_000_000_000);
+            Ratio::new(rng.gen_range(0..=denominator), denominator)
+        };
+        let fee_handling = match rng.gen_range(0..3) {
+            0 => FeeHandling::PayToProposer,
+            1 => FeeHandling::Burn,
+            _ => FeeHandling::Accumulate,
+        };
+        let slashing_rate = {
+            let denominator = rng.gen_range(1..1_000_000_000);
+            Ratio::new(rng.gen_range(0..=denominator), denominator)
+        };
+        let slash_handling = if rng.gen() {
+            SlashHandling::Burn
+        } else {
+            SlashHandling::Accumulate
+        };
 
         CoreConfig {
             era_duration,
@@ -52,7 +115,13 @@ impl CoreConfig {
             auction_delay,
             locked_funds_period,
             unbonding_delay,
+            minimum_delegation_amount,
+            max_delegators_per_validator,
             round_seigniorage_rate,
+            refund_ratio,
+            fee_handling,
+            slashing_rate,
+            slash_handling,
         }
     }
 }
@@ -66,7 +135,13 @@ impl ToBytes for CoreConfig {
         buffer.extend(self.auction_delay.to_bytes()?);
         buffer.extend(self.locked_funds_period.to_bytes()?);
         buffer.extend(self.unbonding_delay.to_bytes()?);
+        buffer.extend(self.minimum_delegation_amount.to_bytes()?);
+        buffer.extend(self.max_delegators_per_validator.to_bytes()?);
         buffer.extend(self.round_seigniorage_rate.to_bytes()?);
+        buffer.extend(self.refund_ratio.to_bytes()?);
+        buffer.extend(self.fee_handling.to_bytes()?);
+        buffer.extend(self.slashing_rate.to_bytes()?);
+        buffer.extend(self.slash_handling.to_bytes()?);
         Ok(buffer)
     }
 
@@ -77,7 +152,13 @@ impl ToBytes for CoreConfig {
             + self.auction_delay.serialized_length()
             + self.locked_funds_period.serialized_length()
             + self.unbonding_delay.serialized_length()
+            + self.minimum_delegation_amount.serialized_length()
+            + self.max_delegators_per_validator.serialized_length()
             + self.round_seigniorage_rate.serialized_length()
+            + self.refund_ratio.serialized_length()
+            + self.fee_handling.serialized_length()
+            + self.slashing_rate.serialized_length()
+            + self.slash_handling.serialized_length()
     }
 }
 
@@ -89,7 +170,13 @@ impl FromBytes for CoreConfig {
         let (auction_delay, remainder) = u64::from_bytes(remainder)?;
         let (locked_funds_period, remainder) = TimeDiff::from_bytes(remainder)?;
         let (unbonding_delay, remainder) = u64::from_bytes(remainder)?;
+        let (minimum_delegation_amount, remainder) = u64::from_bytes(remainder)?;
+        let (max_delegators_per_validator, remainder) = u32::from_bytes(remainder)?;
         let (round_seigniorage_rate, remainder) = Ratio::<u64>::from_bytes(remainder)?;
+        let (refund_ratio, remainder) = Ratio::<u64>::from_bytes(remainder)?;
+        let (fee_handling, remainder) = FeeHandling::from_bytes(remainder)?;
+        let (slashing_rate, remainder) = Ratio::<u64>::from_bytes(remainder)?;
+        let (slash_handling, remainder) = SlashHandling::from_bytes(remainder)?;
         let config = CoreConfig {
             era_duration,
             minimum_era_height,
@@ -97,7 +184,13 @@ impl FromBytes for CoreConfig {
             auction_delay,
             locked_funds_period,
             unbonding_delay,
+            minimum_delegation_amount,
+            max_delegators_per_validator,
             round_seigniorage_rate,
+            refund_ratio,
+            fee_handling,
+            slashing_rate,
+            slash_handling,
         };
         Ok((config, remainder))
     }
@@ -114,6 +207,40 @@ mod tests {
         bytesrepr::test_serialization_roundtrip(&config);
     }
 
+    #[test]
+    fn should_validate_for_refund_ratio() {
+        let mut rng = crate::new_rng();
+        let mut core_config = CoreConfig::random(&mut rng);
+
+        // Should be valid for 0 <= refund ratio <= 1.
+        core_config.refund_ratio = Ratio::new(0, 1);
+        assert!(core_config.is_valid());
+        core_config.refund_ratio = Ratio::new(1, 1);
+        assert!(core_config.is_valid());
+        core_config.refund_ratio = Ratio::new(u64::MAX, u64::MAX);
+        assert!(core_config.is_valid());
+
+        // Should be invalid for refund ratio > 1.
+        core_config.refund_ratio = Ratio::new(u64::MAX, u64::MAX - 1);
+        assert!(!core_config.is_valid());
+    }
+
+    #[test]
+    fn should_validate_for_slashing_rate() {
+        let mut rng = crate::new_rng();
+        let mut core_config = CoreConfig::random(&mut rng);
+
+        // Should be valid for 0 <= slashing rate <= 1.
+        core_config.slashing_rate = Ratio::new(0, 1);
+        assert!(core_config.is_valid());
+        core_config.slashing_rate = Ratio::new(1, 1);
+        assert!(core_config.is_valid());
+
+        // Should be invalid for slashing rate > 1.
+        core_config.slashing_rate = Ratio::new(u64::MAX, u64::MAX - 1);
+        assert!(!core_config.is_valid());
+    }
+
     #[test]
     fn toml_roundtrip() {
         let mut rng = crate::new_rng();
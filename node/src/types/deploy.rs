@@ -29,7 +29,8 @@ use casper_types::{
     bytesrepr::{self, FromBytes, ToBytes},
     runtime_args,
     system::standard_payment::ARG_AMOUNT,
-    AsymmetricType, ExecutionResult, PublicKey, RuntimeArgs, SecretKey, Signature, U512,
+    AsymmetricType, ContractEvent, ExecutionResult, PublicKey, RuntimeArgs, SecretKey, Signature,
+    U512,
 };
 
 use super::{BlockHash, Item, Tag, TimeDiff, Timestamp};
@@ -940,6 +941,48 @@ pub struct DeployMetadata {
     pub execution_results: HashMap<BlockHash, ExecutionResult>,
 }
 
+/// An event emitted by a contract, along with the block and deploy in which it was emitted.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct EmittedContractEvent {
+    /// The hash of the block in which the event was emitted.
+    pub block_hash: BlockHash,
+    /// The height of the block in which the event was emitted.
+    pub block_height: u64,
+    /// The hash of the deploy whose execution emitted the event.
+    pub deploy_hash: DeployHash,
+    /// The position of the event among the events emitted by the deploy.
+    pub event_index: u32,
+    /// The event.
+    pub event: ContractEvent,
+}
+
+impl EmittedContractEvent {
+    /// Returns the cursor positioned at this event.
+    pub fn cursor(&self) -> ContractEventCursor {
+        ContractEventCursor {
+            block_height: self.block_height,
+            deploy_hash: self.deploy_hash,
+            event_index: self.event_index,
+        }
+    }
+}
+
+/// The position of an event in the chronological order of the events emitted by a contract.
+///
+/// Events are ordered by the height of the block emitting them, then by the hash of the deploy
+/// emitting them, then by their position among the events emitted by the deploy.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ContractEventCursor {
+    /// The height of the block in which the event was emitted.
+    pub block_height: u64,
+    /// The hash of the deploy whose execution emitted the event.
+    pub deploy_hash: DeployHash,
+    /// The position of the event among the events emitted by the deploy.
+    pub event_index: u32,
+}
+
 impl ToBytes for Deploy {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut buffer = bytesrepr::allocate_buffer(self)?;
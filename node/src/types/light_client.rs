@@ -0,0 +1,152 @@
+use once_cell::sync::Lazy;
+
+use casper_light_client::{
+    EraEnd, EraReport, LightClientBundle, Proof, Reward, SignedBlockHeader, ValidatorWeight,
+};
+use casper_types::{PublicKey, SecretKey};
+
+use crate::{
+    components::rpc_server::rpcs::docs::DocExample,
+    crypto::{hash::Digest, AsymmetricKeyExt},
+    types::{
+        Block, BlockHash, BlockHeader, BlockHeaderWithMetadata, BlockSignatures, FinalitySignature,
+    },
+};
+
+static LIGHT_CLIENT_BUNDLE: Lazy<LightClientBundle> = Lazy::new(|| {
+    let block_header = Block::doc_example().header().clone();
+    let block_hash = block_header.hash();
+    let era_id = block_header.era_id();
+
+    let secret_key = SecretKey::doc_example();
+    let public_key = PublicKey::from(secret_key);
+    let finality_signature = FinalitySignature::new(block_hash, era_id, secret_key, public_key);
+    let mut block_signatures = BlockSignatures::new(block_hash, era_id);
+    block_signatures.insert_proof(finality_signature.public_key, finality_signature.signature);
+
+    LightClientBundle {
+        trusted_ancestors: vec![casper_light_client::BlockHeader::from(&block_header)],
+        switch_blocks: vec![],
+        tip: SignedBlockHeader::from(BlockHeaderWithMetadata {
+            block_header,
+            block_signatures,
+        }),
+    }
+});
+
+fn to_digest(digest: &Digest) -> casper_light_client::Digest {
+    casper_light_client::Digest::from(digest.to_array())
+}
+
+fn to_block_hash(block_hash: &BlockHash) -> casper_light_client::BlockHash {
+    casper_light_client::BlockHash::new(to_digest(block_hash.inner()))
+}
+
+impl From<&BlockHeader> for casper_light_client::BlockHeader {
+    fn from(block_header: &BlockHeader) -> Self {
+        let era_end = block_header
+            .era_end()
+            .zip(block_header.next_era_validator_weights())
+            .map(|(era_report, next_era_validator_weights)| EraEnd {
+                era_report: EraReport {
+                    equivocators: era_report.equivocators.clone(),
+                    rewards: era_report
+                        .rewards
+                        .iter()
+                        .map(|(validator, amount)| Reward {
+                            validator: validator.clone(),
+                            amount: *amount,
+                        })
+                        .collect(),
+                    inactive_validators: era_report.inactive_validators.clone(),
+                },
+                next_era_validator_weights: next_era_validator_weights
+                    .iter()
+                    .map(|(validator, weight)| ValidatorWeight {
+                        validator: validator.clone(),
+                        weight: *weight,
+                    })
+                    .collect(),
+            });
+        casper_light_client::BlockHeader {
+            parent_hash: to_block_hash(block_header.parent_hash()),
+            state_root_hash: to_digest(block_header.state_root_hash()),
+            body_hash: to_digest(block_header.body_hash()),
+            random_bit: block_header.random_bit(),
+            accumulated_seed: to_digest(&block_header.accumulated_seed()),
+            era_end,
+            timestamp: block_header.timestamp().millis().into(),
+            era_id: block_header.era_id(),
+            height: block_header.height(),
+            protocol_version: block_header.protocol_version(),
+        }
+    }
+}
+
+impl From<BlockHeaderWithMetadata> for SignedBlockHeader {
+    fn from(block_header_with_metadata: BlockHeaderWithMetadata) -> Self {
+        let BlockHeaderWithMetadata {
+            block_header,
+            block_signatures,
+        } = block_header_with_metadata;
+        SignedBlockHeader {
+            hash: to_block_hash(&block_header.hash()),
+            header: casper_light_client::BlockHeader::from(&block_header),
+            proofs: block_signatures
+                .proofs
+                .into_iter()
+                .map(|(public_key, signature)| Proof {
+                    public_key,
+                    signature,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl DocExample for LightClientBundle {
+    fn doc_example() -> &'static Self {
+        &*LIGHT_CLIENT_BUNDLE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{EraId, ProtocolVersion};
+
+    use super::*;
+    use crate::{testing::TestRng, types::JsonBlock};
+
+    #[test]
+    fn light_client_block_header_should_match_node_block_header() {
+        let mut rng = TestRng::new();
+        let blocks = vec![
+            Block::doc_example().clone(),
+            Block::random_with_specifics(
+                &mut rng,
+                EraId::new(1),
+                10,
+                ProtocolVersion::V1_0_0,
+                false,
+            ),
+            Block::random_with_specifics(
+                &mut rng,
+                EraId::new(1),
+                11,
+                ProtocolVersion::V1_0_0,
+                true,
+            ),
+        ];
+        for block in blocks {
+            let light_client_header = casper_light_client::BlockHeader::from(block.header());
+            assert_eq!(light_client_header.hash(), to_block_hash(block.hash()));
+
+            // The JSON representations should be interchangeable.
+            let json_block = serde_json::to_value(JsonBlock::new(block, None)).unwrap();
+            assert_eq!(
+                serde_json::to_value(&light_client_header).unwrap(),
+                json_block["header"]
+            );
+        }
+    }
+}
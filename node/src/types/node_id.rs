@@ -11,6 +11,7 @@ use multihash::Multihash;
 use once_cell::sync::Lazy;
 #[cfg(test)]
 use rand::{Rng, RngCore};
+use schemars::JsonSchema;
 use serde::{de::Error as SerdeError, Deserialize, Deserializer, Serialize, Serializer};
 
 #[cfg(test)]
@@ -71,7 +72,7 @@ enum NodeIdAsBytes {
 }
 
 /// Used to serialize and deserialize `NodeID` where the (de)serializer is a human-readable type.
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, JsonSchema)]
 enum NodeIdAsString {
     Tls(String),
     P2p(String),
@@ -139,6 +140,21 @@ impl DocExample for NodeId {
     }
 }
 
+impl JsonSchema for NodeId {
+    fn schema_name() -> String {
+        String::from("NodeId")
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        let schema = gen.subschema_for::<NodeIdAsString>();
+        let mut schema_object = schema.into_object();
+        schema_object.metadata().description = Some(
+            "The network identifier of a node, e.g. a hex-encoded TLS key fingerprint.".to_string(),
+        );
+        schema_object.into()
+    }
+}
+
 impl Debug for NodeId {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
         match self {
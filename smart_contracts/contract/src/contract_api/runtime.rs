@@ -9,11 +9,12 @@ use core::mem::MaybeUninit;
 use casper_types::{
     account::AccountHash,
     api_error,
-    bytesrepr::{self, FromBytes},
+    bytesrepr::{self, FromBytes, ToBytes},
     contracts::{ContractVersion, NamedKeys},
     system::CallStackElement,
     ApiError, BlockTime, CLTyped, CLValue, ContractHash, ContractPackageHash, Key, Phase,
-    RuntimeArgs, URef, BLAKE2B_DIGEST_LENGTH, BLOCKTIME_SERIALIZED_LENGTH, PHASE_SERIALIZED_LENGTH,
+    PublicKey, RuntimeArgs, Signature, URef, BLAKE2B_DIGEST_LENGTH, BLOCKTIME_SERIALIZED_LENGTH,
+    EVENT_TOPIC_MAX_LENGTH, KECCAK256_DIGEST_LENGTH, PHASE_SERIALIZED_LENGTH, SHA256_DIGEST_LENGTH,
 };
 
 use crate::{contract_api, ext_ffi, unwrap_or_revert::UnwrapOrRevert};
@@ -321,6 +322,107 @@ pub fn blake2b<T: AsRef<[u8]>>(input: T) -> [u8; BLAKE2B_DIGEST_LENGTH] {
     ret
 }
 
+/// Returns a 32-byte SHA-256 digest
+pub fn sha256<T: AsRef<[u8]>>(input: T) -> [u8; SHA256_DIGEST_LENGTH] {
+    let mut ret = [0; SHA256_DIGEST_LENGTH];
+    let result = unsafe {
+        ext_ffi::casper_sha256(
+            input.as_ref().as_ptr(),
+            input.as_ref().len(),
+            ret.as_mut_ptr(),
+            SHA256_DIGEST_LENGTH,
+        )
+    };
+    api_error::result_from(result).unwrap_or_revert();
+    ret
+}
+
+/// Returns a 32-byte Keccak-256 digest
+pub fn keccak256<T: AsRef<[u8]>>(input: T) -> [u8; KECCAK256_DIGEST_LENGTH] {
+    let mut ret = [0; KECCAK256_DIGEST_LENGTH];
+    let result = unsafe {
+        ext_ffi::casper_keccak256(
+            input.as_ref().as_ptr(),
+            input.as_ref().len(),
+            ret.as_mut_ptr(),
+            KECCAK256_DIGEST_LENGTH,
+        )
+    };
+    api_error::result_from(result).unwrap_or_revert();
+    ret
+}
+
+/// Emits an event with the given `topic` and `payload`, attributed to the calling contract.
+///
+/// Events are recorded in the execution result of the deploy and indexed by contract hash and
+/// topic.  Must be called from within a stored contract, and the topic must be non-empty and at
+/// most [`EVENT_TOPIC_MAX_LENGTH`] bytes long.
+///
+/// Execution fails if the serialized payload is longer than
+/// [`EVENT_PAYLOAD_MAX_LENGTH`](casper_types::EVENT_PAYLOAD_MAX_LENGTH) bytes, or if more than
+/// [`MAX_EVENTS_PER_DEPLOY`](casper_types::MAX_EVENTS_PER_DEPLOY) events are emitted by the deploy.
+pub fn emit_event<T: CLTyped + ToBytes>(topic: &str, payload: T) {
+    if topic.is_empty() || topic.len() > EVENT_TOPIC_MAX_LENGTH {
+        revert(ApiError::InvalidArgument)
+    }
+    let cl_value = CLValue::from_t(payload).unwrap_or_revert();
+    let (cl_value_ptr, cl_value_size, _bytes) = contract_api::to_ptr(cl_value);
+    let result = unsafe {
+        ext_ffi::casper_emit_event(topic.as_ptr(), topic.len(), cl_value_ptr, cl_value_size)
+    };
+    api_error::result_from(result).unwrap_or_revert()
+}
+
+/// Returns `true` if `signature` is a valid signature of `message` created by the secret key
+/// corresponding to `public_key`.
+pub fn verify_signature<T: AsRef<[u8]>>(
+    public_key: &PublicKey,
+    message: T,
+    signature: &Signature,
+) -> bool {
+    let public_key_bytes = public_key.to_bytes().unwrap_or_revert();
+    let signature_bytes = signature.to_bytes().unwrap_or_revert();
+    let result = unsafe {
+        ext_ffi::casper_verify_signature(
+            public_key_bytes.as_ptr(),
+            public_key_bytes.len(),
+            message.as_ref().as_ptr(),
+            message.as_ref().len(),
+            signature_bytes.as_ptr(),
+            signature_bytes.len(),
+        )
+    };
+    result != 0
+}
+
+/// Recovers the secp256k1 public key which created `signature` over `message`.
+///
+/// The `recovery_id` selects which of the candidate public keys is returned.  Returns
+/// [`ApiError::InvalidArgument`] if `signature` is not a secp256k1 signature or if no public key
+/// can be recovered.
+pub fn recover_secp256k1<T: AsRef<[u8]>>(
+    message: T,
+    signature: &Signature,
+    recovery_id: u8,
+) -> Result<PublicKey, ApiError> {
+    let signature_bytes = signature.to_bytes().unwrap_or_revert();
+    // A serialized secp256k1 public key is its tag byte followed by the compressed key.
+    let mut public_key_bytes = vec![0; 1 + PublicKey::SECP256K1_LENGTH];
+    let result = unsafe {
+        ext_ffi::casper_recover_secp256k1(
+            message.as_ref().as_ptr(),
+            message.as_ref().len(),
+            signature_bytes.as_ptr(),
+            signature_bytes.len(),
+            recovery_id,
+            public_key_bytes.as_mut_ptr(),
+            public_key_bytes.len(),
+        )
+    };
+    api_error::result_from(result)?;
+    Ok(bytesrepr::deserialize(public_key_bytes).unwrap_or_revert())
+}
+
 fn read_host_buffer_into(dest: &mut [u8]) -> Result<usize, ApiError> {
     let mut bytes_written = MaybeUninit::uninit();
     let ret = unsafe {
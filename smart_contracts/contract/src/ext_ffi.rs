@@ -762,4 +762,94 @@ extern "C" {
         value_ptr: *const u8,
         value_size: usize,
     ) -> i32;
+    /// Emits an event attributed to the calling stored contract.  The topic is read as UTF-8
+    /// from the bytes in wasm memory from offset `topic_ptr` to `topic_ptr + topic_size`, and
+    /// the payload by de-serializing a `CLValue` from the bytes from offset `value_ptr` to
+    /// `value_ptr + value_size`.  This function will cause a `Trap` if the payload fails to
+    /// de-serialize or if it is not called from within a stored contract.
+    ///
+    /// # Arguments
+    ///
+    /// * `topic_ptr` - pointer to bytes representing the topic of the event
+    /// * `topic_size` - size of the topic (in bytes)
+    /// * `value_ptr` - pointer to bytes representing the payload of the event
+    /// * `value_size` - size of the payload (in bytes)
+    pub fn casper_emit_event(
+        topic_ptr: *const u8,
+        topic_size: usize,
+        value_ptr: *const u8,
+        value_size: usize,
+    ) -> i32;
+    /// Verifies a signature over a message.  The public key and the signature are de-serialized
+    /// from the bytes in wasm memory from offset `public_key_ptr` to
+    /// `public_key_ptr + public_key_size` and from offset `signature_ptr` to
+    /// `signature_ptr + signature_size` respectively.  Returns a non-zero value if the signature
+    /// is valid, or zero otherwise.  This function will cause a `Trap` if either the public key or
+    /// the signature fails to de-serialize.
+    ///
+    /// # Arguments
+    ///
+    /// * `public_key_ptr` - pointer to bytes representing the public key
+    /// * `public_key_size` - size of the public key (in bytes)
+    /// * `message_ptr` - pointer to bytes representing the signed message
+    /// * `message_size` - size of the message (in bytes)
+    /// * `signature_ptr` - pointer to bytes representing the signature
+    /// * `signature_size` - size of the signature (in bytes)
+    pub fn casper_verify_signature(
+        public_key_ptr: *const u8,
+        public_key_size: usize,
+        message_ptr: *const u8,
+        message_size: usize,
+        signature_ptr: *const u8,
+        signature_size: usize,
+    ) -> i32;
+    /// Recovers the secp256k1 public key which created a signature over a message, and writes it
+    /// in serialized form to the buffer at `out_ptr`.  The signature is de-serialized from the
+    /// bytes in wasm memory from offset `signature_ptr` to `signature_ptr + signature_size`.  This
+    /// function will cause a `Trap` if the signature fails to de-serialize.
+    ///
+    /// # Arguments
+    ///
+    /// * `message_ptr` - pointer to bytes representing the signed message
+    /// * `message_size` - size of the message (in bytes)
+    /// * `signature_ptr` - pointer to bytes representing the signature
+    /// * `signature_size` - size of the signature (in bytes)
+    /// * `recovery_id` - the recovery id selecting the candidate public key
+    /// * `out_ptr` - pointer to the output buffer for the serialized public key
+    /// * `out_size` - size of the output buffer (in bytes)
+    pub fn casper_recover_secp256k1(
+        message_ptr: *const u8,
+        message_size: usize,
+        signature_ptr: *const u8,
+        signature_size: usize,
+        recovery_id: u8,
+        out_ptr: *mut u8,
+        out_size: usize,
+    ) -> i32;
+    /// Returns a 32-byte SHA-256 hash digest from the given input bytes
+    ///
+    /// # Arguments
+    /// * `in_ptr` - pointer to bytes
+    /// * `in_size` - length of bytes
+    /// * `out_ptr` - pointer to the location where argument bytes will be copied from the host side
+    /// * `out_size` - size of output pointer
+    pub fn casper_sha256(
+        in_ptr: *const u8,
+        in_size: usize,
+        out_ptr: *mut u8,
+        out_size: usize,
+    ) -> i32;
+    /// Returns a 32-byte Keccak-256 hash digest from the given input bytes
+    ///
+    /// # Arguments
+    /// * `in_ptr` - pointer to bytes
+    /// * `in_size` - length of bytes
+    /// * `out_ptr` - pointer to the location where argument bytes will be copied from the host side
+    /// * `out_size` - size of output pointer
+    pub fn casper_keccak256(
+        in_ptr: *const u8,
+        in_size: usize,
+        out_ptr: *mut u8,
+        out_size: usize,
+    ) -> i32;
 }
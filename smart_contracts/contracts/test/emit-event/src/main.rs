@@ -0,0 +1,113 @@
+#![no_std]
+#![no_main]
+
+#[macro_use]
+extern crate alloc;
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use casper_contract::contract_api::{runtime, storage};
+use casper_types::{
+    contracts::{EntryPoint, EntryPoints},
+    CLType, CLTyped, EntryPointAccess, EntryPointType, Parameter,
+};
+
+const EMIT_ENTRY_POINT: &str = "emit";
+const EMIT_FROM_SESSION_ENTRY_POINT: &str = "emit_from_session";
+const EMIT_MANY_ENTRY_POINT: &str = "emit_many";
+const EMIT_LARGE_ENTRY_POINT: &str = "emit_large";
+const HASH_KEY_NAME: &str = "emit_event_hash";
+const PACKAGE_HASH_KEY_NAME: &str = "emit_event_package_hash";
+const ARG_TOPIC: &str = "topic";
+const ARG_VALUE: &str = "value";
+const ARG_COUNT: &str = "count";
+const ARG_SIZE: &str = "size";
+
+fn emit_event_from_args() {
+    let topic: String = runtime::get_named_arg(ARG_TOPIC);
+    let value: u64 = runtime::get_named_arg(ARG_VALUE);
+    runtime::emit_event(&topic, value);
+}
+
+#[no_mangle]
+pub extern "C" fn emit() {
+    emit_event_from_args()
+}
+
+#[no_mangle]
+pub extern "C" fn emit_from_session() {
+    emit_event_from_args()
+}
+
+#[no_mangle]
+pub extern "C" fn emit_many() {
+    let count: u32 = runtime::get_named_arg(ARG_COUNT);
+    for _ in 0..count {
+        emit_event_from_args()
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn emit_large() {
+    let topic: String = runtime::get_named_arg(ARG_TOPIC);
+    let size: u32 = runtime::get_named_arg(ARG_SIZE);
+    let payload: Vec<u8> = vec![0; size as usize];
+    runtime::emit_event(&topic, payload);
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let entry_points = {
+        let mut entry_points = EntryPoints::new();
+        let parameters = vec![
+            Parameter::new(ARG_TOPIC, String::cl_type()),
+            Parameter::new(ARG_VALUE, u64::cl_type()),
+        ];
+        entry_points.add_entry_point(EntryPoint::new(
+            EMIT_ENTRY_POINT.to_string(),
+            parameters.clone(),
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        ));
+        entry_points.add_entry_point(EntryPoint::new(
+            EMIT_FROM_SESSION_ENTRY_POINT.to_string(),
+            parameters.clone(),
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Session,
+        ));
+        let mut emit_many_parameters = parameters;
+        emit_many_parameters.push(Parameter::new(ARG_COUNT, u32::cl_type()));
+        entry_points.add_entry_point(EntryPoint::new(
+            EMIT_MANY_ENTRY_POINT.to_string(),
+            emit_many_parameters,
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        ));
+        entry_points.add_entry_point(EntryPoint::new(
+            EMIT_LARGE_ENTRY_POINT.to_string(),
+            vec![
+                Parameter::new(ARG_TOPIC, String::cl_type()),
+                Parameter::new(ARG_SIZE, u32::cl_type()),
+            ],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        ));
+        entry_points
+    };
+
+    let (contract_hash, _contract_version) = storage::new_contract(
+        entry_points,
+        None,
+        Some(PACKAGE_HASH_KEY_NAME.to_string()),
+        None,
+    );
+
+    runtime::put_key(HASH_KEY_NAME, contract_hash.into());
+}
@@ -0,0 +1,32 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use casper_contract::contract_api::{runtime, storage};
+use casper_types::{bytesrepr::Bytes, ApiError};
+
+const HASH_RESULT: &str = "hash_result";
+
+const ARG_ALGORITHM: &str = "algorithm";
+const ARG_BYTES: &str = "bytes";
+
+const ALGORITHM_BLAKE2B: &str = "blake2b";
+const ALGORITHM_SHA256: &str = "sha256";
+const ALGORITHM_KECCAK256: &str = "keccak256";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let algorithm: String = runtime::get_named_arg(ARG_ALGORITHM);
+    let bytes: Bytes = runtime::get_named_arg(ARG_BYTES);
+    let hash = match algorithm.as_str() {
+        ALGORITHM_BLAKE2B => runtime::blake2b(bytes),
+        ALGORITHM_SHA256 => runtime::sha256(bytes),
+        ALGORITHM_KECCAK256 => runtime::keccak256(bytes),
+        _ => runtime::revert(ApiError::InvalidArgument),
+    };
+    let uref = storage::new_uref(hash);
+    runtime::put_key(HASH_RESULT, uref.into())
+}
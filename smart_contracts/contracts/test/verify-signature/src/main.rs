@@ -0,0 +1,40 @@
+#![no_std]
+#![no_main]
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    bytesrepr::{self, Bytes},
+    PublicKey, Signature,
+};
+
+const ARG_PUBLIC_KEY: &str = "public_key";
+const ARG_MESSAGE: &str = "message";
+const ARG_SIGNATURE: &str = "signature";
+const ARG_RECOVERY_ID: &str = "recovery_id";
+
+const IS_VALID: &str = "is_valid";
+const RECOVERED_PUBLIC_KEY: &str = "recovered_public_key";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let public_key: PublicKey = runtime::get_named_arg(ARG_PUBLIC_KEY);
+    let message: Bytes = runtime::get_named_arg(ARG_MESSAGE);
+    let signature_bytes: Bytes = runtime::get_named_arg(ARG_SIGNATURE);
+    let signature: Signature = bytesrepr::deserialize(signature_bytes.into()).unwrap_or_revert();
+
+    let is_valid = runtime::verify_signature(&public_key, &message, &signature);
+    runtime::put_key(IS_VALID, storage::new_uref(is_valid).into());
+
+    if let PublicKey::Secp256k1(_) = public_key {
+        let recovery_id: u8 = runtime::get_named_arg(ARG_RECOVERY_ID);
+        let recovered_public_key =
+            runtime::recover_secp256k1(&message, &signature, recovery_id).unwrap_or_revert();
+        runtime::put_key(
+            RECOVERED_PUBLIC_KEY,
+            storage::new_uref(recovered_public_key).into(),
+        );
+    }
+}
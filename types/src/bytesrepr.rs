@@ -22,6 +22,8 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 pub use bytes::Bytes;
+#[cfg(feature = "derive")]
+pub use casper_types_derive::{FromBytes, ToBytes};
 
 /// The number of bytes in a serialized `()`.
 pub const UNIT_SERIALIZED_LENGTH: usize = 0;
@@ -1127,9 +1129,10 @@ pub(crate) fn vec_u8_serialized_length(vec: &Vec<u8>) -> usize {
     u8_slice_serialized_length(vec.as_slice())
 }
 
-// This test helper is not intended to be used by third party crates.
-#[doc(hidden)]
-/// Returns `true` if a we can serialize and then deserialize a value
+/// Asserts that `t` can be serialized and then deserialized to a value equal to `t`, and that
+/// `t.serialized_length()` matches the length of its serialized form.
+///
+/// Intended for use in tests of `ToBytes` and `FromBytes` implementations.
 pub fn test_serialization_roundtrip<T>(t: &T)
 where
     T: alloc::fmt::Debug + ToBytes + FromBytes + PartialEq,
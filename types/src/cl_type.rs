@@ -7,7 +7,7 @@ use alloc::{
     string::String,
     vec::Vec,
 };
-use core::mem;
+use core::{convert::TryFrom, mem};
 
 use num_rational::Ratio;
 #[cfg(feature = "std")]
@@ -15,7 +15,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    bytesrepr::{self, FromBytes, ToBytes},
+    bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH},
     Key, URef, U128, U256, U512,
 };
 
@@ -42,6 +42,12 @@ const CL_TYPE_TAG_TUPLE2: u8 = 19;
 const CL_TYPE_TAG_TUPLE3: u8 = 20;
 const CL_TYPE_TAG_ANY: u8 = 21;
 const CL_TYPE_TAG_PUBLIC_KEY: u8 = 22;
+const CL_TYPE_TAG_STRUCT: u8 = 23;
+const CL_TYPE_TAG_ENUM: u8 = 24;
+
+/// The maximum number of variants of a [`CLType::Enum`], as values hold the index of their variant
+/// in a single byte.
+pub const CL_TYPE_ENUM_MAX_VARIANTS: usize = 256;
 
 /// Casper types, i.e. types which can be stored and manipulated by smart contracts.
 ///
@@ -101,6 +107,25 @@ pub enum CLType {
     Tuple3([Box<CLType>; 3]),
     /// Unspecified type.
     Any,
+    /// Named struct with named fields of `CLType`s.
+    ///
+    /// Values are serialized as the values of their fields, in order.
+    Struct {
+        /// The name of the struct.
+        name: String,
+        /// The names and types of the fields of the struct.
+        fields: Vec<(String, CLType)>,
+    },
+    /// Named enum whose variants each hold a value of a single `CLType`.
+    ///
+    /// Values are serialized as the `u8` index of their variant followed by the value it holds.
+    /// Variants holding no data use [`CLType::Unit`].
+    Enum {
+        /// The name of the enum.
+        name: String,
+        /// The names and types of the variants of the enum.
+        variants: Vec<(String, CLType)>,
+    },
 }
 
 impl CLType {
@@ -130,6 +155,10 @@ impl CLType {
                 CLType::Tuple1(cl_type_array) => serialized_length_of_cl_tuple_type(cl_type_array),
                 CLType::Tuple2(cl_type_array) => serialized_length_of_cl_tuple_type(cl_type_array),
                 CLType::Tuple3(cl_type_array) => serialized_length_of_cl_tuple_type(cl_type_array),
+                CLType::Struct { name, fields } => serialized_length_of_cl_named_type(name, fields),
+                CLType::Enum { name, variants } => {
+                    serialized_length_of_cl_named_type(name, variants)
+                }
             }
     }
 
@@ -193,6 +222,15 @@ impl CLType {
                 serialize_cl_tuple_type(CL_TYPE_TAG_TUPLE3, cl_type_array, stream)?
             }
             CLType::Any => stream.push(CL_TYPE_TAG_ANY),
+            CLType::Struct { name, fields } => {
+                serialize_cl_named_type(CL_TYPE_TAG_STRUCT, name, fields, stream)?
+            }
+            CLType::Enum { name, variants } => {
+                if variants.len() > CL_TYPE_ENUM_MAX_VARIANTS {
+                    return Err(bytesrepr::Error::Formatting);
+                }
+                serialize_cl_named_type(CL_TYPE_TAG_ENUM, name, variants, stream)?
+            }
         }
         Ok(())
     }
@@ -279,6 +317,19 @@ impl FromBytes for CLType {
                 Ok((cl_type, remainder))
             }
             CL_TYPE_TAG_ANY => Ok((CLType::Any, remainder)),
+            CL_TYPE_TAG_STRUCT => {
+                let (name, remainder) = String::from_bytes(remainder)?;
+                let (fields, remainder) = parse_cl_named_types(remainder)?;
+                Ok((CLType::Struct { name, fields }, remainder))
+            }
+            CL_TYPE_TAG_ENUM => {
+                let (name, remainder) = String::from_bytes(remainder)?;
+                let (variants, remainder) = parse_cl_named_types(remainder)?;
+                if variants.len() > CL_TYPE_ENUM_MAX_VARIANTS {
+                    return Err(bytesrepr::Error::Formatting);
+                }
+                Ok((CLType::Enum { name, variants }, remainder))
+            }
             _ => Err(bytesrepr::Error::Formatting),
         }
     }
@@ -319,6 +370,44 @@ fn serialized_length_of_cl_tuple_type<'a, T: IntoIterator<Item = &'a Box<CLType>
         .sum()
 }
 
+fn serialize_cl_named_type(
+    tag: u8,
+    name: &str,
+    named_cl_types: &[(String, CLType)],
+    stream: &mut Vec<u8>,
+) -> Result<(), bytesrepr::Error> {
+    stream.push(tag);
+    stream.append(&mut name.to_bytes()?);
+    let count = u32::try_from(named_cl_types.len()).map_err(|_| bytesrepr::Error::Formatting)?;
+    stream.append(&mut count.to_bytes()?);
+    for (name, cl_type) in named_cl_types {
+        stream.append(&mut name.to_bytes()?);
+        cl_type.append_bytes(stream)?;
+    }
+    Ok(())
+}
+
+fn parse_cl_named_types(bytes: &[u8]) -> Result<(Vec<(String, CLType)>, &[u8]), bytesrepr::Error> {
+    let (count, mut bytes) = u32::from_bytes(bytes)?;
+    let mut named_cl_types = Vec::new();
+    for _ in 0..count {
+        let (name, remainder) = String::from_bytes(bytes)?;
+        let (cl_type, remainder) = CLType::from_bytes(remainder)?;
+        named_cl_types.push((name, cl_type));
+        bytes = remainder;
+    }
+    Ok((named_cl_types, bytes))
+}
+
+fn serialized_length_of_cl_named_type(name: &str, named_cl_types: &[(String, CLType)]) -> usize {
+    name.serialized_length()
+        + U32_SERIALIZED_LENGTH
+        + named_cl_types
+            .iter()
+            .map(|(name, cl_type)| name.serialized_length() + cl_type.serialized_length())
+            .sum::<usize>()
+}
+
 /// A type which can be described as a [`CLType`].
 pub trait CLTyped {
     /// The `CLType` of `Self`.
@@ -713,4 +802,136 @@ mod tests {
         let any = Any("Any test".to_string());
         round_trip(&any);
     }
+
+    #[derive(PartialEq, Debug, Clone)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    impl CLTyped for Point {
+        fn cl_type() -> CLType {
+            CLType::Struct {
+                name: "Point".to_string(),
+                fields: vec![
+                    ("x".to_string(), CLType::I32),
+                    ("y".to_string(), CLType::I32),
+                ],
+            }
+        }
+    }
+
+    impl ToBytes for Point {
+        fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+            let mut result = self.x.to_bytes()?;
+            result.append(&mut self.y.to_bytes()?);
+            Ok(result)
+        }
+
+        fn serialized_length(&self) -> usize {
+            self.x.serialized_length() + self.y.serialized_length()
+        }
+    }
+
+    impl FromBytes for Point {
+        fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+            let (x, remainder) = i32::from_bytes(bytes)?;
+            let (y, remainder) = i32::from_bytes(remainder)?;
+            Ok((Point { x, y }, remainder))
+        }
+    }
+
+    #[derive(PartialEq, Debug, Clone)]
+    enum Shape {
+        Empty,
+        Circle(u32),
+        Line((Point, Point)),
+    }
+
+    impl CLTyped for Shape {
+        fn cl_type() -> CLType {
+            CLType::Enum {
+                name: "Shape".to_string(),
+                variants: vec![
+                    ("Empty".to_string(), CLType::Unit),
+                    ("Circle".to_string(), CLType::U32),
+                    ("Line".to_string(), <(Point, Point)>::cl_type()),
+                ],
+            }
+        }
+    }
+
+    impl ToBytes for Shape {
+        fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+            match self {
+                Shape::Empty => Ok(vec![0]),
+                Shape::Circle(radius) => {
+                    let mut result = vec![1];
+                    result.append(&mut radius.to_bytes()?);
+                    Ok(result)
+                }
+                Shape::Line(points) => {
+                    let mut result = vec![2];
+                    result.append(&mut points.to_bytes()?);
+                    Ok(result)
+                }
+            }
+        }
+
+        fn serialized_length(&self) -> usize {
+            1 + match self {
+                Shape::Empty => 0,
+                Shape::Circle(radius) => radius.serialized_length(),
+                Shape::Line(points) => points.serialized_length(),
+            }
+        }
+    }
+
+    impl FromBytes for Shape {
+        fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+            let (variant, remainder) = u8::from_bytes(bytes)?;
+            match variant {
+                0 => Ok((Shape::Empty, remainder)),
+                1 => {
+                    let (radius, remainder) = u32::from_bytes(remainder)?;
+                    Ok((Shape::Circle(radius), remainder))
+                }
+                2 => {
+                    let (points, remainder) = FromBytes::from_bytes(remainder)?;
+                    Ok((Shape::Line(points), remainder))
+                }
+                _ => Err(bytesrepr::Error::Formatting),
+            }
+        }
+    }
+
+    #[test]
+    fn struct_should_work() {
+        round_trip(&Point { x: -1, y: 2 });
+        round_trip(&vec![Point { x: 1, y: 2 }, Point { x: 3, y: 4 }]);
+    }
+
+    #[test]
+    fn enum_should_work() {
+        round_trip(&Shape::Empty);
+        round_trip(&Shape::Circle(3));
+        round_trip(&Shape::Line((Point { x: 0, y: 0 }, Point { x: 1, y: -1 })));
+        round_trip(&Some(Shape::Circle(1)));
+    }
+
+    #[test]
+    fn enum_with_too_many_variants_should_fail() {
+        let variants = (0..=CL_TYPE_ENUM_MAX_VARIANTS)
+            .map(|index| (index.to_string(), CLType::Unit))
+            .collect();
+        let cl_type = CLType::Enum {
+            name: "Large".to_string(),
+            variants,
+        };
+        let mut bytes = Vec::new();
+        assert_eq!(
+            cl_type.append_bytes(&mut bytes),
+            Err(bytesrepr::Error::Formatting)
+        );
+    }
 }
@@ -98,6 +98,14 @@ impl CLValue {
         CLValue::from_components(CLType::Unit, Vec::new())
     }
 
+    /// Constructs a `CLValue` of the given type from its JSON representation, in the format of the
+    /// `parsed` field of a JSON-encoded `CLValue`.
+    ///
+    /// Returns `None` if `json_value` does not represent a value of type `cl_type`.
+    pub fn from_json(cl_type: &CLType, json_value: &Value) -> Option<CLValue> {
+        jsonrepr::cl_value_from_json(cl_type, json_value)
+    }
+
     // This is only required in order to implement `TryFrom<state::CLValue> for CLValue` (i.e. the
     // conversion from the Protobuf `CLValue`) in a separate module to this one.
     #[doc(hidden)]
@@ -1,10 +1,13 @@
-use alloc::{string::String, vec, vec::Vec};
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+use core::convert::TryFrom;
 
-use serde::Serialize;
-use serde_json::{json, Value};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Map, Value};
 
 use crate::{
-    bytesrepr::{self, FromBytes, OPTION_NONE_TAG, OPTION_SOME_TAG, RESULT_ERR_TAG, RESULT_OK_TAG},
+    bytesrepr::{
+        self, FromBytes, ToBytes, OPTION_NONE_TAG, OPTION_SOME_TAG, RESULT_ERR_TAG, RESULT_OK_TAG,
+    },
     CLType, CLValue, Key, PublicKey, URef, U128, U256, U512,
 };
 
@@ -98,9 +101,188 @@ fn to_json<'a>(cl_type: &CLType, bytes: &'a [u8]) -> Option<(Value, &'a [u8])> {
             Some((json!([t1, t2, t3]), remainder))
         }
         CLType::Any => None,
+        CLType::Struct { fields, .. } => {
+            let mut stream = bytes;
+            let mut result = Map::new();
+            for (name, field_cl_type) in fields {
+                let (value, remainder) = to_json(field_cl_type, stream)?;
+                result.insert(name.clone(), value);
+                stream = remainder;
+            }
+            Some((Value::Object(result), stream))
+        }
+        CLType::Enum { variants, .. } => {
+            let (index, remainder) = u8::from_bytes(bytes).ok()?;
+            let (name, variant_cl_type) = variants.get(index as usize)?;
+            if *variant_cl_type == CLType::Unit {
+                let ((), remainder) = <()>::from_bytes(remainder).ok()?;
+                return Some((json!(name), remainder));
+            }
+            let (value, remainder) = to_json(variant_cl_type, remainder)?;
+            let mut result = Map::new();
+            result.insert(name.clone(), value);
+            Some((Value::Object(result), remainder))
+        }
     }
 }
 
+/// Converts a JSON value in the format produced by `cl_value_to_json` into a `CLValue` of the given
+/// type.  Returns `None` if the JSON value does not represent a value of that type.
+pub fn cl_value_from_json(cl_type: &CLType, json_value: &Value) -> Option<CLValue> {
+    let mut bytes = Vec::new();
+    from_json(cl_type, json_value, &mut bytes)?;
+    Some(CLValue::from_components(cl_type.clone(), bytes))
+}
+
+fn from_json(cl_type: &CLType, json_value: &Value, stream: &mut Vec<u8>) -> Option<()> {
+    match cl_type {
+        CLType::Bool => simple_type_from_json::<bool>(json_value, stream),
+        CLType::I32 => {
+            let value = i32::try_from(json_value.as_i64()?).ok()?;
+            stream.append(&mut value.to_bytes().ok()?);
+            Some(())
+        }
+        CLType::I64 => {
+            stream.append(&mut json_value.as_i64()?.to_bytes().ok()?);
+            Some(())
+        }
+        CLType::U8 => {
+            let value = u8::try_from(json_value.as_u64()?).ok()?;
+            stream.append(&mut value.to_bytes().ok()?);
+            Some(())
+        }
+        CLType::U32 => {
+            let value = u32::try_from(json_value.as_u64()?).ok()?;
+            stream.append(&mut value.to_bytes().ok()?);
+            Some(())
+        }
+        CLType::U64 => {
+            stream.append(&mut json_value.as_u64()?.to_bytes().ok()?);
+            Some(())
+        }
+        CLType::U128 => simple_type_from_json::<U128>(json_value, stream),
+        CLType::U256 => simple_type_from_json::<U256>(json_value, stream),
+        CLType::U512 => simple_type_from_json::<U512>(json_value, stream),
+        CLType::Unit => json_value.as_null(),
+        CLType::String => simple_type_from_json::<String>(json_value, stream),
+        CLType::Key => simple_type_from_json::<Key>(json_value, stream),
+        CLType::URef => simple_type_from_json::<URef>(json_value, stream),
+        CLType::PublicKey => simple_type_from_json::<PublicKey>(json_value, stream),
+        CLType::Option(inner_cl_type) => {
+            if json_value.is_null() {
+                stream.push(OPTION_NONE_TAG);
+            } else {
+                stream.push(OPTION_SOME_TAG);
+                from_json(inner_cl_type, json_value, stream)?;
+            }
+            Some(())
+        }
+        CLType::List(inner_cl_type) => {
+            let values = json_value.as_array()?;
+            let count = u32::try_from(values.len()).ok()?;
+            stream.append(&mut count.to_bytes().ok()?);
+            for value in values {
+                from_json(inner_cl_type, value, stream)?;
+            }
+            Some(())
+        }
+        CLType::ByteArray(length) => {
+            let mut bytes = hex::decode(json_value.as_str()?).ok()?;
+            if bytes.len() != *length as usize {
+                return None;
+            }
+            stream.append(&mut bytes);
+            Some(())
+        }
+        CLType::Result { ok, err } => {
+            let (variant, value) = single_entry(json_value)?;
+            match variant.as_str() {
+                "Ok" => {
+                    stream.push(RESULT_OK_TAG);
+                    from_json(ok, value, stream)
+                }
+                "Err" => {
+                    stream.push(RESULT_ERR_TAG);
+                    from_json(err, value, stream)
+                }
+                _ => None,
+            }
+        }
+        CLType::Map { key, value } => {
+            let entries = json_value.as_array()?;
+            let count = u32::try_from(entries.len()).ok()?;
+            stream.append(&mut count.to_bytes().ok()?);
+            for entry in entries {
+                let entry = entry.as_object()?;
+                if entry.len() != 2 {
+                    return None;
+                }
+                from_json(key, entry.get("key")?, stream)?;
+                from_json(value, entry.get("value")?, stream)?;
+            }
+            Some(())
+        }
+        CLType::Tuple1(arr) => tuple_from_json(arr, json_value, stream),
+        CLType::Tuple2(arr) => tuple_from_json(arr, json_value, stream),
+        CLType::Tuple3(arr) => tuple_from_json(arr, json_value, stream),
+        CLType::Any => None,
+        CLType::Struct { fields, .. } => {
+            let object = json_value.as_object()?;
+            if object.len() != fields.len() {
+                return None;
+            }
+            for (name, field_cl_type) in fields {
+                from_json(field_cl_type, object.get(name)?, stream)?;
+            }
+            Some(())
+        }
+        CLType::Enum { variants, .. } => {
+            let (name, value) = match json_value {
+                Value::String(name) => (name, &Value::Null),
+                _ => single_entry(json_value)?,
+            };
+            let index = variants
+                .iter()
+                .position(|(variant_name, _)| variant_name == name)?;
+            stream.push(u8::try_from(index).ok()?);
+            from_json(&variants[index].1, value, stream)
+        }
+    }
+}
+
+fn simple_type_from_json<T: ToBytes + DeserializeOwned>(
+    json_value: &Value,
+    stream: &mut Vec<u8>,
+) -> Option<()> {
+    let value: T = serde_json::from_value(json_value.clone()).ok()?;
+    stream.append(&mut value.to_bytes().ok()?);
+    Some(())
+}
+
+fn tuple_from_json(
+    cl_type_array: &[Box<CLType>],
+    json_value: &Value,
+    stream: &mut Vec<u8>,
+) -> Option<()> {
+    let values = json_value.as_array()?;
+    if values.len() != cl_type_array.len() {
+        return None;
+    }
+    for (cl_type, value) in cl_type_array.iter().zip(values) {
+        from_json(cl_type, value, stream)?;
+    }
+    Some(())
+}
+
+/// Returns the key and value of a JSON object with exactly one entry.
+fn single_entry(json_value: &Value) -> Option<(&String, &Value)> {
+    let object = json_value.as_object()?;
+    if object.len() != 1 {
+        return None;
+    }
+    object.iter().next()
+}
+
 fn simple_type_to_json<T: FromBytes + Serialize>(bytes: &[u8]) -> Option<(Value, &[u8])> {
     let (value, remainder) = T::from_bytes(bytes).ok()?;
     Some((json!(value), remainder))
@@ -228,4 +410,89 @@ mod tests {
         test_value((v1.clone(), v2.clone()));
         test_value((v1, v2, v3));
     }
+
+    fn point_cl_type() -> CLType {
+        CLType::Struct {
+            name: String::from("Point"),
+            fields: vec![
+                (String::from("x"), CLType::I32),
+                (String::from("y"), CLType::Option(Box::new(CLType::String))),
+            ],
+        }
+    }
+
+    fn shape_cl_type() -> CLType {
+        CLType::Enum {
+            name: String::from("Shape"),
+            variants: vec![
+                (String::from("Empty"), CLType::Unit),
+                (String::from("Circle"), CLType::U512),
+                (String::from("Point"), point_cl_type()),
+            ],
+        }
+    }
+
+    fn assert_json_round_trip(cl_type: CLType, json_value: Value, expected_bytes: Vec<u8>) {
+        let cl_value = cl_value_from_json(&cl_type, &json_value).unwrap();
+        assert_eq!(*cl_value.inner_bytes(), expected_bytes);
+        assert_eq!(cl_value_to_json(&cl_value).unwrap(), json_value);
+    }
+
+    #[test]
+    fn struct_to_json_value() {
+        let mut bytes = (-1_i32).to_bytes().unwrap();
+        bytes.append(&mut Some(String::from("a")).to_bytes().unwrap());
+        assert_json_round_trip(point_cl_type(), json!({ "x": -1, "y": "a" }), bytes);
+    }
+
+    #[test]
+    fn enum_to_json_value() {
+        assert_json_round_trip(shape_cl_type(), json!("Empty"), vec![0]);
+
+        let mut bytes = vec![1];
+        bytes.append(&mut U512::from(5).to_bytes().unwrap());
+        assert_json_round_trip(shape_cl_type(), json!({ "Circle": "5" }), bytes);
+
+        let mut bytes = vec![2];
+        bytes.append(&mut 3_i32.to_bytes().unwrap());
+        bytes.append(&mut Option::<String>::None.to_bytes().unwrap());
+        assert_json_round_trip(
+            shape_cl_type(),
+            json!({ "Point": { "x": 3, "y": null } }),
+            bytes,
+        );
+    }
+
+    #[test]
+    fn json_value_to_cl_value() {
+        fn check<T: ToBytes + Serialize + Clone + CLTyped>(value: T) {
+            let cl_value = CLValue::from_t(value.clone()).unwrap();
+            let json_value = cl_value_to_json(&cl_value).unwrap();
+            assert_eq!(
+                cl_value_from_json(cl_value.cl_type(), &json_value),
+                Some(cl_value)
+            );
+        }
+
+        check(vec![1_u8, 2]);
+        check(Some(U512::from(u64::max_value())));
+        check(Result::<i64, String>::Err(String::from("Upsss")));
+        check((String::from("Hello"), -1_i32, [1_u8; 32]));
+        check(Key::Hash([1; 32]));
+        let mut map: BTreeMap<String, Vec<u64>> = BTreeMap::new();
+        map.insert(String::from("first"), vec![1, 2]);
+        check(map);
+    }
+
+    #[test]
+    fn invalid_json_value_to_cl_value() {
+        assert!(cl_value_from_json(&CLType::U8, &json!(256)).is_none());
+        assert!(cl_value_from_json(&CLType::ByteArray(2), &json!("01")).is_none());
+        assert!(cl_value_from_json(&point_cl_type(), &json!({ "x": 1 })).is_none());
+        assert!(
+            cl_value_from_json(&point_cl_type(), &json!({ "x": 1, "y": null, "z": 2 })).is_none()
+        );
+        assert!(cl_value_from_json(&shape_cl_type(), &json!("Square")).is_none());
+        assert!(cl_value_from_json(&CLType::Any, &json!(null)).is_none());
+    }
 }
@@ -0,0 +1,246 @@
+// TODO - remove once schemars stops causing warning.
+#![allow(clippy::field_reassign_with_default)]
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "std")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{CLType, CLValue, Contract, EntryPoint, NamedKey, RuntimeArgs};
+
+/// An error encoding the args of an entry point from their JSON representation.
+#[derive(Debug, PartialEq)]
+pub enum ContractSchemaError {
+    /// The contract has no entry point of the given name.
+    NoSuchEntryPoint(String),
+    /// No value was given for the named arg.
+    MissingArg(String),
+    /// A value was given for an arg which the entry point does not take.
+    UnexpectedArg(String),
+    /// The value of the named arg does not represent a value of the given type.
+    InvalidArg {
+        /// The name of the arg.
+        name: String,
+        /// The type of the entry point's parameter.
+        cl_type: CLType,
+    },
+}
+
+impl Display for ContractSchemaError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ContractSchemaError::NoSuchEntryPoint(name) => {
+                write!(f, "no such entry point: {}", name)
+            }
+            ContractSchemaError::MissingArg(name) => write!(f, "missing arg: {}", name),
+            ContractSchemaError::UnexpectedArg(name) => write!(f, "unexpected arg: {}", name),
+            ContractSchemaError::InvalidArg { name, cl_type } => {
+                write!(f, "invalid value for arg {} of type {:?}", name, cl_type)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ContractSchemaError {}
+
+/// A machine-readable description of the entry points and named keys of a stored contract.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ContractSchema {
+    /// The entry points of the contract, ordered by name.
+    pub entry_points: Vec<EntryPoint>,
+    /// The named keys of the contract, ordered by name.
+    pub named_keys: Vec<NamedKey>,
+}
+
+impl ContractSchema {
+    /// Returns the entry point of the given name.
+    pub fn entry_point(&self, name: &str) -> Option<&EntryPoint> {
+        self.entry_points
+            .iter()
+            .find(|entry_point| entry_point.name() == name)
+    }
+
+    /// Encodes the args for a call to the named entry point from a JSON object mapping each arg's
+    /// name to the JSON representation of its value.
+    ///
+    /// Args of type `CLType::Option` may be omitted, in which case they are set to `None`.
+    pub fn encode_args(
+        &self,
+        entry_point_name: &str,
+        json_args: &Map<String, Value>,
+    ) -> Result<RuntimeArgs, ContractSchemaError> {
+        let entry_point = self
+            .entry_point(entry_point_name)
+            .ok_or_else(|| ContractSchemaError::NoSuchEntryPoint(entry_point_name.to_string()))?;
+
+        if let Some(name) = json_args.keys().find(|name| {
+            !entry_point
+                .args()
+                .iter()
+                .any(|parameter| parameter.name() == name.as_str())
+        }) {
+            return Err(ContractSchemaError::UnexpectedArg(name.clone()));
+        }
+
+        let mut runtime_args = RuntimeArgs::new();
+        for parameter in entry_point.args() {
+            let name = parameter.name();
+            let cl_type = parameter.cl_type();
+            let json_value = match (json_args.get(name), cl_type) {
+                (Some(json_value), _) => json_value,
+                (None, CLType::Option(_)) => &Value::Null,
+                (None, _) => return Err(ContractSchemaError::MissingArg(name.to_string())),
+            };
+            let cl_value = CLValue::from_json(cl_type, json_value).ok_or_else(|| {
+                ContractSchemaError::InvalidArg {
+                    name: name.to_string(),
+                    cl_type: cl_type.clone(),
+                }
+            })?;
+            runtime_args.insert_cl_value(name, cl_value);
+        }
+        Ok(runtime_args)
+    }
+}
+
+impl From<&Contract> for ContractSchema {
+    fn from(contract: &Contract) -> Self {
+        let entry_points = contract.entry_points().clone().take_entry_points();
+        let named_keys = contract
+            .named_keys()
+            .iter()
+            .map(|(name, key)| NamedKey {
+                name: name.clone(),
+                key: key.to_formatted_string(),
+            })
+            .collect();
+        ContractSchema {
+            entry_points,
+            named_keys,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        contracts::{EntryPoints, NamedKeys},
+        runtime_args, CLTyped, ContractPackageHash, ContractWasmHash, EntryPointAccess,
+        EntryPointType, Key, Parameter, ProtocolVersion, URef, U512,
+    };
+
+    fn schema() -> ContractSchema {
+        let mut entry_points = EntryPoints::new();
+        entry_points.add_entry_point(EntryPoint::new(
+            "transfer",
+            vec![
+                Parameter::new("amount", U512::cl_type()),
+                Parameter::new("memo", Option::<String>::cl_type()),
+            ],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        ));
+
+        let mut named_keys = NamedKeys::new();
+        named_keys.insert(
+            "balances".to_string(),
+            Key::URef(URef::new([1; 32], Default::default())),
+        );
+
+        let contract = Contract::new(
+            ContractPackageHash::new([2; 32]),
+            ContractWasmHash::new([3; 32]),
+            named_keys,
+            entry_points,
+            ProtocolVersion::V1_0_0,
+        );
+        ContractSchema::from(&contract)
+    }
+
+    fn json_args(json_value: Value) -> Map<String, Value> {
+        json_value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn should_describe_contract() {
+        let schema = schema();
+        assert_eq!(schema.entry_points.len(), 1);
+        assert_eq!(schema.named_keys.len(), 1);
+        assert_eq!(schema.named_keys[0].name, "balances");
+        assert!(schema.entry_point("transfer").is_some());
+
+        let json = serde_json::to_string(&schema).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ContractSchema>(&json).unwrap(),
+            schema
+        );
+    }
+
+    #[test]
+    fn should_encode_args() {
+        let schema = schema();
+
+        let args = json_args(json!({"amount": "100", "memo": "rent"}));
+        assert_eq!(
+            schema.encode_args("transfer", &args).unwrap(),
+            runtime_args! {
+                "amount" => U512::from(100),
+                "memo" => Some("rent".to_string()),
+            }
+        );
+
+        let args = json_args(json!({"amount": "100"}));
+        assert_eq!(
+            schema.encode_args("transfer", &args).unwrap(),
+            runtime_args! {
+                "amount" => U512::from(100),
+                "memo" => Option::<String>::None,
+            }
+        );
+    }
+
+    #[test]
+    fn should_fail_to_encode_invalid_args() {
+        let schema = schema();
+
+        let args = json_args(json!({"amount": "100"}));
+        assert_eq!(
+            schema.encode_args("approve", &args).unwrap_err(),
+            ContractSchemaError::NoSuchEntryPoint("approve".to_string())
+        );
+
+        let args = json_args(json!({}));
+        assert_eq!(
+            schema.encode_args("transfer", &args).unwrap_err(),
+            ContractSchemaError::MissingArg("amount".to_string())
+        );
+
+        let args = json_args(json!({"amount": "100", "recipient": "bob"}));
+        assert_eq!(
+            schema.encode_args("transfer", &args).unwrap_err(),
+            ContractSchemaError::UnexpectedArg("recipient".to_string())
+        );
+
+        let args = json_args(json!({"amount": true}));
+        assert_eq!(
+            schema.encode_args("transfer", &args).unwrap_err(),
+            ContractSchemaError::InvalidArg {
+                name: "amount".to_string(),
+                cl_type: U512::cl_type()
+            }
+        );
+    }
+}
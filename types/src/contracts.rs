@@ -1470,6 +1470,35 @@ mod tests {
         assert_eq!(rem.len(), 0);
     }
 
+    #[test]
+    fn entry_point_with_struct_and_enum_parameters_roundtrip() {
+        let point = CLType::Struct {
+            name: "Point".to_string(),
+            fields: vec![
+                ("x".to_string(), CLType::I32),
+                ("y".to_string(), CLType::I32),
+            ],
+        };
+        let direction = CLType::Enum {
+            name: "Direction".to_string(),
+            variants: vec![
+                ("Left".to_string(), CLType::Unit),
+                ("Right".to_string(), CLType::Unit),
+            ],
+        };
+        let entry_point = EntryPoint::new(
+            "move_point".to_string(),
+            vec![
+                Parameter::new("point", point.clone()),
+                Parameter::new("direction", direction),
+            ],
+            point,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        bytesrepr::test_serialization_roundtrip(&entry_point);
+    }
+
     #[test]
     fn should_remove_group() {
         let mut contract_package = make_contract_package();
@@ -16,17 +16,21 @@ use core::{
 
 use datasize::DataSize;
 use ed25519_dalek::{
-    ed25519::signature::Signature as _Signature, PUBLIC_KEY_LENGTH as ED25519_PUBLIC_KEY_LENGTH,
-    SECRET_KEY_LENGTH as ED25519_SECRET_KEY_LENGTH, SIGNATURE_LENGTH as ED25519_SIGNATURE_LENGTH,
+    ed25519::signature::Signature as _Signature, ExpandedSecretKey,
+    PUBLIC_KEY_LENGTH as ED25519_PUBLIC_KEY_LENGTH, SECRET_KEY_LENGTH as ED25519_SECRET_KEY_LENGTH,
+    SIGNATURE_LENGTH as ED25519_SIGNATURE_LENGTH,
 };
 use hex_fmt::HexFmt;
 use k256::ecdsa::{
+    recoverable,
+    signature::{Signer, Verifier},
     Signature as Secp256k1Signature, SigningKey as Secp256k1SecretKey,
     VerifyingKey as Secp256k1PublicKey,
 };
 #[cfg(feature = "std")]
 use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
 
 use crate::{
     account::AccountHash,
@@ -662,6 +666,96 @@ impl JsonSchema for Signature {
     }
 }
 
+/// Signs the given message using the given key pair.
+pub fn sign<T: AsRef<[u8]>>(
+    message: T,
+    secret_key: &SecretKey,
+    public_key: &PublicKey,
+) -> Signature {
+    match (secret_key, public_key) {
+        (SecretKey::System, PublicKey::System) => {
+            panic!("cannot create signature with system keys",)
+        }
+        (SecretKey::Ed25519(secret_key), PublicKey::Ed25519(public_key)) => {
+            let expanded_secret_key = ExpandedSecretKey::from(secret_key);
+            let signature = expanded_secret_key.sign(message.as_ref(), public_key);
+            Signature::Ed25519(signature)
+        }
+        (SecretKey::Secp256k1(secret_key), PublicKey::Secp256k1(_public_key)) => {
+            let signer = secret_key;
+            let signature: Secp256k1Signature = signer
+                .try_sign(message.as_ref())
+                .expect("should create signature");
+            Signature::Secp256k1(signature)
+        }
+        _ => panic!("secret and public key types must match"),
+    }
+}
+
+/// Verifies the signature of the given message against the given public key.
+pub fn verify<T: AsRef<[u8]>>(
+    message: T,
+    signature: &Signature,
+    public_key: &PublicKey,
+) -> Result<(), Error> {
+    match (signature, public_key) {
+        (Signature::System, _) => Err(Error::AsymmetricKey(String::from(
+            "signatures based on the system key cannot be verified",
+        ))),
+        (Signature::Ed25519(signature), PublicKey::Ed25519(public_key)) => public_key
+            .verify_strict(message.as_ref(), signature)
+            .map_err(|_| Error::AsymmetricKey(String::from("failed to verify Ed25519 signature"))),
+        (Signature::Secp256k1(signature), PublicKey::Secp256k1(public_key)) => {
+            let verifier: &Secp256k1PublicKey = public_key;
+            verifier
+                .verify(message.as_ref(), signature)
+                .map_err(|error| {
+                    Error::AsymmetricKey(format!("failed to verify secp256k1 signature: {}", error))
+                })
+        }
+        _ => Err(Error::AsymmetricKey(format!(
+            "type mismatch between {} and {}",
+            signature, public_key
+        ))),
+    }
+}
+
+/// Recovers the secp256k1 public key which created the given signature of the given message.
+///
+/// As for signing and verifying, the message is hashed using SHA-256.  The `recovery_id` selects
+/// one of the candidate public keys, and is either 0 or 1 for signatures created by this crate.
+pub fn recover_secp256k1<T: AsRef<[u8]>>(
+    message: T,
+    signature: &Signature,
+    recovery_id: u8,
+) -> Result<PublicKey, Error> {
+    let signature = match signature {
+        Signature::Secp256k1(signature) => signature,
+        _ => {
+            return Err(Error::AsymmetricKey(format!(
+                "cannot recover public key from {} signature",
+                signature.variant_name()
+            )))
+        }
+    };
+    let recovery_id = recoverable::Id::new(recovery_id).map_err(|_| {
+        Error::AsymmetricKey(format!("invalid secp256k1 recovery id {}", recovery_id))
+    })?;
+    let recoverable_signature =
+        recoverable::Signature::new(signature, recovery_id).map_err(|error| {
+            Error::AsymmetricKey(format!(
+                "failed to construct recoverable secp256k1 signature: {}",
+                error
+            ))
+        })?;
+    let public_key = recoverable_signature
+        .recover_verify_key_from_digest(Sha256::new().chain(message.as_ref()))
+        .map_err(|error| {
+            Error::AsymmetricKey(format!("failed to recover secp256k1 public key: {}", error))
+        })?;
+    Ok(PublicKey::Secp256k1(public_key))
+}
+
 mod detail {
     use alloc::{string::String, vec::Vec};
 
@@ -1,4 +1,7 @@
-use crate::{crypto::SecretKey, AsymmetricType, PublicKey};
+use crate::{
+    crypto::{self, SecretKey},
+    AsymmetricType, PublicKey,
+};
 
 #[test]
 fn can_construct_ed25519_keypair_from_zeroes() {
@@ -39,3 +42,52 @@ fn can_construct_system_public_key() {
 
     assert_ne!(public_key, (&secret_key).into())
 }
+
+#[test]
+fn should_verify_ed25519_signature() {
+    let secret_key = SecretKey::ed25519_from_bytes([1; SecretKey::ED25519_LENGTH]).unwrap();
+    let public_key = PublicKey::from(&secret_key);
+    let message = b"message";
+    let signature = crypto::sign(message, &secret_key, &public_key);
+
+    assert!(crypto::verify(message, &signature, &public_key).is_ok());
+    assert!(crypto::verify(b"other message", &signature, &public_key).is_err());
+}
+
+#[test]
+fn should_verify_secp256k1_signature() {
+    let secret_key = SecretKey::secp256k1_from_bytes([1; SecretKey::SECP256K1_LENGTH]).unwrap();
+    let public_key = PublicKey::from(&secret_key);
+    let message = b"message";
+    let signature = crypto::sign(message, &secret_key, &public_key);
+
+    assert!(crypto::verify(message, &signature, &public_key).is_ok());
+    assert!(crypto::verify(b"other message", &signature, &public_key).is_err());
+}
+
+#[test]
+fn should_recover_secp256k1_public_key() {
+    let secret_key = SecretKey::secp256k1_from_bytes([1; SecretKey::SECP256K1_LENGTH]).unwrap();
+    let public_key = PublicKey::from(&secret_key);
+    let message = b"message";
+    let signature = crypto::sign(message, &secret_key, &public_key);
+
+    // Exactly one of the two recovery IDs yields the signer's public key.
+    let recovered_count = (0..2)
+        .filter(|recovery_id| {
+            crypto::recover_secp256k1(message, &signature, *recovery_id).ok()
+                == Some(public_key.clone())
+        })
+        .count();
+    assert_eq!(recovered_count, 1);
+    assert!(crypto::recover_secp256k1(message, &signature, 4).is_err());
+}
+
+#[test]
+fn should_not_recover_public_key_from_ed25519_signature() {
+    let secret_key = SecretKey::ed25519_from_bytes([1; SecretKey::ED25519_LENGTH]).unwrap();
+    let public_key = PublicKey::from(&secret_key);
+    let signature = crypto::sign(b"message", &secret_key, &public_key);
+
+    assert!(crypto::recover_secp256k1(b"message", &signature, 0).is_err());
+}
@@ -32,9 +32,18 @@ use crate::{
     account::AccountHash,
     bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
     system::auction::{Bid, EraInfo, UnbondingPurse},
-    CLValue, DeployInfo, NamedKey, Transfer, TransferAddr, U128, U256, U512,
+    CLValue, ContractHash, DeployInfo, NamedKey, Transfer, TransferAddr, U128, U256, U512,
 };
 
+/// The maximum length in bytes of a [`ContractEvent`]'s topic.
+pub const EVENT_TOPIC_MAX_LENGTH: usize = 64;
+
+/// The maximum length in bytes of a [`ContractEvent`]'s serialized payload.
+pub const EVENT_PAYLOAD_MAX_LENGTH: usize = 8 * 1024;
+
+/// The maximum number of [`ContractEvent`]s which can be emitted while executing a single deploy.
+pub const MAX_EVENTS_PER_DEPLOY: usize = 100;
+
 /// Constants to track ExecutionResult serialization.
 const EXECUTION_RESULT_FAILURE_TAG: u8 = 0;
 const EXECUTION_RESULT_SUCCESS_TAG: u8 = 1;
@@ -103,10 +112,17 @@ static EXECUTION_RESULT: Lazy<ExecutionResult> = Lazy::new(|| {
         TransferAddr::new([130; KEY_HASH_LENGTH]),
     ];
 
+    let events = vec![ContractEvent {
+        contract_hash: ContractHash::new([7; KEY_HASH_LENGTH]),
+        topic: "transfer".to_string(),
+        payload: CLValue::from_t(1000u64).unwrap(),
+    }];
+
     ExecutionResult::Success {
         effect,
         transfers,
         cost: U512::from(123_456),
+        events,
     }
 });
 
@@ -134,6 +150,9 @@ pub enum ExecutionResult {
         transfers: Vec<TransferAddr>,
         /// The cost of executing the deploy.
         cost: U512,
+        /// The events emitted by contracts while executing the deploy.
+        #[serde(default)]
+        events: Vec<ContractEvent>,
     },
 }
 
@@ -188,10 +207,17 @@ impl Distribution<ExecutionResult> for Standard {
                 error_message: format!("Error message {}", rng.gen::<u64>()),
             }
         } else {
+            let event_count = rng.gen_range(0..6);
+            let mut events = vec![];
+            for _ in 0..event_count {
+                events.push(rng.gen())
+            }
+
             ExecutionResult::Success {
                 effect,
                 transfers,
                 cost: rng.gen::<u64>().into(),
+                events,
             }
         }
     }
@@ -217,11 +243,13 @@ impl ToBytes for ExecutionResult {
                 effect,
                 transfers,
                 cost,
+                events,
             } => {
                 buffer.push(EXECUTION_RESULT_SUCCESS_TAG);
                 buffer.extend(effect.to_bytes()?);
                 buffer.extend(transfers.to_bytes()?);
                 buffer.extend(cost.to_bytes()?);
+                buffer.extend(events.to_bytes()?);
             }
         }
         Ok(buffer)
@@ -245,10 +273,12 @@ impl ToBytes for ExecutionResult {
                     effect,
                     transfers,
                     cost,
+                    events,
                 } => {
                     effect.serialized_length()
                         + transfers.serialized_length()
                         + cost.serialized_length()
+                        + events.serialized_length()
                 }
             }
     }
@@ -275,10 +305,12 @@ impl FromBytes for ExecutionResult {
                 let (effect, remainder) = ExecutionEffect::from_bytes(remainder)?;
                 let (transfers, remainder) = Vec::<TransferAddr>::from_bytes(remainder)?;
                 let (cost, remainder) = U512::from_bytes(remainder)?;
+                let (events, remainder) = Vec::<ContractEvent>::from_bytes(remainder)?;
                 let execution_result = ExecutionResult::Success {
                     effect,
                     transfers,
                     cost,
+                    events,
                 };
                 Ok((execution_result, remainder))
             }
@@ -287,6 +319,59 @@ impl FromBytes for ExecutionResult {
     }
 }
 
+/// An event emitted by a stored contract while executing a deploy.
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ContractEvent {
+    /// The hash of the contract which emitted the event.
+    pub contract_hash: ContractHash,
+    /// The topic of the event.
+    pub topic: String,
+    /// The payload of the event.
+    pub payload: CLValue,
+}
+
+impl Distribution<ContractEvent> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> ContractEvent {
+        ContractEvent {
+            contract_hash: ContractHash::new(rng.gen()),
+            topic: format!("topic-{}", rng.gen::<u8>()),
+            payload: CLValue::from_t(rng.gen::<u64>()).unwrap(),
+        }
+    }
+}
+
+impl ToBytes for ContractEvent {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.extend(self.contract_hash.to_bytes()?);
+        buffer.extend(self.topic.to_bytes()?);
+        buffer.extend(self.payload.to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.contract_hash.serialized_length()
+            + self.topic.serialized_length()
+            + self.payload.serialized_length()
+    }
+}
+
+impl FromBytes for ContractEvent {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (contract_hash, remainder) = ContractHash::from_bytes(bytes)?;
+        let (topic, remainder) = String::from_bytes(remainder)?;
+        let (payload, remainder) = CLValue::from_bytes(remainder)?;
+        let contract_event = ContractEvent {
+            contract_hash,
+            topic,
+            payload,
+        };
+        Ok((contract_event, remainder))
+    }
+}
+
 /// The effect of executing a single deploy.
 #[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Default, Debug)]
 #[cfg_attr(feature = "std", derive(JsonSchema))]
@@ -683,4 +768,10 @@ mod tests {
         let execution_result: ExecutionResult = rng.gen();
         bytesrepr::test_serialization_roundtrip(&execution_result);
     }
+    #[test]
+    fn bytesrepr_test_contract_event() {
+        let mut rng = get_rng();
+        let contract_event: ContractEvent = rng.gen();
+        bytesrepr::test_serialization_roundtrip(&contract_event);
+    }
 }
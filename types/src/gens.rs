@@ -14,6 +14,7 @@ use proptest::{
 
 use crate::{
     account::{AccountHash, Weight},
+    bytesrepr::ToBytes,
     contracts::{
         ContractPackageStatus, ContractVersions, DisabledVersions, Groups, NamedKeys, Parameters,
     },
@@ -177,6 +178,11 @@ pub fn cl_type_arb() -> impl Strategy<Value = CLType> {
                 Box::new(cl_type1),
                 Box::new(cl_type2)
             ])),
+            // Named structs and enums
+            ("\\PC*", vec(("\\PC*", element.clone()), 0..4))
+                .prop_map(|(name, fields)| CLType::Struct { name, fields }),
+            ("\\PC*", vec(("\\PC*", element.clone()), 0..4))
+                .prop_map(|(name, variants)| CLType::Enum { name, variants }),
             (element.clone(), element.clone(), element).prop_map(
                 |(cl_type1, cl_type2, cl_type3)| CLType::Tuple3([
                     Box::new(cl_type1),
@@ -216,7 +222,9 @@ pub fn cl_value_arb() -> impl Strategy<Value = CLValue> {
             | CLType::Tuple1(_)
             | CLType::Tuple2(_)
             | CLType::Tuple3(_)
-            | CLType::Any => (),
+            | CLType::Any
+            | CLType::Struct { .. }
+            | CLType::Enum { .. } => (),
         }
     };
 
@@ -247,6 +255,27 @@ pub fn cl_value_arb() -> impl Strategy<Value = CLValue> {
             .prop_map(|x| CLValue::from_t(x).expect("should create CLValue")),
         // Fixed lists of any size
         any::<u8>().prop_map(|len| CLValue::from_t([len; 32]).expect("should create CLValue")),
+        // Named structs and enums, serialized like tuples and options respectively
+        (any::<bool>(), any::<i32>()).prop_map(|x| {
+            let cl_type = CLType::Struct {
+                name: String::from("Struct"),
+                fields: vec![
+                    (String::from("a"), CLType::Bool),
+                    (String::from("b"), CLType::I32),
+                ],
+            };
+            CLValue::from_components(cl_type, x.to_bytes().expect("should serialize"))
+        }),
+        option::of(any::<u64>()).prop_map(|x| {
+            let cl_type = CLType::Enum {
+                name: String::from("Enum"),
+                variants: vec![
+                    (String::from("None"), CLType::Unit),
+                    (String::from("Some"), CLType::U64),
+                ],
+            };
+            CLValue::from_components(cl_type, x.to_bytes().expect("should serialize"))
+        }),
     ]
 }
 
@@ -41,6 +41,10 @@ const DICTIONARY_PREFIX: &str = "dictionary-";
 
 /// The number of bytes in a Blake2b hash
 pub const BLAKE2B_DIGEST_LENGTH: usize = 32;
+/// The number of bytes in a SHA-256 hash
+pub const SHA256_DIGEST_LENGTH: usize = 32;
+/// The number of bytes in a Keccak-256 hash
+pub const KECCAK256_DIGEST_LENGTH: usize = 32;
 /// The number of bytes in a [`Key::Hash`].
 pub const KEY_HASH_LENGTH: usize = 32;
 /// The number of bytes in a [`Key::Transfer`].
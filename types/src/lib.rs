@@ -32,6 +32,7 @@ mod block_time;
 pub mod bytesrepr;
 mod cl_type;
 mod cl_value;
+mod contract_schema;
 mod contract_wasm;
 pub mod contracts;
 pub mod crypto;
@@ -58,8 +59,11 @@ pub use access_rights::{AccessRights, ACCESS_RIGHTS_SERIALIZED_LENGTH};
 #[doc(inline)]
 pub use api_error::ApiError;
 pub use block_time::{BlockTime, BLOCKTIME_SERIALIZED_LENGTH};
+#[cfg(feature = "derive")]
+pub use casper_types_derive::CLTyped;
 pub use cl_type::{named_key_type, CLType, CLTyped};
 pub use cl_value::{CLTypeMismatch, CLValue, CLValueError};
+pub use contract_schema::{ContractSchema, ContractSchemaError};
 pub use contract_wasm::{ContractWasm, ContractWasmHash};
 #[doc(inline)]
 pub use contracts::{
@@ -70,13 +74,14 @@ pub use contracts::{
 pub use crypto::*;
 pub use deploy_info::DeployInfo;
 pub use execution_result::{
-    ExecutionEffect, ExecutionResult, OpKind, Operation, Transform, TransformEntry,
+    ContractEvent, ExecutionEffect, ExecutionResult, OpKind, Operation, Transform, TransformEntry,
+    EVENT_PAYLOAD_MAX_LENGTH, EVENT_TOPIC_MAX_LENGTH, MAX_EVENTS_PER_DEPLOY,
 };
 pub use json_pretty_printer::json_pretty_print;
 #[doc(inline)]
 pub use key::{
     DictionaryAddr, HashAddr, Key, KeyTag, BLAKE2B_DIGEST_LENGTH, DICTIONARY_ITEM_KEY_MAX_LENGTH,
-    KEY_DICTIONARY_LENGTH, KEY_HASH_LENGTH,
+    KECCAK256_DIGEST_LENGTH, KEY_DICTIONARY_LENGTH, KEY_HASH_LENGTH, SHA256_DIGEST_LENGTH,
 };
 pub use named_key::NamedKey;
 pub use phase::{Phase, PHASE_SERIALIZED_LENGTH};
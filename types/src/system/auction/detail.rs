@@ -7,8 +7,9 @@ use crate::{
     account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
     system::auction::{
-        constants::*, Auction, Bids, EraId, Error, RuntimeProvider, SeigniorageAllocation,
-        SeigniorageRecipientsSnapshot, StorageProvider, UnbondingPurse, UnbondingPurses,
+        constants::*, Auction, Bids, Delegator, EraId, Error, RuntimeProvider,
+        SeigniorageAllocation, SeigniorageRecipientsSnapshot, SlashHandling, SlashedStake,
+        StorageProvider, UnbondingPurse, UnbondingPurses,
     },
     CLTyped, Key, KeyTag, PublicKey, URef, U512,
 };
@@ -182,6 +183,43 @@ where
     read_from(provider, UNBONDING_DELAY_KEY)
 }
 
+/// Returns the minimum amount of a new delegation, or zero if the auction was installed without
+/// one.
+fn get_minimum_delegation_amount<P>(provider: &mut P) -> Result<U512, Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    if provider
+        .named_keys_get(MINIMUM_DELEGATION_AMOUNT_KEY)
+        .is_none()
+    {
+        return Ok(U512::zero());
+    }
+    let minimum_delegation_amount: u64 = read_from(provider, MINIMUM_DELEGATION_AMOUNT_KEY)?;
+    Ok(U512::from(minimum_delegation_amount))
+}
+
+/// Returns the maximum number of delegators per validator, or `None` if there is no limit.
+fn get_max_delegators_per_validator<P>(provider: &mut P) -> Result<Option<usize>, Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    if provider
+        .named_keys_get(MAX_DELEGATORS_PER_VALIDATOR_KEY)
+        .is_none()
+    {
+        return Ok(None);
+    }
+    let max_delegators_per_validator: u32 = read_from(provider, MAX_DELEGATORS_PER_VALIDATOR_KEY)?;
+    if max_delegators_per_validator == 0 {
+        return Ok(None);
+    }
+    let max_delegators_per_validator = max_delegators_per_validator
+        .try_into()
+        .map_err(|_| Error::ArithmeticOverflow)?;
+    Ok(Some(max_delegators_per_validator))
+}
+
 /// Iterates over unbonding entries and checks if a locked amount can be paid already if
 /// a specific era is reached.
 ///
@@ -205,10 +243,12 @@ pub(crate) fn process_unbond_requests<P: Auction + ?Sized>(provider: &mut P) ->
             // current era id + unbonding delay is equal or greater than the `era_of_creation` that
             // was calculated on `unbond` attempt.
             if current_era_id >= unbonding_purse.era_of_creation() + unbonding_delay {
-                // Move funds from bid purse to unbonding purse
-                provider
-                    .unbond(unbonding_purse)
-                    .map_err(|_| Error::TransferToUnbondingPurse)?;
+                if !handle_redelegation(provider, unbonding_purse)? {
+                    // Move funds from bid purse to unbonding purse
+                    provider
+                        .unbond(unbonding_purse)
+                        .map_err(|_| Error::TransferToUnbondingPurse)?;
+                }
             } else {
                 new_unbonding_list.push(unbonding_purse.clone());
             }
@@ -220,14 +260,130 @@ pub(crate) fn process_unbond_requests<P: Auction + ?Sized>(provider: &mut P) ->
     Ok(())
 }
 
+/// Delegates the funds of an unbonding request to its new validator, if it was created through
+/// [`Auction::redelegate`].
+///
+/// Returns `false` if the funds should be paid out to the unbonder instead, either because the
+/// request is not a redelegation, or because the new validator's bid has since been withdrawn or
+/// deactivated (e.g. as a result of being slashed).
+fn handle_redelegation<P: Auction + ?Sized>(
+    provider: &mut P,
+    unbonding_purse: &UnbondingPurse,
+) -> Result<bool, Error> {
+    let new_validator = match unbonding_purse.new_validator() {
+        Some(new_validator) => new_validator.clone(),
+        None => return Ok(false),
+    };
+
+    let new_validator_account_hash = AccountHash::from(&new_validator);
+    match provider.read_bid(&new_validator_account_hash)? {
+        Some(bid) if !bid.inactive() => {}
+        _ => return Ok(false),
+    }
+
+    match handle_delegation(
+        provider,
+        unbonding_purse.unbonder_public_key().clone(),
+        new_validator,
+        *unbonding_purse.bonding_purse(),
+        *unbonding_purse.amount(),
+    ) {
+        Ok(_) => Ok(true),
+        // The new validator can't accept the delegation, so the amount is paid out instead.
+        Err(Error::DelegationAmountTooSmall) | Err(Error::ExceededDelegatorSizeLimit) => Ok(false),
+        Err(error) => Err(error),
+    }
+}
+
+/// Transfers `amount` from the `source` purse to the delegator's bonding purse for the given
+/// validator, creating the delegation if it doesn't exist yet. Returns the new delegated amount.
+///
+/// A new delegation must be at least the minimum delegation amount, and is rejected if the
+/// validator already has the maximum number of delegators.
+pub(crate) fn handle_delegation<P: Auction + ?Sized>(
+    provider: &mut P,
+    delegator_public_key: PublicKey,
+    validator_public_key: PublicKey,
+    source: URef,
+    amount: U512,
+) -> Result<U512, Error> {
+    let validator_account_hash = AccountHash::from(&validator_public_key);
+
+    let mut bid = match provider.read_bid(&validator_account_hash)? {
+        Some(bid) => bid,
+        None => {
+            // Return early if target validator is not in `bids`
+            return Err(Error::ValidatorNotFound);
+        }
+    };
+
+    if !bid.delegators().contains_key(&delegator_public_key) {
+        if amount < get_minimum_delegation_amount(provider)? {
+            return Err(Error::DelegationAmountTooSmall);
+        }
+        if let Some(max_delegators_per_validator) = get_max_delegators_per_validator(provider)? {
+            if bid.delegators().len() >= max_delegators_per_validator {
+                return Err(Error::ExceededDelegatorSizeLimit);
+            }
+        }
+    }
+
+    let delegators = bid.delegators_mut();
+
+    let new_delegation_amount = match delegators.get_mut(&delegator_public_key) {
+        Some(delegator) => {
+            provider
+                .mint_transfer_direct(
+                    Some(PublicKey::System.to_account_hash()),
+                    source,
+                    *delegator.bonding_purse(),
+                    amount,
+                    None,
+                )
+                .map_err(|_| Error::TransferToDelegatorPurse)?
+                .map_err(|_| Error::TransferToDelegatorPurse)?;
+            delegator.increase_stake(amount)?;
+            *delegator.staked_amount()
+        }
+        None => {
+            let bonding_purse = provider.create_purse()?;
+            provider
+                .mint_transfer_direct(
+                    Some(PublicKey::System.to_account_hash()),
+                    source,
+                    bonding_purse,
+                    amount,
+                    None,
+                )
+                .map_err(|_| Error::TransferToDelegatorPurse)?
+                .map_err(|_| Error::TransferToDelegatorPurse)?;
+            let delegator = Delegator::unlocked(
+                delegator_public_key.clone(),
+                amount,
+                bonding_purse,
+                validator_public_key,
+            );
+            delegators.insert(delegator_public_key, delegator);
+            amount
+        }
+    };
+
+    provider.write_bid(validator_account_hash, bid)?;
+
+    Ok(new_delegation_amount)
+}
+
 /// Creates a new purse in unbonding_purses given a validator's key, amount, and a destination
 /// unbonding purse. Returns the amount of motes remaining in the validator's bid purse.
+///
+/// If `new_validator` is given, the amount will be delegated to it once unbonded.
 pub(crate) fn create_unbonding_purse<P: Auction + ?Sized>(
     provider: &mut P,
     validator_public_key: PublicKey,
     unbonder_public_key: PublicKey,
     bonding_purse: URef,
     amount: U512,
+    new_validator: Option<PublicKey>,
 ) -> Result<(), Error> {
     if provider.get_balance(bonding_purse)?.unwrap_or_default() < amount {
         return Err(Error::UnbondTooLarge);
@@ -242,6 +398,7 @@ pub(crate) fn create_unbonding_purse<P: Auction + ?Sized>(
         unbonder_public_key,
         era_of_creation,
         amount,
+        new_validator,
     );
     unbonding_purses.push(new_unbonding_purse);
     provider.write_withdraw(validator_account_hash, unbonding_purses)?;
@@ -249,6 +406,113 @@ pub(crate) fn create_unbonding_purse<P: Auction + ?Sized>(
     Ok(())
 }
 
+/// Returns the part of `amount` which is slashed at the given `slashing_rate`.
+fn slashed_amount(amount: U512, slashing_rate: Ratio<u64>) -> Result<U512, Error> {
+    let slashed_amount = amount
+        .checked_mul(U512::from(*slashing_rate.numer()))
+        .and_then(|product| product.checked_div(U512::from(*slashing_rate.denom())))
+        .ok_or(Error::ArithmeticOverflow)?;
+    Ok(slashed_amount.min(amount))
+}
+
+/// Removes `amount` of slashed stake from `bonding_purse`, as per the configured
+/// [`SlashHandling`].
+fn remove_slashed_stake<P: Auction + ?Sized>(
+    provider: &mut P,
+    bonding_purse: URef,
+    amount: U512,
+) -> Result<(), Error> {
+    if amount.is_zero() {
+        return Ok(());
+    }
+    match provider.slash_handling() {
+        SlashHandling::Burn => provider.burn(bonding_purse, amount),
+        SlashHandling::Accumulate => provider.accumulate(bonding_purse, amount),
+    }
+}
+
+/// Slashes the stakes of a validator and of its delegators, including their pending unbonding
+/// requests, at the configured slashing rate.  The validator's bid is deactivated once its stake
+/// has been slashed entirely.
+///
+/// Returns the stakes which were slashed, starting with the validator's own stake.
+pub(crate) fn slash_validator<P: Auction + ?Sized>(
+    provider: &mut P,
+    validator_public_key: PublicKey,
+) -> Result<Vec<SlashedStake>, Error> {
+    let slashing_rate = provider.slashing_rate();
+    let validator_account_hash = AccountHash::from(&validator_public_key);
+    let mut validator_amount = U512::zero();
+    let mut delegator_amounts: BTreeMap<PublicKey, U512> = BTreeMap::new();
+
+    if let Some(mut bid) = provider.read_bid(&validator_account_hash)? {
+        let amount = slashed_amount(*bid.staked_amount(), slashing_rate)?;
+        remove_slashed_stake(provider, *bid.bonding_purse(), amount)?;
+        *bid.staked_amount_mut() -= amount;
+        validator_amount += amount;
+
+        for (delegator_public_key, delegator) in bid.delegators_mut().iter_mut() {
+            let amount = slashed_amount(*delegator.staked_amount(), slashing_rate)?;
+            remove_slashed_stake(provider, *delegator.bonding_purse(), amount)?;
+            *delegator.staked_amount_mut() -= amount;
+            *delegator_amounts
+                .entry(delegator_public_key.clone())
+                .or_default() += amount;
+        }
+
+        if bid.staked_amount().is_zero() {
+            bid.deactivate();
+        }
+        provider.write_bid(validator_account_hash, bid)?;
+    }
+
+    let unbonding_purses = provider.read_withdraw(&validator_account_hash)?;
+    if !unbonding_purses.is_empty() {
+        let mut remaining_unbonding_purses = Vec::new();
+        for unbonding_purse in unbonding_purses {
+            let amount = slashed_amount(*unbonding_purse.amount(), slashing_rate)?;
+            remove_slashed_stake(provider, *unbonding_purse.bonding_purse(), amount)?;
+            if unbonding_purse.is_validator() {
+                validator_amount += amount;
+            } else {
+                *delegator_amounts
+                    .entry(unbonding_purse.unbonder_public_key().clone())
+                    .or_default() += amount;
+            }
+
+            let remaining_amount = *unbonding_purse.amount() - amount;
+            if !remaining_amount.is_zero() {
+                remaining_unbonding_purses.push(UnbondingPurse::new(
+                    *unbonding_purse.bonding_purse(),
+                    unbonding_purse.validator_public_key().clone(),
+                    unbonding_purse.unbonder_public_key().clone(),
+                    unbonding_purse.era_of_creation(),
+                    remaining_amount,
+                    unbonding_purse.new_validator().clone(),
+                ));
+            }
+        }
+        provider.write_withdraw(validator_account_hash, remaining_unbonding_purses)?;
+    }
+
+    let mut slashed_stakes = Vec::new();
+    if !validator_amount.is_zero() {
+        slashed_stakes.push(SlashedStake::validator(
+            validator_public_key.clone(),
+            validator_amount,
+        ));
+    }
+    slashed_stakes.extend(
+        delegator_amounts
+            .into_iter()
+            .filter(|(_, amount)| !amount.is_zero())
+            .map(|(delegator_public_key, amount)| {
+                SlashedStake::delegator(delegator_public_key, validator_public_key.clone(), amount)
+            }),
+    );
+    Ok(slashed_stakes)
+}
+
 /// Reinvests delegator reward by increasing its stake.
 pub fn reinvest_delegator_rewards<P>(
     provider: &mut P,
@@ -1,7 +1,7 @@
 // TODO - remove once schemars stops causing warning.
 #![allow(clippy::field_reassign_with_default)]
 
-use alloc::{boxed::Box, vec::Vec};
+use alloc::vec::Vec;
 
 #[cfg(feature = "std")]
 use schemars::JsonSchema;
@@ -15,6 +15,9 @@ use crate::{
 const SEIGNIORAGE_ALLOCATION_VALIDATOR_TAG: u8 = 0;
 const SEIGNIORAGE_ALLOCATION_DELEGATOR_TAG: u8 = 1;
 
+const SLASHED_STAKE_VALIDATOR_TAG: u8 = 0;
+const SLASHED_STAKE_DELEGATOR_TAG: u8 = 1;
+
 /// Information about a seigniorage allocation
 #[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "std", derive(JsonSchema))]
@@ -157,20 +160,175 @@ impl CLTyped for SeigniorageAllocation {
     }
 }
 
+/// Information about the stake slashed from a validator or one of its delegators.
+///
+/// Only the slashed amount is recorded, not the evidence of equivocation which caused it.  The
+/// evidence is logged by the node executing the slash, but is not part of global state.
+#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub enum SlashedStake {
+    /// Info about the stake slashed from a validator
+    Validator {
+        /// Validator's public key
+        validator_public_key: PublicKey,
+        /// Slashed amount
+        amount: U512,
+    },
+    /// Info about the stake slashed from a delegator of a slashed validator
+    Delegator {
+        /// Delegator's public key
+        delegator_public_key: PublicKey,
+        /// Validator's public key
+        validator_public_key: PublicKey,
+        /// Slashed amount
+        amount: U512,
+    },
+}
+
+impl SlashedStake {
+    /// Constructs a [`SlashedStake::Validator`]
+    pub const fn validator(validator_public_key: PublicKey, amount: U512) -> Self {
+        SlashedStake::Validator {
+            validator_public_key,
+            amount,
+        }
+    }
+
+    /// Constructs a [`SlashedStake::Delegator`]
+    pub const fn delegator(
+        delegator_public_key: PublicKey,
+        validator_public_key: PublicKey,
+        amount: U512,
+    ) -> Self {
+        SlashedStake::Delegator {
+            delegator_public_key,
+            validator_public_key,
+            amount,
+        }
+    }
+
+    /// Returns the public key of the slashed validator
+    pub fn validator_public_key(&self) -> &PublicKey {
+        match self {
+            SlashedStake::Validator {
+                validator_public_key,
+                ..
+            } => validator_public_key,
+            SlashedStake::Delegator {
+                validator_public_key,
+                ..
+            } => validator_public_key,
+        }
+    }
+
+    /// Returns the slashed amount
+    pub fn amount(&self) -> &U512 {
+        match self {
+            SlashedStake::Validator { amount, .. } => amount,
+            SlashedStake::Delegator { amount, .. } => amount,
+        }
+    }
+
+    fn tag(&self) -> u8 {
+        match self {
+            SlashedStake::Validator { .. } => SLASHED_STAKE_VALIDATOR_TAG,
+            SlashedStake::Delegator { .. } => SLASHED_STAKE_DELEGATOR_TAG,
+        }
+    }
+}
+
+impl ToBytes for SlashedStake {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.append(&mut self.tag().to_bytes()?);
+        match self {
+            SlashedStake::Validator {
+                validator_public_key,
+                amount,
+            } => {
+                buffer.append(&mut validator_public_key.to_bytes()?);
+                buffer.append(&mut amount.to_bytes()?);
+            }
+            SlashedStake::Delegator {
+                delegator_public_key,
+                validator_public_key,
+                amount,
+            } => {
+                buffer.append(&mut delegator_public_key.to_bytes()?);
+                buffer.append(&mut validator_public_key.to_bytes()?);
+                buffer.append(&mut amount.to_bytes()?);
+            }
+        }
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.tag().serialized_length()
+            + match self {
+                SlashedStake::Validator {
+                    validator_public_key,
+                    amount,
+                } => validator_public_key.serialized_length() + amount.serialized_length(),
+                SlashedStake::Delegator {
+                    delegator_public_key,
+                    validator_public_key,
+                    amount,
+                } => {
+                    delegator_public_key.serialized_length()
+                        + validator_public_key.serialized_length()
+                        + amount.serialized_length()
+                }
+            }
+    }
+}
+
+impl FromBytes for SlashedStake {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, rem) = <u8>::from_bytes(bytes)?;
+        match tag {
+            SLASHED_STAKE_VALIDATOR_TAG => {
+                let (validator_public_key, rem) = PublicKey::from_bytes(rem)?;
+                let (amount, rem) = U512::from_bytes(rem)?;
+                Ok((SlashedStake::validator(validator_public_key, amount), rem))
+            }
+            SLASHED_STAKE_DELEGATOR_TAG => {
+                let (delegator_public_key, rem) = PublicKey::from_bytes(rem)?;
+                let (validator_public_key, rem) = PublicKey::from_bytes(rem)?;
+                let (amount, rem) = U512::from_bytes(rem)?;
+                Ok((
+                    SlashedStake::delegator(delegator_public_key, validator_public_key, amount),
+                    rem,
+                ))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+impl CLTyped for SlashedStake {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
 /// Auction metadata.  Intended to be recorded at each era.
 #[derive(Debug, Default, Clone, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "std", derive(JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub struct EraInfo {
     seigniorage_allocations: Vec<SeigniorageAllocation>,
+    slashed_stakes: Vec<SlashedStake>,
 }
 
 impl EraInfo {
     /// Constructs a [`EraInfo`].
     pub fn new() -> Self {
         let seigniorage_allocations = Vec::new();
+        let slashed_stakes = Vec::new();
         EraInfo {
             seigniorage_allocations,
+            slashed_stakes,
         }
     }
 
@@ -184,6 +342,17 @@ impl EraInfo {
         &mut self.seigniorage_allocations
     }
 
+    /// Returns a reference to the slashed stakes collection, which records amounts but not the
+    /// evidence against the slashed validators
+    pub fn slashed_stakes(&self) -> &Vec<SlashedStake> {
+        &self.slashed_stakes
+    }
+
+    /// Returns a mutable reference to the slashed stakes collection
+    pub fn slashed_stakes_mut(&mut self) -> &mut Vec<SlashedStake> {
+        &mut self.slashed_stakes
+    }
+
     /// Returns all seigniorage allocations that match the provided public key
     /// using the following criteria:
     /// * If the match candidate is a validator allocation, the provided public key is matched
@@ -208,20 +377,25 @@ impl EraInfo {
 
 impl ToBytes for EraInfo {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
-        self.seigniorage_allocations.to_bytes()
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.append(&mut self.seigniorage_allocations.to_bytes()?);
+        buffer.append(&mut self.slashed_stakes.to_bytes()?);
+        Ok(buffer)
     }
 
     fn serialized_length(&self) -> usize {
-        self.seigniorage_allocations.serialized_length()
+        self.seigniorage_allocations.serialized_length() + self.slashed_stakes.serialized_length()
     }
 }
 
 impl FromBytes for EraInfo {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
         let (seigniorage_allocations, rem) = Vec::<SeigniorageAllocation>::from_bytes(bytes)?;
+        let (slashed_stakes, rem) = Vec::<SlashedStake>::from_bytes(rem)?;
         Ok((
             EraInfo {
                 seigniorage_allocations,
+                slashed_stakes,
             },
             rem,
         ))
@@ -230,7 +404,7 @@ impl FromBytes for EraInfo {
 
 impl CLTyped for EraInfo {
     fn cl_type() -> CLType {
-        CLType::List(Box::new(SeigniorageAllocation::cl_type()))
+        CLType::Any
     }
 }
 
@@ -246,7 +420,7 @@ pub mod gens {
     use crate::{
         crypto::gens::public_key_arb,
         gens::u512_arb,
-        system::auction::{EraInfo, SeigniorageAllocation},
+        system::auction::{EraInfo, SeigniorageAllocation, SlashedStake},
     };
 
     fn seigniorage_allocation_validator_arb() -> impl Strategy<Value = SeigniorageAllocation> {
@@ -271,14 +445,38 @@ pub mod gens {
         ]
     }
 
-    /// Creates an arbitrary [`EraInfo`]
-    pub fn era_info_arb(size: impl Into<SizeRange>) -> impl Strategy<Value = EraInfo> {
-        collection::vec(seigniorage_allocation_arb(), size).prop_map(|allocations| {
-            let mut era_info = EraInfo::new();
-            *era_info.seigniorage_allocations_mut() = allocations;
-            era_info
+    fn slashed_stake_validator_arb() -> impl Strategy<Value = SlashedStake> {
+        (public_key_arb(), u512_arb()).prop_map(|(validator_public_key, amount)| {
+            SlashedStake::validator(validator_public_key, amount)
         })
     }
+
+    fn slashed_stake_delegator_arb() -> impl Strategy<Value = SlashedStake> {
+        (public_key_arb(), public_key_arb(), u512_arb()).prop_map(
+            |(delegator_public_key, validator_public_key, amount)| {
+                SlashedStake::delegator(delegator_public_key, validator_public_key, amount)
+            },
+        )
+    }
+
+    /// Creates an arbitrary [`SlashedStake`](crate::system::auction::SlashedStake)
+    pub fn slashed_stake_arb() -> impl Strategy<Value = SlashedStake> {
+        prop_oneof![slashed_stake_validator_arb(), slashed_stake_delegator_arb()]
+    }
+
+    /// Creates an arbitrary [`EraInfo`]
+    pub fn era_info_arb(size: impl Into<SizeRange> + Clone) -> impl Strategy<Value = EraInfo> {
+        (
+            collection::vec(seigniorage_allocation_arb(), size.clone()),
+            collection::vec(slashed_stake_arb(), size),
+        )
+            .prop_map(|(allocations, slashed_stakes)| {
+                let mut era_info = EraInfo::new();
+                *era_info.seigniorage_allocations_mut() = allocations;
+                *era_info.slashed_stakes_mut() = slashed_stakes;
+                era_info
+            })
+    }
 }
 
 #[cfg(test)]
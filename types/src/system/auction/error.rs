@@ -152,6 +152,15 @@ pub enum Error {
     /// An arithmetic overflow has occurred.
     #[cfg_attr(feature = "std", error("Arithmetic overflow"))]
     ArithmeticOverflow = 39,
+    /// Raised when a new delegation is smaller than the minimum delegation amount.
+    #[cfg_attr(feature = "std", error("Delegation amount too small"))]
+    DelegationAmountTooSmall = 40,
+    /// Raised when a validator already has the maximum number of delegators.
+    #[cfg_attr(feature = "std", error("Exceeded delegator size limit"))]
+    ExceededDelegatorSizeLimit = 41,
+    /// Failed to burn the stake of a slashed validator or its delegators.
+    #[cfg_attr(feature = "std", error("Burn slashed stake error"))]
+    BurnSlashedStake = 42,
 
     // NOTE: These variants below and related plumbing will be removed once support for WASM
     // system contracts will be dropped.
@@ -233,6 +242,11 @@ impl TryFrom<u8> for Error {
             d if d == Error::DelegatorFundsLocked as u8 => Ok(Error::DelegatorFundsLocked),
             d if d == Error::GasLimit as u8 => Ok(Error::GasLimit),
             d if d == Error::ArithmeticOverflow as u8 => Ok(Error::ArithmeticOverflow),
+            d if d == Error::DelegationAmountTooSmall as u8 => Ok(Error::DelegationAmountTooSmall),
+            d if d == Error::ExceededDelegatorSizeLimit as u8 => {
+                Ok(Error::ExceededDelegatorSizeLimit)
+            }
+            d if d == Error::BurnSlashedStake as u8 => Ok(Error::BurnSlashedStake),
             _ => Err(TryFromU8ForError(())),
         }
     }
@@ -8,6 +8,7 @@ mod era_info;
 mod error;
 mod providers;
 mod seigniorage_recipient;
+mod slash_handling;
 mod unbonding_purse;
 
 use alloc::{collections::BTreeMap, vec::Vec};
@@ -25,6 +26,7 @@ pub use era_info::*;
 pub use error::Error;
 pub use providers::{AccountProvider, MintProvider, RuntimeProvider, StorageProvider};
 pub use seigniorage_recipient::SeigniorageRecipient;
+pub use slash_handling::SlashHandling;
 pub use unbonding_purse::UnbondingPurse;
 
 /// Representation of delegation rate of tokens. Range from 0..=100.
@@ -203,6 +205,7 @@ pub trait Auction:
             public_key.clone(), // validator is the unbonder
             *bid.bonding_purse(),
             amount,
+            None,
         )?;
 
         if updated_stake.is_zero() {
@@ -214,6 +217,7 @@ pub trait Auction:
                     delegator_public_key.clone(),
                     *delegator.bonding_purse(),
                     *delegator.staked_amount(),
+                    None,
                 )?;
             }
 
@@ -259,70 +263,88 @@ pub trait Auction:
 
         let source = self.get_main_purse()?;
 
-        let validator_account_hash = AccountHash::from(&validator_public_key);
+        detail::handle_delegation(
+            self,
+            delegator_public_key,
+            validator_public_key,
+            source,
+            amount,
+        )
+    }
 
+    /// Removes an amount of motes (or the entry altogether, if the remaining amount is 0) from
+    /// the entry in delegators and calls unbond in the Mint contract to create a new unbonding
+    /// purse.
+    ///
+    /// The arguments are the delegator’s key, the validator key and quantity of motes and
+    /// returns a tuple of the unbonding purse along with the remaining bid amount.
+    fn undelegate(
+        &mut self,
+        delegator_public_key: PublicKey,
+        validator_public_key: PublicKey,
+        amount: U512,
+    ) -> Result<U512, Error> {
+        let provided_account_hash =
+            AccountHash::from_public_key(&delegator_public_key, |x| self.blake2b(x));
+        match self.get_immediate_caller() {
+            Some(&CallStackElement::Session { account_hash })
+                if account_hash != provided_account_hash =>
+            {
+                return Err(Error::InvalidContext)
+            }
+            Some(&CallStackElement::StoredSession { .. }) => {
+                // stored session code is not allowed to call this method
+                return Err(Error::InvalidContext);
+            }
+            _ => {}
+        };
+
+        let validator_account_hash = AccountHash::from(&validator_public_key);
         let mut bid = match self.read_bid(&validator_account_hash)? {
             Some(bid) => bid,
-            None => {
-                // Return early if target validator is not in `bids`
-                return Err(Error::ValidatorNotFound);
-            }
+            None => return Err(Error::ValidatorNotFound),
         };
 
         let delegators = bid.delegators_mut();
 
-        let new_delegation_amount = match delegators.get_mut(&delegator_public_key) {
+        let new_amount = match delegators.get_mut(&delegator_public_key) {
             Some(delegator) => {
-                self.mint_transfer_direct(
-                    Some(PublicKey::System.to_account_hash()),
-                    source,
+                detail::create_unbonding_purse(
+                    self,
+                    validator_public_key,
+                    delegator_public_key.clone(),
                     *delegator.bonding_purse(),
                     amount,
                     None,
-                )
-                .map_err(|_| Error::TransferToDelegatorPurse)?
-                .map_err(|_| Error::TransferToDelegatorPurse)?;
-                delegator.increase_stake(amount)?;
-                *delegator.staked_amount()
-            }
-            None => {
-                let bonding_purse = self.create_purse()?;
-                self.mint_transfer_direct(
-                    Some(PublicKey::System.to_account_hash()),
-                    source,
-                    bonding_purse,
-                    amount,
-                    None,
-                )
-                .map_err(|_| Error::TransferToDelegatorPurse)?
-                .map_err(|_| Error::TransferToDelegatorPurse)?;
-                let delegator = Delegator::unlocked(
-                    delegator_public_key.clone(),
-                    amount,
-                    bonding_purse,
-                    validator_public_key,
-                );
-                delegators.insert(delegator_public_key.clone(), delegator);
-                amount
+                )?;
+
+                let era_end_timestamp_millis = detail::get_era_end_timestamp_millis(self)?;
+                let updated_stake = delegator.decrease_stake(amount, era_end_timestamp_millis)?;
+                if updated_stake == U512::zero() {
+                    delegators.remove(&delegator_public_key);
+                };
+                updated_stake
             }
+            None => return Err(Error::DelegatorNotFound),
         };
 
         self.write_bid(validator_account_hash, bid)?;
 
-        Ok(new_delegation_amount)
+        Ok(new_amount)
     }
 
     /// Removes an amount of motes (or the entry altogether, if the remaining amount is 0) from
-    /// the entry in delegators and calls unbond in the Mint contract to create a new unbonding
-    /// purse.
+    /// the entry in delegators and creates a new unbonding purse which, once the unbonding delay
+    /// has elapsed, delegates the amount to `new_validator` rather than paying it out.
     ///
-    /// The arguments are the delegator’s key, the validator key and quantity of motes and
-    /// returns a tuple of the unbonding purse along with the remaining bid amount.
-    fn undelegate(
+    /// If by then `new_validator` no longer has an active bid, the amount is paid out to the
+    /// delegator as for [`Auction::undelegate`].  Returns the remaining delegated amount.
+    fn redelegate(
         &mut self,
         delegator_public_key: PublicKey,
         validator_public_key: PublicKey,
         amount: U512,
+        new_validator: PublicKey,
     ) -> Result<U512, Error> {
         let provided_account_hash =
             AccountHash::from_public_key(&delegator_public_key, |x| self.blake2b(x));
@@ -339,6 +361,11 @@ pub trait Auction:
             _ => {}
         };
 
+        let new_validator_account_hash = AccountHash::from(&new_validator);
+        if self.read_bid(&new_validator_account_hash)?.is_none() {
+            return Err(Error::ValidatorNotFound);
+        }
+
         let validator_account_hash = AccountHash::from(&validator_public_key);
         let mut bid = match self.read_bid(&validator_account_hash)? {
             Some(bid) => bid,
@@ -355,6 +382,7 @@ pub trait Auction:
                     delegator_public_key.clone(),
                     *delegator.bonding_purse(),
                     amount,
+                    Some(new_validator),
                 )?;
 
                 let era_end_timestamp_millis = detail::get_era_end_timestamp_millis(self)?;
@@ -372,7 +400,8 @@ pub trait Auction:
         Ok(new_amount)
     }
 
-    /// Slashes each validator.
+    /// Slashes each validator and its delegators at the configured slashing rate, and records the
+    /// slashed stakes in the era info of the current era.
     ///
     /// This can be only invoked through a system call.
     fn slash(&mut self, validator_public_keys: Vec<PublicKey>) -> Result<(), Error> {
@@ -380,35 +409,17 @@ pub trait Auction:
             return Err(Error::InvalidCaller);
         }
 
-        let mut burned_amount: U512 = U512::zero();
-
+        let mut slashed_stakes = Vec::new();
         for validator_public_key in validator_public_keys {
-            // Burn stake, deactivate
-            let validator_account_hash = AccountHash::from(&validator_public_key);
-            if let Some(mut bid) = self.read_bid(&validator_account_hash)? {
-                burned_amount += *bid.staked_amount();
-                *bid.staked_amount_mut() = U512::zero();
-                bid.deactivate();
-                // Reset delegator stakes when deactivating validator bid.
-                for delegator in bid.delegators_mut().values_mut() {
-                    *delegator.staked_amount_mut() = U512::zero();
-                }
-                self.write_bid(validator_account_hash, bid)?;
-            };
-
-            let validator_account_hash = AccountHash::from(&validator_public_key);
-            // Update unbonding entries for given validator
-            let unbonding_purses = self.read_withdraw(&validator_account_hash)?;
-            if !unbonding_purses.is_empty() {
-                burned_amount += unbonding_purses
-                    .into_iter()
-                    .map(|unbonding_purse| *unbonding_purse.amount())
-                    .sum();
-                self.write_withdraw(validator_account_hash, Vec::new())?;
-            }
+            slashed_stakes.extend(detail::slash_validator(self, validator_public_key)?);
         }
 
-        self.reduce_total_supply(burned_amount)?;
+        if !slashed_stakes.is_empty() {
+            let era_id = detail::get_era_id(self)?;
+            let mut era_info = self.read_era_info(era_id)?.unwrap_or_default();
+            era_info.slashed_stakes_mut().extend(slashed_stakes);
+            self.record_era_info(era_id, era_info)?;
+        }
 
         Ok(())
     }
@@ -1,10 +1,12 @@
 use alloc::{collections::BTreeSet, vec::Vec};
 
+use num_rational::Ratio;
+
 use crate::{
     account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
     system::{
-        auction::{Bid, EraId, EraInfo, Error, UnbondingPurse},
+        auction::{Bid, EraId, EraInfo, Error, SlashHandling, UnbondingPurse},
         mint, CallStackElement,
     },
     CLTyped, Key, KeyTag, URef, BLAKE2B_DIGEST_LENGTH, U512,
@@ -26,6 +28,12 @@ pub trait RuntimeProvider {
 
     /// Returns a 32-byte BLAKE2b digest
     fn blake2b<T: AsRef<[u8]>>(&self, data: T) -> [u8; BLAKE2B_DIGEST_LENGTH];
+
+    /// Returns the fraction of a slashed validator's and its delegators' stakes which is slashed.
+    fn slashing_rate(&self) -> Ratio<u64>;
+
+    /// Returns the way in which slashed stake is handled.
+    fn slash_handling(&self) -> SlashHandling;
 }
 
 /// Provides functionality of a contract storage.
@@ -52,6 +60,9 @@ pub trait StorageProvider {
         unbonding_purses: Vec<UnbondingPurse>,
     ) -> Result<(), Error>;
 
+    /// Reads the era info recorded at the given era id.
+    fn read_era_info(&mut self, era_id: EraId) -> Result<Option<EraInfo>, Error>;
+
     /// Records era info at the given era id.
     fn record_era_info(&mut self, era_id: EraId, era_info: EraInfo) -> Result<(), Error>;
 }
@@ -88,6 +99,12 @@ pub trait MintProvider {
     /// Reduce total supply by `amount`. Returns unit on success, otherwise
     /// an error.
     fn reduce_total_supply(&mut self, amount: U512) -> Result<(), Error>;
+
+    /// Burns `amount` from the balance of `purse`, reducing the total supply by the same amount.
+    fn burn(&mut self, purse: URef, amount: U512) -> Result<(), Error>;
+
+    /// Transfers `amount` from `purse` to the accumulation purse of the Handle Payment contract.
+    fn accumulate(&mut self, purse: URef, amount: U512) -> Result<(), Error>;
 }
 
 /// Provider of an account related functionality.
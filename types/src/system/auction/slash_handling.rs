@@ -0,0 +1,67 @@
+//! Contains the [`SlashHandling`] type.
+// Can be removed once https://github.com/rust-lang/rustfmt/issues/3362 is resolved.
+#[rustfmt::skip]
+use alloc::vec;
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+
+use crate::bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH};
+
+const BURN_TAG: u8 = 0;
+const ACCUMULATE_TAG: u8 = 1;
+
+/// Defines what happens to the stake removed from a validator and its delegators when the
+/// validator is slashed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlashHandling {
+    /// Burn the slashed stake, reducing the total supply by the same amount.
+    Burn,
+    /// Accumulate the slashed stake in the Handle Payment contract's accumulation purse.
+    Accumulate,
+}
+
+impl Default for SlashHandling {
+    fn default() -> Self {
+        SlashHandling::Burn
+    }
+}
+
+impl ToBytes for SlashHandling {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let tag = match self {
+            SlashHandling::Burn => BURN_TAG,
+            SlashHandling::Accumulate => ACCUMULATE_TAG,
+        };
+        Ok(vec![tag])
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for SlashHandling {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        let slash_handling = match tag {
+            BURN_TAG => SlashHandling::Burn,
+            ACCUMULATE_TAG => SlashHandling::Accumulate,
+            _ => return Err(bytesrepr::Error::Formatting),
+        };
+        Ok((slash_handling, remainder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        for slash_handling in &[SlashHandling::Burn, SlashHandling::Accumulate] {
+            bytesrepr::test_serialization_roundtrip(slash_handling);
+        }
+    }
+}
@@ -27,16 +27,22 @@ pub struct UnbondingPurse {
     era_of_creation: EraId,
     /// Unbonding Amount.
     amount: U512,
+    /// The validator public key to re-delegate to.
+    new_validator: Option<PublicKey>,
 }
 
 impl UnbondingPurse {
     /// Creates [`UnbondingPurse`] instance for an unbonding request.
+    ///
+    /// If `new_validator` is given, the unbonded amount is delegated to that validator rather than
+    /// paid out to the unbonder once the unbonding delay has elapsed.
     pub const fn new(
         bonding_purse: URef,
         validator_public_key: PublicKey,
         unbonder_public_key: PublicKey,
         era_of_creation: EraId,
         amount: U512,
+        new_validator: Option<PublicKey>,
     ) -> Self {
         Self {
             bonding_purse,
@@ -44,6 +50,7 @@ impl UnbondingPurse {
             unbonder_public_key,
             era_of_creation,
             amount,
+            new_validator,
         }
     }
 
@@ -81,6 +88,13 @@ impl UnbondingPurse {
     pub fn amount(&self) -> &U512 {
         &self.amount
     }
+
+    /// Returns the public key of the validator the unbonded amount will be re-delegated to, if
+    /// this unbonding request was created through
+    /// [`crate::system::auction::Auction::redelegate`].
+    pub fn new_validator(&self) -> &Option<PublicKey> {
+        &self.new_validator
+    }
 }
 
 impl ToBytes for UnbondingPurse {
@@ -91,6 +105,7 @@ impl ToBytes for UnbondingPurse {
         result.extend(&self.unbonder_public_key.to_bytes()?);
         result.extend(&self.era_of_creation.to_bytes()?);
         result.extend(&self.amount.to_bytes()?);
+        result.extend(&self.new_validator.to_bytes()?);
         Ok(result)
     }
     fn serialized_length(&self) -> usize {
@@ -99,6 +114,7 @@ impl ToBytes for UnbondingPurse {
             + self.unbonder_public_key.serialized_length()
             + self.era_of_creation.serialized_length()
             + self.amount.serialized_length()
+            + self.new_validator.serialized_length()
     }
 }
 
@@ -109,6 +125,7 @@ impl FromBytes for UnbondingPurse {
         let (unbonder_public_key, bytes) = FromBytes::from_bytes(bytes)?;
         let (era_of_creation, bytes) = FromBytes::from_bytes(bytes)?;
         let (amount, bytes) = FromBytes::from_bytes(bytes)?;
+        let (new_validator, bytes) = FromBytes::from_bytes(bytes)?;
         Ok((
             UnbondingPurse {
                 bonding_purse,
@@ -116,6 +133,7 @@ impl FromBytes for UnbondingPurse {
                 unbonder_public_key,
                 era_of_creation,
                 amount,
+                new_validator,
             },
             bytes,
         ))
@@ -149,6 +167,10 @@ mod tests {
         let secret_key = SecretKey::ed25519_from_bytes([43; SecretKey::ED25519_LENGTH]).unwrap();
         PublicKey::from(&secret_key)
     });
+    static NEW_VALIDATOR_PUBLIC_KEY: Lazy<PublicKey> = Lazy::new(|| {
+        let secret_key = SecretKey::ed25519_from_bytes([44; SecretKey::ED25519_LENGTH]).unwrap();
+        PublicKey::from(&secret_key)
+    });
     static AMOUNT: Lazy<U512> = Lazy::new(|| U512::max_value() - 1);
 
     #[test]
@@ -159,9 +181,17 @@ mod tests {
             unbonder_public_key: UNBONDER_PUBLIC_KEY.clone(),
             era_of_creation: ERA_OF_WITHDRAWAL,
             amount: *AMOUNT,
+            new_validator: None,
         };
 
         bytesrepr::test_serialization_roundtrip(&unbonding_purse);
+
+        let redelegating_purse = UnbondingPurse {
+            new_validator: Some(NEW_VALIDATOR_PUBLIC_KEY.clone()),
+            ..unbonding_purse
+        };
+
+        bytesrepr::test_serialization_roundtrip(&redelegating_purse);
     }
     #[test]
     fn should_be_validator_condition() {
@@ -171,6 +201,7 @@ mod tests {
             VALIDATOR_PUBLIC_KEY.clone(),
             ERA_OF_WITHDRAWAL,
             *AMOUNT,
+            None,
         );
         assert!(validator_unbonding_purse.is_validator());
     }
@@ -183,6 +214,7 @@ mod tests {
             UNBONDER_PUBLIC_KEY.clone(),
             ERA_OF_WITHDRAWAL,
             *AMOUNT,
+            None,
         );
         assert!(!delegator_unbonding_purse.is_validator());
     }
@@ -28,3 +28,7 @@ pub const PAYMENT_PURSE_KEY: &str = "payment_purse";
 /// The uref name where the Handle Payment will refund unused payment back to the user. The uref
 /// this name corresponds to is set by the user.
 pub const REFUND_PURSE_KEY: &str = "refund_purse";
+
+/// The uref name where the Handle Payment accumulates fees when the chain is configured to
+/// accumulate rather than pay them to the block proposer.
+pub const ACCUMULATION_PURSE_KEY: &str = "accumulation_purse";
@@ -129,6 +129,19 @@ pub enum Error {
     #[doc(hidden)]
     #[cfg_attr(feature = "std", error("GasLimit"))]
     GasLimit,
+    /// The accumulation purse key was not a URef.
+    #[cfg_attr(feature = "std", error("Accumulation purse key unexpected type"))]
+    AccumulationPurseKeyUnexpectedType,
+    /// Internal error: while finalizing payment, failed to accumulate the fees (the transfer from
+    /// the Handle Payment contract's payment purse to accumulation purse failed).
+    #[cfg_attr(feature = "std", error("Transfer to accumulation purse has failed"))]
+    FailedTransferToAccumulationPurse,
+    /// Internal error: while finalizing payment, failed to burn the fees.
+    #[cfg_attr(feature = "std", error("Failed to burn fees"))]
+    FailedToBurnFees,
+    /// Raised when the system is unable to create a purse.
+    #[cfg_attr(feature = "std", error("Unable to create purse"))]
+    CreatePurse,
 }
 
 impl CLTyped for Error {
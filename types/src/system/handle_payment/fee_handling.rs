@@ -0,0 +1,75 @@
+//! Contains the [`FeeHandling`] type.
+// Can be removed once https://github.com/rust-lang/rustfmt/issues/3362 is resolved.
+#[rustfmt::skip]
+use alloc::vec;
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+
+use crate::bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH};
+
+const PAY_TO_PROPOSER_TAG: u8 = 0;
+const BURN_TAG: u8 = 1;
+const ACCUMULATE_TAG: u8 = 2;
+
+/// Defines how the fees paid for executing a deploy are handled once payment is finalized.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeHandling {
+    /// Transfer the fees to the purse of the block proposer.
+    PayToProposer,
+    /// Burn the fees, reducing the total supply by the same amount.
+    Burn,
+    /// Accumulate the fees in the Handle Payment contract's accumulation purse.
+    Accumulate,
+}
+
+impl Default for FeeHandling {
+    fn default() -> Self {
+        FeeHandling::PayToProposer
+    }
+}
+
+impl ToBytes for FeeHandling {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let tag = match self {
+            FeeHandling::PayToProposer => PAY_TO_PROPOSER_TAG,
+            FeeHandling::Burn => BURN_TAG,
+            FeeHandling::Accumulate => ACCUMULATE_TAG,
+        };
+        Ok(vec![tag])
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for FeeHandling {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        let fee_handling = match tag {
+            PAY_TO_PROPOSER_TAG => FeeHandling::PayToProposer,
+            BURN_TAG => FeeHandling::Burn,
+            ACCUMULATE_TAG => FeeHandling::Accumulate,
+            _ => return Err(bytesrepr::Error::Formatting),
+        };
+        Ok((fee_handling, remainder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        for fee_handling in &[
+            FeeHandling::PayToProposer,
+            FeeHandling::Burn,
+            FeeHandling::Accumulate,
+        ] {
+            bytesrepr::test_serialization_roundtrip(fee_handling);
+        }
+    }
+}
@@ -20,4 +20,10 @@ pub trait MintProvider {
 
     /// Checks balance of a `purse`. Returns `None` if given purse does not exist.
     fn balance(&mut self, purse: URef) -> Result<Option<U512>, Error>;
+
+    /// Creates a new, empty purse.
+    fn create_purse(&mut self) -> Result<URef, Error>;
+
+    /// Removes `amount` from the balance of `purse` and reduces the total supply accordingly.
+    fn burn(&mut self, purse: URef, amount: U512) -> Result<(), Error>;
 }
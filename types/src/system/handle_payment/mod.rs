@@ -2,6 +2,7 @@
 mod constants;
 mod entry_points;
 mod error;
+mod fee_handling;
 mod mint_provider;
 mod runtime_provider;
 
@@ -11,12 +12,9 @@ use crate::{account::AccountHash, AccessRights, URef, U512};
 
 pub use crate::system::handle_payment::{
     constants::*, entry_points::handle_payment_entry_points, error::Error,
-    mint_provider::MintProvider, runtime_provider::RuntimeProvider,
+    fee_handling::FeeHandling, mint_provider::MintProvider, runtime_provider::RuntimeProvider,
 };
 
-// A simplified representation of a refund percentage which is currently hardcoded to 0%.
-const REFUND_PERCENTAGE: U512 = U512::zero();
-
 /// Handle payment functionality implementation.
 pub trait HandlePayment: MintProvider + RuntimeProvider + Sized {
     /// Get payment purse.
@@ -54,11 +52,11 @@ pub trait HandlePayment: MintProvider + RuntimeProvider + Sized {
 mod internal {
     use crate::{
         account::AccountHash,
-        system::handle_payment::{Error, MintProvider, RuntimeProvider},
+        system::handle_payment::{Error, FeeHandling, MintProvider, RuntimeProvider},
         Key, Phase, PublicKey, URef, U512,
     };
 
-    use super::{PAYMENT_PURSE_KEY, REFUND_PERCENTAGE, REFUND_PURSE_KEY};
+    use super::{ACCUMULATION_PURSE_KEY, PAYMENT_PURSE_KEY, REFUND_PURSE_KEY};
 
     /// Returns the purse for accepting payment for transactions.
     pub fn get_payment_purse<R: RuntimeProvider>(runtime_provider: &R) -> Result<URef, Error> {
@@ -94,10 +92,26 @@ mod internal {
         }
     }
 
-    /// Transfers funds from the payment purse to the validator rewards purse, as well as to the
-    /// refund purse, depending on how much was spent on the computation. This function maintains
-    /// the invariant that the balance of the payment purse is zero at the beginning and end of each
-    /// deploy and that the refund purse is unset at the beginning and end of each deploy.
+    /// Returns the purse in which fees are accumulated, creating it first if it doesn't exist yet.
+    pub fn get_or_create_accumulation_purse<P: MintProvider + RuntimeProvider>(
+        provider: &mut P,
+    ) -> Result<URef, Error> {
+        match provider.get_key(ACCUMULATION_PURSE_KEY) {
+            Some(Key::URef(uref)) => Ok(uref),
+            Some(_) => Err(Error::AccumulationPurseKeyUnexpectedType),
+            None => {
+                let accumulation_purse = provider.create_purse()?;
+                provider.put_key(ACCUMULATION_PURSE_KEY, Key::URef(accumulation_purse))?;
+                Ok(accumulation_purse)
+            }
+        }
+    }
+
+    /// Transfers funds from the payment purse to the validator rewards purse (or handles them
+    /// otherwise, as per the configured [`FeeHandling`]), as well as to the refund purse,
+    /// depending on how much was spent on the computation. This function maintains the invariant
+    /// that the balance of the payment purse is zero at the beginning and end of each deploy and
+    /// that the refund purse is unset at the beginning and end of each deploy.
     pub fn finalize_payment<P: MintProvider + RuntimeProvider>(
         provider: &mut P,
         amount_spent: U512,
@@ -124,29 +138,44 @@ mod internal {
             let refund_amount_raw = total
                 .checked_sub(amount_spent)
                 .ok_or(Error::ArithmeticOverflow)?;
-            // Currently refund percentage is zero and we expect no overflows.
-            // However, we put this check should the constant change in the future.
+            let refund_ratio = provider.refund_ratio();
             refund_amount_raw
-                .checked_mul(REFUND_PERCENTAGE)
+                .checked_mul(U512::from(*refund_ratio.numer()))
+                .and_then(|amount| amount.checked_div(U512::from(*refund_ratio.denom())))
                 .ok_or(Error::ArithmeticOverflow)?
         };
 
-        // Validator reward
-        let validator_reward = total
+        // Fees
+        let fee = total
             .checked_sub(refund_amount)
             .ok_or(Error::ArithmeticOverflow)?;
 
-        // Makes sure both parts: for user, and for validator sums to the total amount in the
+        // Makes sure both parts: for user, and for fees sums to the total amount in the
         // payment's purse.
-        debug_assert_eq!(validator_reward + refund_amount, total);
+        debug_assert_eq!(fee + refund_amount, total);
 
         let refund_purse = get_refund_purse(provider)?;
         provider.remove_key(REFUND_PURSE_KEY)?; //unset refund purse after reading it
 
-        // pay target validator
-        provider
-            .transfer_purse_to_purse(payment_purse, target, validator_reward)
-            .map_err(|_| Error::FailedTransferToRewardsPurse)?;
+        match provider.fee_handling() {
+            FeeHandling::PayToProposer => {
+                // pay target validator
+                provider
+                    .transfer_purse_to_purse(payment_purse, target, fee)
+                    .map_err(|_| Error::FailedTransferToRewardsPurse)?;
+            }
+            FeeHandling::Burn => {
+                provider
+                    .burn(payment_purse, fee)
+                    .map_err(|_| Error::FailedToBurnFees)?;
+            }
+            FeeHandling::Accumulate => {
+                let accumulation_purse = get_or_create_accumulation_purse(provider)?;
+                provider
+                    .transfer_purse_to_purse(payment_purse, accumulation_purse, fee)
+                    .map_err(|_| Error::FailedTransferToAccumulationPurse)?;
+            }
+        }
 
         if refund_amount.is_zero() {
             return Ok(());
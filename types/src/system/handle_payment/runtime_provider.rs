@@ -1,4 +1,10 @@
-use crate::{account::AccountHash, system::handle_payment::Error, BlockTime, Key, Phase};
+use num_rational::Ratio;
+
+use crate::{
+    account::AccountHash,
+    system::handle_payment::{Error, FeeHandling},
+    BlockTime, Key, Phase,
+};
 
 /// Provider of runtime host functionality.
 pub trait RuntimeProvider {
@@ -19,4 +25,10 @@ pub trait RuntimeProvider {
 
     /// Get caller.
     fn get_caller(&self) -> AccountHash;
+
+    /// Get the ratio of unspent payment which is refunded to the caller.
+    fn refund_ratio(&self) -> Ratio<u64>;
+
+    /// Get the way in which fees are handled.
+    fn fee_handling(&self) -> FeeHandling;
 }
@@ -21,6 +21,11 @@ pub trait StorageProvider {
     /// Read balance.
     fn read_balance(&mut self, uref: URef) -> Result<Option<U512>, Error>;
 
+    /// Checks whether a balance exists, without depending on its amount.
+    fn balance_exists(&mut self, uref: URef) -> Result<bool, Error> {
+        Ok(self.read_balance(uref)?.is_some())
+    }
+
     /// Write balance.
     fn write_balance(&mut self, uref: URef, balance: U512) -> Result<(), Error>;
 
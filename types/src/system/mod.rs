@@ -9,6 +9,9 @@ use alloc::vec::Vec;
 use bytesrepr::U8_SERIALIZED_LENGTH;
 use num_derive::{FromPrimitive, ToPrimitive};
 use num_traits::FromPrimitive;
+#[cfg(feature = "std")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     account::AccountHash,
@@ -220,7 +223,8 @@ pub enum CallStackElementTag {
 }
 
 /// Represents the origin of a sub-call.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "std", derive(JsonSchema))]
 pub enum CallStackElement {
     /// Session
     Session {
@@ -0,0 +1,183 @@
+//! Generation of `ToBytes` and `FromBytes` implementations.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse_quote;
+
+use crate::input::{Body, Container, Encoding, Field, FieldList};
+
+/// Returns an expression serializing `field` and appending it to `result`.
+fn append_field(field: &Field) -> TokenStream {
+    let binding = &field.binding;
+    let bytes = match &field.encoding {
+        Encoding::With(path) => quote!(#path::to_bytes(#binding)?),
+        Encoding::Default | Encoding::Skip => {
+            quote!(::casper_types::bytesrepr::ToBytes::to_bytes(#binding)?)
+        }
+    };
+    quote!(result.append(&mut #bytes);)
+}
+
+/// Returns an expression summing the serialized lengths of the serialized fields.
+fn serialized_length(fields: &FieldList) -> TokenStream {
+    let lengths = fields.serialized().map(|field| {
+        let binding = &field.binding;
+        match &field.encoding {
+            Encoding::With(path) => quote!(#path::serialized_length(#binding)),
+            Encoding::Default | Encoding::Skip => {
+                quote!(::casper_types::bytesrepr::ToBytes::serialized_length(#binding))
+            }
+        }
+    });
+    quote!(0 #(+ #lengths)*)
+}
+
+/// Returns statements deserializing the serialized fields from `remainder` into their bindings.
+fn parse_fields(fields: &FieldList) -> TokenStream {
+    let statements = fields.serialized().map(|field| {
+        let binding = &field.binding;
+        let ty = &field.ty;
+        let parsed = match &field.encoding {
+            Encoding::With(path) => quote!(#path::from_bytes(remainder)?),
+            Encoding::Default | Encoding::Skip => {
+                quote!(<#ty as ::casper_types::bytesrepr::FromBytes>::from_bytes(remainder)?)
+            }
+        };
+        quote!(let (#binding, remainder): (#ty, &[u8]) = #parsed;)
+    });
+    quote!(#(#statements)*)
+}
+
+pub(crate) fn generate_to_bytes(container: &Container) -> TokenStream {
+    let ident = &container.ident;
+    let generics = container.bounded_generics(parse_quote!(::casper_types::bytesrepr::ToBytes));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let (append, length) = match &container.body {
+        Body::Struct(fields) => {
+            let pattern = fields.pattern();
+            let appends = fields.serialized().map(append_field);
+            let length = serialized_length(fields);
+            (
+                quote! {
+                    let Self #pattern = self;
+                    #(#appends)*
+                },
+                quote! {
+                    let Self #pattern = self;
+                    #length
+                },
+            )
+        }
+        Body::Enum(variants) => {
+            let append_arms = variants.iter().map(|variant| {
+                let variant_ident = &variant.ident;
+                let index = variant.index;
+                let pattern = variant.fields.pattern();
+                let appends = variant.fields.serialized().map(append_field);
+                quote! {
+                    Self::#variant_ident #pattern => {
+                        result.push(#index);
+                        #(#appends)*
+                    }
+                }
+            });
+            let length_arms = variants.iter().map(|variant| {
+                let variant_ident = &variant.ident;
+                let pattern = variant.fields.pattern();
+                let length = serialized_length(&variant.fields);
+                quote!(Self::#variant_ident #pattern => #length,)
+            });
+            (
+                quote! {
+                    match self {
+                        #(#append_arms)*
+                    }
+                },
+                quote! {
+                    ::casper_types::bytesrepr::U8_SERIALIZED_LENGTH
+                        + match self {
+                            #(#length_arms)*
+                        }
+                },
+            )
+        }
+    };
+
+    quote! {
+        const _: () = {
+            extern crate alloc;
+
+            impl #impl_generics ::casper_types::bytesrepr::ToBytes for #ident #ty_generics
+                #where_clause
+            {
+                fn to_bytes(
+                    &self,
+                ) -> ::core::result::Result<
+                    alloc::vec::Vec<u8>,
+                    ::casper_types::bytesrepr::Error,
+                > {
+                    #[allow(unused_mut)]
+                    let mut result = ::casper_types::bytesrepr::allocate_buffer(self)?;
+                    #append
+                    ::core::result::Result::Ok(result)
+                }
+
+                fn serialized_length(&self) -> usize {
+                    #length
+                }
+            }
+        };
+    }
+}
+
+pub(crate) fn generate_from_bytes(container: &Container) -> TokenStream {
+    let ident = &container.ident;
+    let generics = container.bounded_generics(parse_quote!(::casper_types::bytesrepr::FromBytes));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match &container.body {
+        Body::Struct(fields) => {
+            let parse = parse_fields(fields);
+            let construction = fields.construction();
+            quote! {
+                let remainder = bytes;
+                #parse
+                ::core::result::Result::Ok((Self #construction, remainder))
+            }
+        }
+        Body::Enum(variants) => {
+            let arms = variants.iter().map(|variant| {
+                let variant_ident = &variant.ident;
+                let index = variant.index;
+                let parse = parse_fields(&variant.fields);
+                let construction = variant.fields.construction();
+                quote! {
+                    #index => {
+                        #parse
+                        ::core::result::Result::Ok((Self::#variant_ident #construction, remainder))
+                    }
+                }
+            });
+            quote! {
+                let (tag, remainder) = <u8 as ::casper_types::bytesrepr::FromBytes>::from_bytes(bytes)?;
+                match tag {
+                    #(#arms)*
+                    _ => ::core::result::Result::Err(::casper_types::bytesrepr::Error::Formatting),
+                }
+            }
+        }
+    };
+
+    quote! {
+        impl #impl_generics ::casper_types::bytesrepr::FromBytes for #ident #ty_generics
+            #where_clause
+        {
+            fn from_bytes(
+                bytes: &[u8],
+            ) -> ::core::result::Result<(Self, &[u8]), ::casper_types::bytesrepr::Error> {
+                #body
+            }
+        }
+    }
+}
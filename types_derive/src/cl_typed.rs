@@ -0,0 +1,93 @@
+//! Generation of `CLTyped` implementations.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse_quote;
+
+use crate::input::{Body, Container, Encoding, Field, FieldList};
+
+/// Returns an expression evaluating to the `CLType` of `field`.
+fn field_cl_type(field: &Field) -> TokenStream {
+    let ty = &field.ty;
+    match &field.encoding {
+        Encoding::With(path) => quote!(#path::cl_type()),
+        Encoding::Default | Encoding::Skip => {
+            quote!(<#ty as ::casper_types::CLTyped>::cl_type())
+        }
+    }
+}
+
+/// Returns an expression evaluating to a `CLType::Struct` named `name` with the serialized
+/// `fields`.
+fn struct_cl_type(name: &str, fields: &FieldList) -> TokenStream {
+    let fields = fields.serialized().map(|field| {
+        let field_name = field.name();
+        let cl_type = field_cl_type(field);
+        quote!((alloc::string::String::from(#field_name), #cl_type))
+    });
+    quote! {
+        ::casper_types::CLType::Struct {
+            name: alloc::string::String::from(#name),
+            fields: alloc::vec![#(#fields),*],
+        }
+    }
+}
+
+/// Returns an expression evaluating to the `CLType` of the value held by an enum variant.
+///
+/// This matches the serialized form of the variant's fields: no fields is `CLType::Unit`, a single
+/// unnamed field is that field's type, two or three unnamed fields form a tuple, and anything else
+/// is a `CLType::Struct` named after the variant.
+fn variant_cl_type(name: &str, fields: &FieldList) -> TokenStream {
+    let cl_types = fields.serialized().map(field_cl_type).collect::<Vec<_>>();
+    if fields.named && !cl_types.is_empty() {
+        return struct_cl_type(name, fields);
+    }
+    match cl_types.as_slice() {
+        [] => quote!(::casper_types::CLType::Unit),
+        [cl_type] => cl_type.clone(),
+        [_, _] => quote! {
+            ::casper_types::CLType::Tuple2([#(alloc::boxed::Box::new(#cl_types)),*])
+        },
+        [_, _, _] => quote! {
+            ::casper_types::CLType::Tuple3([#(alloc::boxed::Box::new(#cl_types)),*])
+        },
+        _ => struct_cl_type(name, fields),
+    }
+}
+
+pub(crate) fn generate_cl_typed(container: &Container) -> TokenStream {
+    let ident = &container.ident;
+    let name = ident.to_string();
+    let generics = container.bounded_generics(parse_quote!(::casper_types::CLTyped));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let cl_type = match &container.body {
+        Body::Struct(fields) => struct_cl_type(&name, fields),
+        Body::Enum(variants) => {
+            let variants = variants.iter().map(|variant| {
+                let variant_name = variant.ident.to_string();
+                let cl_type = variant_cl_type(&variant_name, &variant.fields);
+                quote!((alloc::string::String::from(#variant_name), #cl_type))
+            });
+            quote! {
+                ::casper_types::CLType::Enum {
+                    name: alloc::string::String::from(#name),
+                    variants: alloc::vec![#(#variants),*],
+                }
+            }
+        }
+    };
+
+    quote! {
+        const _: () = {
+            extern crate alloc;
+
+            impl #impl_generics ::casper_types::CLTyped for #ident #ty_generics #where_clause {
+                fn cl_type() -> ::casper_types::CLType {
+                    #cl_type
+                }
+            }
+        };
+    }
+}
@@ -0,0 +1,247 @@
+//! The parsed input of the derive macros.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse_quote, Attribute, Data, DeriveInput, Error, Fields, Generics, Ident, Lit, Member, Meta,
+    NestedMeta, Path, Result, Type,
+};
+
+/// The name of the helper attribute, as in `#[casper(skip)]`.
+const ATTRIBUTE_NAME: &str = "casper";
+
+/// The maximum number of variants of an enum, as the variant index is serialized as a `u8`.
+const MAX_VARIANTS: usize = u8::MAX as usize + 1;
+
+/// How a field is serialized.
+pub(crate) enum Encoding {
+    /// Via the field type's own trait implementations.
+    Default,
+    /// Not at all; the field is set to its `Default` value on deserialization.
+    Skip,
+    /// Via the free functions of the given module.
+    With(Path),
+}
+
+/// A field of a struct or an enum variant.
+pub(crate) struct Field {
+    /// The name or index of the field.
+    pub member: Member,
+    /// The field's type.
+    pub ty: Type,
+    /// The name of the local variable the field is bound to in generated code.
+    pub binding: Ident,
+    pub encoding: Encoding,
+}
+
+impl Field {
+    /// Returns the name of the field, or its index for fields of tuple structs and variants.
+    pub fn name(&self) -> String {
+        match &self.member {
+            Member::Named(ident) => ident.to_string(),
+            Member::Unnamed(index) => index.index.to_string(),
+        }
+    }
+
+    /// Returns `true` if the field is serialized.
+    pub fn is_serialized(&self) -> bool {
+        !matches!(self.encoding, Encoding::Skip)
+    }
+}
+
+/// The fields of a struct or an enum variant.
+pub(crate) struct FieldList {
+    /// `true` if the fields are named, i.e. declared in braces.
+    pub named: bool,
+    pub fields: Vec<Field>,
+}
+
+impl FieldList {
+    fn new(fields: &Fields) -> Result<Self> {
+        let named = matches!(fields, Fields::Named(_));
+        let fields = fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let member = match &field.ident {
+                    Some(ident) => Member::Named(ident.clone()),
+                    None => Member::Unnamed(index.into()),
+                };
+                Ok(Field {
+                    member,
+                    ty: field.ty.clone(),
+                    binding: format_ident!("__field{}", index),
+                    encoding: parse_encoding(&field.attrs)?,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(FieldList { named, fields })
+    }
+
+    /// Returns the fields which are serialized.
+    pub fn serialized(&self) -> impl Iterator<Item = &Field> {
+        self.fields.iter().filter(|field| field.is_serialized())
+    }
+
+    /// Returns a pattern binding the serialized fields to their `binding`s, e.g.
+    /// `{ x: __field0, 1: __field1, .. }`.
+    pub fn pattern(&self) -> TokenStream {
+        let bindings = self.serialized().map(|field| {
+            let member = &field.member;
+            let binding = &field.binding;
+            quote!(#member: #binding)
+        });
+        quote!({ #(#bindings,)* .. })
+    }
+
+    /// Returns an expression constructing the fields from their `binding`s, with skipped fields
+    /// set to their `Default` value.
+    pub fn construction(&self) -> TokenStream {
+        let values = self.fields.iter().map(|field| {
+            let member = &field.member;
+            let binding = &field.binding;
+            if field.is_serialized() {
+                quote!(#member: #binding)
+            } else {
+                quote!(#member: ::core::default::Default::default())
+            }
+        });
+        quote!({ #(#values,)* })
+    }
+}
+
+/// A variant of an enum.
+pub(crate) struct Variant {
+    pub ident: Ident,
+    /// The index of the variant, serialized as its tag.
+    pub index: u8,
+    pub fields: FieldList,
+}
+
+/// The body of the type deriving a trait.
+pub(crate) enum Body {
+    Struct(FieldList),
+    Enum(Vec<Variant>),
+}
+
+/// A struct or enum deriving a trait.
+pub(crate) struct Container {
+    pub ident: Ident,
+    pub generics: Generics,
+    pub body: Body,
+}
+
+impl Container {
+    pub fn new(input: &DeriveInput) -> Result<Self> {
+        if let Some(attr) = input.attrs.iter().find(|attr| is_casper_attribute(attr)) {
+            return Err(Error::new_spanned(
+                attr,
+                "`casper` attributes are only supported on fields",
+            ));
+        }
+
+        let body = match &input.data {
+            Data::Struct(data) => Body::Struct(FieldList::new(&data.fields)?),
+            Data::Enum(data) => {
+                if data.variants.is_empty() {
+                    return Err(Error::new_spanned(
+                        &input.ident,
+                        "cannot derive for an enum with no variants",
+                    ));
+                }
+                if data.variants.len() > MAX_VARIANTS {
+                    return Err(Error::new_spanned(
+                        &input.ident,
+                        format!(
+                            "cannot derive for an enum with more than {} variants",
+                            MAX_VARIANTS
+                        ),
+                    ));
+                }
+                let variants = data
+                    .variants
+                    .iter()
+                    .enumerate()
+                    .map(|(index, variant)| {
+                        if let Some(attr) =
+                            variant.attrs.iter().find(|attr| is_casper_attribute(attr))
+                        {
+                            return Err(Error::new_spanned(
+                                attr,
+                                "`casper` attributes are only supported on fields",
+                            ));
+                        }
+                        Ok(Variant {
+                            ident: variant.ident.clone(),
+                            index: index as u8,
+                            fields: FieldList::new(&variant.fields)?,
+                        })
+                    })
+                    .collect::<Result<_>>()?;
+                Body::Enum(variants)
+            }
+            Data::Union(data) => {
+                return Err(Error::new_spanned(
+                    data.union_token,
+                    "cannot derive for a union",
+                ))
+            }
+        };
+
+        Ok(Container {
+            ident: input.ident.clone(),
+            generics: input.generics.clone(),
+            body,
+        })
+    }
+
+    /// Returns the generics of the container with `bound` added to each type parameter.
+    pub fn bounded_generics(&self, bound: Path) -> Generics {
+        let mut generics = self.generics.clone();
+        for type_param in generics.type_params_mut() {
+            type_param.bounds.push(parse_quote!(#bound));
+        }
+        generics
+    }
+}
+
+fn is_casper_attribute(attr: &Attribute) -> bool {
+    attr.path.is_ident(ATTRIBUTE_NAME)
+}
+
+/// Parses the `casper` attributes of a field.
+fn parse_encoding(attrs: &[Attribute]) -> Result<Encoding> {
+    let mut encoding = Encoding::Default;
+    for attr in attrs.iter().filter(|attr| is_casper_attribute(attr)) {
+        let nested = match attr.parse_meta()? {
+            Meta::List(list) => list.nested,
+            meta => return Err(Error::new_spanned(meta, "expected `casper(...)`")),
+        };
+        for meta in nested {
+            if !matches!(encoding, Encoding::Default) {
+                return Err(Error::new_spanned(
+                    meta,
+                    "only one of `skip` and `with` may be given",
+                ));
+            }
+            encoding = match meta {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => Encoding::Skip,
+                NestedMeta::Meta(Meta::NameValue(name_value))
+                    if name_value.path.is_ident("with") =>
+                {
+                    match &name_value.lit {
+                        Lit::Str(lit_str) => Encoding::With(lit_str.parse()?),
+                        lit => return Err(Error::new_spanned(lit, "expected a string literal")),
+                    }
+                }
+                meta => {
+                    return Err(Error::new_spanned(
+                        meta,
+                        "unknown `casper` attribute, expected `skip` or `with = \"...\"`",
+                    ))
+                }
+            };
+        }
+    }
+    Ok(encoding)
+}
@@ -0,0 +1,61 @@
+//! Derive macros for the [`ToBytes`], [`FromBytes`] and [`CLTyped`] traits of `casper-types`. See
+//! `README.md` for details.
+//!
+//! [`ToBytes`]: https://docs.rs/casper-types/latest/casper_types/bytesrepr/trait.ToBytes.html
+//! [`FromBytes`]: https://docs.rs/casper-types/latest/casper_types/bytesrepr/trait.FromBytes.html
+//! [`CLTyped`]: https://docs.rs/casper-types/latest/casper_types/trait.CLTyped.html
+
+#![doc(html_root_url = "https://docs.rs/casper-types-derive/1.0.0")]
+#![doc(
+    html_favicon_url = "https://raw.githubusercontent.com/casper-network/casper-node/master/images/CasperLabs_Logo_Favicon_RGB_50px.png",
+    html_logo_url = "https://raw.githubusercontent.com/casper-network/casper-node/master/images/CasperLabs_Logo_Symbol_RGB.png",
+    test(attr(forbid(warnings)))
+)]
+#![warn(missing_docs, trivial_casts, trivial_numeric_casts)]
+
+mod bytesrepr;
+mod cl_typed;
+mod input;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+use input::Container;
+
+/// Derives `casper_types::bytesrepr::ToBytes`.
+///
+/// Struct fields are serialized in declaration order.  Enums are serialized as the `u8` index of
+/// the variant followed by its fields in declaration order.
+#[proc_macro_derive(ToBytes, attributes(casper))]
+pub fn derive_to_bytes(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    Container::new(&input)
+        .map(|container| bytesrepr::generate_to_bytes(&container))
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+/// Derives `casper_types::bytesrepr::FromBytes`, the inverse of the `ToBytes` derive.
+///
+/// Fields marked `#[casper(skip)]` are set to their `Default` value.
+#[proc_macro_derive(FromBytes, attributes(casper))]
+pub fn derive_from_bytes(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    Container::new(&input)
+        .map(|container| bytesrepr::generate_from_bytes(&container))
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+/// Derives `casper_types::CLTyped`.
+///
+/// Structs map to `CLType::Struct` and enums to `CLType::Enum`, matching the encoding of the
+/// `ToBytes` derive.
+#[proc_macro_derive(CLTyped, attributes(casper))]
+pub fn derive_cl_typed(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    Container::new(&input)
+        .map(|container| cl_typed::generate_cl_typed(&container))
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
@@ -0,0 +1,219 @@
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped, CLValue, U512,
+};
+use casper_types_derive::{CLTyped, FromBytes, ToBytes};
+
+#[derive(PartialEq, Eq, Debug, ToBytes, FromBytes, CLTyped)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(PartialEq, Eq, Debug, ToBytes, FromBytes, CLTyped)]
+struct Pair(u8, String);
+
+#[derive(PartialEq, Eq, Debug, ToBytes, FromBytes, CLTyped)]
+struct Marker;
+
+#[derive(PartialEq, Eq, Debug, ToBytes, FromBytes, CLTyped)]
+struct Wrapper<T> {
+    inner: Vec<T>,
+}
+
+#[derive(PartialEq, Eq, Debug, ToBytes, FromBytes, CLTyped)]
+enum Shape {
+    Empty,
+    Circle(u64),
+    Segment(Point, Point),
+    Rectangle { width: u64, height: u64 },
+}
+
+/// Serializes a `U512` as a `u64`, failing if it doesn't fit.
+mod u512_as_u64 {
+    use casper_types::{bytesrepr, CLType, CLTyped, U512};
+
+    pub fn to_bytes(value: &U512) -> Result<Vec<u8>, bytesrepr::Error> {
+        if *value > U512::from(u64::MAX) {
+            return Err(bytesrepr::Error::Formatting);
+        }
+        bytesrepr::ToBytes::to_bytes(&value.as_u64())
+    }
+
+    pub fn serialized_length(_value: &U512) -> usize {
+        bytesrepr::U64_SERIALIZED_LENGTH
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<(U512, &[u8]), bytesrepr::Error> {
+        let (value, remainder) = <u64 as bytesrepr::FromBytes>::from_bytes(bytes)?;
+        Ok((U512::from(value), remainder))
+    }
+
+    pub fn cl_type() -> CLType {
+        u64::cl_type()
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, ToBytes, FromBytes, CLTyped)]
+struct Account {
+    name: String,
+    #[casper(with = "u512_as_u64")]
+    balance: U512,
+    #[casper(skip)]
+    cached_display_name: Option<String>,
+}
+
+#[test]
+fn struct_should_roundtrip() {
+    bytesrepr::test_serialization_roundtrip(&Point { x: -1, y: 2 });
+    bytesrepr::test_serialization_roundtrip(&Pair(1, "pair".to_string()));
+    bytesrepr::test_serialization_roundtrip(&Marker);
+    bytesrepr::test_serialization_roundtrip(&Wrapper {
+        inner: vec![Marker, Marker],
+    });
+}
+
+#[test]
+fn struct_should_serialize_fields_in_order() {
+    let point = Point { x: -1, y: 2 };
+    assert_eq!(point.to_bytes().unwrap(), (-1i32, 2i32).to_bytes().unwrap());
+    assert!(Marker.to_bytes().unwrap().is_empty());
+}
+
+#[test]
+fn enum_should_roundtrip() {
+    bytesrepr::test_serialization_roundtrip(&Shape::Empty);
+    bytesrepr::test_serialization_roundtrip(&Shape::Circle(3));
+    bytesrepr::test_serialization_roundtrip(&Shape::Segment(
+        Point { x: 0, y: 0 },
+        Point { x: 1, y: 1 },
+    ));
+    bytesrepr::test_serialization_roundtrip(&Shape::Rectangle {
+        width: 4,
+        height: 5,
+    });
+}
+
+#[test]
+fn enum_should_serialize_variant_index_then_fields() {
+    assert_eq!(Shape::Empty.to_bytes().unwrap(), vec![0]);
+
+    let mut expected = vec![3];
+    expected.append(&mut (4u64, 5u64).to_bytes().unwrap());
+    let rectangle = Shape::Rectangle {
+        width: 4,
+        height: 5,
+    };
+    assert_eq!(rectangle.to_bytes().unwrap(), expected);
+}
+
+#[test]
+fn enum_should_fail_to_deserialize_unknown_variant() {
+    assert_eq!(
+        Shape::from_bytes(&[4]).unwrap_err(),
+        bytesrepr::Error::Formatting
+    );
+}
+
+#[test]
+fn field_attributes_should_be_applied() {
+    let account = Account {
+        name: "alice".to_string(),
+        balance: U512::from(10),
+        cached_display_name: Some("Alice".to_string()),
+    };
+
+    let bytes = account.to_bytes().unwrap();
+    assert_eq!(bytes, ("alice".to_string(), 10u64).to_bytes().unwrap());
+    assert_eq!(bytes.len(), account.serialized_length());
+
+    let (deserialized, remainder) = Account::from_bytes(&bytes).unwrap();
+    assert!(remainder.is_empty());
+    assert_eq!(
+        deserialized,
+        Account {
+            cached_display_name: None,
+            ..account
+        }
+    );
+
+    let too_large = Account {
+        name: "bob".to_string(),
+        balance: U512::from(u64::MAX) + 1,
+        cached_display_name: None,
+    };
+    assert_eq!(
+        too_large.to_bytes().unwrap_err(),
+        bytesrepr::Error::Formatting
+    );
+}
+
+#[test]
+fn cl_type_should_describe_struct() {
+    assert_eq!(
+        Point::cl_type(),
+        CLType::Struct {
+            name: "Point".to_string(),
+            fields: vec![
+                ("x".to_string(), CLType::I32),
+                ("y".to_string(), CLType::I32)
+            ],
+        }
+    );
+    assert_eq!(
+        Pair::cl_type(),
+        CLType::Struct {
+            name: "Pair".to_string(),
+            fields: vec![
+                ("0".to_string(), CLType::U8),
+                ("1".to_string(), CLType::String)
+            ],
+        }
+    );
+    assert_eq!(
+        Account::cl_type(),
+        CLType::Struct {
+            name: "Account".to_string(),
+            fields: vec![
+                ("name".to_string(), CLType::String),
+                ("balance".to_string(), CLType::U64)
+            ],
+        }
+    );
+}
+
+#[test]
+fn cl_type_should_describe_enum() {
+    assert_eq!(
+        Shape::cl_type(),
+        CLType::Enum {
+            name: "Shape".to_string(),
+            variants: vec![
+                ("Empty".to_string(), CLType::Unit),
+                ("Circle".to_string(), CLType::U64),
+                (
+                    "Segment".to_string(),
+                    CLType::Tuple2([Box::new(Point::cl_type()), Box::new(Point::cl_type())])
+                ),
+                (
+                    "Rectangle".to_string(),
+                    CLType::Struct {
+                        name: "Rectangle".to_string(),
+                        fields: vec![
+                            ("width".to_string(), CLType::U64),
+                            ("height".to_string(), CLType::U64)
+                        ],
+                    }
+                ),
+            ],
+        }
+    );
+}
+
+#[test]
+fn should_convert_to_and_from_cl_value() {
+    let shape = || Shape::Segment(Point { x: 0, y: 1 }, Point { x: 2, y: 3 });
+    let cl_value = CLValue::from_t(shape()).unwrap();
+    assert_eq!(*cl_value.cl_type(), Shape::cl_type());
+    assert_eq!(cl_value.into_t::<Shape>().unwrap(), shape());
+}
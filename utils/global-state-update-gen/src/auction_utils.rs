@@ -3,17 +3,32 @@ use std::collections::{BTreeMap, BTreeSet};
 use casper_engine_test_support::internal::LmdbWasmTestBuilder;
 use casper_execution_engine::shared::stored_value::StoredValue;
 use casper_types::{
+    bytesrepr::FromBytes,
     system::auction::{
-        Bid, SeigniorageRecipient, SeigniorageRecipientsSnapshot,
+        Bid, SeigniorageRecipient, SeigniorageRecipientsSnapshot, UnbondingPurse, ERA_ID_KEY,
         SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY,
     },
-    AsymmetricType, EraId, Key, ProtocolVersion, PublicKey, U512,
+    AsymmetricType, CLTyped, EraId, Key, ProtocolVersion, PublicKey, U512,
 };
 
 use crate::utils::ValidatorsDiff;
 
 /// Reads the `SeigniorageRecipientsSnapshot` stored in the global state.
 pub fn read_snapshot(builder: &LmdbWasmTestBuilder) -> (Key, SeigniorageRecipientsSnapshot) {
+    read_auction_value(builder, SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY)
+}
+
+/// Reads the current `EraId` stored in the global state.
+pub fn read_era_id(builder: &LmdbWasmTestBuilder) -> EraId {
+    read_auction_value(builder, ERA_ID_KEY).1
+}
+
+/// Reads the value stored under the given named key of the auction contract, along with the key
+/// under which it is stored.
+fn read_auction_value<T: FromBytes + CLTyped>(
+    builder: &LmdbWasmTestBuilder,
+    name: &str,
+) -> (Key, T) {
     let protocol_data = builder
         .get_engine_state()
         .get_protocol_data(ProtocolVersion::from_parts(1, 0, 0)) // TODO: make it a parameter?
@@ -23,21 +38,19 @@ pub fn read_snapshot(builder: &LmdbWasmTestBuilder) -> (Key, SeigniorageRecipien
     // Find the hash of the auction contract.
     let auction_contract_hash = protocol_data.auction();
 
-    // Read the key under which the snapshot is stored.
-    let validators_key = builder
+    // Read the key under which the value is stored.
+    let key = builder
         .get_contract(auction_contract_hash)
         .expect("auction should exist")
-        .named_keys()[SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY];
+        .named_keys()[name];
 
-    // Decode the old snapshot.
-    let stored_value = builder
-        .query(None, validators_key, &[])
-        .expect("should query");
+    // Decode the value.
+    let stored_value = builder.query(None, key, &[]).expect("should query");
     let cl_value = stored_value
         .as_cl_value()
         .cloned()
         .expect("should be cl value");
-    (validators_key, cl_value.into_t().expect("should convert"))
+    (key, cl_value.into_t().expect("should convert"))
 }
 
 /// Generates a new `SeigniorageRecipientsSnapshot` based on:
@@ -151,3 +164,77 @@ pub fn generate_entries_removing_withdraws(
         .map(|acc| (Key::Withdraw(acc), StoredValue::Withdraw(vec![])))
         .collect()
 }
+
+/// Generates the writes necessary to bring all the bids within the delegation limits, ie.:
+/// - remove the delegations smaller than `minimum_delegation_amount`,
+/// - remove the smallest delegations of the validators with more than
+/// `max_delegators_per_validator` delegators.
+///
+/// Every removed delegation is turned into a withdraw created in `era_id`, so that it's paid out to
+/// the delegator once the unbonding delay has passed.
+pub fn generate_entries_removing_delegators(
+    builder: &mut LmdbWasmTestBuilder,
+    era_id: EraId,
+    minimum_delegation_amount: U512,
+    max_delegators_per_validator: Option<usize>,
+) -> BTreeMap<Key, StoredValue> {
+    let mut withdraws = builder.get_withdraws();
+    let mut changed_withdraws = BTreeSet::new();
+    let mut entries = BTreeMap::new();
+
+    for (validator_public_key, mut bid) in builder.get_bids() {
+        // Sort the delegations from the largest, so that the largest ones are kept.
+        let mut delegations: Vec<_> = bid
+            .delegators()
+            .iter()
+            .map(|(delegator_public_key, delegator)| {
+                (delegator_public_key.clone(), *delegator.staked_amount())
+            })
+            .collect();
+        delegations.sort_by(|(_, amount_1), (_, amount_2)| amount_2.cmp(amount_1));
+
+        let to_remove: Vec<_> = delegations
+            .into_iter()
+            .enumerate()
+            .filter(|(index, (_, amount))| {
+                *amount < minimum_delegation_amount
+                    || max_delegators_per_validator.map_or(false, |max| *index >= max)
+            })
+            .map(|(_, (delegator_public_key, _))| delegator_public_key)
+            .collect();
+        if to_remove.is_empty() {
+            continue;
+        }
+
+        for delegator_public_key in to_remove {
+            let delegator = bid
+                .delegators_mut()
+                .remove(&delegator_public_key)
+                .expect("should have delegator");
+            let account_hash = delegator_public_key.to_account_hash();
+            withdraws
+                .entry(account_hash)
+                .or_default()
+                .push(UnbondingPurse::new(
+                    *delegator.bonding_purse(),
+                    validator_public_key.clone(),
+                    delegator_public_key,
+                    era_id,
+                    *delegator.staked_amount(),
+                    None,
+                ));
+            changed_withdraws.insert(account_hash);
+        }
+
+        entries.insert(Key::Bid(validator_public_key.to_account_hash()), bid.into());
+    }
+
+    for account_hash in changed_withdraws {
+        entries.insert(
+            Key::Withdraw(account_hash),
+            StoredValue::Withdraw(withdraws.remove(&account_hash).unwrap_or_default()),
+        );
+    }
+
+    entries
+}
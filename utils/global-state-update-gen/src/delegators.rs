@@ -0,0 +1,38 @@
+use clap::ArgMatches;
+
+use casper_engine_test_support::internal::LmdbWasmTestBuilder;
+use casper_types::U512;
+
+use crate::{
+    auction_utils::{generate_entries_removing_delegators, read_era_id},
+    utils::{hash_from_str, print_entry},
+};
+
+pub(crate) fn generate_delegators_update(matches: &ArgMatches<'_>) {
+    let data_dir = matches.value_of("data_dir").unwrap_or(".");
+    let state_hash = matches.value_of("hash").unwrap();
+    let minimum_delegation_amount =
+        U512::from_dec_str(matches.value_of("minimum_delegation_amount").unwrap()).unwrap();
+    let max_delegators_per_validator: usize = matches
+        .value_of("max_delegators_per_validator")
+        .unwrap()
+        .parse()
+        .unwrap();
+
+    // Open the global state that should be in the supplied directory.
+    let mut test_builder =
+        LmdbWasmTestBuilder::open_raw(data_dir, Default::default(), hash_from_str(state_hash));
+
+    // The removed delegations are unbonded as of the current era.
+    let era_id = read_era_id(&test_builder);
+
+    // Print the writes fixing the bids and the withdraws of the removed delegators.
+    for (key, value) in generate_entries_removing_delegators(
+        &mut test_builder,
+        era_id,
+        minimum_delegation_amount,
+        Some(max_delegators_per_validator).filter(|max| *max != 0),
+    ) {
+        print_entry(&key, &value);
+    }
+}
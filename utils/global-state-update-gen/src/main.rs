@@ -1,11 +1,15 @@
 mod auction_utils;
 mod balances;
+mod delegators;
 mod utils;
 mod validators;
 
 use clap::{crate_version, App, Arg, SubCommand};
 
-use crate::{balances::generate_balances_update, validators::generate_validators_update};
+use crate::{
+    balances::generate_balances_update, delegators::generate_delegators_update,
+    validators::generate_validators_update,
+};
 
 fn main() {
     let matches = App::new("Global State Update Generator")
@@ -102,11 +106,55 @@ fn main() {
                         .required(true),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("delegators")
+                .about("Generates an update removing delegations exceeding the delegation limits")
+                .arg(
+                    Arg::with_name("data_dir")
+                        .short("d")
+                        .long("data-dir")
+                        .value_name("PATH")
+                        .help("Data storage directory containing the global state database file")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("hash")
+                        .short("s")
+                        .long("state-hash")
+                        .value_name("HEX_STRING")
+                        .help("The global state hash to be used as the base")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("minimum_delegation_amount")
+                        .short("m")
+                        .long("minimum-delegation-amount")
+                        .value_name("MOTES")
+                        .help("Delegations smaller than this amount are removed")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("max_delegators_per_validator")
+                        .short("x")
+                        .long("max-delegators-per-validator")
+                        .value_name("NUMBER")
+                        .help(
+                            "Only this many of the largest delegations to each validator are kept; \
+                            0 means there is no limit",
+                        )
+                        .takes_value(true)
+                        .required(true),
+                ),
+        )
         .get_matches();
 
     match matches.subcommand() {
         ("validators", Some(sub_matches)) => generate_validators_update(sub_matches),
         ("balances", Some(sub_matches)) => generate_balances_update(sub_matches),
+        ("delegators", Some(sub_matches)) => generate_delegators_update(sub_matches),
         _ => {
             println!("Unknown subcommand.");
         }
@@ -23,6 +23,7 @@ _REMOTE_FILES=(
     "chainspec.toml.in"
     "config.toml"
     "delegate.wasm"
+    "redelegate.wasm"
     "transfer_to_account_u512.wasm"
     "undelegate.wasm"
     "withdraw_bid.wasm"
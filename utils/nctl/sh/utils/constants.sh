@@ -26,6 +26,7 @@ export NCTL_CONTRACTS_CLIENT_AUCTION=(
     "activate_bid.wasm"
     "add_bid.wasm"
     "delegate.wasm"
+    "redelegate.wasm"
     "undelegate.wasm"
     "withdraw_bid.wasm"
 )